@@ -14,6 +14,7 @@ use mago_span::HasSpan;
 use crate::context::scope::case_scope::CaseScope;
 use crate::context::scope::loop_scope::LoopScope;
 use crate::readonly::PendingReadonlyPropertyWrite;
+use crate::taint::TaintState;
 
 /// Represents scope information extracted from a `Closure::bind()` or `Closure::bindTo()` call.
 /// This is used to pass the bound class scope to closure/arrow function analysis.
@@ -54,6 +55,7 @@ pub struct AnalysisArtifacts {
     pub closure_bind_scope: Option<ClosureBindScope>,
     pub resolved_method_calls: Vec<ResolvedMethodCall>,
     pub(crate) pending_readonly_property_writes: Vec<PendingReadonlyPropertyWrite>,
+    pub(crate) taint: TaintState,
 }
 
 impl Default for AnalysisArtifacts {
@@ -85,6 +87,7 @@ impl AnalysisArtifacts {
             closure_bind_scope: None,
             resolved_method_calls: Vec::new(),
             pending_readonly_property_writes: Vec::new(),
+            taint: TaintState::default(),
         }
     }
 
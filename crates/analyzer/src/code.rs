@@ -32,6 +32,7 @@ pub enum IssueCode {
     ConditionIsTooComplex,
     ConflictingReferenceConstraint,
     ConflictingTemplateEqualityBounds,
+    DeadCode,
     DeprecatedClass,
     DeprecatedClosure,
     DeprecatedConstant,
@@ -39,7 +40,9 @@ pub enum IssueCode {
     DeprecatedFunction,
     DeprecatedMethod,
     DeprecatedTrait,
+    DevDependencyInProduction,
     DirectTraitConstantAccess,
+    DivisionByZero,
     DocblockParameterNarrowing,
     DocblockTypeMismatch,
     DuplicateArrayKey,
@@ -76,6 +79,11 @@ pub enum IssueCode {
     ImpossibleTypeComparison,
     ImpreciseType,
     ImpureConstruct,
+    ImpureFunctionCall,
+    ImpureGlobalAccess,
+    ImpureMethodCall,
+    ImpurePropertyAssignment,
+    ImpureStaticPropertyAccess,
     ImpureStaticVariable,
     IncompatibleConstantAccess,
     IncompatibleConstantOverride,
@@ -101,6 +109,7 @@ pub enum IssueCode {
     InconsistentTemplate,
     IncorrectClassLikeCasing,
     IncorrectFunctionCasing,
+    IntegerOverflow,
     InterfaceInstantiation,
     InvalidArgument,
     InvalidArrayAccess,
@@ -122,6 +131,7 @@ pub enum IssueCode {
     InvalidContinue,
     InvalidDestructuringSource,
     InvalidDocblock,
+    InvalidDoctrineCriteria,
     InvalidEnumCaseValue,
     InvalidExtend,
     InvalidForeachKey,
@@ -144,6 +154,7 @@ pub enum IssueCode {
     InvalidPropertyDefaultValue,
     InvalidPropertyRead,
     InvalidPropertyWrite,
+    InvalidRegexPattern,
     InvalidReturnStatement,
     InvalidScopeKeywordContext,
     InvalidStaticMethodAccess,
@@ -171,6 +182,7 @@ pub enum IssueCode {
     MatchSubjectTypeIsNever,
     MethodAccessOnNull,
     MismatchedArrayIndex,
+    MismatchedDoctrineColumnNullability,
     MissingApiOrInternal,
     MissingConstantType,
     MissingConstructor,
@@ -236,6 +248,7 @@ pub enum IssueCode {
     ParentOutsideClassScope,
     PossibleMethodAccessOnNull,
     PossiblyArrayAppendOverflow,
+    PossiblyDivisionByZero,
     PossiblyFalseArgument,
     PossiblyFalseArrayAccess,
     PossiblyFalseIterator,
@@ -285,6 +298,22 @@ pub enum IssueCode {
     StaticOutsideClassScope,
     StringConstantSelector,
     StringMemberSelector,
+    TaintedCallable,
+    TaintedCookie,
+    TaintedEval,
+    TaintedFile,
+    TaintedHeader,
+    TaintedHtml,
+    TaintedInclude,
+    TaintedLdap,
+    TaintedShell,
+    TaintedSql,
+    TaintedSsrf,
+    TaintedSystemSecret,
+    TaintedTextWithQuotes,
+    TaintedUnserialize,
+    TaintedUserSecret,
+    TaintedXpath,
     TemplateConstraintViolation,
     TooFewArguments,
     TooManyArguments,
@@ -299,6 +328,7 @@ pub enum IssueCode {
     UnavailableFunction,
     UnavailableMethod,
     UnavailableProperty,
+    UndeclaredDependency,
     UndefinedIntArrayIndex,
     UndefinedStringArrayIndex,
     UndefinedVariable,
@@ -321,6 +351,12 @@ pub enum IssueCode {
     UnreachableSwitchDefault,
     UnresolvableClassConstant,
     UnsafeInstantiation,
+    UnusedClassConstant,
+    UnusedClassLike,
+    UnusedConstant,
+    UnusedDependency,
+    UnusedEnumCase,
+    UnusedFunction,
     UnusedFunctionCall,
     UnusedMethod,
     UnusedMethodCall,
@@ -364,6 +400,7 @@ impl IssueCode {
             Self::ConditionIsTooComplex => "condition-is-too-complex",
             Self::ConflictingReferenceConstraint => "conflicting-reference-constraint",
             Self::ConflictingTemplateEqualityBounds => "conflicting-template-equality-bounds",
+            Self::DeadCode => "dead-code",
             Self::DeprecatedClass => "deprecated-class",
             Self::DeprecatedClosure => "deprecated-closure",
             Self::DeprecatedConstant => "deprecated-constant",
@@ -371,7 +408,9 @@ impl IssueCode {
             Self::DeprecatedFunction => "deprecated-function",
             Self::DeprecatedMethod => "deprecated-method",
             Self::DeprecatedTrait => "deprecated-trait",
+            Self::DevDependencyInProduction => "dev-dependency-in-production",
             Self::DirectTraitConstantAccess => "direct-trait-constant-access",
+            Self::DivisionByZero => "division-by-zero",
             Self::DocblockParameterNarrowing => "docblock-parameter-narrowing",
             Self::DocblockTypeMismatch => "docblock-type-mismatch",
             Self::DuplicateArrayKey => "duplicate-array-key",
@@ -408,6 +447,11 @@ impl IssueCode {
             Self::ImpossibleTypeComparison => "impossible-type-comparison",
             Self::ImpreciseType => "imprecise-type",
             Self::ImpureConstruct => "impure-construct",
+            Self::ImpureFunctionCall => "impure-function-call",
+            Self::ImpureGlobalAccess => "impure-global-access",
+            Self::ImpureMethodCall => "impure-method-call",
+            Self::ImpurePropertyAssignment => "impure-property-assignment",
+            Self::ImpureStaticPropertyAccess => "impure-static-property-access",
             Self::ImpureStaticVariable => "impure-static-variable",
             Self::IncompatibleConstantAccess => "incompatible-constant-access",
             Self::IncompatibleConstantOverride => "incompatible-constant-override",
@@ -433,6 +477,7 @@ impl IssueCode {
             Self::InconsistentTemplate => "inconsistent-template",
             Self::IncorrectClassLikeCasing => "incorrect-class-like-casing",
             Self::IncorrectFunctionCasing => "incorrect-function-casing",
+            Self::IntegerOverflow => "integer-overflow",
             Self::InterfaceInstantiation => "interface-instantiation",
             Self::InvalidArgument => "invalid-argument",
             Self::InvalidArrayAccess => "invalid-array-access",
@@ -454,6 +499,7 @@ impl IssueCode {
             Self::InvalidContinue => "invalid-continue",
             Self::InvalidDestructuringSource => "invalid-destructuring-source",
             Self::InvalidDocblock => "invalid-docblock",
+            Self::InvalidDoctrineCriteria => "invalid-doctrine-criteria",
             Self::InvalidEnumCaseValue => "invalid-enum-case-value",
             Self::InvalidExtend => "invalid-extend",
             Self::InvalidForeachKey => "invalid-foreach-key",
@@ -476,6 +522,7 @@ impl IssueCode {
             Self::InvalidPropertyDefaultValue => "invalid-property-default-value",
             Self::InvalidPropertyRead => "invalid-property-read",
             Self::InvalidPropertyWrite => "invalid-property-write",
+            Self::InvalidRegexPattern => "invalid-regex-pattern",
             Self::InvalidReturnStatement => "invalid-return-statement",
             Self::InvalidScopeKeywordContext => "invalid-scope-keyword-context",
             Self::InvalidStaticMethodAccess => "invalid-static-method-access",
@@ -503,6 +550,7 @@ impl IssueCode {
             Self::MatchSubjectTypeIsNever => "match-subject-type-is-never",
             Self::MethodAccessOnNull => "method-access-on-null",
             Self::MismatchedArrayIndex => "mismatched-array-index",
+            Self::MismatchedDoctrineColumnNullability => "mismatched-doctrine-column-nullability",
             Self::MissingApiOrInternal => "missing-api-or-internal",
             Self::MissingConstantType => "missing-constant-type",
             Self::MissingConstructor => "missing-constructor",
@@ -568,6 +616,7 @@ impl IssueCode {
             Self::ParentOutsideClassScope => "parent-outside-class-scope",
             Self::PossibleMethodAccessOnNull => "possible-method-access-on-null",
             Self::PossiblyArrayAppendOverflow => "possibly-array-append-overflow",
+            Self::PossiblyDivisionByZero => "possibly-division-by-zero",
             Self::PossiblyFalseArgument => "possibly-false-argument",
             Self::PossiblyFalseArrayAccess => "possibly-false-array-access",
             Self::PossiblyFalseIterator => "possibly-false-iterator",
@@ -617,6 +666,22 @@ impl IssueCode {
             Self::StaticOutsideClassScope => "static-outside-class-scope",
             Self::StringConstantSelector => "string-constant-selector",
             Self::StringMemberSelector => "string-member-selector",
+            Self::TaintedCallable => "tainted-callable",
+            Self::TaintedCookie => "tainted-cookie",
+            Self::TaintedEval => "tainted-eval",
+            Self::TaintedFile => "tainted-file",
+            Self::TaintedHeader => "tainted-header",
+            Self::TaintedHtml => "tainted-html",
+            Self::TaintedInclude => "tainted-include",
+            Self::TaintedLdap => "tainted-ldap",
+            Self::TaintedShell => "tainted-shell",
+            Self::TaintedSql => "tainted-sql",
+            Self::TaintedSsrf => "tainted-ssrf",
+            Self::TaintedSystemSecret => "tainted-system-secret",
+            Self::TaintedTextWithQuotes => "tainted-text-with-quotes",
+            Self::TaintedUnserialize => "tainted-unserialize",
+            Self::TaintedUserSecret => "tainted-user-secret",
+            Self::TaintedXpath => "tainted-xpath",
             Self::TemplateConstraintViolation => "template-constraint-violation",
             Self::TooFewArguments => "too-few-arguments",
             Self::TooManyArguments => "too-many-arguments",
@@ -631,6 +696,7 @@ impl IssueCode {
             Self::UnavailableFunction => "unavailable-function",
             Self::UnavailableMethod => "unavailable-method",
             Self::UnavailableProperty => "unavailable-property",
+            Self::UndeclaredDependency => "undeclared-dependency",
             Self::UndefinedIntArrayIndex => "undefined-int-array-index",
             Self::UndefinedStringArrayIndex => "undefined-string-array-index",
             Self::UndefinedVariable => "undefined-variable",
@@ -653,6 +719,12 @@ impl IssueCode {
             Self::UnreachableSwitchDefault => "unreachable-switch-default",
             Self::UnresolvableClassConstant => "unresolvable-class-constant",
             Self::UnsafeInstantiation => "unsafe-instantiation",
+            Self::UnusedClassConstant => "unused-class-constant",
+            Self::UnusedClassLike => "unused-class-like",
+            Self::UnusedConstant => "unused-constant",
+            Self::UnusedDependency => "unused-dependency",
+            Self::UnusedEnumCase => "unused-enum-case",
+            Self::UnusedFunction => "unused-function",
             Self::UnusedFunctionCall => "unused-function-call",
             Self::UnusedMethod => "unused-method",
             Self::UnusedMethodCall => "unused-method-call",
@@ -701,6 +773,7 @@ impl IssueCode {
             Self::ConditionIsTooComplex,
             Self::ConflictingReferenceConstraint,
             Self::ConflictingTemplateEqualityBounds,
+            Self::DeadCode,
             Self::DeprecatedClass,
             Self::DeprecatedClosure,
             Self::DeprecatedConstant,
@@ -708,7 +781,9 @@ impl IssueCode {
             Self::DeprecatedFunction,
             Self::DeprecatedMethod,
             Self::DeprecatedTrait,
+            Self::DevDependencyInProduction,
             Self::DirectTraitConstantAccess,
+            Self::DivisionByZero,
             Self::DocblockParameterNarrowing,
             Self::DocblockTypeMismatch,
             Self::DuplicateArrayKey,
@@ -745,6 +820,11 @@ impl IssueCode {
             Self::ImpossibleTypeComparison,
             Self::ImpreciseType,
             Self::ImpureConstruct,
+            Self::ImpureFunctionCall,
+            Self::ImpureGlobalAccess,
+            Self::ImpureMethodCall,
+            Self::ImpurePropertyAssignment,
+            Self::ImpureStaticPropertyAccess,
             Self::ImpureStaticVariable,
             Self::IncompatibleConstantAccess,
             Self::IncompatibleConstantOverride,
@@ -770,6 +850,7 @@ impl IssueCode {
             Self::InconsistentTemplate,
             Self::IncorrectClassLikeCasing,
             Self::IncorrectFunctionCasing,
+            Self::IntegerOverflow,
             Self::InterfaceInstantiation,
             Self::InvalidArgument,
             Self::InvalidArrayAccess,
@@ -791,6 +872,7 @@ impl IssueCode {
             Self::InvalidContinue,
             Self::InvalidDestructuringSource,
             Self::InvalidDocblock,
+            Self::InvalidDoctrineCriteria,
             Self::InvalidEnumCaseValue,
             Self::InvalidExtend,
             Self::InvalidForeachKey,
@@ -813,6 +895,7 @@ impl IssueCode {
             Self::InvalidPropertyDefaultValue,
             Self::InvalidPropertyRead,
             Self::InvalidPropertyWrite,
+            Self::InvalidRegexPattern,
             Self::InvalidReturnStatement,
             Self::InvalidScopeKeywordContext,
             Self::InvalidStaticMethodAccess,
@@ -840,6 +923,7 @@ impl IssueCode {
             Self::MatchSubjectTypeIsNever,
             Self::MethodAccessOnNull,
             Self::MismatchedArrayIndex,
+            Self::MismatchedDoctrineColumnNullability,
             Self::MissingApiOrInternal,
             Self::MissingConstantType,
             Self::MissingConstructor,
@@ -905,6 +989,7 @@ impl IssueCode {
             Self::ParentOutsideClassScope,
             Self::PossibleMethodAccessOnNull,
             Self::PossiblyArrayAppendOverflow,
+            Self::PossiblyDivisionByZero,
             Self::PossiblyFalseArgument,
             Self::PossiblyFalseArrayAccess,
             Self::PossiblyFalseIterator,
@@ -954,6 +1039,22 @@ impl IssueCode {
             Self::StaticOutsideClassScope,
             Self::StringConstantSelector,
             Self::StringMemberSelector,
+            Self::TaintedCallable,
+            Self::TaintedCookie,
+            Self::TaintedEval,
+            Self::TaintedFile,
+            Self::TaintedHeader,
+            Self::TaintedHtml,
+            Self::TaintedInclude,
+            Self::TaintedLdap,
+            Self::TaintedShell,
+            Self::TaintedSql,
+            Self::TaintedSsrf,
+            Self::TaintedSystemSecret,
+            Self::TaintedTextWithQuotes,
+            Self::TaintedUnserialize,
+            Self::TaintedUserSecret,
+            Self::TaintedXpath,
             Self::TemplateConstraintViolation,
             Self::TooFewArguments,
             Self::TooManyArguments,
@@ -968,6 +1069,7 @@ impl IssueCode {
             Self::UnavailableFunction,
             Self::UnavailableMethod,
             Self::UnavailableProperty,
+            Self::UndeclaredDependency,
             Self::UndefinedIntArrayIndex,
             Self::UndefinedStringArrayIndex,
             Self::UndefinedVariable,
@@ -990,6 +1092,12 @@ impl IssueCode {
             Self::UnreachableSwitchDefault,
             Self::UnresolvableClassConstant,
             Self::UnsafeInstantiation,
+            Self::UnusedClassConstant,
+            Self::UnusedClassLike,
+            Self::UnusedConstant,
+            Self::UnusedDependency,
+            Self::UnusedEnumCase,
+            Self::UnusedFunction,
             Self::UnusedFunctionCall,
             Self::UnusedMethod,
             Self::UnusedMethodCall,
@@ -1036,6 +1144,7 @@ impl std::str::FromStr for IssueCode {
             "condition-is-too-complex" => Ok(Self::ConditionIsTooComplex),
             "conflicting-reference-constraint" => Ok(Self::ConflictingReferenceConstraint),
             "conflicting-template-equality-bounds" => Ok(Self::ConflictingTemplateEqualityBounds),
+            "dead-code" => Ok(Self::DeadCode),
             "deprecated-class" => Ok(Self::DeprecatedClass),
             "deprecated-closure" => Ok(Self::DeprecatedClosure),
             "deprecated-constant" => Ok(Self::DeprecatedConstant),
@@ -1043,7 +1152,9 @@ impl std::str::FromStr for IssueCode {
             "deprecated-function" => Ok(Self::DeprecatedFunction),
             "deprecated-method" => Ok(Self::DeprecatedMethod),
             "deprecated-trait" => Ok(Self::DeprecatedTrait),
+            "dev-dependency-in-production" => Ok(Self::DevDependencyInProduction),
             "direct-trait-constant-access" => Ok(Self::DirectTraitConstantAccess),
+            "division-by-zero" => Ok(Self::DivisionByZero),
             "docblock-parameter-narrowing" => Ok(Self::DocblockParameterNarrowing),
             "docblock-type-mismatch" => Ok(Self::DocblockTypeMismatch),
             "duplicate-array-key" => Ok(Self::DuplicateArrayKey),
@@ -1080,6 +1191,11 @@ impl std::str::FromStr for IssueCode {
             "impossible-type-comparison" => Ok(Self::ImpossibleTypeComparison),
             "imprecise-type" => Ok(Self::ImpreciseType),
             "impure-construct" => Ok(Self::ImpureConstruct),
+            "impure-function-call" => Ok(Self::ImpureFunctionCall),
+            "impure-global-access" => Ok(Self::ImpureGlobalAccess),
+            "impure-method-call" => Ok(Self::ImpureMethodCall),
+            "impure-property-assignment" => Ok(Self::ImpurePropertyAssignment),
+            "impure-static-property-access" => Ok(Self::ImpureStaticPropertyAccess),
             "impure-static-variable" => Ok(Self::ImpureStaticVariable),
             "incompatible-constant-access" => Ok(Self::IncompatibleConstantAccess),
             "incompatible-constant-override" => Ok(Self::IncompatibleConstantOverride),
@@ -1105,6 +1221,7 @@ impl std::str::FromStr for IssueCode {
             "inconsistent-template" => Ok(Self::InconsistentTemplate),
             "incorrect-class-like-casing" => Ok(Self::IncorrectClassLikeCasing),
             "incorrect-function-casing" => Ok(Self::IncorrectFunctionCasing),
+            "integer-overflow" => Ok(Self::IntegerOverflow),
             "interface-instantiation" => Ok(Self::InterfaceInstantiation),
             "invalid-argument" => Ok(Self::InvalidArgument),
             "invalid-array-access" => Ok(Self::InvalidArrayAccess),
@@ -1126,6 +1243,7 @@ impl std::str::FromStr for IssueCode {
             "invalid-continue" => Ok(Self::InvalidContinue),
             "invalid-destructuring-source" => Ok(Self::InvalidDestructuringSource),
             "invalid-docblock" => Ok(Self::InvalidDocblock),
+            "invalid-doctrine-criteria" => Ok(Self::InvalidDoctrineCriteria),
             "invalid-enum-case-value" => Ok(Self::InvalidEnumCaseValue),
             "invalid-extend" => Ok(Self::InvalidExtend),
             "invalid-foreach-key" => Ok(Self::InvalidForeachKey),
@@ -1148,6 +1266,7 @@ impl std::str::FromStr for IssueCode {
             "invalid-property-default-value" => Ok(Self::InvalidPropertyDefaultValue),
             "invalid-property-read" => Ok(Self::InvalidPropertyRead),
             "invalid-property-write" => Ok(Self::InvalidPropertyWrite),
+            "invalid-regex-pattern" => Ok(Self::InvalidRegexPattern),
             "invalid-return-statement" => Ok(Self::InvalidReturnStatement),
             "invalid-scope-keyword-context" => Ok(Self::InvalidScopeKeywordContext),
             "invalid-static-method-access" => Ok(Self::InvalidStaticMethodAccess),
@@ -1175,6 +1294,7 @@ impl std::str::FromStr for IssueCode {
             "match-subject-type-is-never" => Ok(Self::MatchSubjectTypeIsNever),
             "method-access-on-null" => Ok(Self::MethodAccessOnNull),
             "mismatched-array-index" => Ok(Self::MismatchedArrayIndex),
+            "mismatched-doctrine-column-nullability" => Ok(Self::MismatchedDoctrineColumnNullability),
             "missing-api-or-internal" => Ok(Self::MissingApiOrInternal),
             "missing-constant-type" => Ok(Self::MissingConstantType),
             "missing-constructor" => Ok(Self::MissingConstructor),
@@ -1240,6 +1360,7 @@ impl std::str::FromStr for IssueCode {
             "parent-outside-class-scope" => Ok(Self::ParentOutsideClassScope),
             "possible-method-access-on-null" => Ok(Self::PossibleMethodAccessOnNull),
             "possibly-array-append-overflow" => Ok(Self::PossiblyArrayAppendOverflow),
+            "possibly-division-by-zero" => Ok(Self::PossiblyDivisionByZero),
             "possibly-false-argument" => Ok(Self::PossiblyFalseArgument),
             "possibly-false-array-access" => Ok(Self::PossiblyFalseArrayAccess),
             "possibly-false-iterator" => Ok(Self::PossiblyFalseIterator),
@@ -1289,6 +1410,22 @@ impl std::str::FromStr for IssueCode {
             "static-outside-class-scope" => Ok(Self::StaticOutsideClassScope),
             "string-constant-selector" => Ok(Self::StringConstantSelector),
             "string-member-selector" => Ok(Self::StringMemberSelector),
+            "tainted-callable" => Ok(Self::TaintedCallable),
+            "tainted-cookie" => Ok(Self::TaintedCookie),
+            "tainted-eval" => Ok(Self::TaintedEval),
+            "tainted-file" => Ok(Self::TaintedFile),
+            "tainted-header" => Ok(Self::TaintedHeader),
+            "tainted-html" => Ok(Self::TaintedHtml),
+            "tainted-include" => Ok(Self::TaintedInclude),
+            "tainted-ldap" => Ok(Self::TaintedLdap),
+            "tainted-shell" => Ok(Self::TaintedShell),
+            "tainted-sql" => Ok(Self::TaintedSql),
+            "tainted-ssrf" => Ok(Self::TaintedSsrf),
+            "tainted-system-secret" => Ok(Self::TaintedSystemSecret),
+            "tainted-text-with-quotes" => Ok(Self::TaintedTextWithQuotes),
+            "tainted-unserialize" => Ok(Self::TaintedUnserialize),
+            "tainted-user-secret" => Ok(Self::TaintedUserSecret),
+            "tainted-xpath" => Ok(Self::TaintedXpath),
             "template-constraint-violation" => Ok(Self::TemplateConstraintViolation),
             "too-few-arguments" => Ok(Self::TooFewArguments),
             "too-many-arguments" => Ok(Self::TooManyArguments),
@@ -1303,6 +1440,7 @@ impl std::str::FromStr for IssueCode {
             "unavailable-function" => Ok(Self::UnavailableFunction),
             "unavailable-method" => Ok(Self::UnavailableMethod),
             "unavailable-property" => Ok(Self::UnavailableProperty),
+            "undeclared-dependency" => Ok(Self::UndeclaredDependency),
             "undefined-int-array-index" => Ok(Self::UndefinedIntArrayIndex),
             "undefined-string-array-index" => Ok(Self::UndefinedStringArrayIndex),
             "undefined-variable" => Ok(Self::UndefinedVariable),
@@ -1325,6 +1463,12 @@ impl std::str::FromStr for IssueCode {
             "unreachable-switch-default" => Ok(Self::UnreachableSwitchDefault),
             "unresolvable-class-constant" => Ok(Self::UnresolvableClassConstant),
             "unsafe-instantiation" => Ok(Self::UnsafeInstantiation),
+            "unused-class-constant" => Ok(Self::UnusedClassConstant),
+            "unused-class-like" => Ok(Self::UnusedClassLike),
+            "unused-constant" => Ok(Self::UnusedConstant),
+            "unused-dependency" => Ok(Self::UnusedDependency),
+            "unused-enum-case" => Ok(Self::UnusedEnumCase),
+            "unused-function" => Ok(Self::UnusedFunction),
             "unused-function-call" => Ok(Self::UnusedFunctionCall),
             "unused-method" => Ok(Self::UnusedMethod),
             "unused-method-call" => Ok(Self::UnusedMethodCall),
@@ -20,6 +20,7 @@ use crate::code::IssueCode;
 use crate::context::Context;
 use crate::context::block::BlockContext;
 use crate::error::AnalysisError;
+use crate::taint;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ConstructInput<'ast, 'arena> {
@@ -96,6 +97,15 @@ where
                     &construct_keyword,
                     true,
                 );
+
+                taint::analyze_construct_input(
+                    context,
+                    block_context,
+                    artifacts,
+                    construct_kind,
+                    construct_keyword,
+                    argument.value(),
+                );
             }
         }
         ConstructInput::Expression(expression) => {
@@ -114,6 +124,15 @@ where
                 &construct_keyword,
                 false,
             );
+
+            taint::analyze_construct_input(
+                context,
+                block_context,
+                artifacts,
+                construct_kind,
+                construct_keyword,
+                expression,
+            );
         }
         ConstructInput::ExpressionList(expressions) => {
             if expressions.is_empty() {
@@ -152,6 +171,15 @@ where
                     &construct_keyword,
                     false, // Not in a formal argument list
                 );
+
+                taint::analyze_construct_input(
+                    context,
+                    block_context,
+                    artifacts,
+                    construct_kind,
+                    construct_keyword,
+                    expression,
+                );
             }
         }
     }
@@ -0,0 +1,773 @@
+//! Dead-code detection and removal.
+//!
+//! Once the whole codebase has been analyzed, its [`SymbolReferences`] tell which user-defined
+//! declarations are never used. This module finds them and turns them into issues carrying
+//! the edits that delete them:
+//!
+//! - Class-likes and functions that nothing outside themselves references, including those
+//!   only referenced by other dead declarations
+//! - Unused private methods and properties (and protected ones in final classes), following
+//!   the same rules as the `unused-method` and `unused-property` checks
+//! - Whole files, when every declaration they contain is dead
+//!
+//! The same references back the `unused-*` issues reported by [`find_unused_symbols`] for
+//! class-likes, functions, constants, class constants and enum cases.
+//!
+//! Declarations carrying an entry-point attribute, declared in a kept namespace, or matching
+//! the configured kept symbols are never considered dead, since frameworks typically invoke
+//! them through reflection.
+
+use foldhash::HashMap;
+use foldhash::HashSet;
+
+use mago_allocator::LocalArena;
+use mago_codex::metadata::CodebaseMetadata;
+use mago_codex::metadata::attribute::AttributeMetadata;
+use mago_codex::metadata::class_like::ClassLikeMetadata;
+use mago_codex::metadata::function_like::FunctionLikeKind;
+use mago_codex::reference::ReferenceOrigin;
+use mago_codex::reference::SymbolReferenceKind;
+use mago_codex::reference::SymbolReferences;
+use mago_codex::symbol::SymbolKind;
+use mago_database::DatabaseReader;
+use mago_database::file::FileId;
+use mago_reporting::Annotation;
+use mago_reporting::Issue;
+use mago_reporting::IssueCollection;
+use mago_span::HasSpan;
+use mago_span::Span;
+use mago_syntax::cst::ClassLikeMember;
+use mago_syntax::cst::Property;
+use mago_syntax::cst::Sequence;
+use mago_syntax::cst::Statement;
+use mago_syntax::parser::parse_file;
+use mago_text_edit::Safety;
+use mago_text_edit::TextEdit;
+use mago_word::Word;
+use mago_word::WordSet;
+use mago_word::ascii_lowercase_word;
+
+use crate::code::IssueCode;
+use crate::settings::EntryPointSettings;
+use crate::settings::EntryPointSymbol;
+use crate::statement::class_like::unused_members::find_unused_members_in_codebase;
+
+/// Attributes that always mark a declaration as an entry point.
+///
+/// These cover the common cases where PHP itself or a widespread framework instantiates a
+/// class or calls a method without any reference to it appearing in the analyzed code.
+pub const BUILTIN_ENTRY_POINT_ATTRIBUTES: &[&str] = &[
+    "Attribute",
+    "PHPUnit\\Framework\\Attributes\\Test",
+    "PHPUnit\\Framework\\Attributes\\Before",
+    "PHPUnit\\Framework\\Attributes\\After",
+    "PHPUnit\\Framework\\Attributes\\BeforeClass",
+    "PHPUnit\\Framework\\Attributes\\AfterClass",
+    "Symfony\\Component\\Console\\Attribute\\AsCommand",
+    "Symfony\\Component\\EventDispatcher\\Attribute\\AsEventListener",
+    "Symfony\\Component\\Messenger\\Attribute\\AsMessageHandler",
+    "Symfony\\Component\\Routing\\Attribute\\Route",
+    "Symfony\\Component\\Routing\\Annotation\\Route",
+];
+
+/// The kind of a dead declaration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DeadSymbolKind {
+    ClassLike,
+    Function,
+    Method,
+    Property,
+}
+
+impl DeadSymbolKind {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::ClassLike => "Class-like",
+            Self::Function => "Function",
+            Self::Method => "Method",
+            Self::Property => "Property",
+        }
+    }
+}
+
+/// A user-defined declaration that is never used.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeadSymbol {
+    pub kind: DeadSymbolKind,
+    /// The display name, e.g. `App\Foo`, `App\helper`, `App\Foo::bar` or `App\Foo::$baz`.
+    pub name: String,
+    /// The span of the declaration; for properties, the span of the property variable.
+    pub span: Span,
+    /// The span of the declared name.
+    pub name_span: Span,
+}
+
+/// The outcome of planning dead-code removal.
+#[derive(Debug, Clone, Default)]
+pub struct DeadCodeRemoval {
+    /// One `dead-code` issue per removable declaration, each carrying the edits that delete it.
+    pub issues: IssueCollection,
+    /// Files whose every declaration is dead; their issue empties them entirely.
+    pub removed_files: Vec<FileId>,
+}
+
+/// Finds every dead user-defined declaration in the codebase.
+///
+/// `symbol_references` must come from analyzing the whole codebase: a reference from a
+/// file left out of the analysis cannot be seen, and would make a used symbol look dead.
+#[must_use]
+pub fn find_dead_code(
+    codebase: &CodebaseMetadata,
+    symbol_references: &SymbolReferences,
+    entry_points: &EntryPointSettings,
+) -> Vec<DeadSymbol> {
+    let entry_points = EntryPoints::new(entry_points);
+
+    let mut referencing_symbols = HashMap::<Word, WordSet>::default();
+    let mut externally_referenced = WordSet::default();
+    symbol_references.for_each_reference(|source, target, kind| {
+        if !matches!(kind, SymbolReferenceKind::Body | SymbolReferenceKind::Signature) {
+            return;
+        }
+
+        // A reference to (or from) a member counts as one to (or from) its class-like.
+        let target = ascii_lowercase_word(target.0.as_bytes());
+        match source {
+            ReferenceOrigin::Symbol(source) => {
+                let source = ascii_lowercase_word(source.0.as_bytes());
+                if source != target {
+                    referencing_symbols.entry(target).or_default().insert(source);
+                }
+            }
+            ReferenceOrigin::File(_) => {
+                externally_referenced.insert(target);
+            }
+        }
+    });
+
+    let mut candidates = HashMap::<Word, DeadSymbol>::default();
+    for (name, class_like_metadata) in &codebase.class_likes {
+        if !class_like_metadata.flags.is_user_defined() || entry_points.keeps_class_like(codebase, class_like_metadata)
+        {
+            continue;
+        }
+
+        // Anonymous classes have no name span, and go away with the expression creating them.
+        let Some(name_span) = class_like_metadata.name_span else {
+            continue;
+        };
+
+        candidates.insert(
+            *name,
+            DeadSymbol {
+                kind: DeadSymbolKind::ClassLike,
+                name: class_like_metadata.original_name.to_string(),
+                span: class_like_metadata.span,
+                name_span,
+            },
+        );
+    }
+
+    for ((scope, name), function_metadata) in &codebase.function_likes {
+        if !scope.is_empty()
+            || function_metadata.kind != FunctionLikeKind::Function
+            || !function_metadata.flags.is_user_defined()
+            || entry_points.keeps_symbol(*name)
+            || entry_points.has_attribute(&function_metadata.attributes)
+        {
+            continue;
+        }
+
+        let Some(name_span) = function_metadata.name_span else {
+            continue;
+        };
+
+        candidates.insert(
+            *name,
+            DeadSymbol {
+                kind: DeadSymbolKind::Function,
+                name: function_metadata.original_name.to_string(),
+                span: function_metadata.span,
+                name_span,
+            },
+        );
+    }
+
+    // Start from everything nothing outside references, then keep whatever is referenced
+    // by a symbol that is still alive until no more symbols are revived.
+    let mut dead =
+        candidates.keys().filter(|name| !externally_referenced.contains(*name)).copied().collect::<WordSet>();
+    loop {
+        let revived = dead
+            .iter()
+            .filter(|name| {
+                referencing_symbols
+                    .get(*name)
+                    .is_some_and(|sources| sources.iter().any(|source| !dead.contains(source)))
+            })
+            .copied()
+            .collect::<Vec<_>>();
+
+        if revived.is_empty() {
+            break;
+        }
+
+        for name in revived {
+            dead.remove(&name);
+        }
+    }
+
+    let mut dead_symbols =
+        candidates.into_iter().filter(|(name, _)| dead.contains(name)).map(|(_, symbol)| symbol).collect::<Vec<_>>();
+
+    for member in find_unused_members_in_codebase(codebase, symbol_references) {
+        let (class_name, member_name) = member.symbol_id;
+        if dead.contains(&class_name) || entry_points.keeps_member(class_name, member_name) {
+            continue;
+        }
+
+        let Some(class_like_metadata) = codebase.class_likes.get(&class_name) else {
+            continue;
+        };
+
+        if !class_like_metadata.flags.is_user_defined() {
+            continue;
+        }
+
+        if member.is_property {
+            let Some(property) = class_like_metadata.properties.get(&member_name) else {
+                continue;
+            };
+
+            // Promoted properties are constructor parameters as well; removing them changes the signature.
+            if property.flags.is_promoted_property() || entry_points.has_attribute(&property.attributes) {
+                continue;
+            }
+
+            dead_symbols.push(DeadSymbol {
+                kind: DeadSymbolKind::Property,
+                name: format!("{}::{member_name}", class_like_metadata.original_name),
+                span: member.span,
+                name_span: member.span,
+            });
+        } else {
+            let Some(method_metadata) = codebase.function_likes.get(&member.symbol_id) else {
+                continue;
+            };
+
+            if entry_points.has_attribute(&method_metadata.attributes) {
+                continue;
+            }
+
+            dead_symbols.push(DeadSymbol {
+                kind: DeadSymbolKind::Method,
+                name: format!("{}::{}", class_like_metadata.original_name, method_metadata.original_name),
+                span: method_metadata.span,
+                name_span: member.span,
+            });
+        }
+    }
+
+    dead_symbols.sort_by_key(|symbol| (symbol.span.file_id, symbol.span.start.offset));
+    dead_symbols
+}
+
+/// Reports every unused user-defined class-like, function, constant, class constant and enum case.
+///
+/// Class-likes and functions are those found by [`find_dead_code`], so one only referenced by
+/// other unused declarations is reported as well. Class constants and enum cases are used when
+/// accessed through their class-like or any class-like inheriting them; an enum's cases are
+/// all used once `cases()`, `from()` or `tryFrom()` is called on it. Unused methods and
+/// properties are left to the per-file `unused-method` and `unused-property` checks.
+///
+/// As with [`find_dead_code`], `symbol_references` must come from analyzing the whole codebase.
+#[must_use]
+pub fn find_unused_symbols(
+    codebase: &CodebaseMetadata,
+    symbol_references: &SymbolReferences,
+    entry_points: &EntryPointSettings,
+) -> IssueCollection {
+    let mut issues = IssueCollection::new();
+    let mut dead_class_likes = WordSet::default();
+    for symbol in find_dead_code(codebase, symbol_references, entry_points) {
+        let (code, kind) = match symbol.kind {
+            DeadSymbolKind::ClassLike => {
+                let name = ascii_lowercase_word(symbol.name.as_bytes());
+                dead_class_likes.insert(name);
+
+                let kind = match codebase.class_likes.get(&name).map(|class_like_metadata| class_like_metadata.kind) {
+                    Some(SymbolKind::Interface) => "Interface",
+                    Some(SymbolKind::Trait) => "Trait",
+                    Some(SymbolKind::Enum) => "Enum",
+                    Some(SymbolKind::Class) | None => "Class",
+                };
+
+                (IssueCode::UnusedClassLike, kind)
+            }
+            DeadSymbolKind::Function => (IssueCode::UnusedFunction, "Function"),
+            DeadSymbolKind::Method | DeadSymbolKind::Property => continue,
+        };
+
+        issues.push(unused_symbol_issue(code, kind, &symbol.name, symbol.name_span));
+    }
+
+    let entry_points = EntryPoints::new(entry_points);
+
+    // A member accessed through a class-like counts as used on every class-like it may be
+    // inherited from.
+    let mut used_members = HashSet::<(Word, Word)>::default();
+    let mut enums_with_all_cases_used = WordSet::default();
+    let mut used_symbols = WordSet::default();
+    symbol_references.for_each_reference(|_, (symbol_name, member_name), kind| {
+        if !matches!(kind, SymbolReferenceKind::Body | SymbolReferenceKind::Signature) {
+            return;
+        }
+
+        let symbol_name = ascii_lowercase_word(symbol_name.as_bytes());
+        if member_name.is_empty() {
+            used_symbols.insert(symbol_name);
+            return;
+        }
+
+        let member = member_name.as_bytes();
+        if member.eq_ignore_ascii_case(b"cases")
+            || member.eq_ignore_ascii_case(b"from")
+            || member.eq_ignore_ascii_case(b"tryfrom")
+        {
+            enums_with_all_cases_used.insert(symbol_name);
+        }
+
+        used_members.insert((symbol_name, member_name));
+        if let Some(class_like_metadata) = codebase.class_likes.get(&symbol_name) {
+            for ancestor in class_like_metadata
+                .all_parent_classes
+                .iter()
+                .chain(&class_like_metadata.all_parent_interfaces)
+                .chain(&class_like_metadata.used_traits)
+            {
+                used_members.insert((ascii_lowercase_word(ancestor.as_bytes()), member_name));
+            }
+        }
+    });
+
+    let mut member_issues = Vec::new();
+    for (name, class_like_metadata) in &codebase.class_likes {
+        if !class_like_metadata.flags.is_user_defined() || dead_class_likes.contains(name) {
+            continue;
+        }
+
+        // Constants inherited from parents and traits are copied into the class-like; only
+        // report those declared in its own body.
+        let class_span = class_like_metadata.span;
+        let is_declared_here =
+            |span: Span| span.file_id == class_span.file_id && class_span.has_offset(span.start.offset);
+
+        for (constant_name, constant_metadata) in &class_like_metadata.constants {
+            if !is_declared_here(constant_metadata.span)
+                || used_members.contains(&(*name, *constant_name))
+                || entry_points.keeps_member(*name, *constant_name)
+                || entry_points.has_attribute(&constant_metadata.attributes)
+            {
+                continue;
+            }
+
+            member_issues.push(unused_symbol_issue(
+                IssueCode::UnusedClassConstant,
+                "Class constant",
+                &format!("{}::{constant_name}", class_like_metadata.original_name),
+                constant_metadata.span,
+            ));
+        }
+
+        if enums_with_all_cases_used.contains(name) {
+            continue;
+        }
+
+        for (case_name, case_metadata) in &class_like_metadata.enum_cases {
+            if used_members.contains(&(*name, *case_name))
+                || entry_points.keeps_member(*name, *case_name)
+                || entry_points.has_attribute(&case_metadata.attributes)
+            {
+                continue;
+            }
+
+            member_issues.push(unused_symbol_issue(
+                IssueCode::UnusedEnumCase,
+                "Enum case",
+                &format!("{}::{case_name}", class_like_metadata.original_name),
+                case_metadata.name_span,
+            ));
+        }
+    }
+
+    for constant_metadata in codebase.constants.values() {
+        if !constant_metadata.flags.is_user_defined()
+            || used_symbols.contains(&ascii_lowercase_word(constant_metadata.name.as_bytes()))
+            || entry_points.keeps_symbol(constant_metadata.name)
+            || entry_points.has_attribute(&constant_metadata.attributes)
+        {
+            continue;
+        }
+
+        member_issues.push(unused_symbol_issue(
+            IssueCode::UnusedConstant,
+            "Constant",
+            &constant_metadata.original_name.to_string(),
+            constant_metadata.span,
+        ));
+    }
+
+    // Hash map iteration order is not stable; report in source order.
+    member_issues.sort_by_key(|issue| {
+        issue.annotations.first().map(|annotation| (annotation.span.file_id, annotation.span.start.offset))
+    });
+    issues.extend(member_issues);
+
+    issues
+}
+
+fn unused_symbol_issue(code: IssueCode, kind: &str, name: &str, span: Span) -> Issue {
+    Issue::help(format!("{kind} `{name}` is never used."))
+        .with_code(code)
+        .with_annotation(Annotation::primary(span).with_message(format!("`{name}` is declared here.")))
+        .with_note("Nothing in the analyzed code references this declaration, except other unused code.")
+        .with_help("Remove the declaration, or list it as an entry point if it is used from outside the analyzed code.")
+}
+
+/// Plans the edits that remove the given dead declarations.
+///
+/// Each affected file is re-parsed to find the full extent of the declarations, including
+/// their docblocks. Declarations that cannot be removed on their own (for example, functions
+/// declared conditionally inside an `if`) are left untouched and not reported.
+#[must_use]
+pub fn plan_dead_code_removal<D>(database: &D, dead_symbols: &[DeadSymbol]) -> DeadCodeRemoval
+where
+    D: DatabaseReader,
+{
+    let mut symbols_by_file = HashMap::<FileId, Vec<&DeadSymbol>>::default();
+    for symbol in dead_symbols {
+        symbols_by_file.entry(symbol.span.file_id).or_default().push(symbol);
+    }
+
+    let mut file_ids = symbols_by_file.keys().copied().collect::<Vec<_>>();
+    file_ids.sort();
+
+    let mut removal = DeadCodeRemoval::default();
+    for file_id in file_ids {
+        let Ok(file) = database.get_ref(&file_id) else {
+            continue;
+        };
+
+        let arena = LocalArena::new();
+        let program = parse_file(&arena, file);
+        if program.has_errors() {
+            continue;
+        }
+
+        let mut declarations = Declarations { is_declarations_only: true, ..Declarations::default() };
+        declarations.collect(&program.statements);
+
+        let symbols = &symbols_by_file[&file_id];
+        let dead_spans = symbols.iter().map(|symbol| symbol.span).collect::<HashSet<_>>();
+        if declarations.is_declarations_only
+            && !declarations.top_level.is_empty()
+            && declarations.top_level.iter().all(|span| dead_spans.contains(span))
+        {
+            let mut issue =
+                Issue::help(format!("File `{}` only declares unused symbols.", mago_bytes::BytesDisplay(&file.name)))
+                    .with_code(IssueCode::DeadCode);
+
+            for (index, symbol) in symbols.iter().enumerate() {
+                let annotation = if index == 0 {
+                    Annotation::primary(symbol.name_span)
+                } else {
+                    Annotation::secondary(symbol.name_span)
+                };
+                issue = issue.with_annotation(annotation.with_message(format!("`{}` is never used.", symbol.name)));
+            }
+
+            removal.issues.push(
+                issue
+                    .with_note("Every class-like and function declared in this file is unreachable from the rest of the codebase.")
+                    .with_help("Delete the file.")
+                    .with_edit(file_id, TextEdit::delete(0..file.size).with_safety(Safety::PotentiallyUnsafe)),
+            );
+            removal.removed_files.push(file_id);
+
+            continue;
+        }
+
+        for symbol in symbols {
+            let range = match symbol.kind {
+                DeadSymbolKind::ClassLike | DeadSymbolKind::Function => declarations
+                    .top_level
+                    .contains(&symbol.span)
+                    .then(|| declaration_range(&file.contents, symbol.span)),
+                DeadSymbolKind::Method => {
+                    declarations.methods.contains(&symbol.span).then(|| declaration_range(&file.contents, symbol.span))
+                }
+                DeadSymbolKind::Property => declarations.property_range(&file.contents, symbol.span),
+            };
+
+            let Some((start, end)) = range else {
+                continue;
+            };
+
+            removal.issues.push(
+                Issue::help(format!("{} `{}` is never used.", symbol.kind.as_str(), symbol.name))
+                    .with_code(IssueCode::DeadCode)
+                    .with_annotation(
+                        Annotation::primary(symbol.name_span)
+                            .with_message(format!("`{}` is declared here.", symbol.name)),
+                    )
+                    .with_note("Nothing in the analyzed code references this declaration, except other dead code.")
+                    .with_help("Remove the declaration, or list it as an entry point if it is invoked by a framework.")
+                    .with_edit(file_id, TextEdit::delete(start..end).with_safety(Safety::PotentiallyUnsafe)),
+            );
+        }
+    }
+
+    removal
+}
+
+/// Entry-point attributes, namespaces and kept symbols, normalized for lookups.
+struct EntryPoints {
+    attributes: WordSet,
+    namespaces: Vec<Word>,
+    symbols: WordSet,
+    members: HashSet<(Word, Word)>,
+    /// Kept symbols and members with a trailing `*` in either half.
+    patterns: Vec<EntryPointSymbol>,
+}
+
+impl EntryPoints {
+    fn new(settings: &EntryPointSettings) -> Self {
+        let mut attributes = settings.attributes.clone();
+        attributes.extend(BUILTIN_ENTRY_POINT_ATTRIBUTES.iter().map(|name| ascii_lowercase_word(name.as_bytes())));
+
+        let mut symbols = WordSet::default();
+        let mut members = HashSet::default();
+        let mut patterns = Vec::new();
+        for symbol in &settings.symbols {
+            let is_pattern = symbol.name.as_bytes().ends_with(b"*")
+                || symbol.class.is_some_and(|class| class.as_bytes().ends_with(b"*"));
+
+            match symbol.class {
+                _ if is_pattern => patterns.push(*symbol),
+                Some(class) => {
+                    members.insert((class, symbol.name));
+                }
+                None => {
+                    symbols.insert(symbol.name);
+                }
+            }
+        }
+
+        Self { attributes, namespaces: settings.namespaces.clone(), symbols, members, patterns }
+    }
+
+    fn has_attribute(&self, attributes: &[AttributeMetadata]) -> bool {
+        attributes.iter().any(|attribute| {
+            let name = attribute.name.as_bytes();
+            let name = name.strip_prefix(b"\\").unwrap_or(name);
+
+            self.attributes.contains(&ascii_lowercase_word(name))
+        })
+    }
+
+    fn keeps_symbol(&self, name: Word) -> bool {
+        let name = ascii_lowercase_word(name.as_bytes());
+
+        self.symbols.contains(&name)
+            || self.is_in_kept_namespace(name)
+            || self.patterns.iter().any(|pattern| pattern.class.is_none() && matches_pattern(pattern.name, name))
+    }
+
+    fn keeps_member(&self, class_name: Word, member_name: Word) -> bool {
+        if self.members.is_empty() && self.namespaces.is_empty() && self.patterns.is_empty() {
+            return false;
+        }
+
+        let class_name = ascii_lowercase_word(class_name.as_bytes());
+        let member_name = ascii_lowercase_word(member_name.as_bytes());
+
+        self.members.contains(&(class_name, member_name))
+            || self.is_in_kept_namespace(class_name)
+            || self.patterns.iter().any(|pattern| {
+                pattern.class.is_some_and(|class| matches_pattern(class, class_name))
+                    && matches_pattern(pattern.name, member_name)
+            })
+    }
+
+    /// Whether the lowercased name is declared in one of the kept namespaces or below.
+    fn is_in_kept_namespace(&self, name: Word) -> bool {
+        let name = name.as_bytes();
+
+        self.namespaces
+            .iter()
+            .any(|namespace| name.strip_prefix(namespace.as_bytes()).is_some_and(|rest| rest.first() == Some(&b'\\')))
+    }
+
+    /// A class-like is kept when it, one of its ancestors, or one of its members is an entry point.
+    fn keeps_class_like(&self, codebase: &CodebaseMetadata, class_like_metadata: &ClassLikeMetadata) -> bool {
+        let class_name = class_like_metadata.name;
+        if self.keeps_symbol(class_name)
+            || self.has_attribute(&class_like_metadata.attributes)
+            || class_like_metadata.all_parent_classes.iter().any(|parent| self.keeps_symbol(*parent))
+            || class_like_metadata.all_parent_interfaces.iter().any(|parent| self.keeps_symbol(*parent))
+        {
+            return true;
+        }
+
+        let keeps_method = class_like_metadata.methods.iter().any(|method_name| {
+            self.keeps_member(class_name, *method_name)
+                || codebase
+                    .function_likes
+                    .get(&(class_name, *method_name))
+                    .is_some_and(|method_metadata| self.has_attribute(&method_metadata.attributes))
+        });
+
+        keeps_method
+            || class_like_metadata.properties.iter().any(|(property_name, property)| {
+                self.keeps_member(class_name, *property_name) || self.has_attribute(&property.attributes)
+            })
+    }
+}
+
+/// Whether a lowercased name matches a kept-symbol pattern, where a trailing `*` matches any suffix.
+fn matches_pattern(pattern: Word, name: Word) -> bool {
+    match pattern.as_bytes().strip_suffix(b"*") {
+        Some(prefix) => name.as_bytes().starts_with(prefix),
+        None => pattern == name,
+    }
+}
+
+/// Spans of the removable declarations found in a file.
+#[derive(Default)]
+struct Declarations {
+    /// Whether the file contains nothing but declarations, namespaces, imports and tags.
+    is_declarations_only: bool,
+    /// Class-likes and functions declared at the top level or directly inside a namespace.
+    top_level: Vec<Span>,
+    methods: Vec<Span>,
+    properties: Vec<PlainPropertyDeclaration>,
+}
+
+/// A plain (non-hooked) property declaration, which may declare several properties.
+struct PlainPropertyDeclaration {
+    span: Span,
+    /// The variable and full span of each declared item.
+    items: Vec<(Span, Span)>,
+}
+
+impl Declarations {
+    fn collect(&mut self, statements: &Sequence<'_, Statement<'_>>) {
+        for statement in statements.iter() {
+            match statement {
+                Statement::Namespace(namespace) => self.collect(namespace.statements()),
+                Statement::Class(class) => {
+                    self.top_level.push(class.span());
+                    self.collect_members(&class.members);
+                }
+                Statement::Interface(interface) => {
+                    self.top_level.push(interface.span());
+                    self.collect_members(&interface.members);
+                }
+                Statement::Trait(r#trait) => {
+                    self.top_level.push(r#trait.span());
+                    self.collect_members(&r#trait.members);
+                }
+                Statement::Enum(r#enum) => {
+                    self.top_level.push(r#enum.span());
+                    self.collect_members(&r#enum.members);
+                }
+                Statement::Function(function) => self.top_level.push(function.span()),
+                Statement::OpeningTag(_)
+                | Statement::ClosingTag(_)
+                | Statement::Use(_)
+                | Statement::Declare(_)
+                | Statement::Noop(_) => {}
+                Statement::Inline(inline) if inline.value.iter().all(u8::is_ascii_whitespace) => {}
+                _ => self.is_declarations_only = false,
+            }
+        }
+    }
+
+    fn collect_members(&mut self, members: &Sequence<'_, ClassLikeMember<'_>>) {
+        for member in members.iter() {
+            match member {
+                ClassLikeMember::Method(method) => self.methods.push(method.span()),
+                ClassLikeMember::Property(Property::Plain(property)) => {
+                    self.properties.push(PlainPropertyDeclaration {
+                        span: property.span(),
+                        items: property.items.iter().map(|item| (item.variable().span, item.span())).collect(),
+                    });
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// The range to delete for the property whose variable has the given span.
+    ///
+    /// A property declared alone takes its whole declaration with it; one declared alongside
+    /// others (`private $a, $b;`) only takes its own item and the adjacent comma.
+    fn property_range(&self, contents: &[u8], variable_span: Span) -> Option<(u32, u32)> {
+        self.properties.iter().find_map(|property| {
+            let index = property.items.iter().position(|(variable, _)| *variable == variable_span)?;
+            if property.items.len() == 1 {
+                return Some(declaration_range(contents, property.span));
+            }
+
+            let (_, item) = property.items[index];
+            Some(match property.items.get(index + 1) {
+                Some((_, next)) => (item.start.offset, next.start.offset),
+                None => (property.items[index - 1].1.end.offset, item.end.offset),
+            })
+        })
+    }
+}
+
+/// Extends a declaration's span to the range that should be deleted with it.
+///
+/// The range covers the docblock directly above the declaration, the indentation before it,
+/// the line break after it, and one blank line separating it from its neighbours.
+fn declaration_range(contents: &[u8], span: Span) -> (u32, u32) {
+    let is_horizontal_whitespace = |byte: &u8| matches!(byte, b' ' | b'\t' | b'\r');
+
+    let mut start = span.start.offset as usize;
+    let mut end = span.end.offset as usize;
+
+    let before = contents[..start].trim_ascii_end();
+    if before.ends_with(b"*/")
+        && let Some(docblock_start) = memchr::memmem::rfind(before, b"/*")
+        && before.get(docblock_start + 2) == Some(&b'*')
+    {
+        start = docblock_start;
+    }
+
+    let line_start = contents[..start].iter().rposition(|byte| *byte == b'\n').map_or(0, |index| index + 1);
+    if !contents[line_start..start].iter().all(is_horizontal_whitespace) {
+        return (start as u32, end as u32);
+    }
+
+    start = line_start;
+    end += contents[end..].iter().take_while(|byte| is_horizontal_whitespace(byte)).count();
+    if contents.get(end) == Some(&b'\n') {
+        end += 1;
+    }
+
+    let next_line_end = end + contents[end..].iter().take_while(|byte| is_horizontal_whitespace(byte)).count();
+    if contents.get(next_line_end) == Some(&b'\n') {
+        end = next_line_end + 1;
+    } else if start > 0 {
+        let previous_line_start =
+            contents[..start - 1].iter().rposition(|byte| *byte == b'\n').map_or(0, |index| index + 1);
+        if previous_line_start > 0 && contents[previous_line_start..start - 1].iter().all(is_horizontal_whitespace) {
+            start = previous_line_start;
+        }
+    }
+
+    (start as u32, end as u32)
+}
@@ -0,0 +1,467 @@
+//! Composer dependency checks.
+//!
+//! Once the whole codebase has been analyzed, every symbol referenced from the project's own
+//! code can be traced back to the Composer package declaring it. This module compares the
+//! packages actually used with those listed in `composer.json`, and reports:
+//!
+//! - Packages used without being required, typically installed only because another package
+//!   depends on them (`undeclared-dependency`)
+//! - Packages listed in `require` that nothing references (`unused-dependency`)
+//! - Packages listed in `require-dev` that production code references (`dev-dependency-in-production`)
+//!
+//! A symbol belongs to a package when it is declared in a file under
+//! `<vendor-dir>/<vendor>/<package>/`, so the vendor directory must be part of the scanned
+//! sources. Code under the paths listed in `autoload-dev` is development code; all other code
+//! is production code.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use foldhash::HashMap;
+use foldhash::HashSet;
+
+use mago_codex::metadata::CodebaseMetadata;
+use mago_codex::reference::ReferenceOrigin;
+use mago_codex::reference::SymbolReferences;
+use mago_codex::symbol::SymbolIdentifier;
+use mago_composer::ComposerPackage;
+use mago_composer::ComposerPackageAutoloadDevPsr0value;
+use mago_composer::ComposerPackageAutoloadDevPsr4value;
+use mago_database::DatabaseReader;
+use mago_database::file::FileId;
+use mago_database::file::FileType;
+use mago_reporting::Annotation;
+use mago_reporting::Issue;
+use mago_reporting::IssueCollection;
+use mago_span::Position;
+use mago_span::Span;
+
+use crate::code::IssueCode;
+
+/// The vendor directory Composer installs packages into, unless configured otherwise.
+const DEFAULT_VENDOR_DIR: &str = "vendor";
+
+/// The packages a `composer.json` file requires, and where its development code lives.
+#[derive(Debug, Clone)]
+struct Manifest {
+    vendor_dir: String,
+    require: HashSet<String>,
+    require_dev: HashSet<String>,
+    dev_paths: Vec<String>,
+}
+
+/// The symbols a file uses from a package, grouped by the declaration using them.
+///
+/// References from top-level code are grouped under the start of the file.
+#[derive(Debug, Clone, Default)]
+struct Usage {
+    declarations: BTreeMap<(u32, u32), BTreeSet<String>>,
+    from_production: bool,
+}
+
+/// Checks the packages used by the codebase against those required by `composer`.
+///
+/// `symbol_references` must come from analyzing the whole codebase: a reference from a
+/// file left out of the analysis cannot be seen, and would make a used package look unused.
+/// Packages that declare no symbols in the codebase, such as Composer plugins or packages
+/// outside the scanned sources, are never reported as unused.
+#[must_use]
+pub fn check_dependencies<D>(
+    database: &D,
+    codebase: &CodebaseMetadata,
+    symbol_references: &SymbolReferences,
+    composer: &ComposerPackage,
+) -> IssueCollection
+where
+    D: DatabaseReader,
+{
+    let manifest = Manifest::new(composer);
+
+    let mut packages = HashMap::<FileId, Option<String>>::default();
+    let mut package_of = |file_id: FileId| -> Option<String> {
+        packages
+            .entry(file_id)
+            .or_insert_with(|| {
+                let file = database.get_ref(&file_id).ok()?;
+                if file.file_type == FileType::Host {
+                    return None;
+                }
+
+                manifest.package_name(&file.name)
+            })
+            .clone()
+    };
+
+    let mut declaring_packages = HashSet::<String>::default();
+    let declaration_files = codebase
+        .class_likes
+        .values()
+        .map(|metadata| metadata.span.file_id)
+        .chain(
+            codebase
+                .function_likes
+                .iter()
+                .filter(|((scope, _), _)| scope.is_empty())
+                .map(|(_, metadata)| metadata.span.file_id),
+        )
+        .chain(codebase.constants.values().map(|metadata| metadata.span.file_id))
+        .collect::<HashSet<_>>();
+
+    for file_id in declaration_files {
+        if let Some(package) = package_of(file_id) {
+            declaring_packages.insert(package);
+        }
+    }
+
+    // Usages are grouped by package, then by the file they come from.
+    let mut usages = BTreeMap::<String, BTreeMap<FileId, Usage>>::new();
+    symbol_references.for_each_reference(|source, target, _| {
+        let Some(source_span) = origin_span(database, codebase, source) else {
+            return;
+        };
+
+        let Ok(source_file) = database.get_ref(&source_span.file_id) else {
+            return;
+        };
+
+        if source_file.file_type != FileType::Host {
+            return;
+        }
+
+        let Some(target_span) = codebase.span_of(target.0.as_bytes()) else {
+            return;
+        };
+
+        let Some(package) = package_of(target_span.file_id) else {
+            return;
+        };
+
+        let usage = usages.entry(package).or_default().entry(source_span.file_id).or_insert_with(|| Usage {
+            declarations: BTreeMap::new(),
+            from_production: !manifest.is_dev_path(&source_file.name),
+        });
+
+        usage
+            .declarations
+            .entry((source_span.start.offset, source_span.end.offset))
+            .or_default()
+            .insert(symbol_name(codebase, target));
+    });
+
+    let mut issues = IssueCollection::new();
+    for (package, package_usages) in &usages {
+        if manifest.require.contains(package) {
+            continue;
+        }
+
+        let is_dev_dependency = manifest.require_dev.contains(package);
+        for (file_id, usage) in package_usages {
+            if is_dev_dependency && !usage.from_production {
+                continue;
+            }
+
+            // One issue per file: the first declaration using the package is the primary
+            // annotation, and the others are secondary ones.
+            let annotations = usage.declarations.iter().enumerate().map(|(i, ((start, end), symbols))| {
+                let span = Span::new(*file_id, Position::new(*start), Position::new(*end));
+                let symbols = symbols.iter().map(|symbol| format!("`{symbol}`")).collect::<Vec<_>>().join(", ");
+                let annotation = if i == 0 { Annotation::primary(span) } else { Annotation::secondary(span) };
+
+                annotation.with_message(format!("Uses {symbols} from `{package}`."))
+            });
+
+            let issue = if is_dev_dependency {
+                Issue::error(format!(
+                    "Package `{package}` is only required for development, but is used in production code."
+                ))
+                .with_code(IssueCode::DevDependencyInProduction)
+                .with_note("Packages in `require-dev` are not installed by `composer install --no-dev`.")
+                .with_help(format!("Move `{package}` from `require-dev` to `require` in `composer.json`."))
+            } else {
+                Issue::error(format!("Package `{package}` is used, but not required in `composer.json`."))
+                    .with_code(IssueCode::UndeclaredDependency)
+                    .with_note(
+                        "The package is only installed because another package depends on it, and may disappear when that package is updated.",
+                    )
+                    .with_help(format!(
+                        "Add `{package}` to `require`, or to `require-dev` if only development code uses it."
+                    ))
+            };
+
+            issues.push(issue.with_annotations(annotations));
+        }
+    }
+
+    let mut unused = manifest
+        .require
+        .iter()
+        .filter(|package| declaring_packages.contains(*package) && !usages.contains_key(*package))
+        .collect::<Vec<_>>();
+    unused.sort();
+
+    for package in unused {
+        issues.push(
+            Issue::warning(format!("Package `{package}` is required, but never used."))
+                .with_code(IssueCode::UnusedDependency)
+                .with_note("No symbol declared by this package is referenced from the analyzed code.")
+                .with_help(format!(
+                    "Remove `{package}` from `require`, or ignore this issue if the package is used in another way, such as a Composer plugin."
+                )),
+        );
+    }
+
+    issues
+}
+
+impl Manifest {
+    fn new(composer: &ComposerPackage) -> Self {
+        let vendor_dir = composer
+            .config
+            .as_ref()
+            .and_then(|config| config.vendor_dir.as_deref())
+            .map(normalize_path)
+            .filter(|vendor_dir| !vendor_dir.is_empty())
+            .unwrap_or_else(|| DEFAULT_VENDOR_DIR.to_string());
+
+        let mut dev_paths = vec![];
+        if let Some(autoload_dev) = &composer.autoload_dev {
+            for value in autoload_dev.psr_4.values() {
+                match value {
+                    ComposerPackageAutoloadDevPsr4value::Array(paths) => dev_paths.extend(paths.iter().cloned()),
+                    ComposerPackageAutoloadDevPsr4value::String(path) => dev_paths.push(path.clone()),
+                }
+            }
+
+            for value in autoload_dev.psr_0.values() {
+                match value {
+                    ComposerPackageAutoloadDevPsr0value::Array(paths) => dev_paths.extend(paths.iter().cloned()),
+                    ComposerPackageAutoloadDevPsr0value::String(path) => dev_paths.push(path.clone()),
+                }
+            }
+
+            dev_paths.extend(autoload_dev.classmap.iter().cloned());
+            dev_paths.extend(autoload_dev.files.iter().cloned());
+        }
+
+        // An empty path maps a namespace to the project root, which says nothing about where
+        // development code lives.
+        let dev_paths = dev_paths.iter().map(|path| normalize_path(path)).filter(|path| !path.is_empty()).collect();
+
+        Self {
+            vendor_dir,
+            require: composer
+                .require
+                .keys()
+                .filter(|name| is_package(name))
+                .map(|name| name.to_ascii_lowercase())
+                .collect(),
+            require_dev: composer
+                .require_dev
+                .keys()
+                .filter(|name| is_package(name))
+                .map(|name| name.to_ascii_lowercase())
+                .collect(),
+            dev_paths,
+        }
+    }
+
+    /// Returns the name of the package a file belongs to, if it is under the vendor directory.
+    fn package_name(&self, file_name: &[u8]) -> Option<String> {
+        let relative = file_name.strip_prefix(self.vendor_dir.as_bytes())?.strip_prefix(b"/")?;
+
+        let mut segments = relative.split(|byte| *byte == b'/');
+        let vendor = segments.next().filter(|segment| !segment.is_empty())?;
+        let package = segments.next().filter(|segment| !segment.is_empty())?;
+        segments.next()?;
+
+        let name = format!("{}/{}", String::from_utf8_lossy(vendor), String::from_utf8_lossy(package));
+
+        Some(name.to_ascii_lowercase())
+    }
+
+    /// Returns `true` if a file is under one of the `autoload-dev` paths.
+    fn is_dev_path(&self, file_name: &[u8]) -> bool {
+        self.dev_paths.iter().any(|path| {
+            file_name.strip_prefix(path.as_bytes()).is_some_and(|rest| rest.is_empty() || rest.starts_with(b"/"))
+        })
+    }
+}
+
+/// Returns `true` for actual packages, as opposed to platform requirements such as `php` or `ext-json`.
+fn is_package(name: &str) -> bool {
+    name.contains('/')
+}
+
+fn normalize_path(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let path = path.trim_start_matches("./").trim_end_matches('/');
+
+    path.to_string()
+}
+
+/// Returns the span identifying where a reference comes from: the name of the referencing
+/// declaration, or the start of the file for references from top-level code.
+fn origin_span<D>(database: &D, codebase: &CodebaseMetadata, origin: ReferenceOrigin) -> Option<Span>
+where
+    D: DatabaseReader,
+{
+    match origin {
+        ReferenceOrigin::Symbol((symbol, member)) => {
+            if !member.is_empty()
+                && let Some(method) = codebase.get_method(symbol.as_bytes(), member.as_bytes())
+            {
+                return Some(method.name_span.unwrap_or(method.span));
+            }
+
+            codebase.span_of(symbol.as_bytes())
+        }
+        ReferenceOrigin::File(name) => {
+            let file_id = database.get_id(name.as_bytes())?;
+
+            Some(Span::new(file_id, Position::zero(), Position::zero()))
+        }
+    }
+}
+
+/// Returns the name of a referenced symbol as it was declared.
+fn symbol_name(codebase: &CodebaseMetadata, (symbol, member): SymbolIdentifier) -> String {
+    let name = if let Some(class_like) = codebase.get_class_like(symbol.as_bytes()) {
+        class_like.original_name
+    } else if let Some(function) = codebase.get_function(symbol.as_bytes()) {
+        function.original_name
+    } else if let Some(constant) = codebase.get_constant(symbol.as_bytes()) {
+        constant.name
+    } else {
+        symbol
+    };
+
+    if member.is_empty() { name.to_string() } else { format!("{name}::{member}") }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::borrow::Cow;
+    use std::path::Path;
+    use std::str::FromStr;
+
+    use foldhash::HashSet;
+
+    use mago_allocator::LocalArena;
+    use mago_codex::metadata::CodebaseMetadata;
+    use mago_codex::populator::populate_codebase;
+    use mago_codex::reference::SymbolReferences;
+    use mago_codex::scanner::scan_program;
+    use mago_composer::ComposerPackage;
+    use mago_database::Database;
+    use mago_database::DatabaseConfiguration;
+    use mago_database::DatabaseReader;
+    use mago_database::file::File;
+    use mago_database::file::FileType;
+    use mago_names::resolver::NameResolver;
+    use mago_syntax::parser::parse_file;
+    use mago_word::WordSet;
+
+    use crate::Analyzer;
+    use crate::analysis_result::AnalysisResult;
+    use crate::plugin::PluginRegistry;
+    use crate::settings::Settings;
+
+    use super::check_dependencies;
+
+    const COMPOSER_JSON: &str = r#"{
+        "require": {
+            "php": "^8.2",
+            "acme/http": "^1.0",
+            "acme/unused": "^1.0"
+        },
+        "require-dev": {
+            "acme/testing": "^1.0"
+        },
+        "autoload-dev": {
+            "psr-4": { "App\\Tests\\": "tests/" }
+        }
+    }"#;
+
+    const FILES: &[(&str, FileType, &str)] = &[
+        ("vendor/acme/http/src/Client.php", FileType::Vendored, "<?php namespace Acme\\Http; class Client {}"),
+        ("vendor/acme/unused/src/Thing.php", FileType::Vendored, "<?php namespace Acme\\Unused; class Thing {}"),
+        ("vendor/acme/psr/src/Message.php", FileType::Vendored, "<?php namespace Acme\\Psr; interface Message {}"),
+        (
+            "vendor/acme/testing/src/TestCase.php",
+            FileType::Vendored,
+            "<?php namespace Acme\\Testing; class TestCase {}",
+        ),
+        (
+            "src/Service.php",
+            FileType::Host,
+            "<?php namespace App; final class Service { public function send(\\Acme\\Psr\\Message $message): \\Acme\\Http\\Client { return new \\Acme\\Http\\Client(); } }",
+        ),
+        (
+            "src/Fixture.php",
+            FileType::Host,
+            "<?php namespace App; final class Fixture extends \\Acme\\Testing\\TestCase {}",
+        ),
+        (
+            "tests/ServiceTest.php",
+            FileType::Host,
+            "<?php namespace App\\Tests; final class ServiceTest extends \\Acme\\Testing\\TestCase {}",
+        ),
+    ];
+
+    #[test]
+    fn reports_undeclared_unused_and_dev_dependencies() {
+        let mut database = Database::new(DatabaseConfiguration::new(Path::new("."), vec![], vec![], vec![], vec![]));
+        for (name, file_type, contents) in FILES {
+            database.add(File::new(
+                Cow::Borrowed(name.as_bytes()),
+                *file_type,
+                None,
+                Cow::Borrowed(contents.as_bytes()),
+            ));
+        }
+
+        let arena = LocalArena::new();
+        let settings = Settings::default();
+        let files = database.files().collect::<Vec<_>>();
+        let mut codebase = CodebaseMetadata::new();
+        let mut programs = vec![];
+        for file in &files {
+            let program = parse_file(&arena, file);
+            let resolved_names = NameResolver::new(&arena).resolve(program);
+
+            codebase.extend(scan_program(&arena, file, program, &resolved_names, settings.version));
+            programs.push((file, program, resolved_names));
+        }
+
+        let mut symbol_references = SymbolReferences::new();
+        populate_codebase(&mut codebase, &mut symbol_references, WordSet::default(), HashSet::default());
+
+        let plugin_registry = PluginRegistry::with_library_providers();
+        let mut analysis_result = AnalysisResult::new(symbol_references);
+        for (file, program, resolved_names) in programs {
+            if file.file_type != FileType::Host {
+                continue;
+            }
+
+            Analyzer::new(&arena, file, &resolved_names, &codebase, &plugin_registry, settings.clone())
+                .analyze(program, &mut analysis_result)
+                .unwrap();
+        }
+
+        let composer = ComposerPackage::from_str(COMPOSER_JSON).unwrap();
+        let issues = check_dependencies(&database, &codebase, &analysis_result.symbol_references, &composer);
+
+        let mut messages = issues.iter().map(|issue| issue.message.clone()).collect::<Vec<_>>();
+        messages.sort();
+
+        assert_eq!(
+            messages,
+            vec![
+                "Package `acme/psr` is used, but not required in `composer.json`.",
+                "Package `acme/testing` is only required for development, but is used in production code.",
+                "Package `acme/unused` is required, but never used.",
+            ]
+        );
+    }
+}
@@ -5,6 +5,7 @@ use mago_codex::ttype::add_optional_union_type;
 use mago_codex::ttype::get_mixed;
 use mago_codex::ttype::get_never;
 use mago_codex::ttype::get_null;
+use mago_span::HasSpan;
 use mago_syntax::cst::Expression;
 use mago_syntax::cst::StaticPropertyAccess;
 use mago_syntax::cst::Variable;
@@ -28,6 +29,8 @@ impl<'ast, 'arena> Analyzable<'ast, 'arena> for StaticPropertyAccess<'arena> {
     where
         A: Arena,
     {
+        crate::purity::check_static_property_read(context, block_context, self.span());
+
         let property_access_id = get_static_property_access_expression_id(
             self.class,
             &self.property,
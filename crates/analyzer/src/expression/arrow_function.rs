@@ -104,6 +104,7 @@ impl<'ast, 'arena> Analyzable<'ast, 'arena> for ArrowFunction<'arena> {
                     block_context.scope.get_class_like(),
                     function_metadata,
                     parameter,
+                    None,
                 );
             }
 
@@ -113,7 +114,10 @@ impl<'ast, 'arena> Analyzable<'ast, 'arena> for ArrowFunction<'arena> {
                 function_metadata,
                 b"arrow function",
                 self.return_type_hint.as_ref(),
+                None,
+                self.parameter_list.span().end_offset(),
                 self.span(),
+                None,
             );
         }
 
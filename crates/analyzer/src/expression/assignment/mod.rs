@@ -50,6 +50,7 @@ use crate::context::scope::var_references_dynamic;
 use crate::error::AnalysisError;
 use crate::expression::find_expression_logic_issues;
 use crate::formula::get_formula;
+use crate::taint;
 use crate::utils::docblock::check_docblock_type_incompatibility;
 use crate::utils::docblock::get_type_from_var_docblock;
 use crate::utils::expression::array::get_array_target_type_given_index;
@@ -88,7 +89,11 @@ impl<'ast, 'arena> Analyzable<'ast, 'arena> for Assignment<'arena> {
             Some(&self.operator),
             Some(self.rhs),
             None,
-        )
+        )?;
+
+        taint::analyze_assignment(context, block_context, artifacts, self);
+
+        Ok(())
     }
 }
 
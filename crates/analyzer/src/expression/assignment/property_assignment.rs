@@ -66,6 +66,8 @@ where
     )?;
     block_context.flags.set_inside_assignment(was_inside_assignment);
 
+    crate::purity::check_property_write(context, block_context, property_access.object, property_access.span());
+
     let mut resolved_property_type = None;
     let mut readable_type: Option<TUnion> = None;
     let mut has_read_clamp = false;
@@ -38,6 +38,8 @@ where
     let property_resolution =
         resolve_static_properties(context, block_context, artifacts, property_access.class, &property_access.property)?;
 
+    crate::purity::check_static_property_write(context, block_context, property_access.span());
+
     let mut resolved_property_type = None;
     let mut matched_all_properties = true;
     let mut widened_assigned_type: Option<TUnion> = None;
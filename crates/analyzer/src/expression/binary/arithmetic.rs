@@ -212,6 +212,7 @@ where
     let mut invalid_right_messages: Vec<(String, Span)> = Vec::new();
     let mut has_valid_left_operand = false;
     let mut has_valid_right_operand = false;
+    let mut has_integer_overflow = false;
 
     let left_atomic_types = left_type
         .types
@@ -354,6 +355,12 @@ where
                 has_valid_left_operand = true;
                 has_valid_right_operand = true;
             } else if left_atomic.is_numeric() && right_atomic.is_numeric() {
+                // Modulo by zero throws; the divisor is reported once all pairs are analyzed.
+                if matches!(binary.operator, BinaryOperator::Modulo(_)) && is_zero(&right_atomic) {
+                    result_atomic_types.push(TAtomic::Never);
+                    continue;
+                }
+
                 if let Some(reason) = definite_arithmetic_runtime_error(&binary.operator, &right_atomic) {
                     invalid_right_messages.push((reason, binary.rhs.span()));
                     pair_result_atomics.push(TAtomic::Never);
@@ -361,25 +368,27 @@ where
                     continue;
                 }
 
+                if let Some(reason) = possible_arithmetic_runtime_error(&binary.operator, &right_atomic) {
+                    invalid_right_messages.push((reason, binary.rhs.span()));
+                }
+
+                let overflow = get_integer_overflow(&binary.operator, &left_atomic, &right_atomic);
+                has_integer_overflow |= overflow == IntegerOverflow::Definite;
+
                 let numeric_results = determine_numeric_result(
                     &binary.operator,
                     &left_atomic,
                     &right_atomic,
                     block_context.flags.inside_loop(),
+                    overflow,
                 );
 
                 if numeric_results.iter().any(|a| matches!(a, TAtomic::Never)) {
                     invalid_pair = true;
-                    if matches!(binary.operator, BinaryOperator::Division(_) | BinaryOperator::Modulo(_)) {
-                        let right_is_zero = matches!(right_atomic.get_literal_int_value(), Some(0))
-                            || matches!(right_atomic.get_literal_float_value(), Some(0.0));
-
-                        if right_is_zero {
-                            invalid_right_messages.push(("Division or modulo by zero".to_string(), binary.rhs.span()));
-                            pair_result_atomics.push(TAtomic::Never);
-                        } else {
-                            pair_result_atomics.extend(numeric_results);
-                        }
+                    if matches!(binary.operator, BinaryOperator::Division(_) | BinaryOperator::Modulo(_))
+                        && is_zero(&right_atomic)
+                    {
+                        pair_result_atomics.push(TAtomic::Never);
                     } else {
                         pair_result_atomics.extend(numeric_results);
                     }
@@ -490,6 +499,22 @@ where
         );
     }
 
+    if matches!(binary.operator, BinaryOperator::Division(_) | BinaryOperator::Modulo(_)) {
+        report_zero_divisor(context, binary, &right_type);
+    }
+
+    if has_integer_overflow {
+        context.collector.report_with_code(
+            IssueCode::IntegerOverflow,
+            Issue::warning("Integer overflow: the result of this operation does not fit in an `int`.")
+                .with_annotation(
+                    Annotation::primary(binary.span()).with_message("This result is outside of the `int` range."),
+                )
+                .with_note("PHP silently converts integers beyond `PHP_INT_MIN` and `PHP_INT_MAX` to `float`, losing precision.")
+                .with_help("Use `float` arithmetic explicitly, or an arbitrary precision extension such as `bcmath` or `gmp`."),
+        );
+    }
+
     let final_type =
         infer_subtraction_type_from_active_relation(context, block_context, binary, &left_type, &right_type)
             .unwrap_or_else(|| {
@@ -602,7 +627,13 @@ where
     None
 }
 
-fn determine_numeric_result(op: &BinaryOperator<'_>, left: &TAtomic, right: &TAtomic, in_loop: bool) -> Vec<TAtomic> {
+fn determine_numeric_result(
+    op: &BinaryOperator<'_>,
+    left: &TAtomic,
+    right: &TAtomic,
+    in_loop: bool,
+    overflow: IntegerOverflow,
+) -> Vec<TAtomic> {
     if in_loop
         && (matches!(left, TAtomic::Scalar(TScalar::Integer(i)) if i.is_unspecified())
             || matches!(right, TAtomic::Scalar(TScalar::Integer(i)) if i.is_unspecified()))
@@ -644,9 +675,13 @@ fn determine_numeric_result(op: &BinaryOperator<'_>, left: &TAtomic, right: &TAt
 
     match (left, right) {
         (TAtomic::Scalar(TScalar::Integer(left_int)), TAtomic::Scalar(TScalar::Integer(right_int))) => {
+            if overflow == IntegerOverflow::Definite {
+                return vec![get_overflowed_float(op, *left_int, *right_int)];
+            }
+
             let result = calculate_int_arithmetic(op, *left_int, *right_int);
 
-            match result {
+            let mut results = match result {
                 Some(integer) => {
                     vec![TAtomic::Scalar(TScalar::Integer(integer))]
                 }
@@ -655,13 +690,21 @@ fn determine_numeric_result(op: &BinaryOperator<'_>, left: &TAtomic, right: &TAt
                         if right_int.is_zero() {
                             vec![TAtomic::Never]
                         } else {
-                            vec![TAtomic::Scalar(TScalar::int()), TAtomic::Scalar(TScalar::float())]
+                            let quotient = calculate_int_division_range(*left_int, *right_int);
+
+                            vec![TAtomic::Scalar(TScalar::Integer(quotient)), TAtomic::Scalar(TScalar::float())]
                         }
                     } else {
                         vec![TAtomic::Scalar(TScalar::int())]
                     }
                 }
+            };
+
+            if overflow == IntegerOverflow::Possible {
+                results.push(TAtomic::Scalar(TScalar::float()));
             }
+
+            results
         }
         (TAtomic::Scalar(TScalar::Float(_)), _) | (_, TAtomic::Scalar(TScalar::Float(_))) => match op {
             BinaryOperator::Modulo(_) => {
@@ -803,7 +846,13 @@ fn calculate_int_arithmetic(op: &BinaryOperator<'_>, left: TInteger, right: TInt
                     }
                 }
             }
-            _ => Unspecified,
+            _ => match get_power_bounds(left, right) {
+                Some((minimum, maximum)) => match (i64::try_from(minimum), i64::try_from(maximum)) {
+                    (Ok(minimum), Ok(maximum)) => TInteger::from_bounds(Some(minimum), Some(maximum)),
+                    _ => Unspecified,
+                },
+                None => Unspecified,
+            },
         },
         _ => return None,
     };
@@ -811,6 +860,121 @@ fn calculate_int_arithmetic(op: &BinaryOperator<'_>, left: TInteger, right: TInt
     if result.is_unspecified() { None } else { Some(result) }
 }
 
+/// Whether an integer operation leaves the `int` range, in which case PHP returns a `float`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum IntegerOverflow {
+    Never,
+    Possible,
+    Definite,
+}
+
+/// Determines whether `left op right` overflows for all, some, or none of the values of two
+/// integer operands, by evaluating the operation on their bounds without clamping.
+fn get_integer_overflow(op: &BinaryOperator<'_>, left: &TAtomic, right: &TAtomic) -> IntegerOverflow {
+    let (TAtomic::Scalar(TScalar::Integer(left)), TAtomic::Scalar(TScalar::Integer(right))) = (left, right) else {
+        return IntegerOverflow::Never;
+    };
+
+    let left_minimum = left.get_minimum_value().map(i128::from);
+    let left_maximum = left.get_maximum_value().map(i128::from);
+    let right_minimum = right.get_minimum_value().map(i128::from);
+    let right_maximum = right.get_maximum_value().map(i128::from);
+
+    let (minimum, maximum) = match op {
+        BinaryOperator::Addition(_) => {
+            (left_minimum.zip(right_minimum).map(|(l, r)| l + r), left_maximum.zip(right_maximum).map(|(l, r)| l + r))
+        }
+        BinaryOperator::Subtraction(_) => {
+            (left_minimum.zip(right_maximum).map(|(l, r)| l - r), left_maximum.zip(right_minimum).map(|(l, r)| l - r))
+        }
+        BinaryOperator::Multiplication(_) => {
+            let (Some(left_minimum), Some(left_maximum), Some(right_minimum), Some(right_maximum)) =
+                (left_minimum, left_maximum, right_minimum, right_maximum)
+            else {
+                return IntegerOverflow::Never;
+            };
+
+            let products = [
+                left_minimum * right_minimum,
+                left_minimum * right_maximum,
+                left_maximum * right_minimum,
+                left_maximum * right_maximum,
+            ];
+
+            (products.iter().min().copied(), products.iter().max().copied())
+        }
+        BinaryOperator::Exponentiation(_) => {
+            let Some((minimum, maximum)) = get_power_bounds(*left, *right) else {
+                return IntegerOverflow::Never;
+            };
+
+            (Some(minimum), Some(maximum))
+        }
+        _ => return IntegerOverflow::Never,
+    };
+
+    let int_minimum = i128::from(i64::MIN);
+    let int_maximum = i128::from(i64::MAX);
+
+    if minimum.is_some_and(|minimum| minimum > int_maximum) || maximum.is_some_and(|maximum| maximum < int_minimum) {
+        IntegerOverflow::Definite
+    } else if maximum.is_some_and(|maximum| maximum > int_maximum)
+        || minimum.is_some_and(|minimum| minimum < int_minimum)
+    {
+        IntegerOverflow::Possible
+    } else {
+        IntegerOverflow::Never
+    }
+}
+
+/// Computes the bounds of `base ** exponent` for a bounded base and a non-negative literal exponent.
+fn get_power_bounds(base: TInteger, exponent: TInteger) -> Option<(i128, i128)> {
+    let minimum = i128::from(base.get_minimum_value()?);
+    let maximum = i128::from(base.get_maximum_value()?);
+    let exponent = u32::try_from(exponent.get_literal_value()?).ok()?;
+
+    let lower = minimum.saturating_pow(exponent);
+    let upper = maximum.saturating_pow(exponent);
+
+    // An even power of a range spanning zero reaches its minimum at zero.
+    if exponent.is_multiple_of(2) && minimum < 0 && maximum > 0 {
+        Some((0, lower.max(upper)))
+    } else {
+        Some((lower.min(upper), lower.max(upper)))
+    }
+}
+
+/// Computes the `float` an overflowing integer operation evaluates to.
+fn get_overflowed_float(op: &BinaryOperator<'_>, left: TInteger, right: TInteger) -> TAtomic {
+    let (Some(left), Some(right)) = (left.get_literal_value(), right.get_literal_value()) else {
+        return TAtomic::Scalar(TScalar::float());
+    };
+
+    let (left, right) = (left as f64, right as f64);
+    let value = match op {
+        BinaryOperator::Addition(_) => left + right,
+        BinaryOperator::Subtraction(_) => left - right,
+        BinaryOperator::Multiplication(_) => left * right,
+        BinaryOperator::Exponentiation(_) => left.powf(right),
+        _ => return TAtomic::Scalar(TScalar::float()),
+    };
+
+    if value.is_finite() { TAtomic::Scalar(TScalar::literal_float(value)) } else { TAtomic::Scalar(TScalar::float()) }
+}
+
+/// Computes the range of the integer results of `left / right`.
+///
+/// Division only yields an `int` when it is exact, in which case the result is the truncated
+/// quotient, so the truncated quotient of the bounds covers every integer result.
+fn calculate_int_division_range(left: TInteger, right: TInteger) -> TInteger {
+    // `int<min, ...> / -1` overflows, so the bounds of such a division cannot be computed.
+    if right.can_be_zero() || left.get_minimum_value() == Some(i64::MIN) || (left.is_literal() && right.is_literal()) {
+        return TInteger::Unspecified;
+    }
+
+    left / right
+}
+
 /// Compose two array shapes under PHP's `+` operator.
 fn compose_array_plus<A>(left: &TArray, right: &TArray, context: &Context<'_, '_, A>) -> Vec<TAtomic>
 where
@@ -948,17 +1112,104 @@ fn key_could_be_in_param(key: &ArrayKey, param: &TUnion) -> bool {
 /// [`determine_numeric_result`].
 fn definite_arithmetic_runtime_error(op: &BinaryOperator<'_>, right: &TAtomic) -> Option<String> {
     match op {
-        BinaryOperator::Modulo(_) => {
-            let zero = matches!(right.get_literal_int_value(), Some(0))
-                || matches!(right.get_literal_float_value(), Some(0.0));
+        BinaryOperator::LeftShift(_) | BinaryOperator::RightShift(_) => {
+            let TAtomic::Scalar(TScalar::Integer(shift)) = right else {
+                return None;
+            };
+
+            if shift.get_maximum_value()? >= 0 {
+                return None;
+            }
 
-            if zero { Some("Modulo by zero".to_string()) } else { None }
+            Some(match shift.get_literal_value() {
+                Some(value) => format!("Bit shift by a negative number (`{value}`)"),
+                None => format!("Bit shift by a negative number (`{}`)", right.get_id()),
+            })
         }
+        _ => None,
+    }
+}
+
+/// Detect arithmetic operations that throw at runtime for some, but not all, values
+/// of a bounded right operand.
+fn possible_arithmetic_runtime_error(op: &BinaryOperator<'_>, right: &TAtomic) -> Option<String> {
+    match op {
         BinaryOperator::LeftShift(_) | BinaryOperator::RightShift(_) => {
-            let value = right.get_literal_int_value()?;
+            let TAtomic::Scalar(TScalar::Integer(shift)) = right else {
+                return None;
+            };
 
-            if value < 0 { Some(format!("Bit shift by a negative number (`{value}`)")) } else { None }
+            if shift.is_unspecified()
+                || shift.is_unspecified_literal()
+                || shift.get_minimum_value().is_some_and(|minimum| minimum >= 0)
+            {
+                return None;
+            }
+
+            Some(format!("Bit shift by a possibly negative number (`{}`)", right.get_id()))
         }
         _ => None,
     }
 }
+
+/// Returns whether `atomic` is a literal zero, which throws a `DivisionByZeroError` as a divisor.
+fn is_zero(atomic: &TAtomic) -> bool {
+    matches!(atomic.get_literal_int_value(), Some(0)) || matches!(atomic.get_literal_float_value(), Some(0.0))
+}
+
+/// Reports a `/` or `%` operation whose divisor is always, or possibly, zero.
+///
+/// Only divisors whose type explicitly admits zero (a literal `0`, a range including it, or
+/// `false`) are reported; an unbounded `int` or `float` divisor is not.
+fn report_zero_divisor<A>(context: &mut Context<'_, '_, A>, binary: &Binary<'_>, right_type: &TUnion)
+where
+    A: Arena,
+{
+    let mut has_zero = false;
+    let mut has_non_zero = false;
+    for atomic in right_type.types.iter() {
+        match atomic {
+            TAtomic::Null => {}
+            TAtomic::Scalar(TScalar::Integer(integer))
+                if !integer.is_zero() && !integer.is_unspecified() && integer.can_be_zero() =>
+            {
+                has_zero = true;
+                has_non_zero = true;
+            }
+            TAtomic::Scalar(TScalar::Bool(bool)) if !bool.is_false() && !bool.is_true() => {
+                has_zero = true;
+                has_non_zero = true;
+            }
+            TAtomic::Scalar(TScalar::Bool(bool)) if bool.is_false() => has_zero = true,
+            atomic if is_zero(atomic) => has_zero = true,
+            _ => has_non_zero = true,
+        }
+    }
+
+    if !has_zero {
+        return;
+    }
+
+    let operation = if matches!(binary.operator, BinaryOperator::Modulo(_)) { "Modulo" } else { "Division" };
+
+    if has_non_zero {
+        context.collector.report_with_code(
+            IssueCode::PossiblyDivisionByZero,
+            Issue::warning(format!("{operation} by a divisor that might be zero."))
+                .with_annotation(
+                    Annotation::primary(binary.rhs.span())
+                        .with_message(format!("This might be zero (type `{}`).", right_type.get_id())),
+                )
+                .with_note("PHP throws a `DivisionByZeroError` when the divisor is zero.")
+                .with_help("Check that the divisor is not zero before performing the operation."),
+        );
+    } else {
+        context.collector.report_with_code(
+            IssueCode::DivisionByZero,
+            Issue::error(format!("{operation} by zero."))
+                .with_annotation(Annotation::primary(binary.rhs.span()).with_message("This is always zero."))
+                .with_note("PHP throws a `DivisionByZeroError` when the divisor is zero.")
+                .with_help("Use a non-zero divisor."),
+        );
+    }
+}
@@ -38,7 +38,9 @@ use crate::invocation::MethodTargetContext;
 use crate::invocation::analyzer::analyze_invocation;
 use crate::invocation::post_process::post_invocation_process;
 use crate::invocation::return_type_fetcher::fetch_invocation_return_type;
+use crate::purity;
 use crate::reconciler::assertion_reconciler;
+use crate::taint;
 use crate::utils::names::display_function_like_identifier;
 
 pub mod function_call;
@@ -218,6 +220,9 @@ where
             &mut argument_types,
         )?;
 
+        taint::analyze_invocation(context, block_context, artifacts, &invocation);
+        purity::check_invocation(context, block_context, &invocation);
+
         let return_type = fetch_invocation_return_type(
             context,
             block_context,
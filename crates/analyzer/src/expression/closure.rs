@@ -166,6 +166,7 @@ impl<'ast, 'arena> Analyzable<'ast, 'arena> for Closure<'arena> {
                     block_context.scope.get_class_like(),
                     function_metadata,
                     parameter,
+                    None,
                 );
             }
 
@@ -175,7 +176,10 @@ impl<'ast, 'arena> Analyzable<'ast, 'arena> for Closure<'arena> {
                 function_metadata,
                 b"closure",
                 self.return_type_hint.as_ref(),
+                None,
+                self.use_clause.as_ref().map_or_else(|| self.parameter_list.span(), HasSpan::span).end_offset(),
                 self.span(),
+                None,
             );
         }
 
@@ -22,6 +22,7 @@ use crate::context::Context;
 use crate::context::block::BlockContext;
 use crate::error::AnalysisError;
 use crate::expression::unary::cast_type_to_string;
+use crate::taint;
 use crate::utils::expression::get_block_expression_id;
 
 impl<'ast, 'arena> Analyzable<'ast, 'arena> for CompositeString<'arena> {
@@ -198,6 +199,8 @@ impl<'ast, 'arena> Analyzable<'ast, 'arena> for CompositeString<'arena> {
             get_string()
         };
 
+        taint::analyze_shell_execute(context, block_context, artifacts, self);
+
         artifacts.set_expression_type(self, resulting_type);
 
         Ok(())
@@ -69,6 +69,8 @@ impl<'arena> Analyzable<'_, 'arena> for ConstantAccess<'arena> {
             return Ok(());
         };
 
+        artifacts.symbol_references.add_reference_to_symbol(&block_context.scope, constant_metadata.name, false);
+
         if constant_metadata.flags.is_deprecated() {
             context.collector.report_with_code(
                 IssueCode::DeprecatedConstant,
@@ -24,6 +24,7 @@ use crate::context::Context;
 use crate::context::block::BlockContext;
 use crate::error::AnalysisError;
 use crate::expression::assignment;
+use crate::purity;
 
 impl<'ast, 'arena> Analyzable<'ast, 'arena> for Variable<'arena> {
     fn analyze<'ctx, A>(
@@ -53,6 +54,8 @@ impl<'ast, 'arena> Analyzable<'ast, 'arena> for DirectVariable<'arena> {
     where
         A: Arena,
     {
+        purity::check_global_access(context, block_context, self.name, self.span(), false);
+
         let resulting_type = read_variable(context, block_context, artifacts, self.name, self.span());
 
         artifacts.set_rc_expression_type(self, resulting_type);
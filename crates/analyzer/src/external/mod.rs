@@ -24,6 +24,10 @@ use mago_extension::Frame;
 use mago_extension::WorkerError;
 use mago_extension::WorkerPool;
 use mago_extension::WorkerRequestHandler;
+use mago_extension::wire::analyzer::PROVIDER_CALLABLE_SIGNATURE;
+use mago_extension::wire::analyzer::PROVIDER_MEMOIZED;
+use mago_extension::wire::analyzer::PROVIDER_OVERRIDES_DECLARED_SIGNATURE;
+use mago_extension::wire::analyzer::PROVIDER_UNDECLARED_RETURN_TYPE_ONLY;
 use mago_names::ResolvedNames;
 use mago_php_version::PHPVersion;
 use mago_reporting::IssueCollection;
@@ -45,11 +49,13 @@ use crate::plugin::available_plugins;
 pub use error::ExternalAnalyzerError;
 pub use lifecycle::AFTER_FILE_ANALYSIS_BATCH_SIZE;
 pub use lifecycle::FileAnalysisSnapshot;
+pub use probe::ProtocolProbe;
 use protocol::Registration;
 
 mod error;
 mod lifecycle;
 mod metadata;
+mod probe;
 pub mod protocol;
 mod scan;
 
@@ -59,10 +65,6 @@ pub use scan::CodebaseScanPlan;
 const SLOW_PROVIDER_THRESHOLD: Duration = Duration::from_millis(5);
 const SLOW_LIFECYCLE_THRESHOLD: Duration = Duration::from_millis(5);
 const MAXIMUM_PROVIDER_RESPONSE_CACHE_ENTRIES: usize = 0x0001_0000;
-const PROVIDER_CALLABLE_SIGNATURE: u8 = 1;
-const PROVIDER_OVERRIDES_DECLARED_SIGNATURE: u8 = 1 << 1;
-const PROVIDER_UNDECLARED_RETURN_TYPE_ONLY: u8 = 1 << 2;
-const PROVIDER_MEMOIZED: u8 = 1 << 3;
 static NEXT_ANALYSIS_GENERATION: AtomicU64 = AtomicU64::new(1);
 
 type ExternalAnalyzerResult<T> = Result<T, Arc<ExternalAnalyzerError>>;
@@ -3710,4 +3712,23 @@ mod tests {
         assert!(list.non_empty);
         assert!(list.element_type.is_string());
     }
+
+    #[test]
+    fn builds_protocol_probe_payloads() {
+        let describe = protocol::encode_describe_request(PHPVersion::PHP85);
+
+        let truncated = ProtocolProbe::TruncatedRequest.payload(PHPVersion::PHP85, 1024);
+        assert!(describe.starts_with(&truncated) && truncated.len() < describe.len());
+
+        let trailing = ProtocolProbe::TrailingBytes.payload(PHPVersion::PHP85, 1024);
+        assert!(trailing.starts_with(&describe) && trailing.len() > describe.len());
+
+        let unknown = ProtocolProbe::UnknownMessage.payload(PHPVersion::PHP85, 1024);
+        assert_eq!(unknown[..4], protocol::ANALYZER_PROTOCOL_MAGIC);
+        assert_ne!(unknown[..12], describe[..12]);
+
+        let maximum = ProtocolProbe::MaximumPayload.payload(PHPVersion::PHP85, 1024);
+        assert!(maximum.starts_with(&unknown) && maximum.len() == 1024);
+        assert_eq!(ProtocolProbe::Timeout.payload(PHPVersion::PHP85, 1024), describe);
+    }
 }
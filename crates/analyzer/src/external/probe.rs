@@ -0,0 +1,69 @@
+//! Protocol edge cases for checking that analyzer workers fail cleanly.
+//!
+//! These mirror the linter probes, but carry analyzer-protocol payloads so a
+//! host is exercised through its analyzer dispatcher as well. Capability-level
+//! cases, such as an unrecognized magic, are covered by the linter probes.
+
+use mago_php_version::PHPVersion;
+
+use super::protocol;
+
+/// A malformed or extreme request sent to an analyzer worker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProtocolProbe {
+    /// An analyzer message whose kind the protocol does not define.
+    UnknownMessage,
+    /// A describe request cut off in the middle of its node-kind table.
+    TruncatedRequest,
+    /// A complete describe request followed by unexpected bytes.
+    TrailingBytes,
+    /// An analyzer message of an undefined kind, padded to the maximum frame size.
+    MaximumPayload,
+    /// A describe request whose deadline expires before the worker can answer.
+    Timeout,
+}
+
+impl ProtocolProbe {
+    /// Every probe, in the order `mago extension test` runs them.
+    pub const ALL: [Self; 5] =
+        [Self::UnknownMessage, Self::TruncatedRequest, Self::TrailingBytes, Self::MaximumPayload, Self::Timeout];
+
+    /// Returns the kebab-case name used in reports.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::UnknownMessage => "unknown-message",
+            Self::TruncatedRequest => "truncated-request",
+            Self::TrailingBytes => "trailing-bytes",
+            Self::MaximumPayload => "maximum-payload",
+            Self::Timeout => "timeout",
+        }
+    }
+
+    /// Builds the request payload for this probe.
+    ///
+    /// `maximum_payload_size` is the frame limit of the worker pool the probe
+    /// is sent to; only [`ProtocolProbe::MaximumPayload`] depends on it.
+    #[must_use]
+    pub fn payload(self, php_version: PHPVersion, maximum_payload_size: usize) -> Vec<u8> {
+        match self {
+            Self::UnknownMessage => protocol::encode_unknown_request(),
+            Self::TruncatedRequest => {
+                let mut payload = protocol::encode_describe_request(php_version);
+                payload.truncate(payload.len() / 2);
+                payload
+            }
+            Self::TrailingBytes => {
+                let mut payload = protocol::encode_describe_request(php_version);
+                payload.extend_from_slice(&[0; 8]);
+                payload
+            }
+            Self::MaximumPayload => {
+                let mut payload = protocol::encode_unknown_request();
+                payload.resize(maximum_payload_size.max(payload.len()), 0);
+                payload
+            }
+            Self::Timeout => protocol::encode_describe_request(php_version),
+        }
+    }
+}
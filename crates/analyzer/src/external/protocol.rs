@@ -86,6 +86,89 @@ use mago_database::file::File;
 use mago_extension::PayloadReader;
 use mago_extension::PayloadWriter;
 use mago_extension::source::write_node_kind_table;
+use mago_extension::wire::HEADER_LENGTH;
+use mago_extension::wire::analyzer::ASSERTION_REQUEST;
+use mago_extension::wire::analyzer::ASSERTION_RESPONSE;
+use mago_extension::wire::analyzer::CALLABLE_SIGNATURE_REQUEST;
+use mago_extension::wire::analyzer::CALLABLE_SIGNATURE_RESPONSE;
+use mago_extension::wire::analyzer::CLASS_INITIALIZER_REQUEST;
+use mago_extension::wire::analyzer::CLASS_INITIALIZER_RESPONSE;
+use mago_extension::wire::analyzer::CODEBASE_SCAN_RESPONSE;
+use mago_extension::wire::analyzer::DESCRIBE_REQUEST;
+use mago_extension::wire::analyzer::DESCRIBE_RESPONSE;
+use mago_extension::wire::analyzer::INITIALIZE_REQUEST;
+use mago_extension::wire::analyzer::INITIALIZE_RESPONSE;
+use mago_extension::wire::analyzer::INVOCATION_FUNCTION;
+use mago_extension::wire::analyzer::INVOCATION_INSTANCE_METHOD;
+use mago_extension::wire::analyzer::INVOCATION_STATIC_METHOD;
+use mago_extension::wire::analyzer::ISSUE_FILTER_REQUEST;
+use mago_extension::wire::analyzer::ISSUE_FILTER_RESPONSE;
+use mago_extension::wire::analyzer::PROPERTY_INITIALIZATION_REQUEST;
+use mago_extension::wire::analyzer::PROPERTY_INITIALIZATION_RESPONSE;
+use mago_extension::wire::analyzer::PROPERTY_TYPE_REQUEST;
+use mago_extension::wire::analyzer::PROPERTY_TYPE_RESPONSE;
+use mago_extension::wire::analyzer::PROVIDER_MEMOIZED;
+use mago_extension::wire::analyzer::RETURN_TYPE_REQUEST;
+use mago_extension::wire::analyzer::RETURN_TYPE_RESPONSE;
+use mago_extension::wire::analyzer::SNAPSHOT_ALIAS;
+use mago_extension::wire::analyzer::SNAPSHOT_ARRAY;
+use mago_extension::wire::analyzer::SNAPSHOT_CALLABLE;
+use mago_extension::wire::analyzer::SNAPSHOT_CONDITIONAL;
+use mago_extension::wire::analyzer::SNAPSHOT_DERIVED;
+use mago_extension::wire::analyzer::SNAPSHOT_GENERIC_PARAMETER;
+use mago_extension::wire::analyzer::SNAPSHOT_ITERABLE;
+use mago_extension::wire::analyzer::SNAPSHOT_MIXED;
+use mago_extension::wire::analyzer::SNAPSHOT_NEVER;
+use mago_extension::wire::analyzer::SNAPSHOT_NULL;
+use mago_extension::wire::analyzer::SNAPSHOT_OBJECT;
+use mago_extension::wire::analyzer::SNAPSHOT_PLACEHOLDER;
+use mago_extension::wire::analyzer::SNAPSHOT_REFERENCE;
+use mago_extension::wire::analyzer::SNAPSHOT_RESOURCE;
+use mago_extension::wire::analyzer::SNAPSHOT_SCALAR;
+use mago_extension::wire::analyzer::SNAPSHOT_VARIABLE;
+use mago_extension::wire::analyzer::SNAPSHOT_VOID;
+use mago_extension::wire::analyzer::TARGET_EXACT;
+use mago_extension::wire::analyzer::TARGET_NAMESPACE;
+use mago_extension::wire::analyzer::TARGET_PREFIX;
+use mago_extension::wire::analyzer::TYPE_ARRAY;
+use mago_extension::wire::analyzer::TYPE_BOOL;
+use mago_extension::wire::analyzer::TYPE_COMPARISON_BATCH_REQUEST;
+use mago_extension::wire::analyzer::TYPE_COMPARISON_BATCH_RESPONSE;
+use mago_extension::wire::analyzer::TYPE_COMPARISON_CAN_BE_IDENTICAL;
+use mago_extension::wire::analyzer::TYPE_COMPARISON_CONTAINED_BY;
+use mago_extension::wire::analyzer::TYPE_COMPARISON_EQUAL;
+use mago_extension::wire::analyzer::TYPE_COMPARISON_REQUEST;
+use mago_extension::wire::analyzer::TYPE_COMPARISON_RESPONSE;
+use mago_extension::wire::analyzer::TYPE_COMPLETE;
+use mago_extension::wire::analyzer::TYPE_FALSE;
+use mago_extension::wire::analyzer::TYPE_FLOAT;
+use mago_extension::wire::analyzer::TYPE_INT;
+use mago_extension::wire::analyzer::TYPE_LIST;
+use mago_extension::wire::analyzer::TYPE_LITERAL_INT;
+use mago_extension::wire::analyzer::TYPE_LITERAL_STRING;
+use mago_extension::wire::analyzer::TYPE_MIXED;
+use mago_extension::wire::analyzer::TYPE_NAMED_OBJECT;
+use mago_extension::wire::analyzer::TYPE_NEVER;
+use mago_extension::wire::analyzer::TYPE_NON_EMPTY_STRING;
+use mago_extension::wire::analyzer::TYPE_NON_NEGATIVE_INT;
+use mago_extension::wire::analyzer::TYPE_NULL;
+use mago_extension::wire::analyzer::TYPE_OBJECT;
+use mago_extension::wire::analyzer::TYPE_REFERENCE;
+use mago_extension::wire::analyzer::TYPE_STRING;
+use mago_extension::wire::analyzer::TYPE_TRUE;
+use mago_extension::wire::analyzer::TYPE_UNION;
+use mago_extension::wire::analyzer::TYPE_VOID;
+use mago_extension::wire::analyzer::UNION_BY_REFERENCE;
+use mago_extension::wire::analyzer::UNION_FROM_TEMPLATE_DEFAULT;
+use mago_extension::wire::analyzer::UNION_FROM_UNSPECIFIED_TEMPLATE;
+use mago_extension::wire::analyzer::UNION_HAD_TEMPLATE;
+use mago_extension::wire::analyzer::UNION_IGNORE_FALSABLE_ISSUES;
+use mago_extension::wire::analyzer::UNION_IGNORE_NULLABLE_ISSUES;
+use mago_extension::wire::analyzer::UNION_NULLSAFE_NULL;
+use mago_extension::wire::analyzer::UNION_POPULATED;
+use mago_extension::wire::analyzer::UNION_POSSIBLY_UNDEFINED;
+use mago_extension::wire::analyzer::UNION_POSSIBLY_UNDEFINED_FROM_TRY;
+use mago_extension::wire::analyzer::UNION_REFERENCE_FREE;
 use mago_php_version::PHPVersion;
 use mago_reporting::AnnotationKind;
 use mago_reporting::Issue;
@@ -129,36 +212,12 @@ use crate::invocation::Invocation;
 use crate::invocation::MethodInvocationKind;
 use crate::invocation::MethodTargetContext;
 
-pub const ANALYZER_PROTOCOL_MAGIC: [u8; 4] = *b"MANA";
-pub const ANALYZER_PROTOCOL_MAJOR: u16 = 1;
-pub const ANALYZER_PROTOCOL_MINOR: u16 = 0;
+pub use mago_extension::wire::analyzer::MAGIC as ANALYZER_PROTOCOL_MAGIC;
+pub use mago_extension::wire::analyzer::MAJOR as ANALYZER_PROTOCOL_MAJOR;
+pub use mago_extension::wire::analyzer::MINOR as ANALYZER_PROTOCOL_MINOR;
 
-const HEADER_LENGTH: usize = 12;
+const UNDEFINED_REQUEST: u16 = 0x7fff;
 const INITIAL_MESSAGE_CAPACITY: usize = 256;
-const DESCRIBE_REQUEST: u16 = 1;
-const RETURN_TYPE_REQUEST: u16 = 2;
-const TYPE_COMPARISON_REQUEST: u16 = 3;
-const INITIALIZE_REQUEST: u16 = 11;
-const CALLABLE_SIGNATURE_REQUEST: u16 = 12;
-const PROPERTY_TYPE_REQUEST: u16 = 13;
-const PROPERTY_INITIALIZATION_REQUEST: u16 = 14;
-const ISSUE_FILTER_REQUEST: u16 = 15;
-const TYPE_COMPARISON_BATCH_REQUEST: u16 = 16;
-const CLASS_INITIALIZER_REQUEST: u16 = 17;
-const ASSERTION_REQUEST: u16 = 18;
-pub(super) const CODEBASE_SCAN_REQUEST: u16 = 19;
-const DESCRIBE_RESPONSE: u16 = 0x8001;
-const RETURN_TYPE_RESPONSE: u16 = 0x8002;
-const TYPE_COMPARISON_RESPONSE: u16 = 0x8003;
-const INITIALIZE_RESPONSE: u16 = 0x800B;
-const CALLABLE_SIGNATURE_RESPONSE: u16 = 0x800C;
-const PROPERTY_TYPE_RESPONSE: u16 = 0x800D;
-const PROPERTY_INITIALIZATION_RESPONSE: u16 = 0x800E;
-const ISSUE_FILTER_RESPONSE: u16 = 0x800F;
-const TYPE_COMPARISON_BATCH_RESPONSE: u16 = 0x8010;
-const CLASS_INITIALIZER_RESPONSE: u16 = 0x8011;
-const ASSERTION_RESPONSE: u16 = 0x8012;
-const CODEBASE_SCAN_RESPONSE: u16 = 0x8013;
 const MAXIMUM_EXTENSIONS: usize = 0x4000;
 const MAXIMUM_PLUGINS: usize = 0x4000;
 const MAXIMUM_PROVIDERS: usize = 0x0001_0000;
@@ -173,57 +232,6 @@ const MAXIMUM_TYPE_MEMBERS: usize = 0x0001_0000;
 const MAXIMUM_TYPE_COMPARISONS: usize = 0x0001_0000;
 const MAXIMUM_ISSUES: usize = 1_000_000;
 
-const TARGET_EXACT: u8 = 1;
-const TARGET_PREFIX: u8 = 2;
-const TARGET_NAMESPACE: u8 = 3;
-const INVOCATION_FUNCTION: u8 = 1;
-const INVOCATION_INSTANCE_METHOD: u8 = 2;
-const INVOCATION_STATIC_METHOD: u8 = 3;
-
-const TYPE_REFERENCE: u8 = 0;
-const TYPE_MIXED: u8 = 1;
-const TYPE_NEVER: u8 = 2;
-const TYPE_NULL: u8 = 3;
-const TYPE_VOID: u8 = 4;
-const TYPE_BOOL: u8 = 5;
-const TYPE_TRUE: u8 = 6;
-const TYPE_FALSE: u8 = 7;
-const TYPE_INT: u8 = 8;
-const TYPE_FLOAT: u8 = 9;
-const TYPE_STRING: u8 = 10;
-const TYPE_LITERAL_STRING: u8 = 11;
-const TYPE_OBJECT: u8 = 12;
-const TYPE_NAMED_OBJECT: u8 = 13;
-const TYPE_ARRAY: u8 = 14;
-const TYPE_LIST: u8 = 15;
-const TYPE_UNION: u8 = 16;
-const TYPE_NON_NEGATIVE_INT: u8 = 17;
-const TYPE_NON_EMPTY_STRING: u8 = 18;
-const TYPE_LITERAL_INT: u8 = 19;
-const TYPE_COMPLETE: u8 = 20;
-
-const TYPE_COMPARISON_EQUAL: u8 = 1;
-const TYPE_COMPARISON_CONTAINED_BY: u8 = 2;
-const TYPE_COMPARISON_CAN_BE_IDENTICAL: u8 = 3;
-
-const SNAPSHOT_SCALAR: u8 = 1;
-const SNAPSHOT_CALLABLE: u8 = 2;
-const SNAPSHOT_MIXED: u8 = 3;
-const SNAPSHOT_OBJECT: u8 = 4;
-const SNAPSHOT_ARRAY: u8 = 5;
-const SNAPSHOT_ITERABLE: u8 = 6;
-const SNAPSHOT_RESOURCE: u8 = 7;
-const SNAPSHOT_REFERENCE: u8 = 8;
-const SNAPSHOT_GENERIC_PARAMETER: u8 = 9;
-const SNAPSHOT_VARIABLE: u8 = 10;
-const SNAPSHOT_CONDITIONAL: u8 = 11;
-const SNAPSHOT_DERIVED: u8 = 12;
-const SNAPSHOT_ALIAS: u8 = 13;
-const SNAPSHOT_NEVER: u8 = 14;
-const SNAPSHOT_NULL: u8 = 15;
-const SNAPSHOT_VOID: u8 = 16;
-const SNAPSHOT_PLACEHOLDER: u8 = 17;
-
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(super) struct Registration {
     pub extensions: Vec<ExternalExtension>,
@@ -298,6 +306,11 @@ pub(super) fn encode_describe_request(php_version: PHPVersion) -> Vec<u8> {
     writer.finish()
 }
 
+/// Encodes a well-formed header for a message kind no protocol version defines.
+pub(super) fn encode_unknown_request() -> Vec<u8> {
+    message_writer(UNDEFINED_REQUEST).finish()
+}
+
 pub(super) fn decode_codebase_scan_response(payload: &[u8]) -> Result<(), ExternalAnalyzerError> {
     message_reader(payload, CODEBASE_SCAN_RESPONSE)?.finish()?;
     Ok(())
@@ -706,7 +719,7 @@ fn read_memoization(
     _index: u16,
     _description: &'static str,
 ) -> Result<u8, ExternalAnalyzerError> {
-    Ok(u8::from(reader.read_bool("provider memoization flag")?) * super::PROVIDER_MEMOIZED)
+    Ok(u8::from(reader.read_bool("provider memoization flag")?) * PROVIDER_MEMOIZED)
 }
 
 fn read_targets<T>(
@@ -1392,17 +1405,24 @@ fn encode_union_snapshot_inner<'type_info>(
     let handle = types.push(union)?;
     writer.write_u32(handle);
     let mut flags = 0u16;
-    flags |= u16::from(union.had_template());
-    flags |= u16::from(union.by_reference()) << 1;
-    flags |= u16::from(union.reference_free()) << 2;
-    flags |= u16::from(union.possibly_undefined_from_try()) << 3;
-    flags |= u16::from(union.possibly_undefined()) << 4;
-    flags |= u16::from(union.ignore_nullable_issues()) << 5;
-    flags |= u16::from(union.ignore_falsable_issues()) << 6;
-    flags |= u16::from(union.from_template_default()) << 7;
-    flags |= u16::from(union.populated()) << 8;
-    flags |= u16::from(union.has_nullsafe_null()) << 9;
-    flags |= u16::from(union.from_unspecified_template()) << 10;
+    for (is_set, flag) in [
+        (union.had_template(), UNION_HAD_TEMPLATE),
+        (union.by_reference(), UNION_BY_REFERENCE),
+        (union.reference_free(), UNION_REFERENCE_FREE),
+        (union.possibly_undefined_from_try(), UNION_POSSIBLY_UNDEFINED_FROM_TRY),
+        (union.possibly_undefined(), UNION_POSSIBLY_UNDEFINED),
+        (union.ignore_nullable_issues(), UNION_IGNORE_NULLABLE_ISSUES),
+        (union.ignore_falsable_issues(), UNION_IGNORE_FALSABLE_ISSUES),
+        (union.from_template_default(), UNION_FROM_TEMPLATE_DEFAULT),
+        (union.populated(), UNION_POPULATED),
+        (union.has_nullsafe_null(), UNION_NULLSAFE_NULL),
+        (union.from_unspecified_template(), UNION_FROM_UNSPECIFIED_TEMPLATE),
+    ] {
+        if is_set {
+            flags |= flag;
+        }
+    }
+
     writer.write_u16(flags);
     write_snapshot_count(writer, union.types.len(), "union atomic types")?;
     for atomic in union.types.as_ref() {
@@ -2479,17 +2499,17 @@ pub(super) fn decode_complete_union(
     }
 
     let mut union = TUnion::from_vec(atomics);
-    union.set_had_template(flags & (1 << 0) != 0);
-    union.set_by_reference(flags & (1 << 1) != 0);
-    union.set_reference_free(flags & (1 << 2) != 0);
-    union.set_possibly_undefined_from_try(flags & (1 << 3) != 0);
-    union.set_possibly_undefined(flags & (1 << 4) != 0, None);
-    union.set_ignore_nullable_issues(flags & (1 << 5) != 0);
-    union.set_ignore_falsable_issues(flags & (1 << 6) != 0);
-    union.set_from_template_default(flags & (1 << 7) != 0);
-    union.set_populated(flags & (1 << 8) != 0);
-    union.set_nullsafe_null(flags & (1 << 9) != 0);
-    union.set_from_unspecified_template(flags & (1 << 10) != 0);
+    union.set_had_template(flags & UNION_HAD_TEMPLATE != 0);
+    union.set_by_reference(flags & UNION_BY_REFERENCE != 0);
+    union.set_reference_free(flags & UNION_REFERENCE_FREE != 0);
+    union.set_possibly_undefined_from_try(flags & UNION_POSSIBLY_UNDEFINED_FROM_TRY != 0);
+    union.set_possibly_undefined(flags & UNION_POSSIBLY_UNDEFINED != 0, None);
+    union.set_ignore_nullable_issues(flags & UNION_IGNORE_NULLABLE_ISSUES != 0);
+    union.set_ignore_falsable_issues(flags & UNION_IGNORE_FALSABLE_ISSUES != 0);
+    union.set_from_template_default(flags & UNION_FROM_TEMPLATE_DEFAULT != 0);
+    union.set_populated(flags & UNION_POPULATED != 0);
+    union.set_nullsafe_null(flags & UNION_NULLSAFE_NULL != 0);
+    union.set_from_unspecified_template(flags & UNION_FROM_UNSPECIFIED_TEMPLATE != 0);
     Ok(union)
 }
 
@@ -3146,9 +3166,128 @@ fn validate_provider_indices(
 }
 
 #[cfg(test)]
-#[allow(clippy::unwrap_used)]
+#[allow(clippy::expect_used, clippy::unwrap_used)]
 pub(super) mod testing {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use mago_allocator::LocalArena;
+    use mago_codex::ttype::get_literal_string;
+    use mago_extension::Frame;
+    use mago_extension::WorkerRequestHandler;
+    use mago_extension_sdk::Extension as SdkExtension;
+    use mago_extension_sdk::FunctionReturnTypeProvider;
+    use mago_extension_sdk::FunctionTarget;
+    use mago_extension_sdk::Plugin as SdkPlugin;
+    use mago_extension_sdk::ReturnTypeContext;
+    use mago_extension_sdk::Type as SdkType;
+    use mago_extension_sdk::TypeView;
+    use mago_extension_sdk::Worker;
+    use mago_extension_sdk::types::AtomicView;
+    use mago_extension_sdk::types::ScalarView;
+    use mago_extension_sdk::types::StringLiteral;
+    use mago_extension_sdk::types::StringView;
+    use mago_syntax::cst::Call;
+    use mago_syntax::cst::Expression;
+    use mago_syntax::cst::ExpressionStatement;
+    use mago_syntax::cst::Statement;
+    use mago_syntax::parser::parse_file;
+
     use super::*;
+    use crate::invocation::InvocationArgumentsSource;
+    use crate::invocation::InvocationTarget;
+
+    struct RecordingReturnType(Rc<RefCell<Option<TypeView>>>);
+
+    impl FunctionReturnTypeProvider for RecordingReturnType {
+        fn targets(&self) -> Vec<FunctionTarget> {
+            vec![FunctionTarget::exact("service")]
+        }
+
+        fn return_type(&mut self, context: &ReturnTypeContext<'_>) -> Option<SdkType> {
+            let argument_type = context.invocation().argument(0, &["$id"])?.argument_type.clone()?;
+            let return_type = SdkType::of(&argument_type);
+            *self.0.borrow_mut() = Some(argument_type);
+
+            Some(return_type)
+        }
+    }
+
+    #[test]
+    fn return_type_requests_round_trip_through_the_rust_sdk() {
+        let received = Rc::new(RefCell::new(None));
+        let plugin = SdkPlugin::new("acme-container", "Acme container", "Container return types.")
+            .with_function_return_type_provider(RecordingReturnType(Rc::clone(&received)));
+        let mut worker =
+            Worker::new(SdkExtension::new("acme/container", "Acme container", "1.0.0").with_plugin(plugin));
+        let mut serve = |payload| worker.handle(&Frame::request(1, payload)).expect("the SDK worker should answer");
+
+        let registration = decode_registration(&serve(encode_describe_request(PHPVersion::PHP85))).unwrap();
+        assert_eq!(registration.function_providers.len(), 1);
+
+        let arena = LocalArena::new();
+        let file = File::ephemeral(Cow::Borrowed(b"test.php"), Cow::Borrowed(b"<?php\n\nservice($id);\n"));
+        let program = parse_file(&arena, &file);
+        let call = program
+            .statements
+            .iter()
+            .find_map(|statement| match statement {
+                Statement::Expression(ExpressionStatement {
+                    expression: Expression::Call(Call::Function(call)),
+                    ..
+                }) => Some(call),
+                _ => None,
+            })
+            .unwrap();
+        let mut argument_type =
+            TUnion::from_vec(vec![get_literal_string(word("logger")).get_single().clone(), TAtomic::Null]);
+        argument_type.set_by_reference(true);
+        let mut artifacts = AnalysisArtifacts::new();
+        artifacts.set_expression_type(call.argument_list.arguments.first().unwrap().value(), argument_type.clone());
+        let invocation = Invocation::new(
+            InvocationTarget::Callable {
+                source: None,
+                signature: TCallableSignature::new(false, false),
+                effective_signature: None,
+                span: call.function.span(),
+            },
+            InvocationArgumentsSource::ArgumentList(&call.argument_list),
+            call.span(),
+        );
+
+        let request = encode_provider_request(
+            ProviderRequestKind::ReturnType,
+            &[0],
+            ProviderTarget::Function(b"service"),
+            &invocation,
+            &artifacts,
+            &file,
+            1,
+            false,
+            false,
+        )
+        .unwrap();
+        let response = serve(request.payload);
+
+        let received = received.borrow();
+        let view = received.as_ref().expect("the provider should receive the argument type");
+        assert_eq!(view.handle(), 0);
+        assert!(view.is_by_reference());
+        assert!(!view.is_possibly_undefined());
+        assert!(view.is_nullable());
+        assert!(matches!(
+            view.atomics(),
+            [
+                AtomicView::Scalar(ScalarView::String(StringView { literal: Some(StringLiteral::Value(value)), .. })),
+                AtomicView::Null,
+            ] if value == b"logger"
+        ));
+
+        let return_type = decode_return_type_response(&response, |handle| resolve_type_handle(&request.types, handle))
+            .unwrap()
+            .expect("the provider should handle the call");
+        assert_eq!(return_type, argument_type);
+    }
 
     #[test]
     fn complete_snapshots_accept_deep_inferred_types() {
@@ -8,6 +8,7 @@ use mago_database::file::FileType;
 use mago_database::matcher::ExclusionMatcher;
 use mago_extension::PayloadWriter;
 use mago_extension::source::SourceSnapshot;
+use mago_extension::wire::analyzer::CODEBASE_SCAN_REQUEST;
 use mago_names::ResolvedNames;
 use mago_syntax::cst::Program;
 
@@ -242,7 +243,7 @@ fn encode_batches(
         .enumerate()
         .map(|(index, range)| {
             let mut writer = protocol::message_writer_with_capacity(
-                protocol::CODEBASE_SCAN_REQUEST,
+                CODEBASE_SCAN_REQUEST,
                 files[range.clone()]
                     .iter()
                     .fold(message_overhead, |size, (file, hooks)| size.saturating_add(file.encoded_len(hooks))),
@@ -459,7 +459,10 @@ fn clear_object_property_narrowings<'ctx, 'arena, A>(
     }
 
     if let Some(metadata) = metadata
-        && (metadata.flags.is_pure() || metadata.flags.is_mutation_free() || metadata.flags.is_external_mutation_free())
+        && (metadata.flags.is_pure()
+            || metadata.flags.is_inferred_pure()
+            || metadata.flags.is_mutation_free()
+            || metadata.flags.is_external_mutation_free())
         && !metadata.flags.suspends_fiber()
     {
         // Mutation free functions are guaranteed not to have side effects, so we can skip clearing property narrowings.
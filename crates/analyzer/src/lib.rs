@@ -33,6 +33,8 @@ use crate::statement::analyze_statements;
 pub mod analysis_result;
 pub mod artifacts;
 pub mod code;
+pub mod dead_code;
+pub mod dependencies;
 pub mod error;
 pub mod external;
 pub mod plugin;
@@ -47,10 +49,12 @@ mod context;
 mod expression;
 mod formula;
 mod invocation;
+mod purity;
 mod readonly;
 mod reconciler;
 mod resolver;
 mod statement;
+mod taint;
 mod utils;
 mod visibility;
 
@@ -186,6 +190,10 @@ where
             context.settings.register_super_globals,
         );
         let mut artifacts = AnalysisArtifacts::new();
+        if context.settings.taint.enabled {
+            taint::record_unconditional_assignments(&mut artifacts, statements);
+        }
+
         #[cfg(not(target_arch = "wasm32"))]
         if let Some(start) = setup_start {
             telemetry::record_setup(start.elapsed());
@@ -281,6 +289,8 @@ mod tests {
     use mago_database::file::File;
     use mago_names::resolver::NameResolver;
     use mago_syntax::parser::parse_file;
+    use mago_text_edit::Safety;
+    use mago_text_edit::TextEditor;
     use mago_word::WordSet;
 
     use crate::Analyzer;
@@ -296,6 +306,7 @@ mod tests {
         settings: Settings,
         expected_issues: Vec<IssueCode>,
         expected_messages: Vec<&'static str>,
+        expected_fixed_code: Option<(Safety, &'static str)>,
     }
 
     impl TestCase {
@@ -310,6 +321,7 @@ mod tests {
                 },
                 expected_issues: vec![],
                 expected_messages: vec![],
+                expected_fixed_code: None,
             }
         }
 
@@ -333,6 +345,12 @@ mod tests {
             self
         }
 
+        /// Expects the code to read `code` once all fixes up to `safety` are applied.
+        pub fn expect_fixed_code(mut self, safety: Safety, code: &'static str) -> Self {
+            self.expected_fixed_code = Some((safety, code));
+            self
+        }
+
         pub fn run(self) {
             run_test_case_inner(self);
         }
@@ -365,6 +383,22 @@ mod tests {
             panic!("Test '{}': Expected analysis to succeed, but it failed with an error: {}", config.name, err);
         }
 
+        if let Some((safety, expected_fixed_code)) = config.expected_fixed_code {
+            let mut editor = TextEditor::with_safety(config.content.as_bytes(), safety);
+            for issue in analysis_result.issues.iter() {
+                for edits in issue.edits.values() {
+                    editor.apply_batch(edits.clone(), None::<fn(&[u8]) -> bool>);
+                }
+            }
+
+            assert_eq!(
+                String::from_utf8_lossy(&editor.finish()),
+                expected_fixed_code,
+                "Test '{}': fixed code does not match",
+                config.name
+            );
+        }
+
         verify_reported_issues(
             config.name,
             analysis_result,
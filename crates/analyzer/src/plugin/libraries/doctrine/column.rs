@@ -0,0 +1,108 @@
+//! `#[ORM\Column]` nullability checks.
+
+use mago_codex::metadata::class_like::ClassLikeMetadata;
+use mago_reporting::Annotation;
+use mago_reporting::Issue;
+use mago_syntax::cst::Class;
+
+use crate::code::IssueCode;
+use crate::plugin::context::HookContext;
+use crate::plugin::hook::ClassDeclarationHook;
+use crate::plugin::hook::HookResult;
+use crate::plugin::libraries::doctrine::COLUMN;
+use crate::plugin::libraries::doctrine::ID;
+use crate::plugin::libraries::doctrine::get_attribute;
+use crate::plugin::libraries::doctrine::get_attribute_argument;
+use crate::plugin::provider::Provider;
+use crate::plugin::provider::ProviderMeta;
+
+/// Reports `#[ORM\Column]` properties whose native type disagrees with the
+/// column's `nullable` option.
+///
+/// Identifiers are skipped, as generated identifiers are commonly declared
+/// nullable until the entity is persisted.
+#[derive(Default)]
+pub struct ColumnNullabilityHook;
+
+impl Provider for ColumnNullabilityHook {
+    fn meta() -> &'static ProviderMeta {
+        static META: ProviderMeta = ProviderMeta::new(
+            "doctrine::column-nullability",
+            "ORM\\Column",
+            "Reports mapped properties whose nullability differs from their column's.",
+        );
+
+        &META
+    }
+}
+
+impl ClassDeclarationHook for ColumnNullabilityHook {
+    fn on_enter_class(
+        &self,
+        _class: &Class<'_>,
+        metadata: &ClassLikeMetadata,
+        context: &mut HookContext<'_, '_>,
+    ) -> HookResult<()> {
+        for property in metadata.properties.values() {
+            let Some(column) = get_attribute(&property.attributes, COLUMN) else {
+                continue;
+            };
+
+            if get_attribute(&property.attributes, ID).is_some() {
+                continue;
+            }
+
+            let Some(property_type) = &property.type_declaration_metadata else {
+                continue;
+            };
+
+            if property_type.type_union.is_mixed() {
+                continue;
+            }
+
+            let is_column_nullable = match get_attribute_argument(column, b"nullable", None) {
+                None => false,
+                Some(nullable) if nullable.is_true() => true,
+                Some(nullable) if nullable.is_false() => false,
+                Some(_) => continue,
+            };
+
+            let is_property_nullable = property_type.type_union.is_nullable();
+            if is_column_nullable == is_property_nullable {
+                continue;
+            }
+
+            let property_name = format!("{}::{}", metadata.original_name, property.name.0);
+            let issue = if is_column_nullable {
+                Issue::error(format!("Property `{property_name}` is not nullable, but its column is."))
+                    .with_note(
+                        "Doctrine hydrates `NULL` column values into the property, which fails with a `TypeError`.",
+                    )
+                    .with_help("Make the property type nullable, or map the column with `nullable: false`.")
+            } else {
+                Issue::warning(format!("Property `{property_name}` is nullable, but its column is not."))
+                    .with_note(
+                        "Flushing the entity while the property is `null` violates the column's `NOT NULL` constraint.",
+                    )
+                    .with_help("Make the property type non-nullable, or map the column with `nullable: true`.")
+            };
+
+            context.report(
+                IssueCode::MismatchedDoctrineColumnNullability,
+                issue
+                    .with_annotation(Annotation::primary(property_type.span).with_message(if is_property_nullable {
+                        "The property accepts `null`."
+                    } else {
+                        "The property does not accept `null`."
+                    }))
+                    .with_annotation(Annotation::secondary(column.span).with_message(if is_column_nullable {
+                        "The column is nullable."
+                    } else {
+                        "The column is not nullable."
+                    })),
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+//! `Doctrine\Persistence\ObjectManager::getRepository()` return type provider.
+
+use mago_codex::ttype::atomic::TAtomic;
+use mago_codex::ttype::atomic::object::TObject;
+use mago_codex::ttype::atomic::object::named::TNamedObject;
+use mago_codex::ttype::union::TUnion;
+use mago_word::word;
+
+use crate::plugin::context::InvocationInfo;
+use crate::plugin::context::ProviderContext;
+use crate::plugin::libraries::doctrine::ENTITY_MANAGER;
+use crate::plugin::libraries::doctrine::ENTITY_REPOSITORY;
+use crate::plugin::libraries::doctrine::OBJECT_MANAGER;
+use crate::plugin::libraries::doctrine::get_repository_class;
+use crate::plugin::libraries::doctrine::is_mapped_entity;
+use crate::plugin::provider::Provider;
+use crate::plugin::provider::ProviderMeta;
+use crate::plugin::provider::method::MethodReturnTypeProvider;
+use crate::plugin::provider::method::MethodTarget;
+
+static META: ProviderMeta = ProviderMeta::new(
+    "doctrine::object-manager::get-repository",
+    "ObjectManager::getRepository",
+    "Returns the repository class mapped for the entity",
+);
+
+// Use wildcard for class since entity managers are commonly decorated
+static TARGETS: [MethodTarget; 1] = [MethodTarget::any_class(b"getRepository")];
+
+/// Provider for the `Doctrine\Persistence\ObjectManager::getRepository()` method.
+///
+/// When called with an entity class-string like `Post::class`, returns the
+/// `repositoryClass` declared by the entity's `#[ORM\Entity]` attribute, or
+/// `Doctrine\ORM\EntityRepository<Post>` when none is declared. Generic
+/// repositories are parameterized with the entity.
+#[derive(Default)]
+pub struct GetRepositoryProvider;
+
+impl Provider for GetRepositoryProvider {
+    fn meta() -> &'static ProviderMeta {
+        &META
+    }
+}
+
+impl MethodReturnTypeProvider for GetRepositoryProvider {
+    fn targets() -> &'static [MethodTarget] {
+        &TARGETS
+    }
+
+    fn get_return_type(
+        &self,
+        context: &ProviderContext<'_, '_, '_>,
+        class_name: &[u8],
+        _method_name: &[u8],
+        invocation: &InvocationInfo<'_, '_, '_>,
+    ) -> Option<TUnion> {
+        if !context.is_instance_of(class_name, OBJECT_MANAGER) && !context.is_instance_of(class_name, ENTITY_MANAGER) {
+            return None;
+        }
+
+        let class_argument = invocation.get_argument(0, &[b"className"])?;
+        let entity_class = context.get_expression_type(class_argument)?.get_single_class_string_value()?;
+        let entity = context.get_class_like(entity_class)?;
+
+        let repository = match get_repository_class(entity) {
+            Some(repository_class) => context.get_class_like(repository_class)?,
+            None if is_mapped_entity(entity) => context.get_class_like(word(ENTITY_REPOSITORY))?,
+            None => return None,
+        };
+
+        let type_parameters = (repository.template_types.len() == 1).then(|| {
+            vec![TUnion::from_atomic(TAtomic::Object(TObject::Named(TNamedObject::new(entity.original_name))))]
+        });
+
+        Some(TUnion::from_atomic(TAtomic::Object(TObject::Named(TNamedObject::new_with_type_parameters(
+            repository.original_name,
+            type_parameters,
+        )))))
+    }
+}
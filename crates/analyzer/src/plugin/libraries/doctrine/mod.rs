@@ -0,0 +1,149 @@
+//! Doctrine ORM providers.
+//!
+//! Entities are read from their attribute mapping (`#[ORM\Entity]`,
+//! `#[ORM\Column]`, `#[ORM\OneToMany]`, ...); entities mapped through XML or
+//! PHP configuration are left to the library's own generic signatures.
+
+mod column;
+mod get_repository;
+mod relation;
+mod repository;
+
+pub use column::ColumnNullabilityHook;
+pub use get_repository::GetRepositoryProvider;
+pub use relation::RelationCollectionHook;
+pub use repository::RepositoryFindProvider;
+
+use mago_codex::metadata::CodebaseMetadata;
+use mago_codex::metadata::attribute::AttributeMetadata;
+use mago_codex::metadata::class_like::ClassLikeMetadata;
+use mago_codex::metadata::property::PropertyMetadata;
+use mago_codex::ttype::atomic::object::TObject;
+use mago_codex::ttype::expander::StaticClassType;
+use mago_codex::ttype::union::TUnion;
+use mago_word::Word;
+use mago_word::ascii_lowercase_word;
+
+use crate::invocation::MethodTargetContext;
+use crate::plugin::Plugin;
+use crate::plugin::PluginMeta;
+use crate::plugin::PluginRegistry;
+
+const ENTITY: &[u8] = b"Doctrine\\ORM\\Mapping\\Entity";
+const ID: &[u8] = b"Doctrine\\ORM\\Mapping\\Id";
+const COLUMN: &[u8] = b"Doctrine\\ORM\\Mapping\\Column";
+const EMBEDDED: &[u8] = b"Doctrine\\ORM\\Mapping\\Embedded";
+const MANY_TO_ONE: &[u8] = b"Doctrine\\ORM\\Mapping\\ManyToOne";
+const ONE_TO_ONE: &[u8] = b"Doctrine\\ORM\\Mapping\\OneToOne";
+const ONE_TO_MANY: &[u8] = b"Doctrine\\ORM\\Mapping\\OneToMany";
+const MANY_TO_MANY: &[u8] = b"Doctrine\\ORM\\Mapping\\ManyToMany";
+
+const ENTITY_REPOSITORY: &[u8] = b"Doctrine\\ORM\\EntityRepository";
+const OBJECT_REPOSITORY: &[u8] = b"Doctrine\\Persistence\\ObjectRepository";
+const OBJECT_MANAGER: &[u8] = b"Doctrine\\Persistence\\ObjectManager";
+const ENTITY_MANAGER: &[u8] = b"Doctrine\\ORM\\EntityManagerInterface";
+const COLLECTION: &[u8] = b"Doctrine\\Common\\Collections\\Collection";
+
+/// Plugin providing type inference and mapping checks for doctrine/orm.
+pub struct DoctrinePlugin;
+
+static META: PluginMeta = PluginMeta::new(
+    "doctrine",
+    "Doctrine ORM",
+    "Type providers and mapping checks for doctrine/orm package",
+    &["doctrine-orm"],
+    false,
+);
+
+impl Plugin for DoctrinePlugin {
+    fn meta(&self) -> &'static PluginMeta {
+        &META
+    }
+
+    fn register(&self, registry: &mut PluginRegistry) {
+        registry.register_method_provider(GetRepositoryProvider);
+        registry.register_method_provider(RepositoryFindProvider);
+        registry.register_expression_hook(RelationCollectionHook);
+        registry.register_class_hook(ColumnNullabilityHook);
+    }
+}
+
+fn get_attribute<'meta>(attributes: &'meta [AttributeMetadata], name: &[u8]) -> Option<&'meta AttributeMetadata> {
+    attributes.iter().find(|attribute| attribute.name.as_bytes().eq_ignore_ascii_case(name))
+}
+
+/// Returns the type of an attribute argument passed by name, or at `position`
+/// when passed positionally.
+fn get_attribute_argument<'meta>(
+    attribute: &'meta AttributeMetadata,
+    name: &[u8],
+    position: Option<usize>,
+) -> Option<&'meta TUnion> {
+    attribute.arguments.iter().enumerate().find_map(|(index, argument)| match argument.name {
+        Some(argument_name) if argument_name.as_bytes() == name => argument.value_type.as_ref(),
+        None if position == Some(index) => argument.value_type.as_ref(),
+        _ => None,
+    })
+}
+
+fn is_mapped_entity(class_like_metadata: &ClassLikeMetadata) -> bool {
+    get_attribute(&class_like_metadata.attributes, ENTITY).is_some()
+}
+
+/// The custom repository named by `#[ORM\Entity(repositoryClass: ...)]`.
+fn get_repository_class(entity: &ClassLikeMetadata) -> Option<Word> {
+    let attribute = get_attribute(&entity.attributes, ENTITY)?;
+
+    get_attribute_argument(attribute, b"repositoryClass", Some(0))?.get_single_class_string_value()
+}
+
+/// Resolves the entity managed by the repository a method is called on.
+///
+/// The entity is named either by a parameterized receiver such as
+/// `EntityRepository<Foo>`, or by the repository declaring
+/// `@extends EntityRepository<Foo>`. Repositories naming neither are not
+/// resolved, as finding the entity mapped to them would mean scanning every
+/// class-like in the codebase at each call site.
+fn get_repository_entity(method_context: &MethodTargetContext<'_>) -> Option<Word> {
+    if let StaticClassType::Object(TObject::Named(receiver)) = &method_context.class_type
+        && let Some(entity) = receiver.get_type_parameters().and_then(<[TUnion]>::first)
+        && let Some(entity) = entity.get_single_named_object()
+    {
+        return Some(entity.name);
+    }
+
+    [ENTITY_REPOSITORY, OBJECT_REPOSITORY].into_iter().find_map(|parent| {
+        method_context
+            .class_like_metadata
+            .template_extended_parameters
+            .get(&ascii_lowercase_word(parent))
+            .and_then(|parameters| parameters.values().next())
+            .and_then(TUnion::get_single_named_object)
+            .map(|entity| entity.name)
+    })
+}
+
+/// Whether `field` can be used as a criteria key for `entity`: a mapped
+/// field, a single-valued association, or `embedded.field` for an embeddable.
+fn is_criteria_field(codebase: &CodebaseMetadata, entity: &ClassLikeMetadata, field: &[u8]) -> bool {
+    let (property_name, is_embedded_field) = match field.iter().position(|byte| *byte == b'.') {
+        Some(position) => (&field[..position], true),
+        None => (field, false),
+    };
+
+    let Some(property) =
+        codebase.get_declaring_property(entity.name.as_bytes(), &[b"$".as_slice(), property_name].concat())
+    else {
+        return false;
+    };
+
+    if is_embedded_field {
+        return get_attribute(&property.attributes, EMBEDDED).is_some();
+    }
+
+    is_mapped_field(property)
+}
+
+fn is_mapped_field(property: &PropertyMetadata) -> bool {
+    [COLUMN, ID, MANY_TO_ONE, ONE_TO_ONE].into_iter().any(|name| get_attribute(&property.attributes, name).is_some())
+}
@@ -0,0 +1,126 @@
+//! To-many association collection typing.
+
+use mago_codex::metadata::CodebaseMetadata;
+use mago_codex::metadata::property::PropertyMetadata;
+use mago_codex::ttype::atomic::TAtomic;
+use mago_codex::ttype::atomic::object::TObject;
+use mago_codex::ttype::atomic::object::named::TNamedObject;
+use mago_codex::ttype::get_arraykey;
+use mago_codex::ttype::get_int;
+use mago_codex::ttype::union::TUnion;
+use mago_syntax::cst::Access;
+use mago_syntax::cst::ClassLikeMemberSelector;
+use mago_syntax::cst::Expression;
+
+use crate::plugin::context::HookContext;
+use crate::plugin::hook::ExpressionHook;
+use crate::plugin::hook::HookResult;
+use crate::plugin::libraries::doctrine::COLLECTION;
+use crate::plugin::libraries::doctrine::MANY_TO_MANY;
+use crate::plugin::libraries::doctrine::ONE_TO_MANY;
+use crate::plugin::libraries::doctrine::get_attribute;
+use crate::plugin::libraries::doctrine::get_attribute_argument;
+use crate::plugin::provider::Provider;
+use crate::plugin::provider::ProviderMeta;
+
+/// Types reads of `#[ORM\OneToMany]` and `#[ORM\ManyToMany]` properties.
+///
+/// A property declared as a bare `Collection` is read as
+/// `Collection<int, Target>`, using the association's `targetEntity`; when the
+/// association is indexed through `indexBy`, keys are `array-key` instead.
+/// Properties that already document their collection's parameters are left
+/// untouched.
+#[derive(Default)]
+pub struct RelationCollectionHook;
+
+impl Provider for RelationCollectionHook {
+    fn meta() -> &'static ProviderMeta {
+        static META: ProviderMeta = ProviderMeta::new(
+            "doctrine::relation-collection",
+            "ORM\\OneToMany, ORM\\ManyToMany",
+            "Types to-many association properties as `Collection<int, TargetEntity>`.",
+        );
+
+        &META
+    }
+}
+
+impl ExpressionHook for RelationCollectionHook {
+    fn after_expression(&self, expression: &Expression<'_>, context: &mut HookContext<'_, '_>) -> HookResult<()> {
+        let (object, selector) = match expression {
+            Expression::Access(Access::Property(access)) => (access.object, &access.property),
+            Expression::Access(Access::NullSafeProperty(access)) => (access.object, &access.property),
+            _ => return Ok(()),
+        };
+
+        let ClassLikeMemberSelector::Identifier(property_name) = selector else {
+            return Ok(());
+        };
+
+        let Some(object) = context.get_expression_type(object).and_then(TUnion::get_single_named_object) else {
+            return Ok(());
+        };
+
+        let codebase = context.codebase();
+        let Some(property) =
+            codebase.get_declaring_property(object.name.as_bytes(), &[b"$".as_slice(), property_name.value].concat())
+        else {
+            return Ok(());
+        };
+
+        let Some(type_parameters) = get_collection_type_parameters(codebase, property) else {
+            return Ok(());
+        };
+
+        let Some(property_type) = context.get_expression_type(expression) else {
+            return Ok(());
+        };
+
+        let types = property_type
+            .types
+            .iter()
+            .map(|atomic| match atomic {
+                TAtomic::Object(TObject::Named(named)) if is_collection(named) => {
+                    TAtomic::Object(TObject::Named(named.clone().with_type_parameters(Some(type_parameters.clone()))))
+                }
+                _ => atomic.clone(),
+            })
+            .collect();
+
+        context.set_expression_type(expression, TUnion::from_vec(types));
+
+        Ok(())
+    }
+}
+
+fn get_collection_type_parameters(codebase: &CodebaseMetadata, property: &PropertyMetadata) -> Option<Vec<TUnion>> {
+    // `targetEntity` moved in `#[ORM\OneToMany]`'s constructor between ORM 2 and 3,
+    // so it is only read positionally for `#[ORM\ManyToMany]`.
+    let (association, target_entity_position) = match get_attribute(&property.attributes, ONE_TO_MANY) {
+        Some(association) => (association, None),
+        None => (get_attribute(&property.attributes, MANY_TO_MANY)?, Some(0)),
+    };
+
+    let declared_type = &property.type_metadata.as_ref()?.type_union;
+    if !declared_type.types.iter().any(|atomic| {
+        matches!(atomic, TAtomic::Object(TObject::Named(named)) if is_collection(named) && !named.has_type_parameters())
+    }) {
+        return None;
+    }
+
+    let target_entity = get_attribute_argument(association, b"targetEntity", target_entity_position)?
+        .get_single_class_string_value()?;
+    let target_entity = codebase.get_class_like(target_entity.as_bytes())?;
+
+    let key_type =
+        if get_attribute_argument(association, b"indexBy", None).is_some() { get_arraykey() } else { get_int() };
+
+    Some(vec![
+        key_type,
+        TUnion::from_atomic(TAtomic::Object(TObject::Named(TNamedObject::new(target_entity.original_name)))),
+    ])
+}
+
+fn is_collection(named: &TNamedObject) -> bool {
+    named.name.as_bytes().eq_ignore_ascii_case(COLLECTION)
+}
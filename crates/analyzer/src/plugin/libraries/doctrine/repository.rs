@@ -0,0 +1,151 @@
+//! `Doctrine\Persistence\ObjectRepository` finder return type provider.
+
+use std::sync::Arc;
+
+use mago_codex::metadata::class_like::ClassLikeMetadata;
+use mago_codex::ttype::atomic::TAtomic;
+use mago_codex::ttype::atomic::array::TArray;
+use mago_codex::ttype::atomic::array::key::ArrayKey;
+use mago_codex::ttype::atomic::array::list::TList;
+use mago_codex::ttype::atomic::object::TObject;
+use mago_codex::ttype::atomic::object::named::TNamedObject;
+use mago_codex::ttype::union::TUnion;
+use mago_reporting::Annotation;
+use mago_reporting::Issue;
+use mago_span::HasSpan;
+use mago_word::starts_with_ignore_case;
+
+use crate::code::IssueCode;
+use crate::plugin::context::InvocationInfo;
+use crate::plugin::context::ProviderContext;
+use crate::plugin::libraries::doctrine::ENTITY_REPOSITORY;
+use crate::plugin::libraries::doctrine::OBJECT_REPOSITORY;
+use crate::plugin::libraries::doctrine::get_repository_entity;
+use crate::plugin::libraries::doctrine::is_criteria_field;
+use crate::plugin::libraries::doctrine::is_mapped_entity;
+use crate::plugin::provider::Provider;
+use crate::plugin::provider::ProviderMeta;
+use crate::plugin::provider::method::MethodReturnTypeProvider;
+use crate::plugin::provider::method::MethodTarget;
+
+static META: ProviderMeta = ProviderMeta::new(
+    "doctrine::object-repository::find",
+    "ObjectRepository::find*",
+    "Returns the repository's entity type and validates criteria keys against mapped fields",
+);
+
+// Use wildcard for class since custom repositories inherit these finders
+static TARGETS: [MethodTarget; 4] = [
+    MethodTarget::any_class(b"find"),
+    MethodTarget::any_class(b"findAll"),
+    MethodTarget::any_class(b"findBy"),
+    MethodTarget::any_class(b"findOneBy"),
+];
+
+/// Provider for the `find()`, `findAll()`, `findBy()` and `findOneBy()`
+/// repository methods.
+///
+/// Single-result finders return `Entity|null` and the others `list<Entity>`,
+/// where the entity is resolved from the repository the method is called on:
+/// either its type parameter, or the entity named by its
+/// `@extends EntityRepository<Entity>` tag.
+/// Finders overridden outside of Doctrine keep their declared return types.
+///
+/// Keys of literal `findBy()` and `findOneBy()` criteria must name a mapped
+/// field or single-valued association of the entity; Doctrine throws for
+/// anything else.
+#[derive(Default)]
+pub struct RepositoryFindProvider;
+
+impl Provider for RepositoryFindProvider {
+    fn meta() -> &'static ProviderMeta {
+        &META
+    }
+}
+
+impl MethodReturnTypeProvider for RepositoryFindProvider {
+    fn targets() -> &'static [MethodTarget] {
+        &TARGETS
+    }
+
+    fn get_return_type(
+        &self,
+        context: &ProviderContext<'_, '_, '_>,
+        class_name: &[u8],
+        method_name: &[u8],
+        invocation: &InvocationInfo<'_, '_, '_>,
+    ) -> Option<TUnion> {
+        if !context.is_instance_of(class_name, OBJECT_REPOSITORY)
+            && !context.is_instance_of(class_name, ENTITY_REPOSITORY)
+        {
+            return None;
+        }
+
+        let method_context = invocation.inner().target.get_method_context()?;
+        let entity_class = get_repository_entity(method_context)?;
+        let entity = context.get_class_like(entity_class)?;
+
+        let returns_list = method_name.eq_ignore_ascii_case(b"findBy") || method_name.eq_ignore_ascii_case(b"findAll");
+        if method_name.eq_ignore_ascii_case(b"findBy") || method_name.eq_ignore_ascii_case(b"findOneBy") {
+            check_criteria(context, entity, invocation);
+        }
+
+        if !starts_with_ignore_case(class_name, b"Doctrine\\") {
+            return None;
+        }
+
+        let entity_type = TUnion::from_atomic(TAtomic::Object(TObject::Named(TNamedObject::new(entity.original_name))));
+
+        Some(if returns_list {
+            TUnion::from_atomic(TAtomic::Array(TArray::List(TList::new(Arc::new(entity_type)))))
+        } else {
+            entity_type.as_nullable()
+        })
+    }
+}
+
+fn check_criteria(
+    context: &ProviderContext<'_, '_, '_>,
+    entity: &ClassLikeMetadata,
+    invocation: &InvocationInfo<'_, '_, '_>,
+) {
+    if !is_mapped_entity(entity) {
+        return;
+    }
+
+    let Some(criteria) = invocation.get_argument(0, &[b"criteria"]) else {
+        return;
+    };
+
+    let Some(criteria_type) = context.get_expression_type(criteria) else {
+        return;
+    };
+
+    for atomic in criteria_type.types.iter() {
+        let TAtomic::Array(TArray::Keyed(keyed_array)) = atomic else {
+            continue;
+        };
+
+        for key in keyed_array.known_items.iter().flat_map(|known_items| known_items.keys()) {
+            let ArrayKey::String(field) = key else {
+                continue;
+            };
+
+            if is_criteria_field(context.codebase(), entity, field.as_bytes()) {
+                continue;
+            }
+
+            context.report(
+                IssueCode::InvalidDoctrineCriteria,
+                Issue::error(format!("Field `{field}` is not mapped on entity `{}`.", entity.original_name))
+                    .with_annotation(
+                        Annotation::primary(criteria.span()).with_message(format!("Criteria key `{field}` is used here.")),
+                    )
+                    .with_note(
+                        "Criteria keys must name a mapped field or a single-valued association; Doctrine throws an `UnrecognizedField` exception for anything else.",
+                    )
+                    .with_help("Use the property name of a mapped field, not its column name."),
+            );
+        }
+    }
+}
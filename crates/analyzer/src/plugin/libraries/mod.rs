@@ -1,5 +1,6 @@
-//! Library-specific providers for PHP stdlib, PSL, Flow-PHP, and PSR-11 Container.
+//! Library-specific providers for PHP stdlib, PSL, Flow-PHP, PSR-11 Container, and Doctrine ORM.
 
+pub mod doctrine;
 pub mod flow_php;
 pub mod psl;
 pub mod psr_container;
@@ -7,10 +8,12 @@ pub mod stdlib;
 
 use crate::plugin::Plugin;
 
+pub use doctrine::DoctrinePlugin;
 pub use flow_php::FlowPhpPlugin;
 pub use psl::PslPlugin;
 pub use psr_container::PsrContainerPlugin;
 pub use stdlib::StdlibPlugin;
 
 /// All available analyzer plugins.
-pub static ALL_PLUGINS: &[&dyn Plugin] = &[&StdlibPlugin, &PslPlugin, &FlowPhpPlugin, &PsrContainerPlugin];
+pub static ALL_PLUGINS: &[&dyn Plugin] =
+    &[&StdlibPlugin, &PslPlugin, &FlowPhpPlugin, &PsrContainerPlugin, &DoctrinePlugin];
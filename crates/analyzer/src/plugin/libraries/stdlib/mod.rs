@@ -10,6 +10,7 @@ pub mod math;
 pub mod object;
 pub mod random;
 pub mod reflection;
+pub mod regex;
 pub mod session;
 pub mod spl;
 pub mod string;
@@ -61,11 +62,13 @@ impl Plugin for StdlibPlugin {
         registry.register_function_provider(array::RangeProvider);
 
         registry.register_function_assertion_provider(array::ArrayAllAssertionProvider);
+        registry.register_function_assertion_provider(regex::PregMatchAssertionProvider);
 
         registry.register_function_call_hook(cookie::SetCookieHook);
         registry.register_function_call_hook(session::SessionSetSaveHandlerHook);
         registry.register_function_call_hook(session::SessionSetCookieParamsHook);
         registry.register_function_call_hook(math::IntdivHook);
+        registry.register_function_call_hook(regex::PregHook);
 
         registry.register_method_provider(closure::ClosureGetCurrentProvider);
         registry.register_method_provider(r#enum::EnumCasesProvider);
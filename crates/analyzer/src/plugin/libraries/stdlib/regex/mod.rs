@@ -0,0 +1,108 @@
+//! PCRE (`preg_*`) function providers.
+
+mod pattern;
+mod preg;
+mod preg_match;
+
+pub use preg::PregHook;
+pub use preg_match::PregMatchAssertionProvider;
+
+use std::collections::BTreeMap;
+
+use mago_codex::ttype::atomic::TAtomic;
+use mago_codex::ttype::atomic::array::TArray;
+use mago_codex::ttype::atomic::array::key::ArrayKey;
+use mago_codex::ttype::atomic::array::keyed::TKeyedArray;
+use mago_codex::ttype::atomic::array::list::TList;
+use mago_codex::ttype::get_int;
+use mago_codex::ttype::get_list;
+use mago_codex::ttype::get_nullable_string;
+use mago_codex::ttype::get_string;
+use mago_codex::ttype::union::TUnion;
+use mago_word::word;
+
+use crate::plugin::libraries::stdlib::regex::pattern::CaptureGroup;
+
+const PREG_SET_ORDER: i64 = 2;
+const PREG_OFFSET_CAPTURE: i64 = 256;
+const PREG_UNMATCHED_AS_NULL: i64 = 512;
+
+/// Returns the capture groups of a literal, well-formed pattern.
+fn get_capture_groups(pattern_type: &TUnion) -> Option<Vec<CaptureGroup>> {
+    let pattern = pattern::parse(pattern_type.get_single_literal_string_value()?).ok()?;
+    if pattern.has_branch_reset {
+        return None;
+    }
+
+    Some(pattern.groups)
+}
+
+/// Returns the `$matches` shape left by a successful `preg_match()` call.
+///
+/// Without `PREG_UNMATCHED_AS_NULL`, groups that did not participate in the match are
+/// reported as `''`, except trailing ones which PHP leaves out entirely.
+fn get_match_shape(groups: &[CaptureGroup], flags: i64) -> TAtomic {
+    let unmatched_as_null = flags & PREG_UNMATCHED_AS_NULL != 0;
+    let offset_capture = flags & PREG_OFFSET_CAPTURE != 0;
+
+    let mut known_items = BTreeMap::from([(ArrayKey::Integer(0), (false, get_capture_type(false, offset_capture)))]);
+    for (index, group) in groups.iter().enumerate() {
+        let possibly_undefined = !unmatched_as_null && groups[index..].iter().all(|group| group.optional);
+        let capture_type = get_capture_type(unmatched_as_null && group.optional, offset_capture);
+
+        if let Some(name) = &group.name {
+            known_items.insert(ArrayKey::String(word(name)), (possibly_undefined, capture_type.clone()));
+        }
+
+        known_items.insert(ArrayKey::Integer(index as i64 + 1), (possibly_undefined, capture_type));
+    }
+
+    TAtomic::Array(TArray::Keyed(TKeyedArray { known_items: Some(known_items), parameters: None, non_empty: true }))
+}
+
+/// Returns the `$matches` type left by a `preg_match_all()` call.
+fn get_match_all_type(groups: &[CaptureGroup], flags: i64) -> TUnion {
+    if flags & PREG_SET_ORDER != 0 {
+        return get_list(TUnion::from_atomic(get_match_shape(groups, flags)));
+    }
+
+    let unmatched_as_null = flags & PREG_UNMATCHED_AS_NULL != 0;
+    let offset_capture = flags & PREG_OFFSET_CAPTURE != 0;
+
+    let mut known_items =
+        BTreeMap::from([(ArrayKey::Integer(0), (false, get_list(get_capture_type(false, offset_capture))))]);
+    for (index, group) in groups.iter().enumerate() {
+        let captures_type = get_list(get_capture_type(unmatched_as_null && group.optional, offset_capture));
+
+        if let Some(name) = &group.name {
+            known_items.insert(ArrayKey::String(word(name)), (false, captures_type.clone()));
+        }
+
+        known_items.insert(ArrayKey::Integer(index as i64 + 1), (false, captures_type));
+    }
+
+    TUnion::from_atomic(TAtomic::Array(TArray::Keyed(TKeyedArray {
+        known_items: Some(known_items),
+        parameters: None,
+        non_empty: true,
+    })))
+}
+
+/// Returns the type of a single capture: the matched text, or a `[text, offset]` pair
+/// with `PREG_OFFSET_CAPTURE`.
+fn get_capture_type(nullable: bool, offset_capture: bool) -> TUnion {
+    let text_type = if nullable { get_nullable_string() } else { get_string() };
+    if !offset_capture {
+        return text_type;
+    }
+
+    TUnion::from_atomic(TAtomic::Array(TArray::List(TList::from_known_elements(BTreeMap::from([
+        (0, (false, text_type)),
+        (1, (false, get_int())),
+    ])))))
+}
+
+/// Returns the empty `$matches` array left by a call that did not match.
+fn get_empty_matches() -> TAtomic {
+    TAtomic::Array(TArray::Keyed(TKeyedArray::new()))
+}
@@ -0,0 +1,278 @@
+//! A small PCRE pattern scanner.
+//!
+//! This is not a full regular expression parser: it understands just enough of the
+//! PCRE syntax to split a pattern into its delimiters, body and modifiers, find the
+//! capture groups in the body, and catch the mistakes that make `preg_*` functions
+//! fail at runtime.
+
+use std::fmt;
+
+/// A capture group found in a pattern, in the order PCRE numbers them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaptureGroup {
+    /// The group's name, for `(?<name>...)`, `(?P<name>...)` and `(?'name'...)` groups.
+    pub name: Option<String>,
+    /// Whether the group may not participate in a successful match, e.g. because it
+    /// is quantified with `?`/`*`/`{0,n}` or sits in one branch of an alternation.
+    pub optional: bool,
+}
+
+/// The capture groups of a well-formed pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    pub groups: Vec<CaptureGroup>,
+    /// Whether the pattern uses `(?|...)`, where alternatives share group numbers.
+    ///
+    /// The groups of such patterns are not numbered in order, so callers should not
+    /// derive a match shape from them.
+    pub has_branch_reset: bool,
+}
+
+/// A reason a pattern would be rejected by `preg_*` functions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatternError {
+    Empty,
+    InvalidDelimiter,
+    MissingEndingDelimiter(u8),
+    UnknownModifier(u8),
+    UnmatchedClosingParenthesis,
+    MissingClosingParenthesis,
+    MissingClosingBracket,
+    InvalidGroupName,
+    DuplicateGroupName(String),
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "Empty regular expression"),
+            Self::InvalidDelimiter => write!(f, "Delimiter must not be alphanumeric, backslash, or NUL"),
+            Self::MissingEndingDelimiter(delimiter) => {
+                write!(f, "No ending delimiter '{}' found", char::from(*delimiter))
+            }
+            Self::UnknownModifier(modifier) => write!(f, "Unknown modifier '{}'", char::from(*modifier)),
+            Self::UnmatchedClosingParenthesis => write!(f, "Unmatched closing parenthesis"),
+            Self::MissingClosingParenthesis => write!(f, "Missing closing parenthesis"),
+            Self::MissingClosingBracket => write!(f, "Missing terminating ] for character class"),
+            Self::InvalidGroupName => write!(f, "Invalid or missing subpattern name"),
+            Self::DuplicateGroupName(name) => write!(f, "Two named subpatterns have the same name `{name}`"),
+        }
+    }
+}
+
+/// A group opened by `(` that has not been closed yet.
+struct Frame {
+    /// Index of the group's own capture, if it captures.
+    capture: Option<usize>,
+    /// Index of the first capture nested inside the group.
+    first_nested: usize,
+    /// Whether the group's branches are alternatives, making every nested capture optional.
+    alternation: bool,
+}
+
+/// Parses a delimited PCRE pattern, e.g. `/(?<year>\d{4})-(\d{2})/i`.
+pub fn parse(pattern: &[u8]) -> Result<Pattern, PatternError> {
+    let start = pattern.iter().position(|byte| !byte.is_ascii_whitespace()).ok_or(PatternError::Empty)?;
+    let delimiter = pattern[start];
+    if delimiter.is_ascii_alphanumeric() || delimiter == b'\\' || delimiter == 0 {
+        return Err(PatternError::InvalidDelimiter);
+    }
+
+    let closing_delimiter = match delimiter {
+        b'(' => b')',
+        b'[' => b']',
+        b'{' => b'}',
+        b'<' => b'>',
+        _ => delimiter,
+    };
+
+    let body_start = start + 1;
+    let body_end = find_closing_delimiter(pattern, body_start, delimiter, closing_delimiter)
+        .ok_or(PatternError::MissingEndingDelimiter(closing_delimiter))?;
+
+    let mut extended = false;
+    let mut no_auto_capture = false;
+    let mut allow_duplicate_names = false;
+    for &modifier in &pattern[body_end + 1..] {
+        match modifier {
+            b'x' => extended = true,
+            b'n' => no_auto_capture = true,
+            b'J' => allow_duplicate_names = true,
+            b'i' | b'm' | b's' | b'A' | b'D' | b'S' | b'U' | b'X' | b'u' | b'r' | b' ' | b'\n' | b'\r' => {}
+            _ => return Err(PatternError::UnknownModifier(modifier)),
+        }
+    }
+
+    let body = &pattern[body_start..body_end];
+    let mut groups: Vec<CaptureGroup> = Vec::new();
+    let mut frames: Vec<Frame> = Vec::new();
+    let mut top_level_alternation = false;
+    let mut has_branch_reset = false;
+    let mut position = 0;
+
+    while position < body.len() {
+        match body[position] {
+            b'\\' if body.get(position + 1) == Some(&b'Q') => {
+                position = find(body, position + 2, b"\\E").map_or(body.len(), |end| end + 2);
+            }
+            b'\\' => position += 2,
+            b'[' => position = skip_character_class(body, position)?,
+            b'#' if extended => {
+                position = find(body, position, b"\n").map_or(body.len(), |end| end + 1);
+            }
+            b'|' => {
+                match frames.last_mut() {
+                    Some(frame) => frame.alternation = true,
+                    None => top_level_alternation = true,
+                }
+
+                position += 1;
+            }
+            b'(' => {
+                let rest = &body[position + 1..];
+
+                // Verbs such as `(*UTF8)` and comments never contain groups.
+                if rest.starts_with(b"*") || rest.starts_with(b"?#") {
+                    position = find(body, position, b")").ok_or(PatternError::MissingClosingParenthesis)? + 1;
+                    continue;
+                }
+
+                let name = if rest.starts_with(b"?<") && !rest.starts_with(b"?<=") && !rest.starts_with(b"?<!") {
+                    Some(read_group_name(&rest[2..], b'>')?)
+                } else if rest.starts_with(b"?P<") {
+                    Some(read_group_name(&rest[3..], b'>')?)
+                } else if rest.starts_with(b"?'") {
+                    Some(read_group_name(&rest[2..], b'\'')?)
+                } else {
+                    None
+                };
+
+                let capturing = name.is_some() || (!rest.starts_with(b"?") && !no_auto_capture);
+                if let Some(name) = &name
+                    && !allow_duplicate_names
+                    && groups.iter().any(|group| group.name.as_ref() == Some(name))
+                {
+                    return Err(PatternError::DuplicateGroupName(name.clone()));
+                }
+
+                has_branch_reset |= rest.starts_with(b"?|");
+
+                let capture = if capturing {
+                    groups.push(CaptureGroup { name, optional: false });
+
+                    Some(groups.len() - 1)
+                } else {
+                    None
+                };
+
+                // A conditional group `(?(condition)yes|no)` takes one of two branches, and its
+                // condition is a group reference rather than a capture.
+                let conditional = rest.starts_with(b"?(");
+                frames.push(Frame { capture, first_nested: groups.len(), alternation: conditional });
+
+                position += 1;
+                if conditional && rest.get(2) != Some(&b'?') {
+                    position = find(body, position, b")").ok_or(PatternError::MissingClosingParenthesis)? + 1;
+                }
+            }
+            b')' => {
+                let frame = frames.pop().ok_or(PatternError::UnmatchedClosingParenthesis)?;
+                if frame.alternation {
+                    groups[frame.first_nested..].iter_mut().for_each(|group| group.optional = true);
+                }
+
+                let quantifier = &body[position + 1..];
+                if quantifier.starts_with(b"?") || quantifier.starts_with(b"*") || quantifier.starts_with(b"{0") {
+                    let first = frame.capture.unwrap_or(frame.first_nested);
+                    groups[first..].iter_mut().for_each(|group| group.optional = true);
+                }
+
+                position += 1;
+            }
+            _ => position += 1,
+        }
+    }
+
+    if !frames.is_empty() {
+        return Err(PatternError::MissingClosingParenthesis);
+    }
+
+    if top_level_alternation {
+        for group in &mut groups {
+            group.optional = true;
+        }
+    }
+
+    Ok(Pattern { groups, has_branch_reset })
+}
+
+/// Finds the delimiter that ends the pattern body, honouring escapes and, for bracket-style
+/// delimiters such as `{...}`, nesting.
+fn find_closing_delimiter(pattern: &[u8], start: usize, delimiter: u8, closing_delimiter: u8) -> Option<usize> {
+    let mut depth = 1usize;
+    let mut position = start;
+
+    while position < pattern.len() {
+        let byte = pattern[position];
+        if byte == b'\\' {
+            position += 2;
+            continue;
+        }
+
+        if byte == closing_delimiter {
+            depth -= 1;
+            if depth == 0 {
+                return Some(position);
+            }
+        } else if byte == delimiter {
+            depth += 1;
+        }
+
+        position += 1;
+    }
+
+    None
+}
+
+/// Returns the position just past the character class starting at `start`.
+fn skip_character_class(body: &[u8], start: usize) -> Result<usize, PatternError> {
+    let mut position = start + 1;
+    if body.get(position) == Some(&b'^') {
+        position += 1;
+    }
+
+    // A `]` right after the opening bracket is a literal.
+    if body.get(position) == Some(&b']') {
+        position += 1;
+    }
+
+    while position < body.len() {
+        match body[position] {
+            b'\\' => position += 2,
+            b'[' if body.get(position + 1) == Some(&b':') => {
+                position = find(body, position + 2, b":]").ok_or(PatternError::MissingClosingBracket)? + 2;
+            }
+            b']' => return Ok(position + 1),
+            _ => position += 1,
+        }
+    }
+
+    Err(PatternError::MissingClosingBracket)
+}
+
+fn read_group_name(input: &[u8], terminator: u8) -> Result<String, PatternError> {
+    let end = input.iter().position(|byte| *byte == terminator).ok_or(PatternError::InvalidGroupName)?;
+    let name = &input[..end];
+
+    let is_valid = name.first().is_some_and(|byte| !byte.is_ascii_digit())
+        && name.iter().all(|byte| byte.is_ascii_alphanumeric() || *byte == b'_');
+    if !is_valid {
+        return Err(PatternError::InvalidGroupName);
+    }
+
+    Ok(String::from_utf8_lossy(name).into_owned())
+}
+
+fn find(haystack: &[u8], start: usize, needle: &[u8]) -> Option<usize> {
+    haystack.get(start..)?.windows(needle.len()).position(|window| window == needle).map(|offset| start + offset)
+}
@@ -0,0 +1,134 @@
+//! `preg_*()` pattern validation and `$matches` inference.
+//!
+//! Literal patterns passed to `preg_match()`, `preg_match_all()`,
+//! `preg_replace_callback()` and `preg_split()` are parsed so that malformed
+//! patterns, which make these functions emit a warning and fail at runtime,
+//! are reported during analysis.
+//!
+//! For `preg_match()` and `preg_match_all()`, the `$matches` variable is then
+//! typed from the pattern's capture groups and the `$flags` argument.
+
+use mago_codex::ttype::union::TUnion;
+use mago_reporting::Annotation;
+use mago_reporting::Issue;
+use mago_span::HasSpan;
+use mago_syntax::cst::Argument;
+use mago_syntax::cst::Expression;
+use mago_syntax::cst::FunctionCall;
+use mago_syntax::cst::Variable;
+
+use crate::code::IssueCode;
+use crate::plugin::context::HookContext;
+use crate::plugin::hook::FunctionCallHook;
+use crate::plugin::hook::HookResult;
+use crate::plugin::libraries::stdlib::regex::get_empty_matches;
+use crate::plugin::libraries::stdlib::regex::get_match_all_type;
+use crate::plugin::libraries::stdlib::regex::get_match_shape;
+use crate::plugin::libraries::stdlib::regex::pattern;
+use crate::plugin::provider::Provider;
+use crate::plugin::provider::ProviderMeta;
+
+const FUNCTIONS: [&str; 4] = ["preg_match", "preg_match_all", "preg_replace_callback", "preg_split"];
+
+#[derive(Default)]
+pub struct PregHook;
+
+impl Provider for PregHook {
+    fn meta() -> &'static ProviderMeta {
+        static META: ProviderMeta = ProviderMeta::new(
+            "php::regex::preg",
+            "preg_*",
+            "Validates literal PCRE patterns and infers the shape of `$matches`.",
+        );
+
+        &META
+    }
+}
+
+impl FunctionCallHook for PregHook {
+    fn after_function_call(&self, call: &FunctionCall<'_>, context: &mut HookContext<'_, '_>) -> HookResult<()> {
+        let Expression::Identifier(identifier) = call.function else {
+            return Ok(());
+        };
+
+        let Some(function_name) = FUNCTIONS
+            .into_iter()
+            .find(|function_name| identifier.value().eq_ignore_ascii_case(function_name.as_bytes()))
+        else {
+            return Ok(());
+        };
+
+        let Some(pattern_argument) = get_argument(call, 0, b"pattern") else {
+            return Ok(());
+        };
+
+        let Some(pattern) =
+            context.get_expression_type(pattern_argument).and_then(TUnion::get_single_literal_string_value)
+        else {
+            return Ok(());
+        };
+
+        let groups = match pattern::parse(pattern) {
+            Ok(pattern) if !pattern.has_branch_reset => pattern.groups,
+            Ok(_) => return Ok(()),
+            Err(error) => {
+                context.report(
+                    IssueCode::InvalidRegexPattern,
+                    Issue::error(format!("Invalid regular expression passed to `{function_name}()`."))
+                        .with_annotation(Annotation::primary(pattern_argument.span()).with_message(error.to_string()))
+                        .with_note(format!(
+                            "`{function_name}()` emits a warning and fails at runtime when given a malformed pattern."
+                        ))
+                        .with_help(
+                            "Fix the pattern; text meant to be matched literally can be escaped with `preg_quote()`.",
+                        ),
+                );
+
+                return Ok(());
+            }
+        };
+
+        let is_match_all = function_name == "preg_match_all";
+        if !is_match_all && function_name != "preg_match" {
+            return Ok(());
+        }
+
+        let Some(Expression::Variable(Variable::Direct(matches_variable))) = get_argument(call, 2, b"matches") else {
+            return Ok(());
+        };
+
+        let flags = match get_argument(call, 3, b"flags") {
+            Some(flags_argument) => {
+                match context.get_expression_type(flags_argument).and_then(TUnion::get_single_literal_int_value) {
+                    Some(flags) => flags,
+                    None => return Ok(()),
+                }
+            }
+            None => 0,
+        };
+
+        // `preg_match()` leaves `$matches` empty when nothing matched; the assertion
+        // provider narrows this union once the call's result is checked.
+        let matches_type = if is_match_all {
+            get_match_all_type(&groups, flags)
+        } else {
+            TUnion::from_vec(vec![get_empty_matches(), get_match_shape(&groups, flags)])
+        };
+
+        context.set_variable_type(matches_variable.name, matches_type);
+
+        Ok(())
+    }
+}
+
+fn get_argument<'arena>(
+    call: &FunctionCall<'arena>,
+    position: usize,
+    name: &[u8],
+) -> Option<&'arena Expression<'arena>> {
+    call.argument_list.arguments.iter().enumerate().find_map(|(index, argument)| match argument {
+        Argument::Positional(argument) if index == position => Some(argument.value),
+        Argument::Named(argument) if argument.name.value == name => Some(argument.value),
+        _ => None,
+    })
+}
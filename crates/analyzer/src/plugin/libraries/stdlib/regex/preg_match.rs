@@ -0,0 +1,63 @@
+//! `preg_match()` assertion provider.
+//!
+//! `preg_match()` fills `$matches` only when the pattern matched, so the
+//! capture-group shape of a literal pattern holds when the call returns a
+//! truthy value, while `$matches` is empty otherwise. Hosts such as
+//! `if (preg_match(...))` or `if (!preg_match(...)) { return; }` consume
+//! this through the existing assertion pipeline.
+
+use mago_codex::assertion::Assertion;
+use mago_word::word;
+
+use crate::plugin::context::InvocationInfo;
+use crate::plugin::context::ProviderContext;
+use crate::plugin::libraries::stdlib::regex::get_capture_groups;
+use crate::plugin::libraries::stdlib::regex::get_empty_matches;
+use crate::plugin::libraries::stdlib::regex::get_match_shape;
+use crate::plugin::provider::Provider;
+use crate::plugin::provider::ProviderMeta;
+use crate::plugin::provider::assertion::FunctionAssertionProvider;
+use crate::plugin::provider::assertion::InvocationAssertions;
+use crate::plugin::provider::function::FunctionTarget;
+
+static META: ProviderMeta = ProviderMeta::new(
+    "php::regex::preg_match",
+    "preg_match",
+    "Narrows `$matches` to the pattern's capture groups when a match is found",
+);
+
+#[derive(Default)]
+pub struct PregMatchAssertionProvider;
+
+impl Provider for PregMatchAssertionProvider {
+    fn meta() -> &'static ProviderMeta {
+        &META
+    }
+}
+
+impl FunctionAssertionProvider for PregMatchAssertionProvider {
+    fn targets() -> FunctionTarget {
+        FunctionTarget::Exact(b"preg_match")
+    }
+
+    fn get_assertions(
+        &self,
+        context: &ProviderContext<'_, '_, '_>,
+        invocation: &InvocationInfo<'_, '_, '_>,
+    ) -> Option<InvocationAssertions> {
+        invocation.get_argument(2, &[b"matches"])?;
+
+        let pattern_type = context.get_expression_type(invocation.get_argument(0, &[b"pattern"])?)?;
+        let groups = get_capture_groups(pattern_type)?;
+
+        let flags = match invocation.get_argument(3, &[b"flags"]) {
+            Some(flags_argument) => context.get_expression_type(flags_argument)?.get_single_literal_int_value()?,
+            None => 0,
+        };
+
+        let mut result = InvocationAssertions::new();
+        result.add_if_true(word("$matches"), vec![Assertion::IsType(get_match_shape(&groups, flags))]);
+        result.add_if_false(word("$matches"), vec![Assertion::IsType(get_empty_matches())]);
+        Some(result)
+    }
+}
@@ -0,0 +1,191 @@
+use mago_allocator::Arena;
+use mago_codex::identifier::function_like::FunctionLikeIdentifier;
+use mago_reporting::Annotation;
+use mago_reporting::Issue;
+use mago_span::Span;
+use mago_syntax::cst::Expression;
+use mago_syntax::cst::Variable;
+
+use crate::code::IssueCode;
+use crate::context::Context;
+use crate::context::block::BlockContext;
+use crate::invocation::Invocation;
+use crate::utils::misc::unwrap_expression;
+
+/// Superglobals holding request and process state.
+const SUPERGLOBALS: [&[u8]; 9] =
+    [b"$GLOBALS", b"$_SERVER", b"$_GET", b"$_POST", b"$_FILES", b"$_COOKIE", b"$_SESSION", b"$_REQUEST", b"$_ENV"];
+
+/// Reports calls to impure functions and methods from a pure or mutation-free scope.
+///
+/// Only statically resolved targets are checked; closures and other callables are analyzed
+/// in their own scope.
+pub(crate) fn check_invocation<A>(
+    context: &mut Context<'_, '_, A>,
+    block_context: &BlockContext<'_>,
+    invocation: &Invocation<'_, '_, '_>,
+) where
+    A: Arena,
+{
+    if !block_context.scope.is_mutation_free() {
+        return;
+    }
+
+    let (Some(identifier), Some(metadata)) =
+        (invocation.target.get_function_like_identifier(), invocation.target.get_function_like_metadata())
+    else {
+        return;
+    };
+
+    if metadata.flags.is_pure() || metadata.flags.is_inferred_pure() || metadata.flags.is_mutation_free() {
+        return;
+    }
+
+    let (code, kind) = match identifier {
+        FunctionLikeIdentifier::Function(_) => (IssueCode::ImpureFunctionCall, "function"),
+        FunctionLikeIdentifier::Method(_, _) => (IssueCode::ImpureMethodCall, "method"),
+        _ => return,
+    };
+
+    let name = invocation.target.guess_name(context);
+    let scope = describe_scope(block_context);
+
+    context.collector.report_with_code(
+        code,
+        Issue::error(format!("Cannot call impure {kind} `{name}` from a {scope} context."))
+            .with_annotation(Annotation::primary(invocation.span).with_message(format!("`{name}` may have side effects.")))
+            .with_note(format!("The {kind} is neither declared `@pure` or `@mutation-free` nor inferred to be pure."))
+            .with_help(format!(
+                "Mark `{name}` as `@pure` if it has no side effects, or remove the purity annotation from the enclosing function/method."
+            )),
+    );
+}
+
+/// Reports a write to an instance property that the current scope is not allowed to mutate.
+///
+/// Mutation-free scopes may not write to any property, while externally mutation-free scopes
+/// may only write to properties of `$this`. Constructors may always initialize `$this`.
+pub(crate) fn check_property_write<A>(
+    context: &mut Context<'_, '_, A>,
+    block_context: &BlockContext<'_>,
+    object: &Expression<'_>,
+    span: Span,
+) where
+    A: Arena,
+{
+    if is_this(object) && is_constructor_scope(block_context) {
+        return;
+    }
+
+    if block_context.scope.is_mutation_free() || (block_context.scope.is_external_mutation_free() && !is_this(object)) {
+        report_property_write(context, block_context, span);
+    }
+}
+
+/// Reports a write to a static property from a scope that is not allowed to mutate external state.
+pub(crate) fn check_static_property_write<A>(
+    context: &mut Context<'_, '_, A>,
+    block_context: &BlockContext<'_>,
+    span: Span,
+) where
+    A: Arena,
+{
+    if block_context.scope.is_external_mutation_free() {
+        report_property_write(context, block_context, span);
+    }
+}
+
+/// Reports a read of a static property from a pure scope.
+pub(crate) fn check_static_property_read<A>(
+    context: &mut Context<'_, '_, A>,
+    block_context: &BlockContext<'_>,
+    span: Span,
+) where
+    A: Arena,
+{
+    if !block_context.scope.is_pure() {
+        return;
+    }
+
+    context.collector.report_with_code(
+        IssueCode::ImpureStaticPropertyAccess,
+        Issue::error("Cannot access static properties inside a pure function or method.")
+            .with_annotation(Annotation::primary(span).with_message("Static property accessed here."))
+            .with_note(
+                "Static properties are shared state that may change between calls, which violates the pure guarantee.",
+            )
+            .with_help(
+                "Pass the value as an argument or remove the `@pure` annotation from the enclosing function/method.",
+            ),
+    );
+}
+
+/// Reports access to global state, through the `global` keyword or a superglobal, from a pure scope.
+pub(crate) fn check_global_access<A>(
+    context: &mut Context<'_, '_, A>,
+    block_context: &BlockContext<'_>,
+    variable_name: &[u8],
+    span: Span,
+    is_global_statement: bool,
+) where
+    A: Arena,
+{
+    if !block_context.scope.is_pure() || !(is_global_statement || SUPERGLOBALS.contains(&variable_name)) {
+        return;
+    }
+
+    let variable_name = String::from_utf8_lossy(variable_name);
+
+    context.collector.report_with_code(
+        IssueCode::ImpureGlobalAccess,
+        Issue::error(format!("Cannot access global variable `{variable_name}` inside a pure function or method."))
+            .with_annotation(Annotation::primary(span).with_message("Global state accessed here."))
+            .with_note("Global variables may change between calls, which violates the pure guarantee.")
+            .with_help(
+                "Pass the value as an argument or remove the `@pure` annotation from the enclosing function/method.",
+            ),
+    );
+}
+
+fn report_property_write<A>(context: &mut Context<'_, '_, A>, block_context: &BlockContext<'_>, span: Span)
+where
+    A: Arena,
+{
+    let scope = describe_scope(block_context);
+
+    context.collector.report_with_code(
+        IssueCode::ImpurePropertyAssignment,
+        Issue::error(format!("Cannot assign to a property from a {scope} context."))
+            .with_annotation(Annotation::primary(span).with_message("Property modified here."))
+            .with_note(if block_context.scope.is_mutation_free() {
+                "Pure, mutation-free, and immutable code may not modify any property, including those of `$this`."
+            } else {
+                "Externally mutation-free code may only modify properties of `$this`."
+            })
+            .with_help(
+                "Return a modified copy instead, or remove the purity annotation from the enclosing function/method.",
+            ),
+    );
+}
+
+fn describe_scope(block_context: &BlockContext<'_>) -> &'static str {
+    if block_context.scope.is_pure() {
+        "pure"
+    } else if block_context.scope.is_mutation_free() {
+        "mutation-free"
+    } else {
+        "externally mutation-free"
+    }
+}
+
+fn is_constructor_scope(block_context: &BlockContext<'_>) -> bool {
+    block_context
+        .scope
+        .get_function_like()
+        .and_then(|function_like| function_like.method_metadata.as_ref())
+        .is_some_and(|method_metadata| method_metadata.is_constructor)
+}
+
+fn is_this(expression: &Expression<'_>) -> bool {
+    matches!(unwrap_expression(expression), Expression::Variable(Variable::Direct(variable)) if variable.name == b"$this")
+}
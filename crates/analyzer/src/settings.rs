@@ -1,5 +1,6 @@
 use mago_algebra::AlgebraThresholds;
 use mago_codex::metadata::class_like::ClassLikeMetadata;
+use mago_codex::metadata::taint::TaintKinds;
 use mago_codex::ttype::combiner::CombinerOptions;
 use mago_php_version::PHPVersion;
 use mago_word::Word;
@@ -34,6 +35,13 @@ pub struct Settings {
     /// Find and report unused definitions (e.g., private methods that are never called). Defaults to `false`.
     pub find_unused_definitions: bool,
 
+    /// Find and report class-likes, functions, constants, class constants and enum cases that
+    /// nothing in the project uses, apart from the configured entry points.
+    ///
+    /// This needs the references of the whole project, so it only runs when every file is
+    /// analyzed together. Defaults to `false`.
+    pub find_unused_symbols: bool,
+
     /// Warn when a function's declared return type contains a branch the body never actually returns
     /// (e.g. `: string|false` on a function that always returns a string). Defaults to `false`.
     pub find_overly_wide_return_types: bool,
@@ -189,6 +197,17 @@ pub struct Settings {
     /// Defaults to `false`.
     pub allow_implicit_pipe_callable_types: bool,
 
+    /// Write inferred types back into the source when fixing missing type hints.
+    ///
+    /// When enabled alongside `check_missing_type_hints`, the edits attached to missing
+    /// parameter and return type hint issues also cover types that cannot be written as a
+    /// native type hint: `@param` and `@return` docblock tags are added for richer types such
+    /// as `list<int>` or array shapes. Untyped parameters without a docblock type fall back
+    /// to the type of their default value.
+    ///
+    /// Defaults to `false`.
+    pub infer_types: bool,
+
     /// Register superglobals (e.g., `$_GET`, `$_POST`, `$_SERVER`) in the analysis context.
     ///
     /// If disabled, super globals won't be available unless explicitly imported using
@@ -283,6 +302,23 @@ pub struct Settings {
     /// Defaults to `true` (impure calls in conditions are allowed).
     pub allow_side_effects_in_conditions: bool,
 
+    /// Taint analysis settings.
+    ///
+    /// When enabled, the analyzer tracks user-controlled data from sources (such as `$_GET`)
+    /// through the variables of each body, and reports it when it reaches a sink (such as `echo`
+    /// or `shell_exec()`) without passing through a matching sanitizer. Across functions, it
+    /// follows the return values of user-defined function-likes and the properties their class
+    /// writes to, using summaries computed when the codebase is populated.
+    ///
+    /// Disabled by default.
+    pub taint: TaintSettings,
+
+    /// Declarations that dead-code removal keeps even when nothing references them.
+    ///
+    /// Framework-invoked code (controllers, event listeners, console commands) is usually
+    /// reached through reflection, so no reference to it appears in the analyzed sources.
+    pub entry_points: EntryPointSettings,
+
     // Performance tuning thresholds
     // Higher values allow deeper analysis at the cost of performance.
     // Lower values improve speed but may reduce precision on complex code.
@@ -395,6 +431,7 @@ impl Settings {
             version,
             find_unused_expressions: true,
             find_unused_definitions: true,
+            find_unused_symbols: false,
             find_overly_wide_return_types: false,
             analyze_dead_code: false,
             memoize_properties: true,
@@ -415,6 +452,7 @@ impl Settings {
             check_closure_missing_type_hints: false,
             check_arrow_function_missing_type_hints: false,
             allow_implicit_pipe_callable_types: false,
+            infer_types: false,
             register_super_globals: true,
             diff: false,
             trust_existence_checks: true,
@@ -424,6 +462,8 @@ impl Settings {
             check_experimental: false,
             check_name_casing: false,
             allow_side_effects_in_conditions: true,
+            taint: TaintSettings::default(),
+            entry_points: EntryPointSettings::default(),
             saturation_complexity_threshold: default_thresholds.saturation_complexity,
             disjunction_complexity_threshold: default_thresholds.disjunction_complexity,
             negation_complexity_threshold: default_thresholds.negation_complexity,
@@ -523,3 +563,121 @@ impl ClassInitializer {
         meta.name == class || meta.all_parent_classes.contains(&class) || meta.all_parent_interfaces.contains(&class)
     }
 }
+
+/// Settings for the taint analysis.
+///
+/// The built-in sources (superglobals), sinks (e.g. `echo`, `exec()`, `mysqli_query()`) and
+/// sanitizers (e.g. `htmlspecialchars()`, `escapeshellarg()`) are always active once enabled;
+/// the entries here extend them with project-specific functions and methods.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TaintSettings {
+    /// Whether taint analysis runs at all. Defaults to `false`.
+    pub enabled: bool,
+    /// Additional functions or methods whose return value is tainted.
+    pub sources: Vec<TaintSource>,
+    /// Additional functions or methods whose arguments must not receive tainted data.
+    pub sinks: Vec<TaintSink>,
+    /// Additional functions or methods whose return value is free of the given taint kinds.
+    pub sanitizers: Vec<TaintSanitizer>,
+}
+
+/// A function (`"name"`) or method (`"Class::method"`) referenced by a taint setting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TaintSymbol {
+    /// Lowercased FQN of the class/interface declaring the method, or `None` for functions.
+    pub class: Option<Word>,
+    /// Lowercased function or method name.
+    pub name: Word,
+}
+
+impl TaintSymbol {
+    /// Parse `"Class::method"` or `"function"`. Returns `None` for empty input
+    /// or empty halves.
+    #[must_use]
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.split_once("::") {
+            Some((class, method)) => {
+                let class = class.trim_start_matches('\\').trim();
+                let method = method.trim();
+                if class.is_empty() || method.is_empty() {
+                    return None;
+                }
+
+                Some(Self {
+                    class: Some(ascii_lowercase_word(class.as_bytes())),
+                    name: ascii_lowercase_word(method.as_bytes()),
+                })
+            }
+            None => {
+                let name = raw.trim_start_matches('\\').trim();
+                if name.is_empty() {
+                    return None;
+                }
+
+                Some(Self { class: None, name: ascii_lowercase_word(name.as_bytes()) })
+            }
+        }
+    }
+}
+
+/// A function or method whose return value carries taint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaintSource {
+    pub symbol: TaintSymbol,
+    /// The kinds of taint introduced by the return value.
+    pub kinds: TaintKinds,
+}
+
+/// A function or method whose arguments must not receive tainted data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaintSink {
+    pub symbol: TaintSymbol,
+    /// The kinds of taint the sink is sensitive to.
+    pub kinds: TaintKinds,
+    /// Zero-based indexes of the sensitive arguments; empty means every argument.
+    pub arguments: Vec<usize>,
+}
+
+/// A function or method that removes taint from the value it returns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaintSanitizer {
+    pub symbol: TaintSymbol,
+    /// The kinds of taint removed.
+    pub kinds: TaintKinds,
+}
+
+/// Declarations treated as used by dead-code removal and unused-symbol detection, regardless
+/// of their references.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EntryPointSettings {
+    /// Lowercased FQNs of attributes that mark a class, function, method, property, constant
+    /// or enum case as an entry point.
+    pub attributes: WordSet,
+    /// Lowercased namespaces, without leading or trailing backslashes, whose declarations
+    /// (including those of nested namespaces) are always kept.
+    pub namespaces: Vec<Word>,
+    /// Top-level symbols (`"name"`) and members (`"Class::member"`) that are always kept.
+    ///
+    /// A kept class also keeps every class-like that extends or implements it.
+    pub symbols: Vec<EntryPointSymbol>,
+}
+
+/// A top-level symbol (`"name"`), or a member (`"Class::member"`), kept by dead-code removal.
+///
+/// Either half may end with `*` to match every name starting with what precedes it, e.g.
+/// `"App\\Controller\\*"` or `"App\\Tests\\*::test*"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EntryPointSymbol {
+    /// Lowercased FQN of the class-like declaring the member, or `None` for top-level symbols.
+    pub class: Option<Word>,
+    /// Lowercased symbol or member name; property names keep their leading `$`.
+    pub name: Word,
+}
+
+impl EntryPointSymbol {
+    /// Parse `"Class::member"` or `"name"`. Returns `None` for empty input or empty halves.
+    #[must_use]
+    pub fn parse(raw: &str) -> Option<Self> {
+        TaintSymbol::parse(raw).map(|symbol| Self { class: symbol.class, name: symbol.name })
+    }
+}
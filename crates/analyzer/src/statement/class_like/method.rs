@@ -76,6 +76,7 @@ impl<'ast, 'arena> Analyzable<'ast, 'arena> for Method<'arena> {
             return Ok(());
         }
 
+        let mut inferred_return_type = None;
         if let MethodBody::Concrete(concrete_body) = &self.body {
             let mut scope =
                 ScopeContext::new(ReferenceOrigin::Symbol((class_like_metadata.name, method_metadata.name)));
@@ -87,7 +88,7 @@ impl<'ast, 'arena> Analyzable<'ast, 'arena> for Method<'arena> {
 
             method_block_context.flags.set_collect_initializations(true);
 
-            analyze_function_like(
+            let inner_artifacts = analyze_function_like(
                 context,
                 artifacts,
                 &mut method_block_context,
@@ -97,6 +98,15 @@ impl<'ast, 'arena> Analyzable<'ast, 'arena> for Method<'arena> {
                 None,
             )?;
 
+            if self.return_type_hint.is_none() {
+                inferred_return_type = missing_type_hints::get_inferred_return_type(
+                    context.codebase,
+                    method_metadata,
+                    &inner_artifacts.inferred_return_types,
+                    method_block_context.flags.has_returned(),
+                );
+            }
+
             let method_key = (class_like_metadata.name, lowercase_method_name);
 
             artifacts
@@ -136,12 +146,14 @@ impl<'ast, 'arena> Analyzable<'ast, 'arena> for Method<'arena> {
         );
 
         // Check for missing type hints
+        let mut inferred_docblock = missing_type_hints::InferredDocblock::new(self.span().start_offset());
         for (i, parameter) in self.parameter_list.parameters.iter().enumerate() {
             missing_type_hints::check_parameter_type_hint(
                 context,
                 Some(class_like_metadata),
                 method_metadata,
                 parameter,
+                Some(&mut inferred_docblock),
             );
 
             missing_type_hints::check_imprecise_parameter_type_hint(context, method_metadata, parameter, i);
@@ -153,9 +165,14 @@ impl<'ast, 'arena> Analyzable<'ast, 'arena> for Method<'arena> {
             method_metadata,
             self.name.value,
             self.return_type_hint.as_ref(),
+            inferred_return_type.as_ref(),
+            self.parameter_list.span().end_offset(),
             self.span(),
+            Some(&mut inferred_docblock),
         );
 
+        inferred_docblock.report(context);
+
         missing_type_hints::check_imprecise_return_type_hint(
             context,
             method_metadata,
@@ -41,6 +41,15 @@ struct CheckableMember {
     is_property: bool,
 }
 
+/// An unused member found by the codebase-wide analysis.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct UnusedMember {
+    pub symbol_id: SymbolIdentifier,
+    /// The span of the member's name (the variable, for properties).
+    pub span: Span,
+    pub is_property: bool,
+}
+
 #[derive(Debug)]
 pub(crate) struct UnusedMemberSpans {
     pub unused_methods: HashSet<Span>,
@@ -252,46 +261,25 @@ pub(crate) fn find_unused_member_spans(
     codebase: &CodebaseMetadata,
     symbol_references: &SymbolReferences,
 ) -> UnusedMemberSpans {
-    let mut referencing_symbols = HashMap::<SymbolIdentifier, HashSet<SymbolIdentifier>>::default();
-    let mut externally_referenced = HashSet::<SymbolIdentifier>::default();
-    let mut read_properties = HashSet::<SymbolIdentifier>::default();
-    symbol_references.for_each_reference(|source, target, kind| {
-        if matches!(kind, SymbolReferenceKind::Body | SymbolReferenceKind::Signature) {
-            match source {
-                ReferenceOrigin::Symbol(source) => {
-                    referencing_symbols.entry(target).or_default().insert(source);
-                }
-                ReferenceOrigin::File(_) => {
-                    externally_referenced.insert(target);
-                }
-            }
-        } else if kind == SymbolReferenceKind::PropertyRead {
-            read_properties.insert(target);
-        }
-    });
+    let index = ReferenceIndex::new(symbol_references);
 
     let mut spans = UnusedMemberSpans {
         unused_methods: HashSet::default(),
         unused_properties: HashSet::default(),
         read_properties: HashSet::default(),
     };
-    for (class_name, class_like_metadata) in &codebase.class_likes {
-        let checkable_members = collect_checkable_members(*class_name, class_like_metadata, codebase);
-        let unused_members =
-            find_unused_members_from_index(&checkable_members, &referencing_symbols, &externally_referenced);
-        for member in checkable_members {
-            if unused_members.contains(&member.symbol_id) {
-                if member.is_property {
-                    spans.unused_properties.insert(member.span);
-                } else {
-                    spans.unused_methods.insert(member.span);
-                }
-            }
+    for member in index.find_unused_members(codebase) {
+        if member.is_property {
+            spans.unused_properties.insert(member.span);
+        } else {
+            spans.unused_methods.insert(member.span);
         }
+    }
 
+    for (class_name, class_like_metadata) in &codebase.class_likes {
         for (property_name, property) in &class_like_metadata.properties {
             let symbol_id = (*class_name, *property_name);
-            if read_properties.contains(&symbol_id)
+            if index.read_properties.contains(&symbol_id)
                 && let Some(property_span) = property.name_span.or(property.span)
             {
                 spans.read_properties.insert(property_span);
@@ -302,6 +290,75 @@ pub(crate) fn find_unused_member_spans(
     spans
 }
 
+/// Finds the unused members of every class-like in the codebase.
+///
+/// This applies the same rules as the per-class check (private members, and protected
+/// members of final classes), but works from the complete set of symbol references.
+pub(crate) fn find_unused_members_in_codebase(
+    codebase: &CodebaseMetadata,
+    symbol_references: &SymbolReferences,
+) -> Vec<UnusedMember> {
+    ReferenceIndex::new(symbol_references).find_unused_members(codebase)
+}
+
+/// Body and signature references grouped by their target.
+struct ReferenceIndex {
+    referencing_symbols: HashMap<SymbolIdentifier, HashSet<SymbolIdentifier>>,
+    externally_referenced: HashSet<SymbolIdentifier>,
+    read_properties: HashSet<SymbolIdentifier>,
+}
+
+impl ReferenceIndex {
+    fn new(symbol_references: &SymbolReferences) -> Self {
+        let mut index = Self {
+            referencing_symbols: HashMap::default(),
+            externally_referenced: HashSet::default(),
+            read_properties: HashSet::default(),
+        };
+
+        symbol_references.for_each_reference(|source, target, kind| {
+            if matches!(kind, SymbolReferenceKind::Body | SymbolReferenceKind::Signature) {
+                match source {
+                    ReferenceOrigin::Symbol(source) => {
+                        index.referencing_symbols.entry(target).or_default().insert(source);
+                    }
+                    ReferenceOrigin::File(_) => {
+                        index.externally_referenced.insert(target);
+                    }
+                }
+            } else if kind == SymbolReferenceKind::PropertyRead {
+                index.read_properties.insert(target);
+            }
+        });
+
+        index
+    }
+
+    fn find_unused_members(&self, codebase: &CodebaseMetadata) -> Vec<UnusedMember> {
+        let mut unused = Vec::new();
+        for (class_name, class_like_metadata) in &codebase.class_likes {
+            let checkable_members = collect_checkable_members(*class_name, class_like_metadata, codebase);
+            let unused_members = find_unused_members_from_index(
+                &checkable_members,
+                &self.referencing_symbols,
+                &self.externally_referenced,
+            );
+
+            for member in checkable_members {
+                if unused_members.contains(&member.symbol_id) {
+                    unused.push(UnusedMember {
+                        symbol_id: member.symbol_id,
+                        span: member.span,
+                        is_property: member.is_property,
+                    });
+                }
+            }
+        }
+
+        unused
+    }
+}
+
 fn find_unused_members_from_index(
     checkable_members: &[CheckableMember],
     referencing_symbols: &HashMap<SymbolIdentifier, HashSet<SymbolIdentifier>>,
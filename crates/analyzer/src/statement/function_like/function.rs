@@ -97,10 +97,11 @@ impl<'ast, 'arena> Analyzable<'ast, 'arena> for Function<'arena> {
         scope.set_class_like(block_context.scope.get_class_like());
         scope.set_function_like(Some(function_metadata));
 
-        analyze_function_like(
+        let mut function_block_context = BlockContext::new(scope, context.settings.register_super_globals);
+        let inner_artifacts = analyze_function_like(
             context,
             artifacts,
-            &mut BlockContext::new(scope, context.settings.register_super_globals),
+            &mut function_block_context,
             function_metadata,
             &self.parameter_list,
             FunctionLikeBody::Statements(self.body.statements.as_slice(), self.body.span()),
@@ -134,24 +135,42 @@ impl<'ast, 'arena> Analyzable<'ast, 'arena> for Function<'arena> {
         }
 
         // Check for missing type hints
+        let mut inferred_docblock = missing_type_hints::InferredDocblock::new(self.span().start_offset());
         for parameter in &self.parameter_list.parameters {
             missing_type_hints::check_parameter_type_hint(
                 context,
                 None, // Functions don't have a class context
                 function_metadata,
                 parameter,
+                Some(&mut inferred_docblock),
             );
         }
 
+        let inferred_return_type = if self.return_type_hint.is_none() {
+            missing_type_hints::get_inferred_return_type(
+                context.codebase,
+                function_metadata,
+                &inner_artifacts.inferred_return_types,
+                function_block_context.flags.has_returned(),
+            )
+        } else {
+            None
+        };
+
         missing_type_hints::check_return_type_hint(
             context,
             None, // Functions don't have a class context
             function_metadata,
             self.name.value,
             self.return_type_hint.as_ref(),
+            inferred_return_type.as_ref(),
+            self.parameter_list.span().end_offset(),
             self.span(),
+            Some(&mut inferred_docblock),
         );
 
+        inferred_docblock.report(context);
+
         // Check for imprecise type hints (bare `array` or `iterable`)
         for (i, parameter) in self.parameter_list.parameters.iter().enumerate() {
             missing_type_hints::check_imprecise_parameter_type_hint(context, function_metadata, parameter, i);
@@ -62,6 +62,7 @@ use crate::statement::analyze_statements;
 use crate::statement::attributes::AttributeTarget;
 use crate::statement::attributes::analyze_attributes;
 use crate::statement::r#return::handle_return_value;
+use crate::taint;
 use crate::utils::expression::get_variable_id;
 
 pub mod function;
@@ -100,6 +101,12 @@ where
     );
 
     let mut artifacts = AnalysisArtifacts::new();
+    if context.settings.taint.enabled {
+        taint::inherit_captured_taint(parent_artifacts, &mut artifacts, block_context, function_like_metadata);
+        if let FunctionLikeBody::Statements(statements, _) = body {
+            taint::record_unconditional_assignments(&mut artifacts, statements);
+        }
+    }
 
     if let Some(return_type) = &function_like_metadata.return_type_metadata {
         report_undefined_type_references(context, return_type);
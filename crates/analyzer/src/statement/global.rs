@@ -42,6 +42,8 @@ impl<'ast, 'arena> Analyzable<'ast, 'arena> for Global<'arena> {
 
         for variable in &self.variables {
             if let Some(var_id) = get_variable_id(variable) {
+                crate::purity::check_global_access(context, block_context, var_id, variable.span(), true);
+
                 block_context.locals.insert(Word::from(var_id), Rc::new(get_mixed()));
             }
         }
@@ -24,6 +24,7 @@ use crate::error::AnalysisError;
 use crate::expression::assignment::PropertyWriteKind;
 use crate::expression::assignment::assign_to_expression;
 use crate::statement::r#loop;
+use crate::taint;
 use crate::utils::expression::get_block_expression_id;
 
 impl<'ast, 'arena> Analyzable<'ast, 'arena> for Foreach<'arena> {
@@ -134,6 +135,12 @@ impl<'ast, 'arena> Analyzable<'ast, 'arena> for Foreach<'arena> {
             loop_block_context.references_to_external_scope.insert(Word::from(direct_variable.name));
         }
 
+        if let Some(key_expression) = self.target.key() {
+            taint::analyze_foreach_target(context, &loop_block_context, artifacts, iterator, key_expression);
+        }
+
+        taint::analyze_foreach_target(context, &loop_block_context, artifacts, iterator, value_expression);
+
         let loop_scope = LoopScope::new(self.span(), block_context.locals.clone(), None);
 
         let (inner_loop_block_context, loop_scope) = r#loop::analyze(
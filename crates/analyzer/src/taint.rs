@@ -0,0 +1,969 @@
+use std::rc::Rc;
+
+use foldhash::HashMap;
+use foldhash::HashSet;
+
+use mago_allocator::Arena;
+use mago_codex::identifier::function_like::FunctionLikeIdentifier;
+use mago_codex::metadata::function_like::FunctionLikeKind;
+use mago_codex::metadata::function_like::FunctionLikeMetadata;
+use mago_codex::metadata::taint::BUILTIN_PROPAGATORS;
+use mago_codex::metadata::taint::BUILTIN_SANITIZERS;
+use mago_codex::metadata::taint::BUILTIN_SOURCES;
+use mago_codex::metadata::taint::BUILTIN_UNESCAPES;
+use mago_codex::metadata::taint::INPUT_SUPERGLOBALS;
+use mago_codex::metadata::taint::TaintKinds;
+use mago_codex::metadata::taint::USER_CONTROLLED_SERVER_KEYS;
+use mago_codex::ttype::atomic::TAtomic;
+use mago_codex::ttype::atomic::object::TObject;
+use mago_codex::ttype::atomic::scalar::TScalar;
+use mago_codex::ttype::union::TUnion;
+use mago_reporting::Annotation;
+use mago_reporting::Issue;
+use mago_span::HasSpan;
+use mago_span::Span;
+use mago_syntax::cst::Access;
+use mago_syntax::cst::ArrayElement;
+use mago_syntax::cst::Assignment;
+use mago_syntax::cst::AssignmentOperator;
+use mago_syntax::cst::Call;
+use mago_syntax::cst::ClassLikeMemberSelector;
+use mago_syntax::cst::CompositeString;
+use mago_syntax::cst::Expression;
+use mago_syntax::cst::Literal;
+use mago_syntax::cst::MatchArm;
+use mago_syntax::cst::NullSafePropertyAccess;
+use mago_syntax::cst::PropertyAccess;
+use mago_syntax::cst::Statement;
+use mago_syntax::cst::StringPart;
+use mago_syntax::cst::UnaryPrefixOperator;
+use mago_syntax::cst::Variable;
+use mago_word::Word;
+use mago_word::WordMap;
+use mago_word::ascii_lowercase_word;
+use mago_word::word;
+
+use crate::artifacts::AnalysisArtifacts;
+use crate::artifacts::get_expression_range;
+use crate::code::IssueCode;
+use crate::context::Context;
+use crate::context::block::BlockContext;
+use crate::invocation::Invocation;
+use crate::invocation::InvocationArgument;
+use crate::invocation::InvocationTarget;
+use crate::settings::TaintSymbol;
+use crate::utils::expression::get_block_expression_id;
+use crate::utils::misc::unwrap_expression;
+
+/// The maximum number of flow steps highlighted when reporting a tainted sink.
+const MAX_REPORTED_STEPS: usize = 16;
+
+/// Built-in sinks, and the zero-based indexes of their sensitive arguments (empty means all).
+const BUILTIN_SINKS: [(Option<&str>, &str, TaintKinds, &[usize]); 46] = [
+    (None, "exec", TaintKinds::SHELL, &[0]),
+    (None, "shell_exec", TaintKinds::SHELL, &[0]),
+    (None, "system", TaintKinds::SHELL, &[0]),
+    (None, "passthru", TaintKinds::SHELL, &[0]),
+    (None, "popen", TaintKinds::SHELL, &[0]),
+    (None, "proc_open", TaintKinds::SHELL, &[0]),
+    (None, "pcntl_exec", TaintKinds::SHELL, &[0, 1]),
+    (None, "mysqli_query", TaintKinds::SQL, &[1]),
+    (None, "mysqli_real_query", TaintKinds::SQL, &[1]),
+    (None, "mysqli_multi_query", TaintKinds::SQL, &[1]),
+    (None, "mysqli_prepare", TaintKinds::SQL, &[1]),
+    (None, "pg_query", TaintKinds::SQL, &[]),
+    (None, "pg_send_query", TaintKinds::SQL, &[1]),
+    (Some("mysqli"), "query", TaintKinds::SQL, &[0]),
+    (Some("mysqli"), "real_query", TaintKinds::SQL, &[0]),
+    (Some("mysqli"), "multi_query", TaintKinds::SQL, &[0]),
+    (Some("mysqli"), "prepare", TaintKinds::SQL, &[0]),
+    (Some("pdo"), "query", TaintKinds::SQL, &[0]),
+    (Some("pdo"), "exec", TaintKinds::SQL, &[0]),
+    (Some("pdo"), "prepare", TaintKinds::SQL, &[0]),
+    (Some("sqlite3"), "query", TaintKinds::SQL, &[0]),
+    (Some("sqlite3"), "exec", TaintKinds::SQL, &[0]),
+    (Some("sqlite3"), "prepare", TaintKinds::SQL, &[0]),
+    (None, "unserialize", TaintKinds::UNSERIALIZE, &[0]),
+    (None, "header", TaintKinds::HEADER, &[0]),
+    (None, "setcookie", TaintKinds::COOKIE, &[0, 1]),
+    (None, "setrawcookie", TaintKinds::COOKIE, &[0, 1]),
+    (None, "file_get_contents", TaintKinds::FILE.union(TaintKinds::SSRF), &[0]),
+    (None, "fopen", TaintKinds::FILE.union(TaintKinds::SSRF), &[0]),
+    (None, "file", TaintKinds::FILE.union(TaintKinds::SSRF), &[0]),
+    (None, "readfile", TaintKinds::FILE.union(TaintKinds::SSRF), &[0]),
+    (None, "file_put_contents", TaintKinds::FILE, &[0]),
+    (None, "unlink", TaintKinds::FILE, &[0]),
+    (None, "copy", TaintKinds::FILE, &[0, 1]),
+    (None, "rename", TaintKinds::FILE, &[0, 1]),
+    (None, "mkdir", TaintKinds::FILE, &[0]),
+    (None, "rmdir", TaintKinds::FILE, &[0]),
+    (None, "curl_init", TaintKinds::SSRF, &[0]),
+    (None, "printf", TaintKinds::HTML, &[]),
+    (None, "vprintf", TaintKinds::HTML, &[]),
+    (None, "ldap_search", TaintKinds::LDAP, &[1, 2]),
+    (None, "ldap_list", TaintKinds::LDAP, &[1, 2]),
+    (Some("domxpath"), "query", TaintKinds::XPATH, &[0]),
+    (Some("domxpath"), "evaluate", TaintKinds::XPATH, &[0]),
+    (None, "call_user_func", TaintKinds::CALLABLE, &[0]),
+    (None, "call_user_func_array", TaintKinds::CALLABLE, &[0]),
+];
+
+/// The issue code, a short description, and a remediation hint for each taint kind.
+const KIND_REPORTS: [(TaintKinds, IssueCode, &str, &str); 16] = [
+    (
+        TaintKinds::HTML,
+        IssueCode::TaintedHtml,
+        "HTML",
+        "Escape the value with `htmlspecialchars()` before writing it to the response.",
+    ),
+    (
+        TaintKinds::HAS_QUOTES,
+        IssueCode::TaintedTextWithQuotes,
+        "text containing quotes",
+        "Escape quotes, for example with `htmlspecialchars($value, ENT_QUOTES)`.",
+    ),
+    (
+        TaintKinds::SQL,
+        IssueCode::TaintedSql,
+        "SQL",
+        "Use a prepared statement with bound parameters instead of building the query from input.",
+    ),
+    (TaintKinds::SHELL, IssueCode::TaintedShell, "shell command", "Escape the value with `escapeshellarg()`."),
+    (
+        TaintKinds::CALLABLE,
+        IssueCode::TaintedCallable,
+        "callable",
+        "Map the input to an allow-list of callables instead of calling it directly.",
+    ),
+    (
+        TaintKinds::UNSERIALIZE,
+        IssueCode::TaintedUnserialize,
+        "serialized data",
+        "Use `json_decode()` for untrusted input, or pass `['allowed_classes' => false]`.",
+    ),
+    (
+        TaintKinds::INCLUDE,
+        IssueCode::TaintedInclude,
+        "include path",
+        "Map the input to an allow-list of files instead of including it directly.",
+    ),
+    (TaintKinds::EVAL, IssueCode::TaintedEval, "code", "Never evaluate user-controlled input."),
+    (TaintKinds::LDAP, IssueCode::TaintedLdap, "LDAP filter", "Escape the value with `ldap_escape()`."),
+    (
+        TaintKinds::FILE,
+        IssueCode::TaintedFile,
+        "file path",
+        "Validate the path against an allow-list, or strip directories with `basename()`.",
+    ),
+    (TaintKinds::HEADER, IssueCode::TaintedHeader, "header", "Validate the value before sending it as a header."),
+    (TaintKinds::COOKIE, IssueCode::TaintedCookie, "cookie", "Validate the value before storing it in a cookie."),
+    (
+        TaintKinds::SSRF,
+        IssueCode::TaintedSsrf,
+        "URL",
+        "Validate the URL's scheme and host against an allow-list before requesting it.",
+    ),
+    (TaintKinds::XPATH, IssueCode::TaintedXpath, "XPath expression", "Escape or validate the value before querying."),
+    (
+        TaintKinds::USER_SECRET,
+        IssueCode::TaintedUserSecret,
+        "user secret",
+        "Make sure secrets are never written to this sink.",
+    ),
+    (
+        TaintKinds::SYSTEM_SECRET,
+        IssueCode::TaintedSystemSecret,
+        "system secret",
+        "Make sure secrets are never written to this sink.",
+    ),
+];
+
+/// A single step in the flow of tainted data, from its source towards a sink.
+#[derive(Debug)]
+struct TaintStep {
+    span: Span,
+    message: String,
+    previous: Option<Rc<TaintStep>>,
+}
+
+/// Taint carried by a value, along with the path it took from its source.
+#[derive(Debug, Clone)]
+pub(crate) struct Taint {
+    kinds: TaintKinds,
+    step: Rc<TaintStep>,
+}
+
+impl Taint {
+    fn source(kinds: TaintKinds, span: Span, message: String) -> Self {
+        Self { kinds, step: Rc::new(TaintStep { span, message, previous: None }) }
+    }
+
+    fn then(&self, kinds: TaintKinds, span: Span, message: String) -> Self {
+        Self { kinds, step: Rc::new(TaintStep { span, message, previous: Some(Rc::clone(&self.step)) }) }
+    }
+
+    /// Combines two taints, keeping the kinds of both and the flow of the first.
+    fn merge(self, other: Option<Taint>) -> Self {
+        match other {
+            Some(other) => Self { kinds: self.kinds | other.kinds, step: self.step },
+            None => self,
+        }
+    }
+
+    fn steps(&self) -> Vec<&TaintStep> {
+        let mut steps = Vec::new();
+        let mut current = Some(&*self.step);
+        while let Some(step) = current {
+            steps.push(step);
+            current = step.previous.as_deref();
+        }
+
+        steps.reverse();
+        if steps.len() > MAX_REPORTED_STEPS {
+            let skipped = steps.len() - MAX_REPORTED_STEPS;
+            steps.drain(1..=skipped);
+        }
+
+        steps
+    }
+}
+
+/// Taint tracked through a single function-like body.
+///
+/// The state follows the body in program order, but is not split at branches or loops: a variable
+/// is considered tainted after any branch taints it, and only assignments that are direct
+/// statements of the body replace its taint.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TaintState {
+    variables: WordMap<Taint>,
+    expressions: HashMap<(u32, u32), Taint>,
+    unconditional_assignments: HashSet<(u32, u32)>,
+}
+
+/// Records the assignments that are direct statements of a body, and therefore always execute.
+pub(crate) fn record_unconditional_assignments(artifacts: &mut AnalysisArtifacts, statements: &[Statement<'_>]) {
+    for statement in statements {
+        match statement {
+            Statement::Namespace(namespace) => {
+                record_unconditional_assignments(artifacts, namespace.statements().as_slice());
+            }
+            Statement::Block(block) => {
+                record_unconditional_assignments(artifacts, block.statements.as_slice());
+            }
+            Statement::Expression(expression_statement) => {
+                if let Expression::Assignment(assignment) = unwrap_expression(expression_statement.expression) {
+                    artifacts.taint.unconditional_assignments.insert(get_expression_range(assignment));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Makes the tainted variables captured from the enclosing scope visible to a closure or arrow function.
+///
+/// Only the variables the function-like captures are inherited: the `use` variables of a closure, or
+/// the enclosing variables an arrow function refers to.
+pub(crate) fn inherit_captured_taint(
+    parent_artifacts: &AnalysisArtifacts,
+    artifacts: &mut AnalysisArtifacts,
+    block_context: &BlockContext<'_>,
+    function_like_metadata: &FunctionLikeMetadata,
+) {
+    if !matches!(function_like_metadata.kind, FunctionLikeKind::Closure | FunctionLikeKind::ArrowFunction) {
+        return;
+    }
+
+    for (variable_id, taint) in &parent_artifacts.taint.variables {
+        if block_context.locals.contains_key(variable_id) {
+            artifacts.taint.variables.insert(*variable_id, taint.clone());
+        }
+    }
+
+    for parameter in &function_like_metadata.parameters {
+        artifacts.taint.variables.remove(&parameter.get_name().0);
+    }
+}
+
+/// Propagates taint from the right-hand side of an assignment to its target.
+pub(crate) fn analyze_assignment<'arena, A>(
+    context: &Context<'_, 'arena, A>,
+    block_context: &BlockContext<'_>,
+    artifacts: &mut AnalysisArtifacts,
+    assignment: &Assignment<'arena>,
+) where
+    A: Arena,
+{
+    if !context.settings.taint.enabled {
+        return;
+    }
+
+    let mut taint = get_expression_taint(context, block_context, artifacts, assignment.rhs);
+    let is_compound = !matches!(assignment.operator, AssignmentOperator::Assign(_));
+    if is_compound {
+        let existing = get_expression_taint(context, block_context, artifacts, assignment.lhs);
+        taint = match taint {
+            Some(taint) => Some(taint.merge(existing)),
+            None => existing,
+        };
+    }
+
+    let overwrites =
+        !is_compound && artifacts.taint.unconditional_assignments.contains(&get_expression_range(assignment));
+
+    assign_taint(context, block_context, artifacts, assignment.lhs, taint, overwrites);
+}
+
+/// Propagates the taint of an iterated value to the `foreach` key and value targets.
+pub(crate) fn analyze_foreach_target<'arena, A>(
+    context: &Context<'_, 'arena, A>,
+    block_context: &BlockContext<'_>,
+    artifacts: &mut AnalysisArtifacts,
+    iterator: &Expression<'arena>,
+    target: &Expression<'arena>,
+) where
+    A: Arena,
+{
+    if !context.settings.taint.enabled {
+        return;
+    }
+
+    let taint = get_expression_taint(context, block_context, artifacts, iterator);
+
+    assign_taint(context, block_context, artifacts, target, taint, false);
+}
+
+fn assign_taint<'arena, A>(
+    context: &Context<'_, 'arena, A>,
+    block_context: &BlockContext<'_>,
+    artifacts: &mut AnalysisArtifacts,
+    target: &Expression<'arena>,
+    taint: Option<Taint>,
+    overwrites: bool,
+) where
+    A: Arena,
+{
+    let target = unwrap_expression(target);
+
+    let elements = match target {
+        Expression::List(list) => Some(&list.elements),
+        Expression::Array(array) => Some(&array.elements),
+        Expression::LegacyArray(array) => Some(&array.elements),
+        _ => None,
+    };
+
+    if let Some(elements) = elements {
+        for element in elements.iter() {
+            if let Some(value) = element.get_value() {
+                assign_taint(context, block_context, artifacts, value, taint.clone(), overwrites);
+            }
+        }
+
+        return;
+    }
+
+    let target = match target {
+        Expression::UnaryPrefix(prefix) if matches!(prefix.operator, UnaryPrefixOperator::Reference(_)) => {
+            prefix.operand
+        }
+        Expression::ArrayAppend(append) => append.array,
+        _ => target,
+    };
+
+    let Some(target_id) = get_block_expression_id(target, context, block_context) else {
+        return;
+    };
+
+    match taint {
+        Some(taint) => {
+            let taint = taint.then(taint.kinds, target.span(), format!("assigned to `{target_id}`"));
+            let taint = match artifacts.taint.variables.remove(&target_id) {
+                Some(existing) if !overwrites => taint.merge(Some(existing)),
+                _ => taint,
+            };
+
+            artifacts.taint.variables.insert(target_id, taint);
+        }
+        None if overwrites => {
+            artifacts.taint.variables.remove(&target_id);
+        }
+        None => {}
+    }
+}
+
+/// Checks a value passed to a language construct such as `echo`, `include` or `eval`.
+pub(crate) fn analyze_construct_input<'arena, A>(
+    context: &mut Context<'_, 'arena, A>,
+    block_context: &BlockContext<'_>,
+    artifacts: &AnalysisArtifacts,
+    construct_kind: &str,
+    construct_keyword: Span,
+    input: &Expression<'arena>,
+) where
+    A: Arena,
+{
+    if !context.settings.taint.enabled {
+        return;
+    }
+
+    let sink_kinds = match construct_kind {
+        "echo" | "print" | "exit" | "die" => TaintKinds::HTML,
+        "include" | "include_once" | "require" | "require_once" => TaintKinds::INCLUDE,
+        "eval" => TaintKinds::EVAL,
+        _ => return,
+    };
+
+    if let Some(taint) = get_expression_taint(context, block_context, artifacts, input) {
+        report_tainted_sink(
+            context,
+            &taint,
+            sink_kinds,
+            input.span(),
+            construct_keyword,
+            &format!("`{construct_kind}`"),
+        );
+    }
+}
+
+/// Checks the interpolated parts of a shell execution string (`` `...` ``).
+pub(crate) fn analyze_shell_execute<'arena, A>(
+    context: &mut Context<'_, 'arena, A>,
+    block_context: &BlockContext<'_>,
+    artifacts: &AnalysisArtifacts,
+    string: &CompositeString<'arena>,
+) where
+    A: Arena,
+{
+    if !context.settings.taint.enabled || !matches!(string, CompositeString::ShellExecute(_)) {
+        return;
+    }
+
+    for part in string.parts().iter() {
+        let part_expression = match part {
+            StringPart::Literal(_) => continue,
+            StringPart::Expression(expression) => *expression,
+            StringPart::BracedExpression(braced) => braced.expression,
+        };
+
+        if let Some(taint) = get_expression_taint(context, block_context, artifacts, part_expression) {
+            report_tainted_sink(
+                context,
+                &taint,
+                TaintKinds::SHELL,
+                part_expression.span(),
+                string.span(),
+                "a shell command",
+            );
+        }
+    }
+}
+
+/// Checks the arguments of an invocation against its sinks, and computes the taint of its result.
+///
+/// Function-likes with a body return the taint of the arguments their summary passes on. Those
+/// without one only pass the taint of their arguments through when they are known sanitizers or
+/// propagators, declare `@taint-escape` or `@taint-unescape`, or are configured sanitizers.
+pub(crate) fn analyze_invocation<'arena, A>(
+    context: &mut Context<'_, 'arena, A>,
+    block_context: &BlockContext<'_>,
+    artifacts: &mut AnalysisArtifacts,
+    invocation: &Invocation<'_, '_, 'arena>,
+) where
+    A: Arena,
+{
+    if !context.settings.taint.enabled {
+        return;
+    }
+
+    let metadata = invocation.target.get_function_like_metadata();
+    let summary = metadata.filter(|metadata| metadata.taint.has_return_summary());
+    let symbol = get_invocation_symbol(&invocation.target);
+    let display_name = invocation.target.guess_name(context);
+
+    let mut source_kinds =
+        metadata.map_or_else(TaintKinds::empty, |metadata| metadata.taint.sources | metadata.taint.returned_sources);
+    let mut escapes = metadata.map_or_else(TaintKinds::empty, |metadata| metadata.taint.escapes);
+    let mut unescapes = metadata.map_or_else(TaintKinds::empty, |metadata| metadata.taint.unescapes);
+    let mut passes_arguments = !escapes.is_empty() || !unescapes.is_empty();
+    if let Some(symbol) = symbol {
+        for (class, name, kinds) in BUILTIN_SOURCES {
+            if symbol_matches_builtin(context, symbol, class, name) {
+                source_kinds |= kinds;
+            }
+        }
+
+        for (class, name, kinds) in BUILTIN_SANITIZERS {
+            if symbol_matches_builtin(context, symbol, class, name) {
+                escapes |= kinds;
+                passes_arguments = true;
+            }
+        }
+
+        for (class, name, kinds) in BUILTIN_UNESCAPES {
+            if symbol_matches_builtin(context, symbol, class, name) {
+                unescapes |= kinds;
+                passes_arguments = true;
+            }
+        }
+
+        passes_arguments |=
+            BUILTIN_PROPAGATORS.iter().any(|(class, name)| symbol_matches_builtin(context, symbol, *class, name));
+
+        for source in &context.settings.taint.sources {
+            if symbol_matches(context, symbol, source.symbol) {
+                source_kinds |= source.kinds;
+            }
+        }
+
+        for sanitizer in &context.settings.taint.sanitizers {
+            if symbol_matches(context, symbol, sanitizer.symbol) {
+                escapes |= sanitizer.kinds;
+                passes_arguments = true;
+            }
+        }
+    }
+
+    let mut argument_taint: Option<Taint> = None;
+    for (index, argument) in invocation.arguments_source.iter_arguments().enumerate() {
+        let Some(value) = argument.value() else {
+            continue;
+        };
+
+        let Some(taint) = get_expression_taint(context, block_context, artifacts, value) else {
+            continue;
+        };
+
+        let parameter_name = metadata.and_then(|metadata| get_argument_parameter_name(metadata, index, &argument));
+
+        let mut sink_kinds = TaintKinds::empty();
+        if let Some(metadata) = metadata
+            && let Some(parameter_name) = parameter_name
+        {
+            sink_kinds |= metadata.taint.get_sink(parameter_name);
+        }
+
+        if let Some(symbol) = symbol {
+            for (class, name, kinds, arguments) in BUILTIN_SINKS {
+                if (arguments.is_empty() || arguments.contains(&index))
+                    && symbol_matches_builtin(context, symbol, class, name)
+                {
+                    sink_kinds |= kinds;
+                }
+            }
+
+            for sink in &context.settings.taint.sinks {
+                if (sink.arguments.is_empty() || sink.arguments.contains(&index))
+                    && symbol_matches(context, symbol, sink.symbol)
+                {
+                    sink_kinds |= sink.kinds;
+                }
+            }
+        }
+
+        report_tainted_sink(context, &taint, sink_kinds, value.span(), invocation.span, &format!("`{display_name}()`"));
+
+        let returned_kinds = match summary {
+            Some(summary) => {
+                parameter_name.map_or_else(TaintKinds::empty, |name| summary.taint.get_returned_parameter(name))
+            }
+            None if passes_arguments => TaintKinds::ALL,
+            None => TaintKinds::empty(),
+        };
+
+        let kinds = taint.kinds.intersection(returned_kinds);
+        if kinds.is_empty() {
+            continue;
+        }
+
+        let taint = Taint { kinds, step: taint.step };
+        argument_taint = Some(match argument_taint {
+            Some(existing) => existing.merge(Some(taint)),
+            None => taint,
+        });
+    }
+
+    let mut result = None;
+    if !source_kinds.is_empty() {
+        result =
+            Some(Taint::source(source_kinds, invocation.span, format!("tainted data returned by `{display_name}()`")));
+    }
+
+    if let Some(argument_taint) = argument_taint {
+        let kinds = argument_taint.kinds.difference(escapes) | unescapes;
+        let message = if escapes.intersects(argument_taint.kinds) {
+            format!("sanitized by `{display_name}()`")
+        } else {
+            format!("passed through `{display_name}()`")
+        };
+
+        let propagated = argument_taint.then(kinds, invocation.span, message);
+        result = Some(match result {
+            Some(result) => result.merge(Some(propagated)),
+            None => propagated,
+        });
+    }
+
+    if let Some(result) = result {
+        let range = get_expression_range(&invocation.span);
+        let result = match artifacts.taint.expressions.remove(&range) {
+            Some(existing) => existing.merge(Some(result)),
+            None => result,
+        };
+
+        artifacts.taint.expressions.insert(range, result);
+    }
+}
+
+/// Returns the taint carried by the given expression, if any.
+pub(crate) fn get_expression_taint<'arena, A>(
+    context: &Context<'_, 'arena, A>,
+    block_context: &BlockContext<'_>,
+    artifacts: &AnalysisArtifacts,
+    expression: &Expression<'arena>,
+) -> Option<Taint>
+where
+    A: Arena,
+{
+    let expression = unwrap_expression(expression);
+    if artifacts.get_expression_type(expression).is_some_and(cannot_carry_taint) {
+        return None;
+    }
+
+    match expression {
+        Expression::Variable(_) | Expression::ArrayAccess(_) | Expression::Access(_) => {
+            get_reference_taint(context, block_context, artifacts, expression)
+        }
+        Expression::Binary(binary) => {
+            if binary.operator.is_comparison()
+                || binary.operator.is_logical()
+                || binary.operator.is_instanceof()
+                || binary.operator.is_equality()
+            {
+                return None;
+            }
+
+            merge_taints(
+                get_expression_taint(context, block_context, artifacts, binary.lhs),
+                get_expression_taint(context, block_context, artifacts, binary.rhs),
+            )
+        }
+        Expression::UnaryPrefix(prefix) => match prefix.operator {
+            UnaryPrefixOperator::ErrorControl(_)
+            | UnaryPrefixOperator::Reference(_)
+            | UnaryPrefixOperator::ArrayCast(_, _)
+            | UnaryPrefixOperator::ObjectCast(_, _)
+            | UnaryPrefixOperator::StringCast(_, _)
+            | UnaryPrefixOperator::BinaryCast(_, _) => {
+                get_expression_taint(context, block_context, artifacts, prefix.operand)
+            }
+            _ => None,
+        },
+        Expression::Conditional(conditional) => merge_taints(
+            get_expression_taint(context, block_context, artifacts, conditional.then.unwrap_or(conditional.condition)),
+            get_expression_taint(context, block_context, artifacts, conditional.r#else),
+        ),
+        Expression::Match(r#match) => r#match.arms.iter().fold(None, |taint, arm| {
+            let arm_expression = match arm {
+                MatchArm::Expression(arm) => arm.expression,
+                MatchArm::Default(arm) => arm.expression,
+            };
+
+            merge_taints(taint, get_expression_taint(context, block_context, artifacts, arm_expression))
+        }),
+        Expression::CompositeString(string) => string.parts().iter().fold(None, |taint, part| {
+            let part_expression = match part {
+                StringPart::Literal(_) => return taint,
+                StringPart::Expression(expression) => *expression,
+                StringPart::BracedExpression(braced) => braced.expression,
+            };
+
+            merge_taints(taint, get_expression_taint(context, block_context, artifacts, part_expression))
+        }),
+        Expression::Array(array) => get_elements_taint(context, block_context, artifacts, array.elements.iter()),
+        Expression::LegacyArray(array) => get_elements_taint(context, block_context, artifacts, array.elements.iter()),
+        Expression::Assignment(assignment) => {
+            let taint = get_expression_taint(context, block_context, artifacts, assignment.rhs);
+            if matches!(assignment.operator, AssignmentOperator::Assign(_)) {
+                taint
+            } else {
+                merge_taints(taint, get_expression_taint(context, block_context, artifacts, assignment.lhs))
+            }
+        }
+        Expression::Clone(clone) => get_expression_taint(context, block_context, artifacts, clone.object),
+        Expression::Call(Call::Function(_) | Call::Method(_) | Call::NullSafeMethod(_) | Call::StaticMethod(_))
+        | Expression::Pipe(_) => artifacts.taint.expressions.get(&get_expression_range(expression)).cloned(),
+        _ => None,
+    }
+}
+
+fn get_reference_taint<'arena, A>(
+    context: &Context<'_, 'arena, A>,
+    block_context: &BlockContext<'_>,
+    artifacts: &AnalysisArtifacts,
+    expression: &Expression<'arena>,
+) -> Option<Taint>
+where
+    A: Arena,
+{
+    if let Some(taint) = get_superglobal_taint(expression) {
+        return Some(taint);
+    }
+
+    if let Some(expression_id) = get_block_expression_id(expression, context, block_context)
+        && let Some(taint) = artifacts.taint.variables.get(&expression_id)
+    {
+        return Some(taint.clone());
+    }
+
+    if let Some(taint) = get_property_taint(context, block_context, artifacts, expression) {
+        return Some(taint);
+    }
+
+    let container = match expression {
+        Expression::ArrayAccess(array_access) => array_access.array,
+        Expression::Access(Access::Property(property_access)) => property_access.object,
+        Expression::Access(Access::NullSafeProperty(property_access)) => property_access.object,
+        _ => return None,
+    };
+
+    get_expression_taint(context, block_context, artifacts, container)
+}
+
+/// Returns the taint written to a property by the methods of its class, as resolved in the codebase.
+fn get_property_taint<'arena, A>(
+    context: &Context<'_, 'arena, A>,
+    block_context: &BlockContext<'_>,
+    artifacts: &AnalysisArtifacts,
+    expression: &Expression<'arena>,
+) -> Option<Taint>
+where
+    A: Arena,
+{
+    let (class_names, property_name) = match expression {
+        Expression::Access(Access::Property(PropertyAccess { object, property, .. }))
+        | Expression::Access(Access::NullSafeProperty(NullSafePropertyAccess { object, property, .. })) => {
+            let ClassLikeMemberSelector::Identifier(property) = property else {
+                return None;
+            };
+
+            let class_names = artifacts
+                .get_expression_type(*object)?
+                .types
+                .iter()
+                .filter_map(|atomic| match atomic {
+                    TAtomic::Object(TObject::Named(named_object)) => Some(named_object.name),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+
+            (class_names, word([b"$".as_slice(), property.value].concat()))
+        }
+        Expression::Access(Access::StaticProperty(access)) => {
+            let Variable::Direct(property) = &access.property else {
+                return None;
+            };
+
+            let class_name = match access.class {
+                Expression::Self_(_) | Expression::Static(_) => block_context.scope.get_class_like_name()?,
+                Expression::Identifier(identifier) => word(context.resolved_names.get(identifier)),
+                _ => return None,
+            };
+
+            (vec![class_name], word(property.name))
+        }
+        _ => return None,
+    };
+
+    let (class_name, kinds) = class_names
+        .into_iter()
+        .filter_map(|class_name| {
+            let property_metadata =
+                context.codebase.get_declaring_property(class_name.as_bytes(), property_name.as_bytes())?;
+
+            (!property_metadata.taint.is_empty()).then_some((class_name, property_metadata.taint))
+        })
+        .reduce(|(class_name, kinds), (_, other)| (class_name, kinds | other))?;
+
+    Some(Taint::source(kinds, expression.span(), format!("tainted data stored in `{class_name}::{property_name}`")))
+}
+
+fn get_superglobal_taint(expression: &Expression<'_>) -> Option<Taint> {
+    let (variable, key) = match expression {
+        Expression::Variable(Variable::Direct(variable)) => (variable, None),
+        Expression::ArrayAccess(array_access) => match unwrap_expression(array_access.array) {
+            Expression::Variable(Variable::Direct(variable)) => (variable, Some(array_access.index)),
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    if INPUT_SUPERGLOBALS.contains(&variable.name) {
+        return Some(Taint::source(
+            TaintKinds::INPUT,
+            expression.span(),
+            format!("user input read from `{}`", String::from_utf8_lossy(variable.name)),
+        ));
+    }
+
+    if variable.name != b"$_SERVER" {
+        return None;
+    }
+
+    if let Some(Expression::Literal(Literal::String(key))) = key.map(unwrap_expression)
+        && let Some(key) = key.value
+        && !key.starts_with(b"HTTP_")
+        && !USER_CONTROLLED_SERVER_KEYS.contains(&key)
+    {
+        return None;
+    }
+
+    Some(Taint::source(TaintKinds::INPUT, expression.span(), "user input read from `$_SERVER`".to_string()))
+}
+
+fn get_elements_taint<'ast, 'arena, A>(
+    context: &Context<'_, 'arena, A>,
+    block_context: &BlockContext<'_>,
+    artifacts: &AnalysisArtifacts,
+    elements: impl Iterator<Item = &'ast ArrayElement<'arena>>,
+) -> Option<Taint>
+where
+    A: Arena,
+    'arena: 'ast,
+{
+    elements.fold(None, |taint, element| {
+        let key_taint = element.get_key().and_then(|key| get_expression_taint(context, block_context, artifacts, key));
+        let value_taint =
+            element.get_value().and_then(|value| get_expression_taint(context, block_context, artifacts, value));
+
+        merge_taints(merge_taints(taint, key_taint), value_taint)
+    })
+}
+
+fn merge_taints(first: Option<Taint>, second: Option<Taint>) -> Option<Taint> {
+    match first {
+        Some(first) => Some(first.merge(second)),
+        None => second,
+    }
+}
+
+/// Returns `true` if no value of the given type can carry user-controlled data.
+///
+/// Numbers, booleans, `null`, enums and literal strings are fixed by the program itself;
+/// objects are tracked through their properties rather than as a whole.
+fn cannot_carry_taint(union: &TUnion) -> bool {
+    !union.types.is_empty()
+        && union.types.iter().all(|atomic| match atomic {
+            TAtomic::Scalar(scalar) => {
+                scalar.is_literal_value() || scalar.is_int_or_float() || matches!(scalar, TScalar::Bool(_))
+            }
+            TAtomic::Object(_) => true,
+            TAtomic::Null | TAtomic::Void | TAtomic::Never => true,
+            _ => false,
+        })
+}
+
+fn get_argument_parameter_name(
+    metadata: &FunctionLikeMetadata,
+    index: usize,
+    argument: &InvocationArgument<'_, '_>,
+) -> Option<Word> {
+    if let Some(name) = argument.get_parameter_name() {
+        return metadata
+            .parameters
+            .iter()
+            .map(|parameter| parameter.get_name().0)
+            .find(|parameter_name| parameter_name.as_bytes().get(1..) == Some(name));
+    }
+
+    match metadata.parameters.get(index) {
+        Some(parameter) => Some(parameter.get_name().0),
+        None => metadata
+            .parameters
+            .last()
+            .filter(|parameter| parameter.flags.is_variadic())
+            .map(|parameter| parameter.get_name().0),
+    }
+}
+
+fn get_invocation_symbol(target: &InvocationTarget<'_>) -> Option<TaintSymbol> {
+    match target.get_function_like_identifier()? {
+        FunctionLikeIdentifier::Function(name) => {
+            let name = name.as_bytes();
+
+            Some(TaintSymbol { class: None, name: ascii_lowercase_word(name.strip_prefix(b"\\").unwrap_or(name)) })
+        }
+        FunctionLikeIdentifier::Method(class, method) => {
+            Some(TaintSymbol { class: Some(*class), name: ascii_lowercase_word(method.as_bytes()) })
+        }
+        FunctionLikeIdentifier::Closure(_) => None,
+    }
+}
+
+fn symbol_matches<A>(context: &Context<'_, '_, A>, symbol: TaintSymbol, expected: TaintSymbol) -> bool
+where
+    A: Arena,
+{
+    if symbol.name != expected.name {
+        return false;
+    }
+
+    match (symbol.class, expected.class) {
+        (None, None) => true,
+        (Some(class), Some(expected_class)) => {
+            context.codebase.is_instance_of(class.as_bytes(), expected_class.as_bytes())
+        }
+        _ => false,
+    }
+}
+
+fn symbol_matches_builtin<A>(context: &Context<'_, '_, A>, symbol: TaintSymbol, class: Option<&str>, name: &str) -> bool
+where
+    A: Arena,
+{
+    if symbol.name.as_bytes() != name.as_bytes() {
+        return false;
+    }
+
+    match (symbol.class, class) {
+        (None, None) => true,
+        (Some(symbol_class), Some(class)) => context.codebase.is_instance_of(symbol_class.as_bytes(), class.as_bytes()),
+        _ => false,
+    }
+}
+
+fn report_tainted_sink<A>(
+    context: &mut Context<'_, '_, A>,
+    taint: &Taint,
+    sink_kinds: TaintKinds,
+    value_span: Span,
+    sink_span: Span,
+    sink_name: &str,
+) where
+    A: Arena,
+{
+    let kinds = taint.kinds.intersection(sink_kinds);
+    let Some((_, code, description, help)) = KIND_REPORTS.iter().find(|(kind, ..)| kinds.contains(*kind)) else {
+        return;
+    };
+
+    let mut issue = Issue::error(format!("Tainted {description} flows into {sink_name}.")).with_annotation(
+        Annotation::primary(value_span).with_message(format!("This value may contain user-controlled {description}")),
+    );
+
+    for step in taint.steps() {
+        if step.span == value_span {
+            continue;
+        }
+
+        issue = issue.with_annotation(Annotation::secondary(step.span).with_message(step.message.clone()));
+    }
+
+    issue = issue
+        .with_annotation(Annotation::secondary(sink_span).with_message(format!("Sink: {sink_name}")))
+        .with_note(
+            "Taint analysis follows user-controlled data from its source through assignments and calls; the highlighted steps show how it reached this sink.",
+        )
+        .with_help(*help);
+
+    context.collector.report_with_code(*code, issue);
+}
@@ -1,19 +1,42 @@
+use std::fmt::Write as _;
+use std::rc::Rc;
+
 use mago_allocator::Arena;
+use mago_codex::metadata::CodebaseMetadata;
 use mago_codex::metadata::class_like::ClassLikeMetadata;
 use mago_codex::metadata::function_like::FunctionLikeKind;
 use mago_codex::metadata::function_like::FunctionLikeMetadata;
 use mago_codex::metadata::property::PropertyMetadata;
+use mago_codex::ttype::TType;
+use mago_codex::ttype::TypeRef;
+use mago_codex::ttype::add_optional_union_type;
+use mago_codex::ttype::atomic::TAtomic;
+use mago_codex::ttype::atomic::array::TArray;
+use mago_codex::ttype::atomic::object::TObject;
+use mago_codex::ttype::atomic::scalar::TScalar;
+use mago_codex::ttype::get_array_parameters;
+use mago_codex::ttype::get_array_value_parameter;
+use mago_codex::ttype::get_never;
+use mago_codex::ttype::get_null;
+use mago_codex::ttype::get_void;
+use mago_codex::ttype::union::TUnion;
 use mago_php_version::PHPVersion;
 use mago_php_version::feature::Feature;
 use mago_reporting::Annotation;
 use mago_reporting::Issue;
 use mago_span::HasSpan;
 use mago_span::Span;
+use mago_syntax::comments::docblock::get_docblock_before_position;
 use mago_syntax::cst::ClassLikeConstant;
+use mago_syntax::cst::Expression;
 use mago_syntax::cst::FunctionLikeParameter;
 use mago_syntax::cst::FunctionLikeReturnTypeHint;
 use mago_syntax::cst::Hint;
+use mago_syntax::cst::Literal;
 use mago_syntax::cst::Property;
+use mago_text_edit::Safety;
+use mago_text_edit::TextEdit;
+use mago_word::word;
 
 use crate::code::IssueCode;
 use crate::context::Context;
@@ -113,11 +136,15 @@ pub fn check_property_type_hint<'arena, A>(
 /// 3. The method is not overriding a parent method (where adding a type hint might cause issues)
 /// 4. If it's a closure/arrow function parameter, the corresponding ignore setting is not enabled
 /// 5. Typed parameters are supported in the target PHP version
+///
+/// Functions and methods pass the [`InferredDocblock`] of their signature, which holds the
+/// issue back until the whole signature has been checked.
 pub fn check_parameter_type_hint<'arena, A>(
     context: &mut Context<'_, 'arena, A>,
     class_like_metadata: Option<&ClassLikeMetadata>,
     function_like_metadata: &FunctionLikeMetadata,
     parameter: &FunctionLikeParameter<'arena>,
+    docblock: Option<&mut InferredDocblock>,
 ) where
     A: Arena,
 {
@@ -150,17 +177,58 @@ pub fn check_parameter_type_hint<'arena, A>(
         return;
     }
 
+    let has_null_default = parameter
+        .default_value
+        .as_ref()
+        .is_some_and(|default_value| matches!(default_value.value, Expression::Literal(Literal::Null(_))));
+
+    // A docblock type that can be expressed natively becomes the suggested hint. Variadic
+    // parameters are left alone, as their docblock type may describe the collected array.
+    // With `infer_types`, parameters without a docblock type fall back to their default value.
+    let parameter_metadata = function_like_metadata.get_parameter(word(parameter.variable.name));
+    let mut docblock_tag = None;
+    let native_hint = if parameter.ellipsis.is_some() {
+        None
+    } else if let Some(type_metadata) = parameter_metadata.and_then(|p| p.type_metadata.as_ref())
+        && type_metadata.from_docblock
+    {
+        get_native_type_hint(context, &type_metadata.type_union, has_null_default, false)
+    } else if context.settings.infer_types
+        && let Some(default_type) = parameter_metadata.and_then(|p| p.default_type.as_ref())
+    {
+        let mut default_type = default_type.type_union.clone();
+        default_type.widen_literals();
+
+        docblock_tag = get_default_value_docblock_type(context.codebase, &default_type)
+            .map(|docblock_type| format!("@param {docblock_type} {}", BytesDisplay(parameter.variable.name)));
+
+        get_native_type_hint(context, &default_type, false, false)
+    } else {
+        None
+    };
+
     let parameter_name = BytesDisplay(parameter.variable.name);
-    context.collector.report(
-        Issue::warning(format!("Parameter `{parameter_name}` is missing a type hint."))
-            .with_code(IssueCode::MissingParameterType.as_str())
-            .with_annotation(
-                Annotation::primary(parameter.span())
-                    .with_message(format!("Parameter `{parameter_name}` declared here without a type hint")),
-            )
-            .with_note("Type hints improve code readability and help prevent type-related errors.")
-            .with_help(format!("Consider adding a type hint to parameter `{parameter_name}`.")),
-    );
+    let mut issue = Issue::warning(format!("Parameter `{parameter_name}` is missing a type hint."))
+        .with_annotation(
+            Annotation::primary(parameter.span())
+                .with_message(format!("Parameter `{parameter_name}` declared here without a type hint")),
+        )
+        .with_note("Type hints improve code readability and help prevent type-related errors.");
+
+    issue = if let Some(native_hint) = &native_hint {
+        issue.with_help(format!("Add the `{native_hint}` type hint to parameter `{parameter_name}`."))
+    } else {
+        issue.with_help(format!("Consider adding a type hint to parameter `{parameter_name}`."))
+    };
+
+    let mut edits = vec![];
+    if let Some(native_hint) = native_hint {
+        let offset = parameter.ampersand.unwrap_or_else(|| parameter.variable.span()).start_offset();
+
+        edits.push(TextEdit::insert(offset, format!("{native_hint} ")).with_safety(Safety::PotentiallyUnsafe));
+    }
+
+    report_missing_type_hint(context, docblock, IssueCode::MissingParameterType, issue, edits, docblock_tag);
 }
 
 /// Check if a function or method is missing a return type hint.
@@ -171,13 +239,22 @@ pub fn check_parameter_type_hint<'arena, A>(
 /// 3. If it's a method, it's not overriding a parent method
 /// 4. If it's a closure/arrow function, the corresponding ignore setting is not enabled
 /// 5. Return type hints are supported in the target PHP version
+///
+/// When `inferred_return_type` can be written as a native type hint, the issue carries an
+/// edit inserting it at `return_type_hint_offset`, right after the parameter list (or the
+/// `use` clause of a closure). With `infer_types` enabled, types richer than their native
+/// hint are also documented with a `@return` tag in `docblock`.
+#[allow(clippy::too_many_arguments)]
 pub fn check_return_type_hint<'arena, A>(
     context: &mut Context<'_, 'arena, A>,
     class_like_metadata: Option<&ClassLikeMetadata>,
     function_like_metadata: &FunctionLikeMetadata,
     function_name: &[u8],
     return_type_hint: Option<&FunctionLikeReturnTypeHint<'arena>>,
+    inferred_return_type: Option<&TUnion>,
+    return_type_hint_offset: u32,
     span: Span,
+    docblock: Option<&mut InferredDocblock>,
 ) where
     A: Arena,
 {
@@ -214,17 +291,470 @@ pub fn check_return_type_hint<'arena, A>(
         return;
     }
 
+    // Literals are widened so that, for example, a function returning `'yes'` documents `string`.
+    let infer_types = context.settings.infer_types
+        && !function_like_metadata.return_type_metadata.as_ref().is_some_and(|metadata| metadata.from_docblock);
+
+    let inferred_return_type = inferred_return_type.map(|inferred_return_type| {
+        let mut inferred_return_type = inferred_return_type.clone();
+        if infer_types {
+            inferred_return_type.widen_literals();
+        }
+
+        inferred_return_type
+    });
+
+    let native_hint = inferred_return_type
+        .as_ref()
+        .and_then(|inferred_return_type| get_native_type_hint(context, inferred_return_type, false, true));
+
+    let docblock_tag = match &inferred_return_type {
+        Some(inferred_return_type)
+            if infer_types
+                && docblock.is_some()
+                && (native_hint.is_none() || !is_described_by_native_hint(inferred_return_type)) =>
+        {
+            let mut docblock_return_type = inferred_return_type.clone();
+            docblock_return_type.widen_scalars();
+
+            get_docblock_type(context.codebase, &docblock_return_type)
+                .map(|docblock_type| format!("@return {docblock_type}"))
+        }
+        _ => None,
+    };
+
     let function_name = BytesDisplay(function_name);
-    context.collector.report(
-        Issue::warning(format!("Function `{function_name}` is missing a return type hint."))
-            .with_code(IssueCode::MissingReturnType.as_str())
-            .with_annotation(
-                Annotation::primary(span)
-                    .with_message(format!("Function `{function_name}` declared here without a return type hint")),
+    let mut issue = Issue::warning(format!("Function `{function_name}` is missing a return type hint."))
+        .with_annotation(
+            Annotation::primary(span)
+                .with_message(format!("Function `{function_name}` declared here without a return type hint")),
+        )
+        .with_note("Return type hints improve code readability and help prevent type-related errors.");
+
+    issue = if let Some(native_hint) = &native_hint {
+        issue.with_help(format!("Add the `{native_hint}` return type hint to function `{function_name}`."))
+    } else {
+        issue.with_help(format!("Consider adding a return type hint to function `{function_name}`."))
+    };
+
+    // Subclasses overriding the method without a return type would stop compiling once the
+    // parent declares one, so the fix is only potentially unsafe when the method cannot be overridden.
+    let safety =
+        if class_like_metadata.is_none_or(|class_metadata| !is_overridable(class_metadata, function_like_metadata)) {
+            Safety::PotentiallyUnsafe
+        } else {
+            Safety::Unsafe
+        };
+
+    let mut edits = vec![];
+    if let Some(native_hint) = native_hint {
+        edits.push(TextEdit::insert(return_type_hint_offset, format!(": {native_hint}")).with_safety(safety));
+    }
+
+    report_missing_type_hint(context, docblock, IssueCode::MissingReturnType, issue, edits, docblock_tag);
+}
+
+/// Combines the types a function-like body was seen returning into the type a return
+/// type hint for it should describe.
+///
+/// A `@return` docblock type takes precedence over the inferred types. A body whose end is
+/// reachable also returns `null` implicitly, and one that neither returns nor reaches its
+/// end is `never`. Generators are not handled, as their return type is not the returned value.
+pub fn get_inferred_return_type(
+    codebase: &CodebaseMetadata,
+    function_like_metadata: &FunctionLikeMetadata,
+    inferred_return_types: &[Rc<TUnion>],
+    has_returned: bool,
+) -> Option<TUnion> {
+    if function_like_metadata.flags.has_yield() {
+        return None;
+    }
+
+    if let Some(return_type_metadata) = &function_like_metadata.return_type_metadata
+        && return_type_metadata.from_docblock
+    {
+        return Some(return_type_metadata.type_union.clone());
+    }
+
+    let mut inferred_return_type = None;
+    for inferred in inferred_return_types {
+        inferred_return_type =
+            Some(add_optional_union_type((**inferred).clone(), inferred_return_type.as_ref(), codebase));
+    }
+
+    Some(match inferred_return_type {
+        Some(inferred_return_type) if !has_returned && !inferred_return_type.is_void() => {
+            add_optional_union_type(get_null(), Some(&inferred_return_type), codebase)
+        }
+        Some(inferred_return_type) => inferred_return_type,
+        None if has_returned => get_never(),
+        None => get_void(),
+    })
+}
+
+/// Renders `type_union` as a native type hint supported by the configured PHP version.
+///
+/// Returns `None` when part of the type has no native equivalent (templates, callables,
+/// resources, ...) or would need syntax the target version does not support.
+fn get_native_type_hint<A>(
+    context: &Context<'_, '_, A>,
+    type_union: &TUnion,
+    force_nullable: bool,
+    is_return_type: bool,
+) -> Option<String>
+where
+    A: Arena,
+{
+    let version = context.settings.version;
+
+    if type_union.is_void() {
+        return (is_return_type && version.is_supported(Feature::VoidTypeHint)).then(|| "void".to_string());
+    }
+
+    if type_union.is_never() {
+        return (is_return_type && version.is_supported(Feature::NeverTypeHint)).then(|| "never".to_string());
+    }
+
+    let mut nullable = force_nullable;
+    let mut hints: Vec<String> = vec![];
+    for atomic in type_union.types.iter() {
+        let atomic_hints = match atomic {
+            TAtomic::Null => {
+                nullable = true;
+
+                continue;
+            }
+            TAtomic::Scalar(TScalar::Bool(_)) => vec!["bool".to_string()],
+            TAtomic::Scalar(TScalar::Integer(_)) => vec!["int".to_string()],
+            TAtomic::Scalar(TScalar::Float(_)) => vec!["float".to_string()],
+            TAtomic::Scalar(TScalar::String(_) | TScalar::ClassLikeString(_)) => vec!["string".to_string()],
+            TAtomic::Scalar(TScalar::ArrayKey) => vec!["int".to_string(), "string".to_string()],
+            TAtomic::Array(_) => vec!["array".to_string()],
+            TAtomic::Iterable(_) if version.is_supported(Feature::IterableTypeHint) => vec!["iterable".to_string()],
+            TAtomic::Object(TObject::Any) if version >= PHPVersion::PHP72 => vec!["object".to_string()],
+            TAtomic::Object(TObject::Named(named_object)) if named_object.is_static => {
+                if !is_return_type || !version.is_supported(Feature::StaticReturnTypeHint) {
+                    return None;
+                }
+
+                vec!["static".to_string()]
+            }
+            TAtomic::Object(TObject::Named(named_object)) if named_object.intersection_types.is_none() => {
+                vec![get_class_like_hint(context.codebase, named_object.name.as_bytes())?]
+            }
+            TAtomic::Object(TObject::Enum(enum_object)) => {
+                vec![get_class_like_hint(context.codebase, enum_object.name.as_bytes())?]
+            }
+            _ => return None,
+        };
+
+        for hint in atomic_hints {
+            if !hints.contains(&hint) {
+                hints.push(hint);
+            }
+        }
+    }
+
+    match hints.as_slice() {
+        [] => None,
+        [hint] if !nullable => Some(hint.clone()),
+        [hint] => version.is_supported(Feature::NullableTypeHint).then(|| format!("?{hint}")),
+        _ if version.is_supported(Feature::NativeUnionTypes) => {
+            if nullable {
+                hints.push("null".to_string());
+            }
+
+            Some(hints.join("|"))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the fully qualified, correctly cased name of a class-like for use in a type hint.
+fn get_class_like_hint(codebase: &CodebaseMetadata, name: &[u8]) -> Option<String> {
+    let class_like_metadata = codebase.get_class_like(name)?;
+
+    // Anonymous classes have synthetic names that cannot be written in source.
+    if class_like_metadata.name.as_bytes().starts_with(b"{") {
+        return None;
+    }
+
+    Some(format!("\\{}", class_like_metadata.original_name))
+}
+
+/// Docblock tags inferred for the signature of a function or method.
+///
+/// Parameters and the return type are reported as separate issues, but their tags have to
+/// end up in a single docblock. The issues are held back until the whole signature has been
+/// checked, and the docblock edit is then attached to one of them.
+#[derive(Debug)]
+pub struct InferredDocblock {
+    offset: u32,
+    issues: Vec<(IssueCode, Issue, Vec<TextEdit>)>,
+    tags: Vec<String>,
+}
+
+impl InferredDocblock {
+    /// Creates an empty docblock for the function-like starting at `offset`, including its attributes.
+    #[must_use]
+    pub fn new(offset: u32) -> Self {
+        Self { offset, issues: vec![], tags: vec![] }
+    }
+
+    /// Reports the held back issues.
+    ///
+    /// The docblock edit goes to the first issue whose own edits are not unsafe, so that
+    /// the tags are not lost when an unsafe return type hint is skipped.
+    pub fn report<A>(self, context: &mut Context<'_, '_, A>)
+    where
+        A: Arena,
+    {
+        let mut docblock_edit = self.get_edit(context);
+        let docblock_issue = self
+            .issues
+            .iter()
+            .position(|(_, _, edits)| edits.iter().all(|edit| edit.safety != Safety::Unsafe))
+            .unwrap_or(0);
+
+        for (i, (code, issue, mut edits)) in self.issues.into_iter().enumerate() {
+            if i == docblock_issue
+                && let Some(docblock_edit) = docblock_edit.take()
+            {
+                edits.push(docblock_edit);
+            }
+
+            context.collector.propose_with_code(code, issue, |issue_edits| issue_edits.extend(edits));
+        }
+    }
+
+    /// Builds the edit adding the tags to the existing docblock, or creating one.
+    fn get_edit<A>(&self, context: &Context<'_, '_, A>) -> Option<TextEdit>
+    where
+        A: Arena,
+    {
+        if self.tags.is_empty() {
+            return None;
+        }
+
+        let offset = self.offset;
+        let line_start_offset =
+            context.source_file.get_line_start_offset(context.source_file.line_number(offset)).unwrap_or(offset);
+        let line_slice = &context.source_file.contents[line_start_offset as usize..offset as usize];
+        let indent_end = line_slice.iter().take_while(|b| b.is_ascii_whitespace()).count();
+        let indent = std::str::from_utf8(&line_slice[..indent_end]).unwrap_or_default();
+
+        let tags = self.get_tag_lines(indent);
+        let Some(existing) = get_docblock_before_position(context.comments, offset) else {
+            return Some(
+                TextEdit::insert(offset, format!("/**\n{tags}{indent} */\n{indent}"))
+                    .with_safety(Safety::PotentiallyUnsafe),
+            );
+        };
+
+        let closing = existing.value.windows(2).rposition(|window| window == b"*/")?;
+        let body = &existing.value[..closing];
+        let closing_line_start = body.iter().rposition(|b| *b == b'\n').map_or(0, |position| position + 1);
+        let docblock_offset = existing.span.start_offset();
+
+        if closing_line_start > 0 && body[closing_line_start..].iter().all(u8::is_ascii_whitespace) {
+            // The docblock closes on its own line; the tags go right above it.
+            return Some(
+                TextEdit::insert(docblock_offset + closing_line_start as u32, tags)
+                    .with_safety(Safety::PotentiallyUnsafe),
+            );
+        }
+
+        // A single-line docblock is split so that each tag gets its own line.
+        let body_end = body.trim_ascii_end().len() as u32;
+
+        Some(
+            TextEdit::replace(
+                docblock_offset + body_end..docblock_offset + closing as u32,
+                format!("\n{tags}{indent} "),
             )
-            .with_note("Return type hints improve code readability and help prevent type-related errors.")
-            .with_help(format!("Consider adding a return type hint to function `{function_name}`.")),
-    );
+            .with_safety(Safety::PotentiallyUnsafe),
+        )
+    }
+
+    /// Renders each tag on its own docblock line.
+    fn get_tag_lines(&self, indent: &str) -> String {
+        let mut lines = String::new();
+        for tag in &self.tags {
+            let _ = writeln!(lines, "{indent} * {tag}");
+        }
+
+        lines
+    }
+}
+
+/// Reports a missing type hint issue, or hands it to the signature's [`InferredDocblock`].
+fn report_missing_type_hint<A>(
+    context: &mut Context<'_, '_, A>,
+    docblock: Option<&mut InferredDocblock>,
+    code: IssueCode,
+    issue: Issue,
+    edits: Vec<TextEdit>,
+    docblock_tag: Option<String>,
+) where
+    A: Arena,
+{
+    let Some(docblock) = docblock else {
+        context.collector.propose_with_code(code, issue, |issue_edits| issue_edits.extend(edits));
+
+        return;
+    };
+
+    docblock.issues.push((code, issue, edits));
+    docblock.tags.extend(docblock_tag);
+}
+
+/// Whether the native type hint for `type_union` describes it completely, leaving nothing
+/// for a docblock tag to add.
+fn is_described_by_native_hint(type_union: &TUnion) -> bool {
+    type_union.types.iter().all(|atomic| match atomic {
+        TAtomic::Null | TAtomic::Void | TAtomic::Never | TAtomic::Object(TObject::Any) => true,
+        TAtomic::Scalar(_) => {
+            matches!(atomic.get_id().as_bytes(), b"bool" | b"int" | b"float" | b"string" | b"array-key")
+        }
+        TAtomic::Array(array) => array.is_vanilla() || array.is_empty(),
+        TAtomic::Object(TObject::Named(named_object)) => named_object.type_parameters.is_none(),
+        TAtomic::Object(TObject::Enum(enum_object)) => enum_object.case.is_none(),
+        TAtomic::Mixed(mixed) => mixed.is_vanilla(),
+        _ => false,
+    })
+}
+
+/// Describes the default value of a parameter in a `@param` tag.
+///
+/// Only non-empty array defaults are described. Their items are generalized to the
+/// `list<T>` or `array<K, V>` form of their general scalar types, as the default is
+/// rarely the only accepted value.
+fn get_default_value_docblock_type(codebase: &CodebaseMetadata, default_type: &TUnion) -> Option<String> {
+    let [TAtomic::Array(array)] = default_type.types.as_ref() else {
+        return None;
+    };
+
+    if array.is_empty() {
+        return None;
+    }
+
+    match array {
+        TArray::List(_) => {
+            let mut value_type = get_array_value_parameter(array, codebase);
+            value_type.widen_scalars();
+
+            Some(format!("list<{}>", get_docblock_type(codebase, &value_type)?))
+        }
+        TArray::Keyed(_) => {
+            let (mut key_type, mut value_type) = get_array_parameters(array, codebase);
+            key_type.widen_scalars();
+            value_type.widen_scalars();
+
+            Some(format!(
+                "array<{}, {}>",
+                get_docblock_type(codebase, &key_type)?,
+                get_docblock_type(codebase, &value_type)?
+            ))
+        }
+    }
+}
+
+/// Renders `type_union` in docblock syntax.
+///
+/// Scalars are expected to be widened beforehand, as the ids of literal and refined
+/// scalars, such as `truthy-lowercase-string`, are internal to the analyzer.
+///
+/// Returns `None` for types that cannot be written outside of their context, such as
+/// templates, `static`, or closures.
+fn get_docblock_type(codebase: &CodebaseMetadata, type_union: &TUnion) -> Option<String> {
+    let mut docblock_types: Vec<String> = vec![];
+    for atomic in type_union.types.iter() {
+        let docblock_type = get_docblock_atomic_type(codebase, atomic)?;
+        if !docblock_types.contains(&docblock_type) {
+            docblock_types.push(docblock_type);
+        }
+    }
+
+    (!docblock_types.is_empty()).then(|| docblock_types.join("|"))
+}
+
+fn get_docblock_atomic_type(codebase: &CodebaseMetadata, atomic: &TAtomic) -> Option<String> {
+    Some(match atomic {
+        TAtomic::Object(TObject::Named(named_object)) => {
+            if named_object.is_static || named_object.intersection_types.is_some() {
+                return None;
+            }
+
+            let name = get_class_like_hint(codebase, named_object.name.as_bytes())?;
+            match &named_object.type_parameters {
+                Some(type_parameters) => {
+                    let type_parameters = type_parameters
+                        .iter()
+                        .map(|type_parameter| get_docblock_type(codebase, type_parameter))
+                        .collect::<Option<Vec<_>>>()?;
+
+                    format!("{name}<{}>", type_parameters.join(", "))
+                }
+                None => name,
+            }
+        }
+        TAtomic::Object(TObject::Enum(enum_object)) => {
+            let name = get_class_like_hint(codebase, enum_object.name.as_bytes())?;
+            match &enum_object.case {
+                Some(case) => format!("{name}::{case}"),
+                None => name,
+            }
+        }
+        TAtomic::Array(TArray::List(list)) if list.known_elements.is_none() => {
+            let element_type = get_docblock_type(codebase, &list.element_type)?;
+
+            if list.non_empty { format!("non-empty-list<{element_type}>") } else { format!("list<{element_type}>") }
+        }
+        TAtomic::Array(TArray::Keyed(keyed_array)) if keyed_array.known_items.is_none() => {
+            let (key_type, value_type) = keyed_array.parameters.as_ref()?;
+            let key_type = get_docblock_type(codebase, key_type)?;
+            let value_type = get_docblock_type(codebase, value_type)?;
+
+            if keyed_array.non_empty {
+                format!("non-empty-array<{key_type}, {value_type}>")
+            } else {
+                format!("array<{key_type}, {value_type}>")
+            }
+        }
+        _ => {
+            // Anything else is written as its id, as long as it does not refer to a class-like.
+            let is_writable_atomic = |atomic: &TAtomic| match atomic {
+                TAtomic::Scalar(scalar) => !matches!(scalar, TScalar::ClassLikeString(_)),
+                TAtomic::Mixed(mixed) => mixed.is_vanilla(),
+                TAtomic::Null | TAtomic::Never | TAtomic::Array(_) => true,
+                _ => false,
+            };
+
+            let is_writable = is_writable_atomic(atomic)
+                && atomic.get_all_child_nodes().into_iter().all(|node| match node {
+                    TypeRef::Atomic(atomic) => is_writable_atomic(atomic),
+                    TypeRef::Union(_) => true,
+                });
+
+            if !is_writable {
+                return None;
+            }
+
+            atomic.get_id().to_string()
+        }
+    })
+}
+
+/// Whether a subclass could override the given method.
+fn is_overridable(class_like_metadata: &ClassLikeMetadata, function_like_metadata: &FunctionLikeMetadata) -> bool {
+    if class_like_metadata.flags.is_final() || class_like_metadata.kind.is_enum() {
+        return false;
+    }
+
+    function_like_metadata
+        .method_metadata
+        .as_ref()
+        .is_some_and(|method_metadata| !method_metadata.is_final && !method_metadata.visibility.is_private())
 }
 
 /// Check if a return type hint uses a bare `array` or `iterable` without a more specific
@@ -493,3 +1023,209 @@ where
 
     true
 }
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use mago_text_edit::Safety;
+
+    use crate::code::IssueCode;
+    use crate::settings::Settings;
+    use crate::tests::TestCase;
+
+    fn settings() -> Settings {
+        Settings { check_missing_type_hints: true, ..Default::default() }
+    }
+
+    fn infer_types_settings() -> Settings {
+        Settings { check_missing_type_hints: true, infer_types: true, ..Default::default() }
+    }
+
+    #[test]
+    fn fixes_missing_type_hints_of_functions() {
+        TestCase::new(
+            "fixes_missing_type_hints_of_functions",
+            indoc! {"
+                <?php
+
+                /**
+                 * @param list<string> $names
+                 */
+                function greet($names)
+                {
+                    if ($names === []) {
+                        return null;
+                    }
+
+                    return 'Hello';
+                }
+            "},
+        )
+        .settings(settings())
+        .expect_issues(vec![IssueCode::MissingParameterType, IssueCode::MissingReturnType])
+        .expect_fixed_code(
+            Safety::PotentiallyUnsafe,
+            indoc! {"
+                <?php
+
+                /**
+                 * @param list<string> $names
+                 */
+                function greet(array $names): ?string
+                {
+                    if ($names === []) {
+                        return null;
+                    }
+
+                    return 'Hello';
+                }
+            "},
+        )
+        .run();
+    }
+
+    #[test]
+    fn fixes_missing_return_type_of_final_method() {
+        TestCase::new(
+            "fixes_missing_return_type_of_final_method",
+            indoc! {"
+                <?php
+
+                final class Counter
+                {
+                    private int $count = 0;
+
+                    public function increment()
+                    {
+                        $this->count++;
+                    }
+
+                    public function count()
+                    {
+                        return $this->count;
+                    }
+                }
+            "},
+        )
+        .settings(settings())
+        .expect_issues(vec![IssueCode::MissingReturnType, IssueCode::MissingReturnType])
+        .expect_fixed_code(
+            Safety::PotentiallyUnsafe,
+            indoc! {"
+                <?php
+
+                final class Counter
+                {
+                    private int $count = 0;
+
+                    public function increment(): void
+                    {
+                        $this->count++;
+                    }
+
+                    public function count(): int
+                    {
+                        return $this->count;
+                    }
+                }
+            "},
+        )
+        .run();
+    }
+
+    #[test]
+    fn return_type_fix_of_overridable_method_is_unsafe() {
+        let code = indoc! {"
+            <?php
+
+            class Counter
+            {
+                public function count()
+                {
+                    return 1;
+                }
+            }
+        "};
+
+        TestCase::new("return_type_fix_of_overridable_method_is_unsafe", code)
+            .settings(settings())
+            .expect_issues(vec![IssueCode::MissingReturnType])
+            .expect_fixed_code(Safety::PotentiallyUnsafe, code)
+            .run();
+    }
+
+    #[test]
+    fn infers_types_from_parameter_default_values() {
+        TestCase::new(
+            "infers_types_from_parameter_default_values",
+            indoc! {"
+                <?php
+
+                function configure($options = ['debug' => false])
+                {
+                    echo 'Configured';
+                }
+            "},
+        )
+        .settings(infer_types_settings())
+        .expect_issues(vec![IssueCode::MissingParameterType, IssueCode::MissingReturnType])
+        .expect_fixed_code(
+            Safety::PotentiallyUnsafe,
+            indoc! {"
+                <?php
+
+                /**
+                 * @param array<string, bool> $options
+                 */
+                function configure(array $options = ['debug' => false]): void
+                {
+                    echo 'Configured';
+                }
+            "},
+        )
+        .run();
+    }
+
+    #[test]
+    fn infers_docblock_return_type_richer_than_native_hint() {
+        TestCase::new(
+            "infers_docblock_return_type_richer_than_native_hint",
+            indoc! {"
+                <?php
+
+                final class Inventory
+                {
+                    /**
+                     * Returns the names of the items.
+                     */
+                    public function names()
+                    {
+                        return ['apple', 'pear'];
+                    }
+                }
+            "},
+        )
+        .settings(infer_types_settings())
+        .expect_issues(vec![IssueCode::MissingReturnType])
+        .expect_fixed_code(
+            Safety::PotentiallyUnsafe,
+            indoc! {"
+                <?php
+
+                final class Inventory
+                {
+                    /**
+                     * Returns the names of the items.
+                     * @return list{string, string}
+                     */
+                    public function names(): array
+                    {
+                        return ['apple', 'pear'];
+                    }
+                }
+            "},
+        )
+        .run();
+    }
+}
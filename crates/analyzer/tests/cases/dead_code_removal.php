@@ -0,0 +1,73 @@
+<?php
+
+namespace App;
+
+use Attribute;
+
+#[Attribute]
+final class Job
+{
+}
+
+final class UnusedService
+{
+    public function run(): void
+    {
+        helper_only_used_by_dead_code();
+    }
+}
+
+function helper_only_used_by_dead_code(): void
+{
+}
+
+function unused_function(): void
+{
+}
+
+final class UsedService
+{
+    private int $counter = 0;
+
+    private string $unused = '';
+
+    public function run(): int
+    {
+        return $this->increment();
+    }
+
+    private function increment(): int
+    {
+        $this->counter++;
+
+        return $this->counter;
+    }
+
+    private function neverCalled(): void
+    {
+    }
+
+    #[Job]
+    private function scheduled(): void
+    {
+    }
+}
+
+#[Job]
+final class ScheduledTask
+{
+}
+
+final class Kept
+{
+}
+
+interface OnlyImplementedByDeadCode
+{
+}
+
+final class DeadImplementation implements OnlyImplementedByDeadCode
+{
+}
+
+echo (new UsedService())->run();
@@ -0,0 +1,350 @@
+<?php
+
+declare(strict_types=1);
+
+namespace Doctrine\Common\Collections {
+    /**
+     * @template TKey of array-key
+     * @template T
+     *
+     * @extends \IteratorAggregate<TKey, T>
+     */
+    interface Collection extends \IteratorAggregate
+    {
+        /**
+         * @return T|false
+         */
+        public function first(): mixed;
+    }
+}
+
+namespace Doctrine\Persistence {
+    /**
+     * @template T of object
+     */
+    interface ObjectRepository
+    {
+        /**
+         * @return T|null
+         */
+        public function find(mixed $id): null|object;
+
+        /**
+         * @param array<string, mixed> $criteria
+         *
+         * @return list<T>
+         */
+        public function findBy(array $criteria): array;
+
+        /**
+         * @param array<string, mixed> $criteria
+         *
+         * @return T|null
+         */
+        public function findOneBy(array $criteria): null|object;
+    }
+
+    interface ObjectManager
+    {
+        /**
+         * @template T of object
+         *
+         * @param class-string<T> $className
+         *
+         * @return ObjectRepository<T>
+         */
+        public function getRepository(string $className): ObjectRepository;
+    }
+}
+
+namespace Doctrine\ORM {
+    use Doctrine\Persistence\ObjectManager;
+    use Doctrine\Persistence\ObjectRepository;
+
+    /**
+     * @template T of object
+     *
+     * @implements ObjectRepository<T>
+     */
+    class EntityRepository implements ObjectRepository
+    {
+        /**
+         * @return T|null
+         */
+        public function find(mixed $id): null|object
+        {
+            return null;
+        }
+
+        /**
+         * @param array<string, mixed> $criteria
+         *
+         * @return list<T>
+         */
+        public function findBy(array $criteria): array
+        {
+            return [];
+        }
+
+        /**
+         * @param array<string, mixed> $criteria
+         *
+         * @return T|null
+         */
+        public function findOneBy(array $criteria): null|object
+        {
+            return null;
+        }
+    }
+
+    interface EntityManagerInterface extends ObjectManager
+    {
+        /**
+         * @template T of object
+         *
+         * @param class-string<T> $className
+         *
+         * @return EntityRepository<T>
+         */
+        public function getRepository(string $className): EntityRepository;
+    }
+}
+
+namespace Doctrine\ORM\Mapping {
+    use Attribute;
+
+    #[Attribute(Attribute::TARGET_CLASS)]
+    final class Entity
+    {
+        /**
+         * @param class-string|null $repositoryClass
+         */
+        public function __construct(
+            public null|string $repositoryClass = null,
+        ) {}
+    }
+
+    #[Attribute(Attribute::TARGET_PROPERTY)]
+    final class Id
+    {
+    }
+
+    #[Attribute(Attribute::TARGET_PROPERTY)]
+    final class Column
+    {
+        public function __construct(
+            public null|string $name = null,
+            public bool $nullable = false,
+        ) {}
+    }
+
+    #[Attribute(Attribute::TARGET_PROPERTY)]
+    final class ManyToOne
+    {
+        /**
+         * @param class-string|null $targetEntity
+         */
+        public function __construct(
+            public null|string $targetEntity = null,
+        ) {}
+    }
+
+    #[Attribute(Attribute::TARGET_PROPERTY)]
+    final class OneToMany
+    {
+        /**
+         * @param class-string|null $targetEntity
+         */
+        public function __construct(
+            public null|string $mappedBy = null,
+            public null|string $targetEntity = null,
+        ) {}
+    }
+
+    #[Attribute(Attribute::TARGET_PROPERTY)]
+    final class ManyToMany
+    {
+        /**
+         * @param class-string $targetEntity
+         */
+        public function __construct(
+            public string $targetEntity,
+            public null|string $indexBy = null,
+        ) {}
+    }
+}
+
+namespace App {
+    use Doctrine\Common\Collections\Collection;
+    use Doctrine\ORM\EntityManagerInterface;
+    use Doctrine\ORM\EntityRepository;
+    use Doctrine\ORM\Mapping as ORM;
+
+    #[ORM\Entity]
+    final class Author
+    {
+        #[ORM\Id]
+        #[ORM\Column]
+        public null|int $id = null;
+
+        #[ORM\Column(name: 'display_name')]
+        public string $displayName = '';
+
+        // @mago-expect analysis:mismatched-doctrine-column-nullability
+        #[ORM\Column(nullable: true)]
+        public string $biography = '';
+
+        // @mago-expect analysis:mismatched-doctrine-column-nullability
+        #[ORM\Column]
+        public null|string $email = null;
+
+        #[ORM\Column(nullable: true)]
+        public null|string $website = null;
+    }
+
+    #[ORM\Entity(repositoryClass: CommentRepository::class)]
+    final class Comment
+    {
+        #[ORM\Id]
+        #[ORM\Column]
+        public null|int $id = null;
+
+        #[ORM\Column]
+        public string $body = '';
+    }
+
+    #[ORM\Entity]
+    final class Tag
+    {
+        #[ORM\Id]
+        #[ORM\Column]
+        public string $name = '';
+    }
+
+    #[ORM\Entity(repositoryClass: PostRepository::class)]
+    final class Post
+    {
+        #[ORM\Id]
+        #[ORM\Column]
+        public null|int $id = null;
+
+        #[ORM\OneToMany(mappedBy: 'post', targetEntity: Comment::class)]
+        public Collection $comments;
+
+        #[ORM\ManyToMany(Tag::class, indexBy: 'name')]
+        public Collection $tags;
+
+        public function __construct(
+            #[ORM\Column]
+            public string $title,
+            #[ORM\ManyToOne(targetEntity: Author::class)]
+            public Author $author,
+            Collection $comments,
+            Collection $tags,
+        ) {
+            $this->comments = $comments;
+            $this->tags = $tags;
+        }
+    }
+
+    /**
+     * @extends EntityRepository<Post>
+     */
+    final class PostRepository extends EntityRepository
+    {
+    }
+
+    // @mago-expect analysis:missing-template-parameter
+    final class CommentRepository extends EntityRepository
+    {
+    }
+
+    function post_repository(EntityManagerInterface $entityManager): PostRepository
+    {
+        return $entityManager->getRepository(Post::class);
+    }
+
+    function comment_repository(EntityManagerInterface $entityManager): CommentRepository
+    {
+        return $entityManager->getRepository(Comment::class);
+    }
+
+    /**
+     * @return EntityRepository<Author>
+     */
+    function author_repository(EntityManagerInterface $entityManager): EntityRepository
+    {
+        return $entityManager->getRepository(Author::class);
+    }
+
+    function find_post(PostRepository $repository, int $id): null|Post
+    {
+        return $repository->find($id);
+    }
+
+    /**
+     * @param EntityRepository<Comment> $repository
+     */
+    function find_comment(EntityRepository $repository, int $id): null|Comment
+    {
+        return $repository->find($id);
+    }
+
+    /**
+     * @return list<Post>
+     */
+    function posts_by(PostRepository $repository, Author $author): array
+    {
+        return $repository->findBy(['author' => $author, 'title' => 'Hello']);
+    }
+
+    /**
+     * @param array<string, mixed> $criteria
+     *
+     * @return list<Post>
+     */
+    function posts_matching(PostRepository $repository, array $criteria): array
+    {
+        return $repository->findBy($criteria);
+    }
+
+    function author_by_email(EntityManagerInterface $entityManager, string $email): null|Author
+    {
+        return $entityManager->getRepository(Author::class)->findOneBy(['email' => $email]);
+    }
+
+    function author_by_column_name(EntityManagerInterface $entityManager): null|Author
+    {
+        /** @mago-expect analysis:invalid-doctrine-criteria */
+        return $entityManager->getRepository(Author::class)->findOneBy(['display_name' => 'Jane']);
+    }
+
+    /**
+     * @return list<Post>
+     */
+    function posts_by_comments(PostRepository $repository, Comment $comment): array
+    {
+        /** @mago-expect analysis:invalid-doctrine-criteria */
+        return $repository->findBy(['comments' => $comment]);
+    }
+
+    function first_comment(Post $post): null|Comment
+    {
+        $comment = $post->comments->first();
+
+        return false === $comment ? null : $comment;
+    }
+
+    /**
+     * @return list<string>
+     */
+    function tag_names(Post $post): array
+    {
+        $names = [];
+        foreach ($post->tags as $tag) {
+            $names[] = $tag->name;
+        }
+
+        return $names;
+    }
+}
@@ -0,0 +1,111 @@
+<?php
+
+declare(strict_types=1);
+
+/**
+ * @param int<5, 10>|float $quotient
+ */
+function take_quotient(int|float $quotient): void
+{
+}
+
+/**
+ * @param int<0, 100> $square
+ */
+function take_square(int $square): void
+{
+}
+
+function take_int(int $value): void
+{
+}
+
+function take_float(float $value): void
+{
+}
+
+/**
+ * @param int<10, 20> $value
+ * @param int<-10, 10> $base
+ */
+function range_arithmetic(int $value, int $base): void
+{
+    take_quotient($value / 2);
+    take_square($base ** 2);
+}
+
+function definite_overflow(): void
+{
+    /** @mago-expect analysis:integer-overflow */
+    $sum = 9223372036854775807 + 1;
+    take_float($sum);
+
+    /** @mago-expect analysis:integer-overflow */
+    $power = 2 ** 64;
+    take_float($power);
+}
+
+/**
+ * @param int<9223372036854775800, 9223372036854775807> $large
+ */
+function possible_overflow(int $large): void
+{
+    take_int($large - 1);
+    take_int($large + 5); // @mago-expect analysis:possibly-invalid-argument
+}
+
+/**
+ * @param int<0, 10> $divisor
+ */
+function possibly_zero_divisor(int $value, int $divisor): int|float
+{
+    /** @mago-expect analysis:possibly-division-by-zero */
+    return $value / $divisor;
+}
+
+/**
+ * @param int<0, 10> $divisor
+ */
+function possibly_zero_modulo(int $value, int $divisor): int
+{
+    /** @mago-expect analysis:possibly-division-by-zero */
+    return $value % $divisor;
+}
+
+/**
+ * @param int<0, 10> $divisor
+ */
+function divisor_narrowed_by_identity(int $value, int $divisor): int|float
+{
+    if ($divisor === 0) {
+        return 0;
+    }
+
+    return $value / $divisor;
+}
+
+/**
+ * @param int<0, 10> $divisor
+ */
+function divisor_narrowed_by_comparison(int $value, int $divisor): int
+{
+    if ($divisor > 0) {
+        return $value % $divisor;
+    }
+
+    return 0;
+}
+
+function unbounded_divisor(int $value, int $divisor): int|float
+{
+    return $value / $divisor;
+}
+
+/**
+ * @param int<-1, 4> $shift
+ */
+function possibly_negative_shift(int $value, int $shift): int
+{
+    /** @mago-expect analysis:possibly-invalid-operand */
+    return $value << $shift;
+}
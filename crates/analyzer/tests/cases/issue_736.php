@@ -14,7 +14,7 @@ final class Picker
      */
     public static function pickCase(array $choices, int $identifier): mixed
     {
-        return $choices[$identifier % count($choices)] ?? null;
+        return $choices[$identifier % count($choices)] ?? null; // @mago-expect analysis:possibly-division-by-zero
     }
 }
 
@@ -28,7 +28,7 @@ final class Picker
  */
 function pickCase(array $choices, int $identifier): mixed
 {
-    return $choices[$identifier % count($choices)] ?? null;
+    return $choices[$identifier % count($choices)] ?? null; // @mago-expect analysis:possibly-division-by-zero
 }
 
 enum MyCases: string
@@ -0,0 +1,96 @@
+<?php
+
+declare(strict_types=1);
+
+/**
+ * @return array{string, string}
+ */
+function parse_date(string $date): array
+{
+    /** @mago-expect analysis:reference-to-undefined-variable */
+    if (!preg_match('/^(?<year>\d{4})-(\d{2})$/', $date, $matches)) {
+        return ['', ''];
+    }
+
+    return [$matches['year'], $matches[2]];
+}
+
+function optional_trailing_group(string $input): string
+{
+    /** @mago-expect analysis:reference-to-undefined-variable */
+    if (preg_match('/^(\w+)(?:-(\d+))?$/', $input, $matches)) {
+        return $matches[1] . ($matches[2] ?? '');
+    }
+
+    return '';
+}
+
+function unmatched_as_null(string $input): null|string
+{
+    /** @mago-expect analysis:reference-to-undefined-variable */
+    if (preg_match('/^(a)|(b)$/', $input, $matches, PREG_UNMATCHED_AS_NULL)) {
+        return $matches[2];
+    }
+
+    return null;
+}
+
+function offset_capture(string $input): int
+{
+    /** @mago-expect analysis:reference-to-undefined-variable */
+    if (preg_match('/(\d+)/', $input, $matches, PREG_OFFSET_CAPTURE)) {
+        return $matches[1][1];
+    }
+
+    return -1;
+}
+
+/**
+ * @return list<string>
+ */
+function all_words(string $input): array
+{
+    /** @mago-expect analysis:reference-to-undefined-variable */
+    preg_match_all('/(?<word>\w+)/', $input, $matches);
+
+    return $matches['word'];
+}
+
+/**
+ * @return list<string>
+ */
+function all_words_in_set_order(string $input): array
+{
+    /** @mago-expect analysis:reference-to-undefined-variable */
+    preg_match_all('/(\w+)/', $input, $matches, PREG_SET_ORDER);
+
+    $words = [];
+    foreach ($matches as $match) {
+        $words[] = $match[1];
+    }
+
+    return $words;
+}
+
+function invalid_patterns(string $input): void
+{
+    /** @mago-expect analysis:invalid-regex-pattern */
+    preg_match('/abc', $input);
+    /** @mago-expect analysis:invalid-regex-pattern */
+    preg_match('/abc/e', $input);
+    /** @mago-expect analysis:invalid-regex-pattern */
+    preg_match('abc', $input);
+    /** @mago-expect analysis:invalid-regex-pattern */
+    preg_match_all('/(abc/', $input);
+    /** @mago-expect analysis:invalid-regex-pattern */
+    preg_replace_callback('/(?<x>a)(?<x>b)/', static fn(array $m): string => implode('', $m), $input);
+}
+
+/**
+ * @return list<string>|false
+ */
+function split_invalid(string $input): array|false
+{
+    /** @mago-expect analysis:invalid-regex-pattern */
+    return preg_split('/[a-z/', $input);
+}
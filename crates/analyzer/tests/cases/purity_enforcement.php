@@ -0,0 +1,173 @@
+<?php
+
+declare(strict_types=1);
+
+function purity_double(int $value): int
+{
+    return $value * 2;
+}
+
+function purity_quadruple(int $value): int
+{
+    return purity_double(purity_double($value));
+}
+
+function purity_is_even(int $value): bool
+{
+    return $value === 0 || purity_is_odd($value - 1);
+}
+
+function purity_is_odd(int $value): bool
+{
+    return $value !== 0 && purity_is_even($value - 1);
+}
+
+function purity_log(string $message): void
+{
+    echo $message;
+}
+
+/**
+ * @pure
+ */
+function purity_uses_inferred_helpers(int $value): int
+{
+    if (purity_is_even($value)) {
+        return purity_quadruple($value);
+    }
+
+    return strlen((string) $value);
+}
+
+/**
+ * @pure
+ *
+ * @mago-expect analysis:impure-function-call
+ */
+function purity_calls_impure_function(int $value): int
+{
+    purity_log('computing');
+
+    return $value;
+}
+
+/**
+ * @pure
+ *
+ * @mago-expect analysis:impure-global-access
+ */
+function purity_reads_global(): bool
+{
+    global $purity_config;
+
+    return $purity_config !== null;
+}
+
+/**
+ * @pure
+ *
+ * @mago-expect analysis:impure-global-access
+ */
+function purity_reads_superglobal(): bool
+{
+    return count($_GET) > 0;
+}
+
+final class PurityCounter
+{
+    private static int $count = 0;
+
+    public static function increment(): void
+    {
+        self::$count++;
+    }
+
+    /**
+     * @pure
+     *
+     * @mago-expect analysis:impure-static-property-access
+     */
+    public static function current(): int
+    {
+        return self::$count;
+    }
+}
+
+/**
+ * @psalm-immutable
+ */
+final class PurityMoney
+{
+    public function __construct(
+        private int $amount,
+    ) {
+        $this->amount = max(0, $amount);
+    }
+
+    public function getAmount(): int
+    {
+        return $this->amount;
+    }
+
+    public function plus(int $amount): self
+    {
+        return new self($this->amount + $amount);
+    }
+
+    /**
+     * @mago-expect analysis:impure-property-assignment
+     */
+    public function add(int $amount): void
+    {
+        $this->amount = $this->amount + $amount;
+    }
+}
+
+final class PurityAccount
+{
+    public int $balance = 0;
+
+    public function reset(): void
+    {
+        $this->balance = 0;
+    }
+
+    /**
+     * @mutation-free
+     */
+    public function getBalance(): int
+    {
+        return $this->balance;
+    }
+
+    /**
+     * @mutation-free
+     *
+     * @mago-expect analysis:impure-method-call
+     */
+    public function resetAndGetBalance(): int
+    {
+        $this->reset();
+
+        return $this->balance;
+    }
+
+    /**
+     * @external-mutation-free
+     */
+    public function deposit(int $amount): void
+    {
+        $this->balance = $this->getBalance() + $amount;
+    }
+
+    /**
+     * @external-mutation-free
+     *
+     * @mago-expect analysis:impure-property-assignment
+     */
+    public function transferTo(PurityAccount $other): void
+    {
+        $other->balance = $other->balance + $this->balance;
+        $this->balance = 0;
+    }
+}
@@ -3,6 +3,6 @@
 declare(strict_types=1);
 
 $a = 5;
-/** @mago-expect analysis:invalid-operand */
+/** @mago-expect analysis:division-by-zero */
 /** @mago-expect analysis:mixed-assignment */
 $b = $a / 0;
@@ -2,6 +2,6 @@
 
 declare(strict_types=1);
 
-/** @mago-expect analysis:invalid-operand */
+/** @mago-expect analysis:division-by-zero */
 /** @mago-expect analysis:impossible-assignment */
 $x = 10 % 0;
@@ -4,5 +4,6 @@ declare(strict_types=1);
 
 function takesIntFloat(int|float $x): int|float { return $x; }
 
+/** @mago-expect analysis:integer-overflow */
 $a = 2 ** 100;
 takesIntFloat($a);
@@ -0,0 +1,200 @@
+<?php
+
+/**
+ * @psalm-taint-source input
+ */
+function read_input(string $key): string
+{
+    return $key;
+}
+
+/**
+ * @psalm-taint-sink sql $query
+ */
+function run_query(string $query): void
+{
+    echo strlen($query);
+}
+
+/**
+ * @psalm-taint-escape sql
+ */
+function escape_sql(string $value): string
+{
+    return $value;
+}
+
+function greeting(string $name): string
+{
+    return 'Hello, ' . $name;
+}
+
+/**
+ * @mago-expect analysis:tainted-html
+ */
+function echo_through_variables_and_calls(): void
+{
+    $name = read_input('name');
+    $message = greeting($name);
+    echo $message;
+}
+
+function echo_escaped(): void
+{
+    echo htmlspecialchars(read_input('name'));
+}
+
+function echo_overwritten(): void
+{
+    $name = read_input('name');
+    echo strlen($name);
+    $name = strtoupper('anonymous');
+    echo $name;
+}
+
+/**
+ * @mago-expect analysis:tainted-sql
+ */
+function query_through_interpolation(): void
+{
+    $id = read_input('id');
+    run_query("SELECT * FROM users WHERE id = '{$id}'");
+}
+
+function query_escaped(): void
+{
+    $id = escape_sql(read_input('id'));
+    run_query("SELECT * FROM users WHERE id = '{$id}'");
+}
+
+/**
+ * @mago-expect analysis:tainted-shell
+ */
+function run_command_from_superglobal(): void
+{
+    $file = $_GET['file'] ?? '';
+    if (!is_string($file)) {
+        return;
+    }
+
+    shell_exec('ls ' . $file);
+}
+
+function run_escaped_command(): void
+{
+    $file = $_GET['file'] ?? '';
+    if (!is_string($file)) {
+        return;
+    }
+
+    shell_exec('ls ' . escapeshellarg($file));
+}
+
+function echo_after_comparing_against_literals(): void
+{
+    $mode = $_GET['mode'] ?? '';
+    if ($mode === 'light' || $mode === 'dark') {
+        echo $mode;
+    }
+}
+
+/**
+ * @mago-expect analysis:tainted-html
+ */
+function echo_from_closure(): void
+{
+    $name = read_input('name');
+    $print = function () use ($name): void {
+        echo $name;
+    };
+
+    $print();
+}
+
+/**
+ * @mago-expect analysis:tainted-html
+ */
+function echo_conditionally_tainted(bool $condition): void
+{
+    $name = 'guest';
+    if ($condition) {
+        $name = read_input('name');
+    }
+
+    echo $name;
+}
+
+function read_name(): string
+{
+    $name = $_GET['name'] ?? '';
+
+    return is_string($name) ? $name : '';
+}
+
+function identity(string $value): string
+{
+    return $value;
+}
+
+function describe(string $value): string
+{
+    return 'A value of ' . strlen($value) . ' bytes';
+}
+
+/**
+ * @mago-expect analysis:tainted-html
+ */
+function echo_returned_from_another_function(): void
+{
+    echo read_name();
+}
+
+/**
+ * @mago-expect analysis:tainted-html
+ */
+function echo_returned_parameter(): void
+{
+    echo identity(trim(read_input('name')));
+}
+
+function echo_unrelated_return(): void
+{
+    echo describe(read_input('name'));
+    echo gettype(read_input('name'));
+}
+
+final class SearchRequest
+{
+    public string $sort = 'name';
+
+    private string $query = '';
+
+    public function __construct()
+    {
+        $query = $_GET['q'] ?? '';
+        $this->query = is_string($query) ? $query : '';
+        $sort = $_POST['sort'] ?? '';
+        $this->sort = is_string($sort) ? $sort : 'name';
+    }
+
+    public function query(): string
+    {
+        return $this->query;
+    }
+}
+
+/**
+ * @mago-expect analysis:tainted-html
+ */
+function echo_property_through_getter(SearchRequest $request): void
+{
+    echo $request->query();
+}
+
+/**
+ * @mago-expect analysis:tainted-html
+ */
+function echo_property(SearchRequest $request): void
+{
+    echo $request->sort;
+}
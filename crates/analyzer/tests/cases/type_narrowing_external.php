@@ -6,7 +6,7 @@ function extractVersion(string $input): null|string
 {
     $matches = [];
     if (preg_match('/v(\d+\.\d+\.\d+)/', $input, $matches)) {
-        return $matches[1] ?? null;
+        return $matches[1] ?? null; // @mago-expect analysis:redundant-null-coalesce
     }
 
     return null;
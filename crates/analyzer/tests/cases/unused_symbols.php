@@ -0,0 +1,99 @@
+<?php
+
+declare(strict_types=1);
+
+namespace App {
+    use Attribute;
+
+    #[Attribute]
+    final class Listener
+    {
+    }
+
+    interface UnusedInterface
+    {
+    }
+
+    interface Shape
+    {
+        public const int SIDES = 0;
+        public const string UNUSED_INTERFACE_CONSTANT = '';
+    }
+
+    final class Square implements Shape
+    {
+        public const int SIDES = 4;
+        private const int UNUSED = 1;
+        private const int KEPT_FOR_REFLECTION = 2;
+        private const int SIZE = self::SIDES * 2;
+
+        public function area(): int
+        {
+            return self::SIZE * self::SIZE;
+        }
+    }
+
+    final class OnlyUsedByDeadCode
+    {
+    }
+
+    final class DeadUser
+    {
+        public function create(): OnlyUsedByDeadCode
+        {
+            return new OnlyUsedByDeadCode();
+        }
+    }
+
+    enum Status: string
+    {
+        case Active = 'active';
+        case Inactive = 'inactive';
+    }
+
+    enum Suit
+    {
+        case Hearts;
+        case Spades;
+    }
+
+    enum Direction: string
+    {
+        case Up = 'up';
+        case Down = 'down';
+    }
+
+    const USED_CONSTANT = 1;
+    const UNUSED_CONSTANT = 2;
+
+    function used_function(): int
+    {
+        return USED_CONSTANT;
+    }
+
+    function unused_function(): void
+    {
+    }
+
+    function kept_by_pattern(): void
+    {
+    }
+
+    #[Listener]
+    function on_event(): void
+    {
+    }
+
+    echo used_function();
+    echo (new Square())->area();
+    echo Status::Active->value;
+    echo Suit::Hearts->name;
+    echo Direction::from('up')->value;
+}
+
+namespace App\Api {
+    final class Endpoint
+    {
+        public const string PATH = '/';
+    }
+}
@@ -10,11 +10,15 @@ use foldhash::HashSet;
 use mago_allocator::LocalArena;
 use mago_analyzer::Analyzer;
 use mago_analyzer::analysis_result::AnalysisResult;
+use mago_analyzer::dead_code::find_dead_code;
+use mago_analyzer::dead_code::find_unused_symbols;
 use mago_analyzer::plugin::PluginRegistry;
 use mago_analyzer::settings::Settings;
 use mago_codex::metadata::CodebaseMetadata;
 use mago_codex::populator::populate_codebase;
+use mago_codex::populator::populate_taint_summaries;
 use mago_codex::scanner::scan_program;
+use mago_codex::scanner::scan_taint_flows;
 use mago_database::DatabaseReader;
 use mago_database::file::File;
 use mago_names::resolver::NameResolver;
@@ -34,6 +38,8 @@ pub struct TestCase<'src> {
     settings: Option<Settings>,
     expected_property_reads: Vec<(&'src str, &'src str, usize)>,
     expected_symbol_references: Vec<(&'src str, &'src str, usize)>,
+    expected_dead_code: Option<Vec<&'src str>>,
+    expected_unused_symbols: Option<Vec<&'src str>>,
 }
 
 impl<'src> TestCase<'src> {
@@ -45,6 +51,8 @@ impl<'src> TestCase<'src> {
             settings: None,
             expected_property_reads: Vec::new(),
             expected_symbol_references: Vec::new(),
+            expected_dead_code: None,
+            expected_unused_symbols: None,
         }
     }
 
@@ -66,6 +74,18 @@ impl<'src> TestCase<'src> {
         self
     }
 
+    #[must_use]
+    pub fn expect_dead_code(mut self, symbols: Vec<&'src str>) -> Self {
+        self.expected_dead_code = Some(symbols);
+        self
+    }
+
+    #[must_use]
+    pub fn expect_unused_symbols(mut self, messages: Vec<&'src str>) -> Self {
+        self.expected_unused_symbols = Some(messages);
+        self
+    }
+
     pub fn run(self) {
         run_test_case_inner(self);
     }
@@ -133,10 +153,19 @@ fn run_test_case_inner(config: TestCase) {
     let resolved_names = resolver.resolve(program);
 
     let settings = config.settings.unwrap_or_else(default_test_settings);
+    let entry_points = settings.entry_points.clone();
+
+    let mut file_metadata = scan_program(&arena, source_file, program, &resolved_names, settings.version);
+    if settings.taint.enabled {
+        scan_taint_flows(program, &resolved_names, &mut file_metadata);
+    }
 
-    metadata.extend(scan_program(&arena, source_file, program, &resolved_names, settings.version));
+    metadata.extend(file_metadata);
 
     populate_codebase(&mut metadata, &mut symbol_references, WordSet::default(), HashSet::default());
+    if settings.taint.enabled {
+        populate_taint_summaries(&mut metadata);
+    }
 
     let mut analysis_result = AnalysisResult::new(symbol_references);
     let analyzer = Analyzer::new(&arena, source_file, &resolved_names, &metadata, &PLUGIN_REGISTRY, settings);
@@ -168,6 +197,32 @@ fn run_test_case_inner(config: TestCase) {
         );
     }
 
+    if let Some(expected_dead_code) = config.expected_dead_code {
+        let mut expected = expected_dead_code.into_iter().map(str::to_string).collect::<Vec<_>>();
+        expected.sort();
+
+        let mut actual = find_dead_code(&metadata, &analysis_result.symbol_references, &entry_points)
+            .into_iter()
+            .map(|symbol| symbol.name)
+            .collect::<Vec<_>>();
+        actual.sort();
+
+        assert_eq!(expected, actual, "Test '{}': unexpected dead code", config.name);
+    }
+
+    if let Some(expected_unused_symbols) = config.expected_unused_symbols {
+        let mut expected = expected_unused_symbols.into_iter().map(str::to_string).collect::<Vec<_>>();
+        expected.sort();
+
+        let mut actual = find_unused_symbols(&metadata, &analysis_result.symbol_references, &entry_points)
+            .into_iter()
+            .map(|issue| issue.message)
+            .collect::<Vec<_>>();
+        actual.sort();
+
+        assert_eq!(expected, actual, "Test '{}': unexpected unused symbols", config.name);
+    }
+
     verify_reported_issues(config.name, analysis_result, metadata);
 }
 
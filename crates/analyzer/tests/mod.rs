@@ -12,6 +12,9 @@ mod framework;
 ///
 /// - `test_case!(test_name)`: Creates a test using default settings.
 /// - `test_case!(test_name, settings_expression)`: Creates a test with custom settings.
+/// - `test_case!(test_name, expectation: [...], settings: settings_expression)`: Creates a test with
+///   custom settings that calls the `TestCase` method `expectation`, such as `expect_dead_code`, with
+///   the listed values.
 ///
 /// For a given test name, e.g., `my_test`, this macro will:
 ///
@@ -37,6 +40,16 @@ macro_rules! test_case {
             test.run();
         }
     };
+    ($test_name:ident, $expectation:ident: [$($expected:literal),* $(,)?], settings: $settings:expr) => {
+        #[test]
+        fn $test_name() {
+            let content = include_bytes!(concat!("cases/", stringify!($test_name), ".php"));
+            $crate::framework::TestCase::new(stringify!($test_name), content)
+                .settings($settings)
+                .$expectation(vec![$($expected),*])
+                .run();
+        }
+    };
     ($test_name:ident, $settings:expr) => {
         #[test]
         fn $test_name() {
@@ -65,6 +78,48 @@ test_case!(unavailable_symbols, {
     settings.version = mago_php_version::PHPVersion::PHP85;
     settings
 });
+test_case!(taint_analysis, {
+    let mut settings = default_test_settings();
+    settings.taint.enabled = true;
+    settings
+});
+test_case!(dead_code_removal, expect_dead_code: [
+    "App\\UnusedService",
+    "App\\helper_only_used_by_dead_code",
+    "App\\unused_function",
+    "App\\UsedService::$unused",
+    "App\\UsedService::neverCalled",
+    "App\\OnlyImplementedByDeadCode",
+    "App\\DeadImplementation",
+], settings: {
+    let mut settings = default_test_settings();
+    settings.find_unused_definitions = false;
+    settings.entry_points.attributes.insert(mago_word::ascii_lowercase_word(b"App\\Job"));
+    settings.entry_points.symbols.push(mago_analyzer::settings::EntryPointSymbol::parse("App\\Kept").unwrap());
+    settings
+});
+test_case!(unused_symbols, expect_unused_symbols: [
+    "Interface `App\\UnusedInterface` is never used.",
+    "Class `App\\OnlyUsedByDeadCode` is never used.",
+    "Class `App\\DeadUser` is never used.",
+    "Function `App\\unused_function` is never used.",
+    "Class constant `App\\Shape::UNUSED_INTERFACE_CONSTANT` is never used.",
+    "Class constant `App\\Square::UNUSED` is never used.",
+    "Enum case `App\\Status::Inactive` is never used.",
+    "Enum case `App\\Suit::Spades` is never used.",
+    "Constant `App\\UNUSED_CONSTANT` is never used.",
+], settings: {
+    let mut settings = default_test_settings();
+    settings.find_unused_symbols = true;
+    settings.entry_points.attributes.insert(mago_word::ascii_lowercase_word(b"App\\Listener"));
+    settings.entry_points.namespaces.push(mago_word::ascii_lowercase_word(b"App\\Api"));
+    settings.entry_points.symbols.push(mago_analyzer::settings::EntryPointSymbol::parse("App\\kept_*").unwrap());
+    settings
+        .entry_points
+        .symbols
+        .push(mago_analyzer::settings::EntryPointSymbol::parse("App\\Square::KEPT_*").unwrap());
+    settings
+});
 test_case!(argument_count);
 test_case!(array_append_no_overflow_when_max_below_php_int_max);
 test_case!(array_append_overflow_after_explicit_assign_php_int_max);
@@ -407,6 +462,7 @@ test_case!(multiline_intersection_var);
 test_case!(multiline_union_param);
 test_case!(int_mask);
 test_case!(integer_literal_overflow);
+test_case!(integer_range_arithmetic);
 test_case!(integer_range_reconciliation);
 test_case!(integer_reconciliation);
 test_case!(isset_and_nullable_access_assertions);
@@ -424,8 +480,11 @@ test_case!(narrowing_to_numeric_is_idempotent);
 test_case!(priority_queue_implementation);
 test_case!(psl_integration);
 test_case!(psl_int_range);
+test_case!(preg_match_shapes);
 test_case!(psr_container_get);
+test_case!(purity_enforcement);
 test_case!(flow_php_integration);
+test_case!(doctrine_orm);
 test_case!(reconcile_array_index_type);
 test_case!(reconcile_empty_string);
 test_case!(reconcile_non_empty_string);
@@ -52,6 +52,32 @@ impl<'ctx> ScopeContext<'ctx> {
         false
     }
 
+    /// Returns whether the current scope may not mutate any state, including `$this`.
+    #[inline]
+    #[must_use]
+    pub const fn is_mutation_free(&self) -> bool {
+        if let Some(function_like) = self.function_like
+            && (function_like.flags.is_pure() || function_like.flags.is_mutation_free())
+        {
+            return true;
+        }
+
+        false
+    }
+
+    /// Returns whether the current scope may not mutate state other than `$this`.
+    #[inline]
+    #[must_use]
+    pub const fn is_external_mutation_free(&self) -> bool {
+        if let Some(function_like) = self.function_like
+            && function_like.flags.is_external_mutation_free()
+        {
+            return true;
+        }
+
+        self.is_mutation_free()
+    }
+
     /// Returns the calling class-like context, if available.
     #[inline]
     #[must_use]
@@ -22,6 +22,8 @@ use crate::ttype::union::TUnion;
 pub struct ConstantMetadata {
     pub attributes: Vec<AttributeMetadata>,
     pub name: Word,
+    /// The name as written in the source, with the namespace's original casing.
+    pub original_name: Word,
     pub span: Span,
     pub type_metadata: Option<TypeMetadata>,
     pub inferred_type: Option<TUnion>,
@@ -35,14 +37,16 @@ impl ConstantMetadata {
     ///
     /// # Arguments
     ///
-    /// * `name`: The identifier (name) of the constant.
+    /// * `name`: The identifier (name) of the constant, with a lowercased namespace.
+    /// * `original_name`: The identifier as written in the source.
     /// * `span`: The source code location of this specific constant's definition item (`NAME = value`).
     #[inline]
     #[must_use]
-    pub fn new(name: Word, span: Span, flags: MetadataFlags) -> Self {
+    pub fn new(name: Word, original_name: Word, span: Span, flags: MetadataFlags) -> Self {
         Self {
             attributes: Vec::new(),
             name,
+            original_name,
             span,
             flags,
             type_metadata: None,
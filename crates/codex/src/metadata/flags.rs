@@ -52,6 +52,7 @@ impl MetadataFlags {
     pub const POLYFILL: MetadataFlags = MetadataFlags(1 << 41);
     pub const PATCH: MetadataFlags = MetadataFlags(1 << 42);
     pub const EXTERNAL: MetadataFlags = MetadataFlags(1 << 43);
+    pub const INFERRED_PURE: MetadataFlags = MetadataFlags(1 << 44);
 }
 
 impl MetadataFlags {
@@ -322,6 +323,13 @@ impl MetadataFlags {
         self.contains(Self::EXTERNAL_MUTATION_FREE)
     }
 
+    /// Returns whether purity was inferred from the body rather than declared with `@pure`.
+    #[inline]
+    #[must_use]
+    pub const fn is_inferred_pure(self) -> bool {
+        self.contains(Self::INFERRED_PURE)
+    }
+
     #[inline]
     #[must_use]
     pub const fn suspends_fiber(self) -> bool {
@@ -16,6 +16,7 @@ use crate::metadata::attribute::AttributeMetadata;
 use crate::metadata::class_like::TemplateTypes;
 use crate::metadata::flags::MetadataFlags;
 use crate::metadata::parameter::FunctionLikeParameterMetadata;
+use crate::metadata::taint::TaintMetadata;
 use crate::metadata::ttype::TypeMetadata;
 use crate::metadata::version_constraint::VersionConstraint;
 use crate::ttype::resolution::TypeResolutionContext;
@@ -157,10 +158,19 @@ pub struct FunctionLikeMetadata {
     /// callee can reassign them behind the caller's back.
     pub globals_accessed: WordSet,
 
+    /// Names of the functions called by a body that has no side effects of its own,
+    /// as resolved at the call sites. The populator marks the function as
+    /// [`MetadataFlags::INFERRED_PURE`] once every one of them is pure. `None` when
+    /// the body is impure, or when the function is declared `@impure`.
+    pub purity_dependencies: Option<Vec<Word>>,
+
     /// Tracks whether this function/method has a docblock comment.
     /// Used to determine if docblock inheritance should occur implicitly.
     pub has_docblock: bool,
 
+    /// Taint sources, sinks and sanitizers declared through `@psalm-taint-*` docblock tags.
+    pub taint: TaintMetadata,
+
     pub flags: MetadataFlags,
 
     /// PHP version range in which this function-like is available, derived
@@ -230,7 +240,9 @@ impl FunctionLikeMetadata {
             if_false_assertions: BTreeMap::new(),
             assertions_inferred: false,
             globals_accessed: WordSet::default(),
+            purity_dependencies: None,
             has_docblock: false,
+            taint: TaintMetadata::default(),
             issues: vec![],
             version_constraint: VersionConstraint::unconstrained(),
         }
@@ -7,6 +7,7 @@ use mago_word::WordMap;
 use crate::metadata::attribute::AttributeMetadata;
 use crate::metadata::flags::MetadataFlags;
 use crate::metadata::property_hook::PropertyHookMetadata;
+use crate::metadata::taint::TaintKinds;
 use crate::metadata::ttype::TypeMetadata;
 use crate::metadata::version_constraint::VersionConstraint;
 use crate::misc::VariableIdentifier;
@@ -88,6 +89,10 @@ pub struct PropertyMetadata {
     /// `Mago\AvailableSince` / `Mago\AvailableUntil` attributes during
     /// scanning.
     pub version_constraint: VersionConstraint,
+
+    /// Kinds of taint the methods of the declaring class may write to the property,
+    /// resolved by the populator.
+    pub taint: TaintKinds,
 }
 
 impl PropertyMetadata {
@@ -110,6 +115,7 @@ impl PropertyMetadata {
             attributes: Vec::new(),
             hooks: WordMap::default(),
             version_constraint: VersionConstraint::unconstrained(),
+            taint: TaintKinds::empty(),
         }
     }
 
@@ -0,0 +1,410 @@
+use mago_word::Word;
+
+/// Superglobals whose every entry is controlled by the client.
+pub const INPUT_SUPERGLOBALS: [&[u8]; 5] = [b"$_GET", b"$_POST", b"$_REQUEST", b"$_COOKIE", b"$_FILES"];
+
+/// `$_SERVER` entries that are populated from the request, besides the `HTTP_*` headers.
+pub const USER_CONTROLLED_SERVER_KEYS: [&[u8]; 6] =
+    [b"REQUEST_URI", b"QUERY_STRING", b"PHP_SELF", b"PATH_INFO", b"PATH_TRANSLATED", b"ORIG_PATH_INFO"];
+
+/// Built-in functions and methods returning user-controlled data.
+pub const BUILTIN_SOURCES: [(Option<&str>, &str, TaintKinds); 4] = [
+    (None, "getallheaders", TaintKinds::INPUT),
+    (None, "apache_request_headers", TaintKinds::INPUT),
+    (None, "filter_input", TaintKinds::INPUT),
+    (None, "filter_input_array", TaintKinds::INPUT),
+];
+
+/// Built-in sanitizers, and the kinds of taint they remove from the value they return.
+pub const BUILTIN_SANITIZERS: [(Option<&str>, &str, TaintKinds); 32] = [
+    (None, "htmlspecialchars", TaintKinds::HTML.union(TaintKinds::HAS_QUOTES)),
+    (None, "htmlentities", TaintKinds::HTML.union(TaintKinds::HAS_QUOTES)),
+    (None, "strip_tags", TaintKinds::HTML),
+    (None, "escapeshellarg", TaintKinds::SHELL),
+    (None, "escapeshellcmd", TaintKinds::SHELL),
+    (None, "mysqli_real_escape_string", TaintKinds::SQL.union(TaintKinds::HAS_QUOTES)),
+    (None, "mysqli_escape_string", TaintKinds::SQL.union(TaintKinds::HAS_QUOTES)),
+    (None, "pg_escape_string", TaintKinds::SQL.union(TaintKinds::HAS_QUOTES)),
+    (None, "pg_escape_literal", TaintKinds::SQL.union(TaintKinds::HAS_QUOTES)),
+    (Some("mysqli"), "real_escape_string", TaintKinds::SQL.union(TaintKinds::HAS_QUOTES)),
+    (Some("mysqli"), "escape_string", TaintKinds::SQL.union(TaintKinds::HAS_QUOTES)),
+    (Some("pdo"), "quote", TaintKinds::SQL.union(TaintKinds::HAS_QUOTES)),
+    (Some("sqlite3"), "escapestring", TaintKinds::SQL.union(TaintKinds::HAS_QUOTES)),
+    (None, "ldap_escape", TaintKinds::LDAP),
+    (None, "urlencode", TaintKinds::HTML.union(TaintKinds::HAS_QUOTES).union(TaintKinds::SHELL)),
+    (None, "rawurlencode", TaintKinds::HTML.union(TaintKinds::HAS_QUOTES).union(TaintKinds::SHELL)),
+    (None, "basename", TaintKinds::FILE.union(TaintKinds::INCLUDE)),
+    (None, "intval", TaintKinds::ALL),
+    (None, "floatval", TaintKinds::ALL),
+    (None, "boolval", TaintKinds::ALL),
+    (None, "count", TaintKinds::ALL),
+    (None, "strlen", TaintKinds::ALL),
+    (None, "md5", TaintKinds::ALL),
+    (None, "sha1", TaintKinds::ALL),
+    (None, "crc32", TaintKinds::ALL),
+    (None, "hash", TaintKinds::ALL),
+    (None, "hash_hmac", TaintKinds::ALL),
+    (None, "bin2hex", TaintKinds::ALL),
+    (None, "base64_encode", TaintKinds::ALL),
+    (None, "password_hash", TaintKinds::ALL),
+    (None, "uniqid", TaintKinds::ALL),
+    (None, "spl_object_hash", TaintKinds::ALL),
+];
+
+/// Built-in functions that re-introduce taint previously removed by a sanitizer.
+pub const BUILTIN_UNESCAPES: [(Option<&str>, &str, TaintKinds); 2] = [
+    (None, "html_entity_decode", TaintKinds::HTML.union(TaintKinds::HAS_QUOTES)),
+    (None, "htmlspecialchars_decode", TaintKinds::HTML.union(TaintKinds::HAS_QUOTES)),
+];
+
+/// Built-in functions whose return value is derived from their arguments, and so carries their taint.
+///
+/// Other calls to functions without a body only pass taint through when the function is a known
+/// sanitizer, or declares `@taint-escape` or `@taint-unescape`.
+pub const BUILTIN_PROPAGATORS: [(Option<&str>, &str); 32] = [
+    (None, "trim"),
+    (None, "ltrim"),
+    (None, "rtrim"),
+    (None, "strtolower"),
+    (None, "strtoupper"),
+    (None, "mb_strtolower"),
+    (None, "mb_strtoupper"),
+    (None, "ucfirst"),
+    (None, "lcfirst"),
+    (None, "ucwords"),
+    (None, "substr"),
+    (None, "mb_substr"),
+    (None, "str_pad"),
+    (None, "str_repeat"),
+    (None, "strrev"),
+    (None, "str_replace"),
+    (None, "str_ireplace"),
+    (None, "preg_replace"),
+    (None, "sprintf"),
+    (None, "vsprintf"),
+    (None, "implode"),
+    (None, "join"),
+    (None, "explode"),
+    (None, "nl2br"),
+    (None, "wordwrap"),
+    (None, "stripslashes"),
+    (None, "urldecode"),
+    (None, "rawurldecode"),
+    (None, "base64_decode"),
+    (None, "json_encode"),
+    (None, "json_decode"),
+    (None, "http_build_query"),
+];
+
+/// A set of taint kinds, describing what a tainted value could be abused for.
+///
+/// Kinds follow the naming used by `@psalm-taint-*` annotations, e.g. `html`, `sql` or `shell`.
+/// The pseudo-kind `input` stands for every kind that user-controlled input can carry.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TaintKinds(u32);
+
+impl TaintKinds {
+    pub const HTML: TaintKinds = TaintKinds(1 << 0);
+    pub const HAS_QUOTES: TaintKinds = TaintKinds(1 << 1);
+    pub const SQL: TaintKinds = TaintKinds(1 << 2);
+    pub const SHELL: TaintKinds = TaintKinds(1 << 3);
+    pub const CALLABLE: TaintKinds = TaintKinds(1 << 4);
+    pub const UNSERIALIZE: TaintKinds = TaintKinds(1 << 5);
+    pub const INCLUDE: TaintKinds = TaintKinds(1 << 6);
+    pub const EVAL: TaintKinds = TaintKinds(1 << 7);
+    pub const LDAP: TaintKinds = TaintKinds(1 << 8);
+    pub const FILE: TaintKinds = TaintKinds(1 << 9);
+    pub const HEADER: TaintKinds = TaintKinds(1 << 10);
+    pub const COOKIE: TaintKinds = TaintKinds(1 << 11);
+    pub const SSRF: TaintKinds = TaintKinds(1 << 12);
+    pub const XPATH: TaintKinds = TaintKinds(1 << 13);
+    pub const USER_SECRET: TaintKinds = TaintKinds(1 << 14);
+    pub const SYSTEM_SECRET: TaintKinds = TaintKinds(1 << 15);
+
+    /// Every kind carried by user-controlled input.
+    pub const INPUT: TaintKinds = TaintKinds(
+        Self::HTML.0
+            | Self::HAS_QUOTES.0
+            | Self::SQL.0
+            | Self::SHELL.0
+            | Self::CALLABLE.0
+            | Self::UNSERIALIZE.0
+            | Self::INCLUDE.0
+            | Self::EVAL.0
+            | Self::LDAP.0
+            | Self::FILE.0
+            | Self::HEADER.0
+            | Self::COOKIE.0
+            | Self::SSRF.0
+            | Self::XPATH.0,
+    );
+
+    /// Every known kind, including secrets.
+    pub const ALL: TaintKinds = TaintKinds(Self::INPUT.0 | Self::USER_SECRET.0 | Self::SYSTEM_SECRET.0);
+
+    const NAMES: [(&'static str, TaintKinds); 16] = [
+        ("html", Self::HTML),
+        ("has_quotes", Self::HAS_QUOTES),
+        ("sql", Self::SQL),
+        ("shell", Self::SHELL),
+        ("callable", Self::CALLABLE),
+        ("unserialize", Self::UNSERIALIZE),
+        ("include", Self::INCLUDE),
+        ("eval", Self::EVAL),
+        ("ldap", Self::LDAP),
+        ("file", Self::FILE),
+        ("header", Self::HEADER),
+        ("cookie", Self::COOKIE),
+        ("ssrf", Self::SSRF),
+        ("xpath", Self::XPATH),
+        ("user_secret", Self::USER_SECRET),
+        ("system_secret", Self::SYSTEM_SECRET),
+    ];
+
+    #[inline]
+    #[must_use]
+    pub const fn empty() -> Self {
+        TaintKinds(0)
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn bits(self) -> u32 {
+        self.0
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn contains(self, other: TaintKinds) -> bool {
+        (self.0 & other.0) == other.0
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn intersects(self, other: TaintKinds) -> bool {
+        (self.0 & other.0) != 0
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn union(self, other: TaintKinds) -> Self {
+        TaintKinds(self.0 | other.0)
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn intersection(self, other: TaintKinds) -> Self {
+        TaintKinds(self.0 & other.0)
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn difference(self, other: TaintKinds) -> Self {
+        TaintKinds(self.0 & !other.0)
+    }
+
+    #[inline]
+    pub const fn insert(&mut self, other: TaintKinds) {
+        self.0 |= other.0;
+    }
+
+    /// Parses a taint kind name, as written in `@psalm-taint-*` annotations or configuration.
+    ///
+    /// Names are case-insensitive, and dashes are accepted in place of underscores.
+    /// Besides the individual kinds, `input` and `all` are recognized.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim().to_ascii_lowercase().replace('-', "_");
+
+        match name.as_str() {
+            "input" => Some(Self::INPUT),
+            "all" => Some(Self::ALL),
+            _ => Self::NAMES.iter().find(|(kind_name, _)| *kind_name == name).map(|(_, kind)| *kind),
+        }
+    }
+
+    /// Returns the names of the individual kinds in this set.
+    pub fn names(self) -> impl Iterator<Item = &'static str> {
+        Self::NAMES.into_iter().filter(move |(_, kind)| self.contains(*kind)).map(|(name, _)| name)
+    }
+}
+
+impl std::ops::BitOr for TaintKinds {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.union(rhs)
+    }
+}
+
+impl std::ops::BitOrAssign for TaintKinds {
+    #[inline]
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.insert(rhs);
+    }
+}
+
+/// A value whose taint can only be resolved once the whole codebase has been scanned.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TaintDependency {
+    /// The value returned by a call to the named function (lowercase, as resolved in its namespace),
+    /// with the flows of its arguments and the names of the named ones.
+    FunctionCall(Word, Vec<(Option<Word>, TaintFlow)>),
+
+    /// The value returned by a call to a method of the given class (both lowercase), with the flows of
+    /// its arguments and the names of the named ones.
+    MethodCall(Word, Word, Vec<(Option<Word>, TaintFlow)>),
+
+    /// The value of a property of the given class. Property names include the leading `$`.
+    Property(Word, Word),
+}
+
+/// Where the taint of a value may come from, as inferred from a function-like body by the scanner.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TaintFlow {
+    /// Kinds of taint introduced directly, e.g. by reading `$_GET`.
+    pub sources: TaintKinds,
+
+    /// Parameters of the function-like the value may come from, including the leading `$`.
+    pub parameters: Vec<Word>,
+
+    /// Calls and properties the value may come from.
+    pub dependencies: Vec<TaintDependency>,
+}
+
+impl TaintFlow {
+    /// Returns `true` if the value cannot carry any taint.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.sources.is_empty() && self.parameters.is_empty() && self.dependencies.is_empty()
+    }
+
+    /// Adds everything the other flow may come from to this one.
+    pub fn extend(&mut self, other: TaintFlow) {
+        self.sources |= other.sources;
+
+        for parameter in other.parameters {
+            if !self.parameters.contains(&parameter) {
+                self.parameters.push(parameter);
+            }
+        }
+
+        for dependency in other.dependencies {
+            if !self.dependencies.contains(&dependency) {
+                self.dependencies.push(dependency);
+            }
+        }
+    }
+}
+
+/// Taint annotations declared on a function-like through `@psalm-taint-*` docblock tags, and the
+/// summary of its body inferred by the scanner and resolved by the populator.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TaintMetadata {
+    /// Kinds of taint introduced by the return value (`@psalm-taint-source`).
+    pub sources: TaintKinds,
+
+    /// Parameters that must not receive tainted values, and the kinds they are sensitive to
+    /// (`@psalm-taint-sink <kind> $param`). Parameter names include the leading `$`.
+    pub sinks: Vec<(Word, TaintKinds)>,
+
+    /// Kinds of taint removed from values flowing through the function (`@psalm-taint-escape`).
+    pub escapes: TaintKinds,
+
+    /// Kinds of taint re-introduced for values flowing through the function (`@psalm-taint-unescape`).
+    pub unescapes: TaintKinds,
+
+    /// Where the return value may come from, as inferred from the body.
+    ///
+    /// `None` for function-likes without a body, such as built-in functions and abstract methods.
+    pub return_flow: Option<TaintFlow>,
+
+    /// Values written to the properties of the declaring class (`$this->name`, `self::$name`),
+    /// as inferred from the body. Property names include the leading `$`.
+    pub property_flows: Vec<(Word, TaintFlow)>,
+
+    /// Kinds of taint the return value carries whatever the arguments, resolved by the populator.
+    pub returned_sources: TaintKinds,
+
+    /// Parameters whose taint flows into the return value, and the kinds of taint that survive the
+    /// flow, resolved by the populator.
+    pub returned_parameters: Vec<(Word, TaintKinds)>,
+}
+
+impl TaintMetadata {
+    /// Returns `true` if no taint annotation was declared.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.sources.is_empty() && self.sinks.is_empty() && self.escapes.is_empty() && self.unescapes.is_empty()
+    }
+
+    /// Returns `true` if the return value is summarized from the body of the function-like.
+    #[inline]
+    #[must_use]
+    pub fn has_return_summary(&self) -> bool {
+        self.return_flow.is_some()
+    }
+
+    /// Returns the kinds of taint that flow from the given parameter into the return value.
+    #[must_use]
+    pub fn get_returned_parameter(&self, parameter: Word) -> TaintKinds {
+        self.returned_parameters
+            .iter()
+            .filter(|(name, _)| *name == parameter)
+            .fold(TaintKinds::empty(), |kinds, (_, parameter_kinds)| kinds | *parameter_kinds)
+    }
+
+    /// Returns the kinds the given parameter is sensitive to, if it is declared as a sink.
+    #[must_use]
+    pub fn get_sink(&self, parameter: Word) -> TaintKinds {
+        self.sinks
+            .iter()
+            .filter(|(name, _)| *name == parameter)
+            .fold(TaintKinds::empty(), |kinds, (_, sink_kinds)| kinds | *sink_kinds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_kind_names() {
+        assert_eq!(TaintKinds::from_name("html"), Some(TaintKinds::HTML));
+        assert_eq!(TaintKinds::from_name(" SQL "), Some(TaintKinds::SQL));
+        assert_eq!(TaintKinds::from_name("input"), Some(TaintKinds::INPUT));
+        assert_eq!(TaintKinds::from_name("unknown"), None);
+    }
+
+    #[test]
+    fn input_excludes_secrets() {
+        assert!(TaintKinds::INPUT.contains(TaintKinds::HTML));
+        assert!(!TaintKinds::INPUT.intersects(TaintKinds::USER_SECRET | TaintKinds::SYSTEM_SECRET));
+        assert!(TaintKinds::ALL.contains(TaintKinds::SYSTEM_SECRET));
+    }
+
+    #[test]
+    fn extends_flows_without_duplicates() {
+        let parameter = mago_word::word("$value");
+        let mut flow = TaintFlow { parameters: vec![parameter], ..TaintFlow::default() };
+        flow.extend(TaintFlow { sources: TaintKinds::HTML, parameters: vec![parameter], dependencies: vec![] });
+
+        assert_eq!(flow.sources, TaintKinds::HTML);
+        assert_eq!(flow.parameters, vec![parameter]);
+        assert!(!flow.is_empty());
+        assert!(TaintFlow::default().is_empty());
+    }
+}
@@ -18,8 +18,10 @@ mod hierarchy;
 mod merge;
 mod methods;
 mod properties;
+mod purity;
 mod signatures;
 mod sorter;
+mod taint;
 mod templates;
 
 /// Populates the codebase metadata, resolving types and inheritance.
@@ -30,6 +32,7 @@ mod templates;
 /// - Calculate inheritance hierarchies (parent classes, interfaces, traits).
 /// - Determine method and property origins (declaring vs. appearing).
 /// - Build descendant maps for efficient lookup.
+/// - Infer the purity of functions from their bodies and the functions they call.
 #[allow(clippy::implicit_hasher)]
 pub fn populate_codebase(
     codebase: &mut CodebaseMetadata,
@@ -320,10 +323,22 @@ fn populate_codebase_inner(
         docblock::inherit_property_docblocks(codebase, &safe_symbols, dirty_classes);
     }
 
+    purity::infer_function_purity(codebase);
+
     codebase.safe_symbols = safe_symbols;
     codebase.safe_symbol_members = safe_symbol_members;
 }
 
+/// Resolves the taint returned by function-likes and written to properties, from the flows
+/// recorded by [`scan_taint_flows`].
+///
+/// This only needs to run when taint analysis is enabled, after the codebase has been populated.
+///
+/// [`scan_taint_flows`]: crate::scanner::scan_taint_flows
+pub fn populate_taint_summaries(codebase: &mut CodebaseMetadata) {
+    taint::infer_taint_summaries(codebase);
+}
+
 /// Populates a single constant's type metadata.
 fn populate_constant(
     name: Word,
@@ -0,0 +1,73 @@
+use mago_word::Word;
+use mago_word::WordMap;
+use mago_word::empty_word;
+
+use crate::metadata::CodebaseMetadata;
+use crate::metadata::flags::MetadataFlags;
+
+/// Marks functions as [`MetadataFlags::INFERRED_PURE`] when their bodies have no side
+/// effects and every function they call is pure.
+///
+/// Every function with recorded purity dependencies starts out as a candidate; candidates
+/// calling a function that is neither declared pure nor still a candidate are dropped until
+/// nothing changes, so recursive and mutually recursive functions are inferred as well.
+pub fn infer_function_purity(codebase: &mut CodebaseMetadata) {
+    let mut candidates: WordMap<Vec<Word>> = WordMap::default();
+    for ((class_name, function_name), metadata) in &mut codebase.function_likes {
+        metadata.flags.set(MetadataFlags::INFERRED_PURE, false);
+
+        if class_name.is_empty()
+            && !metadata.flags.is_pure()
+            && let Some(dependencies) = &metadata.purity_dependencies
+        {
+            candidates.insert(*function_name, dependencies.clone());
+        }
+    }
+
+    loop {
+        let impure = candidates
+            .iter()
+            .filter(|(_, dependencies)| {
+                !dependencies.iter().all(|dependency| is_pure_dependency(codebase, &candidates, *dependency))
+            })
+            .map(|(function_name, _)| *function_name)
+            .collect::<Vec<_>>();
+
+        if impure.is_empty() {
+            break;
+        }
+
+        for function_name in impure {
+            candidates.remove(&function_name);
+        }
+    }
+
+    for function_name in candidates.keys() {
+        if let Some(metadata) = codebase.function_likes.get_mut(&(empty_word(), *function_name)) {
+            metadata.flags |= MetadataFlags::INFERRED_PURE;
+        }
+    }
+}
+
+fn is_pure_dependency(codebase: &CodebaseMetadata, candidates: &WordMap<Vec<Word>>, dependency: Word) -> bool {
+    let Some(function_name) = resolve_function_name(codebase, dependency) else {
+        return false;
+    };
+
+    candidates.contains_key(&function_name)
+        || codebase.function_likes.get(&(empty_word(), function_name)).is_some_and(|metadata| metadata.flags.is_pure())
+}
+
+/// Resolves a called name the way PHP does, falling back to the global function when a
+/// namespaced one does not exist.
+pub(super) fn resolve_function_name(codebase: &CodebaseMetadata, name: Word) -> Option<Word> {
+    if codebase.function_likes.contains_key(&(empty_word(), name)) {
+        return Some(name);
+    }
+
+    let bytes = name.as_bytes();
+    let separator = memchr::memrchr(b'\\', bytes)?;
+    let global_name = Word::new(&bytes[separator + 1..]);
+
+    codebase.function_likes.contains_key(&(empty_word(), global_name)).then_some(global_name)
+}
@@ -0,0 +1,266 @@
+use mago_word::Word;
+use mago_word::empty_word;
+
+use crate::metadata::CodebaseMetadata;
+use crate::metadata::function_like::FunctionLikeMetadata;
+use crate::metadata::taint::BUILTIN_PROPAGATORS;
+use crate::metadata::taint::BUILTIN_SANITIZERS;
+use crate::metadata::taint::BUILTIN_SOURCES;
+use crate::metadata::taint::BUILTIN_UNESCAPES;
+use crate::metadata::taint::TaintDependency;
+use crate::metadata::taint::TaintFlow;
+use crate::metadata::taint::TaintKinds;
+use crate::populator::purity::resolve_function_name;
+
+/// Resolves the taint summaries of function-likes and properties from the flows recorded by the scanner.
+///
+/// A function-like returns the sources of its return flow, and those of the calls and properties the
+/// flow depends on; parameters are followed through the arguments of the calls they are passed to.
+/// A property carries the sources written to it by the methods of its class. Summaries only grow, so
+/// resolution repeats until nothing changes, which also covers recursive and mutually recursive calls.
+pub fn infer_taint_summaries(codebase: &mut CodebaseMetadata) {
+    for metadata in codebase.function_likes.values_mut() {
+        metadata.taint.returned_sources = TaintKinds::empty();
+        metadata.taint.returned_parameters.clear();
+    }
+
+    for class_like_metadata in codebase.class_likes.values_mut() {
+        for property_metadata in class_like_metadata.properties.values_mut() {
+            property_metadata.taint = TaintKinds::empty();
+        }
+    }
+
+    loop {
+        let mut returns = vec![];
+        let mut properties = vec![];
+        for (function_like_id, metadata) in &codebase.function_likes {
+            if let Some(return_flow) = &metadata.taint.return_flow {
+                let resolved =
+                    resolve_flow(codebase, return_flow).through(metadata.taint.escapes, metadata.taint.unescapes);
+
+                if !metadata.taint.returned_sources.contains(resolved.sources)
+                    || resolved
+                        .parameters
+                        .iter()
+                        .any(|(parameter, kinds)| !metadata.taint.get_returned_parameter(*parameter).contains(*kinds))
+                {
+                    returns.push((*function_like_id, resolved));
+                }
+            }
+
+            for (property_name, property_flow) in &metadata.taint.property_flows {
+                let sources = resolve_flow(codebase, property_flow).sources;
+                if sources.is_empty() {
+                    continue;
+                }
+
+                if let Some(declaring_class) =
+                    codebase.get_declaring_property_class(function_like_id.0.as_bytes(), property_name.as_bytes())
+                    && let Some(property_metadata) = codebase
+                        .class_likes
+                        .get(&declaring_class)
+                        .and_then(|class_like_metadata| class_like_metadata.properties.get(property_name))
+                    && !property_metadata.taint.contains(sources)
+                {
+                    properties.push((declaring_class, *property_name, sources));
+                }
+            }
+        }
+
+        if returns.is_empty() && properties.is_empty() {
+            break;
+        }
+
+        for (function_like_id, resolved) in returns {
+            if let Some(metadata) = codebase.function_likes.get_mut(&function_like_id) {
+                metadata.taint.returned_sources |= resolved.sources;
+                for (parameter, kinds) in resolved.parameters {
+                    add_parameter(&mut metadata.taint.returned_parameters, parameter, kinds);
+                }
+            }
+        }
+
+        for (declaring_class, property_name, sources) in properties {
+            if let Some(property_metadata) = codebase
+                .class_likes
+                .get_mut(&declaring_class)
+                .and_then(|class_like_metadata| class_like_metadata.properties.get_mut(&property_name))
+            {
+                property_metadata.taint |= sources;
+            }
+        }
+    }
+}
+
+/// The taint of a value: the kinds it carries whatever the arguments, and the parameters it may
+/// come from, with the kinds of taint that survive on the way.
+#[derive(Debug, Default)]
+struct ResolvedTaint {
+    sources: TaintKinds,
+    parameters: Vec<(Word, TaintKinds)>,
+}
+
+impl ResolvedTaint {
+    fn extend(&mut self, other: ResolvedTaint) {
+        self.sources |= other.sources;
+        for (parameter, kinds) in other.parameters {
+            add_parameter(&mut self.parameters, parameter, kinds);
+        }
+    }
+
+    /// Applies a function's sanitizers and unescapes to the taint flowing through it.
+    fn through(mut self, escapes: TaintKinds, unescapes: TaintKinds) -> Self {
+        if !self.sources.is_empty() {
+            self.sources = self.sources.difference(escapes) | unescapes;
+        }
+
+        for (_, kinds) in &mut self.parameters {
+            *kinds = kinds.difference(escapes) | unescapes;
+        }
+
+        self.parameters.retain(|(_, kinds)| !kinds.is_empty());
+        self
+    }
+
+    /// Keeps only the given kinds of taint.
+    fn restrict(mut self, kinds: TaintKinds) -> Self {
+        self.sources = self.sources.intersection(kinds);
+        for (_, parameter_kinds) in &mut self.parameters {
+            *parameter_kinds = parameter_kinds.intersection(kinds);
+        }
+
+        self.parameters.retain(|(_, kinds)| !kinds.is_empty());
+        self
+    }
+}
+
+fn add_parameter(parameters: &mut Vec<(Word, TaintKinds)>, parameter: Word, kinds: TaintKinds) {
+    if kinds.is_empty() {
+        return;
+    }
+
+    match parameters.iter_mut().find(|(name, _)| *name == parameter) {
+        Some((_, existing)) => *existing |= kinds,
+        None => parameters.push((parameter, kinds)),
+    }
+}
+
+fn resolve_flow(codebase: &CodebaseMetadata, flow: &TaintFlow) -> ResolvedTaint {
+    let mut resolved = ResolvedTaint {
+        sources: flow.sources,
+        parameters: flow.parameters.iter().map(|parameter| (*parameter, TaintKinds::ALL)).collect(),
+    };
+
+    for dependency in &flow.dependencies {
+        match dependency {
+            TaintDependency::Property(class_name, property_name) => {
+                if let Some(property_metadata) =
+                    codebase.get_declaring_property(class_name.as_bytes(), property_name.as_bytes())
+                {
+                    resolved.sources |= property_metadata.taint;
+                }
+            }
+            TaintDependency::FunctionCall(function_name, arguments) => {
+                if let Some(function_name) = resolve_function_name(codebase, *function_name)
+                    && let Some(callee) = codebase.function_likes.get(&(empty_word(), function_name))
+                {
+                    resolved.extend(resolve_call(codebase, callee, Some(function_name), arguments));
+                }
+            }
+            TaintDependency::MethodCall(class_name, method_name, arguments) => {
+                if let Some(callee) = codebase.get_declaring_method(class_name.as_bytes(), method_name.as_bytes()) {
+                    resolved.extend(resolve_call(codebase, callee, None, arguments));
+                }
+            }
+        }
+    }
+
+    resolved
+}
+
+/// Resolves the taint of the value returned by a call.
+///
+/// Callees with a body pass on the arguments their summary returns; callees without one only pass
+/// their arguments through when they are known sanitizers or propagators, or declare
+/// `@taint-escape` or `@taint-unescape`.
+fn resolve_call(
+    codebase: &CodebaseMetadata,
+    callee: &FunctionLikeMetadata,
+    function_name: Option<Word>,
+    arguments: &[(Option<Word>, TaintFlow)],
+) -> ResolvedTaint {
+    let mut resolved = ResolvedTaint { sources: callee.taint.sources, ..ResolvedTaint::default() };
+
+    if callee.taint.has_return_summary() {
+        resolved.sources |= callee.taint.returned_sources;
+        for (index, parameter) in callee.parameters.iter().enumerate() {
+            let kinds = callee.taint.get_returned_parameter(parameter.get_name().0);
+            if kinds.is_empty() {
+                continue;
+            }
+
+            for argument in get_argument_flows(callee, arguments, index) {
+                resolved.extend(resolve_flow(codebase, argument).restrict(kinds));
+            }
+        }
+
+        return resolved;
+    }
+
+    let mut escapes = callee.taint.escapes;
+    let mut unescapes = callee.taint.unescapes;
+    let mut propagates = !escapes.is_empty() || !unescapes.is_empty();
+    if let Some(function_name) = function_name {
+        let function_name = function_name.as_bytes();
+        for (class, name, kinds) in BUILTIN_SOURCES {
+            if class.is_none() && name.as_bytes() == function_name {
+                resolved.sources |= kinds;
+            }
+        }
+
+        for (class, name, kinds) in BUILTIN_SANITIZERS {
+            if class.is_none() && name.as_bytes() == function_name {
+                escapes |= kinds;
+                propagates = true;
+            }
+        }
+
+        for (class, name, kinds) in BUILTIN_UNESCAPES {
+            if class.is_none() && name.as_bytes() == function_name {
+                unescapes |= kinds;
+                propagates = true;
+            }
+        }
+
+        propagates |=
+            BUILTIN_PROPAGATORS.iter().any(|(class, name)| class.is_none() && name.as_bytes() == function_name);
+    }
+
+    if propagates {
+        for (_, argument) in arguments {
+            resolved.extend(resolve_flow(codebase, argument).through(escapes, unescapes));
+        }
+    }
+
+    resolved
+}
+
+/// Returns the flows of the arguments passed to the parameter at `index`, by position or by name.
+fn get_argument_flows<'flow>(
+    callee: &FunctionLikeMetadata,
+    arguments: &'flow [(Option<Word>, TaintFlow)],
+    index: usize,
+) -> impl Iterator<Item = &'flow TaintFlow> {
+    let parameter = &callee.parameters[index];
+    let parameter_name = parameter.get_name().0;
+    let is_variadic = parameter.flags.is_variadic();
+
+    arguments
+        .iter()
+        .enumerate()
+        .filter(move |(position, (argument_name, _))| match argument_name {
+            Some(argument_name) => *argument_name == parameter_name,
+            None => *position == index || (is_variadic && *position > index),
+        })
+        .map(|(_, (_, flow))| flow)
+}
@@ -410,6 +410,13 @@ where
                 TagValue::ConsistentConstructor(_) => {
                     class_like_metadata.flags |= MetadataFlags::CONSISTENT_CONSTRUCTOR;
                 }
+                TagValue::Immutable(_) | TagValue::MutationFree(_) => {
+                    class_like_metadata.flags |= MetadataFlags::MUTATION_FREE;
+                    class_like_metadata.flags |= MetadataFlags::EXTERNAL_MUTATION_FREE;
+                }
+                TagValue::ExternalMutationFree(_) => {
+                    class_like_metadata.flags |= MetadataFlags::EXTERNAL_MUTATION_FREE;
+                }
                 TagValue::ConsistentTemplates(_) => {
                     class_like_metadata.flags |= MetadataFlags::CONSISTENT_TEMPLATES;
                 }
@@ -1540,7 +1547,9 @@ fn create_enum_method(
         if_false_assertions: BTreeMap::default(),
         assertions_inferred: false,
         globals_accessed: WordSet::default(),
+        purity_dependencies: None,
         has_docblock: false,
+        taint: crate::metadata::taint::TaintMetadata::default(),
         flags: MetadataFlags::POPULATED,
         version_constraint: crate::metadata::version_constraint::VersionConstraint::unconstrained(),
     }
@@ -11,6 +11,7 @@ use mago_syntax::cst::FunctionCall;
 use mago_syntax::cst::Literal;
 use mago_word::Word;
 use mago_word::ascii_lowercase_constant_name_word;
+use mago_word::word;
 
 use crate::issue::ScanningIssueKind;
 use crate::metadata::constant::ConstantMetadata;
@@ -47,9 +48,10 @@ where
         .items
         .iter()
         .map(|item| {
-            let name = ascii_lowercase_constant_name_word(context.resolved_names.get(&item.name));
+            let original_name = context.resolved_names.get(&item.name);
+            let name = ascii_lowercase_constant_name_word(original_name);
 
-            let mut metadata = ConstantMetadata::new(name, item.span(), flags);
+            let mut metadata = ConstantMetadata::new(name, word(original_name), item.span(), flags);
             metadata.version_constraint = verdict.constraint.clone();
             metadata.attributes.clone_from(&attributes);
             metadata.inferred_type = infer(context, scope, item.value, None);
@@ -94,10 +96,11 @@ where
 
     let document = parse_docblock(context, define);
 
-    let name = ascii_lowercase_constant_name_word(name_string.value?);
+    let original_name = name_string.value?;
+    let name = ascii_lowercase_constant_name_word(original_name);
     let flags = MetadataFlags::origin_flags(context.file.file_type);
 
-    let mut metadata = ConstantMetadata::new(name, define.span(), flags);
+    let mut metadata = ConstantMetadata::new(name, word(original_name), define.span(), flags);
     metadata.inferred_type = infer(context, scope, value_arg.value(), None);
 
     process_constant_docblock(&mut metadata, document.as_ref(), None, type_context, scope);
@@ -4,6 +4,7 @@ use mago_names::scope::NamespaceScope;
 use mago_phpdoc_syntax::cst::AssertPattern;
 use mago_phpdoc_syntax::cst::AssertTagValue;
 use mago_phpdoc_syntax::cst::Element;
+use mago_phpdoc_syntax::cst::Identifier;
 use mago_phpdoc_syntax::cst::TagValue;
 use mago_phpdoc_syntax::cst::TextSegment;
 use mago_phpdoc_syntax::cst::r#type::Type;
@@ -41,6 +42,7 @@ use crate::metadata::flags::MetadataFlags;
 use crate::metadata::function_like::FunctionLikeKind;
 use crate::metadata::function_like::FunctionLikeMetadata;
 use crate::metadata::function_like::MethodMetadata;
+use crate::metadata::taint::TaintKinds;
 use crate::misc::GenericParent;
 use crate::scanner::Context;
 use crate::scanner::assertion_inference::infer_assertions_from_block_body;
@@ -53,6 +55,7 @@ use crate::scanner::docblock::for_each_tag_by_ascending_trust;
 use crate::scanner::docblock::parse_docblock;
 use crate::scanner::parameter::scan_function_like_parameter;
 use crate::scanner::parameter::scan_function_like_parameter_with_constants;
+use crate::scanner::purity_inference::infer_purity_dependencies_from_block_body;
 use crate::scanner::ttype::get_type_metadata_from_hint;
 use crate::scanner::ttype::get_type_metadata_from_type;
 use crate::scanner::ttype::merge_type_preserving_nullability;
@@ -155,6 +158,18 @@ where
         infer_assertions_from_block_body(block, &mut metadata, context.resolved_names);
     }
 
+    // Instance methods of an `@immutable` or `@mutation-free` class inherit its guarantees; the
+    // constructor is exempt, since it is where the properties are initialized.
+    if !method.modifiers.contains_static() && !method_name_str.eq_ignore_ascii_case(b"__construct") {
+        if class_like_metadata.flags.is_mutation_free() {
+            metadata.flags |= MetadataFlags::MUTATION_FREE;
+        }
+
+        if class_like_metadata.flags.is_external_mutation_free() {
+            metadata.flags |= MetadataFlags::EXTERNAL_MUTATION_FREE;
+        }
+    }
+
     if metadata.attributes.iter().any(|attr| attr.name.as_bytes().eq_ignore_ascii_case(b"Deprecated")) {
         metadata.flags |= MetadataFlags::DEPRECATED;
     }
@@ -227,6 +242,8 @@ where
         )));
     }
 
+    infer_purity_dependencies_from_block_body(&function.body, &mut metadata, context.resolved_names);
+
     scan_function_like_docblock(function.span(), functionlike_id, &mut metadata, classname, context, scope);
 
     infer_assertions_from_block_body(&function.body, &mut metadata, context.resolved_names);
@@ -422,6 +439,7 @@ fn scan_function_like_docblock<A>(
             }
             TagValue::Impure(_) => {
                 metadata.flags.set(MetadataFlags::PURE, false);
+                metadata.purity_dependencies = None;
             }
             TagValue::MutationFree(_) => {
                 metadata.flags |= MetadataFlags::MUTATION_FREE;
@@ -447,6 +465,45 @@ fn scan_function_like_docblock<A>(
             TagValue::NoNamedArguments(_) => {
                 metadata.flags |= MetadataFlags::NO_NAMED_ARGUMENTS;
             }
+            TagValue::TaintSource(value) => {
+                if let Some(kinds) = parse_taint_kinds(metadata, &value.kind) {
+                    metadata.taint.sources |= kinds;
+                }
+            }
+            TagValue::TaintEscape(value) => {
+                if let Some(kinds) = parse_taint_kinds(metadata, &value.kind) {
+                    metadata.taint.escapes |= kinds;
+                }
+            }
+            TagValue::TaintUnescape(value) => {
+                if let Some(kinds) = parse_taint_kinds(metadata, &value.kind) {
+                    metadata.taint.unescapes |= kinds;
+                }
+            }
+            TagValue::TaintSink(value) => {
+                let Some(kinds) = parse_taint_kinds(metadata, &value.kind) else {
+                    continue;
+                };
+
+                let parameter_name = word(value.parameter.value);
+                if metadata.get_parameter(parameter_name).is_none() {
+                    metadata.issues.push(
+                        Issue::error(format!(
+                            "`@taint-sink` refers to unknown parameter `{}`.",
+                            String::from_utf8_lossy(value.parameter.value)
+                        ))
+                        .with_code(ScanningIssueKind::InvalidTaintTag)
+                        .with_annotation(
+                            Annotation::primary(value.parameter.span)
+                                .with_message("This parameter is not declared by the function."),
+                        ),
+                    );
+
+                    continue;
+                }
+
+                metadata.taint.sinks.push((parameter_name, kinds));
+            }
             _ => {}
         }
     }
@@ -871,6 +928,26 @@ fn parse_assertions_from_tag(
     assertions
 }
 
+/// Resolves the kind named by a `@taint-*` tag, reporting an issue when it is not recognized.
+fn parse_taint_kinds(function_like_metadata: &mut FunctionLikeMetadata, kind: &Identifier<'_>) -> Option<TaintKinds> {
+    let name = String::from_utf8_lossy(kind.value);
+    if let Some(kinds) = TaintKinds::from_name(&name) {
+        return Some(kinds);
+    }
+
+    function_like_metadata.issues.push(
+        Issue::error(format!("Unknown taint kind `{name}`."))
+            .with_code(ScanningIssueKind::InvalidTaintTag)
+            .with_annotation(Annotation::primary(kind.span).with_message("This taint kind is not recognized."))
+            .with_help(format!(
+                "Use one of `input`, `all`, or `{}`.",
+                TaintKinds::ALL.names().collect::<Vec<_>>().join("`, `")
+            )),
+    );
+
+    None
+}
+
 /// Collects every variable imported via `global $x;` anywhere in `block`, without
 /// descending into nested function/closure/arrow-function definitions (those are
 /// separate scopes).
@@ -59,6 +59,7 @@ use crate::scanner::function_like::scan_closure;
 use crate::scanner::function_like::scan_function;
 use crate::scanner::function_like::scan_method;
 use crate::scanner::property::scan_promoted_property;
+use crate::scanner::taint_inference::TaintFlowScanner;
 use crate::ttype::resolution::TypeResolutionContext;
 use crate::ttype::template::GenericTemplate;
 
@@ -77,6 +78,8 @@ pub mod inference;
 
 mod parameter;
 mod property;
+mod purity_inference;
+mod taint_inference;
 mod ttype;
 mod version_claim;
 
@@ -114,6 +117,22 @@ where
     scanner.codebase
 }
 
+/// Records the taint flows of the functions and methods declared in a program in the codebase
+/// metadata scanned from it, for [`populate_taint_summaries`] to resolve.
+///
+/// This is separate from [`scan_program`] so that only taint analysis pays for it.
+///
+/// [`populate_taint_summaries`]: crate::populator::populate_taint_summaries
+pub fn scan_taint_flows<'arena>(
+    program: &'arena Program<'arena>,
+    resolved_names: &ResolvedNames<'arena>,
+    codebase: &mut CodebaseMetadata,
+) {
+    let mut scanner = TaintFlowScanner { codebase, resolved_names, class_likes: vec![] };
+
+    scanner.walk_program(program, &mut ());
+}
+
 #[derive(Clone, Debug)]
 struct Context<'ctx, 'arena, A> {
     pub arena: &'arena A,
@@ -0,0 +1,130 @@
+use mago_names::ResolvedNames;
+use mago_syntax::cst::ArrayElement;
+use mago_syntax::cst::Block;
+use mago_syntax::cst::Expression;
+use mago_syntax::cst::Node;
+use mago_syntax::cst::Variable;
+use mago_word::Word;
+use mago_word::ascii_lowercase_word;
+
+use crate::metadata::function_like::FunctionLikeMetadata;
+
+const SUPERGLOBALS: [&[u8]; 9] =
+    [b"$GLOBALS", b"$_SERVER", b"$_GET", b"$_POST", b"$_FILES", b"$_COOKIE", b"$_SESSION", b"$_REQUEST", b"$_ENV"];
+
+/// Records the purity dependencies of a function whose body has no side effects of its own.
+///
+/// The body may only compute values, assign local variables, throw new exceptions, and call
+/// functions by name; anything else (output, global or static state, property writes, method
+/// calls, instantiation, dynamic calls, ...) makes the function impure. The called functions
+/// are resolved later by the populator, which infers purity once all of them are pure.
+pub(super) fn infer_purity_dependencies_from_block_body<'arena>(
+    body: &'arena Block<'arena>,
+    metadata: &mut FunctionLikeMetadata,
+    resolved_names: &ResolvedNames<'arena>,
+) {
+    if metadata.flags.has_yield()
+        || metadata.flags.is_by_reference()
+        || metadata.parameters.iter().any(|parameter| parameter.flags.is_by_reference())
+    {
+        return;
+    }
+
+    let mut dependencies = vec![];
+    if is_free_of_side_effects(Node::Block(body), resolved_names, &mut dependencies) {
+        dependencies.sort_unstable();
+        dependencies.dedup();
+
+        metadata.purity_dependencies = Some(dependencies);
+    }
+}
+
+fn is_free_of_side_effects<'arena>(
+    node: Node<'_, 'arena>,
+    resolved_names: &ResolvedNames<'arena>,
+    dependencies: &mut Vec<Word>,
+) -> bool {
+    match node {
+        Node::Global(_)
+        | Node::Static(_)
+        | Node::Echo(_)
+        | Node::EchoTag(_)
+        | Node::Inline(_)
+        | Node::PrintConstruct(_)
+        | Node::ExitConstruct(_)
+        | Node::DieConstruct(_)
+        | Node::EvalConstruct(_)
+        | Node::IncludeConstruct(_)
+        | Node::IncludeOnceConstruct(_)
+        | Node::RequireConstruct(_)
+        | Node::RequireOnceConstruct(_)
+        | Node::ShellExecuteString(_)
+        | Node::HaltCompiler(_)
+        | Node::Yield(_)
+        | Node::MethodCall(_)
+        | Node::NullSafeMethodCall(_)
+        | Node::StaticMethodCall(_)
+        | Node::Instantiation(_)
+        | Node::Clone(_)
+        | Node::Pipe(_)
+        | Node::IndirectVariable(_)
+        | Node::NestedVariable(_)
+        | Node::Function(_)
+        | Node::Class(_)
+        | Node::Interface(_)
+        | Node::Trait(_)
+        | Node::Enum(_)
+        | Node::AnonymousClass(_)
+        | Node::Constant(_) => return false,
+        Node::DirectVariable(variable) if SUPERGLOBALS.contains(&variable.name) => return false,
+        Node::Assignment(assignment) if !is_local_target(assignment.lhs) => return false,
+        Node::UnaryPrefix(unary) if unary.operator.is_increment_or_decrement() && !is_local_target(unary.operand) => {
+            return false;
+        }
+        Node::UnaryPostfix(unary) if !is_local_target(unary.operand) => return false,
+        Node::Unset(unset) if !unset.values.iter().all(|value| is_local_target(value)) => return false,
+        // Constructing the exception being thrown is allowed, since the call ends anyway.
+        Node::Throw(throw) => {
+            if let Expression::Instantiation(instantiation) = throw.exception
+                && matches!(instantiation.class, Expression::Identifier(_))
+            {
+                return instantiation.argument_list.as_ref().is_none_or(|arguments| {
+                    is_free_of_side_effects(Node::ArgumentList(arguments), resolved_names, dependencies)
+                });
+            }
+        }
+        Node::FunctionCall(call) => {
+            let Expression::Identifier(identifier) = call.function else {
+                return false;
+            };
+
+            dependencies.push(ascii_lowercase_word(resolved_names.get(identifier)));
+        }
+        _ => {}
+    }
+
+    node.children().into_iter().all(|child| is_free_of_side_effects(child, resolved_names, dependencies))
+}
+
+/// Returns whether writing to `target` only affects variables local to the function.
+fn is_local_target(target: &Expression<'_>) -> bool {
+    match target {
+        Expression::Variable(Variable::Direct(variable)) => !SUPERGLOBALS.contains(&variable.name),
+        Expression::ArrayAccess(access) => is_local_target(access.array),
+        Expression::ArrayAppend(append) => is_local_target(append.array),
+        Expression::Parenthesized(parenthesized) => is_local_target(parenthesized.expression),
+        Expression::Array(array) => array.elements.iter().all(is_local_element),
+        Expression::LegacyArray(array) => array.elements.iter().all(is_local_element),
+        Expression::List(list) => list.elements.iter().all(is_local_element),
+        _ => false,
+    }
+}
+
+fn is_local_element(element: &ArrayElement<'_>) -> bool {
+    match element {
+        ArrayElement::KeyValue(element) => is_local_target(element.value),
+        ArrayElement::Value(element) => is_local_target(element.value),
+        ArrayElement::Variadic(element) => is_local_target(element.value),
+        ArrayElement::Missing(_) => true,
+    }
+}
@@ -0,0 +1,493 @@
+use mago_names::ResolvedNames;
+use mago_syntax::cst::Access;
+use mago_syntax::cst::AnonymousClass;
+use mago_syntax::cst::Argument;
+use mago_syntax::cst::ArgumentList;
+use mago_syntax::cst::ArrayElement;
+use mago_syntax::cst::AssignmentOperator;
+use mago_syntax::cst::Block;
+use mago_syntax::cst::Call;
+use mago_syntax::cst::Class;
+use mago_syntax::cst::ClassLikeMemberSelector;
+use mago_syntax::cst::Enum;
+use mago_syntax::cst::Expression;
+use mago_syntax::cst::Function;
+use mago_syntax::cst::Interface;
+use mago_syntax::cst::Literal;
+use mago_syntax::cst::LocalIdentifier;
+use mago_syntax::cst::MatchArm;
+use mago_syntax::cst::Method;
+use mago_syntax::cst::MethodBody;
+use mago_syntax::cst::Node;
+use mago_syntax::cst::StringPart;
+use mago_syntax::cst::Trait;
+use mago_syntax::cst::UnaryPrefixOperator;
+use mago_syntax::cst::Variable;
+use mago_syntax::walker::MutWalker;
+use mago_word::Word;
+use mago_word::WordMap;
+use mago_word::ascii_lowercase_word;
+use mago_word::empty_word;
+use mago_word::word;
+
+use crate::metadata::CodebaseMetadata;
+use crate::metadata::function_like::FunctionLikeMetadata;
+use crate::metadata::taint::INPUT_SUPERGLOBALS;
+use crate::metadata::taint::TaintDependency;
+use crate::metadata::taint::TaintFlow;
+use crate::metadata::taint::TaintKinds;
+use crate::metadata::taint::USER_CONTROLLED_SERVER_KEYS;
+
+/// The number of passes over a body, so that values assigned later in a loop reach earlier reads.
+const PASSES: usize = 2;
+
+/// The maximum nesting of call arguments kept in a flow; deeper arguments are dropped.
+const MAXIMUM_ARGUMENT_DEPTH: usize = 4;
+
+/// The maximum number of dependencies kept in a flow; further ones are dropped.
+const MAXIMUM_DEPENDENCIES: usize = 32;
+
+/// Walks a program, recording the taint flows of the functions and methods it declares in the
+/// codebase metadata scanned from it.
+pub(super) struct TaintFlowScanner<'codebase, 'names, 'arena> {
+    pub(super) codebase: &'codebase mut CodebaseMetadata,
+    pub(super) resolved_names: &'names ResolvedNames<'arena>,
+    /// The lowercase names of the enclosing class-likes, `None` for anonymous classes.
+    pub(super) class_likes: Vec<Option<Word>>,
+}
+
+impl TaintFlowScanner<'_, '_, '_> {
+    fn enter_class_like(&mut self, name: &LocalIdentifier<'_>) {
+        self.class_likes.push(Some(ascii_lowercase_word(self.resolved_names.get(name))));
+    }
+}
+
+impl<'arena> MutWalker<'arena, 'arena, ()> for TaintFlowScanner<'_, '_, 'arena> {
+    fn walk_in_function(&mut self, function: &'arena Function<'arena>, _context: &mut ()) {
+        let name = ascii_lowercase_word(self.resolved_names.get(&function.name));
+        if let Some(metadata) = self.codebase.function_likes.get_mut(&(empty_word(), name))
+            && metadata.taint.return_flow.is_none()
+        {
+            infer_taint_flows_from_block_body(&function.body, metadata, None, self.resolved_names);
+        }
+    }
+
+    fn walk_in_method(&mut self, method: &'arena Method<'arena>, _context: &mut ()) {
+        let MethodBody::Concrete(block) = &method.body else {
+            return;
+        };
+
+        let Some(Some(class_like_id)) = self.class_likes.last().copied() else {
+            return;
+        };
+
+        let Some(class_name) = self.codebase.class_likes.get(&class_like_id).map(|metadata| metadata.name) else {
+            return;
+        };
+
+        let method_id = (class_name, ascii_lowercase_word(method.name.value));
+        if let Some(metadata) = self.codebase.function_likes.get_mut(&method_id)
+            && metadata.taint.return_flow.is_none()
+        {
+            infer_taint_flows_from_block_body(block, metadata, Some(class_name), self.resolved_names);
+        }
+    }
+
+    fn walk_in_class(&mut self, class: &'arena Class<'arena>, _context: &mut ()) {
+        self.enter_class_like(&class.name);
+    }
+
+    fn walk_out_class(&mut self, _class: &'arena Class<'arena>, _context: &mut ()) {
+        self.class_likes.pop();
+    }
+
+    fn walk_in_interface(&mut self, interface: &'arena Interface<'arena>, _context: &mut ()) {
+        self.enter_class_like(&interface.name);
+    }
+
+    fn walk_out_interface(&mut self, _interface: &'arena Interface<'arena>, _context: &mut ()) {
+        self.class_likes.pop();
+    }
+
+    fn walk_in_trait(&mut self, r#trait: &'arena Trait<'arena>, _context: &mut ()) {
+        self.enter_class_like(&r#trait.name);
+    }
+
+    fn walk_out_trait(&mut self, _trait: &'arena Trait<'arena>, _context: &mut ()) {
+        self.class_likes.pop();
+    }
+
+    fn walk_in_enum(&mut self, r#enum: &'arena Enum<'arena>, _context: &mut ()) {
+        self.enter_class_like(&r#enum.name);
+    }
+
+    fn walk_out_enum(&mut self, _enum: &'arena Enum<'arena>, _context: &mut ()) {
+        self.class_likes.pop();
+    }
+
+    fn walk_in_anonymous_class(&mut self, _anonymous_class: &'arena AnonymousClass<'arena>, _context: &mut ()) {
+        self.class_likes.push(None);
+    }
+
+    fn walk_out_anonymous_class(&mut self, _anonymous_class: &'arena AnonymousClass<'arena>, _context: &mut ()) {
+        self.class_likes.pop();
+    }
+}
+
+/// Records where the return value of a function-like body, and the values it writes to the
+/// properties of its class, may come from.
+///
+/// The body is followed without regard to the order of its statements: a variable may hold
+/// anything assigned to it anywhere in the body. Calls to functions by name, and calls and property
+/// accesses through `$this`, `self` or `static` are kept as dependencies, which the populator resolves
+/// once the whole codebase is known. Other calls and accesses are not followed.
+fn infer_taint_flows_from_block_body<'arena>(
+    body: &'arena Block<'arena>,
+    metadata: &mut FunctionLikeMetadata,
+    class_name: Option<Word>,
+    resolved_names: &ResolvedNames<'arena>,
+) {
+    if metadata.flags.has_yield() {
+        return;
+    }
+
+    let mut inference = TaintInference {
+        resolved_names,
+        class_name,
+        parameters: metadata.parameters.iter().map(|parameter| parameter.get_name().0).collect(),
+        variables: WordMap::default(),
+        return_flow: TaintFlow::default(),
+        property_flows: WordMap::default(),
+    };
+
+    for _ in 0..PASSES {
+        inference.visit(Node::Block(body));
+    }
+
+    let mut property_flows = inference.property_flows.into_iter().collect::<Vec<_>>();
+    property_flows.sort_unstable_by_key(|(property, _)| *property);
+
+    metadata.taint.return_flow = Some(inference.return_flow);
+    metadata.taint.property_flows = property_flows;
+}
+
+struct TaintInference<'names, 'arena> {
+    resolved_names: &'names ResolvedNames<'arena>,
+    class_name: Option<Word>,
+    parameters: Vec<Word>,
+    variables: WordMap<TaintFlow>,
+    return_flow: TaintFlow,
+    property_flows: WordMap<TaintFlow>,
+}
+
+impl<'arena> TaintInference<'_, 'arena> {
+    fn visit<'ast>(&mut self, node: Node<'ast, 'arena>) {
+        match node {
+            Node::Function(_)
+            | Node::Closure(_)
+            | Node::ArrowFunction(_)
+            | Node::Class(_)
+            | Node::Interface(_)
+            | Node::Trait(_)
+            | Node::Enum(_)
+            | Node::AnonymousClass(_) => return,
+            Node::Assignment(assignment) => {
+                let mut flow = self.get_flow(assignment.rhs, 0);
+                if !matches!(assignment.operator, AssignmentOperator::Assign(_)) {
+                    flow.extend(self.get_flow(assignment.lhs, 0));
+                }
+
+                self.assign(assignment.lhs, &flow);
+            }
+            Node::Foreach(foreach) => {
+                let flow = self.get_flow(foreach.expression, 0);
+                if let Some(key) = foreach.target.key() {
+                    self.assign(key, &flow);
+                }
+
+                self.assign(foreach.target.value(), &flow);
+            }
+            Node::Return(r#return) => {
+                if let Some(value) = r#return.value {
+                    let flow = self.get_flow(value, 0);
+                    extend_flow(&mut self.return_flow, flow);
+                }
+            }
+            _ => {}
+        }
+
+        for child in node.children() {
+            self.visit(child);
+        }
+    }
+
+    fn assign(&mut self, target: &Expression<'arena>, flow: &TaintFlow) {
+        match target {
+            Expression::Parenthesized(parenthesized) => self.assign(parenthesized.expression, flow),
+            Expression::UnaryPrefix(prefix) if matches!(prefix.operator, UnaryPrefixOperator::Reference(_)) => {
+                self.assign(prefix.operand, flow);
+            }
+            Expression::ArrayAccess(access) => self.assign(access.array, flow),
+            Expression::ArrayAppend(append) => self.assign(append.array, flow),
+            Expression::Array(array) => self.assign_elements(array.elements.iter(), flow),
+            Expression::LegacyArray(array) => self.assign_elements(array.elements.iter(), flow),
+            Expression::List(list) => self.assign_elements(list.elements.iter(), flow),
+            Expression::Variable(Variable::Direct(variable)) if !is_superglobal(variable.name) => {
+                extend_flow(self.variables.entry(word(variable.name)).or_default(), flow.clone());
+            }
+            _ => {
+                if let Some(property) = self.get_own_property(target) {
+                    extend_flow(self.property_flows.entry(property).or_default(), flow.clone());
+                }
+            }
+        }
+    }
+
+    fn assign_elements<'ast>(&mut self, elements: impl Iterator<Item = &'ast ArrayElement<'arena>>, flow: &TaintFlow)
+    where
+        'arena: 'ast,
+    {
+        for element in elements {
+            if let Some(value) = element.get_value() {
+                self.assign(value, flow);
+            }
+        }
+    }
+
+    fn get_flow(&self, expression: &Expression<'arena>, depth: usize) -> TaintFlow {
+        let mut flow = TaintFlow::default();
+
+        match expression {
+            Expression::Parenthesized(parenthesized) => return self.get_flow(parenthesized.expression, depth),
+            Expression::Variable(Variable::Direct(variable)) => {
+                if is_superglobal(variable.name) {
+                    flow.sources = TaintKinds::INPUT;
+                } else {
+                    let name = word(variable.name);
+                    if self.parameters.contains(&name) {
+                        flow.parameters.push(name);
+                    }
+
+                    if let Some(variable_flow) = self.variables.get(&name) {
+                        flow.extend(variable_flow.clone());
+                    }
+                }
+            }
+            Expression::ArrayAccess(access) => {
+                if let Expression::Variable(Variable::Direct(variable)) = access.array
+                    && variable.name == b"$_SERVER"
+                    && let Expression::Literal(Literal::String(key)) = access.index
+                    && let Some(key) = key.value
+                    && !key.starts_with(b"HTTP_")
+                    && !USER_CONTROLLED_SERVER_KEYS.contains(&key)
+                {
+                    return flow;
+                }
+
+                return self.get_flow(access.array, depth);
+            }
+            Expression::Access(access) => {
+                if let Some(class_name) = self.class_name
+                    && let Some(property) = self.get_own_property(expression)
+                {
+                    flow.dependencies.push(TaintDependency::Property(class_name, property));
+
+                    return flow;
+                }
+
+                match access {
+                    Access::Property(access) => return self.get_flow(access.object, depth),
+                    Access::NullSafeProperty(access) => return self.get_flow(access.object, depth),
+                    _ => {}
+                }
+            }
+            Expression::Binary(binary) => {
+                if binary.operator.is_comparison()
+                    || binary.operator.is_equality()
+                    || binary.operator.is_logical()
+                    || binary.operator.is_instanceof()
+                    || binary.operator.is_arithmetic()
+                    || binary.operator.is_bitwise()
+                    || binary.operator.is_bit_shift()
+                {
+                    return flow;
+                }
+
+                flow = self.get_flow(binary.lhs, depth);
+                flow.extend(self.get_flow(binary.rhs, depth));
+            }
+            Expression::UnaryPrefix(prefix) => match prefix.operator {
+                UnaryPrefixOperator::ErrorControl(_)
+                | UnaryPrefixOperator::Reference(_)
+                | UnaryPrefixOperator::ArrayCast(_, _)
+                | UnaryPrefixOperator::ObjectCast(_, _)
+                | UnaryPrefixOperator::StringCast(_, _)
+                | UnaryPrefixOperator::BinaryCast(_, _) => return self.get_flow(prefix.operand, depth),
+                _ => {}
+            },
+            Expression::Conditional(conditional) => {
+                flow = self.get_flow(conditional.then.unwrap_or(conditional.condition), depth);
+                flow.extend(self.get_flow(conditional.r#else, depth));
+            }
+            Expression::Match(r#match) => {
+                for arm in r#match.arms.iter() {
+                    let arm_expression = match arm {
+                        MatchArm::Expression(arm) => arm.expression,
+                        MatchArm::Default(arm) => arm.expression,
+                    };
+
+                    flow.extend(self.get_flow(arm_expression, depth));
+                }
+            }
+            Expression::CompositeString(string) => {
+                for part in string.parts().iter() {
+                    match part {
+                        StringPart::Literal(_) => {}
+                        StringPart::Expression(expression) => flow.extend(self.get_flow(expression, depth)),
+                        StringPart::BracedExpression(braced) => flow.extend(self.get_flow(braced.expression, depth)),
+                    }
+                }
+            }
+            Expression::Array(array) => self.extend_with_elements(&mut flow, array.elements.iter(), depth),
+            Expression::LegacyArray(array) => self.extend_with_elements(&mut flow, array.elements.iter(), depth),
+            Expression::Assignment(assignment) => {
+                flow = self.get_flow(assignment.rhs, depth);
+                if !matches!(assignment.operator, AssignmentOperator::Assign(_)) {
+                    flow.extend(self.get_flow(assignment.lhs, depth));
+                }
+            }
+            Expression::Clone(clone) => return self.get_flow(clone.object, depth),
+            Expression::Call(Call::Function(call)) => {
+                if let Expression::Identifier(identifier) = call.function {
+                    let function_name = ascii_lowercase_word(self.resolved_names.get(identifier));
+                    let arguments = self.get_argument_flows(&call.argument_list, depth);
+
+                    flow.dependencies.push(TaintDependency::FunctionCall(function_name, arguments));
+                }
+            }
+            Expression::Call(Call::Method(call)) if is_this(call.object) => {
+                if let (Some(class_name), ClassLikeMemberSelector::Identifier(method)) = (self.class_name, &call.method)
+                {
+                    let arguments = self.get_argument_flows(&call.argument_list, depth);
+
+                    flow.dependencies.push(TaintDependency::MethodCall(
+                        class_name,
+                        ascii_lowercase_word(method.value),
+                        arguments,
+                    ));
+                }
+            }
+            Expression::Call(Call::NullSafeMethod(call)) if is_this(call.object) => {
+                if let (Some(class_name), ClassLikeMemberSelector::Identifier(method)) = (self.class_name, &call.method)
+                {
+                    let arguments = self.get_argument_flows(&call.argument_list, depth);
+
+                    flow.dependencies.push(TaintDependency::MethodCall(
+                        class_name,
+                        ascii_lowercase_word(method.value),
+                        arguments,
+                    ));
+                }
+            }
+            Expression::Call(Call::StaticMethod(call)) if is_own_class(call.class) => {
+                if let (Some(class_name), ClassLikeMemberSelector::Identifier(method)) = (self.class_name, &call.method)
+                {
+                    let arguments = self.get_argument_flows(&call.argument_list, depth);
+
+                    flow.dependencies.push(TaintDependency::MethodCall(
+                        class_name,
+                        ascii_lowercase_word(method.value),
+                        arguments,
+                    ));
+                }
+            }
+            _ => {}
+        }
+
+        flow.dependencies.truncate(MAXIMUM_DEPENDENCIES);
+        flow
+    }
+
+    fn extend_with_elements<'ast>(
+        &self,
+        flow: &mut TaintFlow,
+        elements: impl Iterator<Item = &'ast ArrayElement<'arena>>,
+        depth: usize,
+    ) where
+        'arena: 'ast,
+    {
+        for element in elements {
+            if let Some(key) = element.get_key() {
+                flow.extend(self.get_flow(key, depth));
+            }
+
+            if let Some(value) = element.get_value() {
+                flow.extend(self.get_flow(value, depth));
+            }
+        }
+    }
+
+    /// Returns the flows of the arguments of a call, keyed by the parameter name of named arguments.
+    ///
+    /// Unpacked arguments are skipped, as they cannot be matched to a parameter.
+    fn get_argument_flows(&self, argument_list: &ArgumentList<'arena>, depth: usize) -> Vec<(Option<Word>, TaintFlow)> {
+        if depth >= MAXIMUM_ARGUMENT_DEPTH {
+            return vec![];
+        }
+
+        argument_list
+            .arguments
+            .iter()
+            .take_while(|argument| !argument.is_unpacked())
+            .map(|argument| {
+                let name = match argument {
+                    Argument::Named(named) => Some(word([b"$".as_slice(), named.name.value].concat())),
+                    Argument::Positional(_) => None,
+                };
+
+                (name, self.get_flow(argument.value(), depth + 1))
+            })
+            .collect()
+    }
+
+    /// Returns the name of the property of the current class accessed through `$this->name`,
+    /// `self::$name` or `static::$name`, including the leading `$`.
+    fn get_own_property(&self, expression: &Expression<'arena>) -> Option<Word> {
+        self.class_name?;
+
+        match expression {
+            Expression::Access(Access::Property(access)) if is_this(access.object) => match &access.property {
+                ClassLikeMemberSelector::Identifier(property) => Some(word([b"$".as_slice(), property.value].concat())),
+                _ => None,
+            },
+            Expression::Access(Access::NullSafeProperty(access)) if is_this(access.object) => match &access.property {
+                ClassLikeMemberSelector::Identifier(property) => Some(word([b"$".as_slice(), property.value].concat())),
+                _ => None,
+            },
+            Expression::Access(Access::StaticProperty(access)) if is_own_class(access.class) => {
+                match &access.property {
+                    Variable::Direct(property) => Some(word(property.name)),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+fn extend_flow(flow: &mut TaintFlow, other: TaintFlow) {
+    flow.extend(other);
+    flow.dependencies.truncate(MAXIMUM_DEPENDENCIES);
+}
+
+fn is_this(expression: &Expression<'_>) -> bool {
+    matches!(expression, Expression::Variable(Variable::Direct(variable)) if variable.name == b"$this")
+}
+
+fn is_own_class(expression: &Expression<'_>) -> bool {
+    matches!(expression, Expression::Self_(_) | Expression::Static(_))
+}
+
+fn is_superglobal(name: &[u8]) -> bool {
+    INPUT_SUPERGLOBALS.contains(&name) || name == b"$_SERVER"
+}
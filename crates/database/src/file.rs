@@ -8,6 +8,9 @@ use std::path::PathBuf;
 use crate::error::DatabaseError;
 use crate::utils::read_file;
 
+/// The file extension used by Twig templates (e.g. `base.html.twig`).
+pub const TWIG_TEMPLATE_EXTENSION: &str = "twig";
+
 /// A stable, unique identifier for a file.
 ///
 /// This ID is generated by hashing the file's logical name, ensuring it remains
@@ -200,6 +203,25 @@ impl File {
 
         offset - self.lines[line]
     }
+
+    /// Returns `true` if the file is a Twig template rather than PHP source code.
+    ///
+    /// Templates are recognised by their `.twig` extension, so `base.html.twig` and
+    /// `email.txt.twig` both qualify.
+    #[inline]
+    #[must_use]
+    pub fn is_twig_template(&self) -> bool {
+        is_twig_template_name(&self.name)
+    }
+}
+
+/// Returns `true` if the given logical name or path ends with the Twig template extension.
+#[inline]
+#[must_use]
+fn is_twig_template_name(name: &[u8]) -> bool {
+    name.rsplit(|&b| b == b'.').next().is_some_and(|extension| {
+        extension.len() < name.len() && extension.eq_ignore_ascii_case(TWIG_TEMPLATE_EXTENSION.as_bytes())
+    })
 }
 
 impl FileType {
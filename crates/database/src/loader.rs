@@ -677,6 +677,7 @@ mod tests {
     use super::*;
     use crate::DatabaseReader;
     use crate::GlobSettings;
+    use crate::file::TWIG_TEMPLATE_EXTENSION;
     use std::borrow::Cow;
     use tempfile::TempDir;
 
@@ -1035,6 +1036,35 @@ mod tests {
         assert!(!names.iter().any(|n| n.ends_with("bin/console")), "extensionless console should be skipped");
     }
 
+    #[test]
+    fn test_twig_templates_are_loaded_when_extension_is_configured() {
+        let temp_dir = TempDir::new().unwrap();
+        create_test_file(&temp_dir, "templates/base.html.twig", "{% block body %}{% endblock %}");
+        create_test_file(&temp_dir, "src/Controller.php", "<?php");
+
+        let mut config = create_test_config(&temp_dir, vec!["templates", "src"], vec![]);
+        config.extensions.push(Cow::Borrowed(TWIG_TEMPLATE_EXTENSION.as_bytes()));
+        let db = DatabaseLoader::new(config).load().unwrap();
+
+        let template = db.files().find(|f| name_str(&f.name).ends_with("base.html.twig")).unwrap();
+        assert!(template.is_twig_template());
+        assert_eq!(template.file_type, FileType::Host);
+
+        let source = db.files().find(|f| name_str(&f.name).ends_with("Controller.php")).unwrap();
+        assert!(!source.is_twig_template());
+    }
+
+    #[test]
+    fn test_twig_templates_are_skipped_by_default() {
+        let temp_dir = TempDir::new().unwrap();
+        create_test_file(&temp_dir, "templates/base.html.twig", "{{ title }}");
+
+        let config = create_test_config(&temp_dir, vec!["templates"], vec![]);
+        let db = DatabaseLoader::new(config).load().unwrap();
+
+        assert!(db.files().all(|f| !f.is_twig_template()));
+    }
+
     #[test]
     fn test_patch_beats_vendored_at_equal_specificity() {
         // A file covered by both patches and includes at the same directory-level specificity
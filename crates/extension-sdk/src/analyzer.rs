@@ -0,0 +1,524 @@
+//! Analyzer plugins and the worker side of the `MANA` analyzer protocol.
+//!
+//! The Rust SDK serves plugin registration, function and method return-type
+//! providers, and entry points. Plugins needing lifecycle hooks, callable
+//! signatures, assertions, or codebase queries should use the PHP SDK.
+
+use mago_extension::PayloadReader;
+use mago_extension::PayloadWriter;
+use mago_extension::wire::analyzer::DESCRIBE_RESPONSE;
+use mago_extension::wire::analyzer::INVOCATION_FUNCTION;
+use mago_extension::wire::analyzer::INVOCATION_INSTANCE_METHOD;
+use mago_extension::wire::analyzer::INVOCATION_STATIC_METHOD;
+use mago_extension::wire::analyzer::PROVIDER_MEMOIZED;
+use mago_extension::wire::analyzer::PROVIDER_UNDECLARED_RETURN_TYPE_ONLY;
+use mago_extension::wire::analyzer::RETURN_TYPE_RESPONSE;
+use mago_extension::wire::analyzer::TARGET_EXACT;
+use mago_extension::wire::analyzer::TARGET_NAMESPACE;
+use mago_extension::wire::analyzer::TARGET_PREFIX;
+
+use crate::extension::RegisteredExtension;
+use crate::message::ANALYZER;
+use crate::message::RequestError;
+use crate::syntax::Span;
+use crate::syntax::read_node_kinds;
+use crate::types::Type;
+use crate::types::TypeView;
+use crate::types::read_type;
+
+/// The functions a [`FunctionReturnTypeProvider`] handles.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum FunctionTarget {
+    /// A function with this fully qualified name.
+    Exact(String),
+    /// Functions whose fully qualified name starts with this prefix.
+    Prefix(String),
+    /// Functions in this namespace or its sub-namespaces.
+    Namespace(String),
+}
+
+impl FunctionTarget {
+    #[must_use]
+    pub fn exact(name: impl Into<String>) -> Self {
+        Self::Exact(name.into())
+    }
+
+    #[must_use]
+    pub fn prefix(prefix: impl Into<String>) -> Self {
+        Self::Prefix(prefix.into())
+    }
+
+    #[must_use]
+    pub fn namespace(namespace: impl Into<String>) -> Self {
+        Self::Namespace(namespace.into())
+    }
+
+    fn write_to(&self, writer: &mut PayloadWriter) -> Result<(), RequestError> {
+        let (kind, value) = match self {
+            Self::Exact(name) => (TARGET_EXACT, name),
+            Self::Prefix(prefix) => (TARGET_PREFIX, prefix),
+            Self::Namespace(namespace) => (TARGET_NAMESPACE, namespace),
+        };
+
+        writer.write_u8(kind);
+        writer.write_string(value)?;
+
+        Ok(())
+    }
+}
+
+/// The methods a [`MethodReturnTypeProvider`] handles.
+///
+/// An exact class also matches its subclasses and implementations. A trailing
+/// `*` in either pattern matches any suffix.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MethodTarget {
+    pub class: String,
+    pub method: String,
+}
+
+impl MethodTarget {
+    #[must_use]
+    pub fn exact(class: impl Into<String>, method: impl Into<String>) -> Self {
+        Self { class: class.into(), method: method.into() }
+    }
+
+    /// Targets every method of `class`.
+    #[must_use]
+    pub fn all_methods(class: impl Into<String>) -> Self {
+        Self::exact(class, "*")
+    }
+
+    /// Targets `method` on any class.
+    #[must_use]
+    pub fn any_class(method: impl Into<String>) -> Self {
+        Self::exact("*", method)
+    }
+
+    fn write_to(&self, writer: &mut PayloadWriter) -> Result<(), RequestError> {
+        writer.write_string(&self.class)?;
+        writer.write_string(&self.method)?;
+
+        Ok(())
+    }
+}
+
+/// Refines the return type of function calls.
+pub trait FunctionReturnTypeProvider {
+    /// Returns the functions this provider handles. Called once, when the worker starts.
+    fn targets(&self) -> Vec<FunctionTarget>;
+
+    /// Returns the type of the call, or `None` to defer to the next provider and ultimately to Mago.
+    fn return_type(&mut self, context: &ReturnTypeContext<'_>) -> Option<Type>;
+
+    /// Whether Mago should skip this provider for functions with a declared return type.
+    fn undeclared_return_type_only(&self) -> bool {
+        false
+    }
+
+    /// Whether the result depends only on the callee and argument types, letting Mago cache it.
+    ///
+    /// Memoized requests carry empty spans.
+    fn memoized(&self) -> bool {
+        false
+    }
+}
+
+/// Refines the return type of method calls.
+pub trait MethodReturnTypeProvider {
+    /// Returns the methods this provider handles. Called once, when the worker starts.
+    fn targets(&self) -> Vec<MethodTarget>;
+
+    /// Returns the type of the call, or `None` to defer to the next provider and ultimately to Mago.
+    fn return_type(&mut self, context: &ReturnTypeContext<'_>) -> Option<Type>;
+
+    /// Whether Mago should skip this provider for methods with a declared return type.
+    fn undeclared_return_type_only(&self) -> bool {
+        false
+    }
+
+    /// Whether the result depends only on the callee and argument types, letting Mago cache it.
+    ///
+    /// Memoized requests carry empty spans.
+    fn memoized(&self) -> bool {
+        false
+    }
+}
+
+/// An analyzer plugin: a selectable group of providers and entry points.
+pub struct Plugin {
+    identifier: String,
+    name: String,
+    description: String,
+    default_enabled: bool,
+    aliases: Vec<String>,
+    function_providers: Vec<Box<dyn FunctionReturnTypeProvider>>,
+    method_providers: Vec<Box<dyn MethodReturnTypeProvider>>,
+    entry_points: Vec<MethodTarget>,
+}
+
+impl Plugin {
+    /// Creates an enabled plugin. The identifier, name, and description must not be empty.
+    #[must_use]
+    pub fn new(identifier: impl Into<String>, name: impl Into<String>, description: impl Into<String>) -> Self {
+        Self {
+            identifier: identifier.into(),
+            name: name.into(),
+            description: description.into(),
+            default_enabled: true,
+            aliases: vec![],
+            function_providers: vec![],
+            method_providers: vec![],
+            entry_points: vec![],
+        }
+    }
+
+    #[must_use]
+    pub const fn with_default_enabled(mut self, default_enabled: bool) -> Self {
+        self.default_enabled = default_enabled;
+        self
+    }
+
+    /// Adds another name the plugin can be selected by in the `[analyzer]` configuration.
+    #[must_use]
+    pub fn with_alias(mut self, alias: impl Into<String>) -> Self {
+        self.aliases.push(alias.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_function_return_type_provider(mut self, provider: impl FunctionReturnTypeProvider + 'static) -> Self {
+        self.function_providers.push(Box::new(provider));
+        self
+    }
+
+    #[must_use]
+    pub fn with_method_return_type_provider(mut self, provider: impl MethodReturnTypeProvider + 'static) -> Self {
+        self.method_providers.push(Box::new(provider));
+        self
+    }
+
+    /// Marks methods matching `target` as used, e.g. framework controller actions.
+    #[must_use]
+    pub fn with_entry_point(mut self, target: MethodTarget) -> Self {
+        self.entry_points.push(target);
+        self
+    }
+}
+
+impl std::fmt::Debug for Plugin {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter
+            .debug_struct("Plugin")
+            .field("identifier", &self.identifier)
+            .field("name", &self.name)
+            .field("default_enabled", &self.default_enabled)
+            .field("aliases", &self.aliases)
+            .field("function_providers", &self.function_providers.len())
+            .field("method_providers", &self.method_providers.len())
+            .field("entry_points", &self.entry_points)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InvocationKind {
+    Function,
+    InstanceMethod,
+    StaticMethod,
+}
+
+/// A call a return-type provider is asked about.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Invocation {
+    pub kind: InvocationKind,
+    /// The resolved function or method name.
+    pub name: Vec<u8>,
+    /// The resolved declaring class of a method.
+    pub declaring_class: Option<Vec<u8>>,
+    /// The inferred receiver type of a method call.
+    pub receiver_type: Option<TypeView>,
+    /// The complete call span, empty for memoized providers.
+    pub span: Span,
+    /// The arguments, in source order.
+    pub arguments: Vec<Argument>,
+}
+
+impl Invocation {
+    /// Returns the unnamed argument at `index`, or else the named argument matching one of `names`.
+    ///
+    /// Names may be given with or without the leading `$`.
+    #[must_use]
+    pub fn argument(&self, index: usize, names: &[&str]) -> Option<&Argument> {
+        if let Some(argument) = self.arguments.get(index)
+            && argument.name.is_none()
+        {
+            return Some(argument);
+        }
+
+        self.arguments.iter().find(|argument| {
+            argument.name.as_deref().is_some_and(|name| {
+                let name = name.trim_start_matches('$');
+
+                names.iter().any(|candidate| candidate.trim_start_matches('$') == name)
+            })
+        })
+    }
+}
+
+/// One argument of an [`Invocation`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Argument {
+    /// The parameter name of a named argument.
+    pub name: Option<String>,
+    pub unpacked: bool,
+    pub placeholder: bool,
+    /// The argument span, empty for memoized providers.
+    pub span: Span,
+    /// The source text of the argument value.
+    pub expression: Vec<u8>,
+    /// The type Mago inferred for the argument value, if any.
+    pub argument_type: Option<TypeView>,
+}
+
+/// The input of a return-type provider.
+#[derive(Debug)]
+pub struct ReturnTypeContext<'request> {
+    php_version_id: u32,
+    invocation: &'request Invocation,
+}
+
+impl<'request> ReturnTypeContext<'request> {
+    /// Returns the configured PHP version, as a `PHP_VERSION_ID` value such as `80400`.
+    #[must_use]
+    pub const fn php_version_id(&self) -> u32 {
+        self.php_version_id
+    }
+
+    #[must_use]
+    pub const fn invocation(&self) -> &'request Invocation {
+        self.invocation
+    }
+}
+
+/// The analyzer state of a worker: what Mago told it at registration.
+#[derive(Debug, Default)]
+pub(crate) struct Analyzer {
+    php_version_id: Option<u32>,
+    /// The plugin and position of every function provider, in registration order.
+    function_providers: Vec<(usize, usize)>,
+    /// The plugin and position of every method provider, in registration order.
+    method_providers: Vec<(usize, usize)>,
+}
+
+impl Analyzer {
+    pub(crate) fn describe(
+        &mut self,
+        mut reader: PayloadReader<'_>,
+        extensions: &[RegisteredExtension],
+        plugins: &[Plugin],
+    ) -> Result<Vec<u8>, RequestError> {
+        let php_version_id = reader.read_u32("php version")?;
+        let _kinds = read_node_kinds(&mut reader)?;
+        reader.finish()?;
+
+        self.function_providers.clear();
+        self.method_providers.clear();
+
+        let mut writer = ANALYZER.writer(DESCRIBE_RESPONSE);
+        writer.write_length(extensions.len())?;
+        for extension in extensions {
+            extension.write_identity(&mut writer)?;
+            writer.write_length(extension.plugins.len())?;
+            for plugin_index in extension.plugins.clone() {
+                self.describe_plugin(&mut writer, plugin_index, &plugins[plugin_index])?;
+            }
+        }
+
+        self.php_version_id = Some(php_version_id);
+
+        Ok(writer.finish())
+    }
+
+    fn describe_plugin(
+        &mut self,
+        writer: &mut PayloadWriter,
+        plugin_index: usize,
+        plugin: &Plugin,
+    ) -> Result<(), RequestError> {
+        writer.write_string(&plugin.identifier)?;
+        writer.write_string(&plugin.name)?;
+        writer.write_string(&plugin.description)?;
+        writer.write_bool(plugin.default_enabled);
+        // Lifecycle hooks are not served by this SDK.
+        writer.write_u8(0);
+        writer.write_length(plugin.aliases.len())?;
+        for alias in &plugin.aliases {
+            writer.write_string(alias)?;
+        }
+
+        writer.write_length(plugin.function_providers.len())?;
+        for (position, provider) in plugin.function_providers.iter().enumerate() {
+            write_provider_index(writer, self.function_providers.len())?;
+            writer.write_u8(capabilities(provider.undeclared_return_type_only(), provider.memoized()));
+            let targets = provider.targets();
+            if targets.is_empty() {
+                return Err(RequestError::new(format!(
+                    "a function return-type provider of plugin `{}` has no targets",
+                    plugin.identifier
+                )));
+            }
+
+            writer.write_length(targets.len())?;
+            for target in &targets {
+                target.write_to(writer)?;
+            }
+
+            self.function_providers.push((plugin_index, position));
+        }
+
+        writer.write_length(plugin.method_providers.len())?;
+        for (position, provider) in plugin.method_providers.iter().enumerate() {
+            write_provider_index(writer, self.method_providers.len())?;
+            writer.write_u8(capabilities(provider.undeclared_return_type_only(), provider.memoized()));
+            let targets = provider.targets();
+            if targets.is_empty() {
+                return Err(RequestError::new(format!(
+                    "a method return-type provider of plugin `{}` has no targets",
+                    plugin.identifier
+                )));
+            }
+
+            writer.write_length(targets.len())?;
+            for target in &targets {
+                target.write_to(writer)?;
+            }
+
+            self.method_providers.push((plugin_index, position));
+        }
+
+        // Property, property initialization, and class initializer providers.
+        writer.write_length(0)?;
+        writer.write_length(0)?;
+        writer.write_length(0)?;
+
+        writer.write_length(plugin.entry_points.len())?;
+        for entry_point in &plugin.entry_points {
+            entry_point.write_to(writer)?;
+        }
+
+        // Attributed entry points; issue-filter, node, method-call, and class-like hooks;
+        // function and method assertion providers; and codebase-scan hooks.
+        for _ in 0..8 {
+            writer.write_length(0)?;
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn return_type(
+        &self,
+        mut reader: PayloadReader<'_>,
+        plugins: &mut [Plugin],
+    ) -> Result<Vec<u8>, RequestError> {
+        let Some(php_version_id) = self.php_version_id else {
+            return Err(RequestError::new("Mago sent a return-type request before describing the analyzer"));
+        };
+
+        let _generation = reader.read_u64("analysis generation")?;
+        let kind = match reader.read_u8("invocation kind")? {
+            INVOCATION_FUNCTION => InvocationKind::Function,
+            INVOCATION_INSTANCE_METHOD => InvocationKind::InstanceMethod,
+            INVOCATION_STATIC_METHOD => InvocationKind::StaticMethod,
+            kind => return Err(RequestError::new(format!("unknown invocation kind {kind}"))),
+        };
+
+        let provider_count = reader.read_u16("provider count")?;
+        let mut providers = Vec::with_capacity(provider_count as usize);
+        for _ in 0..provider_count {
+            providers.push(reader.read_u16("provider index")? as usize);
+        }
+
+        let (declaring_class, name, receiver_type) = if kind == InvocationKind::Function {
+            (None, reader.read_bytes("function name")?.to_vec(), None)
+        } else {
+            let class = reader.read_bytes("method class")?.to_vec();
+            let name = reader.read_bytes("method name")?.to_vec();
+
+            (Some(class), name, Some(read_type(&mut reader)?))
+        };
+
+        let span = Span::new(reader.read_u32("invocation start")?, reader.read_u32("invocation end")?);
+        let argument_count = reader.read_u16("argument count")?;
+        let mut arguments = Vec::with_capacity(argument_count as usize);
+        for _ in 0..argument_count {
+            let name = reader.read_optional_string("argument name")?;
+            let unpacked = reader.read_bool("argument unpacked flag")?;
+            let placeholder = reader.read_bool("argument placeholder flag")?;
+            let span = Span::new(reader.read_u32("argument start")?, reader.read_u32("argument end")?);
+            let expression = reader.read_bytes("argument expression")?.to_vec();
+            let argument_type = if reader.read_bool("argument type flag")? {
+                let _type_id = reader.read_bytes("argument type identifier")?;
+
+                Some(read_type(&mut reader)?)
+            } else {
+                None
+            };
+
+            arguments.push(Argument { name, unpacked, placeholder, span, expression, argument_type });
+        }
+
+        reader.finish()?;
+
+        let invocation = Invocation { kind, name, declaring_class, receiver_type, span, arguments };
+        let context = ReturnTypeContext { php_version_id, invocation: &invocation };
+        let mut result = None;
+        for index in providers {
+            let table =
+                if kind == InvocationKind::Function { &self.function_providers } else { &self.method_providers };
+            let &(plugin, position) = table.get(index).ok_or_else(|| {
+                RequestError::new(format!("Mago requested unregistered return-type provider {index}"))
+            })?;
+
+            let plugin = &mut plugins[plugin];
+            result = if kind == InvocationKind::Function {
+                plugin.function_providers[position].return_type(&context)
+            } else {
+                plugin.method_providers[position].return_type(&context)
+            };
+
+            if result.is_some() {
+                break;
+            }
+        }
+
+        let mut writer = ANALYZER.writer(RETURN_TYPE_RESPONSE);
+        writer.write_bool(result.is_some());
+        if let Some(result) = result {
+            result.write_to(&mut writer)?;
+        }
+
+        Ok(writer.finish())
+    }
+}
+
+fn write_provider_index(writer: &mut PayloadWriter, index: usize) -> Result<(), RequestError> {
+    let index =
+        u16::try_from(index).map_err(|_| RequestError::new("a worker can register at most 65,536 providers"))?;
+    writer.write_u16(index);
+
+    Ok(())
+}
+
+const fn capabilities(undeclared_return_type_only: bool, memoized: bool) -> u8 {
+    let mut capabilities = 0;
+    if undeclared_return_type_only {
+        capabilities |= PROVIDER_UNDECLARED_RETURN_TYPE_ONLY;
+    }
+
+    if memoized {
+        capabilities |= PROVIDER_MEMOIZED;
+    }
+
+    capabilities
+}
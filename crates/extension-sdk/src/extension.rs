@@ -0,0 +1,213 @@
+//! Extension registration and worker-state reduction.
+
+use std::cell::RefCell;
+use std::ops::Range;
+use std::rc::Rc;
+
+use mago_extension::PayloadError;
+use mago_extension::PayloadReader;
+use mago_extension::PayloadWriter;
+use mago_extension::wire::worker::COLLECT_RESPONSE;
+use mago_extension::wire::worker::REDUCE_RESPONSE;
+
+use crate::analyzer::Plugin;
+use crate::linter::Rule;
+use crate::message::RequestError;
+use crate::message::WORKER;
+
+const MAXIMUM_WORKER_PAYLOADS: usize = 0x0001_0000;
+
+/// Terminal aggregation of process-local extension state.
+///
+/// When a worker pool shuts down, Mago asks every worker to
+/// [`collect`](WorkerReducer::collect) an opaque payload, then sends the
+/// complete batch, including the receiving worker's own payload, to the
+/// [`reduce`](WorkerReducer::reduce) of a single surviving worker. Reduction
+/// suits extension-owned output such as metrics or indexes; it cannot add
+/// issues to the completed Mago result.
+pub trait WorkerReducer {
+    /// Serializes the state of this worker.
+    fn collect(&mut self) -> Vec<u8>;
+
+    /// Performs the terminal work with the payloads of every worker, in stable worker order.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message that Mago logs as a worker-reduction warning.
+    fn reduce(&mut self, payloads: &[&[u8]]) -> Result<(), String>;
+}
+
+/// Lets rules and plugins record into the same reducer they are registered with.
+impl<T: WorkerReducer> WorkerReducer for Rc<RefCell<T>> {
+    fn collect(&mut self) -> Vec<u8> {
+        self.borrow_mut().collect()
+    }
+
+    fn reduce(&mut self, payloads: &[&[u8]]) -> Result<(), String> {
+        self.borrow_mut().reduce(payloads)
+    }
+}
+
+/// A named group of linter rules and analyzer plugins.
+///
+/// A worker serves one or more extensions. Mago displays the identity of an
+/// extension in `mago extension list`, and prefixes the codes of its rules
+/// with its identifier.
+pub struct Extension {
+    identifier: String,
+    name: String,
+    version: String,
+    rules: Vec<Box<dyn Rule>>,
+    plugins: Vec<Plugin>,
+    reducer: Option<Box<dyn WorkerReducer>>,
+}
+
+impl Extension {
+    /// Creates an extension without rules or plugins.
+    ///
+    /// The identifier, name, and version must not be empty.
+    #[must_use]
+    pub fn new(identifier: impl Into<String>, name: impl Into<String>, version: impl Into<String>) -> Self {
+        Self {
+            identifier: identifier.into(),
+            name: name.into(),
+            version: version.into(),
+            rules: vec![],
+            plugins: vec![],
+            reducer: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_rule(mut self, rule: impl Rule + 'static) -> Self {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    #[must_use]
+    pub fn with_plugin(mut self, plugin: Plugin) -> Self {
+        self.plugins.push(plugin);
+        self
+    }
+
+    #[must_use]
+    pub fn with_reducer(mut self, reducer: impl WorkerReducer + 'static) -> Self {
+        self.reducer = Some(Box::new(reducer));
+        self
+    }
+
+    /// Splits the extension into its identity and its rules and plugins, which
+    /// the worker appends to its flat tables at `first_rule` and `first_plugin`.
+    pub(crate) fn register(
+        self,
+        first_rule: usize,
+        first_plugin: usize,
+    ) -> (RegisteredExtension, Vec<Box<dyn Rule>>, Vec<Plugin>) {
+        let registered = RegisteredExtension {
+            identifier: self.identifier,
+            name: self.name,
+            version: self.version,
+            reducer: self.reducer,
+            rules: first_rule..first_rule + self.rules.len(),
+            plugins: first_plugin..first_plugin + self.plugins.len(),
+        };
+
+        (registered, self.rules, self.plugins)
+    }
+}
+
+impl std::fmt::Debug for Extension {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter
+            .debug_struct("Extension")
+            .field("identifier", &self.identifier)
+            .field("name", &self.name)
+            .field("version", &self.version)
+            .field("rules", &self.rules.len())
+            .field("plugins", &self.plugins.len())
+            .field("reducer", &self.reducer.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+/// An extension served by a worker, with the positions of its rules and plugins in the worker's flat tables.
+pub(crate) struct RegisteredExtension {
+    pub identifier: String,
+    pub name: String,
+    pub version: String,
+    pub reducer: Option<Box<dyn WorkerReducer>>,
+    pub rules: Range<usize>,
+    pub plugins: Range<usize>,
+}
+
+impl RegisteredExtension {
+    /// Writes the identity shared by the linter and analyzer registrations.
+    pub(crate) fn write_identity(&self, writer: &mut PayloadWriter) -> Result<(), PayloadError> {
+        writer.write_string(&self.identifier)?;
+        writer.write_string(&self.name)?;
+        writer.write_string(&self.version)?;
+        writer.write_bool(self.reducer.is_some());
+
+        Ok(())
+    }
+}
+
+/// Serves a collection request with the state of every extension that has a reducer.
+pub(crate) fn collect(
+    reader: PayloadReader<'_>,
+    extensions: &mut [RegisteredExtension],
+) -> Result<Vec<u8>, RequestError> {
+    reader.finish()?;
+
+    let mut writer = WORKER.writer(COLLECT_RESPONSE);
+    writer.write_length(extensions.iter().filter(|extension| extension.reducer.is_some()).count())?;
+    for (index, extension) in extensions.iter_mut().enumerate() {
+        if let Some(reducer) = &mut extension.reducer {
+            writer.write_u32(index as u32);
+            writer.write_bytes(&reducer.collect())?;
+        }
+    }
+
+    Ok(writer.finish())
+}
+
+/// Serves a reduction request, passing each reducer the payloads collected for its extension.
+pub(crate) fn reduce(
+    mut reader: PayloadReader<'_>,
+    extensions: &mut [RegisteredExtension],
+) -> Result<Vec<u8>, RequestError> {
+    let count = reader.read_count("worker payloads", MAXIMUM_WORKER_PAYLOADS)?;
+    let mut payloads = vec![Vec::new(); extensions.len()];
+    for _ in 0..count {
+        let response = reader.read_bytes("worker payload")?;
+        let (kind, mut response) = WORKER.reader(response)?;
+        if kind != COLLECT_RESPONSE {
+            return Err(RequestError::new(format!("expected a collection response, received message kind {kind}")));
+        }
+
+        let reducers = response.read_count("worker reducers", extensions.len())?;
+        for _ in 0..reducers {
+            let index = response.read_u32("worker reducer extension index")? as usize;
+            let payload = response.read_bytes("worker reducer payload")?;
+            if extensions.get(index).is_none_or(|extension| extension.reducer.is_none()) {
+                return Err(RequestError::new(format!("extension {index} has no worker reducer")));
+            }
+
+            payloads[index].push(payload);
+        }
+
+        response.finish()?;
+    }
+
+    reader.finish()?;
+
+    for (extension, payloads) in extensions.iter_mut().zip(&payloads) {
+        if let Some(reducer) = &mut extension.reducer {
+            reducer.reduce(payloads).map_err(|error| {
+                RequestError::new(format!("worker reducer of extension `{}` failed: {error}", extension.identifier))
+            })?;
+        }
+    }
+
+    Ok(WORKER.writer(REDUCE_RESPONSE).finish())
+}
@@ -0,0 +1,67 @@
+//! Worker-side SDK for writing Mago extensions in Rust.
+//!
+//! An extension worker is a separate executable that Mago starts and talks to
+//! over the extension protocol. This crate decodes Mago's requests into syntax
+//! and type views, runs the registered linter rules and analyzer providers,
+//! and encodes their issues, suggested edits, and types back into responses.
+//!
+//! ```no_run
+//! use mago_extension_sdk::Annotation;
+//! use mago_extension_sdk::Extension;
+//! use mago_extension_sdk::Issue;
+//! use mago_extension_sdk::LintContext;
+//! use mago_extension_sdk::Rule;
+//! use mago_extension_sdk::RuleDefinition;
+//! use mago_extension_sdk::Worker;
+//! use mago_syntax::cst::NodeKind;
+//!
+//! struct NoGoto;
+//!
+//! impl Rule for NoGoto {
+//!     fn definition(&self) -> RuleDefinition {
+//!         RuleDefinition::new("no-goto", "No goto", "Disallows `goto` statements.").with_target(NodeKind::Goto)
+//!     }
+//!
+//!     fn lint(&mut self, context: &mut LintContext<'_>) {
+//!         let span = context.node().span();
+//!
+//!         context.report(Issue::new("Do not use `goto`.").with_annotation(Annotation::primary(span)));
+//!     }
+//! }
+//!
+//! fn main() -> Result<(), mago_extension::ProtocolError> {
+//!     Worker::new(Extension::new("acme/style", "Acme style", "1.0.0").with_rule(NoGoto)).run()
+//! }
+//! ```
+
+pub mod analyzer;
+pub mod extension;
+pub mod linter;
+pub mod reporting;
+pub mod syntax;
+pub mod types;
+pub mod worker;
+
+mod message;
+
+pub use analyzer::FunctionReturnTypeProvider;
+pub use analyzer::FunctionTarget;
+pub use analyzer::MethodReturnTypeProvider;
+pub use analyzer::MethodTarget;
+pub use analyzer::Plugin;
+pub use analyzer::ReturnTypeContext;
+pub use extension::Extension;
+pub use extension::WorkerReducer;
+pub use linter::LintContext;
+pub use linter::Rule;
+pub use linter::RuleDefinition;
+pub use reporting::Annotation;
+pub use reporting::Issue;
+pub use reporting::Level;
+pub use reporting::TextEdit;
+pub use syntax::Node;
+pub use syntax::SourceFile;
+pub use syntax::Span;
+pub use types::Type;
+pub use types::TypeView;
+pub use worker::Worker;
@@ -0,0 +1,215 @@
+//! Custom linter rules and the worker side of the `MLNT` linter protocol.
+
+use mago_extension::PayloadReader;
+use mago_extension::wire::linter::DESCRIBE_RESPONSE;
+use mago_extension::wire::linter::LINT_FILE_RESPONSE;
+use mago_syntax::cst::NodeKind;
+
+use crate::extension::RegisteredExtension;
+use crate::message::LINTER;
+use crate::message::RequestError;
+use crate::reporting::AnnotationKind;
+use crate::reporting::Issue;
+use crate::reporting::Level;
+use crate::syntax::Node;
+use crate::syntax::SourceFile;
+use crate::syntax::read_node_kinds;
+
+const MAXIMUM_ISSUES_PER_FILE: usize = 1_000_000;
+
+/// The registration of a linter rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleDefinition {
+    /// The rule code, e.g. `no-debug-calls`. Mago prefixes it with the extension identifier.
+    pub code: String,
+    /// A short human-readable name.
+    pub name: String,
+    pub description: String,
+    /// The level of the issues this rule reports, unless configured otherwise.
+    pub level: Level,
+    pub default_enabled: bool,
+    /// The node kinds this rule is invoked for. Mago only sends files containing these nodes.
+    pub targets: Vec<NodeKind>,
+}
+
+impl RuleDefinition {
+    /// Creates an enabled rule reporting warnings.
+    #[must_use]
+    pub fn new(code: impl Into<String>, name: impl Into<String>, description: impl Into<String>) -> Self {
+        Self {
+            code: code.into(),
+            name: name.into(),
+            description: description.into(),
+            level: Level::Warning,
+            default_enabled: true,
+            targets: vec![],
+        }
+    }
+
+    #[must_use]
+    pub const fn with_level(mut self, level: Level) -> Self {
+        self.level = level;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_default_enabled(mut self, default_enabled: bool) -> Self {
+        self.default_enabled = default_enabled;
+        self
+    }
+
+    #[must_use]
+    pub fn with_target(mut self, target: NodeKind) -> Self {
+        self.targets.push(target);
+        self
+    }
+}
+
+/// A custom linter rule.
+pub trait Rule {
+    /// Returns the registration of this rule.
+    ///
+    /// This is called once, when the worker starts.
+    fn definition(&self) -> RuleDefinition;
+
+    /// Checks one target node, reporting issues through `context`.
+    fn lint(&mut self, context: &mut LintContext<'_>);
+}
+
+/// The node a [`Rule`] is invoked for, and the issues it reports.
+#[derive(Debug)]
+pub struct LintContext<'file> {
+    node: Node<'file>,
+    issues: Vec<Issue>,
+}
+
+impl<'file> LintContext<'file> {
+    /// Returns the target node.
+    #[must_use]
+    pub const fn node(&self) -> Node<'file> {
+        self.node
+    }
+
+    /// Returns the file being linted.
+    #[must_use]
+    pub const fn file(&self) -> &'file SourceFile {
+        self.node.file()
+    }
+
+    /// Reports an issue for the current rule.
+    pub fn report(&mut self, issue: Issue) {
+        self.issues.push(issue);
+    }
+}
+
+pub(crate) struct RegisteredRule {
+    pub rule: Box<dyn Rule>,
+    pub definition: RuleDefinition,
+}
+
+/// The linter state of a worker: its rules, and what Mago told it at registration.
+#[derive(Default)]
+pub(crate) struct Linter {
+    kinds: Vec<NodeKind>,
+    active_rules: Vec<u16>,
+    rules_by_kind: Vec<Vec<usize>>,
+}
+
+impl Linter {
+    pub(crate) fn describe(
+        &mut self,
+        mut reader: PayloadReader<'_>,
+        extensions: &[RegisteredExtension],
+        rules: &[RegisteredRule],
+    ) -> Result<Vec<u8>, RequestError> {
+        let _php_version = reader.read_u32("php version")?;
+        self.kinds = read_node_kinds(&mut reader)?;
+        reader.finish()?;
+
+        let mut writer = LINTER.writer(DESCRIBE_RESPONSE);
+        writer.write_length(extensions.len())?;
+        for extension in extensions {
+            extension.write_identity(&mut writer)?;
+            writer.write_length(extension.rules.len())?;
+            for rule in &rules[extension.rules.clone()] {
+                let definition = &rule.definition;
+                writer.write_string(&definition.code)?;
+                writer.write_string(&definition.name)?;
+                writer.write_string(&definition.description)?;
+                writer.write_u8(definition.level.code());
+                writer.write_bool(definition.default_enabled);
+                writer.write_length(definition.targets.len())?;
+                for target in &definition.targets {
+                    writer.write_string(&target.to_string())?;
+                }
+            }
+        }
+
+        Ok(writer.finish())
+    }
+
+    pub(crate) fn lint_file(
+        &mut self,
+        mut reader: PayloadReader<'_>,
+        rules: &mut [RegisteredRule],
+    ) -> Result<Vec<u8>, RequestError> {
+        if self.kinds.is_empty() {
+            return Err(RequestError::new("Mago sent a lint request before describing the linter"));
+        }
+
+        let name = reader.read_bytes("file name")?.to_vec();
+        let contents = reader.read_bytes("file contents")?.to_vec();
+        let active_rule_count = reader.read_u16("active rule count")?;
+        let mut active_rules = Vec::with_capacity(active_rule_count as usize);
+        for _ in 0..active_rule_count {
+            active_rules.push(reader.read_u16("active rule index")?);
+        }
+
+        let file = SourceFile::read(&mut reader, &self.kinds, name, contents)?;
+        reader.finish()?;
+
+        if active_rules != self.active_rules {
+            let mut rules_by_kind = vec![Vec::new(); self.kinds.len()];
+            for index in &active_rules {
+                let rule = rules.get(*index as usize).ok_or_else(|| {
+                    RequestError::new(format!("Mago requested unregistered linter rule index {index}"))
+                })?;
+
+                for target in &rule.definition.targets {
+                    rules_by_kind[*target as usize].push(*index as usize);
+                }
+            }
+
+            self.active_rules = active_rules;
+            self.rules_by_kind = rules_by_kind;
+        }
+
+        let mut reported = Vec::new();
+        for node in file.target_nodes() {
+            for index in &self.rules_by_kind[node.kind() as usize] {
+                let mut context = LintContext { node, issues: vec![] };
+                rules[*index].rule.lint(&mut context);
+                reported.extend(context.issues.into_iter().map(|issue| (*index as u16, issue)));
+            }
+        }
+
+        if reported.len() > MAXIMUM_ISSUES_PER_FILE {
+            return Err(RequestError::new("a linter response contains too many issues"));
+        }
+
+        let mut writer = LINTER.writer(LINT_FILE_RESPONSE);
+        writer.write_length(reported.len())?;
+        for (index, issue) in &reported {
+            if !issue.annotations.iter().any(|annotation| annotation.kind == AnnotationKind::Primary) {
+                let code = &rules[*index as usize].definition.code;
+
+                return Err(RequestError::new(format!("rule `{code}` reported an issue without a primary annotation")));
+            }
+
+            writer.write_u16(*index);
+            issue.write_to(&mut writer)?;
+        }
+
+        Ok(writer.finish())
+    }
+}
@@ -0,0 +1,93 @@
+//! Capability message headers shared by the linter, analyzer, and worker-management protocols.
+//!
+//! Every capability payload starts with a 12-byte header: four magic bytes, the
+//! protocol major and minor versions, the message kind, and a reserved zero.
+
+use mago_extension::PayloadError;
+use mago_extension::PayloadReader;
+use mago_extension::PayloadWriter;
+use mago_extension::wire;
+use mago_extension::wire::HEADER_LENGTH;
+
+/// A capability protocol identified by its header magic and version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Capability {
+    pub name: &'static str,
+    pub magic: [u8; 4],
+    pub major: u16,
+    pub minor: u16,
+}
+
+pub(crate) const LINTER: Capability =
+    Capability { name: "linter", magic: wire::linter::MAGIC, major: wire::linter::MAJOR, minor: wire::linter::MINOR };
+pub(crate) const ANALYZER: Capability = Capability {
+    name: "analyzer",
+    magic: wire::analyzer::MAGIC,
+    major: wire::analyzer::MAJOR,
+    minor: wire::analyzer::MINOR,
+};
+pub(crate) const WORKER: Capability = Capability {
+    name: "worker management",
+    magic: wire::worker::MAGIC,
+    major: wire::worker::MAJOR,
+    minor: wire::worker::MINOR,
+};
+
+/// An error raised while serving one request, sent back to Mago as an error response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RequestError(pub String);
+
+impl RequestError {
+    pub(crate) fn new(message: impl Into<String>) -> Self {
+        Self(message.into())
+    }
+}
+
+impl From<PayloadError> for RequestError {
+    fn from(error: PayloadError) -> Self {
+        Self(error.to_string())
+    }
+}
+
+impl Capability {
+    /// Returns whether `payload` starts with this capability's magic bytes.
+    pub(crate) fn matches(self, payload: &[u8]) -> bool {
+        payload.starts_with(&self.magic)
+    }
+
+    /// Starts a message of `kind`.
+    pub(crate) fn writer(self, kind: u16) -> PayloadWriter {
+        let mut writer = PayloadWriter::with_capacity(HEADER_LENGTH);
+        writer.write_raw(&self.magic);
+        writer.write_u16(self.major);
+        writer.write_u16(self.minor);
+        writer.write_u16(kind);
+        writer.write_u16(0);
+        writer
+    }
+
+    /// Validates the header of `payload` and returns its message kind and a reader positioned after it.
+    pub(crate) fn reader(self, payload: &[u8]) -> Result<(u16, PayloadReader<'_>), RequestError> {
+        let mut reader = PayloadReader::new(payload);
+        if reader.read_array::<4>("message magic")? != self.magic {
+            return Err(RequestError::new(format!("invalid {} message magic", self.name)));
+        }
+
+        let major = reader.read_u16("protocol major version")?;
+        let minor = reader.read_u16("protocol minor version")?;
+        if major != self.major {
+            return Err(RequestError::new(format!("unsupported {} protocol version {major}.{minor}", self.name)));
+        }
+
+        let kind = reader.read_u16("message kind")?;
+        let reserved = reader.read_u16("reserved header")?;
+        if reserved != 0 {
+            return Err(RequestError::new(format!(
+                "{} message reserved header is non-zero: {reserved:#06x}",
+                self.name
+            )));
+        }
+
+        Ok((kind, reader))
+    }
+}
@@ -0,0 +1,186 @@
+//! Issues and suggested edits reported by extension rules.
+
+use mago_extension::PayloadError;
+use mago_extension::PayloadWriter;
+
+use crate::syntax::Span;
+
+/// The severity of an issue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    Note,
+    Help,
+    Warning,
+    Error,
+}
+
+impl Level {
+    pub(crate) const fn code(self) -> u8 {
+        match self {
+            Self::Note => 1,
+            Self::Help => 2,
+            Self::Warning => 3,
+            Self::Error => 4,
+        }
+    }
+}
+
+/// Whether an annotation marks the main location of an issue or related context.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AnnotationKind {
+    Primary,
+    Secondary,
+}
+
+/// A labeled location of an issue.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Annotation {
+    pub kind: AnnotationKind,
+    pub span: Span,
+    pub message: Option<String>,
+}
+
+impl Annotation {
+    #[must_use]
+    pub const fn primary(span: Span) -> Self {
+        Self { kind: AnnotationKind::Primary, span, message: None }
+    }
+
+    #[must_use]
+    pub const fn secondary(span: Span) -> Self {
+        Self { kind: AnnotationKind::Secondary, span, message: None }
+    }
+
+    #[must_use]
+    pub fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+}
+
+/// How safe it is to apply a [`TextEdit`] automatically.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Safety {
+    #[default]
+    Safe,
+    PotentiallyUnsafe,
+    Unsafe,
+}
+
+/// A suggested replacement of a range of the current file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    pub span: Span,
+    pub replacement: Vec<u8>,
+    pub safety: Safety,
+}
+
+impl TextEdit {
+    #[must_use]
+    pub fn replace(span: Span, replacement: impl Into<Vec<u8>>) -> Self {
+        Self { span, replacement: replacement.into(), safety: Safety::Safe }
+    }
+
+    #[must_use]
+    pub fn insert(offset: u32, text: impl Into<Vec<u8>>) -> Self {
+        Self::replace(Span::new(offset, offset), text)
+    }
+
+    #[must_use]
+    pub fn delete(span: Span) -> Self {
+        Self::replace(span, Vec::new())
+    }
+
+    #[must_use]
+    pub const fn with_safety(mut self, safety: Safety) -> Self {
+        self.safety = safety;
+        self
+    }
+}
+
+/// An issue reported by an extension rule.
+///
+/// Mago assigns the code and level of the reporting rule, so an issue only
+/// carries its message, context, and suggested edits. Every issue needs at
+/// least one primary annotation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Issue {
+    pub message: String,
+    pub notes: Vec<String>,
+    pub help: Option<String>,
+    pub link: Option<String>,
+    pub annotations: Vec<Annotation>,
+    pub edits: Vec<TextEdit>,
+}
+
+impl Issue {
+    #[must_use]
+    pub fn new(message: impl Into<String>) -> Self {
+        Self { message: message.into(), notes: vec![], help: None, link: None, annotations: vec![], edits: vec![] }
+    }
+
+    #[must_use]
+    pub fn with_annotation(mut self, annotation: Annotation) -> Self {
+        self.annotations.push(annotation);
+        self
+    }
+
+    #[must_use]
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_link(mut self, link: impl Into<String>) -> Self {
+        self.link = Some(link.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_edit(mut self, edit: TextEdit) -> Self {
+        self.edits.push(edit);
+        self
+    }
+
+    pub(crate) fn write_to(&self, writer: &mut PayloadWriter) -> Result<(), PayloadError> {
+        writer.write_string(&self.message)?;
+        writer.write_length(self.notes.len())?;
+        for note in &self.notes {
+            writer.write_string(note)?;
+        }
+
+        writer.write_optional_string(self.help.as_deref())?;
+        writer.write_optional_string(self.link.as_deref())?;
+        writer.write_length(self.annotations.len())?;
+        for annotation in &self.annotations {
+            writer.write_u8(match annotation.kind {
+                AnnotationKind::Primary => 1,
+                AnnotationKind::Secondary => 2,
+            });
+            writer.write_u32(annotation.span.start);
+            writer.write_u32(annotation.span.end);
+            writer.write_optional_string(annotation.message.as_deref())?;
+        }
+
+        writer.write_length(self.edits.len())?;
+        for edit in &self.edits {
+            writer.write_u32(edit.span.start);
+            writer.write_u32(edit.span.end);
+            writer.write_u8(match edit.safety {
+                Safety::Safe => 1,
+                Safety::PotentiallyUnsafe => 2,
+                Safety::Unsafe => 3,
+            });
+            writer.write_bytes(&edit.replacement)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,406 @@
+//! Decoded views of the syntax snapshots Mago sends to extension workers.
+//!
+//! Mago flattens the syntax tree of a file into packed node records before
+//! sending it to a worker. A [`SourceFile`] owns the decoded tables, and
+//! [`Node`] is a cheap handle for navigating them.
+
+use mago_extension::PayloadReader;
+use mago_extension::source::NO_NODE;
+use mago_syntax::cst::NodeKind;
+use strum::IntoEnumIterator;
+
+use crate::message::RequestError;
+
+const MAXIMUM_NODE_KINDS: usize = 0x0001_0000;
+const MAXIMUM_SNAPSHOT_RECORDS: usize = 0x1000_0000;
+
+/// A half-open byte range within a source file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Span {
+    /// Offset of the first byte.
+    pub start: u32,
+    /// Offset one past the last byte.
+    pub end: u32,
+}
+
+impl Span {
+    #[must_use]
+    pub const fn new(start: u32, end: u32) -> Self {
+        Self { start, end }
+    }
+
+    #[must_use]
+    pub const fn len(&self) -> u32 {
+        self.end.saturating_sub(self.start)
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    /// Returns whether `other` lies entirely within this span.
+    #[must_use]
+    pub const fn contains(&self, other: Span) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+}
+
+/// The lexical kind of a comment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TriviaKind {
+    SingleLineComment,
+    MultiLineComment,
+    HashComment,
+    DocBlockComment,
+}
+
+/// A comment in the source file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Trivia {
+    pub kind: TriviaKind,
+    pub span: Span,
+}
+
+/// A name resolved by Mago's name resolver, such as the target of a `use` import.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResolvedName<'file> {
+    /// The location of the name in the source.
+    pub span: Span,
+    /// The fully qualified name.
+    pub value: &'file [u8],
+    /// Whether the name was resolved through an import.
+    pub imported: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct NodeRecord {
+    kind: NodeKind,
+    span: Span,
+    parent: u32,
+    first_child: u32,
+    next_sibling: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct NameRecord {
+    span: Span,
+    offset: usize,
+    length: usize,
+    imported: bool,
+}
+
+/// A source file and the syntax snapshot Mago sent for it.
+///
+/// Linter snapshots only contain the subtrees of nodes targeted by the active
+/// rules, so the parent of a target node may be absent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceFile {
+    name: Vec<u8>,
+    contents: Vec<u8>,
+    targets: Vec<u32>,
+    nodes: Vec<NodeRecord>,
+    names: Vec<NameRecord>,
+    name_buffer: Vec<u8>,
+    trivia: Vec<Trivia>,
+}
+
+/// A handle to one node of a [`SourceFile`].
+#[derive(Clone, Copy)]
+pub struct Node<'file> {
+    file: &'file SourceFile,
+    id: u32,
+}
+
+impl SourceFile {
+    /// Returns the logical file name, relative to the workspace.
+    #[must_use]
+    pub fn name(&self) -> &[u8] {
+        &self.name
+    }
+
+    /// Returns the full contents of the file.
+    #[must_use]
+    pub fn contents(&self) -> &[u8] {
+        &self.contents
+    }
+
+    /// Returns the source text covered by `span`, or an empty slice when it lies outside the file.
+    #[must_use]
+    pub fn text(&self, span: Span) -> &[u8] {
+        self.contents.get(span.start as usize..span.end as usize).unwrap_or_default()
+    }
+
+    /// Returns the nodes Mago selected for the current request, in source order.
+    pub fn target_nodes(&self) -> impl Iterator<Item = Node<'_>> {
+        self.targets.iter().map(|id| Node { file: self, id: *id })
+    }
+
+    /// Returns every node of the snapshot, in pre-order.
+    pub fn nodes(&self) -> impl Iterator<Item = Node<'_>> {
+        (0..self.nodes.len() as u32).map(|id| Node { file: self, id })
+    }
+
+    /// Returns the node with the given snapshot identifier.
+    #[must_use]
+    pub fn node(&self, id: u32) -> Option<Node<'_>> {
+        ((id as usize) < self.nodes.len()).then_some(Node { file: self, id })
+    }
+
+    /// Returns the resolved name starting at the start of `span`, if there is one.
+    #[must_use]
+    pub fn resolved_name(&self, span: Span) -> Option<ResolvedName<'_>> {
+        let index = self.names.partition_point(|name| name.span.start < span.start);
+        self.names.get(index).filter(|name| name.span.start == span.start).map(|name| self.name_at(name))
+    }
+
+    /// Returns the resolved names within `span`, in source order.
+    pub fn resolved_names(&self, span: Span) -> impl Iterator<Item = ResolvedName<'_>> {
+        let start = self.names.partition_point(|name| name.span.start < span.start);
+        self.names[start..]
+            .iter()
+            .take_while(move |name| name.span.start < span.end)
+            .filter(move |name| span.contains(name.span))
+            .map(|name| self.name_at(name))
+    }
+
+    /// Returns the comments of the file, in source order.
+    ///
+    /// Snapshots that do not include comments return an empty slice.
+    #[must_use]
+    pub fn trivia(&self) -> &[Trivia] {
+        &self.trivia
+    }
+
+    fn name_at(&self, name: &NameRecord) -> ResolvedName<'_> {
+        ResolvedName {
+            span: name.span,
+            value: &self.name_buffer[name.offset..name.offset + name.length],
+            imported: name.imported,
+        }
+    }
+
+    fn record(&self, id: u32) -> &NodeRecord {
+        &self.nodes[id as usize]
+    }
+
+    fn link(&self, id: u32) -> Option<Node<'_>> {
+        (id != NO_NODE).then_some(Node { file: self, id })
+    }
+
+    /// Decodes a file name, its contents, and the snapshot that follows them.
+    pub(crate) fn read(
+        reader: &mut PayloadReader<'_>,
+        kinds: &[NodeKind],
+        name: Vec<u8>,
+        contents: Vec<u8>,
+    ) -> Result<Self, RequestError> {
+        let size =
+            u32::try_from(contents.len()).map_err(|_| RequestError::new("source file exceeds u32::MAX bytes"))?;
+
+        let target_count = reader.read_count("snapshot targets", MAXIMUM_SNAPSHOT_RECORDS)?;
+        let mut targets = Vec::with_capacity(target_count);
+        for _ in 0..target_count {
+            targets.push(reader.read_u32("snapshot target")?);
+        }
+
+        let node_count = reader.read_count("snapshot nodes", MAXIMUM_SNAPSHOT_RECORDS)?;
+        let mut nodes = Vec::with_capacity(node_count);
+        for _ in 0..node_count {
+            let kind = reader.read_u8("node kind")?;
+            let kind = *kinds
+                .get(kind as usize)
+                .ok_or_else(|| RequestError::new(format!("snapshot contains unknown node kind {kind}")))?;
+            let span = read_span(reader, size, "node")?;
+            nodes.push(NodeRecord {
+                kind,
+                span,
+                parent: reader.read_u32("node parent")?,
+                first_child: reader.read_u32("node first child")?,
+                next_sibling: reader.read_u32("node next sibling")?,
+            });
+        }
+
+        let is_link = |link: u32| link == NO_NODE || (link as usize) < node_count;
+        if targets.iter().any(|target| (*target as usize) >= node_count)
+            || nodes
+                .iter()
+                .any(|node| !is_link(node.parent) || !is_link(node.first_child) || !is_link(node.next_sibling))
+        {
+            return Err(RequestError::new("snapshot contains a dangling node reference"));
+        }
+
+        let name_count = reader.read_count("resolved names", MAXIMUM_SNAPSHOT_RECORDS)?;
+        let mut starts = Vec::with_capacity(name_count);
+        for _ in 0..name_count {
+            starts.push(reader.read_u32("resolved name start")?);
+        }
+
+        let mut names = Vec::with_capacity(name_count);
+        for start in starts {
+            let end = reader.read_u32("resolved name end")?;
+            if start > end || end > size {
+                return Err(RequestError::new(format!("resolved name has invalid span {start}..{end}")));
+            }
+
+            names.push(NameRecord {
+                span: Span::new(start, end),
+                offset: reader.read_u32("resolved name offset")? as usize,
+                length: reader.read_u32("resolved name length")? as usize,
+                imported: reader.read_bool("resolved name imported flag")?,
+            });
+        }
+
+        let name_buffer = reader.read_bytes("resolved name buffer")?.to_vec();
+        if names.iter().any(|name| name.offset.checked_add(name.length).is_none_or(|end| end > name_buffer.len())) {
+            return Err(RequestError::new("resolved name lies outside the name buffer"));
+        }
+
+        let trivia_count = reader.read_count("trivia", MAXIMUM_SNAPSHOT_RECORDS)?;
+        let mut trivia = Vec::with_capacity(trivia_count);
+        for _ in 0..trivia_count {
+            let kind = match reader.read_u8("trivia kind")? {
+                1 => TriviaKind::SingleLineComment,
+                2 => TriviaKind::MultiLineComment,
+                3 => TriviaKind::HashComment,
+                4 => TriviaKind::DocBlockComment,
+                kind => return Err(RequestError::new(format!("snapshot contains unknown trivia kind {kind}"))),
+            };
+
+            trivia.push(Trivia { kind, span: read_span(reader, size, "trivia")? });
+        }
+
+        Ok(Self { name, contents, targets, nodes, names, name_buffer, trivia })
+    }
+}
+
+impl<'file> Node<'file> {
+    /// Returns the snapshot identifier of this node.
+    #[must_use]
+    pub const fn id(&self) -> u32 {
+        self.id
+    }
+
+    #[must_use]
+    pub fn kind(&self) -> NodeKind {
+        self.file.record(self.id).kind
+    }
+
+    #[must_use]
+    pub fn span(&self) -> Span {
+        self.file.record(self.id).span
+    }
+
+    /// Returns the source text of this node.
+    #[must_use]
+    pub fn text(&self) -> &'file [u8] {
+        self.file.text(self.span())
+    }
+
+    /// Returns the parent of this node, if it is part of the snapshot.
+    #[must_use]
+    pub fn parent(&self) -> Option<Node<'file>> {
+        self.file.link(self.file.record(self.id).parent)
+    }
+
+    #[must_use]
+    pub fn first_child(&self) -> Option<Node<'file>> {
+        self.file.link(self.file.record(self.id).first_child)
+    }
+
+    #[must_use]
+    pub fn next_sibling(&self) -> Option<Node<'file>> {
+        self.file.link(self.file.record(self.id).next_sibling)
+    }
+
+    /// Returns the direct children of this node, in source order.
+    pub fn children(&self) -> impl Iterator<Item = Node<'file>> {
+        std::iter::successors(self.first_child(), Node::next_sibling)
+    }
+
+    /// Returns the ancestors of this node, starting with its parent.
+    pub fn ancestors(&self) -> impl Iterator<Item = Node<'file>> {
+        std::iter::successors(self.parent(), Node::parent)
+    }
+
+    /// Returns the descendants of this node, in pre-order.
+    pub fn descendants(&self) -> impl Iterator<Item = Node<'file>> {
+        let mut stack = self.children().collect::<Vec<_>>();
+        stack.reverse();
+
+        std::iter::from_fn(move || {
+            let node = stack.pop()?;
+            let start = stack.len();
+            stack.extend(node.children());
+            stack[start..].reverse();
+
+            Some(node)
+        })
+    }
+
+    /// Returns the first descendant of the given kind, in pre-order.
+    #[must_use]
+    pub fn first_descendant(&self, kind: NodeKind) -> Option<Node<'file>> {
+        self.descendants().find(|node| node.kind() == kind)
+    }
+
+    /// Returns the resolved name starting at this node, if there is one.
+    #[must_use]
+    pub fn resolved_name(&self) -> Option<ResolvedName<'file>> {
+        self.file.resolved_name(self.span())
+    }
+
+    /// Returns the source file this node belongs to.
+    #[must_use]
+    pub const fn file(&self) -> &'file SourceFile {
+        self.file
+    }
+}
+
+impl PartialEq for Node<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self.file, other.file) && self.id == other.id
+    }
+}
+
+impl Eq for Node<'_> {}
+
+impl std::fmt::Debug for Node<'_> {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter
+            .debug_struct("Node")
+            .field("id", &self.id)
+            .field("kind", &self.kind())
+            .field("span", &self.span())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Reads the node-kind table of a describe request and checks that it matches this SDK.
+pub(crate) fn read_node_kinds(reader: &mut PayloadReader<'_>) -> Result<Vec<NodeKind>, RequestError> {
+    let kinds = NodeKind::iter().collect::<Vec<_>>();
+    let count = reader.read_count("node kinds", MAXIMUM_NODE_KINDS)?;
+    let mut matches = count == kinds.len();
+    for index in 0..count {
+        let name = reader.read_str("node kind")?;
+        matches &= kinds.get(index).is_some_and(|kind| kind.to_string() == name);
+    }
+
+    if !matches {
+        return Err(RequestError::new(
+            "the Mago and extension SDK node-kind tables differ; rebuild the worker against this Mago version",
+        ));
+    }
+
+    Ok(kinds)
+}
+
+fn read_span(reader: &mut PayloadReader<'_>, size: u32, field: &'static str) -> Result<Span, RequestError> {
+    let start = reader.read_u32("span start")?;
+    let end = reader.read_u32("span end")?;
+    if start > end || end > size {
+        return Err(RequestError::new(format!("{field} has invalid span {start}..{end} for a {size}-byte file")));
+    }
+
+    Ok(Span::new(start, end))
+}
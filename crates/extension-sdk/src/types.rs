@@ -0,0 +1,1147 @@
+//! Decoded views of analyzer types, and the types providers return.
+//!
+//! Mago snapshots the inferred types of a request, such as the receiver and
+//! argument types of an invocation, as [`TypeView`] trees. Every union in a
+//! snapshot carries a request-local handle, so a provider can return an
+//! inferred type unchanged with [`Type::of`] instead of re-encoding it.
+
+use mago_extension::PayloadReader;
+use mago_extension::PayloadWriter;
+use mago_extension::wire::analyzer::SNAPSHOT_ALIAS;
+use mago_extension::wire::analyzer::SNAPSHOT_ARRAY;
+use mago_extension::wire::analyzer::SNAPSHOT_CALLABLE;
+use mago_extension::wire::analyzer::SNAPSHOT_CONDITIONAL;
+use mago_extension::wire::analyzer::SNAPSHOT_DERIVED;
+use mago_extension::wire::analyzer::SNAPSHOT_GENERIC_PARAMETER;
+use mago_extension::wire::analyzer::SNAPSHOT_ITERABLE;
+use mago_extension::wire::analyzer::SNAPSHOT_MIXED;
+use mago_extension::wire::analyzer::SNAPSHOT_NEVER;
+use mago_extension::wire::analyzer::SNAPSHOT_NULL;
+use mago_extension::wire::analyzer::SNAPSHOT_OBJECT;
+use mago_extension::wire::analyzer::SNAPSHOT_PLACEHOLDER;
+use mago_extension::wire::analyzer::SNAPSHOT_REFERENCE;
+use mago_extension::wire::analyzer::SNAPSHOT_RESOURCE;
+use mago_extension::wire::analyzer::SNAPSHOT_SCALAR;
+use mago_extension::wire::analyzer::SNAPSHOT_VARIABLE;
+use mago_extension::wire::analyzer::SNAPSHOT_VOID;
+use mago_extension::wire::analyzer::TYPE_ARRAY;
+use mago_extension::wire::analyzer::TYPE_BOOL;
+use mago_extension::wire::analyzer::TYPE_FALSE;
+use mago_extension::wire::analyzer::TYPE_FLOAT;
+use mago_extension::wire::analyzer::TYPE_INT;
+use mago_extension::wire::analyzer::TYPE_LIST;
+use mago_extension::wire::analyzer::TYPE_LITERAL_INT;
+use mago_extension::wire::analyzer::TYPE_LITERAL_STRING;
+use mago_extension::wire::analyzer::TYPE_MIXED;
+use mago_extension::wire::analyzer::TYPE_NAMED_OBJECT;
+use mago_extension::wire::analyzer::TYPE_NEVER;
+use mago_extension::wire::analyzer::TYPE_NON_EMPTY_STRING;
+use mago_extension::wire::analyzer::TYPE_NON_NEGATIVE_INT;
+use mago_extension::wire::analyzer::TYPE_NULL;
+use mago_extension::wire::analyzer::TYPE_OBJECT;
+use mago_extension::wire::analyzer::TYPE_REFERENCE;
+use mago_extension::wire::analyzer::TYPE_STRING;
+use mago_extension::wire::analyzer::TYPE_TRUE;
+use mago_extension::wire::analyzer::TYPE_UNION;
+use mago_extension::wire::analyzer::TYPE_VOID;
+use mago_extension::wire::analyzer::UNION_BY_REFERENCE;
+use mago_extension::wire::analyzer::UNION_NULLSAFE_NULL;
+use mago_extension::wire::analyzer::UNION_POSSIBLY_UNDEFINED;
+
+use crate::message::RequestError;
+
+const MAXIMUM_TYPE_DEPTH: usize = 256;
+const MAXIMUM_TYPE_MEMBERS: usize = 0x0001_0000;
+
+/// A union type inferred by Mago.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeView {
+    handle: u32,
+    flags: u16,
+    atomics: Vec<AtomicView>,
+}
+
+/// One member of a [`TypeView`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum AtomicView {
+    Scalar(ScalarView),
+    Callable(Box<CallableView>),
+    Mixed(MixedView),
+    Object(Box<ObjectView>),
+    Array(Box<ArrayView>),
+    Iterable(Box<IterableView>),
+    /// A resource, and whether it is known to be closed.
+    Resource(Option<bool>),
+    Reference(Box<ReferenceView>),
+    GenericParameter(Box<GenericParameterView>),
+    /// A variable type, e.g. `$foo` in a conditional return type.
+    Variable(Vec<u8>),
+    Conditional(Box<ConditionalView>),
+    Derived(Box<DerivedView>),
+    /// A type alias, as a class-like name and an alias name.
+    Alias(Vec<u8>, Vec<u8>),
+    Never,
+    Null,
+    Void,
+    Placeholder,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Truthiness {
+    Undetermined,
+    Truthy,
+    Falsy,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MixedView {
+    pub isset_from_loop: bool,
+    pub non_null: bool,
+    pub empty: bool,
+    pub truthiness: Truthiness,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScalarView {
+    Generic,
+    Numeric,
+    ArrayKey,
+    /// A boolean, and its value when known.
+    Bool(Option<bool>),
+    Integer(IntegerView),
+    Float(FloatView),
+    String(StringView),
+    ClassLikeString(ClassLikeStringView),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntegerView {
+    Literal(i64),
+    From(i64),
+    To(i64),
+    Range(i64, i64),
+    Unspecified,
+    UnspecifiedLiteral,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FloatView {
+    Float,
+    UnspecifiedLiteral,
+    Literal(f64),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StringLiteral {
+    Unspecified,
+    Value(Vec<u8>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StringCasing {
+    Unspecified,
+    Lowercase,
+    Uppercase,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StringView {
+    pub literal: Option<StringLiteral>,
+    pub numeric: bool,
+    pub truthy: bool,
+    pub non_empty: bool,
+    pub callable: bool,
+    pub casing: StringCasing,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClassLikeKind {
+    Class,
+    Interface,
+    Enum,
+    Trait,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClassLikeStringView {
+    Any { kind: ClassLikeKind },
+    Generic { kind: ClassLikeKind, parameter: Vec<u8>, parent: GenericParent, constraint: Box<AtomicView> },
+    Literal { value: Vec<u8> },
+    OfType { kind: ClassLikeKind, constraint: Box<AtomicView> },
+}
+
+/// The class-like or function-like a template parameter is defined on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GenericParent {
+    ClassLike(Vec<u8>),
+    FunctionLike(Vec<u8>, Vec<u8>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FunctionLikeIdentifier {
+    Function(Vec<u8>),
+    Method(Vec<u8>, Vec<u8>),
+    Closure(Vec<u8>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variance {
+    Invariant,
+    Covariant,
+    Contravariant,
+    Bivariant,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CallableView {
+    Signature(CallableSignatureView),
+    Alias(FunctionLikeIdentifier),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CallableSignatureView {
+    pub is_pure: bool,
+    pub is_closure: bool,
+    pub parameters: Vec<CallableParameterView>,
+    pub return_type: Option<TypeView>,
+    pub source: Option<FunctionLikeIdentifier>,
+    pub constraints: Vec<CallableConstraintView>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CallableParameterView {
+    pub name: Option<Vec<u8>>,
+    pub parameter_type: Option<TypeView>,
+    pub closure_this_type: Option<TypeView>,
+    pub by_reference: bool,
+    pub variadic: bool,
+    pub has_default: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CallableConstraintView {
+    pub parameter_names: Vec<Vec<u8>>,
+    pub input_type: TypeView,
+    pub parameter_type: TypeView,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ObjectView {
+    Any,
+    Named {
+        name: Vec<u8>,
+        type_parameters: Option<Vec<TypeView>>,
+        variances: Option<Vec<Variance>>,
+        is_static: bool,
+        is_this: bool,
+        intersections: Option<Vec<AtomicView>>,
+        remapped_parameters: bool,
+    },
+    Enum {
+        name: Vec<u8>,
+        case: Option<String>,
+    },
+    WithProperties {
+        sealed: bool,
+        /// The known properties, as name, whether the property is optional, and type.
+        properties: Vec<(Vec<u8>, bool, TypeView)>,
+    },
+    HasMethod {
+        method: Vec<u8>,
+        intersections: Option<Vec<AtomicView>>,
+    },
+    HasProperty {
+        property: Vec<u8>,
+        intersections: Option<Vec<AtomicView>>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArrayKey {
+    Integer(i64),
+    String(Vec<u8>),
+    ClassLikeConstant { class_like: Vec<u8>, constant: Vec<u8> },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArrayView {
+    List {
+        element_type: TypeView,
+        /// The known elements, as index, whether the element is optional, and type.
+        known_elements: Option<Vec<(u64, bool, TypeView)>>,
+        known_count: Option<u64>,
+        non_empty: bool,
+    },
+    Keyed {
+        /// The known items, as key, whether the item is optional, and type.
+        known_items: Option<Vec<(ArrayKey, bool, TypeView)>>,
+        /// The generic key and value types.
+        parameters: Option<(TypeView, TypeView)>,
+        non_empty: bool,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IterableView {
+    pub key_type: TypeView,
+    pub value_type: TypeView,
+    pub intersections: Option<Vec<AtomicView>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MemberSelector {
+    Wildcard,
+    Identifier(Vec<u8>),
+    StartsWith(Vec<u8>),
+    EndsWith(Vec<u8>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReferenceView {
+    Symbol {
+        name: Vec<u8>,
+        parameters: Option<Vec<TypeView>>,
+        variances: Option<Vec<Variance>>,
+        intersections: Option<Vec<AtomicView>>,
+    },
+    Member {
+        class_like: Vec<u8>,
+        selector: MemberSelector,
+    },
+    Global {
+        selector: MemberSelector,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenericParameterView {
+    pub name: Vec<u8>,
+    pub constraint: TypeView,
+    pub parent: GenericParent,
+    pub intersections: Option<Vec<AtomicView>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConditionalView {
+    pub subject: TypeView,
+    pub target: TypeView,
+    pub then: TypeView,
+    pub otherwise: TypeView,
+    pub negated: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Visibility {
+    Public,
+    Protected,
+    Private,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DerivedView {
+    KeyOf(TypeView),
+    ValueOf(TypeView),
+    IntMask(Vec<TypeView>),
+    IntMaskOf(TypeView),
+    PropertiesOf { visibility: Option<Visibility>, target: TypeView },
+    IndexAccess { target: TypeView, index: TypeView },
+    New(TypeView),
+    TemplateType { object: TypeView, class_name: TypeView, template_name: TypeView },
+    Intersection { base: TypeView, intersections: Vec<AtomicView> },
+}
+
+impl TypeView {
+    /// Returns the atomic members of the union.
+    #[must_use]
+    pub fn atomics(&self) -> &[AtomicView] {
+        &self.atomics
+    }
+
+    /// Returns the request-local handle of this type.
+    #[must_use]
+    pub const fn handle(&self) -> u32 {
+        self.handle
+    }
+
+    #[must_use]
+    pub const fn is_by_reference(&self) -> bool {
+        self.flags & UNION_BY_REFERENCE != 0
+    }
+
+    #[must_use]
+    pub const fn is_possibly_undefined(&self) -> bool {
+        self.flags & UNION_POSSIBLY_UNDEFINED != 0
+    }
+
+    #[must_use]
+    pub const fn has_nullsafe_null(&self) -> bool {
+        self.flags & UNION_NULLSAFE_NULL != 0
+    }
+
+    /// Returns whether the union has exactly one member.
+    #[must_use]
+    pub fn is_single(&self) -> bool {
+        self.atomics.len() == 1
+    }
+
+    /// Returns whether the union contains `null`.
+    #[must_use]
+    pub fn is_nullable(&self) -> bool {
+        self.atomics.iter().any(|atomic| matches!(atomic, AtomicView::Null))
+    }
+
+    /// Returns whether the union contains `mixed`.
+    #[must_use]
+    pub fn is_mixed(&self) -> bool {
+        self.atomics.iter().any(|atomic| matches!(atomic, AtomicView::Mixed(_)))
+    }
+
+    /// Returns the literal string values of the union, or `None` if any member is not a literal string.
+    #[must_use]
+    pub fn literal_strings(&self) -> Option<Vec<&[u8]>> {
+        self.atomics
+            .iter()
+            .map(|atomic| match atomic {
+                AtomicView::Scalar(ScalarView::String(StringView {
+                    literal: Some(StringLiteral::Value(value)),
+                    ..
+                })) => Some(value.as_slice()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns the literal integer values of the union, or `None` if any member is not a literal integer.
+    #[must_use]
+    pub fn literal_integers(&self) -> Option<Vec<i64>> {
+        self.atomics
+            .iter()
+            .map(|atomic| match atomic {
+                AtomicView::Scalar(ScalarView::Integer(IntegerView::Literal(value))) => Some(*value),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns the class names of the named objects of the union.
+    pub fn object_names(&self) -> impl Iterator<Item = &[u8]> {
+        self.atomics.iter().filter_map(|atomic| match atomic {
+            AtomicView::Object(object) => match object.as_ref() {
+                ObjectView::Named { name, .. } | ObjectView::Enum { name, .. } => Some(name.as_slice()),
+                _ => None,
+            },
+            _ => None,
+        })
+    }
+}
+
+/// A type returned by an analyzer provider.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Type {
+    /// A type inferred by Mago, returned unchanged.
+    Reference(u32),
+    Mixed,
+    Never,
+    Null,
+    Void,
+    Bool,
+    True,
+    False,
+    Int,
+    NonNegativeInt,
+    LiteralInt(i64),
+    Float,
+    String,
+    NonEmptyString,
+    LiteralString(Vec<u8>),
+    Object,
+    NamedObject {
+        name: Vec<u8>,
+        type_parameters: Vec<Type>,
+    },
+    Array {
+        key: Box<Type>,
+        value: Box<Type>,
+    },
+    List(Box<Type>),
+    Union(Vec<Type>),
+}
+
+impl Type {
+    /// Returns an inferred type unchanged.
+    #[must_use]
+    pub const fn of(view: &TypeView) -> Self {
+        Self::Reference(view.handle)
+    }
+
+    #[must_use]
+    pub fn named_object(name: impl Into<Vec<u8>>) -> Self {
+        Self::NamedObject { name: name.into(), type_parameters: vec![] }
+    }
+
+    #[must_use]
+    pub fn generic_object(name: impl Into<Vec<u8>>, type_parameters: Vec<Type>) -> Self {
+        Self::NamedObject { name: name.into(), type_parameters }
+    }
+
+    #[must_use]
+    pub fn literal_string(value: impl Into<Vec<u8>>) -> Self {
+        Self::LiteralString(value.into())
+    }
+
+    #[must_use]
+    pub fn array(key: Type, value: Type) -> Self {
+        Self::Array { key: Box::new(key), value: Box::new(value) }
+    }
+
+    #[must_use]
+    pub fn list(value: Type) -> Self {
+        Self::List(Box::new(value))
+    }
+
+    #[must_use]
+    pub fn nullable(self) -> Self {
+        match self {
+            Self::Union(mut members) => {
+                members.push(Self::Null);
+                Self::Union(members)
+            }
+            other => Self::Union(vec![other, Self::Null]),
+        }
+    }
+
+    pub(crate) fn write_to(&self, writer: &mut PayloadWriter) -> Result<(), RequestError> {
+        self.write_with_depth(writer, 0)
+    }
+
+    fn write_with_depth(&self, writer: &mut PayloadWriter, depth: usize) -> Result<(), RequestError> {
+        if depth >= MAXIMUM_TYPE_DEPTH {
+            return Err(RequestError::new("provider type exceeds the maximum nesting depth"));
+        }
+
+        match self {
+            Self::Reference(handle) => {
+                writer.write_u8(TYPE_REFERENCE);
+                writer.write_u32(*handle);
+            }
+            Self::Mixed => writer.write_u8(TYPE_MIXED),
+            Self::Never => writer.write_u8(TYPE_NEVER),
+            Self::Null => writer.write_u8(TYPE_NULL),
+            Self::Void => writer.write_u8(TYPE_VOID),
+            Self::Bool => writer.write_u8(TYPE_BOOL),
+            Self::True => writer.write_u8(TYPE_TRUE),
+            Self::False => writer.write_u8(TYPE_FALSE),
+            Self::Int => writer.write_u8(TYPE_INT),
+            Self::NonNegativeInt => writer.write_u8(TYPE_NON_NEGATIVE_INT),
+            Self::LiteralInt(value) => {
+                writer.write_u8(TYPE_LITERAL_INT);
+                writer.write_u64(*value as u64);
+            }
+            Self::Float => writer.write_u8(TYPE_FLOAT),
+            Self::String => writer.write_u8(TYPE_STRING),
+            Self::NonEmptyString => writer.write_u8(TYPE_NON_EMPTY_STRING),
+            Self::LiteralString(value) => {
+                writer.write_u8(TYPE_LITERAL_STRING);
+                writer.write_bytes(value)?;
+            }
+            Self::Object => writer.write_u8(TYPE_OBJECT),
+            Self::NamedObject { name, type_parameters } => {
+                if name.is_empty() {
+                    return Err(RequestError::new("named object type has an empty name"));
+                }
+
+                writer.write_u8(TYPE_NAMED_OBJECT);
+                writer.write_bytes(name)?;
+                writer.write_length(type_parameters.len())?;
+                for parameter in type_parameters {
+                    parameter.write_with_depth(writer, depth + 1)?;
+                }
+            }
+            Self::Array { key, value } => {
+                writer.write_u8(TYPE_ARRAY);
+                key.write_with_depth(writer, depth + 1)?;
+                value.write_with_depth(writer, depth + 1)?;
+            }
+            Self::List(value) => {
+                writer.write_u8(TYPE_LIST);
+                value.write_with_depth(writer, depth + 1)?;
+            }
+            Self::Union(members) => {
+                if members.is_empty() {
+                    return Err(RequestError::new("union type contains no members"));
+                }
+
+                writer.write_u8(TYPE_UNION);
+                writer.write_length(members.len())?;
+                for member in members {
+                    member.write_with_depth(writer, depth + 1)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Decodes a union snapshot.
+pub(crate) fn read_type(reader: &mut PayloadReader<'_>) -> Result<TypeView, RequestError> {
+    read_union(reader, 0)
+}
+
+fn read_union(reader: &mut PayloadReader<'_>, depth: usize) -> Result<TypeView, RequestError> {
+    ensure_depth(depth)?;
+    let handle = reader.read_u32("type handle")?;
+    let flags = reader.read_u16("union flags")?;
+    let atomics = read_atomics(reader, depth)?;
+
+    Ok(TypeView { handle, flags, atomics })
+}
+
+fn read_atomic(reader: &mut PayloadReader<'_>, depth: usize) -> Result<AtomicView, RequestError> {
+    ensure_depth(depth)?;
+    let depth = depth + 1;
+
+    Ok(match reader.read_u8("atomic type tag")? {
+        SNAPSHOT_SCALAR => AtomicView::Scalar(read_scalar(reader, depth)?),
+        SNAPSHOT_CALLABLE => AtomicView::Callable(Box::new(read_callable(reader, depth)?)),
+        SNAPSHOT_MIXED => {
+            let flags = reader.read_u8("mixed flags")?;
+            let truthiness = match reader.read_u8("mixed truthiness")? {
+                0 => Truthiness::Undetermined,
+                1 => Truthiness::Truthy,
+                2 => Truthiness::Falsy,
+                value => return Err(unknown("mixed truthiness", value)),
+            };
+
+            AtomicView::Mixed(MixedView {
+                isset_from_loop: flags & 1 != 0,
+                non_null: flags & (1 << 1) != 0,
+                empty: flags & (1 << 2) != 0,
+                truthiness,
+            })
+        }
+        SNAPSHOT_OBJECT => AtomicView::Object(Box::new(read_object(reader, depth)?)),
+        SNAPSHOT_ARRAY => AtomicView::Array(Box::new(read_array(reader, depth)?)),
+        SNAPSHOT_ITERABLE => AtomicView::Iterable(Box::new(IterableView {
+            key_type: read_union(reader, depth)?,
+            value_type: read_union(reader, depth)?,
+            intersections: read_optional_atomics(reader, depth)?,
+        })),
+        SNAPSHOT_RESOURCE => AtomicView::Resource(match reader.read_u8("resource state")? {
+            0 => None,
+            1 => Some(false),
+            2 => Some(true),
+            value => return Err(unknown("resource state", value)),
+        }),
+        SNAPSHOT_REFERENCE => AtomicView::Reference(Box::new(read_reference(reader, depth)?)),
+        SNAPSHOT_GENERIC_PARAMETER => AtomicView::GenericParameter(Box::new(GenericParameterView {
+            name: reader.read_bytes("generic parameter name")?.to_vec(),
+            constraint: read_union(reader, depth)?,
+            parent: read_generic_parent(reader)?,
+            intersections: read_optional_atomics(reader, depth)?,
+        })),
+        SNAPSHOT_VARIABLE => AtomicView::Variable(reader.read_bytes("variable type name")?.to_vec()),
+        SNAPSHOT_CONDITIONAL => AtomicView::Conditional(Box::new(ConditionalView {
+            subject: read_union(reader, depth)?,
+            target: read_union(reader, depth)?,
+            then: read_union(reader, depth)?,
+            otherwise: read_union(reader, depth)?,
+            negated: reader.read_bool("conditional negation")?,
+        })),
+        SNAPSHOT_DERIVED => AtomicView::Derived(Box::new(read_derived(reader, depth)?)),
+        SNAPSHOT_ALIAS => AtomicView::Alias(
+            reader.read_bytes("alias class-like")?.to_vec(),
+            reader.read_bytes("alias name")?.to_vec(),
+        ),
+        SNAPSHOT_NEVER => AtomicView::Never,
+        SNAPSHOT_NULL => AtomicView::Null,
+        SNAPSHOT_VOID => AtomicView::Void,
+        SNAPSHOT_PLACEHOLDER => AtomicView::Placeholder,
+        tag => return Err(unknown("atomic type tag", tag)),
+    })
+}
+
+fn read_scalar(reader: &mut PayloadReader<'_>, depth: usize) -> Result<ScalarView, RequestError> {
+    Ok(match reader.read_u8("scalar type tag")? {
+        1 => ScalarView::Generic,
+        2 => ScalarView::Numeric,
+        3 => ScalarView::ArrayKey,
+        4 => ScalarView::Bool(match reader.read_u8("boolean value")? {
+            0 => None,
+            1 => Some(false),
+            2 => Some(true),
+            value => return Err(unknown("boolean value", value)),
+        }),
+        5 => ScalarView::Integer(match reader.read_u8("integer type tag")? {
+            1 => IntegerView::Literal(reader.read_u64("integer literal")? as i64),
+            2 => IntegerView::From(reader.read_u64("integer lower bound")? as i64),
+            3 => IntegerView::To(reader.read_u64("integer upper bound")? as i64),
+            4 => IntegerView::Range(
+                reader.read_u64("integer lower bound")? as i64,
+                reader.read_u64("integer upper bound")? as i64,
+            ),
+            5 => IntegerView::Unspecified,
+            6 => IntegerView::UnspecifiedLiteral,
+            tag => return Err(unknown("integer type tag", tag)),
+        }),
+        6 => ScalarView::Float(match reader.read_u8("float type tag")? {
+            1 => FloatView::Float,
+            2 => FloatView::UnspecifiedLiteral,
+            3 => FloatView::Literal(f64::from_bits(reader.read_u64("float literal")?)),
+            tag => return Err(unknown("float type tag", tag)),
+        }),
+        7 => {
+            let literal = match reader.read_u8("string literal tag")? {
+                0 => None,
+                1 => Some(StringLiteral::Unspecified),
+                2 => Some(StringLiteral::Value(reader.read_bytes("string literal")?.to_vec())),
+                tag => return Err(unknown("string literal tag", tag)),
+            };
+            let flags = reader.read_u8("string flags")?;
+            let casing = match reader.read_u8("string casing")? {
+                0 => StringCasing::Unspecified,
+                1 => StringCasing::Lowercase,
+                2 => StringCasing::Uppercase,
+                value => return Err(unknown("string casing", value)),
+            };
+
+            ScalarView::String(StringView {
+                literal,
+                numeric: flags & 1 != 0,
+                truthy: flags & (1 << 1) != 0,
+                non_empty: flags & (1 << 2) != 0,
+                callable: flags & (1 << 3) != 0,
+                casing,
+            })
+        }
+        8 => ScalarView::ClassLikeString(match reader.read_u8("class-like string tag")? {
+            1 => ClassLikeStringView::Any { kind: read_class_like_kind(reader)? },
+            2 => ClassLikeStringView::Generic {
+                kind: read_class_like_kind(reader)?,
+                parameter: reader.read_bytes("class-like string parameter")?.to_vec(),
+                parent: read_generic_parent(reader)?,
+                constraint: Box::new(read_atomic(reader, depth)?),
+            },
+            3 => ClassLikeStringView::Literal { value: reader.read_bytes("class-like string literal")?.to_vec() },
+            4 => ClassLikeStringView::OfType {
+                kind: read_class_like_kind(reader)?,
+                constraint: Box::new(read_atomic(reader, depth)?),
+            },
+            tag => return Err(unknown("class-like string tag", tag)),
+        }),
+        tag => return Err(unknown("scalar type tag", tag)),
+    })
+}
+
+fn read_callable(reader: &mut PayloadReader<'_>, depth: usize) -> Result<CallableView, RequestError> {
+    Ok(match reader.read_u8("callable type tag")? {
+        1 => {
+            let is_pure = reader.read_bool("callable purity")?;
+            let is_closure = reader.read_bool("callable closure flag")?;
+            let parameter_count = reader.read_count("callable parameters", MAXIMUM_TYPE_MEMBERS)?;
+            let mut parameters = Vec::with_capacity(parameter_count);
+            for _ in 0..parameter_count {
+                let name = if reader.read_bool("callable parameter name flag")? {
+                    Some(reader.read_bytes("callable parameter name")?.to_vec())
+                } else {
+                    None
+                };
+
+                parameters.push(CallableParameterView {
+                    name,
+                    parameter_type: read_optional_union(reader, depth)?,
+                    closure_this_type: read_optional_union(reader, depth)?,
+                    by_reference: reader.read_bool("callable parameter by-reference flag")?,
+                    variadic: reader.read_bool("callable parameter variadic flag")?,
+                    has_default: reader.read_bool("callable parameter default flag")?,
+                });
+            }
+
+            let return_type = read_optional_union(reader, depth)?;
+            let source = if reader.read_bool("callable source flag")? {
+                Some(read_function_like_identifier(reader)?)
+            } else {
+                None
+            };
+
+            let constraint_count = reader.read_count("callable constraints", MAXIMUM_TYPE_MEMBERS)?;
+            let mut constraints = Vec::with_capacity(constraint_count);
+            for _ in 0..constraint_count {
+                let name_count = reader.read_count("callable constraint names", MAXIMUM_TYPE_MEMBERS)?;
+                let mut parameter_names = Vec::with_capacity(name_count);
+                for _ in 0..name_count {
+                    parameter_names.push(reader.read_bytes("callable constraint name")?.to_vec());
+                }
+
+                constraints.push(CallableConstraintView {
+                    parameter_names,
+                    input_type: read_union(reader, depth)?,
+                    parameter_type: read_union(reader, depth)?,
+                });
+            }
+
+            CallableView::Signature(CallableSignatureView {
+                is_pure,
+                is_closure,
+                parameters,
+                return_type,
+                source,
+                constraints,
+            })
+        }
+        2 => CallableView::Alias(read_function_like_identifier(reader)?),
+        tag => return Err(unknown("callable type tag", tag)),
+    })
+}
+
+fn read_object(reader: &mut PayloadReader<'_>, depth: usize) -> Result<ObjectView, RequestError> {
+    Ok(match reader.read_u8("object type tag")? {
+        1 => ObjectView::Any,
+        2 => ObjectView::Named {
+            name: reader.read_bytes("object name")?.to_vec(),
+            type_parameters: read_optional_unions(reader, depth)?,
+            variances: read_optional_variances(reader)?,
+            is_static: reader.read_bool("object static flag")?,
+            is_this: reader.read_bool("object this flag")?,
+            intersections: read_optional_atomics(reader, depth)?,
+            remapped_parameters: reader.read_bool("object remapped-parameters flag")?,
+        },
+        3 => ObjectView::Enum {
+            name: reader.read_bytes("enum name")?.to_vec(),
+            case: reader.read_optional_string("enum case")?,
+        },
+        4 => {
+            let sealed = reader.read_bool("object shape sealed flag")?;
+            let count = reader.read_count("object properties", MAXIMUM_TYPE_MEMBERS)?;
+            let mut properties = Vec::with_capacity(count);
+            for _ in 0..count {
+                properties.push((
+                    reader.read_bytes("object property name")?.to_vec(),
+                    reader.read_bool("object property optional flag")?,
+                    read_union(reader, depth)?,
+                ));
+            }
+
+            ObjectView::WithProperties { sealed, properties }
+        }
+        5 => ObjectView::HasMethod {
+            method: reader.read_bytes("object method name")?.to_vec(),
+            intersections: read_optional_atomics(reader, depth)?,
+        },
+        6 => ObjectView::HasProperty {
+            property: reader.read_bytes("object property name")?.to_vec(),
+            intersections: read_optional_atomics(reader, depth)?,
+        },
+        tag => return Err(unknown("object type tag", tag)),
+    })
+}
+
+fn read_array(reader: &mut PayloadReader<'_>, depth: usize) -> Result<ArrayView, RequestError> {
+    Ok(match reader.read_u8("array type tag")? {
+        1 => {
+            let element_type = read_union(reader, depth)?;
+            let known_elements = if reader.read_bool("known list elements flag")? {
+                let count = reader.read_count("known list elements", MAXIMUM_TYPE_MEMBERS)?;
+                let mut elements = Vec::with_capacity(count);
+                for _ in 0..count {
+                    elements.push((
+                        reader.read_u64("list element index")?,
+                        reader.read_bool("list element optional flag")?,
+                        read_union(reader, depth)?,
+                    ));
+                }
+
+                Some(elements)
+            } else {
+                None
+            };
+
+            let known_count = if reader.read_bool("known list count flag")? {
+                Some(reader.read_u64("known list count")?)
+            } else {
+                None
+            };
+
+            ArrayView::List {
+                element_type,
+                known_elements,
+                known_count,
+                non_empty: reader.read_bool("non-empty flag")?,
+            }
+        }
+        2 => {
+            let known_items = if reader.read_bool("known array items flag")? {
+                let count = reader.read_count("known array items", MAXIMUM_TYPE_MEMBERS)?;
+                let mut items = Vec::with_capacity(count);
+                for _ in 0..count {
+                    items.push((
+                        read_array_key(reader)?,
+                        reader.read_bool("array item optional flag")?,
+                        read_union(reader, depth)?,
+                    ));
+                }
+
+                Some(items)
+            } else {
+                None
+            };
+
+            let parameters = if reader.read_bool("array parameters flag")? {
+                Some((read_union(reader, depth)?, read_union(reader, depth)?))
+            } else {
+                None
+            };
+
+            ArrayView::Keyed { known_items, parameters, non_empty: reader.read_bool("non-empty flag")? }
+        }
+        tag => return Err(unknown("array type tag", tag)),
+    })
+}
+
+fn read_reference(reader: &mut PayloadReader<'_>, depth: usize) -> Result<ReferenceView, RequestError> {
+    Ok(match reader.read_u8("reference type tag")? {
+        1 => ReferenceView::Symbol {
+            name: reader.read_bytes("reference name")?.to_vec(),
+            parameters: read_optional_unions(reader, depth)?,
+            variances: read_optional_variances(reader)?,
+            intersections: read_optional_atomics(reader, depth)?,
+        },
+        2 => ReferenceView::Member {
+            class_like: reader.read_bytes("reference class-like")?.to_vec(),
+            selector: read_member_selector(reader)?,
+        },
+        3 => ReferenceView::Global { selector: read_member_selector(reader)? },
+        tag => return Err(unknown("reference type tag", tag)),
+    })
+}
+
+fn read_derived(reader: &mut PayloadReader<'_>, depth: usize) -> Result<DerivedView, RequestError> {
+    Ok(match reader.read_u8("derived type tag")? {
+        1 => DerivedView::KeyOf(read_union(reader, depth)?),
+        2 => DerivedView::ValueOf(read_union(reader, depth)?),
+        3 => {
+            let count = reader.read_count("int-mask values", MAXIMUM_TYPE_MEMBERS)?;
+            let mut values = Vec::with_capacity(count);
+            for _ in 0..count {
+                values.push(read_union(reader, depth)?);
+            }
+
+            DerivedView::IntMask(values)
+        }
+        4 => DerivedView::IntMaskOf(read_union(reader, depth)?),
+        5 => DerivedView::PropertiesOf {
+            visibility: match reader.read_u8("properties-of visibility")? {
+                0 => None,
+                1 => Some(Visibility::Public),
+                2 => Some(Visibility::Protected),
+                3 => Some(Visibility::Private),
+                value => return Err(unknown("properties-of visibility", value)),
+            },
+            target: read_union(reader, depth)?,
+        },
+        6 => DerivedView::IndexAccess { target: read_union(reader, depth)?, index: read_union(reader, depth)? },
+        7 => DerivedView::New(read_union(reader, depth)?),
+        8 => DerivedView::TemplateType {
+            object: read_union(reader, depth)?,
+            class_name: read_union(reader, depth)?,
+            template_name: read_union(reader, depth)?,
+        },
+        9 => {
+            DerivedView::Intersection { base: read_union(reader, depth)?, intersections: read_atomics(reader, depth)? }
+        }
+        tag => return Err(unknown("derived type tag", tag)),
+    })
+}
+
+fn read_atomics(reader: &mut PayloadReader<'_>, depth: usize) -> Result<Vec<AtomicView>, RequestError> {
+    let count = reader.read_count("atomic types", MAXIMUM_TYPE_MEMBERS)?;
+    let mut atomics = Vec::with_capacity(count);
+    for _ in 0..count {
+        atomics.push(read_atomic(reader, depth + 1)?);
+    }
+
+    Ok(atomics)
+}
+
+fn read_optional_atomics(
+    reader: &mut PayloadReader<'_>,
+    depth: usize,
+) -> Result<Option<Vec<AtomicView>>, RequestError> {
+    if reader.read_bool("intersection types flag")? { read_atomics(reader, depth).map(Some) } else { Ok(None) }
+}
+
+fn read_optional_union(reader: &mut PayloadReader<'_>, depth: usize) -> Result<Option<TypeView>, RequestError> {
+    if reader.read_bool("optional type flag")? { read_union(reader, depth + 1).map(Some) } else { Ok(None) }
+}
+
+fn read_optional_unions(reader: &mut PayloadReader<'_>, depth: usize) -> Result<Option<Vec<TypeView>>, RequestError> {
+    if !reader.read_bool("type parameters flag")? {
+        return Ok(None);
+    }
+
+    let count = reader.read_count("type parameters", MAXIMUM_TYPE_MEMBERS)?;
+    let mut unions = Vec::with_capacity(count);
+    for _ in 0..count {
+        unions.push(read_union(reader, depth + 1)?);
+    }
+
+    Ok(Some(unions))
+}
+
+fn read_optional_variances(reader: &mut PayloadReader<'_>) -> Result<Option<Vec<Variance>>, RequestError> {
+    if !reader.read_bool("variances flag")? {
+        return Ok(None);
+    }
+
+    let count = reader.read_count("variances", MAXIMUM_TYPE_MEMBERS)?;
+    let mut variances = Vec::with_capacity(count);
+    for _ in 0..count {
+        variances.push(match reader.read_u8("variance")? {
+            1 => Variance::Invariant,
+            2 => Variance::Covariant,
+            3 => Variance::Contravariant,
+            4 => Variance::Bivariant,
+            value => return Err(unknown("variance", value)),
+        });
+    }
+
+    Ok(Some(variances))
+}
+
+fn read_generic_parent(reader: &mut PayloadReader<'_>) -> Result<GenericParent, RequestError> {
+    Ok(match reader.read_u8("generic parent tag")? {
+        1 => GenericParent::ClassLike(reader.read_bytes("generic parent class-like")?.to_vec()),
+        2 => GenericParent::FunctionLike(
+            reader.read_bytes("generic parent function-like")?.to_vec(),
+            reader.read_bytes("generic parent member")?.to_vec(),
+        ),
+        tag => return Err(unknown("generic parent tag", tag)),
+    })
+}
+
+fn read_function_like_identifier(reader: &mut PayloadReader<'_>) -> Result<FunctionLikeIdentifier, RequestError> {
+    Ok(match reader.read_u8("function-like identifier tag")? {
+        1 => FunctionLikeIdentifier::Function(reader.read_bytes("function name")?.to_vec()),
+        2 => FunctionLikeIdentifier::Method(
+            reader.read_bytes("method class-like")?.to_vec(),
+            reader.read_bytes("method name")?.to_vec(),
+        ),
+        3 => FunctionLikeIdentifier::Closure(reader.read_bytes("closure name")?.to_vec()),
+        tag => return Err(unknown("function-like identifier tag", tag)),
+    })
+}
+
+fn read_class_like_kind(reader: &mut PayloadReader<'_>) -> Result<ClassLikeKind, RequestError> {
+    Ok(match reader.read_u8("class-like kind")? {
+        1 => ClassLikeKind::Class,
+        2 => ClassLikeKind::Interface,
+        3 => ClassLikeKind::Enum,
+        4 => ClassLikeKind::Trait,
+        value => return Err(unknown("class-like kind", value)),
+    })
+}
+
+fn read_array_key(reader: &mut PayloadReader<'_>) -> Result<ArrayKey, RequestError> {
+    Ok(match reader.read_u8("array key tag")? {
+        1 => ArrayKey::Integer(reader.read_u64("integer array key")? as i64),
+        2 => ArrayKey::String(reader.read_bytes("string array key")?.to_vec()),
+        3 => ArrayKey::ClassLikeConstant {
+            class_like: reader.read_bytes("array key class-like")?.to_vec(),
+            constant: reader.read_bytes("array key constant")?.to_vec(),
+        },
+        tag => return Err(unknown("array key tag", tag)),
+    })
+}
+
+fn read_member_selector(reader: &mut PayloadReader<'_>) -> Result<MemberSelector, RequestError> {
+    Ok(match reader.read_u8("member selector tag")? {
+        1 => MemberSelector::Wildcard,
+        2 => MemberSelector::Identifier(reader.read_bytes("member selector identifier")?.to_vec()),
+        3 => MemberSelector::StartsWith(reader.read_bytes("member selector prefix")?.to_vec()),
+        4 => MemberSelector::EndsWith(reader.read_bytes("member selector suffix")?.to_vec()),
+        tag => return Err(unknown("member selector tag", tag)),
+    })
+}
+
+fn ensure_depth(depth: usize) -> Result<(), RequestError> {
+    if depth >= MAXIMUM_TYPE_DEPTH {
+        return Err(RequestError::new("type snapshot exceeds the maximum nesting depth"));
+    }
+
+    Ok(())
+}
+
+fn unknown(field: &str, value: u8) -> RequestError {
+    RequestError::new(format!("type snapshot contains unknown {field} {value}"))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_nested_union_snapshots() {
+        let mut writer = PayloadWriter::new();
+        writer.write_u32(0);
+        writer.write_u16(UNION_POSSIBLY_UNDEFINED);
+        writer.write_u32(2);
+        writer.write_u8(5);
+        writer.write_u8(1);
+        writer.write_u32(1);
+        writer.write_u16(0);
+        writer.write_u32(1);
+        writer.write_u8(1);
+        writer.write_u8(5);
+        writer.write_u8(1);
+        writer.write_u64(-3i64 as u64);
+        writer.write_bool(false);
+        writer.write_bool(false);
+        writer.write_bool(true);
+        writer.write_u8(15);
+        let payload = writer.finish();
+
+        let mut reader = PayloadReader::new(&payload);
+        let view = read_type(&mut reader).unwrap();
+        reader.finish().unwrap();
+
+        assert_eq!(view.handle(), 0);
+        assert!(view.is_possibly_undefined());
+        assert!(view.is_nullable());
+        let AtomicView::Array(array) = &view.atomics()[0] else { panic!("expected an array") };
+        let ArrayView::List { element_type, non_empty, .. } = array.as_ref() else { panic!("expected a list") };
+        assert!(non_empty);
+        assert_eq!(element_type.handle(), 1);
+        assert_eq!(element_type.literal_integers(), Some(vec![-3]));
+    }
+
+    #[test]
+    fn rejects_unknown_atomic_tags() {
+        let mut writer = PayloadWriter::new();
+        writer.write_u32(0);
+        writer.write_u16(0);
+        writer.write_u32(1);
+        writer.write_u8(99);
+        let payload = writer.finish();
+
+        let error = read_type(&mut PayloadReader::new(&payload)).unwrap_err();
+        assert_eq!(error.0, "type snapshot contains unknown atomic type tag 99");
+    }
+
+    #[test]
+    fn encodes_provider_types() {
+        let mut writer = PayloadWriter::new();
+        Type::list(Type::named_object("Acme\\User")).nullable().write_to(&mut writer).unwrap();
+
+        let mut expected = PayloadWriter::new();
+        expected.write_u8(TYPE_UNION);
+        expected.write_u32(2);
+        expected.write_u8(TYPE_LIST);
+        expected.write_u8(TYPE_NAMED_OBJECT);
+        expected.write_bytes(b"Acme\\User").unwrap();
+        expected.write_u32(0);
+        expected.write_u8(TYPE_NULL);
+        assert_eq!(writer.finish(), expected.finish());
+        assert!(Type::Union(vec![]).write_to(&mut PayloadWriter::new()).is_err());
+    }
+}
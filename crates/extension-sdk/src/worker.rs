@@ -0,0 +1,413 @@
+//! The worker process: request dispatch and the frame event loop.
+
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::io::Read;
+use std::io::Write;
+
+use mago_extension::Frame;
+use mago_extension::ProtocolError;
+use mago_extension::WorkerRequestHandler;
+use mago_extension::protocol::FrameFlags;
+use mago_extension::protocol::FrameKind;
+use mago_extension::wire;
+
+use crate::analyzer::Analyzer;
+use crate::analyzer::Plugin;
+use crate::extension;
+use crate::extension::Extension;
+use crate::extension::RegisteredExtension;
+use crate::linter::Linter;
+use crate::linter::RegisteredRule;
+use crate::message::ANALYZER;
+use crate::message::LINTER;
+use crate::message::RequestError;
+use crate::message::WORKER;
+
+/// The default limit for the payload of one frame, matching Mago's default `maximum-payload-size`.
+pub const DEFAULT_MAXIMUM_PAYLOAD_SIZE: usize = 64 * 1024 * 1024;
+
+#[cfg(windows)]
+const INPUT_ADDRESS_ENVIRONMENT_VARIABLE: &str = "MAGO_EXTENSION_INPUT_ADDRESS";
+#[cfg(windows)]
+const INPUT_TOKEN_ENVIRONMENT_VARIABLE: &str = "MAGO_EXTENSION_INPUT_TOKEN";
+
+/// A worker process serving one or more extensions.
+///
+/// Requests are served one at a time, in the order Mago sends them. Mago
+/// starts several workers to analyze files in parallel, so each worker only
+/// needs to be single-threaded.
+pub struct Worker {
+    extensions: Vec<RegisteredExtension>,
+    rules: Vec<RegisteredRule>,
+    plugins: Vec<Plugin>,
+    linter: Linter,
+    analyzer: Analyzer,
+}
+
+impl Worker {
+    #[must_use]
+    pub fn new(extension: Extension) -> Self {
+        Self {
+            extensions: vec![],
+            rules: vec![],
+            plugins: vec![],
+            linter: Linter::default(),
+            analyzer: Analyzer::default(),
+        }
+        .with_extension(extension)
+    }
+
+    /// Serves another extension from the same process.
+    #[must_use]
+    pub fn with_extension(mut self, extension: Extension) -> Self {
+        let (extension, rules, plugins) = extension.register(self.rules.len(), self.plugins.len());
+        self.rules.extend(rules.into_iter().map(|rule| RegisteredRule { definition: rule.definition(), rule }));
+        self.plugins.extend(plugins);
+        self.extensions.push(extension);
+        self
+    }
+
+    /// Serves requests from Mago until it shuts the worker down.
+    ///
+    /// Frames are read from standard input, or on Windows from the socket Mago
+    /// advertises in the environment, and written to standard output. Nothing
+    /// else may write to standard output; use standard error for diagnostics.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the connection to Mago fails or Mago sends a malformed frame.
+    pub fn run(mut self) -> Result<(), ProtocolError> {
+        let mut output = BufWriter::new(std::io::stdout().lock());
+
+        #[cfg(windows)]
+        if let Some(input) = connect_windows_input()? {
+            return self.run_with(&mut BufReader::new(input), &mut output, DEFAULT_MAXIMUM_PAYLOAD_SIZE);
+        }
+
+        self.run_with(&mut BufReader::new(std::io::stdin().lock()), &mut output, DEFAULT_MAXIMUM_PAYLOAD_SIZE)
+    }
+
+    /// Serves requests read from `input`, writing responses to `output`, until
+    /// `input` ends or Mago sends a shutdown frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading or writing a frame fails, or a frame is malformed.
+    pub fn run_with(
+        &mut self,
+        input: &mut impl Read,
+        output: &mut impl Write,
+        maximum_payload_size: usize,
+    ) -> Result<(), ProtocolError> {
+        while let Some(frame) = Frame::read_from(input, maximum_payload_size)? {
+            match frame.kind {
+                FrameKind::Request => {}
+                FrameKind::Shutdown => return Ok(()),
+                // Requests are served to completion before the next frame is read, so a
+                // cancellation always refers to a request that has already been answered.
+                // This SDK sends no nested requests, so it never expects a response.
+                FrameKind::Cancel | FrameKind::Response | FrameKind::Notification => continue,
+            }
+
+            if frame.id == 0 {
+                continue;
+            }
+
+            let response = if frame.flags != FrameFlags::empty() || frame.parent_id != 0 {
+                Frame::error(frame.id, 0, b"top-level requests must not carry flags or a parent".to_vec())
+            } else {
+                match self.handle(&frame) {
+                    Ok(payload) => Frame::response(frame.id, 0, payload),
+                    Err(payload) => Frame::error(frame.id, 0, payload),
+                }
+            };
+
+            response.write_to(output, maximum_payload_size)?;
+            output.flush()?;
+        }
+
+        Ok(())
+    }
+
+    fn dispatch(&mut self, payload: &[u8]) -> Result<Vec<u8>, RequestError> {
+        if LINTER.matches(payload) {
+            let (kind, reader) = LINTER.reader(payload)?;
+
+            return match kind {
+                wire::linter::DESCRIBE_REQUEST => self.linter.describe(reader, &self.extensions, &self.rules),
+                wire::linter::LINT_FILE_REQUEST => self.linter.lint_file(reader, &mut self.rules),
+                kind => Err(RequestError::new(format!("unknown linter message kind {kind}"))),
+            };
+        }
+
+        if ANALYZER.matches(payload) {
+            let (kind, reader) = ANALYZER.reader(payload)?;
+
+            return match kind {
+                wire::analyzer::DESCRIBE_REQUEST => self.analyzer.describe(reader, &self.extensions, &self.plugins),
+                wire::analyzer::RETURN_TYPE_REQUEST => self.analyzer.return_type(reader, &mut self.plugins),
+                kind => Err(RequestError::new(format!(
+                    "the Rust extension SDK does not serve analyzer message kind {kind}"
+                ))),
+            };
+        }
+
+        if WORKER.matches(payload) {
+            let (kind, reader) = WORKER.reader(payload)?;
+
+            return match kind {
+                wire::worker::COLLECT_REQUEST => extension::collect(reader, &mut self.extensions),
+                wire::worker::REDUCE_REQUEST => extension::reduce(reader, &mut self.extensions),
+                kind => Err(RequestError::new(format!("unknown worker management message kind {kind}"))),
+            };
+        }
+
+        Err(RequestError::new("Unknown Mago extension capability protocol."))
+    }
+}
+
+/// Serves one request in-process, which is convenient for testing rules and providers.
+impl WorkerRequestHandler for Worker {
+    fn handle(&mut self, request: &Frame) -> Result<Vec<u8>, Vec<u8>> {
+        self.dispatch(&request.payload).map_err(|error| error.0.into_bytes())
+    }
+}
+
+impl std::fmt::Debug for Worker {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter
+            .debug_struct("Worker")
+            .field("extensions", &self.extensions.len())
+            .field("rules", &self.rules.len())
+            .field("plugins", &self.plugins)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Connects to the authenticated input socket Mago uses on Windows, if one is advertised.
+#[cfg(windows)]
+fn connect_windows_input() -> Result<Option<std::net::TcpStream>, ProtocolError> {
+    let (Ok(address), Ok(token)) =
+        (std::env::var(INPUT_ADDRESS_ENVIRONMENT_VARIABLE), std::env::var(INPUT_TOKEN_ENVIRONMENT_VARIABLE))
+    else {
+        return Ok(None);
+    };
+
+    let mut stream = std::net::TcpStream::connect(address)?;
+    stream.write_all(token.as_bytes())?;
+    stream.flush()?;
+    stream.shutdown(std::net::Shutdown::Write)?;
+
+    Ok(Some(stream))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use mago_extension::source::NO_NODE;
+    use mago_extension::source::write_node_kind_table;
+    use mago_syntax::cst::NodeKind;
+
+    use super::*;
+    use crate::Annotation;
+    use crate::FunctionReturnTypeProvider;
+    use crate::FunctionTarget;
+    use crate::Issue;
+    use crate::LintContext;
+    use crate::ReturnTypeContext;
+    use crate::Rule;
+    use crate::RuleDefinition;
+    use crate::Type;
+    use crate::WorkerReducer;
+
+    struct NoGoto;
+
+    impl Rule for NoGoto {
+        fn definition(&self) -> RuleDefinition {
+            RuleDefinition::new("no-goto", "No goto", "Disallows goto.").with_target(NodeKind::Goto)
+        }
+
+        fn lint(&mut self, context: &mut LintContext<'_>) {
+            let node = context.node();
+            let message = format!("Do not use `{}`.", String::from_utf8_lossy(node.text()));
+
+            context.report(Issue::new(message).with_annotation(Annotation::primary(node.span())));
+        }
+    }
+
+    struct ServiceReturnType;
+
+    impl FunctionReturnTypeProvider for ServiceReturnType {
+        fn targets(&self) -> Vec<FunctionTarget> {
+            vec![FunctionTarget::exact("service")]
+        }
+
+        fn return_type(&mut self, context: &ReturnTypeContext<'_>) -> Option<Type> {
+            let argument = context.invocation().argument(0, &["$id"])?;
+            let names = argument.argument_type.as_ref()?.literal_strings()?;
+
+            (names == [b"logger".as_slice()]).then(|| Type::named_object("Acme\\Logger"))
+        }
+    }
+
+    #[derive(Default)]
+    struct Recorder {
+        reduced: Vec<Vec<u8>>,
+    }
+
+    impl WorkerReducer for Recorder {
+        fn collect(&mut self) -> Vec<u8> {
+            b"state".to_vec()
+        }
+
+        fn reduce(&mut self, payloads: &[&[u8]]) -> Result<(), String> {
+            self.reduced = payloads.iter().map(|payload| payload.to_vec()).collect();
+
+            Ok(())
+        }
+    }
+
+    fn request(worker: &mut Worker, payload: Vec<u8>) -> Result<Vec<u8>, String> {
+        worker.handle(&Frame::request(1, payload)).map_err(|error| String::from_utf8(error).unwrap())
+    }
+
+    fn describe_request(capability: crate::message::Capability) -> Vec<u8> {
+        let mut writer = capability.writer(1);
+        writer.write_u32(80400);
+        write_node_kind_table(&mut writer);
+        writer.finish()
+    }
+
+    #[test]
+    fn describes_and_runs_linter_rules() {
+        let mut worker = Worker::new(Extension::new("acme/style", "Acme style", "1.0.0").with_rule(NoGoto));
+
+        let description = request(&mut worker, describe_request(LINTER)).unwrap();
+        let (kind, mut reader) = LINTER.reader(&description).unwrap();
+        assert_eq!(kind, wire::linter::DESCRIBE_RESPONSE);
+        assert_eq!(reader.read_u32("extensions").unwrap(), 1);
+        assert_eq!(reader.read_str("identifier").unwrap(), "acme/style");
+
+        let mut writer = LINTER.writer(wire::linter::LINT_FILE_REQUEST);
+        writer.write_bytes(b"example.php").unwrap();
+        writer.write_bytes(b"goto end;").unwrap();
+        writer.write_u16(1);
+        writer.write_u16(0);
+        writer.write_length(1).unwrap();
+        writer.write_u32(0);
+        writer.write_length(1).unwrap();
+        writer.write_u8(NodeKind::Goto as u8);
+        for value in [0, 9, NO_NODE, NO_NODE, NO_NODE] {
+            writer.write_u32(value);
+        }
+        writer.write_length(0).unwrap();
+        writer.write_length(0).unwrap();
+        writer.write_length(0).unwrap();
+
+        let response = request(&mut worker, writer.finish()).unwrap();
+        let (kind, mut reader) = LINTER.reader(&response).unwrap();
+        assert_eq!(kind, wire::linter::LINT_FILE_RESPONSE);
+        assert_eq!(reader.read_u32("issues").unwrap(), 1);
+        assert_eq!(reader.read_u16("rule").unwrap(), 0);
+        assert_eq!(reader.read_str("message").unwrap(), "Do not use `goto end;`.");
+    }
+
+    #[test]
+    fn rejects_lint_requests_before_registration() {
+        let mut worker = Worker::new(Extension::new("acme/style", "Acme style", "1.0.0").with_rule(NoGoto));
+        let mut writer = LINTER.writer(wire::linter::LINT_FILE_REQUEST);
+        writer.write_bytes(b"example.php").unwrap();
+
+        let error = request(&mut worker, writer.finish()).unwrap_err();
+        assert!(error.contains("before describing"));
+    }
+
+    #[test]
+    fn answers_return_type_requests_from_argument_types() {
+        let plugin = Plugin::new("acme-container", "Acme container", "Container return types.")
+            .with_function_return_type_provider(ServiceReturnType);
+        let mut worker = Worker::new(Extension::new("acme/container", "Acme container", "1.0.0").with_plugin(plugin));
+        request(&mut worker, describe_request(ANALYZER)).unwrap();
+
+        let mut writer = ANALYZER.writer(wire::analyzer::RETURN_TYPE_REQUEST);
+        writer.write_u64(1);
+        writer.write_u8(wire::analyzer::INVOCATION_FUNCTION);
+        writer.write_u16(1);
+        writer.write_u16(0);
+        writer.write_bytes(b"service").unwrap();
+        writer.write_u32(0);
+        writer.write_u32(17);
+        writer.write_u16(1);
+        writer.write_optional_string(None).unwrap();
+        writer.write_bool(false);
+        writer.write_bool(false);
+        writer.write_u32(8);
+        writer.write_u32(16);
+        writer.write_bytes(b"'logger'").unwrap();
+        writer.write_bool(true);
+        writer.write_bytes(b"'logger'").unwrap();
+        writer.write_u32(0);
+        writer.write_u16(0);
+        writer.write_length(1).unwrap();
+        writer.write_u8(wire::analyzer::SNAPSHOT_SCALAR);
+        writer.write_u8(7);
+        writer.write_u8(2);
+        writer.write_bytes(b"logger").unwrap();
+        writer.write_u8(0);
+        writer.write_u8(0);
+
+        let response = request(&mut worker, writer.finish()).unwrap();
+        let (kind, mut reader) = ANALYZER.reader(&response).unwrap();
+        assert_eq!(kind, wire::analyzer::RETURN_TYPE_RESPONSE);
+        assert!(reader.read_bool("handled").unwrap());
+        assert_eq!(reader.read_u8("tag").unwrap(), wire::analyzer::TYPE_NAMED_OBJECT);
+        assert_eq!(reader.read_bytes("name").unwrap(), b"Acme\\Logger");
+        assert_eq!(reader.read_u32("parameters").unwrap(), 0);
+        reader.finish().unwrap();
+    }
+
+    #[test]
+    fn collects_and_reduces_worker_state() {
+        let recorder = Rc::new(RefCell::new(Recorder::default()));
+        let mut worker =
+            Worker::new(Extension::new("acme/metrics", "Acme metrics", "1.0.0").with_reducer(Rc::clone(&recorder)));
+
+        let collected = request(&mut worker, WORKER.writer(wire::worker::COLLECT_REQUEST).finish()).unwrap();
+        let (kind, mut reader) = WORKER.reader(&collected).unwrap();
+        assert_eq!(kind, wire::worker::COLLECT_RESPONSE);
+        assert_eq!(reader.read_u32("reducers").unwrap(), 1);
+        assert_eq!(reader.read_u32("extension").unwrap(), 0);
+        assert_eq!(reader.read_bytes("payload").unwrap(), b"state");
+
+        let mut writer = WORKER.writer(wire::worker::REDUCE_REQUEST);
+        writer.write_length(2).unwrap();
+        writer.write_bytes(&collected).unwrap();
+        writer.write_bytes(&collected).unwrap();
+        request(&mut worker, writer.finish()).unwrap();
+
+        assert_eq!(recorder.borrow().reduced, vec![b"state".to_vec(), b"state".to_vec()]);
+    }
+
+    #[test]
+    fn serves_frames_until_shutdown() {
+        let mut worker = Worker::new(Extension::new("acme/style", "Acme style", "1.0.0"));
+        let mut input = Vec::new();
+        Frame::request(7, b"????".to_vec()).write_to(&mut input, 1024).unwrap();
+        Frame::shutdown().write_to(&mut input, 1024).unwrap();
+        Frame::request(8, b"????".to_vec()).write_to(&mut input, 1024).unwrap();
+
+        let mut output = Vec::new();
+        worker.run_with(&mut input.as_slice(), &mut output, 1024).unwrap();
+
+        let mut output = output.as_slice();
+        let response = Frame::read_from(&mut output, 1024).unwrap().unwrap();
+        assert_eq!(response.id, 7);
+        assert!(response.flags.contains(FrameFlags::ERROR));
+        assert_eq!(response.payload, b"Unknown Mago extension capability protocol.");
+        assert!(Frame::read_from(&mut output, 1024).unwrap().is_none());
+    }
+}
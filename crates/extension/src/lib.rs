@@ -11,6 +11,7 @@ pub mod payload;
 pub mod pool;
 pub mod protocol;
 pub mod source;
+pub mod wire;
 pub mod worker;
 
 mod reduction;
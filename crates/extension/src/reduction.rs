@@ -4,15 +4,15 @@ use crate::PayloadReader;
 use crate::PayloadWriter;
 use crate::ProtocolError;
 use crate::WorkerError;
+use crate::wire::HEADER_LENGTH;
+use crate::wire::worker::COLLECT_REQUEST;
+use crate::wire::worker::COLLECT_RESPONSE;
+use crate::wire::worker::MAGIC;
+use crate::wire::worker::MAJOR;
+use crate::wire::worker::MINOR;
+use crate::wire::worker::REDUCE_REQUEST;
+use crate::wire::worker::REDUCE_RESPONSE;
 
-const MAGIC: [u8; 4] = *b"MEXT";
-const MAJOR: u16 = 1;
-const MINOR: u16 = 0;
-const HEADER_LENGTH: usize = 12;
-const COLLECT_REQUEST: u16 = 1;
-const REDUCE_REQUEST: u16 = 2;
-const COLLECT_RESPONSE: u16 = 0x8001;
-const REDUCE_RESPONSE: u16 = 0x8002;
 const MAXIMUM_REDUCERS: usize = 0x0000_4000;
 
 pub(crate) fn collect_request() -> Vec<u8> {
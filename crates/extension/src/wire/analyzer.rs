@@ -0,0 +1,111 @@
+//! Tags of the `MANA` analyzer protocol.
+
+/// Magic identifying analyzer messages.
+pub const MAGIC: [u8; 4] = *b"MANA";
+
+/// Major version of the analyzer protocol; workers must match it exactly.
+pub const MAJOR: u16 = 1;
+
+/// Minor version of the analyzer protocol.
+pub const MINOR: u16 = 0;
+
+pub const DESCRIBE_REQUEST: u16 = 1;
+pub const RETURN_TYPE_REQUEST: u16 = 2;
+pub const TYPE_COMPARISON_REQUEST: u16 = 3;
+pub const INITIALIZE_REQUEST: u16 = 11;
+pub const CALLABLE_SIGNATURE_REQUEST: u16 = 12;
+pub const PROPERTY_TYPE_REQUEST: u16 = 13;
+pub const PROPERTY_INITIALIZATION_REQUEST: u16 = 14;
+pub const ISSUE_FILTER_REQUEST: u16 = 15;
+pub const TYPE_COMPARISON_BATCH_REQUEST: u16 = 16;
+pub const CLASS_INITIALIZER_REQUEST: u16 = 17;
+pub const ASSERTION_REQUEST: u16 = 18;
+pub const CODEBASE_SCAN_REQUEST: u16 = 19;
+pub const DESCRIBE_RESPONSE: u16 = 0x8001;
+pub const RETURN_TYPE_RESPONSE: u16 = 0x8002;
+pub const TYPE_COMPARISON_RESPONSE: u16 = 0x8003;
+pub const INITIALIZE_RESPONSE: u16 = 0x800B;
+pub const CALLABLE_SIGNATURE_RESPONSE: u16 = 0x800C;
+pub const PROPERTY_TYPE_RESPONSE: u16 = 0x800D;
+pub const PROPERTY_INITIALIZATION_RESPONSE: u16 = 0x800E;
+pub const ISSUE_FILTER_RESPONSE: u16 = 0x800F;
+pub const TYPE_COMPARISON_BATCH_RESPONSE: u16 = 0x8010;
+pub const CLASS_INITIALIZER_RESPONSE: u16 = 0x8011;
+pub const ASSERTION_RESPONSE: u16 = 0x8012;
+pub const CODEBASE_SCAN_RESPONSE: u16 = 0x8013;
+
+// How a provider target matches a function or class name.
+pub const TARGET_EXACT: u8 = 1;
+pub const TARGET_PREFIX: u8 = 2;
+pub const TARGET_NAMESPACE: u8 = 3;
+
+// How the invocation a provider request describes was called.
+pub const INVOCATION_FUNCTION: u8 = 1;
+pub const INVOCATION_INSTANCE_METHOD: u8 = 2;
+pub const INVOCATION_STATIC_METHOD: u8 = 3;
+
+// Capability flags a worker advertises for each provider.
+pub const PROVIDER_CALLABLE_SIGNATURE: u8 = 1;
+pub const PROVIDER_OVERRIDES_DECLARED_SIGNATURE: u8 = 1 << 1;
+pub const PROVIDER_UNDECLARED_RETURN_TYPE_ONLY: u8 = 1 << 2;
+pub const PROVIDER_MEMOIZED: u8 = 1 << 3;
+
+// Tags of the types a worker returns.
+pub const TYPE_REFERENCE: u8 = 0;
+pub const TYPE_MIXED: u8 = 1;
+pub const TYPE_NEVER: u8 = 2;
+pub const TYPE_NULL: u8 = 3;
+pub const TYPE_VOID: u8 = 4;
+pub const TYPE_BOOL: u8 = 5;
+pub const TYPE_TRUE: u8 = 6;
+pub const TYPE_FALSE: u8 = 7;
+pub const TYPE_INT: u8 = 8;
+pub const TYPE_FLOAT: u8 = 9;
+pub const TYPE_STRING: u8 = 10;
+pub const TYPE_LITERAL_STRING: u8 = 11;
+pub const TYPE_OBJECT: u8 = 12;
+pub const TYPE_NAMED_OBJECT: u8 = 13;
+pub const TYPE_ARRAY: u8 = 14;
+pub const TYPE_LIST: u8 = 15;
+pub const TYPE_UNION: u8 = 16;
+pub const TYPE_NON_NEGATIVE_INT: u8 = 17;
+pub const TYPE_NON_EMPTY_STRING: u8 = 18;
+pub const TYPE_LITERAL_INT: u8 = 19;
+pub const TYPE_COMPLETE: u8 = 20;
+
+// Operations of a nested type comparison request.
+pub const TYPE_COMPARISON_EQUAL: u8 = 1;
+pub const TYPE_COMPARISON_CONTAINED_BY: u8 = 2;
+pub const TYPE_COMPARISON_CAN_BE_IDENTICAL: u8 = 3;
+
+// Tags of the atomic types in a type snapshot.
+pub const SNAPSHOT_SCALAR: u8 = 1;
+pub const SNAPSHOT_CALLABLE: u8 = 2;
+pub const SNAPSHOT_MIXED: u8 = 3;
+pub const SNAPSHOT_OBJECT: u8 = 4;
+pub const SNAPSHOT_ARRAY: u8 = 5;
+pub const SNAPSHOT_ITERABLE: u8 = 6;
+pub const SNAPSHOT_RESOURCE: u8 = 7;
+pub const SNAPSHOT_REFERENCE: u8 = 8;
+pub const SNAPSHOT_GENERIC_PARAMETER: u8 = 9;
+pub const SNAPSHOT_VARIABLE: u8 = 10;
+pub const SNAPSHOT_CONDITIONAL: u8 = 11;
+pub const SNAPSHOT_DERIVED: u8 = 12;
+pub const SNAPSHOT_ALIAS: u8 = 13;
+pub const SNAPSHOT_NEVER: u8 = 14;
+pub const SNAPSHOT_NULL: u8 = 15;
+pub const SNAPSHOT_VOID: u8 = 16;
+pub const SNAPSHOT_PLACEHOLDER: u8 = 17;
+
+// Flags of a union in a type snapshot or a complete type.
+pub const UNION_HAD_TEMPLATE: u16 = 1;
+pub const UNION_BY_REFERENCE: u16 = 1 << 1;
+pub const UNION_REFERENCE_FREE: u16 = 1 << 2;
+pub const UNION_POSSIBLY_UNDEFINED_FROM_TRY: u16 = 1 << 3;
+pub const UNION_POSSIBLY_UNDEFINED: u16 = 1 << 4;
+pub const UNION_IGNORE_NULLABLE_ISSUES: u16 = 1 << 5;
+pub const UNION_IGNORE_FALSABLE_ISSUES: u16 = 1 << 6;
+pub const UNION_FROM_TEMPLATE_DEFAULT: u16 = 1 << 7;
+pub const UNION_POPULATED: u16 = 1 << 8;
+pub const UNION_NULLSAFE_NULL: u16 = 1 << 9;
+pub const UNION_FROM_UNSPECIFIED_TEMPLATE: u16 = 1 << 10;
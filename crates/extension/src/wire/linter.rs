@@ -0,0 +1,15 @@
+//! Tags of the `MLNT` linter protocol.
+
+/// Magic identifying linter messages.
+pub const MAGIC: [u8; 4] = *b"MLNT";
+
+/// Major version of the linter protocol; workers must match it exactly.
+pub const MAJOR: u16 = 1;
+
+/// Minor version of the linter protocol.
+pub const MINOR: u16 = 0;
+
+pub const DESCRIBE_REQUEST: u16 = 1;
+pub const LINT_FILE_REQUEST: u16 = 2;
+pub const DESCRIBE_RESPONSE: u16 = 0x8001;
+pub const LINT_FILE_RESPONSE: u16 = 0x8002;
@@ -0,0 +1,14 @@
+//! Message tags shared by Mago's extension hosts and the Rust extension SDK.
+//!
+//! Every capability message starts with a fixed header: the capability magic,
+//! protocol major, protocol minor, message kind, and a reserved zero, all
+//! big-endian. Requests use kinds from `1`; a response echoes its request's
+//! kind with the high bit set. The constants of each capability live in its
+//! own module, so both ends of a protocol read their tags from one place.
+
+pub mod analyzer;
+pub mod linter;
+pub mod worker;
+
+/// Length of the fixed capability message header.
+pub const HEADER_LENGTH: usize = 12;
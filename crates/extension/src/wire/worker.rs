@@ -0,0 +1,15 @@
+//! Tags of the `MEXT` worker-management protocol.
+
+/// Magic identifying worker-management messages.
+pub const MAGIC: [u8; 4] = *b"MEXT";
+
+/// Major version of the worker-management protocol; workers must match it exactly.
+pub const MAJOR: u16 = 1;
+
+/// Minor version of the worker-management protocol.
+pub const MINOR: u16 = 0;
+
+pub const COLLECT_REQUEST: u16 = 1;
+pub const REDUCE_REQUEST: u16 = 2;
+pub const COLLECT_RESPONSE: u16 = 0x8001;
+pub const REDUCE_RESPONSE: u16 = 0x8002;
@@ -0,0 +1,922 @@
+use mago_allocator::Arena;
+use mago_allocator::vec::Vec;
+use mago_allocator::vec_in;
+use mago_phpdoc_syntax::PHPDocParser;
+use mago_phpdoc_syntax::cst::Tag;
+use mago_phpdoc_syntax::cst::TagValue;
+use mago_phpdoc_syntax::cst::Variable;
+use mago_phpdoc_syntax::parse_type;
+use mago_span::HasSpan;
+use mago_span::Position;
+use mago_span::Span;
+
+use crate::document::BreakMode;
+use crate::document::Document;
+use crate::document::Group;
+use crate::document::IfBreak;
+use crate::document::Line;
+use crate::internal::FormatterState;
+use crate::internal::comment::Comment;
+use crate::internal::utils::string_width;
+
+/// The prefix of a wrapped line of description text.
+const TEXT_CONTINUATION_PREFIX: &[u8] = b" * ";
+
+/// The prefix of a wrapped line of a tag description, indented past the tag itself.
+const TAG_CONTINUATION_PREFIX: &[u8] = b" *   ";
+
+/// The groups docblock tags are sorted into, in the order they are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum TagGroup {
+    TypeAlias,
+    Template,
+    Inheritance,
+    Member,
+    Var,
+    Param,
+    Assert,
+    Return,
+    Throws,
+    Other,
+}
+
+/// A docblock line, without its leading ` * `.
+#[derive(Debug, Clone, Copy)]
+struct DocblockLine<'arena> {
+    offset: u32,
+    content: &'arena [u8],
+}
+
+/// The first line of a tag, split into the columns that are aligned across tags.
+#[derive(Debug, Clone, Copy)]
+struct TagColumns<'arena> {
+    name: &'arena [u8],
+    r#type: Option<&'arena [u8]>,
+    variable: Option<&'arena [u8]>,
+    description: &'arena [u8],
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct ColumnWidths {
+    name: usize,
+    r#type: usize,
+    variable: usize,
+}
+
+#[derive(Debug)]
+enum TagHead<'arena> {
+    Columns(TagColumns<'arena>),
+    /// A first line that is printed as written, e.g. for tags whose type spans several lines.
+    Verbatim(&'arena [u8]),
+}
+
+#[derive(Debug)]
+struct TagBlock<'arena, A>
+where
+    A: Arena,
+{
+    group: TagGroup,
+    head: TagHead<'arena>,
+    continuation: Vec<'arena, &'arena [u8], A>,
+}
+
+#[derive(Debug)]
+enum Block<'arena, A>
+where
+    A: Arena,
+{
+    Blank,
+    /// Consecutive lines of plain text.
+    Paragraph(Vec<'arena, &'arena [u8], A>),
+    /// A line printed as written: code, list items, tables, and indented text.
+    Verbatim(&'arena [u8]),
+    Tag(TagBlock<'arena, A>),
+}
+
+impl<'arena, A> FormatterState<'_, 'arena, A>
+where
+    A: Arena,
+{
+    /// Prints a multi-line docblock according to the docblock settings.
+    ///
+    /// Returns `None` when none of the docblock settings is enabled, or when the docblock
+    /// cannot be parsed, in which case the comment is printed as written.
+    pub(super) fn print_docblock(&self, comment: Comment) -> Option<Document<'arena, A>> {
+        let settings = &self.settings;
+        if !settings.sort_docblock_tags
+            && !settings.align_docblock_tags
+            && !settings.normalize_docblock_types
+            && !settings.normalize_docblock_blank_lines
+            && !settings.wrap_docblock_text
+        {
+            return None;
+        }
+
+        let content = self.get_source_slice(comment.start, comment.end);
+        if !content.starts_with(b"/**") || content.starts_with(b"/***") || content.len() < 5 {
+            return None;
+        }
+
+        let span = Span::new(self.file.id, Position::new(comment.start), Position::new(comment.end));
+        let document = PHPDocParser::parse_with_span(self.arena, content, span);
+        if document.has_errors() {
+            return None;
+        }
+
+        let tags = document.tags().collect::<std::vec::Vec<_>>();
+        let mut blocks = self.docblock_blocks(comment, &tags);
+        if blocks.iter().all(|block| matches!(block, Block::Blank)) {
+            return None;
+        }
+
+        if settings.sort_docblock_tags {
+            blocks = self.sort_docblock_tags(blocks);
+        }
+
+        if settings.normalize_docblock_blank_lines {
+            blocks = self.normalize_docblock_blank_lines(blocks);
+        }
+
+        Some(self.print_docblock_blocks(&blocks))
+    }
+
+    /// Splits the docblock into lines, removing the comment markers and the leading asterisks.
+    fn docblock_lines(&self, comment: Comment) -> Vec<'arena, DocblockLine<'arena>, A> {
+        let source = self.source_text;
+        let end = comment.end as usize - 2;
+
+        let mut lines = Vec::new_in(self.arena);
+        let mut line_start = comment.start as usize + 3;
+        let mut is_first = true;
+        loop {
+            let line_end = memchr::memchr(b'\n', &source[line_start..end]).map_or(end, |index| line_start + index);
+            let is_last = line_end == end;
+
+            let mut start = line_start;
+            while start < line_end && matches!(source[start], b' ' | b'\t') {
+                start += 1;
+            }
+
+            if !is_first && start < line_end && source[start] == b'*' {
+                start += 1;
+                if start < line_end && source[start] == b' ' {
+                    start += 1;
+                }
+            }
+
+            let content = source[start..line_end].trim_ascii_end();
+            if !(is_first || is_last) || !content.is_empty() {
+                lines.push(DocblockLine { offset: start as u32, content });
+            }
+
+            if is_last {
+                break;
+            }
+
+            line_start = line_end + 1;
+            is_first = false;
+        }
+
+        lines
+    }
+
+    fn docblock_blocks(&self, comment: Comment, tags: &[&'arena Tag<'arena>]) -> Vec<'arena, Block<'arena, A>, A> {
+        let mut blocks: Vec<'arena, Block<'arena, A>, A> = Vec::new_in(self.arena);
+        let mut in_code_fence = false;
+        let mut in_tag = false;
+
+        for line in self.docblock_lines(comment) {
+            let content = line.content;
+
+            if in_code_fence || is_code_fence(content) {
+                if is_code_fence(content) {
+                    in_code_fence = !in_code_fence;
+                }
+
+                in_tag = false;
+                blocks.push(Block::Verbatim(content));
+                continue;
+            }
+
+            if content.is_empty() {
+                in_tag = false;
+                blocks.push(Block::Blank);
+                continue;
+            }
+
+            if content.starts_with(b"@")
+                && let Some(tag) = tags.iter().find(|tag| tag.at.start.offset == line.offset)
+            {
+                let columns = if self.settings.align_docblock_tags
+                    || self.settings.normalize_docblock_types
+                    || self.settings.wrap_docblock_text
+                {
+                    self.docblock_tag_columns(tag, line)
+                } else {
+                    None
+                };
+
+                in_tag = true;
+                blocks.push(Block::Tag(TagBlock {
+                    group: tag_group(&tag.value),
+                    head: columns.map_or(TagHead::Verbatim(content), TagHead::Columns),
+                    continuation: Vec::new_in(self.arena),
+                }));
+                continue;
+            }
+
+            if in_tag && let Some(Block::Tag(tag)) = blocks.last_mut() {
+                tag.continuation.push(content);
+                continue;
+            }
+
+            if is_preformatted(content) {
+                blocks.push(Block::Verbatim(content));
+            } else if let Some(Block::Paragraph(lines)) = blocks.last_mut() {
+                lines.push(content);
+            } else {
+                blocks.push(Block::Paragraph(vec_in![self.arena; content]));
+            }
+        }
+
+        blocks
+    }
+
+    /// Splits the first line of a tag into columns.
+    ///
+    /// Returns `None` for tags without a type or variable, and for tags whose type or
+    /// variable does not fit on the first line.
+    fn docblock_tag_columns(&self, tag: &Tag<'arena>, line: DocblockLine<'arena>) -> Option<TagColumns<'arena>> {
+        let (r#type, variable) = match &tag.value {
+            TagValue::Param(value) => {
+                (Some(value.r#type), parameter_span(value.ampersand, value.ellipsis, value.parameter)?)
+            }
+            TagValue::TypelessParam(value) => {
+                (None, parameter_span(value.ampersand, value.ellipsis, Some(value.parameter))?)
+            }
+            TagValue::ParamOut(value) => (Some(value.r#type), Some(value.parameter.span)),
+            TagValue::ParamClosureThis(value) => (Some(value.r#type), Some(value.parameter.span)),
+            TagValue::ParamImmediatelyInvokedCallable(value) => (None, Some(value.parameter.span)),
+            TagValue::ParamLaterInvokedCallable(value) => (None, Some(value.parameter.span)),
+            TagValue::Return(value) | TagValue::RealReturn(value) => (Some(value.r#type), None),
+            TagValue::Throws(value) => (Some(value.r#type), None),
+            TagValue::Mixin(value) => (Some(value.r#type), None),
+            TagValue::SelfOut(value) => (Some(value.r#type), None),
+            TagValue::Var(value) => (Some(value.r#type), value.variable.map(|variable| variable.span)),
+            TagValue::Property(value) | TagValue::PropertyRead(value) | TagValue::PropertyWrite(value) => {
+                (value.r#type, Some(value.variable.span))
+            }
+            _ => return None,
+        };
+
+        let line_end = line.offset + line.content.len() as u32;
+        let mut cursor = tag.name.span.end.offset;
+        let mut columns = TagColumns {
+            name: self.get_source_slice(tag.name.span.start.offset, cursor),
+            r#type: None,
+            variable: None,
+            description: b"",
+        };
+
+        if let Some(r#type) = r#type {
+            let span = r#type.span();
+            if span.end.offset > line_end || !self.is_whitespace_between(cursor, span.start.offset) {
+                return None;
+            }
+
+            let written = self.get_source_slice(span.start.offset, span.end.offset);
+            columns.r#type = Some(if self.settings.normalize_docblock_types {
+                self.normalize_docblock_type(written, span)
+            } else {
+                written
+            });
+            cursor = span.end.offset;
+        }
+
+        if let Some(span) = variable {
+            if span.end.offset > line_end || !self.is_whitespace_between(cursor, span.start.offset) {
+                return None;
+            }
+
+            columns.variable = Some(self.without_whitespace(self.get_source_slice(span.start.offset, span.end.offset)));
+            cursor = span.end.offset;
+        }
+
+        let rest = self.get_source_slice(cursor, line_end);
+        if rest.first().is_some_and(|byte| !byte.is_ascii_whitespace()) {
+            return None;
+        }
+
+        columns.description = rest.trim_ascii();
+
+        Some(columns)
+    }
+
+    fn is_whitespace_between(&self, start: u32, end: u32) -> bool {
+        start < end && self.get_source_slice(start, end).iter().all(u8::is_ascii_whitespace)
+    }
+
+    fn without_whitespace(&self, value: &'arena [u8]) -> &'arena [u8] {
+        if !value.iter().any(u8::is_ascii_whitespace) {
+            return value;
+        }
+
+        let mut buffer = Vec::with_capacity_in(value.len(), self.arena);
+        buffer.extend(value.iter().copied().filter(|byte| !byte.is_ascii_whitespace()));
+        buffer.leak()
+    }
+
+    /// Reprints a docblock type with canonical spacing.
+    ///
+    /// Only whitespace is changed, and the written type is kept if the result does not parse.
+    fn normalize_docblock_type(&self, written: &'arena [u8], span: Span) -> &'arena [u8] {
+        let tokens = type_tokens(written);
+        let ternary = ternary_tokens(&tokens);
+        let mut buffer = Vec::with_capacity_in(written.len(), self.arena);
+
+        for (index, token) in tokens.iter().enumerate() {
+            if index > 0
+                && token_spacing(
+                    tokens[index - 1],
+                    *token,
+                    tokens.get(index + 1).copied(),
+                    ternary[index - 1] || ternary[index],
+                )
+            {
+                buffer.push(b' ');
+            }
+
+            buffer.extend_from_slice(token.value);
+        }
+
+        let normalized: &'arena [u8] = buffer.leak();
+        if normalized == written || parse_type(self.arena, normalized, span).is_err() {
+            return written;
+        }
+
+        normalized
+    }
+
+    fn sort_docblock_tags(&self, blocks: Vec<'arena, Block<'arena, A>, A>) -> Vec<'arena, Block<'arena, A>, A> {
+        let mut result = Vec::with_capacity_in(blocks.len(), self.arena);
+        let mut run = Vec::new_in(self.arena);
+
+        for block in blocks {
+            match block {
+                Block::Tag(_) => run.push(block),
+                Block::Blank if !run.is_empty() => run.push(block),
+                block => {
+                    flush_sorted_tags(&mut result, &mut run);
+                    result.push(block);
+                }
+            }
+        }
+
+        flush_sorted_tags(&mut result, &mut run);
+
+        result
+    }
+
+    /// Removes leading, trailing, and repeated blank lines, separates the description from the
+    /// tags, and separates tags of different groups.
+    fn normalize_docblock_blank_lines(
+        &self,
+        blocks: Vec<'arena, Block<'arena, A>, A>,
+    ) -> Vec<'arena, Block<'arena, A>, A> {
+        let mut result: Vec<'arena, Block<'arena, A>, A> = Vec::with_capacity_in(blocks.len(), self.arena);
+        let mut has_blank_line = false;
+
+        for block in blocks {
+            if matches!(block, Block::Blank) {
+                has_blank_line = true;
+                continue;
+            }
+
+            let separate = match (result.last(), &block) {
+                (None, _) => false,
+                (Some(Block::Tag(previous)), Block::Tag(current)) => previous.group != current.group,
+                (Some(_), Block::Tag(_)) => true,
+                _ => has_blank_line,
+            };
+
+            if separate {
+                result.push(Block::Blank);
+            }
+
+            result.push(block);
+            has_blank_line = false;
+        }
+
+        result
+    }
+
+    fn print_docblock_blocks(&self, blocks: &[Block<'arena, A>]) -> Document<'arena, A> {
+        let widths = self.docblock_column_widths(blocks);
+        let mut contents = vec_in![self.arena; Document::String(b"/**")];
+
+        for (index, block) in blocks.iter().enumerate() {
+            match block {
+                Block::Blank => {
+                    contents.push(Document::Line(Line::hard()));
+                    contents.push(Document::String(b" *"));
+                }
+                Block::Verbatim(content) => push_docblock_line(&mut contents, content),
+                Block::Paragraph(lines) => {
+                    if self.settings.wrap_docblock_text {
+                        contents.push(Document::Line(Line::hard()));
+                        contents.push(Document::String(TEXT_CONTINUATION_PREFIX));
+                        self.push_docblock_words(&mut contents, lines, TEXT_CONTINUATION_PREFIX, true);
+                    } else {
+                        for line in lines.iter() {
+                            push_docblock_line(&mut contents, line);
+                        }
+                    }
+                }
+                Block::Tag(tag) => match &tag.head {
+                    TagHead::Columns(columns) => {
+                        let head = self.docblock_tag_head(columns, widths[index]);
+                        let wraps = self.settings.wrap_docblock_text
+                            && tag.continuation.iter().all(|line| !is_preformatted(line));
+
+                        if wraps {
+                            let mut lines = vec_in![self.arena; columns.description];
+                            lines.extend(tag.continuation.iter().copied());
+
+                            contents.push(Document::Line(Line::hard()));
+                            contents.push(Document::String(TEXT_CONTINUATION_PREFIX));
+                            if lines.iter().any(|line| !line.is_empty()) {
+                                contents.push(Document::String(head));
+                                contents.push(Document::String(b" "));
+                                self.push_docblock_words(&mut contents, &lines, TAG_CONTINUATION_PREFIX, true);
+                            } else {
+                                contents.push(Document::String(head.trim_ascii_end()));
+                            }
+                        } else {
+                            if columns.description.is_empty() {
+                                push_docblock_line(&mut contents, head.trim_ascii_end());
+                            } else {
+                                let mut line =
+                                    Vec::with_capacity_in(head.len() + columns.description.len() + 1, self.arena);
+                                line.extend_from_slice(head);
+                                line.push(b' ');
+                                line.extend_from_slice(columns.description);
+                                push_docblock_line(&mut contents, line.leak());
+                            }
+
+                            for line in tag.continuation.iter() {
+                                push_docblock_line(&mut contents, line);
+                            }
+                        }
+                    }
+                    TagHead::Verbatim(head) => {
+                        push_docblock_line(&mut contents, head);
+                        for line in tag.continuation.iter() {
+                            push_docblock_line(&mut contents, line);
+                        }
+                    }
+                },
+            }
+        }
+
+        contents.push(Document::Line(Line::hard()));
+        contents.push(Document::String(b" */"));
+
+        // Hard lines do not break the enclosing group, so it is broken explicitly; otherwise the
+        // words of wrapped text would be printed flat without being measured.
+        Document::Group(Group::new(contents).with_break_mode(BreakMode::Force))
+    }
+
+    /// Pushes the words of the given lines, breaking onto a new line whenever the next word
+    /// does not fit within the print width.
+    fn push_docblock_words(
+        &self,
+        contents: &mut Vec<'arena, Document<'arena, A>, A>,
+        lines: &[&'arena [u8]],
+        continuation_prefix: &'static [u8],
+        starts_line: bool,
+    ) {
+        for (index, word) in lines.iter().copied().flat_map(text_words).enumerate() {
+            if index == 0 && starts_line {
+                contents.push(Document::String(word));
+                continue;
+            }
+
+            let separator = IfBreak::new(
+                self.arena,
+                Document::Array(vec_in![
+                    self.arena;
+                    Document::Line(Line::default()),
+                    Document::String(continuation_prefix)
+                ]),
+                Document::String(b" "),
+            );
+
+            contents.push(Document::Group(Group::new(vec_in![
+                self.arena;
+                Document::IfBreak(separator),
+                Document::String(word)
+            ])));
+        }
+    }
+
+    /// Renders the name, type, and variable columns of a tag, padded to the given widths.
+    fn docblock_tag_head(&self, columns: &TagColumns<'arena>, widths: Option<ColumnWidths>) -> &'arena [u8] {
+        let mut head = Vec::new_in(self.arena);
+
+        match widths {
+            None => {
+                head.extend_from_slice(columns.name);
+                for part in [columns.r#type, columns.variable].into_iter().flatten() {
+                    head.push(b' ');
+                    head.extend_from_slice(part);
+                }
+            }
+            Some(widths) => {
+                let cells = [
+                    (columns.name, widths.name),
+                    (columns.r#type.unwrap_or_default(), widths.r#type),
+                    (columns.variable.unwrap_or_default(), widths.variable),
+                ];
+
+                for (index, (cell, width)) in cells.into_iter().enumerate() {
+                    if width == 0 {
+                        continue;
+                    }
+
+                    if index > 0 {
+                        head.push(b' ');
+                    }
+
+                    head.extend_from_slice(cell);
+                    head.extend(std::iter::repeat_n(b' ', width.saturating_sub(string_width(cell))));
+                }
+            }
+        }
+
+        head.leak()
+    }
+
+    /// Computes the column widths of each run of tags that are only separated by blank lines, when tags are aligned.
+    fn docblock_column_widths(&self, blocks: &[Block<'arena, A>]) -> std::vec::Vec<Option<ColumnWidths>> {
+        let mut widths = vec![None; blocks.len()];
+        if !self.settings.align_docblock_tags {
+            return widths;
+        }
+
+        let columns_of = |block: &Block<'arena, A>| match block {
+            Block::Tag(TagBlock { head: TagHead::Columns(columns), .. }) => Some(*columns),
+            _ => None,
+        };
+
+        let mut start = 0;
+        while start < blocks.len() {
+            if columns_of(&blocks[start]).is_none() {
+                start += 1;
+                continue;
+            }
+
+            // Blank lines do not end a run, so tags separated by group stay aligned.
+            let mut end = start;
+            let mut run_widths = ColumnWidths::default();
+            while let Some(block) = blocks.get(end) {
+                if let Some(columns) = columns_of(block) {
+                    run_widths.name = run_widths.name.max(string_width(columns.name));
+                    run_widths.r#type = run_widths.r#type.max(columns.r#type.map_or(0, string_width));
+                    run_widths.variable = run_widths.variable.max(columns.variable.map_or(0, string_width));
+                } else if !matches!(block, Block::Blank) {
+                    break;
+                }
+
+                end += 1;
+            }
+
+            for width in &mut widths[start..end] {
+                *width = Some(run_widths);
+            }
+
+            start = end;
+        }
+
+        widths
+    }
+}
+
+/// Appends a run of tags, and the blank lines between them, in canonical order.
+///
+/// Blank lines between the tags are dropped when the run has to be reordered.
+fn flush_sorted_tags<'arena, A>(
+    result: &mut Vec<'arena, Block<'arena, A>, A>,
+    run: &mut Vec<'arena, Block<'arena, A>, A>,
+) where
+    A: Arena,
+{
+    let mut trailing_blank_lines = 0;
+    while matches!(run.last(), Some(Block::Blank)) {
+        run.pop();
+        trailing_blank_lines += 1;
+    }
+
+    let groups = run
+        .iter()
+        .filter_map(|block| match block {
+            Block::Tag(tag) => Some(tag.group),
+            _ => None,
+        })
+        .collect::<std::vec::Vec<_>>();
+
+    if groups.is_sorted() {
+        result.extend(run.drain(..));
+    } else {
+        let mut tags = run.drain(..).filter(|block| matches!(block, Block::Tag(_))).collect::<std::vec::Vec<_>>();
+        tags.sort_by_key(|block| match block {
+            Block::Tag(tag) => tag.group,
+            _ => TagGroup::Other,
+        });
+
+        result.extend(tags);
+    }
+
+    for _ in 0..trailing_blank_lines {
+        result.push(Block::Blank);
+    }
+}
+
+fn push_docblock_line<'arena, A>(contents: &mut Vec<'arena, Document<'arena, A>, A>, line: &'arena [u8])
+where
+    A: Arena,
+{
+    contents.push(Document::Line(Line::hard()));
+    if line.is_empty() {
+        contents.push(Document::String(b" *"));
+    } else {
+        contents.push(Document::String(TEXT_CONTINUATION_PREFIX));
+        contents.push(Document::String(line));
+    }
+}
+
+/// Tracks whether the tokens of a type are inside a conditional type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConditionalState {
+    None,
+    /// After `is`, before the `?`.
+    Subject,
+    /// After the `?`, before the `:`.
+    Then,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TypeToken<'arena> {
+    value: &'arena [u8],
+    spaced: bool,
+}
+
+fn tag_group(value: &TagValue<'_>) -> TagGroup {
+    match value {
+        TagValue::TypeAlias(_) | TagValue::TypeAliasImport(_) => TagGroup::TypeAlias,
+        TagValue::Template(_) | TagValue::Where(_) => TagGroup::Template,
+        TagValue::Extends(_)
+        | TagValue::Implements(_)
+        | TagValue::Use(_)
+        | TagValue::RequireExtends(_)
+        | TagValue::RequireImplements(_)
+        | TagValue::Mixin(_)
+        | TagValue::Sealed(_)
+        | TagValue::Inheritors(_) => TagGroup::Inheritance,
+        TagValue::Property(_) | TagValue::PropertyRead(_) | TagValue::PropertyWrite(_) | TagValue::Method(_) => {
+            TagGroup::Member
+        }
+        TagValue::Var(_) => TagGroup::Var,
+        TagValue::Param(_)
+        | TagValue::TypelessParam(_)
+        | TagValue::ParamOut(_)
+        | TagValue::ParamClosureThis(_)
+        | TagValue::ParamImmediatelyInvokedCallable(_)
+        | TagValue::ParamLaterInvokedCallable(_) => TagGroup::Param,
+        TagValue::Assert(_) | TagValue::AssertIfTrue(_) | TagValue::AssertIfFalse(_) => TagGroup::Assert,
+        TagValue::Return(_) | TagValue::RealReturn(_) | TagValue::SelfOut(_) => TagGroup::Return,
+        TagValue::Throws(_) => TagGroup::Throws,
+        _ => TagGroup::Other,
+    }
+}
+
+/// Returns the span of a parameter with its `&` and `...` modifiers, `Some(None)` when there is
+/// no parameter, and `None` when there are modifiers without a parameter.
+fn parameter_span(
+    ampersand: Option<Span>,
+    ellipsis: Option<Span>,
+    parameter: Option<Variable<'_>>,
+) -> Option<Option<Span>> {
+    match parameter {
+        Some(parameter) => {
+            Some(Some(ampersand.or(ellipsis).map_or(parameter.span, |start| start.join(parameter.span))))
+        }
+        None if ampersand.is_none() && ellipsis.is_none() => Some(None),
+        None => None,
+    }
+}
+
+fn is_code_fence(content: &[u8]) -> bool {
+    content.starts_with(b"```") || content.starts_with(b"~~~")
+}
+
+/// Whether a line of text must be printed as written rather than joined with its neighbours.
+fn is_preformatted(content: &[u8]) -> bool {
+    let Some(&first) = content.first() else {
+        return true;
+    };
+
+    match first {
+        b' ' | b'\t' | b'#' | b'>' | b'|' | b'<' | b'{' => true,
+        b'-' | b'+' | b'*' => content.get(1).is_none_or(|byte| *byte == b' '),
+        b'0'..=b'9' => {
+            let digits = content.iter().take_while(|byte| byte.is_ascii_digit()).count();
+
+            matches!(content.get(digits), Some(b'.' | b')')) && content.get(digits + 1).is_none_or(|byte| *byte == b' ')
+        }
+        _ => false,
+    }
+}
+
+/// Splits a line of text into words, keeping inline tags and inline code together.
+fn text_words(line: &[u8]) -> impl Iterator<Item = &[u8]> {
+    let mut position = 0;
+
+    std::iter::from_fn(move || {
+        while position < line.len() && line[position].is_ascii_whitespace() {
+            position += 1;
+        }
+
+        if position >= line.len() {
+            return None;
+        }
+
+        let start = position;
+        let mut inline_tag_depth = 0usize;
+        let mut in_code = false;
+        while position < line.len() {
+            let byte = line[position];
+            if in_code {
+                in_code = byte != b'`';
+            } else if byte == b'`' {
+                in_code = true;
+            } else if byte == b'{' && line.get(position + 1) == Some(&b'@') {
+                inline_tag_depth += 1;
+            } else if byte == b'}' && inline_tag_depth > 0 {
+                inline_tag_depth -= 1;
+            } else if byte.is_ascii_whitespace() && inline_tag_depth == 0 {
+                break;
+            }
+
+            position += 1;
+        }
+
+        Some(&line[start..position])
+    })
+}
+
+fn punctuation(value: &[u8]) -> Option<u8> {
+    match value {
+        [byte @ (b'|' | b'&' | b'<' | b'>' | b'(' | b')' | b'[' | b']' | b'{' | b'}' | b',' | b':' | b'?')] => {
+            Some(*byte)
+        }
+        _ => None,
+    }
+}
+
+/// Splits a type into punctuation, quoted strings, and words, recording whether each token
+/// was preceded by whitespace.
+fn type_tokens(value: &[u8]) -> std::vec::Vec<TypeToken<'_>> {
+    let mut tokens = std::vec::Vec::new();
+    let mut position = 0;
+
+    while position < value.len() {
+        let spaced = value[position].is_ascii_whitespace();
+        while position < value.len() && value[position].is_ascii_whitespace() {
+            position += 1;
+        }
+
+        let Some(&byte) = value.get(position) else {
+            break;
+        };
+
+        let start = position;
+        if byte == b'\'' || byte == b'"' {
+            position += 1;
+            while position < value.len() && value[position] != byte {
+                position += if value[position] == b'\\' { 2 } else { 1 };
+            }
+
+            position = (position + 1).min(value.len());
+        } else if punctuation(&value[position..=position]).is_some() && !value[position..].starts_with(b"::") {
+            position += 1;
+        } else {
+            while position < value.len() {
+                if value[position..].starts_with(b"::") {
+                    position += 2;
+                    continue;
+                }
+
+                let byte = value[position];
+                if byte.is_ascii_whitespace()
+                    || byte == b'\''
+                    || byte == b'"'
+                    || punctuation(&value[position..=position]).is_some()
+                {
+                    break;
+                }
+
+                position += 1;
+            }
+        }
+
+        tokens.push(TypeToken { value: &value[start..position], spaced: spaced && start > 0 });
+    }
+
+    tokens
+}
+
+/// Whether a token can end an operand, as opposed to a keyword or an opening bracket.
+fn ends_operand(value: &[u8]) -> bool {
+    match punctuation(value) {
+        Some(byte) => matches!(byte, b'>' | b')' | b']' | b'}'),
+        None => !["is", "not", "of", "as"].iter().any(|keyword| value.eq_ignore_ascii_case(keyword.as_bytes())),
+    }
+}
+
+/// Finds the `?` and `:` tokens of conditional types, which are surrounded by spaces.
+fn ternary_tokens(tokens: &[TypeToken<'_>]) -> std::vec::Vec<bool> {
+    let mut ternary = vec![false; tokens.len()];
+    let mut frames = vec![ConditionalState::None];
+
+    for (index, token) in tokens.iter().enumerate() {
+        let state = frames.last().copied().unwrap_or(ConditionalState::None);
+        let is_ternary = match punctuation(token.value) {
+            Some(b'?') => {
+                state != ConditionalState::None
+                    && index
+                        .checked_sub(1)
+                        .and_then(|index| tokens.get(index))
+                        .is_some_and(|previous| ends_operand(previous.value))
+            }
+            Some(b':') => state == ConditionalState::Then,
+            _ => false,
+        };
+
+        ternary[index] = is_ternary;
+
+        let next_state = match punctuation(token.value) {
+            Some(b'<' | b'(' | b'[' | b'{') => {
+                frames.push(ConditionalState::None);
+                continue;
+            }
+            Some(b'>' | b')' | b']' | b'}') => {
+                if frames.len() > 1 {
+                    frames.pop();
+                }
+
+                continue;
+            }
+            Some(b'?') if is_ternary => ConditionalState::Then,
+            Some(b',' | b':') => ConditionalState::None,
+            None if token.value.eq_ignore_ascii_case(b"is") => ConditionalState::Subject,
+            _ => continue,
+        };
+
+        if let Some(frame) = frames.last_mut() {
+            *frame = next_state;
+        }
+    }
+
+    ternary
+}
+
+/// Decides whether a space separates two tokens of a type.
+fn token_spacing(
+    previous: TypeToken<'_>,
+    current: TypeToken<'_>,
+    next: Option<TypeToken<'_>>,
+    around_ternary: bool,
+) -> bool {
+    if around_ternary {
+        return true;
+    }
+
+    if matches!(punctuation(current.value), Some(b',' | b'>' | b')' | b']' | b'}')) {
+        return false;
+    }
+
+    match punctuation(previous.value) {
+        Some(b',' | b':') => return true,
+        Some(b'<' | b'(' | b'[' | b'{' | b'|' | b'&' | b'?') => return false,
+        _ => {}
+    }
+
+    match punctuation(current.value) {
+        Some(b'|' | b':') => false,
+        Some(b'&') => {
+            current.spaced && next.is_some_and(|next| next.value.starts_with(b"$") || next.value.starts_with(b"..."))
+        }
+        Some(b'?' | b'<' | b'[' | b'{') => !ends_operand(previous.value) && current.spaced,
+        _ => current.spaced,
+    }
+}
@@ -647,6 +647,10 @@ where
             return Document::String(content);
         }
 
+        if let Some(document) = self.print_docblock(comment) {
+            return document;
+        }
+
         let lines = self.split_lines(content);
         let mut contents = Vec::with_capacity_in(lines.len() * 2, self.arena);
 
@@ -22,6 +22,7 @@ pub mod format;
 pub mod macros;
 pub mod parens;
 pub mod printer;
+pub mod range;
 pub mod utils;
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -0,0 +1,258 @@
+use std::mem::discriminant;
+
+use mago_span::HasSpan;
+use mago_span::Span;
+use mago_syntax::cst::Node;
+use mago_syntax::cst::Program;
+use mago_syntax::cst::Statement;
+use mago_text_edit::TextEdit;
+use mago_text_edit::TextRange;
+
+/// A run of sibling statements, or class-like members, that encloses a formatting range.
+#[derive(Debug, Clone)]
+struct Selection {
+    /// The index of each enclosing statement, from the program down to the parent of the run.
+    path: Vec<usize>,
+    /// The index of the first statement of the run.
+    start: usize,
+    /// The index of the last statement of the run.
+    end: usize,
+}
+
+/// The lines of a selection in the original source, and the corresponding lines in the
+/// formatted source.
+#[derive(Debug, Clone, Copy)]
+struct Region {
+    original: TextRange,
+    formatted: TextRange,
+}
+
+/// Computes the edits that apply the formatted program to the statements enclosing each range.
+///
+/// Each range is widened to the statements that enclose it, and the lines of those statements
+/// are replaced with their formatted counterparts. When the statements cannot be matched in the
+/// formatted program, or share a line with code outside of them, the selection moves up to the
+/// enclosing statement until they can, falling back to the whole file.
+pub fn range_edits(original: &Program<'_>, formatted: &Program<'_>, ranges: &[TextRange]) -> Vec<TextEdit> {
+    let mut regions = ranges.iter().filter_map(|range| range_region(original, formatted, *range)).collect::<Vec<_>>();
+    regions.sort_by_key(|region| (region.original.start, region.original.end));
+
+    let mut merged: Vec<Region> = Vec::with_capacity(regions.len());
+    for region in regions {
+        match merged.last_mut() {
+            Some(last) if region.original.start < last.original.end => {
+                last.original.end = last.original.end.max(region.original.end);
+                last.formatted.end = last.formatted.end.max(region.formatted.end);
+            }
+            _ => merged.push(region),
+        }
+    }
+
+    merged
+        .into_iter()
+        .filter_map(|region| {
+            let original_text = slice(original.source_text, region.original);
+            let formatted_text = slice(formatted.source_text, region.formatted);
+
+            (original_text != formatted_text)
+                .then(|| TextEdit::replace(region.original.start..region.original.end, formatted_text))
+        })
+        .collect()
+}
+
+fn range_region(original: &Program<'_>, formatted: &Program<'_>, range: TextRange) -> Option<Region> {
+    let mut selection = enclosing_statements(original, range)?;
+
+    loop {
+        if let Some(region) = selection_region(original, formatted, &mut selection) {
+            return Some(region);
+        }
+
+        let Some(index) = selection.path.pop() else {
+            return Some(Region {
+                original: TextRange::new(0, original.source_text.len() as u32),
+                formatted: TextRange::new(0, formatted.source_text.len() as u32),
+            });
+        };
+
+        selection.start = index;
+        selection.end = index;
+    }
+}
+
+/// Finds the innermost run of sibling statements that overlaps the range.
+fn enclosing_statements(program: &Program<'_>, range: TextRange) -> Option<Selection> {
+    let mut path = vec![];
+    let mut parent = Node::Program(program);
+
+    loop {
+        let children = statement_children(parent);
+        let mut overlapping =
+            children.iter().enumerate().filter(|(_, child)| overlaps(child.span(), range)).map(|(index, _)| index);
+
+        let Some(start) = overlapping.next() else {
+            let index = path.pop()?;
+
+            return Some(Selection { path, start: index, end: index });
+        };
+
+        let end = overlapping.next_back().unwrap_or(start);
+        let child = children[start];
+        if start == end && contains(child.span(), range) {
+            path.push(start);
+            parent = child;
+            continue;
+        }
+
+        return Some(Selection { path, start, end });
+    }
+}
+
+/// Maps the selection onto the formatted program, widening it to whole lines.
+///
+/// Returns `None` when the selection cannot be matched in the formatted program, or when its
+/// lines contain code that does not belong to it.
+fn selection_region(original: &Program<'_>, formatted: &Program<'_>, selection: &mut Selection) -> Option<Region> {
+    let mut original_parent = Node::Program(original);
+    let mut formatted_parent = Node::Program(formatted);
+    for index in &selection.path {
+        let original_children = statement_children(original_parent);
+        let formatted_children = statement_children(formatted_parent);
+        if original_children.len() != formatted_children.len()
+            || !is_same_kind(original_children[*index], formatted_children[*index])
+        {
+            return None;
+        }
+
+        original_parent = original_children[*index];
+        formatted_parent = formatted_children[*index];
+    }
+
+    let original_siblings = statement_children(original_parent);
+    let formatted_siblings = statement_children(formatted_parent);
+    if original_siblings.len() != formatted_siblings.len() {
+        return None;
+    }
+
+    // Sorting `use` statements moves them across each other, so they are selected together.
+    let is_use = |node: Node<'_, '_>| matches!(node, Node::Statement(Statement::Use(_)));
+    while selection.start > 0
+        && is_use(original_siblings[selection.start])
+        && is_use(original_siblings[selection.start - 1])
+    {
+        selection.start -= 1;
+    }
+
+    while selection.end + 1 < original_siblings.len()
+        && is_use(original_siblings[selection.end])
+        && is_use(original_siblings[selection.end + 1])
+    {
+        selection.end += 1;
+    }
+
+    // Statements sharing a line with the selection are selected with it.
+    while widen_to_lines(original.source_text, &original_siblings, selection)
+        || widen_to_lines(formatted.source_text, &formatted_siblings, selection)
+    {}
+
+    if (selection.start..=selection.end).any(|index| !is_same_kind(original_siblings[index], formatted_siblings[index]))
+    {
+        return None;
+    }
+
+    Some(Region {
+        original: lines_of(original.source_text, &original_siblings, selection)?,
+        formatted: lines_of(formatted.source_text, &formatted_siblings, selection)?,
+    })
+}
+
+/// Widens the selection by one statement on each side that shares a line with it.
+///
+/// Returns `true` if the selection changed.
+fn widen_to_lines(source: &[u8], siblings: &[Node<'_, '_>], selection: &mut Selection) -> bool {
+    let mut changed = false;
+
+    let first_line_start = line_start(source, siblings[selection.start].span().start.offset);
+    if selection.start > 0 && siblings[selection.start - 1].span().end.offset > first_line_start {
+        selection.start -= 1;
+        changed = true;
+    }
+
+    let last_line_end = line_end(source, siblings[selection.end].span().end.offset);
+    if selection.end + 1 < siblings.len() && siblings[selection.end + 1].span().start.offset < last_line_end {
+        selection.end += 1;
+        changed = true;
+    }
+
+    changed
+}
+
+/// Returns the lines of the selection, if nothing but whitespace precedes it on its first line,
+/// and nothing but whitespace or a comment follows it on its last line.
+fn lines_of(source: &[u8], siblings: &[Node<'_, '_>], selection: &Selection) -> Option<TextRange> {
+    let start = siblings[selection.start].span().start.offset;
+    let end = siblings[selection.end].span().end.offset;
+
+    let first_line_start = line_start(source, start);
+    let last_line_end = line_end(source, end);
+
+    let before = &source[first_line_start as usize..start as usize];
+    let after = source[end as usize..last_line_end as usize].trim_ascii();
+    if !before.iter().all(u8::is_ascii_whitespace)
+        || !(after.is_empty() || after.starts_with(b"//") || after.starts_with(b"#"))
+    {
+        return None;
+    }
+
+    Some(TextRange::new(first_line_start, last_line_end))
+}
+
+/// Collects the statements and class-like members nested directly within a node.
+fn statement_children<'ast, 'arena>(node: Node<'ast, 'arena>) -> Vec<Node<'ast, 'arena>> {
+    let mut statements = vec![];
+    node.visit_children(|child| collect_statements(child, &mut statements));
+
+    statements
+}
+
+fn collect_statements<'ast, 'arena>(node: Node<'ast, 'arena>, statements: &mut Vec<Node<'ast, 'arena>>) {
+    if matches!(node, Node::Statement(_) | Node::ClassLikeMember(_)) {
+        statements.push(node);
+    } else {
+        node.visit_children(|child| collect_statements(child, statements));
+    }
+}
+
+fn is_same_kind(original: Node<'_, '_>, formatted: Node<'_, '_>) -> bool {
+    match (original, formatted) {
+        (Node::Statement(original), Node::Statement(formatted)) => discriminant(original) == discriminant(formatted),
+        (Node::ClassLikeMember(original), Node::ClassLikeMember(formatted)) => {
+            discriminant(original) == discriminant(formatted)
+        }
+        _ => false,
+    }
+}
+
+fn overlaps(span: Span, range: TextRange) -> bool {
+    if range.is_empty() {
+        span.start.offset <= range.start && range.start <= span.end.offset
+    } else {
+        span.start.offset < range.end && range.start < span.end.offset
+    }
+}
+
+fn contains(span: Span, range: TextRange) -> bool {
+    span.start.offset <= range.start && range.end <= span.end.offset
+}
+
+fn line_start(source: &[u8], offset: u32) -> u32 {
+    memchr::memrchr(b'\n', &source[..offset as usize]).map_or(0, |index| index as u32 + 1)
+}
+
+fn line_end(source: &[u8], offset: u32) -> u32 {
+    memchr::memchr(b'\n', &source[offset as usize..]).map_or(source.len() as u32, |index| offset + index as u32)
+}
+
+fn slice(source: &[u8], range: TextRange) -> &[u8] {
+    &source[range.start as usize..range.end as usize]
+}
@@ -19,13 +19,17 @@ use mago_php_version::PHPVersion;
 use mago_syntax::cst::Program;
 use mago_syntax::cst::TriviaSequenceExt;
 use mago_syntax::error::ParseError;
+use mago_syntax::parser::parse_file_content_with_settings;
 use mago_syntax::parser::parse_file_with_settings;
 use mago_syntax::settings::ParserSettings;
+use mago_text_edit::TextEdit;
+use mago_text_edit::TextRange;
 
 use crate::document::Document;
 use crate::internal::FormatterState;
 use crate::internal::format::Format;
 use crate::internal::printer::Printer;
+use crate::internal::range::range_edits;
 use crate::settings::FormatSettings;
 
 pub mod document;
@@ -112,6 +116,54 @@ where
         Ok(self.format(file, program))
     }
 
+    /// Formats only the statements that enclose a byte range of a [`File`].
+    ///
+    /// The range is widened to the innermost statements, or class-like members, that overlap
+    /// it, and the returned edits replace the lines of those statements with their formatted
+    /// version; the rest of the file is left as written. This is intended for editors that
+    /// format a selection, and for formatting only the lines touched by a change.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [`ParseError`] if the file's content contains syntax errors.
+    pub fn format_range(&self, file: &File, range: TextRange) -> Result<Vec<TextEdit>, ParseError> {
+        self.format_ranges(file, &[range])
+    }
+
+    /// Formats only the statements that enclose any of the given byte ranges of a [`File`].
+    ///
+    /// See [`format_range`](Self::format_range). The file is formatted once, and the edits of
+    /// overlapping ranges are merged, so the returned edits never overlap and are sorted by
+    /// their position in the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [`ParseError`] if the file's content contains syntax errors.
+    pub fn format_ranges(&self, file: &File, ranges: &[TextRange]) -> Result<Vec<TextEdit>, ParseError> {
+        let program = parse_file_with_settings(self.arena, file, self.parser_settings);
+        if let Some(error) = program.errors.first() {
+            return Err(error.clone());
+        }
+
+        if ranges.is_empty() || has_format_ignore_comment(program) {
+            return Ok(vec![]);
+        }
+
+        let formatted = self.format(file, program);
+        if formatted == program.source_text {
+            return Ok(vec![]);
+        }
+
+        // The formatter never produces code that fails to parse; should it happen, nothing is
+        // changed rather than risking edits that cannot be matched to the original code.
+        let formatted_program = parse_file_content_with_settings(self.arena, file.id, formatted, self.parser_settings);
+        if !formatted_program.errors.is_empty() {
+            return Ok(vec![]);
+        }
+
+        Ok(range_edits(program, formatted_program, ranges))
+    }
+
     /// Formats a pre-parsed [`Program`] (AST).
     ///
     /// This is the lowest-level formatting method that operates directly on the AST.
@@ -213,6 +213,11 @@ const DEFAULT_PRESET: FormatSettings = FormatSettings {
     separate_trait_use: true,
     indent_heredoc: true,
     uppercase_literal_keyword: false,
+    sort_docblock_tags: false,
+    align_docblock_tags: false,
+    normalize_docblock_types: false,
+    normalize_docblock_blank_lines: false,
+    wrap_docblock_text: false,
 };
 
 /// The PSR-12 formatter preset.
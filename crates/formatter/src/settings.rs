@@ -1242,6 +1242,72 @@ generate_formatter_settings! {
     ///
     /// Default: false
     uppercase_literal_keyword: bool => "default_false",
+
+    /// Whether to reorder docblock tags into a canonical order.
+    ///
+    /// Tags are grouped as type aliases, templates, inheritance tags (`@extends`, `@implements`, ...),
+    /// `@property` and `@method`, `@var`, `@param`, assertions, `@return`, and `@throws`. Tags the
+    /// formatter does not recognize, such as `@see` or Doctrine annotations, keep their relative order
+    /// after the known ones. Tags within a group keep their original order.
+    ///
+    /// Example:
+    /// ```php
+    /// /**
+    ///  * @return int
+    ///  * @param string $name
+    ///  */
+    /// ```
+    ///
+    /// Becomes:
+    /// ```php
+    /// /**
+    ///  * @param string $name
+    ///  * @return int
+    ///  */
+    /// ```
+    ///
+    /// Default: false
+    sort_docblock_tags: bool => "default_false",
+
+    /// Whether to column-align the type, variable, and description of consecutive docblock tags.
+    ///
+    /// Example:
+    /// ```php
+    /// /**
+    ///  * @param string               $name    The name.
+    ///  * @param array<string, mixed> $options The options.
+    ///  * @return int
+    ///  */
+    /// ```
+    ///
+    /// Default: false
+    align_docblock_tags: bool => "default_false",
+
+    /// Whether to print the types in docblock tags with canonical spacing.
+    ///
+    /// No spaces are printed around `|` and `&` or inside brackets, and a single space follows
+    /// each `,` and each `:` in shapes and callables, e.g. `array<int, string>|null`. Types that
+    /// span multiple lines are left as written.
+    ///
+    /// Default: false
+    normalize_docblock_types: bool => "default_false",
+
+    /// Whether to normalize blank lines in docblocks.
+    ///
+    /// Leading, trailing, and repeated blank lines are removed, a single blank line separates the
+    /// description from the tags, and a blank line separates tags of different groups (see
+    /// `sort_docblock_tags`) while tags of the same group are kept together.
+    ///
+    /// Default: false
+    normalize_docblock_blank_lines: bool => "default_false",
+
+    /// Whether to reflow docblock description text to fit within `print_width`.
+    ///
+    /// Consecutive lines of plain text, including tag descriptions, are joined and wrapped at
+    /// word boundaries. Code blocks, lists, tables, and indented lines are left as written.
+    ///
+    /// Default: false
+    wrap_docblock_text: bool => "default_false",
 }
 
 impl Default for FormatSettings {
@@ -0,0 +1,37 @@
+<?php
+
+namespace App;
+
+/**
+ * Greets a person by name, optionally in a different language, and returns the number of characters that were written.
+ *
+ *
+ * @return int   the number of characters written
+ * @throws \RuntimeException   when the output is closed
+ * @param string $name the name of the person
+ * @param array<string,int|null> $options   the options
+ * @see Greeter
+ */
+function greet(string $name, array $options = []): int
+{
+    return 0;
+}
+
+final class Greeter
+{
+    /**
+     * @var list<string>
+     */
+    private array $names = [];
+
+    /**
+     * Returns the greeting.
+     * @template T of object
+     * @param T $subject
+     * @return array{name:string,age?:int}
+     */
+    public function greeting(object $subject): array
+    {
+        return ['name' => ''];
+    }
+}
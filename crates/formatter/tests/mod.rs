@@ -328,6 +328,8 @@ test_case!(comment_placement_binary);
 test_case!(comment_placement_conditional);
 test_case!(comment_placement_conditional_preserve);
 test_case!(fits_line_suffix);
+test_case!(docblock_formatting);
+test_case!(docblock_formatting_default_off);
 
 // A special test case for regressions in the Psl codebase
 test_case!(psl_regressions);
@@ -0,0 +1,112 @@
+#![allow(clippy::unwrap_used)]
+
+use std::borrow::Cow;
+
+use indoc::indoc;
+
+use mago_allocator::LocalArena;
+use mago_database::file::File;
+use mago_formatter::Formatter;
+use mago_formatter::settings::FormatSettings;
+use mago_php_version::PHPVersion;
+use mago_text_edit::TextEditor;
+use mago_text_edit::TextRange;
+
+/// Formats the statements enclosing the first occurrence of `needle`, and applies the edits.
+fn format_range(code: &'static str, needle: &str) -> String {
+    let arena = LocalArena::new();
+    let formatter = Formatter::new(&arena, PHPVersion::LATEST, FormatSettings::default());
+    let file = File::ephemeral(Cow::Borrowed(b"code.php"), Cow::Borrowed(code.as_bytes()));
+
+    let start = code.find(needle).unwrap() as u32;
+    let edits = formatter.format_range(&file, TextRange::new(start, start + needle.len() as u32)).unwrap();
+
+    let mut editor = TextEditor::new(code.as_bytes());
+    editor.apply_batch(edits, None::<fn(&[u8]) -> bool>);
+
+    String::from_utf8(editor.finish()).unwrap()
+}
+
+#[test]
+fn formats_only_the_enclosing_statement() {
+    let code = indoc! {"
+        <?php
+
+        $a   =   1;
+        $b   =   2;
+        $c   =   3;
+    "};
+
+    let expected = indoc! {"
+        <?php
+
+        $a   =   1;
+        $b = 2;
+        $c   =   3;
+    "};
+
+    pretty_assertions::assert_eq!(expected, format_range(code, "$b"));
+}
+
+#[test]
+fn formats_the_innermost_enclosing_statement() {
+    let code = indoc! {"
+        <?php
+
+        function foo() {
+          $a   =   1;
+          $b   =   2;
+        }
+    "};
+
+    let expected = indoc! {"
+        <?php
+
+        function foo() {
+          $a   =   1;
+            $b = 2;
+        }
+    "};
+
+    pretty_assertions::assert_eq!(expected, format_range(code, "$b"));
+}
+
+#[test]
+fn widens_to_the_statement_sharing_its_line() {
+    let code = indoc! {"
+        <?php
+
+        if ($x) { $a   =   1; }
+        $b   =   2;
+    "};
+
+    let expected = indoc! {"
+        <?php
+
+        if ($x) {
+            $a = 1;
+        }
+        $b   =   2;
+    "};
+
+    pretty_assertions::assert_eq!(expected, format_range(code, "$a"));
+}
+
+#[test]
+fn returns_no_edits_for_formatted_statements() {
+    let code = indoc! {"
+        <?php
+
+        $a = 1;
+        $b   =   2;
+    "};
+
+    let arena = LocalArena::new();
+    let formatter = Formatter::new(&arena, PHPVersion::LATEST, FormatSettings::default());
+    let file = File::ephemeral(Cow::Borrowed(b"code.php"), Cow::Borrowed(code.as_bytes()));
+
+    let start = code.find("$a").unwrap() as u32;
+    let edits = formatter.format_range(&file, TextRange::new(start, start)).unwrap();
+
+    assert!(edits.is_empty());
+}
@@ -0,0 +1,303 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+use mago_span::Span;
+
+use crate::matcher;
+use crate::path::NamespacePath;
+use crate::report::breach::BreachVector;
+use crate::settings::PermittedDependencyKind;
+
+/// The name used for the global namespace in the graph.
+const GLOBAL_NODE: &str = "@global";
+
+/// The namespace dependency graph of a codebase.
+///
+/// Nodes are namespaces (or layers, see [`DependencyGraph::group_by_layers`]), and each edge
+/// counts the dependencies from one node on symbols of another, grouped by dependency kind.
+/// Dependencies within a single node are not recorded.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DependencyGraph {
+    nodes: BTreeSet<String>,
+    edges: BTreeMap<(String, String), DependencyEdge>,
+    declarations: BTreeMap<String, NamespaceDeclaration>,
+}
+
+/// A directed edge of the [`DependencyGraph`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct DependencyEdge {
+    /// The node that depends on `target`.
+    pub source: String,
+    /// The node that `source` depends on.
+    pub target: String,
+    /// The total number of dependencies.
+    pub count: usize,
+    /// The number of dependencies of each kind.
+    pub kinds: BTreeMap<PermittedDependencyKind, usize>,
+    /// The symbol references that make up the edge, in the order they were found.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub references: Vec<DependencyReference>,
+}
+
+/// A single reference to a symbol of another namespace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyReference {
+    /// The fully qualified name of the referenced symbol.
+    pub symbol: String,
+    /// The kind of the referenced symbol.
+    pub kind: PermittedDependencyKind,
+    /// The location of the reference.
+    pub span: Span,
+    /// Whether the referenced symbol is built into PHP or one of its extensions.
+    pub builtin: bool,
+}
+
+/// The class-likes declared in a namespace of the analyzed code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NamespaceDeclaration {
+    /// The location of the first declaration of the namespace.
+    pub span: Span,
+    /// The number of class-likes declared in the namespace.
+    pub class_likes: usize,
+    /// The number of interfaces and abstract classes declared in the namespace.
+    pub abstract_class_likes: usize,
+}
+
+impl DependencyGraph {
+    /// Records a dependency of `source_namespace` on `dependency_fqn`.
+    pub fn add_dependency(
+        &mut self,
+        source_namespace: &[u8],
+        dependency_fqn: &[u8],
+        dependency_kind: PermittedDependencyKind,
+        vector: BreachVector,
+        span: Span,
+        builtin: bool,
+    ) {
+        let target_namespace = match dependency_fqn.iter().rposition(|byte| *byte == b'\\') {
+            Some(position) => &dependency_fqn[..position],
+            None => &[],
+        };
+
+        // Attributes are resolved as class-likes, but are reported as their own kind.
+        let kind = if vector == BreachVector::Attribute { PermittedDependencyKind::Attribute } else { dependency_kind };
+
+        let reference =
+            DependencyReference { symbol: String::from_utf8_lossy(dependency_fqn).into_owned(), kind, span, builtin };
+
+        self.add_edge(node_name(source_namespace), node_name(target_namespace), vec![reference]);
+    }
+
+    /// Records a declaration of `namespace`.
+    pub fn add_namespace(&mut self, namespace: &[u8], span: Span) {
+        self.declare(node_name(namespace), NamespaceDeclaration { span, class_likes: 0, abstract_class_likes: 0 });
+    }
+
+    /// Records a class-like declared in `namespace`.
+    pub fn add_class_like(&mut self, namespace: &[u8], span: Span, is_abstract: bool) {
+        let declaration = NamespaceDeclaration { span, class_likes: 1, abstract_class_likes: usize::from(is_abstract) };
+
+        self.declare(node_name(namespace), declaration);
+    }
+
+    /// Merges the nodes and edges of another graph into this one.
+    pub fn extend(&mut self, other: DependencyGraph) {
+        self.nodes.extend(other.nodes);
+
+        for edge in other.edges.into_values() {
+            self.add_edge(edge.source, edge.target, edge.references);
+        }
+
+        for (namespace, declaration) in other.declarations {
+            self.declare(namespace, declaration);
+        }
+    }
+
+    /// Returns a graph where namespaces are replaced by the first `layering` entry they belong to.
+    ///
+    /// Namespaces that do not belong to any layer are kept as they are.
+    #[must_use]
+    pub fn group_by_layers(&self, layering: &[NamespacePath]) -> DependencyGraph {
+        let layer_of = |node: &str| -> String {
+            layering
+                .iter()
+                .find(|layer| match layer {
+                    NamespacePath::Global => node == GLOBAL_NODE,
+                    NamespacePath::Specific(namespace) => {
+                        node != GLOBAL_NODE && matcher::matches(node.as_bytes(), namespace.as_bytes(), false, true)
+                    }
+                })
+                .map_or_else(|| node.to_string(), ToString::to_string)
+        };
+
+        let mut graph = DependencyGraph::default();
+        for node in &self.nodes {
+            graph.nodes.insert(layer_of(node));
+        }
+
+        for edge in self.edges.values() {
+            graph.add_edge(layer_of(&edge.source), layer_of(&edge.target), edge.references.clone());
+        }
+
+        for (namespace, declaration) in &self.declarations {
+            graph.declare(layer_of(namespace), *declaration);
+        }
+
+        graph
+    }
+
+    /// Returns the nodes of the graph, in alphabetical order.
+    pub fn nodes(&self) -> impl Iterator<Item = &str> {
+        self.nodes.iter().map(String::as_str)
+    }
+
+    /// Returns the edges of the graph, ordered by source and target.
+    pub fn edges(&self) -> impl Iterator<Item = &DependencyEdge> {
+        self.edges.values()
+    }
+
+    /// Returns the edge from `source` to `target`, if there is one.
+    #[must_use]
+    pub fn edge(&self, source: &str, target: &str) -> Option<&DependencyEdge> {
+        self.edges.get(&(source.to_string(), target.to_string()))
+    }
+
+    /// Returns the namespaces declared in the analyzed code, in alphabetical order.
+    pub fn declarations(&self) -> impl Iterator<Item = (&str, &NamespaceDeclaration)> {
+        self.declarations.iter().map(|(namespace, declaration)| (namespace.as_str(), declaration))
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Renders the graph in the Graphviz DOT language.
+    #[must_use]
+    pub fn to_dot(&self) -> String {
+        let mut output = String::from("digraph dependencies {\n    rankdir=LR;\n    node [shape=box];\n");
+
+        for node in &self.nodes {
+            let _ = writeln!(output, "    \"{}\";", escape_dot(node));
+        }
+
+        for edge in self.edges.values() {
+            let _ = writeln!(
+                output,
+                "    \"{}\" -> \"{}\" [label=\"{}\"];",
+                escape_dot(&edge.source),
+                escape_dot(&edge.target),
+                edge.label()
+            );
+        }
+
+        output.push_str("}\n");
+        output
+    }
+
+    /// Renders the graph as a Mermaid flowchart.
+    #[must_use]
+    pub fn to_mermaid(&self) -> String {
+        let identifiers: BTreeMap<&str, String> =
+            self.nodes.iter().enumerate().map(|(index, node)| (node.as_str(), format!("n{index}"))).collect();
+
+        let mut output = String::from("flowchart LR\n");
+
+        for (node, identifier) in &identifiers {
+            let _ = writeln!(output, "    {identifier}[\"{}\"]", escape_mermaid(node));
+        }
+
+        for edge in self.edges.values() {
+            let (Some(source), Some(target)) =
+                (identifiers.get(edge.source.as_str()), identifiers.get(edge.target.as_str()))
+            else {
+                continue;
+            };
+
+            let _ = writeln!(output, "    {source} -->|\"{}\"| {target}", edge.label());
+        }
+
+        output
+    }
+
+    fn add_edge(&mut self, source: String, target: String, references: Vec<DependencyReference>) {
+        self.nodes.insert(source.clone());
+        self.nodes.insert(target.clone());
+
+        if source == target {
+            return;
+        }
+
+        let edge = self.edges.entry((source.clone(), target.clone())).or_insert_with(|| DependencyEdge {
+            source,
+            target,
+            count: 0,
+            kinds: BTreeMap::new(),
+            references: vec![],
+        });
+
+        for reference in references {
+            edge.count += 1;
+            *edge.kinds.entry(reference.kind).or_default() += 1;
+            edge.references.push(reference);
+        }
+    }
+
+    fn declare(&mut self, namespace: String, declaration: NamespaceDeclaration) {
+        self.nodes.insert(namespace.clone());
+
+        self.declarations
+            .entry(namespace)
+            .and_modify(|existing| {
+                existing.span = existing.span.min(declaration.span);
+                existing.class_likes += declaration.class_likes;
+                existing.abstract_class_likes += declaration.abstract_class_likes;
+            })
+            .or_insert(declaration);
+    }
+}
+
+impl DependencyEdge {
+    /// Returns whether every reference of the edge is to a built-in symbol.
+    #[must_use]
+    pub fn is_builtin(&self) -> bool {
+        self.references.iter().all(|reference| reference.builtin)
+    }
+
+    /// Returns a human-readable summary of the edge, e.g. `3 (class-like: 2, function: 1)`.
+    #[must_use]
+    pub fn label(&self) -> String {
+        let kinds = self.kinds.iter().map(|(kind, count)| format!("{kind}: {count}")).collect::<Vec<_>>().join(", ");
+
+        format!("{} ({kinds})", self.count)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for DependencyGraph {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("DependencyGraph", 2)?;
+        state.serialize_field("nodes", &self.nodes)?;
+        state.serialize_field("edges", &self.edges.values().collect::<Vec<_>>())?;
+        state.end()
+    }
+}
+
+fn node_name(namespace: &[u8]) -> String {
+    if namespace.is_empty() { GLOBAL_NODE.to_string() } else { String::from_utf8_lossy(namespace).into_owned() }
+}
+
+fn escape_dot(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn escape_mermaid(value: &str) -> String {
+    value.replace('"', "#quot;")
+}
@@ -4,11 +4,17 @@ use mago_syntax::cst::Program;
 use mago_syntax::walker::MutWalker;
 
 use crate::context::GuardContext;
+use crate::graph::DependencyGraph;
 use crate::perimeter::DependenciesGuardWalker;
+use crate::perimeter::cycles::find_cycles;
+use crate::perimeter::metrics::check_metrics;
 use crate::report::FortressReport;
+use crate::report::GraphReport;
+use crate::report::cycle::CycleScope;
 use crate::settings::Settings;
 use crate::structural::StructuralGuardWalker;
 
+pub mod graph;
 pub mod path;
 pub mod report;
 pub mod settings;
@@ -44,7 +50,9 @@ impl ArchitecturalGuard {
     ///
     /// # Returns
     ///
-    /// A `FortressReport` with all violations found and skip status.
+    /// A `FortressReport` with all violations found and skip status. When cycle detection or
+    /// metric thresholds are configured, it also holds the dependency graph of the program,
+    /// to be merged with those of the other files and passed to [`ArchitecturalGuard::check_graph`].
     #[must_use]
     pub fn check<'ast, 'arena>(
         &self,
@@ -53,6 +61,9 @@ impl ArchitecturalGuard {
         resolved_names: &'ast ResolvedNames<'arena>,
     ) -> FortressReport {
         let mut context = GuardContext::new(resolved_names, &self.settings, codebase);
+        if self.settings.perimeter.requires_dependency_graph() {
+            context.dependency_graph = Some(DependencyGraph::default());
+        }
 
         let mut missing_perimeter_configuration = false;
         let mut missing_structural_configuration = false;
@@ -80,4 +91,61 @@ impl ArchitecturalGuard {
         report.missing_structural_configuration = missing_structural_configuration;
         report
     }
+
+    /// Collects the namespace dependency graph of a program.
+    ///
+    /// Every dependency the perimeter guard would check is recorded, regardless of the
+    /// configured mode and rules.
+    ///
+    /// # Arguments
+    ///
+    /// * `codebase` - The codebase metadata for symbol lookups
+    /// * `program` - The AST of the program
+    /// * `resolved_names` - The resolved names for the program
+    #[must_use]
+    pub fn graph<'ast, 'arena>(
+        &self,
+        codebase: &CodebaseMetadata,
+        program: &'ast Program<'arena>,
+        resolved_names: &'ast ResolvedNames<'arena>,
+    ) -> DependencyGraph {
+        let mut context = GuardContext::new(resolved_names, &self.settings, codebase);
+        context.dependency_graph = Some(DependencyGraph::default());
+
+        DependenciesGuardWalker.walk_program(program, &mut context);
+
+        context.dependency_graph.unwrap_or_default()
+    }
+
+    /// Checks the dependency graph of the whole codebase for cycles and package metric thresholds.
+    ///
+    /// Cycles are looked for between namespaces and, when `layering` is configured, between layers.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - The merged dependency graphs of all checked files
+    #[must_use]
+    pub fn check_graph(&self, graph: &DependencyGraph) -> GraphReport {
+        let perimeter = &self.settings.perimeter;
+        let mut report = GraphReport::default();
+
+        if perimeter.forbid_cycles {
+            report.dependency_cycles.extend(find_cycles(graph, CycleScope::Namespace, |_| true));
+
+            if !perimeter.layering.is_empty() {
+                let layers: Vec<String> = perimeter.layering.iter().map(ToString::to_string).collect();
+                let layered_graph = graph.group_by_layers(&perimeter.layering);
+
+                report.dependency_cycles.extend(find_cycles(&layered_graph, CycleScope::Layer, |node| {
+                    layers.iter().any(|layer| layer == node)
+                }));
+            }
+        }
+
+        if !perimeter.metrics.is_empty() {
+            report.metric_violations = check_metrics(graph, &perimeter.metrics);
+        }
+
+        report
+    }
 }
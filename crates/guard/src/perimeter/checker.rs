@@ -30,9 +30,15 @@ pub fn check_usage(
     vector: BreachVector,
     span: Span,
 ) {
+    let source_namespace = ctx.get_current_namespace();
+    let builtin = ctx.dependency_graph.is_some() && is_native(ctx.codebase, dependency_fqn);
+    if let Some(graph) = &mut ctx.dependency_graph {
+        graph.add_dependency(source_namespace, dependency_fqn, dependency_kind, vector, span, builtin);
+    }
+
     if let Some(reason) = check_allowed(ctx, dependency_fqn, dependency_kind) {
         ctx.boundary_breaches.push(BoundaryBreach {
-            source_namespace: ctx.get_current_namespace().to_vec(),
+            source_namespace: source_namespace.to_vec(),
             dependency_fqn: dependency_fqn.to_vec(),
             dependency_kind,
             vector,
@@ -0,0 +1,132 @@
+use std::collections::BTreeMap;
+use std::collections::VecDeque;
+
+use crate::graph::DependencyGraph;
+use crate::report::cycle::CycleScope;
+use crate::report::cycle::DependencyCycle;
+
+/// Finds a shortest cycle in every group of mutually dependent nodes of the graph.
+///
+/// Only nodes for which `include` returns `true` are considered.
+pub fn find_cycles(graph: &DependencyGraph, scope: CycleScope, include: impl Fn(&str) -> bool) -> Vec<DependencyCycle> {
+    let nodes: Vec<&str> = graph.nodes().filter(|node| include(node)).collect();
+    let indices: BTreeMap<&str, usize> = nodes.iter().enumerate().map(|(index, node)| (*node, index)).collect();
+
+    let mut successors = vec![vec![]; nodes.len()];
+    for edge in graph.edges() {
+        if let (Some(source), Some(target)) = (indices.get(edge.source.as_str()), indices.get(edge.target.as_str())) {
+            successors[*source].push(*target);
+        }
+    }
+
+    strongly_connected_components(&successors)
+        .into_iter()
+        .filter(|component| component.len() > 1)
+        .filter_map(|component| {
+            let path = shortest_cycle(&successors, &component)?;
+
+            let edges = path
+                .iter()
+                .zip(path.iter().cycle().skip(1))
+                .filter_map(|(source, target)| graph.edge(nodes[*source], nodes[*target]).cloned())
+                .collect();
+
+            let members = component.iter().map(|member| nodes[*member].to_string()).collect();
+
+            Some(DependencyCycle { scope, edges, members })
+        })
+        .collect()
+}
+
+/// Returns the strongly connected components of the graph, using an iterative version of Tarjan's algorithm.
+///
+/// Each component is sorted, and the components are ordered by their first node.
+fn strongly_connected_components(successors: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let count = successors.len();
+    let mut indices: Vec<Option<usize>> = vec![None; count];
+    let mut low_links = vec![0; count];
+    let mut on_stack = vec![false; count];
+    let mut stack = vec![];
+    let mut components = vec![];
+    let mut next_index = 0;
+
+    for root in 0..count {
+        if indices[root].is_some() {
+            continue;
+        }
+
+        let mut work = vec![(root, 0)];
+        while let Some((node, child)) = work.pop() {
+            if child == 0 {
+                indices[node] = Some(next_index);
+                low_links[node] = next_index;
+                next_index += 1;
+                stack.push(node);
+                on_stack[node] = true;
+            }
+
+            if let Some(successor) = successors[node].get(child).copied() {
+                work.push((node, child + 1));
+
+                match indices[successor] {
+                    None => work.push((successor, 0)),
+                    Some(index) if on_stack[successor] => low_links[node] = low_links[node].min(index),
+                    Some(_) => {}
+                }
+
+                continue;
+            }
+
+            if let Some((parent, _)) = work.last() {
+                low_links[*parent] = low_links[*parent].min(low_links[node]);
+            }
+
+            if Some(low_links[node]) == indices[node] {
+                let mut component = vec![];
+                while let Some(member) = stack.pop() {
+                    on_stack[member] = false;
+                    component.push(member);
+
+                    if member == node {
+                        break;
+                    }
+                }
+
+                component.sort_unstable();
+                components.push(component);
+            }
+        }
+    }
+
+    components.sort_unstable();
+    components
+}
+
+/// Returns a shortest cycle through the first node of a strongly connected component.
+fn shortest_cycle(successors: &[Vec<usize>], component: &[usize]) -> Option<Vec<usize>> {
+    let start = *component.first()?;
+    let mut parents: BTreeMap<usize, usize> = BTreeMap::new();
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        for successor in &successors[node] {
+            if *successor == start {
+                let mut path = vec![node];
+                while let Some(parent) = parents.get(path.last()?) {
+                    path.push(*parent);
+                }
+
+                path.reverse();
+
+                return Some(path);
+            }
+
+            if component.binary_search(successor).is_ok() && !parents.contains_key(successor) {
+                parents.insert(*successor, node);
+                queue.push_back(*successor);
+            }
+        }
+    }
+
+    None
+}
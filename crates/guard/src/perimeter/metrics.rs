@@ -0,0 +1,74 @@
+use std::collections::BTreeMap;
+
+use crate::graph::DependencyGraph;
+use crate::report::metric::MetricViolation;
+use crate::report::metric::PackageMetric;
+use crate::report::metric::PackageMetrics;
+use crate::settings::MetricRatio;
+use crate::settings::MetricThresholds;
+
+/// Computes the package metrics of every namespace declared in the analyzed code.
+///
+/// Edges that only consist of references to built-in symbols are not counted as coupling.
+#[allow(clippy::float_arithmetic)]
+pub fn compute_metrics(graph: &DependencyGraph) -> Vec<PackageMetrics> {
+    let mut afferent_couplings: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut efferent_couplings: BTreeMap<&str, usize> = BTreeMap::new();
+    for edge in graph.edges().filter(|edge| !edge.is_builtin()) {
+        *efferent_couplings.entry(edge.source.as_str()).or_default() += 1;
+        *afferent_couplings.entry(edge.target.as_str()).or_default() += 1;
+    }
+
+    graph
+        .declarations()
+        .map(|(namespace, declaration)| {
+            let afferent_coupling = afferent_couplings.get(namespace).copied().unwrap_or_default();
+            let efferent_coupling = efferent_couplings.get(namespace).copied().unwrap_or_default();
+
+            let instability = if afferent_coupling + efferent_coupling == 0 {
+                0.0
+            } else {
+                efferent_coupling as f64 / (afferent_coupling + efferent_coupling) as f64
+            };
+
+            let abstractness = if declaration.class_likes == 0 {
+                0.0
+            } else {
+                declaration.abstract_class_likes as f64 / declaration.class_likes as f64
+            };
+
+            PackageMetrics {
+                namespace: namespace.to_string(),
+                span: declaration.span,
+                afferent_coupling,
+                efferent_coupling,
+                instability,
+                abstractness,
+                distance: (abstractness + instability - 1.0).abs(),
+            }
+        })
+        .collect()
+}
+
+/// Returns every metric of every namespace that exceeds its configured threshold.
+pub fn check_metrics(graph: &DependencyGraph, thresholds: &MetricThresholds) -> Vec<MetricViolation> {
+    let limits = [
+        (PackageMetric::AfferentCoupling, thresholds.max_afferent_coupling.map(|limit| limit as f64)),
+        (PackageMetric::EfferentCoupling, thresholds.max_efferent_coupling.map(|limit| limit as f64)),
+        (PackageMetric::Instability, thresholds.max_instability.map(MetricRatio::get)),
+        (PackageMetric::Distance, thresholds.max_distance.map(MetricRatio::get)),
+    ];
+
+    let mut violations = vec![];
+    for metrics in compute_metrics(graph) {
+        for (metric, threshold) in limits {
+            if let Some(threshold) = threshold
+                && metric.value(&metrics) > threshold
+            {
+                violations.push(MetricViolation { metrics: metrics.clone(), metric, threshold });
+            }
+        }
+    }
+
+    violations
+}
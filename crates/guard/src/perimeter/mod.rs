@@ -1,9 +1,15 @@
 mod checker;
 
+pub mod cycles;
+pub mod metrics;
+
 use mago_span::HasSpan;
+use mago_span::Span;
 use mago_syntax::cst::Attribute;
+use mago_syntax::cst::Class;
 use mago_syntax::cst::ClassConstantAccess;
 use mago_syntax::cst::ConstantAccess;
+use mago_syntax::cst::Enum;
 use mago_syntax::cst::Expression;
 use mago_syntax::cst::Extends;
 use mago_syntax::cst::FunctionCall;
@@ -13,11 +19,14 @@ use mago_syntax::cst::FunctionPartialApplication;
 use mago_syntax::cst::Hint;
 use mago_syntax::cst::Implements;
 use mago_syntax::cst::Instantiation;
+use mago_syntax::cst::Interface;
+use mago_syntax::cst::ModifierSequenceExt;
 use mago_syntax::cst::Namespace;
 use mago_syntax::cst::Property;
 use mago_syntax::cst::StaticMethodCall;
 use mago_syntax::cst::StaticMethodPartialApplication;
 use mago_syntax::cst::StaticPropertyAccess;
+use mago_syntax::cst::Trait;
 use mago_syntax::cst::TraitUse;
 use mago_syntax::cst::Use;
 use mago_syntax::cst::UseItems;
@@ -33,6 +42,14 @@ use crate::settings::PermittedDependencyKind;
 pub struct DependenciesGuardWalker;
 
 impl DependenciesGuardWalker {
+    /// Records a class-like declaration in the dependency graph, when one is being collected.
+    fn record_class_like(span: Span, is_abstract: bool, context: &mut GuardContext<'_, '_>) {
+        let namespace = context.get_current_namespace();
+        if let Some(graph) = &mut context.dependency_graph {
+            graph.add_class_like(namespace, span, is_abstract);
+        }
+    }
+
     fn check_hint_in_context(hint: &Hint, usage_kind: BreachVector, context: &mut GuardContext<'_, '_>) {
         Self::check_hint_recursive(hint, usage_kind, context);
     }
@@ -65,12 +82,34 @@ impl DependenciesGuardWalker {
 impl<'ast, 'ctx, 'arena> MutWalker<'ast, 'arena, GuardContext<'ctx, 'arena>> for DependenciesGuardWalker {
     fn walk_in_namespace(&mut self, namespace: &'ast Namespace<'arena>, context: &mut GuardContext<'ctx, 'arena>) {
         context.set_current_namespace(namespace.name.as_ref().map(mago_syntax::cst::Identifier::value));
+
+        if let Some(name) = &namespace.name
+            && let Some(graph) = &mut context.dependency_graph
+        {
+            graph.add_namespace(name.value(), name.span());
+        }
     }
 
     fn walk_out_namespace(&mut self, _namespace: &'ast Namespace<'arena>, context: &mut GuardContext<'ctx, 'arena>) {
         context.set_current_namespace(None);
     }
 
+    fn walk_in_class(&mut self, class: &'ast Class<'arena>, context: &mut GuardContext<'ctx, 'arena>) {
+        Self::record_class_like(class.name.span, class.modifiers.contains_abstract(), context);
+    }
+
+    fn walk_in_interface(&mut self, interface: &'ast Interface<'arena>, context: &mut GuardContext<'ctx, 'arena>) {
+        Self::record_class_like(interface.name.span, true, context);
+    }
+
+    fn walk_in_trait(&mut self, r#trait: &'ast Trait<'arena>, context: &mut GuardContext<'ctx, 'arena>) {
+        Self::record_class_like(r#trait.name.span, false, context);
+    }
+
+    fn walk_in_enum(&mut self, r#enum: &'ast Enum<'arena>, context: &mut GuardContext<'ctx, 'arena>) {
+        Self::record_class_like(r#enum.name.span, false, context);
+    }
+
     // Check use statements
     fn walk_in_use(&mut self, r#use: &'ast Use<'arena>, context: &mut GuardContext<'ctx, 'arena>) {
         match &r#use.items {
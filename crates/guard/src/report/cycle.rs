@@ -0,0 +1,103 @@
+use std::fmt;
+
+use mago_reporting::Annotation;
+use mago_reporting::Issue;
+
+use crate::graph::DependencyEdge;
+
+/// A cycle of dependencies between namespaces or layers.
+#[derive(Debug)]
+pub struct DependencyCycle {
+    /// Whether the cycle is between namespaces or between layers.
+    pub scope: CycleScope,
+    /// The edges forming the cycle, in order. The last edge leads back to the source of the first.
+    pub edges: Vec<DependencyEdge>,
+    /// All namespaces or layers that depend on each other through this and other cycles.
+    pub members: Vec<String>,
+}
+
+/// The kind of nodes a [`DependencyCycle`] is formed by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CycleScope {
+    Namespace,
+    Layer,
+}
+
+impl CycleScope {
+    /// Returns the error code for this scope.
+    #[must_use]
+    pub const fn error_code(&self) -> &'static str {
+        match self {
+            Self::Namespace => "namespace-dependency-cycle",
+            Self::Layer => "layer-dependency-cycle",
+        }
+    }
+
+    const fn plural(self) -> &'static str {
+        match self {
+            Self::Namespace => "namespaces",
+            Self::Layer => "layers",
+        }
+    }
+}
+
+impl fmt::Display for CycleScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Namespace => write!(f, "namespace"),
+            Self::Layer => write!(f, "layer"),
+        }
+    }
+}
+
+impl DependencyCycle {
+    /// Returns the cycle as a path, e.g. `` `A` → `B` → `A` ``.
+    #[must_use]
+    pub fn path(&self) -> String {
+        let mut path: Vec<String> = self.edges.iter().map(|edge| format!("`{}`", edge.source)).collect();
+        if let Some(first) = self.edges.first() {
+            path.push(format!("`{}`", first.source));
+        }
+
+        path.join(" → ")
+    }
+}
+
+impl From<DependencyCycle> for Issue {
+    fn from(cycle: DependencyCycle) -> Self {
+        let mut issue = Issue::error(format!("Dependency cycle between {}: {}", cycle.scope.plural(), cycle.path()));
+
+        for (index, edge) in cycle.edges.iter().enumerate() {
+            let Some(reference) = edge.references.first() else {
+                continue;
+            };
+
+            let annotation =
+                if index == 0 { Annotation::primary(reference.span) } else { Annotation::secondary(reference.span) };
+
+            issue = issue.with_annotation(annotation.with_message(format!(
+                "`{}` depends on `{}` through `{}`",
+                edge.source, edge.target, reference.symbol
+            )));
+        }
+
+        for edge in &cycle.edges {
+            issue = issue.with_note(format!("`{}` → `{}`: {}.", edge.source, edge.target, edge.label()));
+        }
+
+        if cycle.members.len() > cycle.edges.len() {
+            let members = cycle.members.iter().map(|member| format!("`{member}`")).collect::<Vec<_>>().join(", ");
+
+            issue = issue.with_note(format!(
+                "This cycle is part of a larger group of mutually dependent {}: {members}.",
+                cycle.scope.plural()
+            ));
+        }
+
+        issue.with_help(format!(
+            "Break the cycle by removing or inverting one of these dependencies, \
+             for example by depending on an abstraction owned by the other {}.",
+            cycle.scope
+        ))
+    }
+}
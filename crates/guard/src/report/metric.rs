@@ -0,0 +1,123 @@
+use std::fmt;
+
+use mago_reporting::Annotation;
+use mago_reporting::Issue;
+use mago_span::Span;
+
+/// The Robert C. Martin package metrics of a namespace.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PackageMetrics {
+    /// The namespace the metrics are computed for.
+    pub namespace: String,
+    /// The location of the first declaration of the namespace.
+    pub span: Span,
+    /// The number of namespaces that depend on this namespace (Ca).
+    pub afferent_coupling: usize,
+    /// The number of namespaces this namespace depends on (Ce).
+    pub efferent_coupling: usize,
+    /// `Ce / (Ca + Ce)`, or `0.0` for a namespace without any coupling (I).
+    pub instability: f64,
+    /// The ratio of interfaces and abstract classes to all class-likes in the namespace (A).
+    pub abstractness: f64,
+    /// The distance from the main sequence, `|A + I - 1|` (D).
+    pub distance: f64,
+}
+
+/// A package metric of a namespace that exceeds its configured threshold.
+#[derive(Debug)]
+pub struct MetricViolation {
+    /// The metrics of the namespace.
+    pub metrics: PackageMetrics,
+    /// The metric that exceeds its threshold.
+    pub metric: PackageMetric,
+    /// The configured threshold.
+    pub threshold: f64,
+}
+
+/// The package metrics that can be given a threshold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackageMetric {
+    AfferentCoupling,
+    EfferentCoupling,
+    Instability,
+    Distance,
+}
+
+impl PackageMetric {
+    /// Returns the error code for this metric.
+    #[must_use]
+    pub const fn error_code(&self) -> &'static str {
+        match self {
+            Self::AfferentCoupling => "excessive-afferent-coupling",
+            Self::EfferentCoupling => "excessive-efferent-coupling",
+            Self::Instability => "excessive-instability",
+            Self::Distance => "excessive-distance-from-main-sequence",
+        }
+    }
+
+    /// Returns the value of this metric in `metrics`.
+    #[must_use]
+    pub fn value(&self, metrics: &PackageMetrics) -> f64 {
+        match self {
+            Self::AfferentCoupling => metrics.afferent_coupling as f64,
+            Self::EfferentCoupling => metrics.efferent_coupling as f64,
+            Self::Instability => metrics.instability,
+            Self::Distance => metrics.distance,
+        }
+    }
+
+    fn format(self, value: f64) -> String {
+        match self {
+            Self::AfferentCoupling | Self::EfferentCoupling => format!("{value:.0}"),
+            Self::Instability | Self::Distance => format!("{value:.2}"),
+        }
+    }
+}
+
+impl fmt::Display for PackageMetric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AfferentCoupling => write!(f, "afferent coupling"),
+            Self::EfferentCoupling => write!(f, "efferent coupling"),
+            Self::Instability => write!(f, "instability"),
+            Self::Distance => write!(f, "distance from the main sequence"),
+        }
+    }
+}
+
+impl From<MetricViolation> for Issue {
+    fn from(violation: MetricViolation) -> Self {
+        let metrics = &violation.metrics;
+        let value = violation.metric.value(metrics);
+
+        let issue = Issue::warning(format!(
+            "Namespace `{}` exceeds the maximum {} ({} > {})",
+            metrics.namespace,
+            violation.metric,
+            violation.metric.format(value),
+            violation.metric.format(violation.threshold)
+        ))
+        .with_annotation(Annotation::primary(metrics.span).with_message("Namespace declared here"))
+        .with_note(format!(
+            "Ca = {}, Ce = {}, I = {:.2}, A = {:.2}, D = {:.2}.",
+            metrics.afferent_coupling,
+            metrics.efferent_coupling,
+            metrics.instability,
+            metrics.abstractness,
+            metrics.distance
+        ));
+
+        let help = match violation.metric {
+            PackageMetric::AfferentCoupling => "Split this namespace so that fewer namespaces depend on each part.",
+            PackageMetric::EfferentCoupling => "Reduce the number of namespaces this namespace depends on.",
+            PackageMetric::Instability => {
+                "Depend on fewer namespaces, or move code that others rely on into this namespace."
+            }
+            PackageMetric::Distance => {
+                "Balance abstractness and stability: stable namespaces should be abstract, unstable ones concrete."
+            }
+        };
+
+        issue.with_help(help)
+    }
+}
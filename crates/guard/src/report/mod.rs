@@ -2,14 +2,20 @@ use mago_allocator::Arena;
 
 use mago_collector::Collector;
 use mago_database::file::File;
+use mago_reporting::Issue;
 use mago_reporting::IssueCollection;
 use mago_syntax::cst::Program;
 
+use crate::graph::DependencyGraph;
 use crate::report::breach::BoundaryBreach;
+use crate::report::cycle::DependencyCycle;
 use crate::report::flaw::StructuralFlaw;
+use crate::report::metric::MetricViolation;
 
 pub mod breach;
+pub mod cycle;
 pub mod flaw;
+pub mod metric;
 
 const COLLECTOR_CATEGORIES: &[&str] = &["guard"];
 
@@ -21,6 +27,15 @@ pub struct FortressReport {
     pub missing_perimeter_configuration: bool,
     /// Whether structural guard was skipped due to missing configuration.
     pub missing_structural_configuration: bool,
+    /// The dependency graph of the program, when cycle detection or metric thresholds are configured.
+    pub dependency_graph: Option<DependencyGraph>,
+}
+
+/// The result of checking the dependency graph of the whole codebase.
+#[derive(Debug, Default)]
+pub struct GraphReport {
+    pub dependency_cycles: Vec<DependencyCycle>,
+    pub metric_violations: Vec<MetricViolation>,
 }
 
 impl FortressReport {
@@ -45,3 +60,28 @@ impl FortressReport {
         collector.finish()
     }
 }
+
+impl GraphReport {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.dependency_cycles.is_empty() && self.metric_violations.is_empty()
+    }
+
+    #[must_use]
+    pub fn into_issues(self) -> IssueCollection {
+        let mut issues = IssueCollection::new();
+        for dependency_cycle in self.dependency_cycles {
+            let code = dependency_cycle.scope.error_code();
+
+            issues.push(Issue::from(dependency_cycle).with_code(code));
+        }
+
+        for metric_violation in self.metric_violations {
+            let code = metric_violation.metric.error_code();
+
+            issues.push(Issue::from(metric_violation).with_code(code));
+        }
+
+        issues
+    }
+}
@@ -42,6 +42,52 @@ pub struct PerimeterSettings {
     pub rules: Vec<PerimeterRule>,
     /// Target-oriented dependency restrictions applied before ordinary perimeter rules.
     pub restrictions: Vec<DependencyRestriction>,
+    /// Whether dependency cycles between namespaces, and between `layering` layers, are reported.
+    pub forbid_cycles: bool,
+    /// Thresholds for the package metrics of each namespace.
+    pub metrics: MetricThresholds,
+}
+
+/// Upper bounds for the Robert C. Martin package metrics of each namespace.
+///
+/// Coupling is counted in namespaces: the afferent coupling of a namespace is the number of
+/// namespaces that depend on it, and its efferent coupling the number of namespaces it depends on.
+/// Dependencies on built-in symbols are not counted.
+#[derive(Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, rename_all = "kebab-case", deny_unknown_fields))]
+pub struct MetricThresholds {
+    /// The maximum number of namespaces that may depend on a namespace (Ca).
+    pub max_afferent_coupling: Option<usize>,
+    /// The maximum number of namespaces a namespace may depend on (Ce).
+    pub max_efferent_coupling: Option<usize>,
+    /// The maximum instability, `Ce / (Ca + Ce)`.
+    pub max_instability: Option<MetricRatio>,
+    /// The maximum distance from the main sequence, `|A + I - 1|`.
+    pub max_distance: Option<MetricRatio>,
+}
+
+/// A package metric ratio threshold, between `0.0` and `1.0` inclusive.
+///
+/// Ratios are validated on construction, so they are never `NaN` and can be compared with `Eq`.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, JsonSchema)]
+#[schemars(with = "f64")]
+pub struct MetricRatio(f64);
+
+impl Eq for MetricRatio {}
+
+impl MetricRatio {
+    /// Creates a ratio, returning `None` if `value` is not between `0.0` and `1.0`.
+    #[must_use]
+    pub fn new(value: f64) -> Option<Self> {
+        (0.0..=1.0).contains(&value).then_some(Self(value))
+    }
+
+    /// Returns the ratio as a floating-point number.
+    #[must_use]
+    pub const fn get(self) -> f64 {
+        self.0
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, JsonSchema)]
@@ -78,7 +124,7 @@ pub enum PermittedDependency {
 }
 
 /// Represents the specific types of symbols allowed from a path.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, JsonSchema)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, JsonSchema)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
 pub enum PermittedDependencyKind {
@@ -286,6 +332,29 @@ impl<'de> serde::Deserialize<'de> for StructuralInheritanceConstraint {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for MetricRatio {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = f64::deserialize(deserializer)?;
+
+        MetricRatio::new(value)
+            .ok_or_else(|| de::Error::custom(format!("Invalid ratio `{value}`: must be between 0.0 and 1.0.")))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for MetricRatio {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_f64(self.0)
+    }
+}
+
 #[cfg(feature = "serde")]
 impl serde::Serialize for PermittedDependency {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -345,10 +414,30 @@ impl fmt::Display for StructuralSymbolKind {
 }
 
 impl PerimeterSettings {
-    /// Returns true if there are no perimeter rules, restrictions, or layering configured.
+    /// Returns true if there are no perimeter rules, restrictions, layering, or graph checks configured.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+            && self.restrictions.is_empty()
+            && self.layering.is_empty()
+            && !self.requires_dependency_graph()
+    }
+
+    /// Returns true if cycle detection or metric thresholds require the dependency graph of the codebase.
+    #[must_use]
+    pub fn requires_dependency_graph(&self) -> bool {
+        self.forbid_cycles || !self.metrics.is_empty()
+    }
+}
+
+impl MetricThresholds {
+    /// Returns true if no threshold is configured.
     #[must_use]
     pub fn is_empty(&self) -> bool {
-        self.rules.is_empty() && self.restrictions.is_empty() && self.layering.is_empty()
+        self.max_afferent_coupling.is_none()
+            && self.max_efferent_coupling.is_none()
+            && self.max_instability.is_none()
+            && self.max_distance.is_none()
     }
 }
 
@@ -7,19 +7,25 @@ use foldhash::HashSet;
 use indoc::indoc;
 use mago_allocator::LocalArena;
 
+use mago_codex::metadata::CodebaseMetadata;
 use mago_codex::populator::populate_codebase;
 use mago_codex::scanner::scan_program;
 use mago_database::DatabaseReader;
 use mago_database::file::File;
 use mago_guard::ArchitecturalGuard;
+use mago_guard::graph::DependencyGraph;
 use mago_guard::path::NamespacePath;
 use mago_guard::path::Path;
 use mago_guard::path::SymbolSelector;
 use mago_guard::report::FortressReport;
 use mago_guard::report::breach::BreachReason;
 use mago_guard::report::breach::BreachVector;
+use mago_guard::report::cycle::CycleScope;
 use mago_guard::report::flaw::FlawKind;
+use mago_guard::report::metric::PackageMetric;
 use mago_guard::settings::DependencyRestriction;
+use mago_guard::settings::MetricRatio;
+use mago_guard::settings::MetricThresholds;
 use mago_guard::settings::PerimeterRule;
 use mago_guard::settings::PerimeterSettings;
 use mago_guard::settings::PermittedDependency;
@@ -28,8 +34,10 @@ use mago_guard::settings::Settings;
 use mago_guard::settings::StructuralRule;
 use mago_guard::settings::StructuralSettings;
 use mago_guard::settings::StructuralSymbolKind;
+use mago_names::ResolvedNames;
 use mago_names::resolver::NameResolver;
 use mago_prelude::Prelude;
+use mago_syntax::cst::Program;
 use mago_syntax::parser::parse_file;
 use mago_word::WordSet;
 
@@ -51,6 +59,22 @@ fn deny_all_settings() -> Settings {
 }
 
 fn test_guard(name: &'static str, code: &'static str, settings: Settings) -> FortressReport {
+    let guard = ArchitecturalGuard::new(settings);
+
+    with_program(name, code, |metadata, program, resolved_names| guard.check(metadata, program, resolved_names))
+}
+
+fn test_graph(name: &'static str, code: &'static str) -> DependencyGraph {
+    let guard = ArchitecturalGuard::new(Settings::default());
+
+    with_program(name, code, |metadata, program, resolved_names| guard.graph(metadata, program, resolved_names))
+}
+
+fn with_program<T>(
+    name: &'static str,
+    code: &'static str,
+    f: impl for<'arena> FnOnce(&CodebaseMetadata, &Program<'arena>, &ResolvedNames<'arena>) -> T,
+) -> T {
     let Prelude { mut database, mut metadata, mut symbol_references } = PRELUDE.clone();
 
     let file = File::ephemeral(Cow::Borrowed(name.as_bytes()), Cow::Borrowed(code.as_bytes()));
@@ -70,8 +94,7 @@ fn test_guard(name: &'static str, code: &'static str, settings: Settings) -> For
 
     populate_codebase(&mut metadata, &mut symbol_references, WordSet::default(), HashSet::default());
 
-    let guard = ArchitecturalGuard::new(settings);
-    guard.check(&metadata, program, &resolved_names)
+    f(&metadata, program, &resolved_names)
 }
 
 #[test]
@@ -845,3 +868,211 @@ pub fn test_is_final_annotation_counted() {
         "Expected non violations: Should allow declare final class with annotation"
     );
 }
+
+#[test]
+pub fn test_dependency_graph() {
+    let code = indoc! {r"
+        <?php
+
+        namespace App\Http;
+
+        use App\Domain\User;
+        use App\Domain\UserRepository;
+
+        #[\App\Routing\Route('/users')]
+        final class UserController
+        {
+            public function __construct(private UserRepository $users) {}
+
+            public function show(int $id): User
+            {
+                return \App\Support\cached(fn() => $this->users->find($id), \App\Support\TTL);
+            }
+        }
+    "};
+
+    let graph = test_graph("graph.php", code);
+
+    let nodes: Vec<&str> = graph.nodes().collect();
+    assert_eq!(nodes, vec!["App\\Domain", "App\\Http", "App\\Routing", "App\\Support"]);
+
+    let edges: Vec<(&str, &str, usize, String)> =
+        graph.edges().map(|edge| (edge.source.as_str(), edge.target.as_str(), edge.count, edge.label())).collect();
+
+    assert_eq!(
+        edges,
+        vec![
+            ("App\\Http", "App\\Domain", 4, "4 (class-like: 4)".to_string()),
+            ("App\\Http", "App\\Routing", 1, "1 (attribute: 1)".to_string()),
+            ("App\\Http", "App\\Support", 2, "2 (function: 1, constant: 1)".to_string()),
+        ]
+    );
+
+    let layers = graph.group_by_layers(&[
+        NamespacePath::Specific("App\\Http\\".to_string()),
+        NamespacePath::Specific("App\\Domain\\".to_string()),
+    ]);
+
+    assert_eq!(
+        layers.to_mermaid(),
+        indoc! {r#"
+            flowchart LR
+                n0["App\Domain\"]
+                n1["App\Http\"]
+                n2["App\Routing"]
+                n3["App\Support"]
+                n1 -->|"4 (class-like: 4)"| n0
+                n1 -->|"1 (attribute: 1)"| n2
+                n1 -->|"2 (function: 1, constant: 1)"| n3
+        "#}
+    );
+}
+
+#[test]
+pub fn test_dependency_cycles() {
+    let code = indoc! {r"
+        <?php
+
+        namespace App\Billing {
+            use App\Shipping\Shipment;
+
+            final class Invoice
+            {
+                public function ship(): Shipment
+                {
+                    return new Shipment($this);
+                }
+            }
+        }
+
+        namespace App\Shipping {
+            use App\Billing\Invoice;
+
+            final class Shipment
+            {
+                public function __construct(public Invoice $invoice) {}
+            }
+        }
+
+        namespace App\Http {
+            final class Controller
+            {
+                public function __invoke(\App\Billing\Invoice $invoice): void {}
+            }
+        }
+    "};
+
+    let settings = Settings {
+        perimeter: PerimeterSettings {
+            layering: vec![
+                NamespacePath::Specific("App\\Http\\".to_string()),
+                NamespacePath::Specific("App\\Billing\\".to_string()),
+                NamespacePath::Specific("App\\Shipping\\".to_string()),
+            ],
+            forbid_cycles: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let guard = ArchitecturalGuard::new(settings.clone());
+    let mut report = test_guard("dependency_cycles", code, settings);
+    let graph = report.dependency_graph.take().expect("The dependency graph should be collected");
+    let graph_report = guard.check_graph(&graph);
+
+    assert_eq!(graph_report.dependency_cycles.len(), 2);
+
+    let namespace_cycle = &graph_report.dependency_cycles[0];
+    assert_eq!(namespace_cycle.scope, CycleScope::Namespace);
+    assert_eq!(namespace_cycle.path(), "`App\\Billing` → `App\\Shipping` → `App\\Billing`");
+    assert_eq!(namespace_cycle.edges[0].count, 3);
+    assert_eq!(namespace_cycle.edges[0].references[0].symbol, "App\\Shipping\\Shipment");
+    assert_eq!(namespace_cycle.edges[1].count, 2);
+
+    let layer_cycle = &graph_report.dependency_cycles[1];
+    assert_eq!(layer_cycle.scope, CycleScope::Layer);
+    assert_eq!(layer_cycle.members, vec!["App\\Billing\\".to_string(), "App\\Shipping\\".to_string()]);
+}
+
+#[test]
+pub fn test_package_metrics() {
+    let code = indoc! {r"
+        <?php
+
+        namespace App\Domain {
+            interface Repository {}
+            abstract class Entity {}
+            final class User extends Entity {}
+        }
+
+        namespace App\Infrastructure {
+            use App\Domain\Repository;
+            use App\Domain\User;
+
+            final class UserRepository implements Repository
+            {
+                public function find(): User
+                {
+                    return new User();
+                }
+            }
+        }
+
+        namespace App\Http {
+            use App\Domain\User;
+            use App\Infrastructure\UserRepository;
+
+            final class Controller
+            {
+                public function __construct(private UserRepository $users) {}
+
+                public function show(): User
+                {
+                    return $this->users->find();
+                }
+            }
+        }
+    "};
+
+    let settings = Settings {
+        perimeter: PerimeterSettings {
+            metrics: MetricThresholds {
+                max_efferent_coupling: Some(1),
+                max_distance: MetricRatio::new(0.5),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let guard = ArchitecturalGuard::new(settings.clone());
+    let mut report = test_guard("package_metrics", code, settings);
+    let graph = report.dependency_graph.take().expect("The dependency graph should be collected");
+    let graph_report = guard.check_graph(&graph);
+
+    let violations: Vec<(&str, PackageMetric)> = graph_report
+        .metric_violations
+        .iter()
+        .map(|violation| (violation.metrics.namespace.as_str(), violation.metric))
+        .collect();
+
+    // `App\Domain`: Ca = 2, Ce = 0, I = 0.00, A = 0.67, D = 0.33
+    // `App\Http`: Ca = 0, Ce = 2, I = 1.00, A = 0.00, D = 0.00
+    // `App\Infrastructure`: Ca = 1, Ce = 1, I = 0.50, A = 0.00, D = 0.50
+    assert_eq!(violations, vec![("App\\Http", PackageMetric::EfferentCoupling)]);
+
+    let http = &graph_report.metric_violations[0].metrics;
+    assert_eq!(http.afferent_coupling, 0);
+    assert_eq!(http.efferent_coupling, 2);
+}
+
+#[test]
+pub fn test_metric_ratio_bounds() {
+    assert_eq!(MetricRatio::new(0.5).map(MetricRatio::get), Some(0.5));
+    assert_eq!(MetricRatio::new(0.0).map(MetricRatio::get), Some(0.0));
+    assert_eq!(MetricRatio::new(1.0).map(MetricRatio::get), Some(1.0));
+    assert_eq!(MetricRatio::new(1.5), None);
+    assert_eq!(MetricRatio::new(-0.1), None);
+    assert_eq!(MetricRatio::new(f64::NAN), None);
+}
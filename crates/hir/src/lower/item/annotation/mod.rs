@@ -293,6 +293,7 @@ where
             TagValue::Pure(_) => ItemAnnotationTag::Pure,
             TagValue::Impure(_) => ItemAnnotationTag::Impure,
             TagValue::Readonly(_) => ItemAnnotationTag::Readonly,
+            TagValue::Immutable(_) => ItemAnnotationTag::Immutable,
             TagValue::MustUse(_) => ItemAnnotationTag::MustUse,
             TagValue::NoNamedArguments(_) => ItemAnnotationTag::NoNamedArguments,
             TagValue::NotDeprecated(_) => ItemAnnotationTag::NotDeprecated,
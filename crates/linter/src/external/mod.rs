@@ -26,9 +26,11 @@ use crate::rule::AnyRule;
 use crate::settings::Settings;
 
 pub use error::ExternalLintError;
+pub use probe::ProtocolProbe;
 use protocol::Registration;
 
 mod error;
+mod probe;
 pub mod protocol;
 
 const SLOW_FILE_THRESHOLD: Duration = Duration::from_millis(10);
@@ -471,6 +473,17 @@ mod tests {
 
     use mago_allocator::LocalArena;
     use mago_database::file::File;
+    use mago_extension::Frame;
+    use mago_extension::WorkerRequestHandler;
+    use mago_extension_sdk::Annotation as SdkAnnotation;
+    use mago_extension_sdk::Extension as SdkExtension;
+    use mago_extension_sdk::Issue as SdkIssue;
+    use mago_extension_sdk::LintContext;
+    use mago_extension_sdk::Rule as SdkRule;
+    use mago_extension_sdk::RuleDefinition;
+    use mago_extension_sdk::TextEdit as SdkTextEdit;
+    use mago_extension_sdk::Worker;
+    use mago_extension_sdk::reporting::Safety as SdkSafety;
     use mago_names::resolver::NameResolver;
     use mago_reporting::Annotation;
     use mago_reporting::Issue;
@@ -486,6 +499,25 @@ mod tests {
     use super::protocol::testing;
     use super::*;
 
+    struct SdkNoCall;
+
+    impl SdkRule for SdkNoCall {
+        fn definition(&self) -> RuleDefinition {
+            RuleDefinition::new("no-call", "No calls", "Disallows calls.").with_target(NodeKind::FunctionCall)
+        }
+
+        fn lint(&mut self, context: &mut LintContext<'_>) {
+            let node = context.node();
+            let message = format!("Do not call `{}`.", String::from_utf8_lossy(node.text()));
+
+            context.report(
+                SdkIssue::new(message)
+                    .with_annotation(SdkAnnotation::primary(node.span()).with_message("Called here."))
+                    .with_edit(SdkTextEdit::delete(node.span()).with_safety(SdkSafety::Unsafe)),
+            );
+        }
+    }
+
     #[derive(Debug)]
     struct MockTransport {
         registration: Vec<u8>,
@@ -765,4 +797,98 @@ mod tests {
         assert!(external.contains_rule("acme/prefer-array-any"));
         assert!(external.contains_rule("acme/no-interface"));
     }
+
+    #[test]
+    fn rejects_truncated_and_corrupted_lint_responses_without_panicking() {
+        let source = b"<?php\nfoo();\n";
+        let file = File::ephemeral(Cow::Borrowed(b"test.php"), Cow::Borrowed(source));
+        let rules = [ExternalRule {
+            code: "acme/no-foo".to_string(),
+            name: "No foo".to_string(),
+            description: "Disallows foo.".to_string(),
+            default_level: Level::Warning,
+            default_enabled: true,
+            targets: vec![NodeKind::FunctionCall],
+        }];
+        let issue = Issue::warning("Do not call foo")
+            .with_code("acme/no-foo")
+            .with_annotation(Annotation::primary(Span::new(
+                file.id,
+                mago_span::Position::new(6),
+                mago_span::Position::new(11),
+            )))
+            .with_edit(file.id, TextEdit::replace(6..9, "bar"));
+        let response = testing::lint_response(&[(0, issue)]);
+        protocol::decode_lint_response(&response, &file, &rules, &[0]).unwrap();
+
+        for length in 0..response.len() {
+            let result = protocol::decode_lint_response(&response[..length], &file, &rules, &[0]);
+            assert!(result.is_err(), "a response truncated to {length} bytes should be rejected");
+        }
+
+        for index in 0..response.len() {
+            for value in [0x00, 0x7f, 0xff] {
+                let mut corrupted = response.clone();
+                corrupted[index] = value;
+                let _result = protocol::decode_lint_response(&corrupted, &file, &rules, &[0]);
+            }
+        }
+    }
+
+    #[test]
+    fn lint_requests_round_trip_through_the_rust_sdk() {
+        let mut worker = Worker::new(SdkExtension::new("acme/tools", "Acme Tools", "1.0.0").with_rule(SdkNoCall));
+        let mut serve = |payload| worker.handle(&Frame::request(1, payload)).expect("the SDK worker should answer");
+
+        let description = serve(protocol::encode_describe_request(PHPVersion::PHP85));
+        let registration = protocol::decode_registration(&description).expect("the SDK registration should decode");
+        assert_eq!(registration.rules.len(), 1);
+        assert_eq!(registration.rules[0].code, "no-call");
+        assert_eq!(registration.rules[0].default_level, Level::Warning);
+        assert_eq!(registration.rules[0].targets, [NodeKind::FunctionCall]);
+
+        let source = b"<?php\n\nfoo();\n";
+        let arena = LocalArena::new();
+        let file = File::ephemeral(Cow::Borrowed(b"src/test.php"), Cow::Borrowed(source));
+        let program = parse_file(&arena, &file);
+        let resolved_names = NameResolver::new(&arena).resolve(program);
+        let mut target_kinds = [false; u8::MAX as usize + 1];
+        target_kinds[NodeKind::FunctionCall as usize] = true;
+        let request = protocol::encode_lint_request(&file, program, &resolved_names, &[0], &target_kinds, false)
+            .expect("the lint request should encode")
+            .expect("the file contains a targeted node");
+
+        let response = serve(request.payload);
+        let issues = protocol::decode_lint_response(&response, &file, &registration.rules, &[0])
+            .expect("the SDK lint response should decode");
+        assert_eq!(issues.len(), 1);
+        let issue = issues.iter().next().expect("the SDK issue should exist");
+        assert_eq!(issue.message, "Do not call `foo()`.");
+        assert_eq!(issue.annotations.len(), 1);
+        assert_eq!((issue.annotations[0].span.start.offset, issue.annotations[0].span.end.offset), (7, 12));
+        assert_eq!(issue.annotations[0].message.as_deref(), Some("Called here."));
+        let edits = issue.edits.get(&file.id).expect("the SDK issue should carry its edit");
+        assert_eq!(edits.len(), 1);
+        assert_eq!((edits[0].range.start, edits[0].range.end), (7, 12));
+        assert!(edits[0].new_text.is_empty());
+        assert_eq!(edits[0].safety, Safety::Unsafe);
+    }
+
+    #[test]
+    fn builds_protocol_probe_payloads() {
+        let describe = protocol::encode_describe_request(PHPVersion::PHP85);
+
+        let truncated = ProtocolProbe::TruncatedRequest.payload(PHPVersion::PHP85, 1024);
+        assert!(describe.starts_with(&truncated) && truncated.len() < describe.len());
+
+        let trailing = ProtocolProbe::TrailingBytes.payload(PHPVersion::PHP85, 1024);
+        assert!(trailing.starts_with(&describe) && trailing.len() > describe.len());
+
+        let unknown = ProtocolProbe::UnknownMessage.payload(PHPVersion::PHP85, 1024);
+        assert_eq!(unknown[..4], protocol::LINTER_PROTOCOL_MAGIC);
+        assert_ne!(unknown[..12], describe[..12]);
+
+        assert_eq!(ProtocolProbe::MaximumPayload.payload(PHPVersion::PHP85, 1024).len(), 1024);
+        assert_eq!(ProtocolProbe::Timeout.payload(PHPVersion::PHP85, 1024), describe);
+    }
 }
@@ -0,0 +1,80 @@
+//! Protocol edge cases for checking that linter workers fail cleanly.
+//!
+//! Each probe is a request a conforming worker must reject with an error
+//! response while staying alive and in sync with its frame stream. They back
+//! `mago extension test`, which gives extension authors the same checks in CI
+//! that Mago relies on at runtime.
+
+use mago_php_version::PHPVersion;
+
+use super::protocol;
+
+/// A malformed or extreme request sent to a linter worker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProtocolProbe {
+    /// A payload that does not start with the magic of any Mago capability.
+    UnknownCapability,
+    /// A linter message whose kind the protocol does not define.
+    UnknownMessage,
+    /// A describe request cut off in the middle of its node-kind table.
+    TruncatedRequest,
+    /// A complete describe request followed by unexpected bytes.
+    TrailingBytes,
+    /// An unrecognized payload of exactly the maximum frame size.
+    MaximumPayload,
+    /// A describe request whose deadline expires before the worker can answer.
+    Timeout,
+}
+
+impl ProtocolProbe {
+    /// Every probe, in the order `mago extension test` runs them.
+    pub const ALL: [Self; 6] = [
+        Self::UnknownCapability,
+        Self::UnknownMessage,
+        Self::TruncatedRequest,
+        Self::TrailingBytes,
+        Self::MaximumPayload,
+        Self::Timeout,
+    ];
+
+    /// Returns the kebab-case name used in reports.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::UnknownCapability => "unknown-capability",
+            Self::UnknownMessage => "unknown-message",
+            Self::TruncatedRequest => "truncated-request",
+            Self::TrailingBytes => "trailing-bytes",
+            Self::MaximumPayload => "maximum-payload",
+            Self::Timeout => "timeout",
+        }
+    }
+
+    /// Builds the request payload for this probe.
+    ///
+    /// `maximum_payload_size` is the frame limit of the worker pool the probe
+    /// is sent to; only [`ProtocolProbe::MaximumPayload`] depends on it.
+    #[must_use]
+    pub fn payload(self, php_version: PHPVersion, maximum_payload_size: usize) -> Vec<u8> {
+        match self {
+            Self::UnknownCapability => b"MZZZ".to_vec(),
+            Self::UnknownMessage => protocol::encode_unknown_request(),
+            Self::TruncatedRequest => {
+                let mut payload = protocol::encode_describe_request(php_version);
+                payload.truncate(payload.len() / 2);
+                payload
+            }
+            Self::TrailingBytes => {
+                let mut payload = protocol::encode_describe_request(php_version);
+                payload.extend_from_slice(&[0; 8]);
+                payload
+            }
+            Self::MaximumPayload => {
+                let mut payload = vec![0; maximum_payload_size.max(4)];
+                payload[..4].copy_from_slice(b"MZZZ");
+                payload
+            }
+            Self::Timeout => protocol::encode_describe_request(php_version),
+        }
+    }
+}
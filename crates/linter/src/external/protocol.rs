@@ -17,6 +17,11 @@ use mago_extension::PayloadWriter;
 use mago_extension::source::NO_NODE;
 use mago_extension::source::SourceSnapshot;
 use mago_extension::source::write_node_kind_table;
+use mago_extension::wire::HEADER_LENGTH;
+use mago_extension::wire::linter::DESCRIBE_REQUEST;
+use mago_extension::wire::linter::DESCRIBE_RESPONSE;
+use mago_extension::wire::linter::LINT_FILE_REQUEST;
+use mago_extension::wire::linter::LINT_FILE_RESPONSE;
 use mago_names::ResolvedNames;
 use mago_php_version::PHPVersion;
 use mago_reporting::Annotation;
@@ -37,15 +42,11 @@ use strum::IntoEnumIterator;
 use super::ExternalLintError;
 use super::ExternalRule;
 
-pub const LINTER_PROTOCOL_MAGIC: [u8; 4] = *b"MLNT";
-pub const LINTER_PROTOCOL_MAJOR: u16 = 1;
-pub const LINTER_PROTOCOL_MINOR: u16 = 0;
+pub use mago_extension::wire::linter::MAGIC as LINTER_PROTOCOL_MAGIC;
+pub use mago_extension::wire::linter::MAJOR as LINTER_PROTOCOL_MAJOR;
+pub use mago_extension::wire::linter::MINOR as LINTER_PROTOCOL_MINOR;
 
-const HEADER_LENGTH: usize = 12;
-const DESCRIBE_REQUEST: u16 = 1;
-const LINT_FILE_REQUEST: u16 = 2;
-const DESCRIBE_RESPONSE: u16 = 0x8001;
-const LINT_FILE_RESPONSE: u16 = 0x8002;
+const UNDEFINED_REQUEST: u16 = 0x7fff;
 const MAXIMUM_EXTENSIONS: usize = 0x4000;
 const MAXIMUM_EXTENSIONS_RULES: usize = 0x4000;
 const MAXIMUM_TARGETS_PER_RULE: usize = 512;
@@ -80,6 +81,11 @@ pub(super) fn encode_describe_request(php_version: PHPVersion) -> Vec<u8> {
     writer.finish()
 }
 
+/// Encodes a well-formed header for a message kind no protocol version defines.
+pub(super) fn encode_unknown_request() -> Vec<u8> {
+    message_writer(UNDEFINED_REQUEST).finish()
+}
+
 pub(super) fn decode_registration(payload: &[u8]) -> Result<Registration, ExternalLintError> {
     let mut reader = message_reader(payload, DESCRIBE_RESPONSE)?;
     let extension_count = reader.read_count("extensions", MAXIMUM_EXTENSIONS)?;
@@ -12,6 +12,10 @@ use std::sync::Arc;
 use mago_allocator::prelude::*;
 use mago_collector::Collector;
 use mago_database::file::File;
+use mago_hir::ir::IR;
+use mago_hir::lower::LowerSettings;
+use mago_hir::lower::Lowering;
+use mago_hir::node::Node as HirNode;
 use mago_names::ResolvedNames;
 use mago_php_version::PHPVersion;
 use mago_reporting::IssueCollection;
@@ -152,6 +156,13 @@ where
 
         walk(Node::Program(program), &mut context, excluded_rules.as_slice());
 
+        if self.registry.requires_hir() {
+            let ir: IR<'arena, (), (), ()> =
+                Lowering::new(self.arena, self.arena, source_file, program, LowerSettings::default()).lower();
+
+            walk_hir(HirNode::Ir(&ir), &mut context, excluded_rules.as_slice());
+        }
+
         if let Some(external) = external {
             let external_issues = external.lint(source_file, program, resolved_names, self.registry.only.as_deref())?;
             context.collector.extend(external_issues);
@@ -232,3 +243,28 @@ where
         }
     }
 }
+
+fn walk_hir<'arena, A>(
+    root: HirNode<'_, 'arena, (), (), ()>,
+    ctx: &mut LintContext<'_, 'arena, A>,
+    excluded_rules: &[usize],
+) where
+    A: Arena,
+{
+    let mut stack = Vec::with_capacity_in(64, ctx.arena);
+    stack.push(root);
+
+    while let Some(node) = stack.pop() {
+        for &rule_index in ctx.registry.for_hir_kind(node.kind()) {
+            if excluded_rules.contains(&rule_index) {
+                continue;
+            }
+
+            ctx.registry.rule(rule_index).check_hir(ctx, node);
+        }
+
+        let start = stack.len();
+        node.visit_children(|child| stack.push(child));
+        stack[start..].reverse();
+    }
+}
@@ -1,4 +1,5 @@
 use mago_database::matcher::ExclusionMatcher;
+use mago_hir::node::NodeKind as HirNodeKind;
 use mago_syntax::cst::NodeKind;
 
 use crate::integration::Integration;
@@ -14,6 +15,7 @@ pub struct RuleRegistry {
     rules: Vec<AnyRule>,
     rule_excludes: Vec<ExclusionMatcher<String>>,
     by_kind: Vec<Box<[usize]>>,
+    by_hir_kind: Vec<Box<[usize]>>,
 }
 
 impl RuleRegistry {
@@ -56,7 +58,16 @@ impl RuleRegistry {
 
         let by_kind: Vec<Box<[usize]>> = temp.into_iter().map(|v| v.into_boxed_slice()).collect();
 
-        Self { only, integrations, rules, rule_excludes, by_kind }
+        let mut temp: Vec<Vec<usize>> = vec![Vec::new(); max_kind];
+        for (i, r) in rules.iter().enumerate() {
+            for &k in r.hir_targets() {
+                temp[k as usize].push(i);
+            }
+        }
+
+        let by_hir_kind: Vec<Box<[usize]>> = temp.into_iter().map(|v| v.into_boxed_slice()).collect();
+
+        Self { only, integrations, rules, rule_excludes, by_kind, by_hir_kind }
     }
 
     /// Checks if a specific rule is enabled in the registry.
@@ -103,6 +114,20 @@ impl RuleRegistry {
         &self.by_kind[kind as usize]
     }
 
+    #[inline]
+    #[must_use]
+    pub fn for_hir_kind(&self, kind: HirNodeKind) -> &[usize] {
+        &self.by_hir_kind[kind as usize]
+    }
+
+    /// Checks if any rule in the registry inspects HIR nodes, in which case
+    /// every linted file has to be lowered.
+    #[inline]
+    #[must_use]
+    pub fn requires_hir(&self) -> bool {
+        self.by_hir_kind.iter().any(|rules| !rules.is_empty())
+    }
+
     #[inline]
     #[must_use]
     pub fn rule(&self, idx: usize) -> &AnyRule {
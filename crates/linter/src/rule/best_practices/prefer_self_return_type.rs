@@ -2,12 +2,13 @@ use indoc::indoc;
 use mago_allocator::Arena;
 use schemars::JsonSchema;
 
+use mago_hir::ir::r#type::Type;
+use mago_hir::ir::r#type::TypeKind;
+use mago_hir::node::Node as HirNode;
+use mago_hir::node::NodeKind as HirNodeKind;
 use mago_reporting::Annotation;
 use mago_reporting::Issue;
 use mago_reporting::Level;
-use mago_span::HasSpan;
-use mago_syntax::cst::FunctionLikeReturnTypeHint;
-use mago_syntax::cst::Hint;
 use mago_syntax::cst::Node;
 use mago_syntax::cst::NodeKind;
 use mago_text_edit::TextEdit;
@@ -18,7 +19,6 @@ use crate::requirements::RuleRequirements;
 use crate::rule::Config;
 use crate::rule::LintRule;
 use crate::rule_meta::RuleMeta;
-use crate::scope::ClassLikeScope;
 use crate::settings::RuleSettings;
 
 #[derive(Debug, Clone)]
@@ -99,8 +99,11 @@ impl LintRule for PreferSelfReturnTypeRule {
     }
 
     fn targets() -> &'static [NodeKind] {
-        const TARGETS: &[NodeKind] =
-            &[NodeKind::Function, NodeKind::Method, NodeKind::Closure, NodeKind::ArrowFunction];
+        &[]
+    }
+
+    fn hir_targets() -> &'static [HirNodeKind] {
+        const TARGETS: &[HirNodeKind] = &[HirNodeKind::Class, HirNodeKind::Interface, HirNodeKind::Enum];
 
         TARGETS
     }
@@ -109,73 +112,84 @@ impl LintRule for PreferSelfReturnTypeRule {
         Self { meta: Self::meta(), cfg: settings.config }
     }
 
-    fn check<'arena, A>(&self, ctx: &mut LintContext<'_, 'arena, A>, node: Node<'_, 'arena>)
+    fn check<'arena, A>(&self, _ctx: &mut LintContext<'_, 'arena, A>, _node: Node<'_, 'arena>)
+    where
+        A: Arena,
+    {
+    }
+
+    fn check_hir<'arena, A>(&self, ctx: &mut LintContext<'_, 'arena, A>, node: HirNode<'_, 'arena, (), (), ()>)
     where
         A: Arena,
     {
-        let return_type_hint = match node {
-            Node::Function(function) => function.return_type_hint.as_ref(),
-            Node::Method(method) => method.return_type_hint.as_ref(),
-            Node::Closure(closure) => closure.return_type_hint.as_ref(),
-            Node::ArrowFunction(arrow_function) => arrow_function.return_type_hint.as_ref(),
+        // HIR declaration names are already qualified with their namespace.
+        let class_fqn = match node {
+            HirNode::Class(class) => class.name.value,
+            HirNode::Interface(interface) => interface.name.value,
+            HirNode::Enum(r#enum) => r#enum.name.value,
             _ => return,
         };
 
-        let Some(FunctionLikeReturnTypeHint { hint, .. }) = return_type_hint else {
-            return;
-        };
+        let mut stack = vec![];
+        node.visit_children(|child| stack.push(child));
+
+        while let Some(node) = stack.pop() {
+            let return_type = match node {
+                HirNode::Method(method) => method.return_type,
+                HirNode::Function(function) => function.return_type,
+                HirNode::Closure(closure) => closure.return_type,
+                HirNode::ArrowFunction(arrow_function) => arrow_function.return_type,
+                // `self` inside an anonymous class refers to the anonymous class.
+                HirNode::AnonymousClass(_) => continue,
+                _ => None,
+            };
 
-        let class_fqn = match ctx.scope.get_class_like_scope() {
-            Some(ClassLikeScope::Class(name))
-            | Some(ClassLikeScope::Interface(name))
-            | Some(ClassLikeScope::Enum(name)) => name,
-            Some(ClassLikeScope::Trait(_)) | Some(ClassLikeScope::AnonymousClass(_)) | None => return,
-        };
+            if let Some(return_type) = return_type {
+                self.check_type(ctx, class_fqn, return_type);
+            }
 
-        self.check_hint(ctx, class_fqn, hint);
+            node.visit_children(|child| stack.push(child));
+        }
     }
 }
 
 impl PreferSelfReturnTypeRule {
-    fn check_hint<'arena, A>(&self, ctx: &mut LintContext<'_, 'arena, A>, class_fqn: &[u8], hint: &Hint<'arena>)
+    fn check_type<A>(&self, ctx: &mut LintContext<'_, '_, A>, class_fqn: &[u8], r#type: &Type<'_>)
     where
         A: Arena,
     {
-        match hint {
-            Hint::Identifier(identifier) => {
-                let resolved = ctx.lookup_name(identifier);
-                if resolved.eq_ignore_ascii_case(class_fqn) {
-                    let used = mago_bytes::BytesDisplay(identifier.value());
-                    let issue = Issue::new(
-                        self.cfg.level(),
-                        format!("Return type `{used}` refers to the enclosing class; use `self` instead."),
-                    )
-                    .with_code(self.meta.code)
-                    .with_annotation(
-                        Annotation::primary(identifier.span())
-                            .with_message(format!("Replace `{used}` with `self`")),
-                    )
-                    .with_note(
-                        "Using `self` decouples the signature from the class name, so renaming the class does not require updating return types.",
-                    )
-                    .with_help(
-                        "Replace the explicit class name with `self`, or with `static` if the method should return instances of subclasses.",
-                    );
-
-                    ctx.collector.propose(issue, |edits| {
-                        edits.push(TextEdit::replace(identifier.span(), "self"));
-                    });
+        match r#type.kind {
+            TypeKind::Named(identifier) => {
+                if !identifier.value.eq_ignore_ascii_case(class_fqn) {
+                    return;
                 }
+
+                let used = mago_bytes::BytesDisplay(&ctx.source_file.contents[identifier.span.to_range_usize()]);
+                let issue = Issue::new(
+                    self.cfg.level(),
+                    format!("Return type `{used}` refers to the enclosing class; use `self` instead."),
+                )
+                .with_code(self.meta.code)
+                .with_annotation(
+                    Annotation::primary(identifier.span)
+                        .with_message(format!("Replace `{used}` with `self`")),
+                )
+                .with_note(
+                    "Using `self` decouples the signature from the class name, so renaming the class does not require updating return types.",
+                )
+                .with_help(
+                    "Replace the explicit class name with `self`, or with `static` if the method should return instances of subclasses.",
+                );
+
+                ctx.collector.propose(issue, |edits| {
+                    edits.push(TextEdit::replace(identifier.span, "self"));
+                });
             }
-            Hint::Parenthesized(parenthesized) => self.check_hint(ctx, class_fqn, parenthesized.hint),
-            Hint::Nullable(nullable) => self.check_hint(ctx, class_fqn, nullable.hint),
-            Hint::Union(union) => {
-                self.check_hint(ctx, class_fqn, union.left);
-                self.check_hint(ctx, class_fqn, union.right);
-            }
-            Hint::Intersection(intersection) => {
-                self.check_hint(ctx, class_fqn, intersection.left);
-                self.check_hint(ctx, class_fqn, intersection.right);
+            TypeKind::Parenthesized(inner) | TypeKind::Nullable(inner) => self.check_type(ctx, class_fqn, inner),
+            TypeKind::Union(types) | TypeKind::Intersection(types) => {
+                for inner in types {
+                    self.check_type(ctx, class_fqn, inner);
+                }
             }
             _ => {}
         }
@@ -434,6 +448,57 @@ mod tests {
         "}
     }
 
+    test_lint_failure! {
+        name = closure_in_method_returning_class_name_is_flagged,
+        rule = PreferSelfReturnTypeRule,
+        code = indoc! {r"
+            <?php
+
+            final class Box
+            {
+                public function factory(): \Closure
+                {
+                    return static fn(): Box => new Box();
+                }
+            }
+        "}
+    }
+
+    test_lint_failure! {
+        name = each_enclosing_class_reference_is_flagged,
+        rule = PreferSelfReturnTypeRule,
+        count = 2,
+        code = indoc! {r"
+            <?php
+
+            namespace App;
+
+            use App\Box as Alias;
+
+            final class Box
+            {
+                public function first(): Alias
+                {
+                    return $this;
+                }
+
+                public function second(): ?\App\Box
+                {
+                    return null;
+                }
+
+                public function unrelated(): Other
+                {
+                    return new Other();
+                }
+            }
+
+            final class Other
+            {
+            }
+        "}
+    }
+
     test_lint_fix! {
         name = fix_simple_class_name_return,
         rule = PreferSelfReturnTypeRule,
@@ -22,6 +22,7 @@ pub mod no_hash_comment;
 pub mod no_php_tag_terminator;
 pub mod no_trailing_space;
 pub mod property_name;
+pub mod psr_autoload;
 pub mod string_style;
 pub mod trait_name;
 pub mod variable_name;
@@ -61,6 +62,7 @@ pub use no_hash_comment::*;
 pub use no_php_tag_terminator::*;
 pub use no_trailing_space::*;
 pub use property_name::*;
+pub use psr_autoload::*;
 pub use string_style::*;
 pub use trait_name::*;
 pub use variable_name::*;
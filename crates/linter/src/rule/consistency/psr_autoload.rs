@@ -0,0 +1,541 @@
+use std::collections::BTreeMap;
+
+use indoc::indoc;
+use mago_allocator::Arena;
+use schemars::JsonSchema;
+
+use mago_reporting::Annotation;
+use mago_reporting::Issue;
+use mago_reporting::Level;
+use mago_span::HasSpan;
+use mago_span::Span;
+use mago_syntax::cst::Identifier;
+use mago_syntax::cst::Node;
+use mago_syntax::cst::NodeKind;
+use mago_syntax::cst::Program;
+use mago_syntax::cst::Statement;
+use mago_text_edit::Safety;
+use mago_text_edit::TextEdit;
+
+use crate::category::Category;
+use crate::context::LintContext;
+use crate::requirements::RuleRequirements;
+use crate::rule::Config;
+use crate::rule::LintRule;
+use crate::rule_meta::RuleMeta;
+use crate::settings::RuleSettings;
+
+#[derive(Debug, Clone)]
+pub struct PsrAutoloadRule {
+    meta: &'static RuleMeta,
+    cfg: PsrAutoloadConfig,
+}
+
+/// How a misplaced class-like is fixed.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Default, JsonSchema)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum PsrAutoloadFix {
+    /// Rewrite the namespace declaration to match the file location.
+    #[default]
+    Namespace,
+    /// Move the file to where the autoloader expects the class-like.
+    Move,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, JsonSchema)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, rename_all = "kebab-case", deny_unknown_fields))]
+pub struct PsrAutoloadConfig {
+    pub level: Level,
+    /// PSR-4 namespace prefixes, and the directories they map to.
+    ///
+    /// When both `psr-4` and `psr-0` are empty, they are read from the `autoload` and
+    /// `autoload-dev` sections of `composer.json`.
+    pub psr_4: BTreeMap<String, Vec<String>>,
+    /// PSR-0 namespace prefixes, and the directories they map to.
+    pub psr_0: BTreeMap<String, Vec<String>>,
+    /// How `--fix` resolves a mismatch: `"namespace"` rewrites the namespace declaration,
+    /// and `"move"` moves the file. Both are potentially unsafe, and are only applied with
+    /// `--potentially-unsafe` or `--unsafe`.
+    pub fix: PsrAutoloadFix,
+}
+
+impl Default for PsrAutoloadConfig {
+    fn default() -> Self {
+        Self { level: Level::Warning, psr_4: BTreeMap::new(), psr_0: BTreeMap::new(), fix: PsrAutoloadFix::default() }
+    }
+}
+
+impl Config for PsrAutoloadConfig {
+    fn level(&self) -> Level {
+        self.level
+    }
+}
+
+/// Where the autoload mappings expect a misplaced class-like, or the file declaring it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AutoloadMismatch {
+    /// The namespace a class-like in this file must be declared in, if the file is named after
+    /// the class-like.
+    pub expected_namespace: Option<String>,
+    /// The path of the file the class-like must be declared in, relative to the workspace.
+    pub expected_path: Option<String>,
+}
+
+/// A class-like declared at the top level of a file.
+struct DeclaredClassLike<'ast, 'arena> {
+    kind: &'static str,
+    name: &'arena [u8],
+    name_span: Span,
+    namespace: Option<&'ast Identifier<'arena>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Standard {
+    Psr4,
+    Psr0,
+}
+
+/// A single namespace prefix to directory mapping.
+struct Mapping {
+    standard: Standard,
+    prefix: String,
+    directory: String,
+}
+
+impl PsrAutoloadConfig {
+    /// Returns `true` if no autoload mapping is configured.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.psr_4.is_empty() && self.psr_0.is_empty()
+    }
+
+    /// Checks the location of a class-like against the autoload mappings.
+    ///
+    /// Returns `None` when the class-like can be autoloaded from `file_name`, or when the file is
+    /// outside of every mapped directory.
+    #[must_use]
+    pub fn check(&self, file_name: &str, class_name: &str) -> Option<AutoloadMismatch> {
+        let mappings = self.mappings();
+
+        let mut covering: Option<&Mapping> = None;
+        let mut expected_path: Option<(usize, String)> = None;
+        for mapping in &mappings {
+            if let Some(path) = mapping.path_for(class_name) {
+                if path == file_name {
+                    return None;
+                }
+
+                if expected_path.as_ref().is_none_or(|(length, _)| mapping.prefix.len() > *length) {
+                    expected_path = Some((mapping.prefix.len(), path));
+                }
+            }
+
+            if mapping.contains(file_name)
+                && covering.is_none_or(|covering| mapping.directory.len() > covering.directory.len())
+            {
+                covering = Some(mapping);
+            }
+        }
+
+        let covering = covering?;
+        let short_name = class_name.rsplit('\\').next().unwrap_or(class_name);
+        let expected_namespace = covering.class_for(file_name).and_then(|expected| match expected.rsplit_once('\\') {
+            Some((namespace, name)) => (name == short_name).then(|| namespace.to_string()),
+            None => (expected == short_name).then(String::new),
+        });
+
+        Some(AutoloadMismatch { expected_namespace, expected_path: expected_path.map(|(_, path)| path) })
+    }
+
+    /// Returns where the class-like declared in `program` must be moved, if it is misplaced.
+    #[must_use]
+    pub fn destination(&self, file_name: &str, program: &Program<'_>) -> Option<String> {
+        let declared = declared_class_like(program)?;
+
+        self.check(file_name, &qualified_name(&declared))?.expected_path
+    }
+
+    fn mappings(&self) -> Vec<Mapping> {
+        let psr_4 = self.psr_4.iter().map(|(prefix, directories)| (Standard::Psr4, prefix, directories));
+        let psr_0 = self.psr_0.iter().map(|(prefix, directories)| (Standard::Psr0, prefix, directories));
+
+        psr_4
+            .chain(psr_0)
+            .flat_map(|(standard, prefix, directories)| {
+                let prefix = prefix.trim_start_matches('\\').to_string();
+
+                directories.iter().map(move |directory| Mapping {
+                    standard,
+                    prefix: prefix.clone(),
+                    directory: normalize_directory(directory),
+                })
+            })
+            .collect()
+    }
+}
+
+impl Mapping {
+    /// Returns `true` if `file_name` is inside the mapped directory.
+    fn contains(&self, file_name: &str) -> bool {
+        self.relative_path(file_name).is_some()
+    }
+
+    fn relative_path<'name>(&self, file_name: &'name str) -> Option<&'name str> {
+        if self.directory.is_empty() {
+            return Some(file_name);
+        }
+
+        file_name.strip_prefix(self.directory.as_str())?.strip_prefix('/')
+    }
+
+    /// Returns the path the autoloader loads `class_name` from, if the prefix matches.
+    fn path_for(&self, class_name: &str) -> Option<String> {
+        let relative = match self.standard {
+            Standard::Psr4 => class_name.strip_prefix(self.prefix.as_str())?.replace('\\', "/"),
+            Standard::Psr0 => {
+                if !class_name.starts_with(self.prefix.as_str()) {
+                    return None;
+                }
+
+                match class_name.rsplit_once('\\') {
+                    Some((namespace, name)) => format!("{}/{}", namespace.replace('\\', "/"), name.replace('_', "/")),
+                    None => class_name.replace('_', "/"),
+                }
+            }
+        };
+
+        if relative.is_empty() {
+            return None;
+        }
+
+        Some(if self.directory.is_empty() {
+            format!("{relative}.php")
+        } else {
+            format!("{}/{relative}.php", self.directory)
+        })
+    }
+
+    /// Returns the class-like name the autoloader expects in `file_name`.
+    fn class_for(&self, file_name: &str) -> Option<String> {
+        let relative = self.relative_path(file_name)?.strip_suffix(".php")?;
+        let class_name = match self.standard {
+            Standard::Psr4 => format!("{}{}", self.prefix, relative.replace('/', "\\")),
+            Standard::Psr0 => relative.replace('/', "\\"),
+        };
+
+        class_name.starts_with(self.prefix.as_str()).then_some(class_name)
+    }
+}
+
+impl LintRule for PsrAutoloadRule {
+    type Config = PsrAutoloadConfig;
+
+    fn meta() -> &'static RuleMeta {
+        const META: RuleMeta = RuleMeta {
+            name: "PSR Autoload",
+            code: "psr-autoload",
+            description: indoc! {"
+                Ensures that class-likes are declared where the PSR-4 and PSR-0 autoload mappings of
+                `composer.json` expect them, so that Composer's autoloader can find them.
+
+                For example, with `\"App\\\\\": \"src/\"` in `autoload.psr-4`, the class `App\\Http\\Kernel`
+                must be declared in `src/Http/Kernel.php`. Files outside of every mapped directory are
+                not checked.
+            "},
+            good_example: indoc! {r#"
+                <?php
+                // File: test.php
+                // composer.json: "autoload": { "psr-4": { "App\\": "" } }
+
+                namespace App;
+
+                class test
+                {
+                }
+            "#},
+            bad_example: indoc! {r#"
+                <?php
+                // File: test.php
+                // composer.json: "autoload": { "psr-4": { "App\\": "" } }
+
+                namespace App\Http;
+
+                class test
+                {
+                }
+            "#},
+            category: Category::Consistency,
+            requirements: RuleRequirements::None,
+        };
+
+        &META
+    }
+
+    fn targets() -> &'static [NodeKind] {
+        const TARGETS: &[NodeKind] = &[NodeKind::Program];
+
+        TARGETS
+    }
+
+    fn build(settings: &RuleSettings<Self::Config>) -> Self {
+        Self { meta: Self::meta(), cfg: settings.config.clone() }
+    }
+
+    fn check<'arena, A>(&self, ctx: &mut LintContext<'_, 'arena, A>, node: Node<'_, 'arena>)
+    where
+        A: Arena,
+    {
+        let Node::Program(program) = node else {
+            return;
+        };
+
+        if self.cfg.is_empty() {
+            return;
+        }
+
+        let Some(declared) = declared_class_like(program) else {
+            return;
+        };
+
+        let file_name = String::from_utf8_lossy(&ctx.source_file.name);
+        let class_name = qualified_name(&declared);
+        let Some(mismatch) = self.cfg.check(&file_name, &class_name) else {
+            return;
+        };
+
+        let mut issue = Issue::new(
+            self.cfg.level(),
+            format!("{} `{class_name}` cannot be autoloaded from `{file_name}`.", declared.kind),
+        )
+        .with_code(self.meta.code)
+        .with_annotation(
+            Annotation::primary(declared.name_span)
+                .with_message(format!("{} `{class_name}` is declared here.", declared.kind)),
+        );
+
+        issue = match &mismatch.expected_path {
+            Some(expected_path) => {
+                issue.with_note(format!("The autoloader looks for `{class_name}` in `{expected_path}`."))
+            }
+            None => issue.with_note(format!("No autoload mapping covers the namespace of `{class_name}`.")),
+        };
+
+        let help = match (&mismatch.expected_namespace, &mismatch.expected_path) {
+            (Some(namespace), Some(path)) if !namespace.is_empty() => {
+                format!("Change the namespace to `{namespace}`, or move the file to `{path}`.")
+            }
+            (Some(namespace), None) if !namespace.is_empty() => format!("Change the namespace to `{namespace}`."),
+            (_, Some(path)) => format!("Move the file to `{path}`."),
+            _ => "Move the file into the directory mapped to its namespace in `composer.json`.".to_string(),
+        };
+
+        issue = issue.with_help(help);
+
+        let replacement = match (&mismatch.expected_namespace, declared.namespace) {
+            (Some(namespace), Some(name)) if self.cfg.fix == PsrAutoloadFix::Namespace && !namespace.is_empty() => {
+                Some((name.span(), namespace.clone()))
+            }
+            _ => None,
+        };
+
+        let Some((span, namespace)) = replacement else {
+            ctx.collector.report(issue);
+
+            return;
+        };
+
+        ctx.collector.propose(issue, |edits| {
+            edits.push(TextEdit::replace(span, namespace).with_safety(Safety::PotentiallyUnsafe));
+        });
+    }
+}
+
+/// Returns the class-like declared in `program`, if it declares exactly one.
+fn declared_class_like<'ast, 'arena>(program: &'ast Program<'arena>) -> Option<DeclaredClassLike<'ast, 'arena>> {
+    let mut declared = vec![];
+    for statement in program.statements.iter() {
+        match statement {
+            Statement::Namespace(namespace) => {
+                for statement in namespace.statements().iter() {
+                    collect_class_like(statement, namespace.name.as_ref(), &mut declared);
+                }
+            }
+            statement => collect_class_like(statement, None, &mut declared),
+        }
+    }
+
+    if declared.len() == 1 { declared.pop() } else { None }
+}
+
+fn collect_class_like<'ast, 'arena>(
+    statement: &'ast Statement<'arena>,
+    namespace: Option<&'ast Identifier<'arena>>,
+    declared: &mut Vec<DeclaredClassLike<'ast, 'arena>>,
+) {
+    let (kind, name) = match statement {
+        Statement::Class(class) => ("Class", &class.name),
+        Statement::Interface(interface) => ("Interface", &interface.name),
+        Statement::Trait(r#trait) => ("Trait", &r#trait.name),
+        Statement::Enum(r#enum) => ("Enum", &r#enum.name),
+        _ => return,
+    };
+
+    declared.push(DeclaredClassLike { kind, name: name.value, name_span: name.span(), namespace });
+}
+
+fn qualified_name(declared: &DeclaredClassLike<'_, '_>) -> String {
+    let name = String::from_utf8_lossy(declared.name);
+
+    match declared.namespace {
+        Some(namespace) => {
+            let namespace = String::from_utf8_lossy(namespace.value());
+
+            format!("{}\\{name}", namespace.trim_start_matches('\\'))
+        }
+        None => name.into_owned(),
+    }
+}
+
+fn normalize_directory(directory: &str) -> String {
+    let directory = directory.replace('\\', "/");
+
+    directory.trim_start_matches("./").trim_end_matches('/').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+    use crate::settings::Settings;
+    use crate::test_lint_failure;
+    use crate::test_lint_fix;
+    use crate::test_lint_success;
+
+    fn mappings(settings: &mut Settings) {
+        let config = &mut settings.rules.psr_autoload.config;
+        config.psr_4.insert("App\\".to_string(), vec!["src/".to_string()]);
+        config.psr_4.insert("App\\Tests\\".to_string(), vec!["tests/".to_string()]);
+        config.psr_0.insert("Legacy_".to_string(), vec!["lib/".to_string()]);
+    }
+
+    test_lint_success! {
+        name = class_matching_its_location,
+        rule = PsrAutoloadRule,
+        filename = "src/Http/Kernel.php",
+        settings = mappings,
+        code = indoc! {r#"
+            <?php
+
+            namespace App\Http;
+
+            final class Kernel {}
+        "#}
+    }
+
+    test_lint_success! {
+        name = class_matching_the_longest_prefix,
+        rule = PsrAutoloadRule,
+        filename = "tests/KernelTest.php",
+        settings = mappings,
+        code = indoc! {r#"
+            <?php
+
+            namespace App\Tests;
+
+            final class KernelTest {}
+        "#}
+    }
+
+    test_lint_success! {
+        name = psr_0_class_matching_its_location,
+        rule = PsrAutoloadRule,
+        filename = "lib/Legacy/Http/Kernel.php",
+        settings = mappings,
+        code = indoc! {r#"
+            <?php
+
+            class Legacy_Http_Kernel {}
+        "#}
+    }
+
+    test_lint_success! {
+        name = file_outside_of_mapped_directories,
+        rule = PsrAutoloadRule,
+        filename = "bin/console.php",
+        settings = mappings,
+        code = indoc! {r#"
+            <?php
+
+            namespace Console;
+
+            final class Application {}
+        "#}
+    }
+
+    test_lint_success! {
+        name = no_mappings_configured,
+        rule = PsrAutoloadRule,
+        filename = "src/Http/Kernel.php",
+        code = indoc! {r#"
+            <?php
+
+            namespace Wrong;
+
+            final class Kernel {}
+        "#}
+    }
+
+    test_lint_failure! {
+        name = class_in_the_wrong_directory,
+        rule = PsrAutoloadRule,
+        filename = "src/Kernel.php",
+        settings = mappings,
+        code = indoc! {r#"
+            <?php
+
+            namespace App\Http;
+
+            final class Kernel {}
+        "#}
+    }
+
+    test_lint_fix! {
+        name = namespace_is_rewritten_to_match_the_location,
+        rule = PsrAutoloadRule,
+        filename = "src/Http/Kernel.php",
+        settings = mappings,
+        code = indoc! {r#"
+            <?php
+
+            namespace App\Kernel;
+
+            final class Kernel {}
+        "#},
+        fixed = indoc! {r#"
+            <?php
+
+            namespace App\Http;
+
+            final class Kernel {}
+        "#}
+    }
+
+    #[test]
+    fn destination_follows_the_namespace() {
+        let mut settings = Settings::default();
+        mappings(&mut settings);
+
+        let mismatch = settings.rules.psr_autoload.config.check("src/Kernel.php", "App\\Http\\Kernel");
+
+        assert_eq!(
+            mismatch,
+            Some(AutoloadMismatch {
+                expected_namespace: Some("App".to_string()),
+                expected_path: Some("src/Http/Kernel.php".to_string()),
+            })
+        );
+    }
+}
@@ -1,5 +1,7 @@
 use mago_allocator::Arena;
 
+use mago_hir::node::Node as HirNode;
+use mago_hir::node::NodeKind as HirNodeKind;
 use mago_php_version::PHPVersion;
 use mago_reporting::Level;
 use mago_syntax::cst::Node;
@@ -85,6 +87,26 @@ pub trait LintRule {
     fn check<'arena, A>(&self, ctx: &mut LintContext<'_, 'arena, A>, node: Node<'_, 'arena>)
     where
         A: Arena;
+
+    /// The HIR node kinds this rule inspects through [`LintRule::check_hir`].
+    ///
+    /// The HIR carries resolved names and parsed PHPDoc annotations, so rules that need
+    /// either can target HIR nodes instead of re-deriving them from the CST. A file is
+    /// only lowered when at least one enabled rule returns a non-empty list here.
+    #[must_use]
+    fn hir_targets() -> &'static [HirNodeKind] {
+        &[]
+    }
+
+    /// Checks a HIR node of one of the kinds returned by [`LintRule::hir_targets`].
+    ///
+    /// HIR nodes are visited after the CST walk has finished, so the CST-specific parts
+    /// of the context (ancestors and scope) are not available here.
+    fn check_hir<'arena, A>(&self, _ctx: &mut LintContext<'_, 'arena, A>, _node: HirNode<'_, 'arena, (), (), ()>)
+    where
+        A: Arena,
+    {
+    }
 }
 
 macro_rules! define_rules {
@@ -174,6 +196,23 @@ macro_rules! define_rules {
                     $( AnyRule::$variant(r) => r.check(ctx, node), )*
                 }
             }
+
+            #[inline]
+            pub fn hir_targets(&self) -> &'static [HirNodeKind] {
+                match self {
+                    $( AnyRule::$variant(_) => $rule::hir_targets(), )*
+                }
+            }
+
+            #[inline]
+            pub fn check_hir<'arena, A>(&self, ctx: &mut LintContext<'_, 'arena, A>, node: HirNode<'_, 'arena, (), (), ()>)
+            where
+                A: Arena,
+            {
+                match self {
+                    $( AnyRule::$variant(r) => r.check_hir(ctx, node), )*
+                }
+            }
         }
 
         /// Filters rule settings based on PHP version and integration requirements.
@@ -332,6 +371,7 @@ define_rules! {
     PreferTestAttribute(prefer_test_attribute @ PreferTestAttributeRule),
     PreferViewArray(prefer_view_array @ PreferViewArrayRule),
     PreferWhileLoop(prefer_while_loop @ PreferWhileLoopRule),
+    PsrAutoload(psr_autoload @ PsrAutoloadRule),
     PslArrayFunctions(psl_array_functions @ PslArrayFunctionsRule),
     PslDataStructures(psl_data_structures @ PslDataStructuresRule),
     PslDatetime(psl_datetime @ PslDatetimeRule),
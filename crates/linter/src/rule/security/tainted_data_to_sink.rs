@@ -62,6 +62,10 @@ impl LintRule for TaintedDataToSinkRule {
                 (such as `echo`, `print`, or user-defined "log" functions). If these functions emit
                 or store data without sanitization, it could lead to Cross-Site Scripting (XSS)
                 or other injection attacks.
+
+                This rule is deprecated: it only sees input passed straight into a sink. Enable the
+                analyzer's taint analysis (`[analyzer.taint]`) instead, which follows tainted data
+                through variables, properties, returns and function calls.
             "#},
             good_example: indoc! {r"
                 <?php
@@ -83,6 +87,10 @@ impl LintRule for TaintedDataToSinkRule {
         &META
     }
 
+    fn deprecated() -> bool {
+        true
+    }
+
     fn targets() -> &'static [NodeKind] {
         const TARGETS: &[NodeKind] = &[NodeKind::Echo, NodeKind::PrintConstruct, NodeKind::FunctionCall];
 
@@ -163,6 +163,7 @@ use crate::rule::PslRandomnessFunctionsConfig;
 use crate::rule::PslRegexFunctionsConfig;
 use crate::rule::PslSleepFunctionsConfig;
 use crate::rule::PslStringFunctionsConfig;
+use crate::rule::PsrAutoloadConfig;
 use crate::rule::ReadableLiteralConfig;
 use crate::rule::RedundantStaticConfig;
 use crate::rule::RequireNamespaceConfig;
@@ -366,6 +367,7 @@ pub struct RulesSettings {
     pub psl_regex_functions: RuleSettings<PslRegexFunctionsConfig>,
     pub psl_sleep_functions: RuleSettings<PslSleepFunctionsConfig>,
     pub psl_string_functions: RuleSettings<PslStringFunctionsConfig>,
+    pub psr_autoload: RuleSettings<PsrAutoloadConfig>,
     pub str_contains: RuleSettings<StrContainsConfig>,
     pub str_starts_with: RuleSettings<StrStartsWithConfig>,
     pub strict_behavior: RuleSettings<StrictBehaviorConfig>,
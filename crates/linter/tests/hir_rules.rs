@@ -0,0 +1,113 @@
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use mago_allocator::LocalArena;
+use mago_database::file::File;
+use mago_hir::node::NodeKind as HirNodeKind;
+use mago_linter::Linter;
+use mago_linter::registry::RuleRegistry;
+use mago_linter::settings::Settings;
+use mago_names::resolver::NameResolver;
+use mago_syntax::parser::parse_file;
+
+const RULE: &str = "prefer-self-return-type";
+
+const CODE: &str = "<?php
+
+namespace App;
+
+final class Box
+{
+    public function create(): Box
+    {
+        return new Box();
+    }
+}
+
+interface Shape
+{
+    public function scale(): Shape;
+}
+
+enum Color
+{
+    case Red;
+
+    public function with(): Color
+    {
+        return $this;
+    }
+}
+
+trait Boxing
+{
+    public function boxed(): Boxing
+    {
+        return $this;
+    }
+}
+
+function make(): Box
+{
+    return new Box();
+}
+";
+
+fn lint(filename: &'static str, excludes: &[&str]) -> Vec<u32> {
+    let arena = LocalArena::new();
+    let file = File::ephemeral(Cow::Borrowed(filename.as_bytes()), Cow::Borrowed(CODE.as_bytes()));
+    let program = parse_file(&arena, &file);
+
+    let resolver = NameResolver::new(&arena);
+    let resolved_names = resolver.resolve(program);
+
+    let mut settings = Settings::default();
+    settings.rules.prefer_self_return_type.exclude = excludes.iter().map(|s| (*s).to_string()).collect();
+
+    let registry = RuleRegistry::build(&settings, Some(&[RULE.to_string()]), true);
+    let linter = Linter::from_registry(&arena, Arc::new(registry), settings.php_version);
+
+    let mut lines: Vec<u32> = linter
+        .lint(&file, program, &resolved_names)
+        .iter()
+        .filter_map(|issue| issue.annotations.first())
+        .map(|annotation| file.line_number(annotation.span.start.offset) + 1)
+        .collect();
+
+    lines.sort_unstable();
+    lines
+}
+
+#[test]
+fn hir_rules_are_indexed_by_hir_kind() {
+    let settings = Settings::default();
+    let registry = RuleRegistry::build(&settings, Some(&[RULE.to_string()]), true);
+
+    assert!(registry.requires_hir());
+    assert_eq!(registry.for_hir_kind(HirNodeKind::Class), &[0]);
+    assert_eq!(registry.for_hir_kind(HirNodeKind::Interface), &[0]);
+    assert_eq!(registry.for_hir_kind(HirNodeKind::Enum), &[0]);
+    assert!(registry.for_hir_kind(HirNodeKind::Trait).is_empty());
+    assert!(registry.for_hir_kind(HirNodeKind::Function).is_empty());
+}
+
+#[test]
+fn cst_only_registries_do_not_require_hir() {
+    let settings = Settings::default();
+    let registry = RuleRegistry::build(&settings, Some(&["no-trailing-space".to_string()]), true);
+
+    assert!(!registry.requires_hir());
+}
+
+#[test]
+fn hir_rules_check_targeted_nodes_only() {
+    // Class, interface and enum return types are reported; the trait and the free function are not.
+    assert_eq!(lint("src/Box.php", &[]), vec![7, 15, 22]);
+}
+
+#[test]
+fn hir_rules_respect_excludes() {
+    assert!(lint("src/Box.php", &["src/"]).is_empty());
+    assert!(lint("src/Box.php", &["**/*.php"]).is_empty());
+    assert_eq!(lint("tests/Box.php", &["src/"]).len(), 3);
+}
@@ -10,6 +10,7 @@ use mago_linter::rule::DisallowedEntry;
 use mago_linter::rule::DisallowedFunctionsConfig;
 use mago_linter::rule::DisallowedTypeEntry;
 use mago_linter::rule::DisallowedTypeInstantiationConfig;
+use mago_linter::rule::PsrAutoloadConfig;
 use mago_linter::settings::RuleSettings;
 use mago_linter::settings::RulesSettings;
 use mago_linter::settings::Settings;
@@ -76,6 +77,13 @@ fn test_code_snippet(rule_code: &str, code: &str, should_have_issues: bool) -> R
                 },
                 ..Default::default()
             },
+            psr_autoload: RuleSettings {
+                config: PsrAutoloadConfig {
+                    psr_4: [("App\\".to_string(), vec![String::new()])].into(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
             ..RulesSettings::default()
         },
         ..Settings::default()
@@ -10,6 +10,8 @@ use mago_guard::settings::Settings as GuardSettings;
 use mago_linter::settings::Settings as LinterSettings;
 use mago_php_version::PHPVersion;
 use mago_syntax::settings::ParserSettings;
+use mago_twig_formatter::settings::TwigFormatSettings;
+use mago_twig_linter::settings::TwigLinterSettings;
 
 /// The complete configuration for the orchestrator and all its services.
 ///
@@ -120,6 +122,20 @@ pub struct OrchestratorConfiguration<'cfg> {
     /// See [`mago_formatter::settings::FormatSettings`] for available options.
     pub formatter_settings: FormatSettings,
 
+    /// Settings for the Twig template formatter.
+    ///
+    /// Only used for files with the `twig` extension, which are loaded when `"twig"` is
+    /// listed in `extensions`.
+    /// See [`mago_twig_formatter::settings::TwigFormatSettings`] for available options.
+    pub twig_formatter_settings: TwigFormatSettings,
+
+    /// Settings for the Twig template linter.
+    ///
+    /// Only used for files with the `twig` extension, which are loaded when `"twig"` is
+    /// listed in `extensions`.
+    /// See [`mago_twig_linter::settings::TwigLinterSettings`] for available options.
+    pub twig_linter_settings: TwigLinterSettings,
+
     /// Disable all default analyzer plugins (including stdlib).
     ///
     /// When set to `true`, no plugins will be loaded by default, and only plugins
@@ -133,6 +149,7 @@ pub struct OrchestratorConfiguration<'cfg> {
     /// - `psl` (aliases: `php-standard-library`, `azjezz-psl`)
     /// - `flow-php` (aliases: `flow`, `flow-etl`)
     /// - `psr-container` (aliases: `psr-11`)
+    /// - `doctrine` (aliases: `doctrine-orm`)
     pub analyzer_plugins: Vec<String>,
 
     /// Whether to display progress bars during long-running operations.
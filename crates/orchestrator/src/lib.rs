@@ -51,7 +51,9 @@ use mago_database::DatabaseConfiguration;
 use mago_database::ReadDatabase;
 use mago_database::exclusion::Exclusion;
 use mago_database::file::File;
+use mago_database::file::TWIG_TEMPLATE_EXTENSION;
 use mago_database::loader::DatabaseLoader;
+use mago_text_edit::TextRange;
 
 use crate::service::analysis::AnalysisService;
 use crate::service::format::FileFormatStatus;
@@ -59,6 +61,7 @@ use crate::service::format::FormatService;
 use crate::service::guard::GuardService;
 use crate::service::incremental_analysis::IncrementalAnalysisService;
 use crate::service::lint::LintService;
+use crate::service::refactor::RefactorService;
 
 pub use config::OrchestratorConfiguration;
 pub use error::OrchestratorError;
@@ -151,6 +154,16 @@ impl<'cfg> Orchestrator<'cfg> {
         self.config.excludes.extend(patterns.map(std::convert::AsRef::as_ref));
     }
 
+    /// Includes Twig templates (files with the `twig` extension) when loading the database.
+    ///
+    /// Templates are formatted and linted with the Twig formatter and linter; they are never
+    /// parsed as PHP.
+    pub fn include_twig_templates(&mut self) {
+        if !self.config.extensions.contains(&TWIG_TEMPLATE_EXTENSION) {
+            self.config.extensions.push(TWIG_TEMPLATE_EXTENSION);
+        }
+    }
+
     /// Sets new source paths, keeping the old ones as context for analysis.
     ///
     /// This method replaces the current source paths with the provided paths. The old
@@ -305,6 +318,7 @@ impl<'cfg> Orchestrator<'cfg> {
             self.config.parser_settings,
             self.config.use_progress_bars,
         )
+        .with_twig_settings(self.config.twig_linter_settings.clone())
     }
 
     /// Creates an architectural guard service with the current configuration.
@@ -394,6 +408,32 @@ impl<'cfg> Orchestrator<'cfg> {
         )
     }
 
+    /// Creates a refactoring service for project-wide renames and moves.
+    ///
+    /// The service analyzes the project on first use and plans refactorings as text edits,
+    /// leaving it to the caller to preview or apply them.
+    ///
+    /// # Arguments
+    ///
+    /// * `database` - A read-only database handle containing the PHP files to refactor
+    /// * `codebase` - Base codebase metadata (prelude only, no user symbols)
+    /// * `symbol_references` - Base symbol references (prelude only)
+    ///
+    /// # Returns
+    ///
+    /// A [`RefactorService`] ready for planning refactorings.
+    pub fn get_refactor_service(
+        &self,
+        database: ReadDatabase,
+        codebase: CodebaseMetadata,
+        symbol_references: SymbolReferences,
+    ) -> RefactorService {
+        RefactorService::new(
+            self.get_incremental_analysis_service(database, codebase, symbol_references),
+            self.config.parser_settings,
+        )
+    }
+
     /// Creates a code formatting service with the current configuration.
     ///
     /// The formatting service formats PHP code according to the configured style settings,
@@ -415,6 +455,7 @@ impl<'cfg> Orchestrator<'cfg> {
             self.config.parser_settings,
             self.config.use_progress_bars,
         )
+        .with_twig_settings(self.config.twig_formatter_settings)
     }
 
     /// Formats a single file according to the configured style settings.
@@ -432,6 +473,7 @@ impl<'cfg> Orchestrator<'cfg> {
     /// - `Ok(FileFormatStatus::Unchanged)` if the file is already properly formatted
     /// - `Ok(FileFormatStatus::Changed(String))` if the file was formatted, containing the new content
     /// - `Ok(FileFormatStatus::FailedToParse(ParseError))` if the file couldn't be parsed
+    /// - `Ok(FileFormatStatus::FailedToParseTemplate(String))` if the Twig template couldn't be parsed
     /// - `Err(OrchestratorError)` if formatting failed for other reasons
     ///
     /// # Performance
@@ -450,6 +492,21 @@ impl<'cfg> Orchestrator<'cfg> {
         service.format_file(file)
     }
 
+    /// Formats only the statements that enclose the given byte ranges of a file.
+    ///
+    /// This is the range-formatting counterpart of [`format_file`](Self::format_file): the
+    /// file is formatted as a whole, but only the lines of the statements enclosing `ranges`
+    /// are changed, so the rest of the file is left as written.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OrchestratorError`] when formatting cannot complete.
+    pub fn format_file_ranges(&self, file: &File, ranges: &[TextRange]) -> Result<FileFormatStatus, OrchestratorError> {
+        let service = self.get_format_service(ReadDatabase::empty());
+
+        service.format_file_ranges(file, ranges)
+    }
+
     /// Formats a single file using a provided bump arena for allocations.
     ///
     /// This method is similar to [`format_file`](Self::format_file) but allows you to
@@ -466,6 +523,7 @@ impl<'cfg> Orchestrator<'cfg> {
     /// - `Ok(FileFormatStatus::Unchanged)` if the file is already properly formatted
     /// - `Ok(FileFormatStatus::Changed(String))` if the file was formatted, containing the new content
     /// - `Ok(FileFormatStatus::FailedToParse(ParseError))` if the file couldn't be parsed
+    /// - `Ok(FileFormatStatus::FailedToParseTemplate(String))` if the Twig template couldn't be parsed
     /// - `Err(OrchestratorError)` if formatting failed for other reasons
     ///
     /// # Performance
@@ -14,6 +14,7 @@ use mago_allocator::LocalArena;
 use mago_analyzer::Analyzer;
 use mago_analyzer::analysis_result::AnalysisResult;
 use mago_analyzer::analysis_result::LateSymbolReferenceIssueReconciler;
+use mago_analyzer::dead_code::find_unused_symbols;
 use mago_analyzer::error::AnalysisError;
 use mago_analyzer::external::AFTER_FILE_ANALYSIS_BATCH_SIZE;
 use mago_analyzer::external::FileAnalysisSnapshot;
@@ -23,8 +24,10 @@ use mago_analyzer::settings::Settings;
 use mago_analyzer::telemetry as analyzer_telemetry;
 use mago_codex::metadata::CodebaseMetadata;
 use mago_codex::populator::populate_codebase;
+use mago_codex::populator::populate_taint_summaries;
 use mago_codex::reference::SymbolReferences;
 use mago_codex::scanner::scan_program;
+use mago_codex::scanner::scan_taint_flows;
 use mago_database::DatabaseReader;
 use mago_database::ReadDatabase;
 use mago_database::file::File;
@@ -126,7 +129,11 @@ impl AnalysisService {
         let semantics_checker = SemanticsChecker::new(self.settings.version);
         issues.extend(semantics_checker.check(&file, program, &resolved_names));
 
-        let user_codebase = scan_program(&arena, &file, program, &resolved_names, self.settings.version);
+        let mut user_codebase = scan_program(&arena, &file, program, &resolved_names, self.settings.version);
+        if self.settings.taint.enabled {
+            scan_taint_flows(program, &resolved_names, &mut user_codebase);
+        }
+
         self.codebase.extend(user_codebase);
         let codebase_scan = self
             .plugin_registry
@@ -138,6 +145,9 @@ impl AnalysisService {
             .flatten();
 
         populate_codebase(&mut self.codebase, &mut self.symbol_references, WordSet::default(), HashSet::default());
+        if self.settings.taint.enabled {
+            populate_taint_summaries(&mut self.codebase);
+        }
 
         self.plugin_registry.prepare_external_analyzer().map_err(AnalysisError::from)?;
         self.plugin_registry
@@ -248,7 +258,8 @@ impl AnalysisService {
         let reducer = AnalysisResultReducer {
             plugin_registry: Arc::clone(&self.plugin_registry),
             external_session: external_session.clone(),
-            files: if external_session.is_some() {
+            settings: self.settings.clone(),
+            files: if external_session.is_some() || self.settings.find_unused_symbols {
                 self.database.files().collect::<Vec<_>>().into()
             } else {
                 Arc::from([])
@@ -265,7 +276,8 @@ impl AnalysisService {
             self.settings.version,
             Box::new(reducer),
             self.use_progress_bars,
-        );
+        )
+        .with_taint_inference(self.settings.taint.enabled);
 
         let plugin_registry = Arc::clone(&self.plugin_registry);
         let before_plugin_registry = Arc::clone(&self.plugin_registry);
@@ -362,9 +374,12 @@ impl AnalysisService {
 
                 #[cfg(not(target_arch = "wasm32"))]
                 let analyzer_new_start = trace_enabled.then(Instant::now);
+                // Project-wide issues are only known once every file is analyzed, so their
+                // suppression pragmas must be resolved in the reducer.
+                let find_unused_symbols = settings.find_unused_symbols;
                 let mut analyzer =
                     Analyzer::new(arena, &source_file, &resolved_names, &codebase, &plugin_registry, settings);
-                if after_file || after_analysis {
+                if after_file || after_analysis || find_unused_symbols {
                     analyzer = analyzer.with_deferred_pragmas();
                 }
                 if let Some(session) = external_session.as_deref() {
@@ -465,6 +480,7 @@ struct AnalysisTaskResult {
 struct AnalysisResultReducer {
     plugin_registry: Arc<PluginRegistry>,
     external_session: Option<Arc<mago_analyzer::external::ExternalAnalysisSession>>,
+    settings: Settings,
     files: Arc<[Arc<File>]>,
 }
 
@@ -526,6 +542,14 @@ impl Reducer<AnalysisTaskResult, AnalysisResult> for AnalysisResultReducer {
             }
         }
 
+        if self.settings.find_unused_symbols {
+            aggregated_result.issues.extend(find_unused_symbols(
+                &codebase,
+                &aggregated_result.symbol_references,
+                &self.settings.entry_points,
+            ));
+        }
+
         let mut pragma_reconciler =
             DeferredIssueReconciler::new(aggregated_result.take_deferred_pragmas(), self.files.iter().cloned());
         aggregated_result.issues = pragma_reconciler.reconcile(std::mem::take(&mut aggregated_result.issues))?;
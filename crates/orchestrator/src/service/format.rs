@@ -10,6 +10,10 @@ use mago_formatter::settings::FormatSettings;
 use mago_php_version::PHPVersion;
 use mago_syntax::error::ParseError;
 use mago_syntax::settings::ParserSettings;
+use mago_text_edit::TextEditor;
+use mago_text_edit::TextRange;
+use mago_twig_formatter::TwigFormatter;
+use mago_twig_formatter::settings::TwigFormatSettings;
 
 use crate::error::OrchestratorError;
 use crate::service::pipeline::StatelessParallelPipeline;
@@ -20,6 +24,8 @@ pub enum FileFormatStatus {
     Unchanged,
     Changed(Vec<u8>),
     FailedToParse(ParseError),
+    /// A Twig template could not be parsed; holds the rendered parse error.
+    FailedToParseTemplate(String),
 }
 
 #[derive(Debug)]
@@ -33,6 +39,7 @@ pub struct FormatService {
     php_version: PHPVersion,
     settings: FormatSettings,
     parser_settings: ParserSettings,
+    twig_settings: TwigFormatSettings,
     use_progress_bars: bool,
 }
 
@@ -45,7 +52,21 @@ impl FormatService {
         parser_settings: ParserSettings,
         use_progress_bars: bool,
     ) -> Self {
-        Self { database, php_version, settings, parser_settings, use_progress_bars }
+        Self {
+            database,
+            php_version,
+            settings,
+            parser_settings,
+            twig_settings: TwigFormatSettings::default(),
+            use_progress_bars,
+        }
+    }
+
+    /// Sets the settings used to format Twig templates.
+    #[must_use]
+    pub fn with_twig_settings(mut self, twig_settings: TwigFormatSettings) -> Self {
+        self.twig_settings = twig_settings;
+        self
     }
 
     /// Formats a single file, allocating a fresh arena.
@@ -65,19 +86,21 @@ impl FormatService {
     ///
     /// Returns [`OrchestratorError`] if the formatter fails outside of recoverable parse errors.
     pub fn format_file_in(self, file: &File, arena: &LocalArena) -> Result<FileFormatStatus, OrchestratorError> {
-        let formatter =
-            Formatter::new(arena, self.php_version, self.settings).with_parser_settings(self.parser_settings);
-
-        match formatter.format_file(file) {
-            Ok(formatted_content) => {
-                if file.contents == formatted_content {
-                    Ok(FileFormatStatus::Unchanged)
-                } else {
-                    Ok(FileFormatStatus::Changed(formatted_content.to_vec()))
-                }
-            }
-            Err(parse_error) => Ok(FileFormatStatus::FailedToParse(parse_error)),
-        }
+        Ok(self.context().format(arena, file))
+    }
+
+    /// Formats only the statements that enclose the given byte ranges of a single file.
+    ///
+    /// The rest of the file is left as written. Twig templates cannot be formatted
+    /// partially, and are reported as unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OrchestratorError`] if the formatter fails outside of recoverable parse errors.
+    pub fn format_file_ranges(self, file: &File, ranges: &[TextRange]) -> Result<FileFormatStatus, OrchestratorError> {
+        let arena = LocalArena::new();
+
+        Ok(self.context().format_ranges(&arena, file, ranges))
     }
 
     /// Runs the formatter pipeline over every file in the database.
@@ -86,11 +109,7 @@ impl FormatService {
     ///
     /// Returns [`OrchestratorError`] when a worker fails or the pipeline cannot reduce results.
     pub fn run(self) -> Result<FormatResult, OrchestratorError> {
-        let context = FormatContext {
-            php_version: self.php_version,
-            settings: self.settings,
-            parser_settings: self.parser_settings,
-        };
+        let context = self.context();
 
         let pipeline = StatelessParallelPipeline::new(
             "✨ Formatting",
@@ -100,22 +119,7 @@ impl FormatService {
             self.use_progress_bars,
         );
 
-        pipeline.run(|context, arena, file| {
-            let formatter = Formatter::new(arena, context.php_version, context.settings)
-                .with_parser_settings(context.parser_settings);
-            let status = match formatter.format_file(&file) {
-                Ok(formatted_content) => {
-                    if file.contents == formatted_content {
-                        FileFormatStatus::Unchanged
-                    } else {
-                        FileFormatStatus::Changed(formatted_content.to_vec())
-                    }
-                }
-                Err(parse_error) => FileFormatStatus::FailedToParse(parse_error),
-            };
-
-            Ok((file.id, status))
-        })
+        pipeline.run(|context, arena, file| Ok((file.id, context.format(arena, &file))))
     }
 
     /// Runs the formatter on a specific subset of files by ID.
@@ -139,11 +143,7 @@ impl FormatService {
     where
         Iter: IntoIterator<Item = FileId>,
     {
-        let context = FormatContext {
-            php_version: self.php_version,
-            settings: self.settings,
-            parser_settings: self.parser_settings,
-        };
+        let context = self.context();
 
         let pipeline = StatelessParallelPipeline::new(
             "✨ Formatting",
@@ -153,22 +153,16 @@ impl FormatService {
             self.use_progress_bars,
         );
 
-        pipeline.run_on_files(file_ids, |context, arena, file| {
-            let formatter = Formatter::new(arena, context.php_version, context.settings)
-                .with_parser_settings(context.parser_settings);
-            let status = match formatter.format_file(&file) {
-                Ok(formatted_content) => {
-                    if file.contents == formatted_content {
-                        FileFormatStatus::Unchanged
-                    } else {
-                        FileFormatStatus::Changed(formatted_content.to_vec())
-                    }
-                }
-                Err(parse_error) => FileFormatStatus::FailedToParse(parse_error),
-            };
-
-            Ok((file.id, status))
-        })
+        pipeline.run_on_files(file_ids, |context, arena, file| Ok((file.id, context.format(arena, &file))))
+    }
+
+    fn context(&self) -> FormatContext {
+        FormatContext {
+            php_version: self.php_version,
+            settings: self.settings,
+            parser_settings: self.parser_settings,
+            twig_settings: self.twig_settings,
+        }
     }
 }
 
@@ -191,12 +185,14 @@ impl FormatResult {
 
     #[must_use]
     pub fn is_successful(&self) -> bool {
-        self.changed_files.values().all(|status| !matches!(status, FileFormatStatus::FailedToParse(_)))
+        !self.is_failed()
     }
 
     #[must_use]
     pub fn is_failed(&self) -> bool {
-        self.changed_files.values().any(|status| matches!(status, FileFormatStatus::FailedToParse(_)))
+        self.changed_files.values().any(|status| {
+            matches!(status, FileFormatStatus::FailedToParse(_) | FileFormatStatus::FailedToParseTemplate(_))
+        })
     }
 
     #[must_use]
@@ -210,6 +206,16 @@ impl FormatResult {
         })
     }
 
+    pub fn template_parse_errors(&self) -> impl Iterator<Item = (&FileId, &str)> {
+        self.changed_files.iter().filter_map(|(file_id, status)| {
+            if let FileFormatStatus::FailedToParseTemplate(error) = status {
+                Some((file_id, error.as_str()))
+            } else {
+                None
+            }
+        })
+    }
+
     pub fn changed_files(&self) -> impl Iterator<Item = (&FileId, &Vec<u8>)> {
         self.changed_files.iter().filter_map(|(file_id, status)| {
             if let FileFormatStatus::Changed(content) = status { Some((file_id, content)) } else { None }
@@ -231,6 +237,54 @@ struct FormatContext {
     settings: FormatSettings,
     /// The parser settings.
     parser_settings: ParserSettings,
+    /// The configured settings for the Twig template formatter.
+    twig_settings: TwigFormatSettings,
+}
+
+impl FormatContext {
+    /// Formats a single file, dispatching Twig templates to the template formatter.
+    fn format(self, arena: &LocalArena, file: &File) -> FileFormatStatus {
+        let result = if file.is_twig_template() {
+            TwigFormatter::new(arena, self.twig_settings)
+                .format_file(file)
+                .map_err(|parse_error| FileFormatStatus::FailedToParseTemplate(parse_error.to_string()))
+        } else {
+            Formatter::new(arena, self.php_version, self.settings)
+                .with_parser_settings(self.parser_settings)
+                .format_file(file)
+                .map_err(FileFormatStatus::FailedToParse)
+        };
+
+        match result {
+            Ok(formatted_content) if file.contents == formatted_content => FileFormatStatus::Unchanged,
+            Ok(formatted_content) => FileFormatStatus::Changed(formatted_content.to_vec()),
+            Err(status) => status,
+        }
+    }
+
+    /// Formats the statements enclosing the given ranges of a single file.
+    fn format_ranges(self, arena: &LocalArena, file: &File, ranges: &[TextRange]) -> FileFormatStatus {
+        if file.is_twig_template() {
+            return FileFormatStatus::Unchanged;
+        }
+
+        let edits = match Formatter::new(arena, self.php_version, self.settings)
+            .with_parser_settings(self.parser_settings)
+            .format_ranges(file, ranges)
+        {
+            Ok(edits) => edits,
+            Err(parse_error) => return FileFormatStatus::FailedToParse(parse_error),
+        };
+
+        if edits.is_empty() {
+            return FileFormatStatus::Unchanged;
+        }
+
+        let mut editor = TextEditor::new(&file.contents);
+        editor.apply_batch(edits, None::<fn(&[u8]) -> bool>);
+
+        FileFormatStatus::Changed(editor.finish())
+    }
 }
 
 #[derive(Debug, Clone)]
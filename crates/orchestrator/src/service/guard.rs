@@ -3,6 +3,7 @@ use std::sync::Arc;
 use mago_codex::metadata::CodebaseMetadata;
 use mago_database::ReadDatabase;
 use mago_guard::ArchitecturalGuard;
+use mago_guard::graph::DependencyGraph;
 use mago_guard::settings::Settings;
 use mago_names::resolver::NameResolver;
 use mago_reporting::Issue;
@@ -86,6 +87,10 @@ impl GuardService {
         let skipped_perimeter = matches!(self.settings.should_run_perimeter(), Some(false));
         let skipped_structural = matches!(self.settings.should_run_structural(), Some(false));
 
+        let graph_guard = ArchitecturalGuard::new(self.settings.clone());
+        let check_graph = matches!(self.settings.should_run_perimeter(), Some(true))
+            && self.settings.perimeter.requires_dependency_graph();
+
         let pipeline = StatelessParallelPipeline::new(
             GUARD_PROGRESS_PREFIX,
             self.database,
@@ -94,7 +99,7 @@ impl GuardService {
             self.use_progress_bars,
         );
 
-        let issues = pipeline.run(|(codebase, guard_settings, parser_settings), arena, source_file| {
+        let (mut issues, graph) = pipeline.run(|(codebase, guard_settings, parser_settings), arena, source_file| {
             let mut issues = IssueCollection::new();
 
             let program = parse_file_with_settings(arena, &source_file, parser_settings);
@@ -104,39 +109,99 @@ impl GuardService {
 
             let resolved_names = NameResolver::new(arena).resolve(program);
             let guard = ArchitecturalGuard::new(guard_settings);
-            let report = guard.check(&codebase, program, &resolved_names);
+            let mut report = guard.check(&codebase, program, &resolved_names);
+            let graph = report.dependency_graph.take();
 
             issues.extend(
                 // Report as issues
                 report.report_into_issues(arena, &source_file, program),
             );
 
-            Ok(issues)
+            Ok((issues, graph))
         })?;
 
+        if check_graph {
+            issues.extend(graph_guard.check_graph(&graph).into_issues());
+        }
+
         Ok(GuardResult {
             issues,
             missing_perimeter_configuration: skipped_perimeter,
             missing_structural_configuration: skipped_structural,
         })
     }
+
+    /// Collects the namespace dependency graph of the codebase.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the merged [`DependencyGraph`] of all files, or an [`OrchestratorError`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OrchestratorError`] when the pipeline fails or scanning encounters IO errors.
+    pub fn graph(self) -> Result<DependencyGraph, OrchestratorError> {
+        const GRAPH_PROGRESS_PREFIX: &str = "🕸️  Mapping dependencies";
+
+        let pipeline = StatelessParallelPipeline::new(
+            GRAPH_PROGRESS_PREFIX,
+            self.database,
+            (Arc::new(self.codebase), self.settings, self.parser_settings),
+            Box::new(DependencyGraphReducer),
+            self.use_progress_bars,
+        );
+
+        pipeline.run(|(codebase, guard_settings, parser_settings), arena, source_file| {
+            let program = parse_file_with_settings(arena, &source_file, parser_settings);
+            let resolved_names = NameResolver::new(arena).resolve(program);
+
+            Ok(ArchitecturalGuard::new(guard_settings).graph(&codebase, program, &resolved_names))
+        })
+    }
 }
 
 /// The "reduce" step for the guard pipeline.
 ///
 /// This struct aggregates the `IssueCollection` from each parallel task into a single,
-/// final `IssueCollection` for the entire project.
+/// final `IssueCollection` for the entire project, and merges the dependency graphs of
+/// the files, if any were collected.
 #[derive(Debug, Clone)]
 struct GuardResultReducer;
 
-impl StatelessReducer<IssueCollection, IssueCollection> for GuardResultReducer {
-    fn reduce(&self, results: Vec<IssueCollection>) -> Result<IssueCollection, OrchestratorError> {
+impl StatelessReducer<(IssueCollection, Option<DependencyGraph>), (IssueCollection, DependencyGraph)>
+    for GuardResultReducer
+{
+    fn reduce(
+        &self,
+        results: Vec<(IssueCollection, Option<DependencyGraph>)>,
+    ) -> Result<(IssueCollection, DependencyGraph), OrchestratorError> {
         let mut aggregated_issues = IssueCollection::new();
+        let mut aggregated_graph = DependencyGraph::default();
+
+        for (issues, graph) in results {
+            aggregated_issues.extend(issues);
+
+            if let Some(graph) = graph {
+                aggregated_graph.extend(graph);
+            }
+        }
+
+        Ok((aggregated_issues, aggregated_graph))
+    }
+}
+
+/// The "reduce" step for the dependency graph pipeline, merging the graphs of all files.
+#[derive(Debug, Clone)]
+struct DependencyGraphReducer;
+
+impl StatelessReducer<DependencyGraph, DependencyGraph> for DependencyGraphReducer {
+    fn reduce(&self, results: Vec<DependencyGraph>) -> Result<DependencyGraph, OrchestratorError> {
+        let mut graph = DependencyGraph::default();
 
         for result in results {
-            aggregated_issues.extend(result);
+            graph.extend(result);
         }
 
-        Ok(aggregated_issues)
+        Ok(graph)
     }
 }
@@ -1,3 +1,4 @@
+use std::path::Path;
 use std::sync::Arc;
 #[cfg(not(target_arch = "wasm32"))]
 use std::sync::atomic::AtomicU64;
@@ -11,12 +12,15 @@ use std::time::Instant;
 use foldhash::HashMap;
 use foldhash::HashSet;
 use rayon::prelude::*;
+use serde::Deserialize;
+use serde::Serialize;
 
 use mago_allocator::LocalArena;
 use mago_analyzer::Analyzer;
 use mago_analyzer::analysis_result::AnalysisResult;
 use mago_analyzer::analysis_result::LateSymbolReferenceIssueReconciler;
 use mago_analyzer::artifacts::AnalysisArtifacts;
+use mago_analyzer::dead_code::find_unused_symbols;
 use mago_analyzer::external::AFTER_FILE_ANALYSIS_BATCH_SIZE;
 use mago_analyzer::external::CodebaseScanFile;
 use mago_analyzer::external::CodebaseScanPlan;
@@ -28,8 +32,10 @@ use mago_codex::metadata::CodebaseEntryKeys;
 use mago_codex::metadata::CodebaseMetadata;
 use mago_codex::populator::populate_codebase;
 use mago_codex::populator::populate_codebase_targeted;
+use mago_codex::populator::populate_taint_summaries;
 use mago_codex::reference::SymbolReferences;
 use mago_codex::scanner::scan_program;
+use mago_codex::scanner::scan_taint_flows;
 use mago_codex::signature_builder;
 use mago_collector::DeferredPragmas;
 use mago_database::DatabaseReader;
@@ -48,17 +54,51 @@ use crate::error::OrchestratorError;
 use crate::service::issue_reconciliation::DeferredIssueReconciler;
 
 /// Per-file cached state for incremental analysis.
-#[derive(Debug, Clone)]
+///
+/// Deferred pragmas are only retained for external analyzers, which disable the
+/// on-disk cache, so they are not persisted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct FileState {
     content_hash: u64,
     entry_keys: CodebaseEntryKeys,
     unreconciled_analysis_issues: IssueCollection,
     analysis_issues: IssueCollection,
     codebase_issues: IssueCollection,
+    #[serde(skip)]
     deferred_pragmas: Option<DeferredPragmas>,
     late_symbol_references: SymbolReferences,
 }
 
+/// The state of an [`IncrementalAnalysisService`] as written to the on-disk cache.
+///
+/// Fields borrow from the service when saving; see [`AnalysisCache`] for loading.
+#[derive(Serialize)]
+struct AnalysisCacheRef<'cache> {
+    key: u64,
+    codebase: &'cache CodebaseMetadata,
+    symbol_references: &'cache SymbolReferences,
+    native_symbol_references: &'cache SymbolReferences,
+    external_symbol_references: &'cache SymbolReferences,
+    late_symbol_references: &'cache SymbolReferences,
+    file_states: &'cache HashMap<FileId, FileState>,
+    codebase_issues: &'cache IssueCollection,
+    lifecycle_issues: &'cache IssueCollection,
+}
+
+/// The owned counterpart of [`AnalysisCacheRef`]; both must list the same fields in the same order.
+#[derive(Deserialize)]
+struct AnalysisCache {
+    key: u64,
+    codebase: CodebaseMetadata,
+    symbol_references: SymbolReferences,
+    native_symbol_references: SymbolReferences,
+    external_symbol_references: SymbolReferences,
+    late_symbol_references: SymbolReferences,
+    file_states: HashMap<FileId, FileState>,
+    codebase_issues: IssueCollection,
+    lifecycle_issues: IssueCollection,
+}
+
 struct SelectiveAnalysisOutput {
     result: AnalysisResult,
     native_symbol_references: SymbolReferences,
@@ -240,6 +280,138 @@ impl IncrementalAnalysisService {
         self.file_states.len()
     }
 
+    /// Restores the state of a previous run from the on-disk cache at `path`.
+    ///
+    /// The cache is only used when it was written by the same Mago version with the same
+    /// analyzer and parser settings and the same `fingerprint`, which callers use for
+    /// anything else that affects the results (such as the stubs or plugins in use).
+    /// When this returns `true`, [`analyze_incremental()`](Self::analyze_incremental) only
+    /// re-analyzes the files that changed since the cache was written. A missing, stale,
+    /// or unreadable cache is ignored and `false` is returned.
+    pub fn load_cache(&mut self, path: &Path, fingerprint: &str) -> bool {
+        if !self.supports_cache() {
+            return false;
+        }
+
+        let bytes = match std::fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(error) => {
+                tracing::debug!("No analysis cache loaded from `{}`: {error}", path.display());
+
+                return false;
+            }
+        };
+
+        let cache: AnalysisCache = match bincode::serde::decode_from_slice(&bytes, bincode::config::standard()) {
+            Ok((cache, _)) => cache,
+            Err(error) => {
+                tracing::warn!("Ignoring unreadable analysis cache `{}`: {error}", path.display());
+
+                return false;
+            }
+        };
+
+        if cache.key != self.cache_key(fingerprint) {
+            tracing::debug!("Ignoring analysis cache `{}` written with different settings.", path.display());
+
+            return false;
+        }
+
+        self.codebase = cache.codebase;
+        self.symbol_references = cache.symbol_references;
+        self.native_symbol_references = cache.native_symbol_references;
+        self.external_symbol_references = cache.external_symbol_references;
+        self.late_symbol_references = cache.late_symbol_references;
+        self.file_states = cache.file_states;
+        self.codebase_issues = cache.codebase_issues;
+        self.lifecycle_issues = cache.lifecycle_issues;
+        self.initialized = true;
+
+        tracing::debug!("Loaded analysis cache for {} files from `{}`.", self.file_states.len(), path.display());
+
+        true
+    }
+
+    /// Writes the state of the last run to the on-disk cache at `path`.
+    ///
+    /// Does nothing when no analysis has been run yet, or when an external analyzer is in
+    /// use, as its state lives outside of this service.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OrchestratorError::General`] if the cache cannot be encoded or written.
+    pub fn save_cache(&self, path: &Path, fingerprint: &str) -> Result<(), OrchestratorError> {
+        if !self.initialized || !self.supports_cache() {
+            return Ok(());
+        }
+
+        let cache = AnalysisCacheRef {
+            key: self.cache_key(fingerprint),
+            codebase: &self.codebase,
+            symbol_references: &self.symbol_references,
+            native_symbol_references: &self.native_symbol_references,
+            external_symbol_references: &self.external_symbol_references,
+            late_symbol_references: &self.late_symbol_references,
+            file_states: &self.file_states,
+            codebase_issues: &self.codebase_issues,
+            lifecycle_issues: &self.lifecycle_issues,
+        };
+
+        let bytes = bincode::serde::encode_to_vec(&cache, bincode::config::standard())
+            .map_err(|error| OrchestratorError::General(format!("Failed to encode analysis cache: {error}")))?;
+
+        let write = || -> std::io::Result<()> {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+
+            // Write to a sibling file first so an interrupted run never leaves a truncated cache.
+            let temporary_path = path.with_extension("tmp");
+            std::fs::write(&temporary_path, bytes)?;
+            std::fs::rename(&temporary_path, path)
+        };
+
+        write().map_err(|error| {
+            OrchestratorError::General(format!("Failed to write analysis cache `{}`: {error}", path.display()))
+        })
+    }
+
+    /// Whether the state of this service can be persisted.
+    ///
+    /// External analyzers keep codebase scans and deferred pragmas that are not part of
+    /// the cache, so runs using them always start from scratch.
+    fn supports_cache(&self) -> bool {
+        matches!(self.plugin_registry.external_codebase_scan_plan(), Ok(None))
+            && matches!(self.plugin_registry.has_external_after_file_analysis_hooks(), Ok(false))
+            && matches!(self.plugin_registry.has_external_after_analysis_hooks(), Ok(false))
+    }
+
+    /// Computes the key a cache must match to be reused.
+    fn cache_key(&self, fingerprint: &str) -> u64 {
+        // Colors only affect how issues are rendered, not which issues are found. The sets are
+        // hashed in sorted order so that the key does not depend on their insertion order.
+        let settings = Settings {
+            use_colors: false,
+            unchecked_exceptions: WordSet::default(),
+            unchecked_exception_classes: WordSet::default(),
+            ..self.settings.clone()
+        };
+
+        let mut unchecked_exceptions: Vec<_> = self.settings.unchecked_exceptions.iter().collect();
+        unchecked_exceptions.sort();
+        let mut unchecked_exception_classes: Vec<_> = self.settings.unchecked_exception_classes.iter().collect();
+        unchecked_exception_classes.sort();
+
+        xxhash_rust::xxh3::xxh3_64(
+            format!(
+                "{}\0{settings:?}\0{unchecked_exceptions:?}\0{unchecked_exception_classes:?}\0{:?}\0{fingerprint}",
+                env!("CARGO_PKG_VERSION"),
+                self.parser_settings,
+            )
+            .as_bytes(),
+        )
+    }
+
     /// Merges `new_file_scans` into `merged_codebase` and re-applies patches so member
     /// updates survive the remove/re-add of changed vendor entries.
     ///
@@ -359,6 +531,7 @@ impl IncrementalAnalysisService {
 
         let parser_settings = self.parser_settings;
         let php_version = self.settings.version;
+        let infer_taint = self.settings.taint.enabled;
         let codebase_scan_plan = self.codebase_scan_plan.clone();
         let per_file_results: Vec<(FileId, u64, CodebaseMetadata, Option<CodebaseScanFile>)> = source_files
             .into_par_iter()
@@ -378,6 +551,10 @@ impl IncrementalAnalysisService {
                 let resolved_names = resolver.resolve(program);
                 let file_signature = signature_builder::build_file_signature(program, &resolved_names);
                 let mut metadata = scan_program(arena, &file, program, &resolved_names, php_version);
+                if infer_taint {
+                    scan_taint_flows(program, &resolved_names, &mut metadata);
+                }
+
                 metadata.set_file_signature(file.id, file_signature);
                 if file.file_type.is_patch() {
                     metadata.convert_partial_to_patch();
@@ -412,6 +589,9 @@ impl IncrementalAnalysisService {
 
         let mut symbol_references = (*self.base_symbol_references).clone();
         populate_codebase(&mut merged_codebase, &mut symbol_references, WordSet::default(), HashSet::default());
+        if self.settings.taint.enabled {
+            populate_taint_summaries(&mut merged_codebase);
+        }
         self.plugin_registry
             .run_external_codebase_scan(self.codebase_scan_files.values().cloned().collect())
             .map_err(mago_analyzer::error::AnalysisError::from)?;
@@ -628,6 +808,7 @@ impl IncrementalAnalysisService {
 
         let parser_settings = self.parser_settings;
         let php_version = self.settings.version;
+        let infer_taint = self.settings.taint.enabled;
         let codebase_scan_plan = self.codebase_scan_plan.clone();
         let scanned_files: Vec<(FileId, CodebaseMetadata, Option<CodebaseScanFile>)> = changed_files
             .into_par_iter()
@@ -644,6 +825,10 @@ impl IncrementalAnalysisService {
                 let resolver = NameResolver::new(arena);
                 let resolved_names = resolver.resolve(program);
                 let mut metadata = scan_program(arena, file, program, &resolved_names, php_version);
+                if infer_taint {
+                    scan_taint_flows(program, &resolved_names, &mut metadata);
+                }
+
                 metadata.set_file_signature(file.id, signature_builder::build_file_signature(program, &resolved_names));
                 if file.file_type.is_patch() {
                     metadata.convert_partial_to_patch();
@@ -804,6 +989,9 @@ impl IncrementalAnalysisService {
                 HashSet::default(),
                 changed_symbols,
             );
+            if self.settings.taint.enabled {
+                populate_taint_summaries(&mut merged_codebase);
+            }
             let SelectiveAnalysisOutput {
                 result: mut analysis_result,
                 native_symbol_references,
@@ -975,6 +1163,9 @@ impl IncrementalAnalysisService {
             safe_symbol_members,
             dirty_symbols,
         );
+        if self.settings.taint.enabled {
+            populate_taint_summaries(&mut merged_codebase);
+        }
         let mut files_to_skip: HashSet<FileId> = HashSet::default();
         for &file_id in &unchanged_file_ids {
             let file_invalid = self
@@ -1329,7 +1520,7 @@ impl IncrementalAnalysisService {
                 let semantics_checker = SemanticsChecker::new(settings.version);
                 let mut analyzer =
                     Analyzer::new(arena, &source_file, &resolved_names, codebase, plugin_registry, settings.clone());
-                if after_file || after_analysis {
+                if after_file || after_analysis || settings.find_unused_symbols {
                     analyzer = analyzer.with_deferred_pragmas();
                 }
                 if let Some(session) = external_session.as_deref() {
@@ -1485,6 +1676,14 @@ impl IncrementalAnalysisService {
         aggregated_result.symbol_references.extend(external_symbol_references.clone());
         aggregated_result.symbol_references.extend(late_symbol_references.clone());
 
+        // Reconciled after the per-file pragma states are saved above: these issues are
+        // recomputed on every run, including for files that are skipped.
+        if settings.find_unused_symbols {
+            let unused_symbols =
+                find_unused_symbols(codebase, &aggregated_result.symbol_references, &settings.entry_points);
+            aggregated_result.issues.extend(pragma_reconciler.reconcile(unused_symbols)?);
+        }
+
         let codebase_issues = codebase.take_issues(true);
         if after_analysis {
             let analyzed = snapshots.iter().map(|snapshot| snapshot.file_id()).collect::<HashSet<_>>();
@@ -1721,6 +1920,43 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_cache_round_trip_after_signature_change_matches_full() {
+        let cache_dir = tempfile::TempDir::new().unwrap();
+        let cache_path = cache_dir.path().join("analysis.bin");
+
+        let mut db = make_database(vec![
+            ("src/a.php", "<?php\nfunction compute(): int { return 1; }\n"),
+            ("src/b.php", "<?php\nfunction caller(): int { return compute(); }\n"),
+        ]);
+
+        let mut service = make_service(&db);
+        service.analyze().expect("Initial analysis failed.");
+        service.save_cache(&cache_path, "test").expect("Saving the cache failed.");
+
+        db.update(
+            FileId::new(b"src/a.php"),
+            Cow::Owned(b"<?php\nfunction compute(): string { return 'hello'; }\n".to_vec()),
+        );
+
+        let mut cached_service = make_service(&db);
+        assert!(!cached_service.load_cache(&cache_path, "other"), "A cache with a different fingerprint was loaded.");
+        assert!(
+            cached_service.load_cache(&cache_path, "test"),
+            "The cache written by the previous run was not loaded."
+        );
+        let incremental = cached_service.analyze_incremental(None).expect("Incremental analysis failed.");
+
+        let mut fresh_service = make_service(&db);
+        let full = fresh_service.analyze().expect("Full analysis failed.");
+
+        let (only_incr, only_full) = diff_issues(&incremental.issues, &full.issues);
+        assert!(
+            only_incr.is_empty() && only_full.is_empty(),
+            "After loading the cache, incremental != full.\n  Only in incremental: {only_incr:?}\n  Only in full: {only_full:?}"
+        );
+    }
+
     #[test]
     fn test_incremental_after_file_deletion_matches_full() {
         let mut db = make_database(vec![
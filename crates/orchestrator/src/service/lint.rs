@@ -19,6 +19,11 @@ use mago_reporting::IssueCollection;
 use mago_semantics::SemanticsChecker;
 use mago_syntax::parser::parse_file_with_settings;
 use mago_syntax::settings::ParserSettings;
+use mago_twig_linter::TwigLinter;
+use mago_twig_linter::parse_error_to_issue;
+use mago_twig_linter::settings::TwigLinterSettings;
+use mago_twig_syntax::parser::parse_file_with_settings as parse_template_with_settings;
+use mago_twig_syntax::settings::ParserSettings as TemplateParserSettings;
 
 use crate::OrchestratorError;
 use crate::service::pipeline::StatelessParallelPipeline;
@@ -47,6 +52,9 @@ pub struct LintService {
     /// The parser settings to configure the parsing process.
     parser_settings: ParserSettings,
 
+    /// The settings for linting Twig templates.
+    twig_settings: Arc<TwigLinterSettings>,
+
     /// Whether to display progress bars during linting.
     use_progress_bars: bool,
 
@@ -74,7 +82,21 @@ impl LintService {
         parser_settings: ParserSettings,
         use_progress_bars: bool,
     ) -> Self {
-        Self { database, settings, parser_settings, use_progress_bars, external_linter: None }
+        Self {
+            database,
+            settings,
+            parser_settings,
+            twig_settings: Arc::new(TwigLinterSettings::default()),
+            use_progress_bars,
+            external_linter: None,
+        }
+    }
+
+    /// Sets the settings used to lint Twig templates.
+    #[must_use]
+    pub fn with_twig_settings(mut self, twig_settings: TwigLinterSettings) -> Self {
+        self.twig_settings = Arc::new(twig_settings);
+        self
     }
 
     /// Adds worker-backed custom rules to parallel full lint runs.
@@ -134,6 +156,10 @@ impl LintService {
         include_disabled: bool,
     ) -> IssueCollection {
         let arena = LocalArena::new();
+        if file.is_twig_template() {
+            return lint_template(&arena, file, &self.twig_settings, mode, only);
+        }
+
         let program = parse_file_with_settings(&arena, file, self.parser_settings);
         let resolved_names = NameResolver::new(&arena).resolve(program);
 
@@ -175,6 +201,8 @@ impl LintService {
             php_version: self.settings.php_version,
             parser_settings: self.parser_settings,
             registry: Arc::new(self.create_registry(only, false)),
+            twig_settings: self.twig_settings,
+            only: only.map(Arc::from),
             mode,
             external_linter: self.external_linter,
         };
@@ -197,6 +225,10 @@ impl LintService {
         let result = pipeline.run(move |context, arena, file| {
             #[cfg(not(target_arch = "wasm32"))]
             let per_file_start = trace_enabled.then(Instant::now);
+            if file.is_twig_template() {
+                return Ok(lint_template(arena, &file, &context.twig_settings, context.mode, context.only.as_deref()));
+            }
+
             #[cfg(not(target_arch = "wasm32"))]
             let parse_start = trace_enabled.then(Instant::now);
             let program = parse_file_with_settings(arena, &file, context.parser_settings);
@@ -270,12 +302,40 @@ struct LintContext {
     pub parser_settings: ParserSettings,
     /// A pre-configured `RuleRegistry` instance.
     pub registry: Arc<RuleRegistry>,
+    /// The settings for linting Twig templates.
+    pub twig_settings: Arc<TwigLinterSettings>,
+    /// The rule codes to restrict Twig template issues to, if any.
+    pub only: Option<Arc<[String]>>,
     /// The operational mode, determining which checks to run.
     pub mode: LintMode,
     /// Worker-backed custom rules, when configured by the host.
     pub external_linter: Option<Arc<ExternalLinter>>,
 }
 
+/// Lints a Twig template.
+///
+/// In [`LintMode::SemanticsOnly`], only syntax errors are reported. Rule issues are restricted
+/// to the `only` codes when given; syntax errors are always reported.
+fn lint_template(
+    arena: &LocalArena,
+    file: &File,
+    settings: &TwigLinterSettings,
+    mode: LintMode,
+    only: Option<&[String]>,
+) -> IssueCollection {
+    let template = parse_template_with_settings(arena, file, TemplateParserSettings::default());
+    if template.has_errors() || mode == LintMode::SemanticsOnly {
+        return template.errors.iter().map(parse_error_to_issue).collect();
+    }
+
+    let mut issues = TwigLinter::new(arena, settings.clone()).lint(template);
+    if let Some(only) = only {
+        issues.filter_retain_codes(only);
+    }
+
+    issues
+}
+
 /// The "reduce" step for the linting pipeline.
 ///
 /// This struct implements both stateful and stateless reduction, aggregating
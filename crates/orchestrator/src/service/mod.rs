@@ -10,6 +10,7 @@
 //! - [`format::FormatService`]: Code formatting to ensure consistent style
 //! - [`guard::GuardService`]: Architectural rule enforcement
 //! - [`lint::LintService`]: Linting for code quality and best practices
+//! - [`refactor::RefactorService`]: Project-wide rename and move refactorings
 //!
 //! # Architecture
 //!
@@ -35,3 +36,4 @@ pub mod format;
 pub mod guard;
 pub mod incremental_analysis;
 pub mod lint;
+pub mod refactor;
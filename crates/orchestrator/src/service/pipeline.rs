@@ -12,8 +12,10 @@ use rayon::prelude::*;
 
 use mago_codex::metadata::CodebaseMetadata;
 use mago_codex::populator::populate_codebase;
+use mago_codex::populator::populate_taint_summaries;
 use mago_codex::reference::SymbolReferences;
 use mago_codex::scanner::scan_program;
+use mago_codex::scanner::scan_taint_flows;
 use mago_codex::signature_builder;
 use mago_word::WordSet;
 
@@ -103,6 +105,7 @@ pub struct ParallelPipeline<T, I, R> {
     php_version: PHPVersion,
     reducer: Box<dyn Reducer<I, R> + Send + Sync>,
     should_use_progress_bar: bool,
+    infer_taint: bool,
 }
 
 impl<T, I, R> std::fmt::Debug for ParallelPipeline<T, I, R>
@@ -120,6 +123,7 @@ where
             .field("php_version", &self.php_version)
             .field("reducer", &"<reducer>")
             .field("should_use_progress_bar", &self.should_use_progress_bar)
+            .field("infer_taint", &self.infer_taint)
             .finish()
     }
 }
@@ -166,9 +170,19 @@ where
             php_version,
             reducer,
             should_use_progress_bar,
+            infer_taint: false,
         }
     }
 
+    /// Sets whether the compile phase infers the taint summaries of function-likes and properties.
+    ///
+    /// This is only needed by taint analysis, so it is disabled by default.
+    #[must_use]
+    pub fn with_taint_inference(mut self, infer_taint: bool) -> Self {
+        self.infer_taint = infer_taint;
+        self
+    }
+
     /// Executes the full pipeline with a given map function.
     ///
     /// # Arguments
@@ -220,6 +234,7 @@ where
 
         let parser_settings = self.parser_settings;
         let php_version = self.php_version;
+        let infer_taint = self.infer_taint;
         #[cfg(not(target_arch = "wasm32"))]
         let source_count = source_files.len();
 
@@ -245,6 +260,10 @@ where
                     let file_signature = signature_builder::build_file_signature(program, &resolved_names);
 
                     let mut metadata = scan_program(arena, &file, program, &resolved_names, php_version);
+                    if infer_taint {
+                        scan_taint_flows(program, &resolved_names, &mut metadata);
+                    }
+
                     metadata.set_file_signature(file.id, file_signature);
                     if file.file_type.is_patch() {
                         metadata.convert_partial_to_patch();
@@ -303,6 +322,9 @@ where
         let mut populate_duration = Duration::ZERO;
         measure!(trace_enabled, populate_duration, {
             populate_codebase(&mut merged_codex, &mut symbol_references, safe_symbols, safe_symbol_members);
+            if infer_taint {
+                populate_taint_summaries(&mut merged_codex);
+            }
         });
 
         if let Some(compiling_bar) = compiling_bar {
@@ -0,0 +1,1636 @@
+//! Project-wide rename and move refactorings.
+//!
+//! The [`RefactorService`] plans a [`Refactoring`] over every source file of the project and
+//! returns the resulting [`TextEdit`]s grouped by file, without writing anything. Callers
+//! decide whether to preview the plan as a diff or to apply it.
+//!
+//! # Supported Refactorings
+//!
+//! - Renaming a class, interface, trait, or enum, including `use` imports, `::class`
+//!   lookups, docblock type references, and class names passed as strings to attributes
+//! - Renaming a method, property, or class constant (or enum case) across the class
+//!   hierarchy that declares it
+//! - Moving a class-like to another namespace
+//!
+//! # How Occurrences Are Found
+//!
+//! Class-like references come from name resolution, so imports and aliases are honored: an
+//! aliased import keeps its alias, and a reference that no longer resolves after a move is
+//! either imported or fully qualified. Member references are matched against the receiver
+//! types inferred by the analyzer, and only the files that the symbol references map records
+//! as using the member are inspected.
+//!
+//! Occurrences that cannot be rewritten safely, such as a grouped import whose prefix no
+//! longer matches the moved class, are reported in [`RefactorPlan::skipped`] instead.
+
+use foldhash::HashMap;
+use foldhash::HashSet;
+
+use mago_allocator::LocalArena;
+use mago_analyzer::artifacts::AnalysisArtifacts;
+use mago_codex::metadata::CodebaseMetadata;
+use mago_codex::metadata::class_like::ClassLikeMetadata;
+use mago_codex::reference::ReferenceOrigin;
+use mago_codex::ttype::atomic::TAtomic;
+use mago_database::DatabaseReader;
+use mago_database::file::File;
+use mago_database::file::FileId;
+use mago_database::file::FileType;
+use mago_names::ResolvedNames;
+use mago_names::kind::NameKind;
+use mago_names::resolver::NameResolver;
+use mago_names::scope::NamespaceScope;
+use mago_reporting::Annotation;
+use mago_reporting::Issue;
+use mago_reporting::IssueCollection;
+use mago_span::HasSpan;
+use mago_span::Position;
+use mago_span::Span;
+use mago_syntax::cst::AnonymousClass;
+use mago_syntax::cst::Attribute;
+use mago_syntax::cst::Class;
+use mago_syntax::cst::ClassConstantAccess;
+use mago_syntax::cst::ClassLikeConstantItem;
+use mago_syntax::cst::ClassLikeConstantSelector;
+use mago_syntax::cst::ClassLikeMemberSelector;
+use mago_syntax::cst::Enum;
+use mago_syntax::cst::EnumCaseItem;
+use mago_syntax::cst::Expression;
+use mago_syntax::cst::Interface;
+use mago_syntax::cst::LiteralString;
+use mago_syntax::cst::LiteralStringKind;
+use mago_syntax::cst::LocalIdentifier;
+use mago_syntax::cst::Method;
+use mago_syntax::cst::MethodCall;
+use mago_syntax::cst::MethodPartialApplication;
+use mago_syntax::cst::NamespaceBody;
+use mago_syntax::cst::NullSafeMethodCall;
+use mago_syntax::cst::NullSafePropertyAccess;
+use mago_syntax::cst::Program;
+use mago_syntax::cst::PropertyAccess;
+use mago_syntax::cst::PropertyItem;
+use mago_syntax::cst::Statement;
+use mago_syntax::cst::StaticMethodCall;
+use mago_syntax::cst::StaticMethodPartialApplication;
+use mago_syntax::cst::StaticPropertyAccess;
+use mago_syntax::cst::Trait;
+use mago_syntax::cst::UseItem;
+use mago_syntax::cst::UseItems;
+use mago_syntax::cst::Variable;
+use mago_syntax::parser::parse_file_with_settings;
+use mago_syntax::settings::ParserSettings;
+use mago_syntax::walker::Walker;
+use mago_text_edit::TextEdit;
+use mago_word::Word;
+use mago_word::WordSet;
+use mago_word::ascii_lowercase_word;
+use mago_word::word;
+
+use crate::error::OrchestratorError;
+use crate::service::incremental_analysis::IncrementalAnalysisService;
+
+/// A project-wide refactoring.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refactoring {
+    /// Renames a symbol, keeping it in its namespace or class.
+    ///
+    /// `symbol` is either a class-like name (`App\Foo`) or a member of one: `App\Foo::bar`
+    /// for a method, constant, or enum case, and `App\Foo::$bar` for a property. `new_name`
+    /// is the new short name.
+    Rename { symbol: String, new_name: String },
+    /// Moves a class-like to another namespace, keeping its short name.
+    Move { class_like: String, namespace: String },
+}
+
+/// The edits produced by planning a [`Refactoring`].
+#[derive(Debug, Default)]
+pub struct RefactorPlan {
+    /// A human-readable summary of the refactoring.
+    pub description: String,
+    /// The edits to apply, grouped by file.
+    pub edits: HashMap<FileId, Vec<TextEdit>>,
+    /// Occurrences that could not be rewritten automatically and need manual attention.
+    pub skipped: IssueCollection,
+}
+
+impl RefactorPlan {
+    /// Returns `true` if the plan does not change any file.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.edits.values().all(Vec::is_empty)
+    }
+
+    /// Returns the total number of edits in the plan.
+    #[must_use]
+    pub fn edit_count(&self) -> usize {
+        self.edits.values().map(Vec::len).sum()
+    }
+
+    /// Converts the plan into issues that can go through the regular fix pipeline.
+    ///
+    /// All edits are carried by a single issue, so they are applied (or previewed) together;
+    /// the skipped occurrences follow as warnings without edits.
+    #[must_use]
+    pub fn into_issues(self) -> IssueCollection {
+        let mut issues = IssueCollection::new();
+        if !self.is_empty() {
+            let mut issue = Issue::help(self.description);
+            for (file_id, edits) in self.edits {
+                issue = issue.with_file_edits(file_id, edits);
+            }
+
+            issues.push(issue);
+        }
+
+        issues.extend(self.skipped);
+        issues
+    }
+}
+
+/// Service for planning project-wide rename and move refactorings.
+pub struct RefactorService {
+    /// The analysis providing the codebase, symbol references, and inferred types.
+    analysis: IncrementalAnalysisService,
+
+    /// The parser settings to configure the parsing process.
+    parser_settings: ParserSettings,
+}
+
+impl RefactorService {
+    /// Creates a new instance of the `RefactorService`.
+    ///
+    /// # Arguments
+    ///
+    /// * `analysis` - The analysis service of the project to refactor.
+    /// * `parser_settings` - The parser settings to configure the parsing process.
+    #[must_use]
+    pub fn new(analysis: IncrementalAnalysisService, parser_settings: ParserSettings) -> Self {
+        Self { analysis, parser_settings }
+    }
+
+    /// Plans the given refactoring.
+    ///
+    /// The first call runs a full analysis of the project; later calls reuse it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OrchestratorError::General`] when the symbol does not exist or is not part of
+    /// the project, when the new name is invalid or already taken, or when the declaration
+    /// does not support the refactoring. Analysis errors are propagated as well.
+    pub fn plan(&mut self, refactoring: &Refactoring) -> Result<RefactorPlan, OrchestratorError> {
+        if !self.analysis.is_initialized() {
+            self.analysis.analyze()?;
+        }
+
+        match refactoring {
+            Refactoring::Rename { symbol, new_name } => match symbol.split_once("::") {
+                Some((class_like, member)) => self.plan_member_rename(class_like, member, new_name),
+                None => self.plan_class_like_rename(symbol, new_name),
+            },
+            Refactoring::Move { class_like, namespace } => self.plan_class_like_move(class_like, namespace),
+        }
+    }
+
+    fn plan_class_like_rename(&self, symbol: &str, new_name: &str) -> Result<RefactorPlan, OrchestratorError> {
+        let metadata = self.find_class_like(symbol)?;
+        if new_name.contains('\\') || !is_valid_name(new_name) {
+            return Err(OrchestratorError::General(format!(
+                "`{new_name}` is not a valid class name; use `move` to change the namespace."
+            )));
+        }
+
+        let original_name = metadata.original_name.as_str_lossy();
+        let (namespace, _) = split_name(&original_name);
+        let new_fqn = join_name(namespace, new_name);
+
+        self.plan_class_like_change(metadata, &new_fqn)
+    }
+
+    fn plan_class_like_move(&self, class_like: &str, namespace: &str) -> Result<RefactorPlan, OrchestratorError> {
+        let metadata = self.find_class_like(class_like)?;
+        let namespace = namespace.trim_matches('\\');
+        if namespace.is_empty() {
+            return Err(OrchestratorError::General("Moving to the global namespace is not supported.".to_string()));
+        }
+
+        if !is_valid_name(namespace) {
+            return Err(OrchestratorError::General(format!("`{namespace}` is not a valid namespace.")));
+        }
+
+        let original_name = metadata.original_name.as_str_lossy();
+        let (_, short_name) = split_name(&original_name);
+        let new_fqn = join_name(namespace, short_name);
+
+        self.plan_class_like_change(metadata, &new_fqn)
+    }
+
+    fn plan_class_like_change(
+        &self,
+        metadata: &ClassLikeMetadata,
+        new_fqn: &str,
+    ) -> Result<RefactorPlan, OrchestratorError> {
+        let codebase = self.analysis.codebase();
+        let old_fqn = metadata.original_name.to_string();
+        if old_fqn == new_fqn {
+            return Err(OrchestratorError::General(format!("`{old_fqn}` is already named `{new_fqn}`.")));
+        }
+
+        if !old_fqn.eq_ignore_ascii_case(new_fqn) && codebase.class_like_exists(new_fqn.as_bytes()) {
+            return Err(OrchestratorError::General(format!("`{new_fqn}` already exists.")));
+        }
+
+        let rewrite = ClassLikeRewrite::new(codebase, metadata, &old_fqn, new_fqn);
+        let mut plan = RefactorPlan {
+            description: if rewrite.is_move() {
+                format!("Move `{old_fqn}` to `{new_fqn}`.")
+            } else {
+                format!("Rename `{old_fqn}` to `{new_fqn}`.")
+            },
+            ..RefactorPlan::default()
+        };
+
+        let database = self.analysis.database();
+        let mut files = database
+            .files_with_type(FileType::Host)
+            .filter(|file| {
+                file.id == rewrite.declaring_file
+                    || contains_ignore_ascii_case(&file.contents, rewrite.old_short.as_bytes())
+            })
+            .collect::<Vec<_>>();
+        files.sort_unstable_by_key(|file| file.id);
+
+        for file in files {
+            let arena = LocalArena::new();
+            let program = parse_file_with_settings(&arena, &file, self.parser_settings);
+            if let Some(error) = program.errors.first() {
+                plan.skipped.push(skipped_file_issue(&file, error.span()));
+                continue;
+            }
+
+            let resolved_names = NameResolver::new(&arena).resolve(program);
+            let mut layout = FileLayout::new(program);
+            let edits = rewrite.file_edits(&file, program, &resolved_names, &mut layout, &mut plan.skipped)?;
+            if !edits.is_empty() {
+                plan.edits.insert(file.id, edits);
+            }
+        }
+
+        Ok(plan)
+    }
+
+    fn plan_member_rename(
+        &self,
+        class_like: &str,
+        member: &str,
+        new_name: &str,
+    ) -> Result<RefactorPlan, OrchestratorError> {
+        let codebase = self.analysis.codebase();
+        let metadata = self.find_class_like(class_like)?;
+        let (kind, name) = MemberKind::resolve(metadata, member)?;
+        let new_name = if kind == MemberKind::Property { new_name.trim_start_matches('$') } else { new_name };
+        if new_name.contains('\\') || !is_valid_name(new_name) {
+            return Err(OrchestratorError::General(format!("`{new_name}` is not a valid {} name.", kind.as_str())));
+        }
+
+        if name == new_name {
+            return Err(OrchestratorError::General(format!("The {} is already named `{new_name}`.", kind.as_str())));
+        }
+
+        let roots = std::iter::once(&metadata.name)
+            .chain(metadata.all_parent_classes.iter())
+            .chain(metadata.all_parent_interfaces.iter())
+            .filter_map(|class_like| codebase.get_class_like(class_like.as_bytes()))
+            .filter(|class_like| kind.is_declared_by(class_like, name))
+            .collect::<Vec<_>>();
+
+        if roots.is_empty() {
+            return Err(OrchestratorError::General(format!(
+                "`{}::{}` is not declared by a class or interface; members provided by traits cannot be renamed.",
+                metadata.original_name,
+                kind.display(name),
+            )));
+        }
+
+        let mut family = WordSet::default();
+        for root in &roots {
+            if !root.flags.is_user_defined() {
+                return Err(OrchestratorError::General(format!(
+                    "`{}::{}` is declared by `{}`, which is not part of the project.",
+                    metadata.original_name,
+                    kind.display(name),
+                    root.original_name,
+                )));
+            }
+
+            family.insert(ascii_lowercase_word(root.name.as_bytes()));
+            family.extend(codebase.get_all_descendants(root.name.as_bytes()));
+        }
+
+        for class_like in family.iter().filter_map(|class_like| codebase.get_class_like(class_like.as_bytes())) {
+            if kind.is_available_on(class_like, new_name)
+                && !(kind == MemberKind::Method && name.eq_ignore_ascii_case(new_name))
+            {
+                return Err(OrchestratorError::General(format!(
+                    "`{}` already has a {} named `{}`.",
+                    class_like.original_name,
+                    kind.as_str(),
+                    kind.display(new_name),
+                )));
+            }
+
+            if kind == MemberKind::Property
+                && class_like
+                    .properties
+                    .get(&word(format!("${name}")))
+                    .is_some_and(|property| property.flags.is_promoted_property())
+            {
+                return Err(OrchestratorError::General(format!(
+                    "`{}::${name}` is a promoted constructor property; renaming it would change the constructor's named arguments.",
+                    class_like.original_name,
+                )));
+            }
+        }
+
+        let mut plan = RefactorPlan {
+            description: format!(
+                "Rename {} `{}::{}` to `{}`.",
+                kind.as_str(),
+                metadata.original_name,
+                kind.display(name),
+                kind.display(new_name),
+            ),
+            ..RefactorPlan::default()
+        };
+
+        let rename = MemberRename { kind, name, new_name, family: &family };
+        for file_id in self.member_rename_candidates(&rename) {
+            let Ok(file) = self.analysis.database().get(&file_id) else {
+                continue;
+            };
+
+            if file.file_type != FileType::Host {
+                continue;
+            }
+
+            let Some((_, artifacts)) = self.analysis.analyze_file_with_artifacts(file_id) else {
+                continue;
+            };
+
+            let arena = LocalArena::new();
+            let program = parse_file_with_settings(&arena, &file, self.parser_settings);
+            if let Some(error) = program.errors.first() {
+                plan.skipped.push(skipped_file_issue(&file, error.span()));
+                continue;
+            }
+
+            let resolved_names = NameResolver::new(&arena).resolve(program);
+            let mut context = MemberContext {
+                rename: &rename,
+                codebase,
+                resolved_names: &resolved_names,
+                artifacts: &artifacts,
+                class_likes: Vec::new(),
+                edits: Vec::new(),
+            };
+
+            MemberWalker.walk_program(program, &mut context);
+            if !context.edits.is_empty() {
+                plan.edits.insert(file_id, context.edits);
+            }
+        }
+
+        Ok(plan)
+    }
+
+    /// Returns the files that declare a class of the member's hierarchy, or that the symbol
+    /// references map records as referencing the member.
+    fn member_rename_candidates(&self, rename: &MemberRename<'_>) -> Vec<FileId> {
+        let codebase = self.analysis.codebase();
+        let database = self.analysis.database();
+
+        let mut files = rename
+            .family
+            .iter()
+            .filter_map(|class_like| codebase.get_class_like(class_like.as_bytes()))
+            .map(|class_like| class_like.span.file_id)
+            .collect::<HashSet<_>>();
+
+        self.analysis.symbol_references().for_each_reference(|origin, (class_like, member), _| {
+            if !rename.family.contains(&ascii_lowercase_word(class_like.as_bytes()))
+                || !rename.kind.matches_reference(member.as_bytes(), rename.name)
+            {
+                return;
+            }
+
+            let file_id = match origin {
+                ReferenceOrigin::Symbol((symbol, member)) if member.is_empty() => codebase
+                    .get_class_like(symbol.as_bytes())
+                    .map(|class_like| class_like.span.file_id)
+                    .or_else(|| codebase.get_function(symbol.as_bytes()).map(|function| function.span.file_id)),
+                ReferenceOrigin::Symbol((symbol, _)) => {
+                    codebase.get_class_like(symbol.as_bytes()).map(|class_like| class_like.span.file_id)
+                }
+                ReferenceOrigin::File(name) => database.get_id(name.as_bytes()),
+            };
+
+            files.extend(file_id);
+        });
+
+        let mut files = files.into_iter().collect::<Vec<_>>();
+        files.sort_unstable();
+        files
+    }
+
+    fn find_class_like(&self, name: &str) -> Result<&ClassLikeMetadata, OrchestratorError> {
+        let name = name.trim_start_matches('\\');
+        let Some(metadata) = self.analysis.codebase().get_class_like(name.as_bytes()) else {
+            return Err(OrchestratorError::General(format!("Class-like `{name}` does not exist.")));
+        };
+
+        let declared_in_project = metadata.flags.is_user_defined()
+            && metadata.name_span.is_some()
+            && self
+                .analysis
+                .database()
+                .get_ref(&metadata.span.file_id)
+                .is_ok_and(|file| file.file_type == FileType::Host);
+
+        if !declared_in_project {
+            return Err(OrchestratorError::General(format!(
+                "`{}` is not declared in the project's source files.",
+                metadata.original_name
+            )));
+        }
+
+        Ok(metadata)
+    }
+}
+
+/// The renamed and moved spellings of a class-like, and how to rewrite its references.
+struct ClassLikeRewrite<'codebase> {
+    codebase: &'codebase CodebaseMetadata,
+    old_name: String,
+    old_short: String,
+    old_namespace: String,
+    new_name: String,
+    new_short: String,
+    new_namespace: String,
+    declaring_file: FileId,
+    name_offset: Option<u32>,
+}
+
+impl<'codebase> ClassLikeRewrite<'codebase> {
+    fn new(
+        codebase: &'codebase CodebaseMetadata,
+        metadata: &ClassLikeMetadata,
+        old_name: &str,
+        new_name: &str,
+    ) -> Self {
+        let (old_namespace, old_short) = split_name(old_name);
+        let (new_namespace, new_short) = split_name(new_name);
+
+        Self {
+            codebase,
+            old_name: old_name.to_string(),
+            old_short: old_short.to_string(),
+            old_namespace: old_namespace.to_string(),
+            new_name: new_name.to_string(),
+            new_short: new_short.to_string(),
+            new_namespace: new_namespace.to_string(),
+            declaring_file: metadata.span.file_id,
+            name_offset: metadata.name_span.map(|span| span.start.offset),
+        }
+    }
+
+    fn is_move(&self) -> bool {
+        !self.old_namespace.eq_ignore_ascii_case(&self.new_namespace)
+    }
+
+    fn fully_qualified(&self) -> String {
+        format!("\\{}", self.new_name)
+    }
+
+    /// The new short name, if it differs from the old one.
+    fn renamed_short(&self) -> Option<String> {
+        (self.old_short != self.new_short).then(|| self.new_short.clone())
+    }
+
+    fn replace_last_segment(&self, written: &str) -> Option<String> {
+        let replacement = match written.rsplit_once('\\') {
+            Some((prefix, _)) => format!("{prefix}\\{}", self.new_short),
+            None => self.new_short.clone(),
+        };
+
+        (replacement != written).then_some(replacement)
+    }
+
+    fn file_edits(
+        &self,
+        file: &File,
+        program: &Program<'_>,
+        resolved_names: &ResolvedNames<'_>,
+        layout: &mut FileLayout,
+        skipped: &mut IssueCollection,
+    ) -> Result<Vec<TextEdit>, OrchestratorError> {
+        let contents = &*file.contents;
+        let in_moved_file = self.is_move() && file.id == self.declaring_file;
+
+        let mut edits = Vec::new();
+        if in_moved_file {
+            self.move_namespace(file, layout)?;
+        }
+
+        for (start, end, name, imported) in resolved_names.iter() {
+            if layout.typed_use_items.contains(&start) {
+                continue;
+            }
+
+            let written = String::from_utf8_lossy(&contents[start as usize..end as usize]);
+            let region = layout.region_at(start);
+
+            if name.eq_ignore_ascii_case(self.old_name.as_bytes()) {
+                if file.id == self.declaring_file && Some(start) == self.name_offset {
+                    if let Some(short) = self.renamed_short() {
+                        edits.push(TextEdit::replace(start..end, short));
+                    }
+
+                    continue;
+                }
+
+                if let Some(item) = layout.use_items.iter().find(|item| item.start == start) {
+                    match self.rewrite_import(&written, item.group_prefix.as_deref()) {
+                        Some(text) => edits.push(TextEdit::replace(start..end, text)),
+                        None => skipped.push(
+                            Issue::warning(format!("Could not update a grouped import of `{}`.", self.old_name))
+                                .with_annotation(
+                                    Annotation::primary(Span::new(file.id, Position::new(start), Position::new(end)))
+                                        .with_message("The group prefix does not match the new namespace."),
+                                )
+                                .with_help(format!("Import `{}` in a separate `use` statement.", self.new_name)),
+                        ),
+                    }
+
+                    continue;
+                }
+
+                if let Some(text) = self.rewrite_reference(layout, region, &written, imported, in_moved_file) {
+                    edits.push(TextEdit::replace(start..end, text));
+                }
+            } else if in_moved_file
+                && !imported
+                && layout.regions[region].name_span.is_none_or(|(name_start, _)| name_start != start)
+                && let Some(text) = self.requalify(layout, region, &String::from_utf8_lossy(name), &written)
+            {
+                edits.push(TextEdit::replace(start..end, text));
+            }
+        }
+
+        for trivia in program.trivia.iter().filter(|trivia| trivia.kind.is_docblock()) {
+            for (offset, written) in docblock_names(trivia.value) {
+                let start = trivia.span.start.offset + offset as u32;
+                let end = start + written.len() as u32;
+                let region = layout.region_at(start);
+                let (name, imported) = layout.regions[region].scope.resolve(NameKind::Default, written);
+                let written = String::from_utf8_lossy(written);
+
+                let text = if name.eq_ignore_ascii_case(self.old_name.as_bytes()) {
+                    self.rewrite_reference(layout, region, &written, imported, in_moved_file)
+                } else if in_moved_file && !imported && self.codebase.class_like_exists(&name) {
+                    self.requalify(layout, region, &String::from_utf8_lossy(&name), &written)
+                } else {
+                    None
+                };
+
+                if let Some(text) = text {
+                    edits.push(TextEdit::replace(start..end, text));
+                }
+            }
+        }
+
+        let mut strings = AttributeStrings::default();
+        AttributeStringWalker.walk_program(program, &mut strings);
+        for string in strings.strings {
+            if let Some(text) = self.rewrite_attribute_string(string) {
+                edits.push(TextEdit::replace(string.span.start.offset + 1..string.span.end.offset - 1, text));
+            }
+        }
+
+        edits.extend(layout.take_header_edits());
+
+        Ok(edits)
+    }
+
+    /// Changes the namespace of the moved class-like's file.
+    ///
+    /// The file must declare nothing but the moved class-like, as every other declaration
+    /// would move along with it.
+    fn move_namespace(&self, file: &File, layout: &mut FileLayout) -> Result<(), OrchestratorError> {
+        let file_name = String::from_utf8_lossy(&file.name);
+        let [region] = layout.regions.as_mut_slice() else {
+            return Err(OrchestratorError::General(format!(
+                "`{file_name}` declares more than one namespace; move `{}` to its own file first.",
+                self.old_name
+            )));
+        };
+
+        if region.declarations > 1 {
+            return Err(OrchestratorError::General(format!(
+                "`{file_name}` declares other symbols next to `{}`; move it to its own file first.",
+                self.old_name
+            )));
+        }
+
+        if region.name_span.is_none() {
+            if region.declaration_offset.is_none() {
+                return Err(OrchestratorError::General(format!(
+                    "Could not find where to declare the namespace in `{file_name}`."
+                )));
+            }
+
+            region.namespace_declaration = Some(format!("namespace {};", self.new_namespace));
+        }
+
+        region.new_namespace = Some(self.new_namespace.clone());
+
+        Ok(())
+    }
+
+    fn rewrite_import(&self, written: &str, group_prefix: Option<&str>) -> Option<String> {
+        let Some(prefix) = group_prefix else {
+            let leading = if written.starts_with('\\') { "\\" } else { "" };
+
+            return Some(format!("{leading}{}", self.new_name));
+        };
+
+        let prefix = format!("{}\\", prefix.trim_start_matches('\\'));
+        if self.new_name.len() > prefix.len() && self.new_name[..prefix.len()].eq_ignore_ascii_case(&prefix) {
+            Some(self.new_name[prefix.len()..].to_string())
+        } else {
+            None
+        }
+    }
+
+    fn rewrite_reference(
+        &self,
+        layout: &mut FileLayout,
+        region: usize,
+        written: &str,
+        imported: bool,
+        in_moved_file: bool,
+    ) -> Option<String> {
+        if written.starts_with('\\') || starts_with_ignore_ascii_case(written, "namespace\\") {
+            return Some(self.fully_qualified());
+        }
+
+        let qualified = written.contains('\\');
+        if imported {
+            if qualified {
+                return if self.is_move() { Some(self.fully_qualified()) } else { self.replace_last_segment(written) };
+            }
+
+            if layout.regions[region].explicit_aliases.contains(&written.to_ascii_lowercase()) {
+                return None;
+            }
+
+            return self.renamed_short();
+        }
+
+        if !self.is_move() {
+            return self.replace_last_segment(written);
+        }
+
+        if qualified {
+            return Some(self.fully_qualified());
+        }
+
+        if in_moved_file
+            || layout.regions[region].namespace().eq_ignore_ascii_case(&self.new_namespace)
+            || layout.import(region, &self.new_name, &self.new_short, self.codebase, &self.old_name)
+        {
+            return self.renamed_short();
+        }
+
+        Some(self.fully_qualified())
+    }
+
+    /// Rewrites a name in the moved file that was resolved relative to the old namespace.
+    fn requalify(&self, layout: &mut FileLayout, region: usize, name: &str, written: &str) -> Option<String> {
+        if written.starts_with('\\') || starts_with_ignore_ascii_case(written, "namespace\\") {
+            return None;
+        }
+
+        let in_old_namespace = if self.old_namespace.is_empty() {
+            !name.contains('\\')
+        } else {
+            name.len() > self.old_namespace.len()
+                && starts_with_ignore_ascii_case(name, &self.old_namespace)
+                && name.as_bytes()[self.old_namespace.len()] == b'\\'
+        };
+
+        if !in_old_namespace {
+            return None;
+        }
+
+        if self.codebase.class_like_exists(name.as_bytes()) {
+            if !written.contains('\\') && layout.import(region, name, written, self.codebase, &self.old_name) {
+                return None;
+            }
+
+            return Some(format!("\\{name}"));
+        }
+
+        let namespaced_function_or_constant = name.contains('\\')
+            && (self.codebase.function_exists(name.as_bytes()) || self.codebase.constant_exists(name.as_bytes()));
+
+        namespaced_function_or_constant.then(|| format!("\\{name}"))
+    }
+
+    fn rewrite_attribute_string(&self, string: &LiteralString<'_>) -> Option<String> {
+        let value = String::from_utf8_lossy(string.value?);
+        if !value.trim_start_matches('\\').eq_ignore_ascii_case(&self.old_name) || string.raw.len() < 2 {
+            return None;
+        }
+
+        let escaped = string.raw.windows(2).any(|pair| pair == b"\\\\")
+            || (string.kind == LiteralStringKind::DoubleQuoted
+                && self
+                    .new_name
+                    .split('\\')
+                    .skip(1)
+                    .any(|segment| segment.starts_with(['n', 't', 'v', 'e', 'f', 'r', 'u', 'x'])));
+
+        let leading = if value.starts_with('\\') { "\\" } else { "" };
+        let name = format!("{leading}{}", self.new_name);
+
+        Some(if escaped { name.replace('\\', "\\\\") } else { name })
+    }
+}
+
+/// The namespaces and imports of a parsed file.
+struct FileLayout {
+    regions: Vec<NamespaceRegion>,
+    use_items: Vec<UseItemSite>,
+    /// The starts of the `use function` and `use const` items, which never name a class-like.
+    typed_use_items: HashSet<u32>,
+}
+
+/// A namespace declaration, or the whole file when it declares none.
+struct NamespaceRegion {
+    start: u32,
+    end: u32,
+    namespace: String,
+    name_span: Option<(u32, u32)>,
+    scope: NamespaceScope,
+    explicit_aliases: HashSet<String>,
+    declarations: usize,
+    /// Where new imports go, and the separator to put before them.
+    header_offset: Option<(u32, &'static str)>,
+    /// Where a namespace declaration goes in a file that declares none.
+    declaration_offset: Option<u32>,
+    new_namespace: Option<String>,
+    namespace_declaration: Option<String>,
+    imports: Vec<String>,
+}
+
+/// A class import of a `use` statement.
+struct UseItemSite {
+    start: u32,
+    group_prefix: Option<String>,
+}
+
+impl FileLayout {
+    fn new(program: &Program<'_>) -> Self {
+        let mut layout = Self { regions: Vec::new(), use_items: Vec::new(), typed_use_items: HashSet::default() };
+
+        for statement in program.statements.iter() {
+            let Statement::Namespace(namespace) = statement else {
+                continue;
+            };
+
+            let span = namespace.span();
+            let mut region = NamespaceRegion::new(
+                span.start.offset,
+                span.end.offset,
+                namespace.name.as_ref().map(|name| (name.span(), String::from_utf8_lossy(name.value()).into_owned())),
+            );
+
+            if let NamespaceBody::Implicit(body) = &namespace.body {
+                region.header_offset = Some((body.terminator.span().end.offset, "\n\n"));
+            }
+
+            layout.scan(&mut region, namespace.statements().iter());
+            layout.regions.push(region);
+        }
+
+        if layout.regions.is_empty() {
+            let mut region = NamespaceRegion::new(0, u32::MAX, None);
+            region.declaration_offset = program
+                .statements
+                .iter()
+                .take_while(|statement| matches!(statement, Statement::OpeningTag(_) | Statement::Declare(_)))
+                .last()
+                .map(|statement| statement.span().end.offset);
+            region.header_offset = region.declaration_offset.map(|offset| (offset, "\n\n"));
+
+            layout.scan(&mut region, program.statements.iter());
+            layout.regions.push(region);
+        }
+
+        layout
+    }
+
+    fn scan<'ast, 'arena, I>(&mut self, region: &mut NamespaceRegion, statements: I)
+    where
+        'arena: 'ast,
+        I: Iterator<Item = &'ast Statement<'arena>>,
+    {
+        for statement in statements {
+            match statement {
+                Statement::Use(r#use) => {
+                    region.scope.populate_from_use(r#use);
+                    region.header_offset = Some((r#use.span().end.offset, "\n"));
+
+                    match &r#use.items {
+                        UseItems::Sequence(sequence) => {
+                            for item in sequence.items.iter() {
+                                self.add_use_item(region, item, None);
+                            }
+                        }
+                        UseItems::MixedList(list) => {
+                            let prefix = String::from_utf8_lossy(list.namespace.value()).into_owned();
+                            for item in list.items.iter() {
+                                if item.r#type.is_some() {
+                                    self.typed_use_items.insert(item.item.name.span().start.offset);
+                                } else {
+                                    self.add_use_item(region, &item.item, Some(prefix.clone()));
+                                }
+                            }
+                        }
+                        UseItems::TypedSequence(sequence) => {
+                            self.typed_use_items
+                                .extend(sequence.items.iter().map(|item| item.name.span().start.offset));
+                        }
+                        UseItems::TypedList(list) => {
+                            self.typed_use_items.extend(list.items.iter().map(|item| item.name.span().start.offset));
+                        }
+                    }
+                }
+                Statement::Class(_)
+                | Statement::Interface(_)
+                | Statement::Trait(_)
+                | Statement::Enum(_)
+                | Statement::Function(_)
+                | Statement::Constant(_) => {
+                    region.declarations += 1;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn add_use_item(&mut self, region: &mut NamespaceRegion, item: &UseItem<'_>, group_prefix: Option<String>) {
+        if let Some(alias) = &item.alias {
+            region.explicit_aliases.insert(String::from_utf8_lossy(alias.identifier.value).to_ascii_lowercase());
+        }
+
+        self.use_items.push(UseItemSite { start: item.name.span().start.offset, group_prefix });
+    }
+
+    fn region_at(&self, offset: u32) -> usize {
+        self.regions
+            .iter()
+            .position(|region| region.start <= offset && offset < region.end)
+            .unwrap_or(self.regions.len().saturating_sub(1))
+    }
+
+    /// Imports `name` under `short` in the given region, returning `false` when the short
+    /// name is already taken or the region has no place for imports.
+    fn import(&mut self, region: usize, name: &str, short: &str, codebase: &CodebaseMetadata, ignored: &str) -> bool {
+        let region = &mut self.regions[region];
+        let alias = short.to_ascii_lowercase();
+
+        if let Some(existing) = region.scope.default_aliases().get(alias.as_bytes()) {
+            return existing.eq_ignore_ascii_case(name.as_bytes());
+        }
+
+        if let Some(existing) = region.imports.iter().find(|import| split_name(import).1.eq_ignore_ascii_case(short)) {
+            return existing.eq_ignore_ascii_case(name);
+        }
+
+        if region.header_offset.is_none() {
+            return false;
+        }
+
+        let local = join_name(region.namespace(), short);
+        if !local.eq_ignore_ascii_case(ignored)
+            && !local.eq_ignore_ascii_case(name)
+            && codebase.class_like_exists(local.as_bytes())
+        {
+            return false;
+        }
+
+        region.imports.push(name.to_string());
+
+        true
+    }
+
+    /// Returns the edits that change the namespace declaration and add the pending imports.
+    fn take_header_edits(&mut self) -> Vec<TextEdit> {
+        let mut edits = Vec::new();
+
+        for region in &mut self.regions {
+            if let (Some(namespace), Some((start, end))) = (&region.new_namespace, region.name_span) {
+                edits.push(TextEdit::replace(start..end, namespace.as_str()));
+            }
+
+            let mut declaration = region
+                .namespace_declaration
+                .take()
+                .zip(region.declaration_offset)
+                .map(|(declaration, offset)| (offset, format!("\n\n{declaration}")));
+
+            if let Some((offset, separator)) = region.header_offset
+                && !region.imports.is_empty()
+            {
+                region.imports.sort_unstable_by_key(|import| import.to_ascii_lowercase());
+                let imports =
+                    region.imports.iter().map(|import| format!("use {import};")).collect::<Vec<_>>().join("\n");
+
+                // Inserts at the same offset are merged, as their relative order would be undefined.
+                match declaration.take() {
+                    Some((declaration_offset, text)) if declaration_offset == offset => {
+                        declaration = Some((offset, format!("{text}{separator}{imports}")));
+                    }
+                    other => {
+                        declaration = other;
+                        edits.push(TextEdit::insert(offset, format!("{separator}{imports}")));
+                    }
+                }
+            }
+
+            edits.extend(declaration.map(|(offset, text)| TextEdit::insert(offset, text)));
+        }
+
+        edits
+    }
+}
+
+impl NamespaceRegion {
+    fn new(start: u32, end: u32, name: Option<(Span, String)>) -> Self {
+        let (name_span, namespace) = match name {
+            Some((span, namespace)) => (Some((span.start.offset, span.end.offset)), namespace),
+            None => (None, String::new()),
+        };
+
+        Self {
+            start,
+            end,
+            scope: NamespaceScope::new((!namespace.is_empty()).then(|| namespace.clone().into_bytes())),
+            namespace,
+            name_span,
+            explicit_aliases: HashSet::default(),
+            declarations: 0,
+            header_offset: None,
+            declaration_offset: None,
+            new_namespace: None,
+            namespace_declaration: None,
+            imports: Vec::new(),
+        }
+    }
+
+    /// The namespace the region's names resolve against once the refactoring is applied.
+    fn namespace(&self) -> &str {
+        self.new_namespace.as_deref().unwrap_or(&self.namespace)
+    }
+}
+
+/// The kind of class-like member being renamed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MemberKind {
+    Method,
+    Property,
+    Constant,
+}
+
+impl MemberKind {
+    /// Determines which member `member` refers to, returning its kind and bare name.
+    fn resolve<'member>(
+        metadata: &ClassLikeMetadata,
+        member: &'member str,
+    ) -> Result<(Self, &'member str), OrchestratorError> {
+        if let Some(property) = member.strip_prefix('$') {
+            if metadata.declaring_property_ids.contains_key(&word(member)) {
+                return Ok((Self::Property, property));
+            }
+
+            return Err(OrchestratorError::General(format!(
+                "`{}` has no property named `{member}`.",
+                metadata.original_name
+            )));
+        }
+
+        if metadata.declaring_method_ids.contains_key(&ascii_lowercase_word(member.as_bytes())) {
+            return Ok((Self::Method, member));
+        }
+
+        if Self::Constant.is_available_on(metadata, member) {
+            return Ok((Self::Constant, member));
+        }
+
+        Err(OrchestratorError::General(format!(
+            "`{}` has no method or constant named `{member}`; prefix property names with `$`.",
+            metadata.original_name
+        )))
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Method => "method",
+            Self::Property => "property",
+            Self::Constant => "constant",
+        }
+    }
+
+    fn display(self, name: &str) -> String {
+        match self {
+            Self::Method => format!("{name}()"),
+            Self::Property => format!("${name}"),
+            Self::Constant => name.to_string(),
+        }
+    }
+
+    /// Returns `true` if the class-like declares the member itself.
+    fn is_declared_by(self, metadata: &ClassLikeMetadata, name: &str) -> bool {
+        match self {
+            Self::Method => metadata.methods.contains(&ascii_lowercase_word(name.as_bytes())),
+            Self::Property => metadata.properties.contains_key(&word(format!("${name}"))),
+            Self::Constant => {
+                metadata.constants.contains_key(&word(name)) || metadata.enum_cases.contains_key(&word(name))
+            }
+        }
+    }
+
+    /// Returns `true` if the class-like declares or inherits the member.
+    fn is_available_on(self, metadata: &ClassLikeMetadata, name: &str) -> bool {
+        match self {
+            Self::Method => metadata.declaring_method_ids.contains_key(&ascii_lowercase_word(name.as_bytes())),
+            Self::Property => metadata.declaring_property_ids.contains_key(&word(format!("${name}"))),
+            Self::Constant => {
+                metadata.constants.contains_key(&word(name))
+                    || metadata.enum_cases.contains_key(&word(name))
+                    || metadata.trait_constant_ids.contains_key(&word(name))
+            }
+        }
+    }
+
+    /// Returns `true` if a member name recorded in the symbol references map names the member.
+    fn matches_reference(self, reference: &[u8], name: &str) -> bool {
+        match self {
+            Self::Method => reference.eq_ignore_ascii_case(name.as_bytes()),
+            Self::Property => reference.strip_prefix(b"$").unwrap_or(reference) == name.as_bytes(),
+            Self::Constant => reference == name.as_bytes(),
+        }
+    }
+
+    /// Returns `true` if a name written in the source names the member.
+    fn matches_written(self, written: &[u8], name: &str) -> bool {
+        match self {
+            Self::Method => written.eq_ignore_ascii_case(name.as_bytes()),
+            Self::Property | Self::Constant => written == name.as_bytes(),
+        }
+    }
+}
+
+/// A member rename across the class-likes that share the member.
+struct MemberRename<'rename> {
+    kind: MemberKind,
+    name: &'rename str,
+    new_name: &'rename str,
+    /// The lowercase names of the class-likes declaring the member and of their descendants.
+    family: &'rename WordSet,
+}
+
+struct MemberContext<'ctx, 'arena> {
+    rename: &'ctx MemberRename<'ctx>,
+    codebase: &'ctx CodebaseMetadata,
+    resolved_names: &'ctx ResolvedNames<'arena>,
+    artifacts: &'ctx AnalysisArtifacts,
+    class_likes: Vec<Option<Word>>,
+    edits: Vec<TextEdit>,
+}
+
+impl MemberContext<'_, '_> {
+    fn current_class_like(&self) -> Option<Word> {
+        self.class_likes.last().copied().flatten()
+    }
+
+    fn enter_class_like(&mut self, name: &LocalIdentifier<'_>) {
+        let name = self.resolved_names.resolve(name).map(ascii_lowercase_word);
+
+        self.class_likes.push(name);
+    }
+
+    fn declares(&self, kind: MemberKind) -> bool {
+        self.rename.kind == kind && self.current_class_like().is_some_and(|name| self.rename.family.contains(&name))
+    }
+
+    fn rename_identifier(&mut self, identifier: &LocalIdentifier<'_>) {
+        if self.rename.kind.matches_written(identifier.value, self.rename.name) {
+            self.edits.push(TextEdit::replace(identifier.span, self.rename.new_name));
+        }
+    }
+
+    fn rename_selector(&mut self, selector: &ClassLikeMemberSelector<'_>) {
+        if let ClassLikeMemberSelector::Identifier(identifier) = selector {
+            self.rename_identifier(identifier);
+        }
+    }
+
+    fn is_in_family(&self, name: &Word) -> bool {
+        self.rename.family.contains(&ascii_lowercase_word(name.as_bytes()))
+    }
+
+    /// Returns `true` if the inferred type of `object` is an instance of the member's hierarchy.
+    fn object_is_in_family(&self, object: &Expression<'_>) -> bool {
+        self.artifacts.get_expression_type(object).is_some_and(|object_type| {
+            object_type.types.iter().flat_map(TAtomic::get_all_object_names).any(|name| self.is_in_family(&name))
+        })
+    }
+
+    /// Returns `true` if the class of a static access belongs to the member's hierarchy.
+    fn class_is_in_family(&self, class: &Expression<'_>) -> bool {
+        match class {
+            Expression::Identifier(identifier) => {
+                self.resolved_names.resolve(identifier).is_some_and(|name| self.is_in_family(&word(name)))
+            }
+            Expression::Self_(_) | Expression::Static(_) => {
+                self.current_class_like().is_some_and(|name| self.rename.family.contains(&name))
+            }
+            Expression::Parent(_) => self
+                .current_class_like()
+                .and_then(|name| self.codebase.get_class_like(name.as_bytes()))
+                .and_then(|class_like| class_like.direct_parent_class)
+                .is_some_and(|parent| self.is_in_family(&parent)),
+            _ => self.object_is_in_family(class),
+        }
+    }
+}
+
+/// Walks a file to rename the declarations and accesses of a class-like member.
+struct MemberWalker;
+
+impl<'ast, 'arena, 'ctx> Walker<'ast, 'arena, MemberContext<'ctx, 'arena>> for MemberWalker {
+    fn walk_in_class(&self, class: &'ast Class<'arena>, context: &mut MemberContext<'ctx, 'arena>) {
+        context.enter_class_like(&class.name);
+    }
+
+    fn walk_out_class(&self, _class: &'ast Class<'arena>, context: &mut MemberContext<'ctx, 'arena>) {
+        context.class_likes.pop();
+    }
+
+    fn walk_in_interface(&self, interface: &'ast Interface<'arena>, context: &mut MemberContext<'ctx, 'arena>) {
+        context.enter_class_like(&interface.name);
+    }
+
+    fn walk_out_interface(&self, _interface: &'ast Interface<'arena>, context: &mut MemberContext<'ctx, 'arena>) {
+        context.class_likes.pop();
+    }
+
+    fn walk_in_trait(&self, r#trait: &'ast Trait<'arena>, context: &mut MemberContext<'ctx, 'arena>) {
+        context.enter_class_like(&r#trait.name);
+    }
+
+    fn walk_out_trait(&self, _trait: &'ast Trait<'arena>, context: &mut MemberContext<'ctx, 'arena>) {
+        context.class_likes.pop();
+    }
+
+    fn walk_in_enum(&self, r#enum: &'ast Enum<'arena>, context: &mut MemberContext<'ctx, 'arena>) {
+        context.enter_class_like(&r#enum.name);
+    }
+
+    fn walk_out_enum(&self, _enum: &'ast Enum<'arena>, context: &mut MemberContext<'ctx, 'arena>) {
+        context.class_likes.pop();
+    }
+
+    fn walk_in_anonymous_class(
+        &self,
+        _anonymous_class: &'ast AnonymousClass<'arena>,
+        context: &mut MemberContext<'ctx, 'arena>,
+    ) {
+        context.class_likes.push(None);
+    }
+
+    fn walk_out_anonymous_class(
+        &self,
+        _anonymous_class: &'ast AnonymousClass<'arena>,
+        context: &mut MemberContext<'ctx, 'arena>,
+    ) {
+        context.class_likes.pop();
+    }
+
+    fn walk_in_method(&self, method: &'ast Method<'arena>, context: &mut MemberContext<'ctx, 'arena>) {
+        if context.declares(MemberKind::Method) {
+            context.rename_identifier(&method.name);
+        }
+    }
+
+    fn walk_in_property_item(
+        &self,
+        property_item: &'ast PropertyItem<'arena>,
+        context: &mut MemberContext<'ctx, 'arena>,
+    ) {
+        if !context.declares(MemberKind::Property) {
+            return;
+        }
+
+        let variable = property_item.variable();
+        if variable.name.strip_prefix(b"$") == Some(context.rename.name.as_bytes()) {
+            context.edits.push(TextEdit::replace(variable.span, format!("${}", context.rename.new_name)));
+        }
+    }
+
+    fn walk_in_class_like_constant_item(
+        &self,
+        class_like_constant_item: &'ast ClassLikeConstantItem<'arena>,
+        context: &mut MemberContext<'ctx, 'arena>,
+    ) {
+        if context.declares(MemberKind::Constant) {
+            context.rename_identifier(&class_like_constant_item.name);
+        }
+    }
+
+    fn walk_in_enum_case_item(
+        &self,
+        enum_case_item: &'ast EnumCaseItem<'arena>,
+        context: &mut MemberContext<'ctx, 'arena>,
+    ) {
+        if context.declares(MemberKind::Constant) {
+            context.rename_identifier(enum_case_item.name());
+        }
+    }
+
+    fn walk_in_method_call(&self, method_call: &'ast MethodCall<'arena>, context: &mut MemberContext<'ctx, 'arena>) {
+        if context.rename.kind == MemberKind::Method && context.object_is_in_family(method_call.object) {
+            context.rename_selector(&method_call.method);
+        }
+    }
+
+    fn walk_in_null_safe_method_call(
+        &self,
+        null_safe_method_call: &'ast NullSafeMethodCall<'arena>,
+        context: &mut MemberContext<'ctx, 'arena>,
+    ) {
+        if context.rename.kind == MemberKind::Method && context.object_is_in_family(null_safe_method_call.object) {
+            context.rename_selector(&null_safe_method_call.method);
+        }
+    }
+
+    fn walk_in_method_partial_application(
+        &self,
+        method_partial_application: &'ast MethodPartialApplication<'arena>,
+        context: &mut MemberContext<'ctx, 'arena>,
+    ) {
+        if context.rename.kind == MemberKind::Method && context.object_is_in_family(method_partial_application.object) {
+            context.rename_selector(&method_partial_application.method);
+        }
+    }
+
+    fn walk_in_static_method_call(
+        &self,
+        static_method_call: &'ast StaticMethodCall<'arena>,
+        context: &mut MemberContext<'ctx, 'arena>,
+    ) {
+        if context.rename.kind == MemberKind::Method && context.class_is_in_family(static_method_call.class) {
+            context.rename_selector(&static_method_call.method);
+        }
+    }
+
+    fn walk_in_static_method_partial_application(
+        &self,
+        static_method_partial_application: &'ast StaticMethodPartialApplication<'arena>,
+        context: &mut MemberContext<'ctx, 'arena>,
+    ) {
+        if context.rename.kind == MemberKind::Method
+            && context.class_is_in_family(static_method_partial_application.class)
+        {
+            context.rename_selector(&static_method_partial_application.method);
+        }
+    }
+
+    fn walk_in_property_access(
+        &self,
+        property_access: &'ast PropertyAccess<'arena>,
+        context: &mut MemberContext<'ctx, 'arena>,
+    ) {
+        if context.rename.kind == MemberKind::Property && context.object_is_in_family(property_access.object) {
+            context.rename_selector(&property_access.property);
+        }
+    }
+
+    fn walk_in_null_safe_property_access(
+        &self,
+        null_safe_property_access: &'ast NullSafePropertyAccess<'arena>,
+        context: &mut MemberContext<'ctx, 'arena>,
+    ) {
+        if context.rename.kind == MemberKind::Property && context.object_is_in_family(null_safe_property_access.object)
+        {
+            context.rename_selector(&null_safe_property_access.property);
+        }
+    }
+
+    fn walk_in_static_property_access(
+        &self,
+        static_property_access: &'ast StaticPropertyAccess<'arena>,
+        context: &mut MemberContext<'ctx, 'arena>,
+    ) {
+        if context.rename.kind != MemberKind::Property || !context.class_is_in_family(static_property_access.class) {
+            return;
+        }
+
+        if let Variable::Direct(variable) = &static_property_access.property
+            && variable.name.strip_prefix(b"$") == Some(context.rename.name.as_bytes())
+        {
+            context.edits.push(TextEdit::replace(variable.span, format!("${}", context.rename.new_name)));
+        }
+    }
+
+    fn walk_in_class_constant_access(
+        &self,
+        class_constant_access: &'ast ClassConstantAccess<'arena>,
+        context: &mut MemberContext<'ctx, 'arena>,
+    ) {
+        if context.rename.kind == MemberKind::Constant
+            && context.class_is_in_family(class_constant_access.class)
+            && let ClassLikeConstantSelector::Identifier(identifier) = &class_constant_access.constant
+        {
+            context.rename_identifier(identifier);
+        }
+    }
+}
+
+/// The string literals found inside attribute arguments.
+#[derive(Default)]
+struct AttributeStrings<'ast, 'arena> {
+    depth: usize,
+    strings: Vec<&'ast LiteralString<'arena>>,
+}
+
+/// Walks a file to collect the string literals passed to attributes.
+struct AttributeStringWalker;
+
+impl<'ast, 'arena> Walker<'ast, 'arena, AttributeStrings<'ast, 'arena>> for AttributeStringWalker {
+    fn walk_in_attribute(&self, _attribute: &'ast Attribute<'arena>, context: &mut AttributeStrings<'ast, 'arena>) {
+        context.depth += 1;
+    }
+
+    fn walk_out_attribute(&self, _attribute: &'ast Attribute<'arena>, context: &mut AttributeStrings<'ast, 'arena>) {
+        context.depth -= 1;
+    }
+
+    fn walk_in_literal_string(
+        &self,
+        literal_string: &'ast LiteralString<'arena>,
+        context: &mut AttributeStrings<'ast, 'arena>,
+    ) {
+        if context.depth > 0 {
+            context.strings.push(literal_string);
+        }
+    }
+}
+
+/// Returns the names written on the tag lines of a docblock, with their offsets.
+///
+/// Variables, tag names, and member names (after `::` or `->`) are not names.
+fn docblock_names(docblock: &[u8]) -> Vec<(usize, &[u8])> {
+    let mut names = Vec::new();
+    let mut line_start = 0;
+
+    for line in docblock.split(|byte| *byte == b'\n') {
+        if line.contains(&b'@') {
+            let mut index = 0;
+            while index < line.len() {
+                let starts_name = is_name_start(line[index])
+                    || (line[index] == b'\\' && line.get(index + 1).copied().is_some_and(is_name_start));
+                let preceded = index > 0 && (is_name_part(line[index - 1]) || b"$@:>-\\".contains(&line[index - 1]));
+
+                if !starts_name || preceded {
+                    index += 1;
+                    continue;
+                }
+
+                let length = line[index..].iter().position(|byte| !is_name_part(*byte) && *byte != b'\\');
+                let end = length.map_or(line.len(), |length| index + length);
+                names.push((line_start + index, line[index..end].strip_suffix(b"\\").unwrap_or(&line[index..end])));
+                index = end;
+            }
+        }
+
+        line_start += line.len() + 1;
+    }
+
+    names
+}
+
+fn skipped_file_issue(file: &File, span: Span) -> Issue {
+    Issue::warning(format!("Skipped `{}` because it contains syntax errors.", String::from_utf8_lossy(&file.name)))
+        .with_annotation(Annotation::primary(span).with_message("Syntax error found here."))
+        .with_help("Fix the syntax errors and run the refactoring again.")
+}
+
+fn is_name_start(byte: u8) -> bool {
+    byte.is_ascii_alphabetic() || byte == b'_' || byte >= 0x80
+}
+
+fn is_name_part(byte: u8) -> bool {
+    is_name_start(byte) || byte.is_ascii_digit()
+}
+
+fn is_valid_name(name: &str) -> bool {
+    name.split('\\')
+        .all(|segment| segment.bytes().next().is_some_and(is_name_start) && segment.bytes().all(is_name_part))
+}
+
+/// Splits a fully qualified name into its namespace and short name.
+fn split_name(name: &str) -> (&str, &str) {
+    name.rsplit_once('\\').unwrap_or(("", name))
+}
+
+fn join_name(namespace: &str, short_name: &str) -> String {
+    if namespace.is_empty() { short_name.to_string() } else { format!("{namespace}\\{short_name}") }
+}
+
+fn starts_with_ignore_ascii_case(haystack: &str, prefix: &str) -> bool {
+    haystack.len() >= prefix.len() && haystack.as_bytes()[..prefix.len()].eq_ignore_ascii_case(prefix.as_bytes())
+}
+
+fn contains_ignore_ascii_case(haystack: &[u8], needle: &[u8]) -> bool {
+    !needle.is_empty() && haystack.windows(needle.len()).any(|window| window.eq_ignore_ascii_case(needle))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    use std::borrow::Cow;
+    use std::path::Path;
+    use std::sync::Arc;
+    use std::sync::LazyLock;
+
+    use mago_analyzer::plugin::PluginRegistry;
+    use mago_analyzer::settings::Settings;
+    use mago_codex::reference::SymbolReferences;
+    use mago_database::Database;
+    use mago_database::DatabaseConfiguration;
+    use mago_text_edit::TextEditor;
+
+    static PLUGIN_REGISTRY: LazyLock<Arc<PluginRegistry>> =
+        LazyLock::new(|| Arc::new(PluginRegistry::with_library_providers()));
+
+    fn make_database(files: &[(&str, &str)]) -> Database<'static> {
+        let config = DatabaseConfiguration {
+            workspace: Cow::Owned(Path::new("/test").to_path_buf()),
+            paths: vec![Cow::Borrowed(b"src")],
+            includes: vec![],
+            patches: vec![],
+            excludes: vec![],
+            extensions: vec![Cow::Borrowed(b"php")],
+            glob: mago_database::GlobSettings::default(),
+        };
+
+        let mut db = Database::new(config);
+        for (name, contents) in files {
+            db.add(File::new(
+                Cow::Owned(name.as_bytes().to_vec()),
+                FileType::Host,
+                None,
+                Cow::Owned(contents.as_bytes().to_vec()),
+            ));
+        }
+
+        db
+    }
+
+    /// Plans the refactoring and returns the contents of every file after applying it.
+    fn refactor(files: &[(&str, &str)], refactoring: &Refactoring) -> HashMap<String, String> {
+        let db = make_database(files);
+        let analysis = IncrementalAnalysisService::new(
+            db.read_only(),
+            CodebaseMetadata::new(),
+            SymbolReferences::new(),
+            Settings::default(),
+            ParserSettings::default(),
+            Arc::clone(&PLUGIN_REGISTRY),
+        );
+
+        let mut service = RefactorService::new(analysis, ParserSettings::default());
+        let mut plan = service.plan(refactoring).expect("refactoring should be planned");
+        assert!(plan.skipped.is_empty(), "unexpected skipped occurrences: {:?}", plan.skipped);
+
+        let database = db.read_only();
+        files
+            .iter()
+            .map(|(name, contents)| {
+                let file_id = database.get_id(name.as_bytes()).unwrap();
+                let mut editor = TextEditor::new(contents.as_bytes());
+                editor.apply_batch(plan.edits.remove(&file_id).unwrap_or_default(), None::<fn(&[u8]) -> bool>);
+
+                ((*name).to_string(), String::from_utf8(editor.finish()).unwrap())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_rename_class_updates_imports_docblocks_and_class_constants() {
+        let result = refactor(
+            &[
+                ("src/Foo.php", "<?php\n\nnamespace App;\n\nclass Foo\n{\n}\n"),
+                (
+                    "src/make.php",
+                    "<?php\n\nnamespace App\\Other;\n\nuse App\\Foo;\n\n/**\n * @param Foo $foo\n */\nfunction make(Foo $foo): string\n{\n    return Foo::class;\n}\n",
+                ),
+            ],
+            &Refactoring::Rename { symbol: "App\\Foo".to_string(), new_name: "Baz".to_string() },
+        );
+
+        assert_eq!(result["src/Foo.php"], "<?php\n\nnamespace App;\n\nclass Baz\n{\n}\n");
+        assert_eq!(
+            result["src/make.php"],
+            "<?php\n\nnamespace App\\Other;\n\nuse App\\Baz;\n\n/**\n * @param Baz $foo\n */\nfunction make(Baz $foo): string\n{\n    return Baz::class;\n}\n",
+        );
+    }
+
+    #[test]
+    fn test_move_class_imports_names_that_no_longer_resolve() {
+        let result = refactor(
+            &[
+                (
+                    "src/Foo.php",
+                    "<?php\n\nnamespace App;\n\nfinal class Foo\n{\n    public function bar(): Bar\n    {\n        return new Bar();\n    }\n}\n",
+                ),
+                (
+                    "src/Bar.php",
+                    "<?php\n\nnamespace App;\n\nfinal class Bar\n{\n    public function foo(): Foo\n    {\n        return new Foo();\n    }\n}\n",
+                ),
+            ],
+            &Refactoring::Move { class_like: "App\\Foo".to_string(), namespace: "App\\Models".to_string() },
+        );
+
+        assert_eq!(
+            result["src/Foo.php"],
+            "<?php\n\nnamespace App\\Models;\n\nuse App\\Bar;\n\nfinal class Foo\n{\n    public function bar(): Bar\n    {\n        return new Bar();\n    }\n}\n",
+        );
+        assert_eq!(
+            result["src/Bar.php"],
+            "<?php\n\nnamespace App;\n\nuse App\\Models\\Foo;\n\nfinal class Bar\n{\n    public function foo(): Foo\n    {\n        return new Foo();\n    }\n}\n",
+        );
+    }
+
+    #[test]
+    fn test_rename_method_across_hierarchy_and_call_sites() {
+        let result = refactor(
+            &[
+                (
+                    "src/Foo.php",
+                    "<?php\n\nnamespace App;\n\nclass Foo\n{\n    public function bar(): int\n    {\n        return 1;\n    }\n}\n",
+                ),
+                (
+                    "src/Child.php",
+                    "<?php\n\nnamespace App;\n\nfinal class Child extends Foo\n{\n    public function bar(): int\n    {\n        return parent::bar() + 1;\n    }\n}\n",
+                ),
+                (
+                    "src/run.php",
+                    "<?php\n\nnamespace App;\n\nfunction run(Foo $foo, Child $child): int\n{\n    return $foo->bar() + $child->bar();\n}\n",
+                ),
+            ],
+            &Refactoring::Rename { symbol: "App\\Foo::bar".to_string(), new_name: "baz".to_string() },
+        );
+
+        assert!(result["src/Foo.php"].contains("public function baz(): int"));
+        assert!(result["src/Child.php"].contains("public function baz(): int"));
+        assert!(result["src/Child.php"].contains("return parent::baz() + 1;"));
+        assert!(result["src/run.php"].contains("return $foo->baz() + $child->baz();"));
+    }
+
+    #[test]
+    fn test_rename_to_existing_class_is_rejected() {
+        let db = make_database(&[
+            ("src/Foo.php", "<?php\n\nnamespace App;\n\nclass Foo\n{\n}\n"),
+            ("src/Bar.php", "<?php\n\nnamespace App;\n\nclass Bar\n{\n}\n"),
+        ]);
+
+        let analysis = IncrementalAnalysisService::new(
+            db.read_only(),
+            CodebaseMetadata::new(),
+            SymbolReferences::new(),
+            Settings::default(),
+            ParserSettings::default(),
+            Arc::clone(&PLUGIN_REGISTRY),
+        );
+
+        let mut service = RefactorService::new(analysis, ParserSettings::default());
+        let result = service.plan(&Refactoring::Rename { symbol: "App\\Foo".to_string(), new_name: "Bar".to_string() });
+
+        assert!(matches!(result, Err(OrchestratorError::General(_))));
+    }
+}
@@ -52,6 +52,12 @@ pub struct ReadonlyTagValue<'arena> {
     pub description: Text<'arena>,
 }
 
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ImmutableTagValue<'arena> {
+    pub description: Text<'arena>,
+}
+
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct MustUseTagValue<'arena> {
@@ -222,6 +228,12 @@ impl HasSpan for ReadonlyTagValue<'_> {
     }
 }
 
+impl HasSpan for ImmutableTagValue<'_> {
+    fn span(&self) -> Span {
+        self.description.span()
+    }
+}
+
 impl HasSpan for MustUseTagValue<'_> {
     fn span(&self) -> Span {
         self.description.span()
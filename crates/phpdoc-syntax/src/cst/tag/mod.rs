@@ -9,6 +9,7 @@ pub use crate::cst::tag::meta::*;
 pub use crate::cst::tag::method::*;
 pub use crate::cst::tag::param::*;
 pub use crate::cst::tag::property::*;
+pub use crate::cst::tag::taint::*;
 pub use crate::cst::tag::template::*;
 pub use crate::cst::tag::type_alias::*;
 pub use crate::cst::tag::value::*;
@@ -21,6 +22,7 @@ pub mod meta;
 pub mod method;
 pub mod param;
 pub mod property;
+pub mod taint;
 pub mod template;
 pub mod type_alias;
 pub mod value;
@@ -82,6 +84,7 @@ pub enum TagValue<'arena> {
     Pure(PureTagValue<'arena>),
     Impure(ImpureTagValue<'arena>),
     Readonly(ReadonlyTagValue<'arena>),
+    Immutable(ImmutableTagValue<'arena>),
     MustUse(MustUseTagValue<'arena>),
     NoNamedArguments(NoNamedArgumentsTagValue<'arena>),
     NotDeprecated(NotDeprecatedTagValue<'arena>),
@@ -99,6 +102,10 @@ pub enum TagValue<'arena> {
     IgnoreFalsableReturn(IgnoreFalsableReturnTagValue<'arena>),
     InheritDoc(InheritDocTagValue<'arena>),
     Trace(TraceTagValue<'arena>),
+    TaintSource(TaintTagValue<'arena>),
+    TaintSink(TaintSinkTagValue<'arena>),
+    TaintEscape(TaintTagValue<'arena>),
+    TaintUnescape(TaintTagValue<'arena>),
     TypeAlias(TypeAliasTagValue<'arena>),
     TypeAliasImport(TypeAliasImportTagValue<'arena>),
     PureUnlessCallableIsImpure(PureUnlessCallableIsImpureTagValue<'arena>),
@@ -145,6 +152,7 @@ impl HasSpan for TagValue<'_> {
             TagValue::Pure(value) => value.span(),
             TagValue::Impure(value) => value.span(),
             TagValue::Readonly(value) => value.span(),
+            TagValue::Immutable(value) => value.span(),
             TagValue::MustUse(value) => value.span(),
             TagValue::NoNamedArguments(value) => value.span(),
             TagValue::NotDeprecated(value) => value.span(),
@@ -162,6 +170,10 @@ impl HasSpan for TagValue<'_> {
             TagValue::IgnoreFalsableReturn(value) => value.span(),
             TagValue::InheritDoc(value) => value.span(),
             TagValue::Trace(value) => value.span(),
+            TagValue::TaintSource(value) => value.span(),
+            TagValue::TaintSink(value) => value.span(),
+            TagValue::TaintEscape(value) => value.span(),
+            TagValue::TaintUnescape(value) => value.span(),
             TagValue::TypeAlias(value) => value.span(),
             TagValue::TypeAliasImport(value) => value.span(),
             TagValue::PureUnlessCallableIsImpure(value) => value.span(),
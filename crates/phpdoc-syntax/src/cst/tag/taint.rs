@@ -0,0 +1,39 @@
+use mago_span::HasSpan;
+use mago_span::Span;
+
+use crate::cst::identifier::Identifier;
+use crate::cst::text::Text;
+use crate::cst::variable::Variable;
+
+/// The value of a `@taint-source`, `@taint-escape` or `@taint-unescape` tag, e.g. `@psalm-taint-source input`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TaintTagValue<'arena> {
+    pub kind: Identifier<'arena>,
+    pub description: Option<Text<'arena>>,
+}
+
+/// The value of a `@taint-sink` tag, e.g. `@psalm-taint-sink sql $query`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TaintSinkTagValue<'arena> {
+    pub kind: Identifier<'arena>,
+    pub parameter: Variable<'arena>,
+    pub description: Option<Text<'arena>>,
+}
+
+impl HasSpan for TaintTagValue<'_> {
+    fn span(&self) -> Span {
+        let end = self.description.as_ref().map_or_else(|| self.kind.span(), HasSpan::span);
+
+        self.kind.span().join(end)
+    }
+}
+
+impl HasSpan for TaintSinkTagValue<'_> {
+    fn span(&self) -> Span {
+        let end = self.description.as_ref().map_or_else(|| self.parameter.span(), HasSpan::span);
+
+        self.kind.span().join(end)
+    }
+}
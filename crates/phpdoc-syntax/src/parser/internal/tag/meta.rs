@@ -11,6 +11,7 @@ use crate::cst::tag::FinalTagValue;
 use crate::cst::tag::GenericTagValue;
 use crate::cst::tag::IgnoreFalsableReturnTagValue;
 use crate::cst::tag::IgnoreNullableReturnTagValue;
+use crate::cst::tag::ImmutableTagValue;
 use crate::cst::tag::ImpureTagValue;
 use crate::cst::tag::InheritDocTagValue;
 use crate::cst::tag::InternalTagValue;
@@ -58,6 +59,7 @@ where
         parse_pure_tag_value => Pure(PureTagValue),
         parse_impure_tag_value => Impure(ImpureTagValue),
         parse_readonly_tag_value => Readonly(ReadonlyTagValue),
+        parse_immutable_tag_value => Immutable(ImmutableTagValue),
         parse_must_use_tag_value => MustUse(MustUseTagValue),
         parse_no_named_arguments_tag_value => NoNamedArguments(NoNamedArgumentsTagValue),
         parse_enum_interface_tag_value => EnumInterface(EnumInterfaceTagValue),
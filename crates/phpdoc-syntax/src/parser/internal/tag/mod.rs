@@ -17,6 +17,7 @@ pub(crate) mod meta;
 pub(crate) mod method;
 pub(crate) mod param;
 pub(crate) mod property;
+pub(crate) mod taint;
 pub(crate) mod template;
 pub(crate) mod type_alias;
 pub(crate) mod value;
@@ -115,6 +116,7 @@ where
             b"pure" => self.parse_pure_tag_value(),
             b"impure" => self.parse_impure_tag_value(),
             b"readonly" => self.parse_readonly_tag_value(),
+            b"immutable" => self.parse_immutable_tag_value(),
             b"mustuse" => self.parse_must_use_tag_value(),
             b"nonamedarguments" => self.parse_no_named_arguments_tag_value(),
             b"enuminterface" => self.parse_enum_interface_tag_value(),
@@ -130,6 +132,10 @@ where
             b"ignorenullablereturn" => self.parse_ignore_nullable_return_tag_value(),
             b"ignorefalsablereturn" => self.parse_ignore_falsable_return_tag_value(),
             b"trace" => self.parse_trace_tag_value(),
+            b"taintsource" => self.parse_taint_source_tag_value(),
+            b"taintsink" => self.parse_taint_sink_tag_value(),
+            b"taintescape" => self.parse_taint_escape_tag_value(),
+            b"taintunescape" => self.parse_taint_unescape_tag_value(),
             _ => self.parse_generic_tag_value(),
         }
     }
@@ -0,0 +1,39 @@
+use mago_allocator::Arena;
+
+use crate::cst::tag::TagValue;
+use crate::cst::tag::TaintSinkTagValue;
+use crate::cst::tag::TaintTagValue;
+use crate::error::ParseError;
+use crate::parser::PHPDocParser;
+
+impl<'arena, A> PHPDocParser<'arena, A>
+where
+    A: Arena,
+{
+    pub(crate) fn parse_taint_source_tag_value(&mut self) -> Result<TagValue<'arena>, ParseError> {
+        Ok(TagValue::TaintSource(self.parse_taint_tag_value()?))
+    }
+
+    pub(crate) fn parse_taint_escape_tag_value(&mut self) -> Result<TagValue<'arena>, ParseError> {
+        Ok(TagValue::TaintEscape(self.parse_taint_tag_value()?))
+    }
+
+    pub(crate) fn parse_taint_unescape_tag_value(&mut self) -> Result<TagValue<'arena>, ParseError> {
+        Ok(TagValue::TaintUnescape(self.parse_taint_tag_value()?))
+    }
+
+    pub(crate) fn parse_taint_sink_tag_value(&mut self) -> Result<TagValue<'arena>, ParseError> {
+        let kind = self.parse_identifier()?;
+        let parameter = self.parse_variable()?;
+        let description = self.parse_optional_description(false)?;
+
+        Ok(TagValue::TaintSink(TaintSinkTagValue { kind, parameter, description }))
+    }
+
+    fn parse_taint_tag_value(&mut self) -> Result<TaintTagValue<'arena>, ParseError> {
+        let kind = self.parse_identifier()?;
+        let description = self.parse_optional_description(false)?;
+
+        Ok(TaintTagValue { kind, description })
+    }
+}
@@ -610,3 +610,43 @@ fn backtick_after_whitespace_still_opens_inline_code() {
         text.segments
     );
 }
+
+#[test]
+fn parses_taint_source_and_escape() {
+    let arena = LocalArena::new();
+    let document = parse(&arena, b"/** @psalm-taint-source input */");
+    let tag = first_tag(&document);
+
+    assert_eq!(tag.vendor, Some(TagVendor::Psalm));
+    let TagValue::TaintSource(source) = &tag.value else { panic!() };
+    assert_eq!(source.kind.value, b"input");
+    assert!(source.description.is_none());
+
+    let document = parse(&arena, b"/** @psalm-taint-escape html escapes markup */");
+    let tag = first_tag(&document);
+
+    let TagValue::TaintEscape(escape) = &tag.value else { panic!() };
+    assert_eq!(escape.kind.value, b"html");
+    let Some(description) = &escape.description else { panic!("expected a description") };
+    assert_eq!(plain(description), b"escapes markup");
+}
+
+#[test]
+fn parses_taint_sink_with_parameter() {
+    let arena = LocalArena::new();
+    let document = parse(&arena, b"/** @psalm-taint-sink sql $query */");
+    let tag = first_tag(&document);
+
+    let TagValue::TaintSink(sink) = &tag.value else { panic!() };
+    assert_eq!(sink.kind.value, b"sql");
+    assert_eq!(sink.parameter.value, b"$query");
+}
+
+#[test]
+fn recovers_taint_sink_without_parameter() {
+    let arena = LocalArena::new();
+    let document = parse(&arena, b"/** @psalm-taint-sink html */");
+    let tag = first_tag(&document);
+
+    assert!(matches!(tag.value, TagValue::Invalid(_)));
+}
@@ -6,6 +6,7 @@ use crate::IssueCollection;
 use crate::Level;
 use crate::color::ColorChoice;
 use crate::error::ReportingError;
+use crate::rule::RuleDescriptor;
 
 pub mod ariadne;
 pub mod checkstyle;
@@ -40,6 +41,8 @@ pub struct FormatterConfig {
     /// Supported placeholders: `%file%` (absolute path), `%line%`, `%column%`.
     /// Example: `"phpstorm://open?file=%file%&line=%line%"`
     pub editor_url: Option<String>,
+    /// Metadata for the rules behind the reported issue codes.
+    pub rules: Vec<RuleDescriptor>,
 }
 
 /// Trait for formatting issues to a writer.
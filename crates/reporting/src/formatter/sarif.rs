@@ -1,20 +1,36 @@
+use std::collections::BTreeMap;
 use std::io::Write;
 
+use foldhash::HashMap;
+use foldhash::HashMapExt;
+use serde_sarif::sarif::ArtifactChange;
+use serde_sarif::sarif::ArtifactContent;
 use serde_sarif::sarif::ArtifactLocation;
+use serde_sarif::sarif::CodeFlow;
+use serde_sarif::sarif::Fix;
 use serde_sarif::sarif::Location;
 use serde_sarif::sarif::Message;
+use serde_sarif::sarif::MultiformatMessageString;
 use serde_sarif::sarif::PhysicalLocation;
 use serde_sarif::sarif::Region;
+use serde_sarif::sarif::Replacement;
+use serde_sarif::sarif::ReportingDescriptor;
 use serde_sarif::sarif::Result as SarifResult;
 use serde_sarif::sarif::ResultLevel;
 use serde_sarif::sarif::Run;
 use serde_sarif::sarif::Sarif;
+use serde_sarif::sarif::ThreadFlow;
+use serde_sarif::sarif::ThreadFlowLocation;
 use serde_sarif::sarif::Tool;
 use serde_sarif::sarif::ToolComponent;
 
 use mago_database::DatabaseReader;
 use mago_database::ReadDatabase;
+use mago_database::file::File;
+use mago_database::file::FileId;
 use mago_database::file::HasFileId;
+use mago_span::Span;
+use mago_text_edit::TextEdit;
 use serde_sarif::sarif::Version;
 
 use crate::Annotation;
@@ -24,11 +40,26 @@ use crate::Level;
 use crate::error::ReportingError;
 use crate::formatter::Formatter;
 use crate::formatter::FormatterConfig;
+use crate::rule::RuleDescriptor;
+
+/// The key of the fingerprint mago contributes to `partialFingerprints`.
+///
+/// The version suffix must change whenever the fingerprint computation changes, so that
+/// consumers do not match alerts across incompatible fingerprints.
+const FINGERPRINT_KEY: &str = "magoIssueHash/v1";
+
+/// The code prefix of taint analysis issues, whose annotations trace a source-to-sink flow.
+const TAINT_CODE_PREFIX: &str = "tainted-";
 
 /// Formatter that outputs issues in SARIF (Static Analysis Results Interchange Format) 2.1.0.
 ///
 /// SARIF is an OASIS standard for representing static analysis tool output, enabling
 /// integration with GitHub Code Scanning, GitLab Code Quality, and other CI/CD platforms.
+///
+/// Besides the results themselves, the log describes every reported rule in
+/// `tool.driver.rules`, attaches fixes built from the issue edits, lists secondary
+/// annotations as related locations, and gives each result a partial fingerprint that does
+/// not depend on line numbers, so alerts stay matched when surrounding code moves.
 pub(crate) struct SarifFormatter;
 
 impl Formatter for SarifFormatter {
@@ -51,29 +82,72 @@ fn build_sarif_log(
     config: &FormatterConfig,
     database: &ReadDatabase,
 ) -> Result<Sarif, ReportingError> {
-    let tool = Tool::builder()
-        .driver(
-            ToolComponent::builder()
-                .name("mago")
-                .version(env!("CARGO_PKG_VERSION"))
-                .information_uri("https://github.com/carthage-software/mago")
-                .build(),
-        )
-        .build();
+    let descriptors: HashMap<&str, &RuleDescriptor> =
+        config.rules.iter().map(|rule| (rule.code.as_str(), rule)).collect();
 
+    let mut rules = Vec::new();
+    let mut rule_indices: HashMap<String, i64> = HashMap::new();
+    let mut fingerprints = FingerprintCounter::default();
     let mut results = Vec::new();
     for issue in crate::formatter::utils::filter_issues(issues, config, false) {
-        let sarif_result = convert_issue_to_result(issue, database)?;
+        let rule_index = issue.code.as_ref().map(|code| {
+            *rule_indices.entry(code.clone()).or_insert_with(|| {
+                rules.push(rule_to_descriptor(code, descriptors.get(code.as_str()).copied()));
+
+                i64::try_from(rules.len() - 1).unwrap_or(i64::MAX)
+            })
+        });
+
+        let mut sarif_result = convert_issue_to_result(issue, database, &mut fingerprints)?;
+        sarif_result.rule_index = rule_index;
         results.push(sarif_result);
     }
 
+    let mut driver = ToolComponent::builder()
+        .name("mago")
+        .version(env!("CARGO_PKG_VERSION"))
+        .information_uri("https://github.com/carthage-software/mago")
+        .build();
+    if !rules.is_empty() {
+        driver.rules = Some(rules);
+    }
+
+    let tool = Tool::builder().driver(driver).build();
+
     Ok(Sarif::builder()
         .version(Version::V2_1_0.to_string())
         .runs(vec![Run::builder().tool(tool).results(results).build()])
         .build())
 }
 
-fn convert_issue_to_result(issue: &Issue, database: &ReadDatabase) -> Result<SarifResult, ReportingError> {
+fn rule_to_descriptor(code: &str, rule: Option<&RuleDescriptor>) -> ReportingDescriptor {
+    let mut descriptor = ReportingDescriptor::builder().id(code).build();
+    let Some(rule) = rule else {
+        return descriptor;
+    };
+
+    descriptor.name = Some(rule.name.clone());
+    descriptor.help_uri.clone_from(&rule.help_uri);
+    if !rule.description.trim().is_empty() {
+        let short_description = rule.short_description();
+
+        descriptor.short_description = Some(MultiformatMessageString::builder().text(short_description).build());
+        descriptor.full_description = Some(
+            MultiformatMessageString::builder()
+                .text(rule.description.trim().to_string())
+                .markdown(rule.description.trim().to_string())
+                .build(),
+        );
+    }
+
+    descriptor
+}
+
+fn convert_issue_to_result(
+    issue: &Issue,
+    database: &ReadDatabase,
+    fingerprints: &mut FingerprintCounter,
+) -> Result<SarifResult, ReportingError> {
     let level = level_to_sarif(issue.level);
 
     let message = if !issue.notes.is_empty() || issue.help.is_some() {
@@ -99,52 +173,116 @@ fn convert_issue_to_result(issue: &Issue, database: &ReadDatabase) -> Result<Sar
     };
 
     let mut locations = Vec::new();
+    let mut related_locations = Vec::new();
     for annotation in &issue.annotations {
+        let location = annotation_to_location(annotation, database)?;
         if annotation.is_primary() {
-            let location = annotation_to_location(annotation, database)?;
-            locations.insert(0, location);
-        } else {
-            let location = annotation_to_location(annotation, database)?;
             locations.push(location);
+        } else {
+            related_locations.push(location);
         }
     }
 
-    let result = match (&issue.code, locations.is_empty()) {
-        (Some(code), false) => {
-            SarifResult::builder().message(message).level(level).rule_id(code.clone()).locations(locations).build()
+    // Issues without a primary annotation are still anchored to their first annotation.
+    if locations.is_empty() && !related_locations.is_empty() {
+        locations.push(related_locations.remove(0));
+    }
+
+    let mut result = SarifResult::builder().message(message).level(level).build();
+    result.rule_id.clone_from(&issue.code);
+    result.partial_fingerprints = fingerprint(issue, database, fingerprints)
+        .map(|fingerprint| BTreeMap::from([(FINGERPRINT_KEY.to_string(), fingerprint)]));
+
+    if !locations.is_empty() {
+        result.locations = Some(locations);
+    }
+
+    if !related_locations.is_empty() {
+        for (id, location) in related_locations.iter_mut().enumerate() {
+            location.id = i64::try_from(id).ok();
         }
-        (Some(code), true) => SarifResult::builder().message(message).level(level).rule_id(code.clone()).build(),
-        (None, false) => SarifResult::builder().message(message).level(level).locations(locations).build(),
-        (None, true) => SarifResult::builder().message(message).level(level).build(),
-    };
+
+        result.related_locations = Some(related_locations);
+    }
+
+    if let Some(code_flow) = taint_code_flow(issue, database)? {
+        result.code_flows = Some(vec![code_flow]);
+    }
+
+    if !issue.edits.is_empty() {
+        result.fixes = Some(vec![edits_to_fix(issue, database)?]);
+    }
 
     Ok(result)
 }
 
-fn annotation_to_location(annotation: &Annotation, database: &ReadDatabase) -> Result<Location, ReportingError> {
-    let file = database.get(&annotation.span.file_id())?;
-
-    let start_line = file.line_number(annotation.span.start.offset);
-    let start_column = file.column_number(annotation.span.start.offset);
-    let end_line = file.line_number(annotation.span.end.offset);
-    let end_column = file.column_number(annotation.span.end.offset);
+/// Builds the source-to-sink code flow of a taint issue.
+///
+/// Taint issues lead with the tainted value that reaches the sink, followed by
+/// the steps from the source onwards and, last, the sink itself; the flow
+/// places that value right before the sink.
+fn taint_code_flow(issue: &Issue, database: &ReadDatabase) -> Result<Option<CodeFlow>, ReportingError> {
+    if !issue.code.as_deref().is_some_and(|code| code.starts_with(TAINT_CODE_PREFIX)) {
+        return Ok(None);
+    }
 
-    let uri = if let Some(path) = &file.path {
-        path.to_string_lossy().to_string()
-    } else {
-        String::from_utf8_lossy(&file.name).into_owned()
+    let mut annotations = issue.annotations.iter().filter(|annotation| !annotation.is_primary()).collect::<Vec<_>>();
+    let Some(sink) = annotations.pop() else {
+        return Ok(None);
     };
 
-    let artifact_location = ArtifactLocation::builder().uri(uri).build();
+    annotations.extend(issue.annotations.iter().filter(|annotation| annotation.is_primary()));
+    annotations.push(sink);
 
-    let region = Region::builder()
-        .start_line(i64::from(start_line) + 1)
-        .start_column(i64::from(start_column) + 1)
-        .end_line(i64::from(end_line) + 1)
-        .end_column(i64::from(end_column) + 1)
-        .build();
+    let mut locations = Vec::with_capacity(annotations.len());
+    for annotation in annotations {
+        let location = annotation_to_location(annotation, database)?;
+        locations.push(ThreadFlowLocation::builder().location(location).build());
+    }
+
+    Ok(Some(CodeFlow::builder().thread_flows(vec![ThreadFlow::builder().locations(locations).build()]).build()))
+}
+
+fn edits_to_fix(issue: &Issue, database: &ReadDatabase) -> Result<Fix, ReportingError> {
+    let mut edits = issue.edits.iter().collect::<Vec<_>>();
+    edits.sort_by_key(|(file_id, _)| **file_id);
+
+    let mut artifact_changes = Vec::with_capacity(edits.len());
+    for (file_id, file_edits) in edits {
+        let file = database.get(file_id)?;
+
+        let mut file_edits = file_edits.iter().collect::<Vec<_>>();
+        file_edits.sort_by_key(|edit| (edit.range.start, edit.range.end));
+
+        let replacements = file_edits.into_iter().map(|edit| edit_to_replacement(&file, edit)).collect::<Vec<_>>();
+
+        artifact_changes.push(
+            ArtifactChange::builder().artifact_location(artifact_location(&file)).replacements(replacements).build(),
+        );
+    }
 
-    let physical_location = PhysicalLocation::builder().artifact_location(artifact_location).region(region).build();
+    let description = issue.help.clone().unwrap_or_else(|| issue.message.clone());
+
+    Ok(Fix::builder()
+        .description(Message::builder().text(description).build())
+        .artifact_changes(artifact_changes)
+        .build())
+}
+
+fn edit_to_replacement(file: &File, edit: &TextEdit) -> Replacement {
+    let deleted_region = region(file, edit.range.start, edit.range.end);
+    let inserted_content =
+        ArtifactContent::builder().text(String::from_utf8_lossy(&edit.new_text).into_owned()).build();
+
+    Replacement::builder().deleted_region(deleted_region).inserted_content(inserted_content).build()
+}
+
+fn annotation_to_location(annotation: &Annotation, database: &ReadDatabase) -> Result<Location, ReportingError> {
+    let file = database.get(&annotation.span.file_id())?;
+
+    let region = region(&file, annotation.span.start.offset, annotation.span.end.offset);
+    let physical_location =
+        PhysicalLocation::builder().artifact_location(artifact_location(&file)).region(region).build();
 
     let location = if let Some(msg) = &annotation.message {
         Location::builder()
@@ -158,6 +296,65 @@ fn annotation_to_location(annotation: &Annotation, database: &ReadDatabase) -> R
     Ok(location)
 }
 
+fn artifact_location(file: &File) -> ArtifactLocation {
+    let uri = if let Some(path) = &file.path {
+        path.to_string_lossy().to_string()
+    } else {
+        String::from_utf8_lossy(&file.name).into_owned()
+    };
+
+    ArtifactLocation::builder().uri(uri).build()
+}
+
+fn region(file: &File, start: u32, end: u32) -> Region {
+    Region::builder()
+        .start_line(i64::from(file.line_number(start)) + 1)
+        .start_column(i64::from(file.column_number(start)) + 1)
+        .end_line(i64::from(file.line_number(end)) + 1)
+        .end_column(i64::from(file.column_number(end)) + 1)
+        .build()
+}
+
+/// Counts identical fingerprints so that repeated issues on identical code stay distinct.
+#[derive(Default)]
+struct FingerprintCounter {
+    seen: HashMap<(FileId, String), usize>,
+}
+
+/// Computes a fingerprint that identifies the issue independently of its line number.
+///
+/// The fingerprint hashes the issue code, the file name, and the source lines covered by the
+/// primary annotation with whitespace collapsed, so it survives code moving up or down and
+/// re-indentation. Issues with the same hash in the same file are numbered in order.
+fn fingerprint(issue: &Issue, database: &ReadDatabase, counter: &mut FingerprintCounter) -> Option<String> {
+    let span = issue.primary_span().or_else(|| issue.annotations.first().map(|annotation| annotation.span))?;
+    let file = database.get(&span.file_id()).ok()?;
+
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(issue.code.as_deref().unwrap_or_default().as_bytes());
+    hasher.update(b"\0");
+    hasher.update(&file.name);
+    hasher.update(b"\0");
+    for word in covered_lines(&file, span).split(|byte| byte.is_ascii_whitespace()).filter(|word| !word.is_empty()) {
+        hasher.update(word);
+        hasher.update(b" ");
+    }
+
+    let hash = hasher.finalize().to_hex()[..32].to_string();
+    let occurrence = counter.seen.entry((file.id, hash.clone())).or_default();
+    *occurrence += 1;
+
+    Some(format!("{hash}:{occurrence}"))
+}
+
+/// Returns the source text of the full lines that the span touches.
+fn covered_lines(file: &File, span: Span) -> &[u8] {
+    let start = file.get_line_start_offset(file.line_number(span.start.offset)).unwrap_or(span.start.offset);
+    let end = file.get_line_end_offset(file.line_number(span.end.offset)).unwrap_or(span.end.offset);
+
+    file.contents.get(start as usize..end as usize).unwrap_or_default()
+}
+
 fn level_to_sarif(level: Level) -> ResultLevel {
     match level {
         Level::Error => ResultLevel::Error,
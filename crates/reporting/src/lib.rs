@@ -11,6 +11,7 @@
 //! - [`IssueCollection`]: A collection of issues with filtering and sorting capabilities
 //! - [`reporter::Reporter`]: Handles formatting and outputting issues in various formats
 //! - [`baseline::Baseline`]: Manages baseline files to filter out known issues
+//! - [`RuleDescriptor`]: Describes the rules behind issue codes for formats that embed them
 
 use std::cmp::Ordering;
 use std::iter::Once;
@@ -38,11 +39,13 @@ pub mod color;
 pub mod error;
 pub mod output;
 pub mod reporter;
+pub mod rule;
 
 pub use color::ColorChoice;
 pub use formatter::ReportingFormat;
 pub use formatter::utils::osc8_hyperlink;
 pub use output::ReportingTarget;
+pub use rule::RuleDescriptor;
 
 /// Represents an entry in the analyzer's `ignore` configuration.
 ///
@@ -21,6 +21,7 @@ use crate::formatter::FormatterConfig;
 use crate::formatter::ReportingFormat;
 use crate::formatter::dispatch_format;
 use crate::output::ReportingTarget;
+use crate::rule::RuleDescriptor;
 
 /// Configuration options for the reporter.
 ///
@@ -59,6 +60,12 @@ pub struct ReporterConfig {
     /// Supported placeholders: `%file%` (absolute path), `%line%`, `%column%`.
     /// Example: `"phpstorm://open?file=%file%&line=%line%"`
     pub editor_url: Option<String>,
+
+    /// Metadata for the rules behind the reported issue codes.
+    ///
+    /// Formats that describe their rules, such as SARIF, use it to embed rule names,
+    /// descriptions, and documentation links. Other formats ignore it.
+    pub rules: Vec<RuleDescriptor>,
 }
 
 /// Status information returned after reporting issues.
@@ -153,6 +160,7 @@ impl Reporter {
             minimum_level: self.config.minimum_report_level,
             filter_fixable: self.config.filter_fixable,
             editor_url: self.config.editor_url.clone(),
+            rules: self.config.rules.clone(),
         };
 
         // Dispatch to the appropriate formatter
@@ -236,6 +244,7 @@ impl Reporter {
             minimum_level: self.config.minimum_report_level,
             filter_fixable: self.config.filter_fixable,
             editor_url: self.config.editor_url.clone(),
+            rules: self.config.rules.clone(),
         };
 
         // Dispatch to the appropriate formatter
@@ -259,6 +268,7 @@ mod tests {
     use mago_database::DatabaseConfiguration;
     use mago_database::file::File;
     use mago_span::Span;
+    use mago_text_edit::TextEdit;
 
     use crate::Annotation;
     use crate::Issue;
@@ -280,6 +290,7 @@ mod tests {
                 sort: false,
                 minimum_report_level: None,
                 editor_url: None,
+                rules: Vec::new(),
             },
         )
     }
@@ -382,6 +393,78 @@ mod tests {
         assert_eq!(value["runs"][0]["results"][0]["message"]["text"], "message\0\u{1}\r\n</error>");
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn sarif_describes_rules_fixes_related_locations_and_fingerprints() {
+        let file = File::ephemeral(Cow::Borrowed(b"test.php"), Cow::Borrowed(b"<?php\n\n$a = 1;\n$b = 2;\n"));
+        let file_id = file.id;
+        let mut reporter = reporter_for_file(ReportingFormat::Sarif, file);
+        reporter.config.rules = vec![
+            RuleDescriptor::new("no-assign", "No Assign", "Flags assignments.\n\nAssignments are not allowed.")
+                .with_help_uri("https://example.com/rules/#no-assign"),
+        ];
+
+        let issue = Issue::warning("Assignment found.")
+            .with_code("no-assign")
+            .with_annotation(Annotation::primary(Span::new(file_id, 7u32.into(), 14u32.into())))
+            .with_annotation(
+                Annotation::secondary(Span::new(file_id, 15u32.into(), 22u32.into())).with_message("Here too."),
+            )
+            .with_file_edits(file_id, vec![TextEdit::delete(7..15)]);
+        let other = Issue::error("Unknown.").with_code("other-code");
+
+        let mut buffer = Vec::new();
+        let Ok(_) = reporter.report_to(IssueCollection::from([issue, other]), None, &mut buffer) else {
+            panic!("reporting should succeed");
+        };
+
+        let Ok(value) = serde_json::from_slice::<serde_json::Value>(&buffer) else {
+            panic!("SARIF output should be valid JSON");
+        };
+
+        let run = &value["runs"][0];
+        let rules = &run["tool"]["driver"]["rules"];
+        assert_eq!(rules[0]["id"], "no-assign");
+        assert_eq!(rules[0]["name"], "No Assign");
+        assert_eq!(rules[0]["shortDescription"]["text"], "Flags assignments.");
+        assert_eq!(rules[0]["helpUri"], "https://example.com/rules/#no-assign");
+        assert_eq!(rules[1]["id"], "other-code");
+
+        let result = &run["results"][0];
+        assert_eq!(result["ruleIndex"], 0);
+        assert_eq!(result["locations"].as_array().map(Vec::len), Some(1));
+        assert_eq!(result["relatedLocations"][0]["message"]["text"], "Here too.");
+        assert_eq!(result["fixes"][0]["artifactChanges"][0]["replacements"][0]["deletedRegion"]["startLine"], 3);
+        assert!(result["partialFingerprints"]["magoIssueHash/v1"].as_str().is_some_and(|hash| hash.ends_with(":1")));
+        assert_eq!(run["results"][1]["ruleIndex"], 1);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn sarif_fingerprints_do_not_depend_on_line_numbers() {
+        let fingerprint_for = |contents: &'static [u8], offset: u32| {
+            let file = File::ephemeral(Cow::Borrowed(b"test.php"), Cow::Borrowed(contents));
+            let file_id = file.id;
+            let reporter = reporter_for_file(ReportingFormat::Sarif, file);
+            let issue = Issue::warning("Assignment found.")
+                .with_code("no-assign")
+                .with_annotation(Annotation::primary(Span::new(file_id, offset.into(), (offset + 6).into())));
+
+            let mut buffer = Vec::new();
+            let Ok(_) = reporter.report_to(IssueCollection::from([issue]), None, &mut buffer) else {
+                panic!("reporting should succeed");
+            };
+
+            let Ok(value) = serde_json::from_slice::<serde_json::Value>(&buffer) else {
+                panic!("SARIF output should be valid JSON");
+            };
+
+            value["runs"][0]["results"][0]["partialFingerprints"]["magoIssueHash/v1"].clone()
+        };
+
+        assert_eq!(fingerprint_for(b"<?php\n$a = 1;\n", 6), fingerprint_for(b"<?php\n\n\n    $a = 1;\n", 12));
+    }
+
     #[cfg(feature = "serde")]
     #[test]
     fn json_writes_document_when_empty() {
@@ -0,0 +1,42 @@
+//! Rule metadata for reporting formats.
+//!
+//! Some output formats, such as SARIF, describe the rules behind the reported issues
+//! alongside the issues themselves. The reporting crate does not know about linter rules
+//! or analyzer issue codes, so callers provide this metadata as [`RuleDescriptor`]s.
+
+/// Describes the rule or check that produces issues with a given code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleDescriptor {
+    /// The issue code reported by the rule, e.g. `no-empty`.
+    pub code: String,
+    /// The human-readable name of the rule.
+    pub name: String,
+    /// The full description of the rule, in Markdown.
+    pub description: String,
+    /// A link to the documentation of the rule.
+    pub help_uri: Option<String>,
+}
+
+impl RuleDescriptor {
+    /// Creates a new rule descriptor without a documentation link.
+    #[must_use]
+    pub fn new(code: impl Into<String>, name: impl Into<String>, description: impl Into<String>) -> Self {
+        Self { code: code.into(), name: name.into(), description: description.into(), help_uri: None }
+    }
+
+    /// Sets the link to the documentation of the rule.
+    #[must_use]
+    pub fn with_help_uri(mut self, help_uri: impl Into<String>) -> Self {
+        self.help_uri = Some(help_uri.into());
+
+        self
+    }
+
+    /// Returns the first paragraph of the description, collapsed onto a single line.
+    #[must_use]
+    pub fn short_description(&self) -> String {
+        let paragraph = self.description.trim().split("\n\n").next().unwrap_or_default();
+
+        paragraph.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+}
@@ -0,0 +1,365 @@
+use std::borrow::Cow;
+
+use mago_twig_syntax::token::TwigToken;
+use mago_twig_syntax::token::TwigTokenKind;
+
+use crate::settings::TwigFormatSettings;
+
+/// A significant unit inside a tag: a single token, or a whole interpolated string.
+#[derive(Debug)]
+struct Piece<'input> {
+    kind: TwigTokenKind,
+    value: Cow<'input, [u8]>,
+    spaced_before: bool,
+    is_unary: bool,
+}
+
+/// A bracket opened inside a tag, along with the number of ternary `?` awaiting their `:`.
+#[derive(Debug, Clone, Copy)]
+struct Frame {
+    opener: Option<TwigTokenKind>,
+    pending_ternaries: usize,
+}
+
+/// Prints the token stream back out, rewriting the contents of every `{{ }}` and `{% %}` tag.
+///
+/// Raw text, `{# #}` comments, and verbatim content are copied as-is.
+pub(crate) fn print_tokens(tokens: &[TwigToken<'_>], settings: TwigFormatSettings, size_hint: usize) -> Vec<u8> {
+    let mut output = Vec::with_capacity(size_hint);
+
+    let mut index = 0;
+    while index < tokens.len() {
+        let token = &tokens[index];
+        if !token.kind.is_open_block() && !token.kind.is_open_variable() {
+            output.extend_from_slice(token.value);
+            index += 1;
+
+            continue;
+        }
+
+        let Some(length) = tokens[index..].iter().position(|t| t.kind.is_close_block() || t.kind.is_close_variable())
+        else {
+            tokens[index..].iter().for_each(|t| output.extend_from_slice(t.value));
+
+            break;
+        };
+
+        print_tag(&mut output, &tokens[index..=index + length], settings);
+        index += length + 1;
+    }
+
+    output
+}
+
+fn print_tag(output: &mut Vec<u8>, tag: &[TwigToken<'_>], settings: TwigFormatSettings) {
+    let [open, inner @ .., close] = tag else {
+        for t in tag {
+            output.extend_from_slice(t.value);
+        }
+
+        return;
+    };
+
+    let keeps_layout = inner.iter().any(|t| {
+        t.kind == TwigTokenKind::InlineComment || (t.kind == TwigTokenKind::Whitespace && t.value.contains(&b'\n'))
+    });
+
+    if keeps_layout {
+        for t in tag {
+            output.extend_from_slice(t.value);
+        }
+
+        return;
+    }
+
+    let is_block = open.kind.is_open_block();
+    let pieces = collect_pieces(inner, is_block, settings);
+
+    output.extend_from_slice(open.value);
+    if let (Some(first), Some(last)) = (pieces.first(), pieces.last()) {
+        let pad_start = settings.space_inside_delimiters || matches!(first.value.first(), Some(b'-' | b'~' | b'{'));
+        let pad_end = settings.space_inside_delimiters || matches!(last.value.last(), Some(b'-' | b'~' | b'}' | b'%'));
+
+        if pad_start {
+            output.push(b' ');
+        }
+
+        print_pieces(output, &pieces, is_block, settings);
+
+        if pad_end {
+            output.push(b' ');
+        }
+    }
+
+    output.extend_from_slice(close.value);
+}
+
+/// Groups the tokens inside a tag into pieces, dropping whitespace and keeping
+/// interpolated strings in one piece so their contents are never touched.
+fn collect_pieces<'input>(
+    inner: &[TwigToken<'input>],
+    is_block: bool,
+    settings: TwigFormatSettings,
+) -> Vec<Piece<'input>> {
+    let mut pieces: Vec<Piece<'input>> = Vec::with_capacity(inner.len());
+    let mut spaced_before = false;
+
+    let mut index = 0;
+    while index < inner.len() {
+        let token = &inner[index];
+        match token.kind {
+            TwigTokenKind::Whitespace => {
+                spaced_before = true;
+                index += 1;
+            }
+            TwigTokenKind::DoubleQuoteStart => {
+                let mut depth = 0usize;
+                let mut value = Vec::new();
+                while index < inner.len() {
+                    let part = &inner[index];
+                    value.extend_from_slice(part.value);
+                    index += 1;
+
+                    match part.kind {
+                        TwigTokenKind::DoubleQuoteStart => depth += 1,
+                        TwigTokenKind::DoubleQuoteEnd => {
+                            depth -= 1;
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+
+                pieces.push(Piece {
+                    kind: TwigTokenKind::DoubleQuoteStart,
+                    value: Cow::Owned(value),
+                    spaced_before,
+                    is_unary: false,
+                });
+                spaced_before = false;
+            }
+            kind => {
+                let is_unary = matches!(kind, TwigTokenKind::Minus | TwigTokenKind::Plus)
+                    && pieces.last().is_none_or(|previous| {
+                        (is_block && pieces.len() == 1)
+                            || is_operator(previous)
+                            || is_opener(previous.kind)
+                            || previous.is_unary
+                    });
+
+                let value = if kind.is_string() { requote(token.value, settings) } else { Cow::Borrowed(token.value) };
+
+                pieces.push(Piece { kind, value, spaced_before, is_unary });
+                spaced_before = false;
+                index += 1;
+            }
+        }
+    }
+
+    pieces
+}
+
+fn print_pieces(output: &mut Vec<u8>, pieces: &[Piece<'_>], is_block: bool, settings: TwigFormatSettings) {
+    let mut frames = vec![Frame { opener: None, pending_ternaries: 0 }];
+    let mut follows_ternary_colon = false;
+
+    for (index, piece) in pieces.iter().enumerate() {
+        let frame = frames.last().copied().unwrap_or(Frame { opener: None, pending_ternaries: 0 });
+        let is_ternary_colon = piece.kind == TwigTokenKind::Colon && frame.pending_ternaries > 0;
+
+        if let Some(previous) = index.checked_sub(1).and_then(|i| pieces.get(i)) {
+            let follows_tag_name = is_block && index == 1;
+            if needs_space(previous, piece, frame, follows_tag_name, is_ternary_colon, follows_ternary_colon, settings)
+            {
+                output.push(b' ');
+            }
+        }
+
+        output.extend_from_slice(&piece.value);
+        follows_ternary_colon = is_ternary_colon;
+
+        match piece.kind {
+            TwigTokenKind::LeftParen | TwigTokenKind::LeftBracket | TwigTokenKind::LeftBrace => {
+                frames.push(Frame { opener: Some(piece.kind), pending_ternaries: 0 });
+            }
+            TwigTokenKind::RightParen | TwigTokenKind::RightBracket | TwigTokenKind::RightBrace => {
+                if frames.len() > 1 {
+                    frames.pop();
+                }
+            }
+            TwigTokenKind::Question => {
+                if let Some(frame) = frames.last_mut() {
+                    frame.pending_ternaries += 1;
+                }
+            }
+            TwigTokenKind::Colon if is_ternary_colon => {
+                if let Some(frame) = frames.last_mut() {
+                    frame.pending_ternaries -= 1;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn needs_space(
+    previous: &Piece<'_>,
+    current: &Piece<'_>,
+    frame: Frame,
+    follows_tag_name: bool,
+    is_ternary_colon: bool,
+    follows_ternary_colon: bool,
+    settings: TwigFormatSettings,
+) -> bool {
+    if is_ternary_colon
+        || follows_ternary_colon
+        || previous.kind == TwigTokenKind::Question
+        || current.kind == TwigTokenKind::Question
+    {
+        return true;
+    }
+
+    if matches!(
+        current.kind,
+        TwigTokenKind::RightParen
+            | TwigTokenKind::RightBracket
+            | TwigTokenKind::RightBrace
+            | TwigTokenKind::Comma
+            | TwigTokenKind::Dot
+            | TwigTokenKind::QuestionDot
+            | TwigTokenKind::Pipe
+    ) {
+        return false;
+    }
+
+    if is_opener(previous.kind)
+        || previous.is_unary
+        || matches!(
+            previous.kind,
+            TwigTokenKind::Dot | TwigTokenKind::QuestionDot | TwigTokenKind::Pipe | TwigTokenKind::DotDotDot
+        )
+    {
+        return false;
+    }
+
+    if current.kind == TwigTokenKind::Colon {
+        return false;
+    }
+
+    if previous.kind == TwigTokenKind::Colon {
+        return frame.opener != Some(TwigTokenKind::LeftBracket);
+    }
+
+    if current.kind == TwigTokenKind::DotDot || previous.kind == TwigTokenKind::DotDot {
+        return settings.space_around_range_operator;
+    }
+
+    if current.kind == TwigTokenKind::Equal || previous.kind == TwigTokenKind::Equal {
+        return frame.opener != Some(TwigTokenKind::LeftParen) || current.spaced_before;
+    }
+
+    if follows_tag_name {
+        return true;
+    }
+
+    match current.kind {
+        TwigTokenKind::LeftParen | TwigTokenKind::LeftBracket if is_word(previous) => current.spaced_before,
+        TwigTokenKind::LeftParen | TwigTokenKind::LeftBracket => !ends_operand(previous),
+        _ => true,
+    }
+}
+
+/// Whether the piece can end an operand, so that a following `(` or `[` is a call or subscript.
+fn ends_operand(piece: &Piece<'_>) -> bool {
+    matches!(
+        piece.kind,
+        TwigTokenKind::Name
+            | TwigTokenKind::Number
+            | TwigTokenKind::StringSingleQuoted
+            | TwigTokenKind::StringDoubleQuoted
+            | TwigTokenKind::DoubleQuoteStart
+            | TwigTokenKind::RightParen
+            | TwigTokenKind::RightBracket
+            | TwigTokenKind::RightBrace
+    )
+}
+
+/// Whether the piece is a name, or a word-like test name such as `same as` or `divisible by`.
+fn is_word(piece: &Piece<'_>) -> bool {
+    matches!(piece.kind, TwigTokenKind::Name | TwigTokenKind::SameAs | TwigTokenKind::DivisibleBy)
+}
+
+const fn is_opener(kind: TwigTokenKind) -> bool {
+    matches!(kind, TwigTokenKind::LeftParen | TwigTokenKind::LeftBracket | TwigTokenKind::LeftBrace)
+}
+
+/// Whether the piece is an operator or separator after which a `-` or `+` must be unary.
+fn is_operator(piece: &Piece<'_>) -> bool {
+    piece.is_unary
+        || matches!(
+            piece.kind,
+            TwigTokenKind::Comma
+                | TwigTokenKind::Colon
+                | TwigTokenKind::Question
+                | TwigTokenKind::Equal
+                | TwigTokenKind::FatArrow
+                | TwigTokenKind::Plus
+                | TwigTokenKind::Minus
+                | TwigTokenKind::Asterisk
+                | TwigTokenKind::Slash
+                | TwigTokenKind::Percent
+                | TwigTokenKind::AsteriskAsterisk
+                | TwigTokenKind::SlashSlash
+                | TwigTokenKind::EqualEqual
+                | TwigTokenKind::BangEqual
+                | TwigTokenKind::EqualEqualEqual
+                | TwigTokenKind::BangEqualEqual
+                | TwigTokenKind::LessThan
+                | TwigTokenKind::GreaterThan
+                | TwigTokenKind::LessThanEqual
+                | TwigTokenKind::GreaterThanEqual
+                | TwigTokenKind::Spaceship
+                | TwigTokenKind::Tilde
+                | TwigTokenKind::DotDot
+                | TwigTokenKind::DotDotDot
+                | TwigTokenKind::QuestionQuestion
+                | TwigTokenKind::QuestionColon
+                | TwigTokenKind::And
+                | TwigTokenKind::Or
+                | TwigTokenKind::Xor
+                | TwigTokenKind::BAnd
+                | TwigTokenKind::BOr
+                | TwigTokenKind::BXor
+                | TwigTokenKind::In
+                | TwigTokenKind::NotIn
+                | TwigTokenKind::Is
+                | TwigTokenKind::Not
+                | TwigTokenKind::Matches
+                | TwigTokenKind::StartsWith
+                | TwigTokenKind::EndsWith
+                | TwigTokenKind::HasSome
+                | TwigTokenKind::HasEvery
+        )
+}
+
+/// Switches a plain string literal to the preferred quote style when its value would not change.
+fn requote(value: &[u8], settings: TwigFormatSettings) -> Cow<'_, [u8]> {
+    let (from, to) = if settings.single_quote { (b'"', b'\'') } else { (b'\'', b'"') };
+
+    let Some(content) = value.strip_prefix(&[from]).and_then(|rest| rest.strip_suffix(&[from])) else {
+        return Cow::Borrowed(value);
+    };
+
+    if content.iter().any(|&byte| byte == to || byte == b'\\' || byte == b'#') {
+        return Cow::Borrowed(value);
+    }
+
+    let mut requoted = Vec::with_capacity(value.len());
+    requoted.push(to);
+    requoted.extend_from_slice(content);
+    requoted.push(to);
+
+    Cow::Owned(requoted)
+}
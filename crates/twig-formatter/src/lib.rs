@@ -0,0 +1,130 @@
+//! Provides a formatter for Twig templates.
+//!
+//! The [`TwigFormatter`] normalizes what lives *inside* template tags - the spacing around
+//! delimiters, operators, punctuation, and filters, and the quoting of string literals - while
+//! leaving raw template text, comments, and `{% verbatim %}` content byte-for-byte intact.
+//! Tags that span several lines or carry inline comments are also kept as written, since
+//! their layout is usually deliberate.
+
+use std::borrow::Cow;
+
+use mago_allocator::Arena;
+
+use mago_database::file::File;
+use mago_syntax_core::input::Input;
+use mago_twig_syntax::cst::Template;
+use mago_twig_syntax::error::ParseError;
+use mago_twig_syntax::lexer::TwigLexer;
+use mago_twig_syntax::parser::parse_file_content_with_settings;
+use mago_twig_syntax::parser::parse_file_with_settings;
+use mago_twig_syntax::settings::ParserSettings;
+use mago_twig_syntax::token::TwigTokenKind;
+
+use crate::internal::print_tokens;
+use crate::settings::TwigFormatSettings;
+
+pub mod settings;
+
+mod internal;
+
+/// Markers that indicate a template should not be formatted.
+const FORMAT_IGNORE_MARKERS: [&[u8]; 2] = [b"@mago-format-ignore", b"@mago-formatter-ignore"];
+
+/// The main entry point for formatting Twig templates.
+#[derive(Debug)]
+pub struct TwigFormatter<'arena, A>
+where
+    A: Arena,
+{
+    arena: &'arena A,
+    settings: TwigFormatSettings,
+    parser_settings: ParserSettings,
+}
+
+impl<'arena, A> TwigFormatter<'arena, A>
+where
+    A: Arena,
+{
+    /// Creates a new `TwigFormatter` with the specified settings.
+    ///
+    /// # Arguments
+    ///
+    /// * `arena` - A reference to an arena for memory allocation.
+    /// * `settings` - The formatting settings to use.
+    pub fn new(arena: &'arena A, settings: TwigFormatSettings) -> Self {
+        Self { arena, settings, parser_settings: ParserSettings::default() }
+    }
+
+    /// Sets custom parser settings for the formatter.
+    #[must_use]
+    pub fn with_parser_settings(mut self, parser_settings: ParserSettings) -> Self {
+        self.parser_settings = parser_settings;
+        self
+    }
+
+    /// Formats a string of Twig source.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [`ParseError`] if the template contains syntax errors.
+    pub fn format_code(
+        &self,
+        name: Cow<'static, [u8]>,
+        code: Cow<'static, [u8]>,
+    ) -> Result<&'arena [u8], ParseError<'arena>> {
+        let file = File::ephemeral(name, code);
+
+        self.format_file(&file)
+    }
+
+    /// Formats the contents of a [`File`] holding a Twig template.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [`ParseError`] if the file's content contains syntax errors.
+    pub fn format_file(&self, file: &File) -> Result<&'arena [u8], ParseError<'arena>> {
+        let template = parse_file_with_settings(self.arena, file, self.parser_settings);
+        if let Some(error) = template.errors.first() {
+            return Err(error.clone());
+        }
+
+        Ok(self.format(template))
+    }
+
+    /// Formats a pre-parsed, error-free [`Template`].
+    ///
+    /// If the formatted output would no longer parse, the original source is returned
+    /// unchanged rather than risking a broken template.
+    #[must_use]
+    pub fn format(&self, template: &'arena Template<'arena>) -> &'arena [u8] {
+        let source = template.source_text;
+        let mut lexer = TwigLexer::new(Input::new(template.file_id, source), self.parser_settings.lexer);
+
+        let mut tokens = Vec::new();
+        while let Some(result) = lexer.advance() {
+            let Ok(token) = result else {
+                return source;
+            };
+
+            if token.kind == TwigTokenKind::Comment
+                && FORMAT_IGNORE_MARKERS.iter().any(|marker| memchr::memmem::find(token.value, marker).is_some())
+            {
+                return source;
+            }
+
+            tokens.push(token);
+        }
+
+        let formatted = print_tokens(&tokens, self.settings, source.len());
+        if formatted == source {
+            return source;
+        }
+
+        let reparsed = parse_file_content_with_settings(self.arena, template.file_id, &formatted, self.parser_settings);
+        if reparsed.has_errors() {
+            return source;
+        }
+
+        reparsed.source_text
+    }
+}
@@ -0,0 +1,48 @@
+use schemars::JsonSchema;
+
+/// Format settings for the Twig printer.
+///
+/// **WARNING:** This structure is not to be considered exhaustive. New fields may be added in minor
+/// or patch releases. Do not construct this structure directly outside of the formatter crate.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord, JsonSchema)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case", deny_unknown_fields))]
+pub struct TwigFormatSettings {
+    /// Put a single space between tag delimiters and their contents, e.g. `{{ name }}` and
+    /// `{% if user %}` instead of `{{name}}` and `{%if user%}`.
+    ///
+    /// Default: `true`
+    #[cfg_attr(feature = "serde", serde(default = "default_true"))]
+    pub space_inside_delimiters: bool,
+
+    /// Prefer single quotes over double quotes for string literals.
+    ///
+    /// Strings are only requoted when doing so does not change their value: strings containing
+    /// the other quote, a backslash, or a `#` (which could start an interpolation) are left alone.
+    ///
+    /// Default: `true`
+    #[cfg_attr(feature = "serde", serde(default = "default_true"))]
+    pub single_quote: bool,
+
+    /// Put spaces around the range operator, e.g. `1 .. 10` instead of `1..10`.
+    ///
+    /// Default: `false`
+    #[cfg_attr(feature = "serde", serde(default = "default_false"))]
+    pub space_around_range_operator: bool,
+}
+
+impl Default for TwigFormatSettings {
+    fn default() -> Self {
+        Self { space_inside_delimiters: true, single_quote: true, space_around_range_operator: false }
+    }
+}
+
+#[cfg(feature = "serde")]
+const fn default_true() -> bool {
+    true
+}
+
+#[cfg(feature = "serde")]
+const fn default_false() -> bool {
+    false
+}
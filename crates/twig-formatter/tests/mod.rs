@@ -0,0 +1,54 @@
+#![allow(clippy::unwrap_used)]
+
+use std::borrow::Cow;
+
+use mago_allocator::LocalArena;
+
+use mago_twig_formatter::TwigFormatter;
+use mago_twig_formatter::settings::TwigFormatSettings;
+
+macro_rules! test_case {
+    ($name:ident) => {
+        #[test]
+        pub fn $name() {
+            let code = include_bytes!(concat!("cases/", stringify!($name), "/before.twig"));
+            let expected = include_bytes!(concat!("cases/", stringify!($name), "/after.twig"));
+            let settings: TwigFormatSettings = include!(concat!("cases/", stringify!($name), "/settings.inc"));
+
+            let arena = LocalArena::new();
+            let formatter = TwigFormatter::new(&arena, settings);
+
+            let formatted_code = formatter.format_code(Cow::Borrowed(b"code.twig"), Cow::Borrowed(code)).unwrap();
+
+            pretty_assertions::assert_eq!(
+                String::from_utf8_lossy(expected),
+                String::from_utf8_lossy(formatted_code),
+                "Formatted code does not match expected",
+            );
+
+            let reformatted_code = formatter
+                .format_code(Cow::Borrowed(b"formatted_code.twig"), Cow::Owned(formatted_code.to_vec()))
+                .unwrap();
+
+            pretty_assertions::assert_eq!(
+                String::from_utf8_lossy(expected),
+                String::from_utf8_lossy(reformatted_code),
+                "Reformatted code does not match expected",
+            );
+        }
+    };
+}
+
+test_case!(expression_spacing);
+test_case!(strings_and_calls);
+test_case!(preserves_layout);
+test_case!(format_ignore);
+test_case!(compact_delimiters);
+
+#[test]
+fn reports_parse_errors() {
+    let arena = LocalArena::new();
+    let formatter = TwigFormatter::new(&arena, TwigFormatSettings::default());
+
+    formatter.format_code(Cow::Borrowed(b"broken.twig"), Cow::Borrowed(b"{% if user %}")).unwrap_err();
+}
@@ -0,0 +1,202 @@
+use mago_reporting::Annotation;
+use mago_reporting::Issue;
+use mago_reporting::IssueCollection;
+use mago_span::HasSpan;
+use mago_span::Span;
+use mago_twig_syntax::cst::Argument;
+use mago_twig_syntax::cst::Block;
+use mago_twig_syntax::cst::Call;
+use mago_twig_syntax::cst::Embed;
+use mago_twig_syntax::cst::Expression;
+use mago_twig_syntax::cst::Extends;
+use mago_twig_syntax::cst::Filter;
+use mago_twig_syntax::cst::FilterApplication;
+use mago_twig_syntax::cst::Identifier;
+use mago_twig_syntax::cst::Print;
+use mago_twig_syntax::cst::Test;
+use mago_twig_syntax::cst::Use;
+use mago_twig_syntax::walker::Walker;
+
+use crate::settings::TwigLinterSettings;
+
+const UNDEFINED_BLOCK_REFERENCE_CODE: &str = "undefined-block-reference";
+const DEPRECATED_FILTER_CODE: &str = "deprecated-filter";
+const RAW_OUTPUT_CODE: &str = "raw-output";
+
+/// State gathered while walking a template.
+///
+/// Block references can only be resolved once the whole template has been seen, since a
+/// block may be defined after the place it is referenced from.
+#[derive(Debug, Default)]
+pub(crate) struct LintContext<'arena> {
+    issues: IssueCollection,
+    inherits: bool,
+    embed_depth: usize,
+    defined_blocks: Vec<&'arena [u8]>,
+    block_references: Vec<(&'arena [u8], Span)>,
+    guarded_blocks: Vec<&'arena [u8]>,
+}
+
+impl LintContext<'_> {
+    pub(crate) fn finish(mut self, settings: &TwigLinterSettings) -> IssueCollection {
+        let rule = settings.undefined_block_reference;
+        if rule.enabled && !self.inherits {
+            for (name, span) in &self.block_references {
+                if self.defined_blocks.contains(name) || self.guarded_blocks.contains(name) {
+                    continue;
+                }
+
+                let name = String::from_utf8_lossy(name);
+
+                self.issues.push(
+                    Issue::new(rule.level, format!("Block `{name}` is not defined in this template."))
+                        .with_code(UNDEFINED_BLOCK_REFERENCE_CODE)
+                        .with_annotation(
+                            Annotation::primary(*span).with_message(format!("Block `{name}` is referenced here")),
+                        )
+                        .with_note("The template neither defines this block nor extends or uses a template that could.")
+                        .with_help(format!(
+                            "Define the block with `{{% block {name} %}}`, or check `block('{name}') is defined` first."
+                        )),
+                );
+            }
+        }
+
+        self.issues
+    }
+}
+
+pub(crate) struct TemplateWalker<'settings> {
+    pub(crate) settings: &'settings TwigLinterSettings,
+}
+
+impl TemplateWalker<'_> {
+    fn check_filter_name(&self, name: &Identifier<'_>, context: &mut LintContext<'_>) {
+        let rule = &self.settings.deprecated_filter;
+        if !rule.enabled || !rule.filters.iter().any(|filter| filter.as_bytes() == name.value) {
+            return;
+        }
+
+        let name_str = String::from_utf8_lossy(name.value);
+
+        context.issues.push(
+            Issue::new(rule.level, format!("Filter `{name_str}` is deprecated."))
+                .with_code(DEPRECATED_FILTER_CODE)
+                .with_annotation(Annotation::primary(name.span).with_message("Deprecated filter used here"))
+                .with_note("Deprecated filters may be removed in a future version of Twig.")
+                .with_help(format!("Replace the `{name_str}` filter with a supported alternative.")),
+        );
+    }
+}
+
+impl<'ast, 'arena> Walker<'ast, 'arena, LintContext<'arena>> for TemplateWalker<'_> {
+    fn walk_in_extends(&self, _extends: &'ast Extends<'arena>, context: &mut LintContext<'arena>) {
+        context.inherits = true;
+    }
+
+    fn walk_in_use(&self, _use: &'ast Use<'arena>, context: &mut LintContext<'arena>) {
+        context.inherits = true;
+    }
+
+    fn walk_in_embed(&self, _embed: &'ast Embed<'arena>, context: &mut LintContext<'arena>) {
+        context.embed_depth += 1;
+    }
+
+    fn walk_out_embed(&self, _embed: &'ast Embed<'arena>, context: &mut LintContext<'arena>) {
+        context.embed_depth -= 1;
+    }
+
+    fn walk_in_block(&self, block: &'ast Block<'arena>, context: &mut LintContext<'arena>) {
+        context.defined_blocks.push(block.name.value);
+    }
+
+    fn walk_in_call(&self, call: &'ast Call<'arena>, context: &mut LintContext<'arena>) {
+        // Inside an `{% embed %}`, blocks may come from the embedded template.
+        if context.embed_depth > 0 {
+            return;
+        }
+
+        if let Some(name) = get_referenced_block_name(call) {
+            context.block_references.push((name, call.span()));
+        }
+    }
+
+    fn walk_in_test(&self, test: &'ast Test<'arena>, context: &mut LintContext<'arena>) {
+        // `block('name') is defined` is the documented way to check for a block before rendering it.
+        if test.name.value == b"defined"
+            && let Expression::Call(call) = test.operand
+            && let Some(name) = get_referenced_block_name(call)
+        {
+            context.guarded_blocks.push(name);
+        }
+    }
+
+    fn walk_in_filter(&self, filter: &'ast Filter<'arena>, context: &mut LintContext<'arena>) {
+        self.check_filter_name(&filter.name, context);
+    }
+
+    fn walk_in_filter_application(
+        &self,
+        filter_application: &'ast FilterApplication<'arena>,
+        context: &mut LintContext<'arena>,
+    ) {
+        self.check_filter_name(&filter_application.name, context);
+    }
+
+    fn walk_in_print(&self, print: &'ast Print<'arena>, context: &mut LintContext<'arena>) {
+        let rule = self.settings.raw_output;
+        if !rule.enabled {
+            return;
+        }
+
+        let Expression::Filter(filter) = &print.expression else {
+            return;
+        };
+
+        if filter.name.value != b"raw" || is_literal(filter.operand) {
+            return;
+        }
+
+        context.issues.push(
+            Issue::new(rule.level, "Unescaped output of a dynamic value.")
+                .with_code(RAW_OUTPUT_CODE)
+                .with_annotation(
+                    Annotation::primary(filter.name.span).with_message("The `raw` filter disables auto-escaping"),
+                )
+                .with_annotation(
+                    Annotation::secondary(filter.operand.span()).with_message("This value is printed as-is"),
+                )
+                .with_note("Printing unescaped values can lead to cross-site scripting (XSS) vulnerabilities.")
+                .with_help(
+                    "Remove the `raw` filter, or make sure the value is sanitized before it reaches the template.",
+                ),
+        );
+    }
+}
+
+/// Returns the block name of a `block('name')` call, if the call has that exact shape.
+///
+/// Calls taking a template as a second argument refer to another template and are ignored.
+fn get_referenced_block_name<'arena>(call: &Call<'arena>) -> Option<&'arena [u8]> {
+    let Expression::Name(callee) = call.callee else {
+        return None;
+    };
+
+    if callee.name != b"block" || call.argument_list.arguments.len() != 1 {
+        return None;
+    }
+
+    let Some(Argument::Positional(argument)) = call.argument_list.arguments.first() else {
+        return None;
+    };
+
+    let Expression::String(literal) = argument.value else {
+        return None;
+    };
+
+    literal.raw.get(1..literal.raw.len().saturating_sub(1))
+}
+
+const fn is_literal(expression: &Expression<'_>) -> bool {
+    matches!(expression, Expression::String(_) | Expression::Number(_) | Expression::Bool(_) | Expression::Null(_))
+}
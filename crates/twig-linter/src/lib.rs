@@ -0,0 +1,111 @@
+//! Provides a linter for Twig templates.
+//!
+//! The [`TwigLinter`] parses a template and reports syntax errors along with the findings of
+//! a small set of template-specific rules:
+//!
+//! - `undefined-block-reference`: `block('name')` calls referring to a block that the
+//!   template neither defines nor could inherit.
+//! - `deprecated-filter`: uses of filters listed as deprecated in the settings.
+//! - `raw-output`: printing a non-literal value through the `raw` filter, which bypasses
+//!   auto-escaping.
+
+use std::borrow::Cow;
+
+use mago_allocator::Arena;
+
+use mago_database::file::File;
+use mago_reporting::Annotation;
+use mago_reporting::Issue;
+use mago_reporting::IssueCollection;
+use mago_span::HasSpan;
+use mago_twig_syntax::cst::Template;
+use mago_twig_syntax::error::ParseError;
+use mago_twig_syntax::parser::parse_file_with_settings;
+use mago_twig_syntax::settings::ParserSettings;
+use mago_twig_syntax::walker::Walker;
+
+use crate::internal::LintContext;
+use crate::internal::TemplateWalker;
+use crate::settings::TwigLinterSettings;
+
+pub mod settings;
+
+mod internal;
+
+/// The issue code used for template syntax errors.
+pub const PARSE_ERROR_CODE: &str = "twig-parse";
+
+/// The main entry point for linting Twig templates.
+#[derive(Debug)]
+pub struct TwigLinter<'arena, A>
+where
+    A: Arena,
+{
+    arena: &'arena A,
+    settings: TwigLinterSettings,
+    parser_settings: ParserSettings,
+}
+
+impl<'arena, A> TwigLinter<'arena, A>
+where
+    A: Arena,
+{
+    /// Creates a new `TwigLinter` with the specified settings.
+    ///
+    /// # Arguments
+    ///
+    /// * `arena` - A reference to an arena for memory allocation.
+    /// * `settings` - The linter settings to use.
+    pub fn new(arena: &'arena A, settings: TwigLinterSettings) -> Self {
+        Self { arena, settings, parser_settings: ParserSettings::default() }
+    }
+
+    /// Sets custom parser settings for the linter.
+    #[must_use]
+    pub fn with_parser_settings(mut self, parser_settings: ParserSettings) -> Self {
+        self.parser_settings = parser_settings;
+        self
+    }
+
+    /// Lints a string of Twig source.
+    #[must_use]
+    pub fn lint_code(&self, name: Cow<'static, [u8]>, code: Cow<'static, [u8]>) -> IssueCollection {
+        let file = File::ephemeral(name, code);
+
+        self.lint_file(&file)
+    }
+
+    /// Parses and lints the contents of a [`File`] holding a Twig template.
+    ///
+    /// Syntax errors are reported as issues with the [`PARSE_ERROR_CODE`] code. Rules only run
+    /// on templates that parsed without errors.
+    pub fn lint_file(&self, file: &File) -> IssueCollection {
+        let template = parse_file_with_settings(self.arena, file, self.parser_settings);
+        if template.has_errors() {
+            return template.errors.iter().map(parse_error_to_issue).collect();
+        }
+
+        self.lint(template)
+    }
+
+    /// Runs the enabled rules against a pre-parsed [`Template`].
+    #[must_use]
+    pub fn lint(&self, template: &Template<'_>) -> IssueCollection {
+        let walker = TemplateWalker { settings: &self.settings };
+        let mut context = LintContext::default();
+
+        walker.walk_template(template, &mut context);
+
+        context.finish(&self.settings)
+    }
+}
+
+/// Converts a template [`ParseError`] into an [`Issue`].
+#[must_use]
+pub fn parse_error_to_issue(error: &ParseError<'_>) -> Issue {
+    Issue::error("Template parse error encountered during parsing")
+        .with_code(PARSE_ERROR_CODE)
+        .with_annotation(Annotation::primary(error.span()).with_message(error.to_string()))
+        .with_note("This error indicates that the Twig parser could not make sense of the template.")
+        .with_help("Check the syntax of your template.")
+}
@@ -0,0 +1,71 @@
+use schemars::JsonSchema;
+
+use mago_reporting::Level;
+
+/// Settings for the Twig template linter.
+///
+/// **WARNING:** This structure is not to be considered exhaustive. New fields may be added in minor
+/// or patch releases. Do not construct this structure directly outside of the linter crate.
+#[derive(Debug, Clone, Eq, PartialEq, Default, JsonSchema)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, rename_all = "kebab-case", deny_unknown_fields))]
+pub struct TwigLinterSettings {
+    /// Reports `block('name')` calls referring to a block the template never defines.
+    pub undefined_block_reference: UndefinedBlockReferenceSettings,
+
+    /// Reports uses of deprecated filters, both as `|filter` and in `{% apply %}` tags.
+    pub deprecated_filter: DeprecatedFilterSettings,
+
+    /// Reports `{{ value|raw }}` where `value` is not a literal, bypassing auto-escaping.
+    pub raw_output: RawOutputSettings,
+}
+
+/// Settings for the `undefined-block-reference` rule.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, JsonSchema)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, rename_all = "kebab-case", deny_unknown_fields))]
+pub struct UndefinedBlockReferenceSettings {
+    pub enabled: bool,
+    pub level: Level,
+}
+
+/// Settings for the `deprecated-filter` rule.
+#[derive(Debug, Clone, Eq, PartialEq, JsonSchema)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, rename_all = "kebab-case", deny_unknown_fields))]
+pub struct DeprecatedFilterSettings {
+    pub enabled: bool,
+    pub level: Level,
+
+    /// The names of the filters considered deprecated.
+    ///
+    /// Default: `["spaceless"]`
+    pub filters: Vec<String>,
+}
+
+/// Settings for the `raw-output` rule.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, JsonSchema)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, rename_all = "kebab-case", deny_unknown_fields))]
+pub struct RawOutputSettings {
+    pub enabled: bool,
+    pub level: Level,
+}
+
+impl Default for UndefinedBlockReferenceSettings {
+    fn default() -> Self {
+        Self { enabled: true, level: Level::Error }
+    }
+}
+
+impl Default for DeprecatedFilterSettings {
+    fn default() -> Self {
+        Self { enabled: true, level: Level::Warning, filters: vec!["spaceless".to_string()] }
+    }
+}
+
+impl Default for RawOutputSettings {
+    fn default() -> Self {
+        Self { enabled: true, level: Level::Warning }
+    }
+}
@@ -0,0 +1,100 @@
+use std::borrow::Cow;
+
+use mago_allocator::LocalArena;
+use mago_reporting::IssueCollection;
+use mago_reporting::Level;
+
+use mago_twig_linter::PARSE_ERROR_CODE;
+use mago_twig_linter::TwigLinter;
+use mago_twig_linter::settings::TwigLinterSettings;
+
+fn lint(code: &'static str, settings: TwigLinterSettings) -> IssueCollection {
+    let arena = LocalArena::new();
+    let linter = TwigLinter::new(&arena, settings);
+
+    linter.lint_code(Cow::Borrowed(b"template.twig"), Cow::Borrowed(code.as_bytes()))
+}
+
+fn codes(issues: &IssueCollection) -> Vec<&str> {
+    issues.iter().filter_map(|issue| issue.code.as_deref()).collect()
+}
+
+#[test]
+fn reports_parse_errors() {
+    let issues = lint("{% if user %}", TwigLinterSettings::default());
+
+    assert_eq!(codes(&issues), vec![PARSE_ERROR_CODE]);
+    assert_eq!(issues.get_highest_level(), Some(Level::Error));
+}
+
+#[test]
+fn reports_undefined_block_reference() {
+    let issues = lint(
+        "{% block title %}Home{% endblock %}<title>{{ block('title') }}</title><h1>{{ block('heading') }}</h1>",
+        TwigLinterSettings::default(),
+    );
+
+    assert_eq!(codes(&issues), vec!["undefined-block-reference"]);
+    assert!(issues.iter().all(|issue| issue.message.contains("heading")));
+}
+
+#[test]
+fn ignores_block_references_in_inheriting_templates() {
+    let issues = lint(
+        "{% extends 'base.html.twig' %}{% block body %}{{ block('sidebar') }}{% endblock %}",
+        TwigLinterSettings::default(),
+    );
+
+    assert!(issues.is_empty());
+}
+
+#[test]
+fn ignores_guarded_block_references() {
+    let issues =
+        lint("{% if block('footer') is defined %}{{ block('footer') }}{% endif %}", TwigLinterSettings::default());
+
+    assert!(issues.is_empty());
+}
+
+#[test]
+fn reports_deprecated_filters() {
+    let issues = lint(
+        "{{ content|spaceless }}{% apply spaceless %}<div> </div>{% endapply %}{{ name|upper }}",
+        TwigLinterSettings::default(),
+    );
+
+    assert_eq!(codes(&issues), vec!["deprecated-filter", "deprecated-filter"]);
+}
+
+#[test]
+fn reports_configured_deprecated_filters() {
+    let mut settings = TwigLinterSettings::default();
+    settings.deprecated_filter.filters = vec!["upper".to_string()];
+
+    let issues = lint("{{ name|upper }}{{ content|spaceless }}", settings);
+
+    assert_eq!(codes(&issues), vec!["deprecated-filter"]);
+}
+
+#[test]
+fn reports_raw_output_of_dynamic_values() {
+    let issues = lint(
+        "{{ user.bio|raw }}{{ '<br>'|raw }}{{ comment.body|striptags|raw }}{{ name }}",
+        TwigLinterSettings::default(),
+    );
+
+    assert_eq!(codes(&issues), vec!["raw-output", "raw-output"]);
+    assert_eq!(issues.get_highest_level(), Some(Level::Warning));
+}
+
+#[test]
+fn disabled_rules_are_skipped() {
+    let mut settings = TwigLinterSettings::default();
+    settings.undefined_block_reference.enabled = false;
+    settings.deprecated_filter.enabled = false;
+    settings.raw_output.enabled = false;
+
+    let issues = lint("{{ block('missing') }}{{ content|spaceless }}{{ user.bio|raw }}", settings);
+
+    assert!(issues.is_empty());
+}
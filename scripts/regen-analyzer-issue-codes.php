@@ -348,6 +348,42 @@ final class AnalyzerCodeModuleGenerator
         'unavailable-constant',
         'unavailable-class-constant',
         'unavailable-enum-case',
+        'tainted-html',
+        'tainted-text-with-quotes',
+        'tainted-sql',
+        'tainted-shell',
+        'tainted-callable',
+        'tainted-unserialize',
+        'tainted-include',
+        'tainted-eval',
+        'tainted-ldap',
+        'tainted-file',
+        'tainted-header',
+        'tainted-cookie',
+        'tainted-ssrf',
+        'tainted-xpath',
+        'tainted-user-secret',
+        'tainted-system-secret',
+        'dead-code',
+        'undeclared-dependency',
+        'unused-dependency',
+        'dev-dependency-in-production',
+        'impure-function-call',
+        'impure-method-call',
+        'impure-property-assignment',
+        'impure-global-access',
+        'impure-static-property-access',
+        'division-by-zero',
+        'possibly-division-by-zero',
+        'integer-overflow',
+        'invalid-regex-pattern',
+        'unused-class-like',
+        'unused-class-constant',
+        'unused-enum-case',
+        'unused-function',
+        'unused-constant',
+        'invalid-doctrine-criteria',
+        'mismatched-doctrine-column-nullability',
     ];
 
     /**
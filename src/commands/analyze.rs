@@ -36,6 +36,7 @@
 use std::path::Path;
 use std::path::PathBuf;
 use std::process::ExitCode;
+use std::str::FromStr;
 use std::sync::mpsc;
 use std::time::Duration;
 use std::time::Instant;
@@ -49,10 +50,15 @@ use notify::RecursiveMode;
 use notify::Watcher as NotifyWatcher;
 
 use mago_analyzer::code::IssueCode;
+use mago_analyzer::dead_code::find_dead_code;
+use mago_analyzer::dead_code::plan_dead_code_removal;
+use mago_analyzer::dependencies::check_dependencies;
 use mago_codex::metadata::CodebaseMetadata;
 use mago_codex::reference::SymbolReferences;
+use mago_composer::ComposerPackage;
 use mago_database::Database;
 use mago_database::DatabaseReader;
+use mago_database::file::FileId;
 use mago_database::file::FileType;
 use mago_database::watcher::DatabaseWatcher;
 use mago_database::watcher::WatchOptions;
@@ -64,6 +70,7 @@ use crate::commands::args::baseline_reporting::BaselineReportingArgs;
 use crate::commands::args::substitution::SubstitutionArgs;
 use crate::commands::stdin_input;
 use crate::config::Configuration;
+use crate::consts::COMPOSER_JSON_FILE;
 use crate::consts::PRELUDE_BYTES;
 use crate::error::Error;
 use crate::extensions::initialize_external_analyzer;
@@ -170,6 +177,50 @@ pub struct AnalyzeCommand {
     #[arg(long, conflicts_with_all = ["list_codes", "watch", "staged"])]
     pub stdin_input: bool,
 
+    /// Infer missing parameter and return types and offer them as fixes.
+    ///
+    /// Enables the missing type hint checks and attaches the types the analyzer inferred
+    /// to them: native type hints where the configured PHP version supports them, and
+    /// `@param`/`@return` docblock tags for richer types such as `list<int>` or array
+    /// shapes. Unless `--retain-code` is given, only `missing-parameter-type` and
+    /// `missing-return-type` issues are reported.
+    ///
+    /// Combine with `--fix --potentially-unsafe` to write the types back into the source.
+    #[arg(long, conflicts_with_all = ["list_codes", "watch"])]
+    pub infer_types: bool,
+
+    /// Delete unused classes, functions, methods and properties.
+    ///
+    /// Analyzes the whole project, then removes the user-defined class-likes and functions
+    /// that nothing references, unused private methods and properties, and files left with
+    /// no other declarations. Declarations listed under `[analyzer.entry-points]`, or marked
+    /// with an entry-point attribute, are kept. Only `dead-code` issues are reported.
+    ///
+    /// Combine with `--dry-run` to preview the removals.
+    #[arg(long, conflicts_with_all = ["path", "list_codes", "watch", "staged", "stdin_input", "infer_types", "substitutions"])]
+    pub remove_dead_code: bool,
+
+    /// Check the packages used by the project against those required in `composer.json`.
+    ///
+    /// Analyzes the whole project, then maps every referenced symbol to the vendor package
+    /// declaring it. Reports packages used without being required, packages in `require`
+    /// that nothing uses, and packages in `require-dev` used from production code. The
+    /// vendor directory must be part of the scanned sources, for example through
+    /// `source.includes`. Only dependency issues are reported.
+    #[arg(long, conflicts_with_all = ["path", "list_codes", "watch", "staged", "stdin_input", "infer_types", "substitutions", "remove_dead_code"])]
+    pub check_dependencies: bool,
+
+    /// Persist analysis results to the given directory and reuse them on the next run.
+    ///
+    /// The scanned codebase metadata, symbol references, and per-file issues are stored
+    /// in this directory, keyed by file content hashes and the analyzer settings. A
+    /// subsequent run only re-analyzes files that changed since the cache was written,
+    /// along with the files that depend on them.
+    ///
+    /// The cache is ignored when external analyzer plugins are in use.
+    #[arg(long, value_name = "DIR", conflicts_with_all = ["path", "list_codes", "watch", "staged", "stdin_input", "substitutions"])]
+    pub cache_dir: Option<PathBuf>,
+
     /// Hidden flag to catch `--only` usage and show a helpful error.
     #[arg(long, hide = true, num_args = 1..)]
     pub only: Vec<String>,
@@ -216,7 +267,7 @@ impl AnalyzeCommand {
     ///
     /// Only host files are analyzed for issues; external files only contribute to
     /// the symbol table and type graph.
-    pub fn execute(self, configuration: Configuration, color_choice: ColorChoice) -> Result<ExitCode, Error> {
+    pub fn execute(mut self, configuration: Configuration, color_choice: ColorChoice) -> Result<ExitCode, Error> {
         if !self.only.is_empty() {
             eprintln!("error: the `--only` flag is not available for the analyzer.");
             eprintln!();
@@ -257,6 +308,44 @@ impl AnalyzeCommand {
         let mut orchestrator = create_orchestrator(&configuration, color_choice, false, true, false);
         orchestrator.add_exclude_patterns(configuration.analyzer.excludes.iter());
         orchestrator.add_exclude_patterns(substitution_excludes.iter());
+        if self.infer_types {
+            orchestrator.config.analyzer_settings.check_missing_type_hints = true;
+            orchestrator.config.analyzer_settings.infer_types = true;
+
+            let retain_code = &mut self.baseline_reporting.reporting.retain_code;
+            if retain_code.is_empty() {
+                retain_code.push(IssueCode::MissingParameterType.as_str().to_string());
+                retain_code.push(IssueCode::MissingReturnType.as_str().to_string());
+            }
+        }
+
+        if self.remove_dead_code {
+            orchestrator.config.analyzer_settings.find_unused_definitions = true;
+            orchestrator.config.analyzer_settings.analyze_dead_code = true;
+
+            let reporting = &mut self.baseline_reporting.reporting;
+            reporting.fix = true;
+            reporting.potentially_unsafe = true;
+        }
+
+        // Like `--check-dependencies`, unused symbols are only known once the whole project has been
+        // analyzed; symbols used from the files left out would be reported as unused.
+        if orchestrator.config.analyzer_settings.find_unused_symbols
+            && (self.staged || !self.path.is_empty() || self.stdin_input)
+        {
+            tracing::info!("Skipping `find-unused-symbols`, which requires analyzing the whole project.");
+            orchestrator.config.analyzer_settings.find_unused_symbols = false;
+        }
+
+        let composer = if self.check_dependencies {
+            let composer_json = std::fs::read_to_string(configuration.source.workspace.join(COMPOSER_JSON_FILE))
+                .map_err(Error::ReadingComposerJson)?;
+
+            Some(ComposerPackage::from_str(&composer_json).map_err(Error::ParsingComposerJson)?)
+        } else {
+            None
+        };
+
         for substitution in &substitutions {
             orchestrator.config.paths.push(substitution.temporary.to_string_lossy().into_owned());
         }
@@ -330,11 +419,65 @@ impl AnalyzeCommand {
         }
 
         let service_run_start = trace_enabled.then(Instant::now);
-        let service = orchestrator.get_analysis_service(database.read_only(), metadata, symbol_references);
-        let analysis_result = service.run()?;
+        let mut dead_symbols = None;
+        let mut dependency_issues = None;
+        let analysis_result = if self.cache_dir.is_none() && !self.remove_dead_code && composer.is_none() {
+            orchestrator.get_analysis_service(database.read_only(), metadata, symbol_references).run()?
+        } else {
+            let cache = self.cache_dir.as_ref().map(|cache_dir| {
+                let fingerprint = format!(
+                    "{:?}|{:?}|{}|{:?}|{}",
+                    configuration.source,
+                    configuration.analyzer.excludes,
+                    self.no_stubs,
+                    configuration.analyzer.plugins,
+                    configuration.analyzer.disable_default_plugins,
+                );
+
+                (cache_dir.join("analysis.bin"), fingerprint)
+            });
+
+            let mut service =
+                orchestrator.get_incremental_analysis_service(database.read_only(), metadata, symbol_references);
+            let analysis_result = match &cache {
+                Some((cache_path, fingerprint)) if service.load_cache(cache_path, fingerprint) => {
+                    service.analyze_incremental(None)?
+                }
+                _ => service.analyze()?,
+            };
+
+            if let Some((cache_path, fingerprint)) = &cache
+                && let Err(error) = service.save_cache(cache_path, fingerprint)
+            {
+                tracing::warn!("Failed to write the analysis cache: {error}");
+            }
+
+            if self.remove_dead_code {
+                dead_symbols = Some(find_dead_code(
+                    service.codebase(),
+                    service.symbol_references(),
+                    &orchestrator.config.analyzer_settings.entry_points,
+                ));
+            }
+
+            if let Some(composer) = &composer {
+                dependency_issues =
+                    Some(check_dependencies(&database, service.codebase(), service.symbol_references(), composer));
+            }
+
+            analysis_result
+        };
         let service_run_duration = service_run_start.map(|s| s.elapsed());
         let report_start = trace_enabled.then(Instant::now);
-        let mut issues = analysis_result.issues;
+        let mut removed_files = Vec::new();
+        let mut issues = match dead_symbols {
+            Some(dead_symbols) => {
+                let removal = plan_dead_code_removal(&database, &dead_symbols);
+                removed_files = removal.removed_files;
+                removal.issues
+            }
+            None => dependency_issues.unwrap_or(analysis_result.issues),
+        };
         let ignore_set = self.compile_ignore_set(&configuration);
 
         issues.filter_out_ignored(&ignore_set, |file_id| {
@@ -355,6 +498,10 @@ impl AnalyzeCommand {
         let (exit_code, changed_file_ids) = processor.process_issues(&orchestrator, &mut database, issues)?;
         let report_duration = report_start.map(|s| s.elapsed());
 
+        if !self.baseline_reporting.reporting.dry_run {
+            delete_emptied_files(&database, &removed_files);
+        }
+
         if self.staged && !changed_file_ids.is_empty() {
             git::stage_files(&configuration.source.workspace, &database, changed_file_ids)?;
         }
@@ -552,6 +699,31 @@ impl AnalyzeCommand {
     }
 }
 
+/// Deletes the files that `--remove-dead-code` emptied.
+///
+/// Files whose removal edits were not applied (for example, because they overlapped with
+/// another edit) still have content, and are left in place.
+fn delete_emptied_files(database: &Database<'_>, file_ids: &[FileId]) {
+    for file_id in file_ids {
+        let Ok(file) = database.get_ref(file_id) else {
+            continue;
+        };
+
+        let Some(path) = &file.path else {
+            continue;
+        };
+
+        if !file.contents.is_empty() {
+            continue;
+        }
+
+        match std::fs::remove_file(path) {
+            Ok(()) => tracing::info!("Deleted `{}`, which only contained dead code.", path.display()),
+            Err(error) => tracing::warn!("Failed to delete `{}`: {error}", path.display()),
+        }
+    }
+}
+
 /// Sets up a file system watcher for non-PHP files that should trigger a full restart.
 ///
 /// Watches:
@@ -0,0 +1,433 @@
+//! Fixture-driven conformance tests for configured extension hosts.
+//!
+//! Every `*.php` file below the fixture directory is linted with the external
+//! rules of the configured hosts, over the same worker pools `mago lint` uses.
+//! When the hosts register analyzer plugins, the fixtures are also analyzed
+//! through [`AnalysisService`] with those plugins attached, as `mago analyze`
+//! would. A fixture declares the issues it expects with `// expect: <code>`
+//! comments: on a line of its own, the expectation applies to the next
+//! non-blank line; after code, it applies to that line. When
+//! `<name>.fixed.php` sits next to `<name>.php`, applying every reported edit
+//! must reproduce its contents.
+//!
+//! Linter issues are checked when their code belongs to an external rule.
+//! Analyzer issues are checked when they are reported by an external plugin,
+//! or when their code is expected by one of the fixtures, so a plugin that
+//! changes native diagnostics can be tested too.
+//!
+//! Each host is then sent every [`LinterProbe`] and [`AnalyzerProbe`] through
+//! fresh single-worker pools, checking that malformed, oversized, and timed-out
+//! requests fail cleanly instead of crashing or desynchronizing the worker.
+
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::Duration;
+
+use clap::ColorChoice;
+use mago_analyzer::external::ExternalAnalyzer;
+use mago_analyzer::external::ExternalAnalyzerHandle;
+use mago_analyzer::external::ProtocolProbe as AnalyzerProbe;
+use mago_analyzer::plugin::create_registry_with_plugins;
+use mago_database::Database;
+use mago_database::DatabaseConfiguration;
+use mago_database::DatabaseReader;
+use mago_database::error::DatabaseError;
+use mago_database::file::File;
+use mago_database::file::FileId;
+use mago_database::file::FileType;
+use mago_extension::WorkerCommand;
+use mago_extension::WorkerError;
+use mago_extension::WorkerPool;
+use mago_extension::WorkerPoolOptions;
+use mago_linter::external::ExternalLinter;
+use mago_linter::external::ProtocolProbe as LinterProbe;
+use mago_linter::settings::Settings;
+use mago_orchestrator::service::analysis::AnalysisService;
+use mago_orchestrator::service::lint::LintMode;
+use mago_orchestrator::service::lint::LintService;
+use mago_prelude::Prelude;
+use mago_reporting::Issue;
+use mago_reporting::IssueCollection;
+use mago_text_edit::TextEditor;
+
+use crate::config::Configuration;
+use crate::consts::PRELUDE_BYTES;
+use crate::error::Error;
+
+const EXPECTATION_MARKERS: [&str; 2] = ["// expect:", "# expect:"];
+const FIXED_SUFFIX: &str = ".fixed.php";
+
+/// A fixture file and the outcome it declares.
+struct Fixture {
+    id: FileId,
+    expected: Vec<(u32, String)>,
+    fixed: Option<Vec<u8>>,
+}
+
+/// Lints and analyzes every fixture below `directory`, then probes each enabled host.
+///
+/// Prints one line per fixture and probe, followed by the reasons of every
+/// failure, and returns [`ExitCode::FAILURE`] if anything failed.
+pub(super) fn run(
+    directory: &Path,
+    configuration: &Configuration,
+    linter: ExternalLinter,
+    analyzer: Option<ExternalAnalyzer>,
+    skip_protocol_probes: bool,
+) -> Result<ExitCode, Error> {
+    let directory =
+        directory.canonicalize().map_err(|error| Error::CanonicalizingPath(directory.to_path_buf(), error))?;
+    let mut database = Database::new(DatabaseConfiguration::new(&directory, vec![], vec![], vec![], vec![]));
+    let fixtures = load_fixtures(&directory, &mut database)?;
+    if fixtures.is_empty() {
+        println!("No fixtures found in {}.", directory.display());
+
+        return Ok(ExitCode::FAILURE);
+    }
+
+    let codes = linter
+        .extensions()
+        .iter()
+        .flat_map(|extension| extension.rules.iter().map(|rule| rule.code.clone()))
+        .collect::<Vec<_>>();
+
+    let mut reported = vec![];
+    if !codes.is_empty() {
+        let settings = Settings { php_version: configuration.php_version, ..Settings::default() };
+        let issues = LintService::new(database.read_only(), settings, configuration.parser.to_settings(), false)
+            .with_external_linter(Arc::new(linter))
+            .lint(LintMode::Full, Some(&codes))?;
+
+        reported
+            .extend(issues.into_iter().filter(|issue| issue.code.as_ref().is_some_and(|code| codes.contains(code))));
+    }
+
+    let analyzer =
+        analyzer.filter(|analyzer| analyzer.extensions().iter().any(|extension| !extension.plugins.is_empty()));
+    if let Some(analyzer) = analyzer {
+        let plugins = analyzer
+            .extensions()
+            .iter()
+            .flat_map(|extension| extension.plugins.iter().map(|plugin| format!("{}/", plugin.identifier)))
+            .collect::<Vec<_>>();
+        let expected = fixtures
+            .iter()
+            .flat_map(|fixture| fixture.expected.iter().map(|(_, code)| code))
+            .filter(|code| !codes.contains(code))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let issues = analyze_fixtures(&mut database, configuration, analyzer)?;
+        reported.extend(issues.into_iter().filter(|issue| {
+            issue.code.as_ref().is_some_and(|code| {
+                expected.contains(code) || plugins.iter().any(|plugin| code.starts_with(plugin.as_str()))
+            })
+        }));
+    }
+
+    let mut passed = 0usize;
+    let mut failed = 0usize;
+    for fixture in &fixtures {
+        let file = database.get(&fixture.id)?;
+        let failures = check_fixture(&file, fixture, &reported);
+        report(&mago_bytes::BytesDisplay(&file.name).to_string(), &failures, &mut passed, &mut failed);
+    }
+
+    if !skip_protocol_probes {
+        for (name, host) in configuration.extension_hosts.iter().filter(|(_, host)| host.enabled) {
+            let Some(command) = host.worker_command() else {
+                continue;
+            };
+
+            for probe in LinterProbe::ALL {
+                let failures = run_linter_probe(probe, &command, host.worker_pool_options(), configuration)
+                    .err()
+                    .into_iter()
+                    .collect::<Vec<_>>();
+                report(&format!("{name}: linter {}", probe.name()), &failures, &mut passed, &mut failed);
+            }
+
+            for probe in AnalyzerProbe::ALL {
+                let failures = run_analyzer_probe(probe, &command, host.worker_pool_options(), configuration)
+                    .err()
+                    .into_iter()
+                    .collect::<Vec<_>>();
+                report(&format!("{name}: analyzer {}", probe.name()), &failures, &mut passed, &mut failed);
+            }
+        }
+    }
+
+    println!("{passed} passed, {failed} failed.");
+
+    Ok(if failed == 0 { ExitCode::SUCCESS } else { ExitCode::FAILURE })
+}
+
+fn report(subject: &str, failures: &[String], passed: &mut usize, failed: &mut usize) {
+    if failures.is_empty() {
+        *passed += 1;
+        println!("PASS {subject}");
+
+        return;
+    }
+
+    *failed += 1;
+    println!("FAIL {subject}");
+    for failure in failures {
+        for line in failure.lines() {
+            println!("  {line}");
+        }
+    }
+}
+
+/// Analyzes the fixtures with the enabled native plugins and the hosts' analyzer plugins.
+///
+/// The embedded prelude is merged into `database` first, so fixtures see the
+/// same built-in symbols as under `mago analyze`.
+fn analyze_fixtures(
+    database: &mut Database<'_>,
+    configuration: &Configuration,
+    analyzer: ExternalAnalyzer,
+) -> Result<IssueCollection, Error> {
+    let Prelude { database: prelude_database, metadata, symbol_references } =
+        Prelude::decode(PRELUDE_BYTES).expect("Failed to decode embedded prelude");
+    database.merge_base(prelude_database);
+
+    let mut registry =
+        create_registry_with_plugins(&configuration.analyzer.plugins, configuration.analyzer.disable_default_plugins);
+    registry.set_external_analyzer(Arc::new(ExternalAnalyzerHandle::ready(analyzer)));
+
+    let settings = configuration.analyzer.to_settings(configuration.php_version, ColorChoice::Never, false);
+    let result = AnalysisService::new(
+        database.read_only(),
+        metadata,
+        symbol_references,
+        settings,
+        configuration.parser.to_settings(),
+        false,
+        Arc::new(registry),
+    )
+    .run()?;
+
+    Ok(result.issues)
+}
+
+/// Adds every fixture below `directory` to `database`, in a stable order.
+fn load_fixtures(directory: &Path, database: &mut Database<'_>) -> Result<Vec<Fixture>, Error> {
+    let mut paths = vec![];
+    collect_fixture_paths(directory, &mut paths).map_err(DatabaseError::IOError)?;
+    paths.sort();
+
+    let mut fixtures = Vec::with_capacity(paths.len());
+    for path in paths {
+        let file = File::read(directory, &path, FileType::Host)?;
+        let fixed_path = path.with_extension("fixed.php");
+        let fixed =
+            if fixed_path.is_file() { Some(std::fs::read(&fixed_path).map_err(DatabaseError::IOError)?) } else { None };
+
+        let expected = parse_expectations(&file.contents);
+        fixtures.push(Fixture { id: database.add(file), expected, fixed });
+    }
+
+    Ok(fixtures)
+}
+
+fn collect_fixture_paths(directory: &Path, paths: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(directory)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_fixture_paths(&path, paths)?;
+        } else if path.extension().is_some_and(|extension| extension == "php")
+            && !path.to_string_lossy().ends_with(FIXED_SUFFIX)
+        {
+            paths.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the 1-based line and issue code of every expectation in a fixture.
+fn parse_expectations(contents: &[u8]) -> Vec<(u32, String)> {
+    let mut expected = vec![];
+    let mut pending: Vec<(u32, String)> = vec![];
+    for (index, line) in contents.split(|byte| *byte == b'\n').enumerate() {
+        let line_number = index as u32 + 1;
+        let line = String::from_utf8_lossy(line);
+        let marker = EXPECTATION_MARKERS
+            .iter()
+            .find_map(|marker| line.find(marker).map(|position| (&line[..position], &line[position + marker.len()..])));
+        let codes = marker
+            .map(|(_, codes)| codes.split(',').map(str::trim).filter(|code| !code.is_empty()).map(str::to_string))
+            .into_iter()
+            .flatten();
+
+        if marker.is_some_and(|(before, _)| before.trim().is_empty()) {
+            pending.extend(codes.map(|code| (line_number, code)));
+            continue;
+        }
+
+        if !line.trim().is_empty() {
+            expected.extend(pending.drain(..).map(|(_, code)| (line_number, code)));
+        }
+
+        expected.extend(codes.map(|code| (line_number, code)));
+    }
+
+    // Expectations with nothing after them keep their own line, so they are reported as missing.
+    expected.extend(pending);
+    expected
+}
+
+/// Compares the issues and edits reported for a fixture with the ones it declares.
+///
+/// `issues` holds the checked linter and analyzer issues of every fixture.
+fn check_fixture(file: &File, fixture: &Fixture, issues: &[Issue]) -> Vec<String> {
+    let reported = issues
+        .iter()
+        .filter(|issue| issue.primary_span().is_some_and(|span| span.file_id == file.id))
+        .collect::<Vec<_>>();
+
+    let mut failures = vec![];
+    let mut unmatched = fixture.expected.clone();
+    for issue in &reported {
+        let Some(span) = issue.primary_span() else {
+            continue;
+        };
+
+        let line = file.line_number(span.start.offset) + 1;
+        let code = issue.code.as_deref().unwrap_or_default();
+        if let Some(position) =
+            unmatched.iter().position(|(expected_line, expected_code)| *expected_line == line && expected_code == code)
+        {
+            unmatched.remove(position);
+        } else {
+            failures.push(format!("unexpected `{code}` on line {line}: {}", issue.message));
+        }
+    }
+
+    failures.extend(unmatched.into_iter().map(|(line, code)| format!("missing `{code}` on line {line}")));
+
+    if let Some(fixed) = &fixture.fixed {
+        let actual = apply_edits(file.id, &file.contents, &reported);
+        if actual != *fixed {
+            let patch =
+                diffy::create_patch(&String::from_utf8_lossy(fixed), &String::from_utf8_lossy(&actual)).to_string();
+            failures.push(format!("edits do not reproduce the fixed fixture:\n{patch}"));
+        }
+    }
+
+    failures
+}
+
+fn apply_edits(file_id: FileId, contents: &[u8], issues: &[&Issue]) -> Vec<u8> {
+    let mut editor = TextEditor::new(contents);
+    for issue in issues {
+        if let Some(edits) = issue.edits.get(&file_id) {
+            editor.apply_batch(edits.clone(), None::<fn(&[u8]) -> bool>);
+        }
+    }
+
+    editor.finish()
+}
+
+/// Sends one linter probe to a fresh single-worker pool and checks that it fails cleanly.
+fn run_linter_probe(
+    probe: LinterProbe,
+    command: &WorkerCommand,
+    options: WorkerPoolOptions,
+    configuration: &Configuration,
+) -> Result<(), String> {
+    let timeout = probe == LinterProbe::Timeout;
+    let Some(pool) = send_probe(command, options, timeout, |size| probe.payload(configuration.php_version, size))?
+    else {
+        return Ok(());
+    };
+
+    ExternalLinter::initialize([pool], configuration.php_version)
+        .map(drop)
+        .map_err(|error| format!("the worker did not recover after rejecting the request: {error}"))
+}
+
+/// Sends one analyzer probe to a fresh single-worker pool and checks that it fails cleanly.
+fn run_analyzer_probe(
+    probe: AnalyzerProbe,
+    command: &WorkerCommand,
+    options: WorkerPoolOptions,
+    configuration: &Configuration,
+) -> Result<(), String> {
+    let timeout = probe == AnalyzerProbe::Timeout;
+    let Some(pool) = send_probe(command, options, timeout, |size| probe.payload(configuration.php_version, size))?
+    else {
+        return Ok(());
+    };
+
+    ExternalAnalyzer::initialize(
+        [pool],
+        configuration.php_version,
+        &configuration.analyzer.plugins,
+        configuration.analyzer.disable_default_plugins,
+    )
+    .map(drop)
+    .map_err(|error| format!("the worker did not recover after rejecting the request: {error}"))
+}
+
+/// Sends a probe payload and checks the worker's answer.
+///
+/// Returns the pool when the worker rejected the request and should now be
+/// checked for recovery, or `None` when a timeout probe was handled correctly.
+fn send_probe(
+    command: &WorkerCommand,
+    options: WorkerPoolOptions,
+    timeout: bool,
+    payload: impl FnOnce(usize) -> Vec<u8>,
+) -> Result<Option<Arc<WorkerPool>>, String> {
+    let options = if timeout { WorkerPoolOptions { request_timeout: Duration::ZERO, ..options } } else { options };
+    let payload = payload(options.maximum_payload_size);
+    let pool = WorkerPool::spawn(command.clone(), NonZeroUsize::MIN, options)
+        .map(Arc::new)
+        .map_err(|error| format!("failed to start the worker: {error}"))?;
+
+    match (timeout, pool.request(payload)) {
+        (true, Err(WorkerError::Timeout { .. })) => Ok(None),
+        (true, Err(error)) => Err(format!("the timed-out worker was not cancelled and replaced: {error}")),
+        (true, Ok(_)) => Err("the worker answered a request after its deadline".to_string()),
+        (false, Err(WorkerError::Remote { .. })) => Ok(Some(pool)),
+        (false, Err(error)) => Err(format!("the worker did not answer with an error response: {error}")),
+        (false, Ok(_)) => Err("the worker accepted the request instead of rejecting it".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use mago_text_edit::TextEdit;
+
+    use super::*;
+
+    #[test]
+    fn parses_expectations_for_the_next_line_and_the_same_line() {
+        let source = b"<?php\n\n// expect: acme/no-eval\n\n$a = eval($b);\n$c = die(); // expect: acme/no-die, acme/no-assign\n// expect: acme/trailing\n";
+
+        assert_eq!(
+            parse_expectations(source),
+            [
+                (5, "acme/no-eval".to_string()),
+                (6, "acme/no-die".to_string()),
+                (6, "acme/no-assign".to_string()),
+                (7, "acme/trailing".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn applies_every_reported_edit() {
+        let file = File::ephemeral(Cow::Borrowed(b"fixture.php"), Cow::Borrowed(b"<?php\ndie();\n"));
+        let issue = Issue::warning("Do not use `die`.").with_edit(file.id, TextEdit::replace(6..11, "exit(1)"));
+
+        assert_eq!(apply_edits(file.id, &file.contents, &[&issue]), b"<?php\nexit(1);\n");
+    }
+}
@@ -1,5 +1,6 @@
-//! Inspect and validate configured external extension hosts.
+//! Inspect, validate, and test configured external extension hosts.
 
+use std::path::PathBuf;
 use std::process::ExitCode;
 
 use clap::Parser;
@@ -7,11 +8,14 @@ use clap::Subcommand;
 
 use crate::config::Configuration;
 use crate::error::Error;
+use crate::extensions::initialize_external_analyzer;
 use crate::extensions::initialize_external_linter;
 
+mod conformance;
+
 /// Manage external extensions configured for this workspace.
 #[derive(Parser, Debug)]
-#[command(name = "extension", about = "Inspect and validate external extensions.")]
+#[command(name = "extension", about = "Inspect, validate, and test external extensions.")]
 pub struct ExtensionCommand {
     #[command(subcommand)]
     command: ExtensionSubcommand,
@@ -27,6 +31,21 @@ enum ExtensionSubcommand {
     },
     /// Start every configured host and validate its registration.
     Validate,
+    /// Check external linter rules and analyzer plugins against a directory of PHP fixtures.
+    ///
+    /// Each fixture declares the issues it expects with `// expect: <code>`
+    /// comments, and may have a `<name>.fixed.php` sibling holding the result
+    /// of applying every reported edit. Fixtures are linted, and analyzed too
+    /// when the hosts register analyzer plugins. Each host is also sent
+    /// malformed, oversized, and timed-out linter and analyzer requests, which
+    /// it must reject cleanly.
+    Test {
+        /// The directory containing the fixtures.
+        path: PathBuf,
+        /// Do not send protocol edge cases to the hosts.
+        #[arg(long)]
+        skip_protocol_probes: bool,
+    },
 }
 
 impl ExtensionCommand {
@@ -105,6 +124,24 @@ impl ExtensionCommand {
                     println!("No external extensions are configured.");
                 }
             }
+            ExtensionSubcommand::Test { path, skip_protocol_probes } => {
+                let Some(external) = external else {
+                    println!("No external extensions are configured.");
+
+                    return Ok(ExitCode::FAILURE);
+                };
+
+                let analyzer = initialize_external_analyzer(
+                    &configuration.extension_hosts,
+                    configuration.php_version,
+                    configuration.threads,
+                    &configuration.analyzer.plugins,
+                    configuration.analyzer.disable_default_plugins,
+                )
+                .map_err(mago_orchestrator::OrchestratorError::from)?;
+
+                return conformance::run(&path, &configuration, external, analyzer, skip_protocol_probes);
+            }
         }
 
         Ok(ExitCode::SUCCESS)
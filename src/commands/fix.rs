@@ -0,0 +1,214 @@
+//! Fix command implementation.
+//!
+//! This module implements the `mago fix` command, which runs both the linter and the
+//! analyzer and applies the fixes attached to their issues in a single pass.
+//!
+//! # Fix Sources
+//!
+//! - **Linter**: rule fixes, such as removing redundant `use` statements
+//! - **Analyzer**: fixes derived from type analysis, such as adding a missing
+//!   `#[\Override]` attribute, adding inferred return and parameter type hints,
+//!   or narrowing overly wide return types
+//!
+//! # Safety Levels
+//!
+//! Fixes go through the same safety tiers as `mago lint --fix` and `mago analyze --fix`:
+//! safe fixes are always applied, while potentially unsafe and unsafe fixes require
+//! `--potentially-unsafe` and `--unsafe` respectively. When a linter fix and an analyzer
+//! fix touch the same code, the first one wins and the other is skipped.
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::Arc;
+
+use clap::ColorChoice;
+use clap::Parser;
+
+use mago_database::DatabaseReader;
+use mago_database::file::FileType;
+use mago_database::matcher::ExclusionMatcher;
+use mago_orchestrator::service::lint::LintMode;
+use mago_prelude::Prelude;
+use mago_reporting::CompiledIgnoreSet;
+use mago_reporting::IssueCollection;
+use mago_reporting::Level;
+use mago_reporting::ReportingFormat;
+use mago_reporting::ReportingTarget;
+
+use crate::commands::stdin_input;
+use crate::config::Configuration;
+use crate::consts::PRELUDE_BYTES;
+use crate::error::Error;
+use crate::extensions::initialize_external_linter;
+use crate::extensions::start_external_analyzer;
+use crate::service::IssueProcessor;
+use crate::utils::create_orchestrator;
+
+/// Command for applying linter and analyzer fixes in one pass.
+///
+/// This command lints and analyzes the workspace, then applies every fix allowed by
+/// the selected safety level. Issues without fixes are not reported; use `mago lint`
+/// or `mago analyze` to review them.
+#[derive(Parser, Debug)]
+#[command(name = "fix")]
+pub struct FixCommand {
+    /// Specific files or directories to fix instead of using configuration.
+    ///
+    /// When provided, these paths override the source configuration in mago.toml.
+    #[arg()]
+    pub path: Vec<PathBuf>,
+
+    /// Do not apply fixes from the linter.
+    #[arg(long, conflicts_with = "no_analyze")]
+    pub no_lint: bool,
+
+    /// Do not apply fixes from the analyzer.
+    #[arg(long)]
+    pub no_analyze: bool,
+
+    /// Disable built-in PHP and library stubs for analysis.
+    #[arg(long, default_value_t = false)]
+    pub no_stubs: bool,
+
+    /// Apply fixes that are marked as unsafe.
+    ///
+    /// Unsafe fixes might change code behavior or have unintended consequences.
+    /// Always review changes carefully after applying unsafe fixes.
+    #[arg(long)]
+    pub r#unsafe: bool,
+
+    /// Apply fixes that are marked as potentially unsafe.
+    ///
+    /// These fixes are less risky than unsafe ones but may still require
+    /// manual review to ensure they don't break your code.
+    #[arg(long)]
+    pub potentially_unsafe: bool,
+
+    /// Format the fixed files after applying changes.
+    #[arg(long, alias = "fmt")]
+    pub format_after_fix: bool,
+
+    /// Preview fixes without writing any changes to disk.
+    #[arg(long, short = 'd', alias = "diff")]
+    pub dry_run: bool,
+
+    /// Only apply fixes for issues with the specified code(s).
+    ///
+    /// Can be specified multiple times, e.g. `--retain-code missing-return-type`.
+    #[arg(long, value_name = "CODE")]
+    pub retain_code: Vec<String>,
+}
+
+impl FixCommand {
+    /// Executes the fix process.
+    ///
+    /// Both tools share a single database. Files excluded from the analyzer are not
+    /// loaded at all, and issues in files excluded from the linter are dropped before
+    /// their fixes are applied. The analyzer's `ignore` configuration is honored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database cannot be loaded, if linting or analysis
+    /// fails, or if the fixes cannot be written back.
+    pub fn execute(self, configuration: Configuration, color_choice: ColorChoice) -> Result<ExitCode, Error> {
+        let mut orchestrator = create_orchestrator(&configuration, color_choice, false, true, false);
+        orchestrator.add_exclude_patterns(configuration.analyzer.excludes.iter());
+        if !self.path.is_empty() {
+            stdin_input::set_source_paths_from_paths(&mut orchestrator, &self.path);
+        }
+
+        if !self.no_analyze
+            && let Some(external_analyzer) = start_external_analyzer(
+                &configuration.extension_hosts,
+                configuration.php_version,
+                configuration.threads,
+                &configuration.analyzer.plugins,
+                configuration.analyzer.disable_default_plugins,
+            )
+        {
+            orchestrator.set_external_analyzer_handle(external_analyzer);
+        }
+
+        let Prelude { database: prelude_database, metadata, symbol_references } = if self.no_stubs || self.no_analyze {
+            Prelude::default()
+        } else {
+            Prelude::decode(PRELUDE_BYTES).expect("Failed to decode embedded prelude")
+        };
+
+        let mut database =
+            orchestrator.load_database(&configuration.source.workspace, true, Some(prelude_database), None)?;
+
+        if !database.files().any(|f| f.file_type == FileType::Host) {
+            tracing::warn!("No files found to fix.");
+
+            return Ok(ExitCode::SUCCESS);
+        }
+
+        let mut issues = IssueCollection::new();
+
+        if !self.no_lint {
+            let mut service = orchestrator.get_lint_service(database.read_only());
+            if let Some(external_linter) = initialize_external_linter(
+                &configuration.extension_hosts,
+                configuration.php_version,
+                configuration.threads,
+            )
+            .map_err(mago_orchestrator::OrchestratorError::from)?
+            {
+                service = service.with_external_linter(Arc::new(external_linter));
+            }
+
+            let linter_excludes = ExclusionMatcher::compile(
+                configuration.linter.excludes.iter().map(String::as_str),
+                configuration.source.glob.to_database_settings(),
+            )?;
+
+            issues.extend(service.lint(LintMode::Full, None)?.into_iter().filter(|issue| {
+                !issue.primary_span().is_some_and(|span| {
+                    database
+                        .get_ref(&span.file_id)
+                        .is_ok_and(|file| linter_excludes.is_match(&String::from_utf8_lossy(&file.name)))
+                })
+            }));
+        }
+
+        if !self.no_analyze {
+            let service = orchestrator.get_analysis_service(database.read_only(), metadata, symbol_references);
+            let mut analysis_issues = service.run()?.issues;
+
+            let ignore_set = CompiledIgnoreSet::compile(
+                &configuration.analyzer.ignore,
+                configuration.source.glob.to_database_settings(),
+            );
+
+            analysis_issues.filter_out_ignored(&ignore_set, |file_id| {
+                database.get_ref(&file_id).ok().map(|f| String::from_utf8_lossy(&f.name).into_owned())
+            });
+
+            issues.extend(analysis_issues);
+        }
+
+        let processor = IssueProcessor {
+            fixable_only: false,
+            sort: false,
+            fix: true,
+            r#unsafe: self.r#unsafe,
+            potentially_unsafe: self.potentially_unsafe,
+            format_after_fix: self.format_after_fix,
+            dry_run: self.dry_run,
+            fail_on_remaining: false,
+            reporting_target: ReportingTarget::default(),
+            reporting_format: ReportingFormat::default(),
+            minimum_fail_level: Level::Error,
+            minimum_report_level: None,
+            retain_code: self.retain_code,
+            color_choice,
+            editor_url: None,
+            scoped_analysis: !self.path.is_empty(),
+        };
+
+        let (exit_code, _) = processor.process_issues(&orchestrator, &mut database, issues, None, false)?;
+
+        Ok(exit_code)
+    }
+}
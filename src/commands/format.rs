@@ -12,6 +12,7 @@
 //! - **Check Mode** (`--check`): Validates formatting without making changes (CI-friendly)
 //! - **Dry Run** (`--dry-run`): Shows what would change via diff without modifying files
 //! - **STDIN Mode** (`--stdin-input`): Reads from stdin, writes formatted code to stdout
+//! - **Changed Lines** (`--changed-lines`): Formats only the statements touched since the last commit
 //!
 //! # Configuration
 //!
@@ -38,6 +39,7 @@
 
 use std::borrow::Cow;
 use std::io::Read;
+use std::ops::RangeInclusive;
 use std::path::Path;
 use std::path::PathBuf;
 use std::process::ExitCode;
@@ -52,6 +54,7 @@ use mago_database::error::DatabaseError;
 use mago_database::file::File;
 use mago_orchestrator::service::format::FileFormatStatus;
 use mago_orchestrator::service::format::FormatResult;
+use mago_text_edit::TextRange;
 
 use crate::EXIT_CODE_ERROR;
 use crate::config::Configuration;
@@ -135,6 +138,20 @@ pub struct FormatCommand {
     /// - Not in a git repository
     #[arg(long, short = 's', conflicts_with_all = ["dry_run", "check", "stdin_input", "path"])]
     pub staged: bool,
+
+    /// Format only the lines changed since the last commit.
+    ///
+    /// The hunks of `git diff HEAD` determine which lines were touched, and only
+    /// the statements enclosing those lines are formatted. This keeps diffs small
+    /// when adopting the formatter on an existing codebase. Files that are not
+    /// tracked yet are formatted as a whole.
+    ///
+    /// Combined with `--staged`, only the staged lines of staged files are formatted.
+    ///
+    /// Fails if:
+    /// - Not in a git repository
+    #[arg(long, conflicts_with_all = ["stdin_input"])]
+    pub changed_lines: bool,
 }
 
 impl FormatCommand {
@@ -146,6 +163,7 @@ impl FormatCommand {
     /// 1. **Mode Selection**: Determines which mode to use based on flags
     /// 2. **STDIN Handling**: If `--stdin-input`, reads from stdin and formats immediately
     /// 3. **Staged Handling**: If `--staged`, formats only git-staged files
+    ///    (only their staged lines with `--changed-lines`)
     /// 4. **Database Loading**: Scans workspace for PHP files (unless stdin mode)
     /// 5. **Service Creation**: Creates formatting service with configuration
     /// 6. **Formatting**: Processes each file according to the selected mode
@@ -169,6 +187,7 @@ impl FormatCommand {
     /// - **Dry run** (`--dry-run`): Prints diffs without modifying files
     /// - **STDIN** (`--stdin-input`): Formats input from stdin to stdout
     /// - **Staged** (`--staged`): Formats staged files and re-stages them
+    /// - **Changed lines** (`--changed-lines`): Formats only the statements enclosing changed lines
     pub fn execute(self, configuration: Configuration, color_choice: ColorChoice) -> Result<ExitCode, Error> {
         if self.staged {
             return self.execute_staged(configuration, color_choice);
@@ -176,6 +195,9 @@ impl FormatCommand {
 
         let mut orchestrator = create_orchestrator(&configuration, color_choice, false, true, false);
         orchestrator.add_exclude_patterns(configuration.formatter.excludes.iter());
+        if configuration.twig.enabled {
+            orchestrator.include_twig_templates();
+        }
         if !self.path.is_empty() {
             orchestrator.set_source_paths(self.path.iter().map(|p| p.to_string_lossy().to_string()));
         }
@@ -184,6 +206,10 @@ impl FormatCommand {
             return self.execute_stdin(orchestrator, &configuration);
         }
 
+        if self.changed_lines {
+            return self.execute_changed_lines(&orchestrator, &configuration, color_choice);
+        }
+
         let mut database = orchestrator.load_database(&configuration.source.workspace, false, None, None)?;
         let service = orchestrator.get_format_service(database.read_only());
 
@@ -195,6 +221,12 @@ impl FormatCommand {
             tracing::error!("Failed to parse file '{}': {parse_error}", mago_bytes::BytesDisplay(&file.name));
         }
 
+        for (file_id, parse_error) in result.template_parse_errors() {
+            let file = database.get_ref(file_id)?;
+
+            tracing::error!("Failed to parse template '{}': {parse_error}", mago_bytes::BytesDisplay(&file.name));
+        }
+
         let changed_files_count = result.changed_files_count();
 
         if changed_files_count == 0 {
@@ -275,13 +307,92 @@ impl FormatCommand {
         Ok(emit_stdin_result(status, &file))
     }
 
+    /// Executes formatting for the lines changed since the last commit.
+    ///
+    /// Only the statements enclosing the changed lines of each file are formatted; the
+    /// results are then written, checked, or printed as diffs like in the default mode.
+    fn execute_changed_lines(
+        self,
+        orchestrator: &mago_orchestrator::Orchestrator<'_>,
+        configuration: &Configuration,
+        color_choice: ColorChoice,
+    ) -> Result<ExitCode, Error> {
+        let workspace = &configuration.source.workspace;
+
+        let changed_lines = git::get_changed_lines(workspace)?;
+        if changed_lines.is_empty() {
+            tracing::info!("No changed files to format.");
+            return Ok(ExitCode::SUCCESS);
+        }
+
+        let mut database = orchestrator.load_database(workspace, false, None, None)?;
+
+        let mut formatted_files = Vec::new();
+        for changed in changed_lines {
+            let absolute_path = workspace.join(&changed.path);
+            let canonical_path = absolute_path.canonicalize().unwrap_or(absolute_path);
+
+            let Ok(file) = database.get_by_path(&canonical_path) else {
+                continue;
+            };
+
+            match orchestrator.format_file_ranges(&file, &line_ranges(&file.contents, &changed.lines))? {
+                FileFormatStatus::Unchanged => {}
+                FileFormatStatus::Changed(new_content) => formatted_files.push((file, new_content)),
+                FileFormatStatus::FailedToParse(parse_error) => {
+                    tracing::error!("Failed to parse file '{}': {parse_error}", mago_bytes::BytesDisplay(&file.name));
+                }
+                FileFormatStatus::FailedToParseTemplate(parse_error) => {
+                    tracing::error!(
+                        "Failed to parse template '{}': {parse_error}",
+                        mago_bytes::BytesDisplay(&file.name)
+                    );
+                }
+            }
+        }
+
+        let changed_files_count = formatted_files.len();
+        if changed_files_count == 0 {
+            tracing::info!("All changed lines are already formatted.");
+
+            return Ok(ExitCode::SUCCESS);
+        }
+
+        if self.check {
+            tracing::info!(
+                "Found {changed_files_count} file(s) with changed lines that need formatting. Run the command without '--check' to format them.",
+            );
+
+            return Ok(ExitCode::FAILURE);
+        }
+
+        let change_log = ChangeLog::new();
+        for (file, new_content) in &formatted_files {
+            utils::apply_update(&change_log, file, new_content, self.dry_run, color_choice)?;
+        }
+
+        database.commit(change_log, true)?;
+
+        let exit_code = if self.dry_run {
+            tracing::info!("Found {changed_files_count} file(s) with changed lines that need formatting.");
+
+            ExitCode::FAILURE
+        } else {
+            tracing::info!("Formatted changed lines in {changed_files_count} file(s) successfully.");
+
+            ExitCode::SUCCESS
+        };
+
+        Ok(exit_code)
+    }
+
     /// Executes formatting for staged files.
     ///
     /// This method implements the `--staged` mode for git pre-commit hooks:
     ///
     /// 1. Verifies we're in a git repository
     /// 2. Gets the list of staged PHP files
-    /// 3. Formats the staged files in memory
+    /// 3. Formats the staged files in memory, or only their staged lines with `--changed-lines`
     /// 4. Updates the staged file with its formatted version
     ///
     /// # Arguments
@@ -298,6 +409,9 @@ impl FormatCommand {
 
         let mut orchestrator = create_orchestrator(&configuration, color_choice, false, true, false);
         orchestrator.add_exclude_patterns(configuration.formatter.excludes.iter());
+        if configuration.twig.enabled {
+            orchestrator.include_twig_templates();
+        }
 
         let database = orchestrator.load_database(workspace, false, None, None)?;
 
@@ -308,6 +422,8 @@ impl FormatCommand {
             return Ok(ExitCode::SUCCESS);
         }
 
+        let staged_lines = if self.changed_lines { Some(git::get_staged_changed_lines(workspace)?) } else { None };
+
         let mut changed_files_count = 0;
         for path in staged_file_paths {
             let absolute_path = workspace.join(&path);
@@ -317,7 +433,19 @@ impl FormatCommand {
                 continue;
             }
             let staged_file = get_staged_file(workspace, &path)?;
-            match orchestrator.format_file(&staged_file)? {
+            let status = match &staged_lines {
+                Some(staged_lines) => {
+                    let lines = staged_lines
+                        .iter()
+                        .find(|changed| changed.path == path)
+                        .map_or(&[][..], |changed| changed.lines.as_slice());
+
+                    orchestrator.format_file_ranges(&staged_file, &line_ranges(&staged_file.contents, lines))?
+                }
+                None => orchestrator.format_file(&staged_file)?,
+            };
+
+            match status {
                 FileFormatStatus::Unchanged => continue,
                 FileFormatStatus::Changed(new_content) => {
                     update_staged_file(workspace, &path, new_content)?;
@@ -326,6 +454,9 @@ impl FormatCommand {
                 FileFormatStatus::FailedToParse(parse_error) => {
                     tracing::error!("Failed to parse staged file '{}': {}", path.display(), parse_error);
                 }
+                FileFormatStatus::FailedToParseTemplate(parse_error) => {
+                    tracing::error!("Failed to parse staged template '{}': {}", path.display(), parse_error);
+                }
             };
         }
 
@@ -355,6 +486,23 @@ fn to_change_log(
     Ok(change_log)
 }
 
+/// Converts 1-based, inclusive line ranges into byte ranges of `contents`.
+fn line_ranges(contents: &[u8], lines: &[RangeInclusive<u32>]) -> Vec<TextRange> {
+    let line_starts: Vec<u32> = std::iter::once(0)
+        .chain(contents.iter().enumerate().filter(|(_, byte)| **byte == b'\n').map(|(index, _)| index as u32 + 1))
+        .collect();
+
+    lines
+        .iter()
+        .filter_map(|lines| {
+            let start = *line_starts.get(lines.start().saturating_sub(1) as usize)?;
+            let end = line_starts.get(*lines.end() as usize).map_or(contents.len() as u32, |next| next - 1);
+
+            Some(TextRange::new(start, end.max(start)))
+        })
+        .collect()
+}
+
 fn emit_stdin_result(status: FileFormatStatus, file: &File) -> ExitCode {
     use std::io::Write;
     match status {
@@ -370,6 +518,10 @@ fn emit_stdin_result(status: FileFormatStatus, file: &File) -> ExitCode {
             tracing::error!("Failed to parse {}: {parse_error}", mago_bytes::BytesDisplay(&file.name));
             ExitCode::from(EXIT_CODE_ERROR)
         }
+        FileFormatStatus::FailedToParseTemplate(parse_error) => {
+            tracing::error!("Failed to parse template {}: {parse_error}", mago_bytes::BytesDisplay(&file.name));
+            ExitCode::from(EXIT_CODE_ERROR)
+        }
     }
 }
 
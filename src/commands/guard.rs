@@ -44,9 +44,11 @@ use std::time::Instant;
 
 use clap::ColorChoice;
 use clap::Parser;
+use clap::ValueEnum;
 
 use mago_database::DatabaseReader;
 use mago_database::file::FileType;
+use mago_guard::graph::DependencyGraph;
 use mago_guard::settings::GuardMode;
 use mago_prelude::Prelude;
 
@@ -58,6 +60,18 @@ use crate::consts::PRELUDE_BYTES;
 use crate::error::Error;
 use crate::utils::create_orchestrator;
 
+/// The output formats of the dependency graph.
+#[derive(ValueEnum, Debug, Clone, Copy)]
+#[value(rename_all = "kebab-case")]
+pub enum GraphFormat {
+    /// Graphviz DOT.
+    Dot,
+    /// Mermaid flowchart.
+    Mermaid,
+    /// JSON with `nodes` and `edges`.
+    Json,
+}
+
 /// Command for enforcing architectural rules and layer dependencies.
 ///
 /// The `guard` command performs architectural boundary checking on your PHP codebase.
@@ -108,6 +122,19 @@ pub struct GuardCommand {
     #[arg(long, conflicts_with = "structural")]
     pub perimeter: bool,
 
+    /// Print the namespace dependency graph in the given format instead of checking rules.
+    ///
+    /// Every edge carries the number of dependencies between two namespaces, grouped by
+    /// kind (class-like, function, constant, attribute). The graph is written to stdout.
+    #[arg(long, value_enum, value_name = "FORMAT", conflicts_with_all = ["structural", "perimeter", "stdin_input"])]
+    pub graph: Option<GraphFormat>,
+
+    /// Group the dependency graph by the layers defined in `guard.perimeter.layering`.
+    ///
+    /// Namespaces that do not belong to any layer are kept as they are.
+    #[arg(long, requires = "graph")]
+    pub graph_by_layer: bool,
+
     /// Read the file content from stdin and use the given path for baseline and reporting.
     ///
     /// Intended for editor integrations: pipe unsaved buffer content and pass the real file path.
@@ -229,6 +256,17 @@ impl GuardCommand {
             return Ok(ExitCode::SUCCESS);
         }
 
+        if let Some(format) = self.graph {
+            let mut graph = orchestrator.get_guard_service(database.read_only(), metadata).graph()?;
+            if self.graph_by_layer {
+                graph = graph.group_by_layers(&configuration.guard.settings.perimeter.layering);
+            }
+
+            print!("{}", render_graph(&graph, format)?);
+
+            return Ok(ExitCode::SUCCESS);
+        }
+
         let guard_run_start = trace_enabled.then(Instant::now);
         let service = orchestrator.get_guard_service(database.read_only(), metadata);
         let result = service.run()?;
@@ -282,3 +320,12 @@ impl GuardCommand {
         Ok(exit_code)
     }
 }
+
+/// Renders the dependency graph in the requested format.
+fn render_graph(graph: &DependencyGraph, format: GraphFormat) -> Result<String, Error> {
+    Ok(match format {
+        GraphFormat::Dot => graph.to_dot(),
+        GraphFormat::Mermaid => graph.to_mermaid(),
+        GraphFormat::Json => serde_json::to_string_pretty(graph)? + "\n",
+    })
+}
@@ -0,0 +1,202 @@
+//! HIR inspection command implementation.
+//!
+//! This module implements the `mago hir` command, which lowers a PHP file into Mago's
+//! high-level intermediate representation (HIR) and prints the result. It is the HIR
+//! counterpart of `mago cst`: a diagnostic tool for checking what the lowering pass
+//! produces for a given piece of code.
+//!
+//! # What the HIR Adds
+//!
+//! Compared to the CST, the HIR:
+//!
+//! - Drops purely syntactic nodes (keywords, delimiters, opening tags)
+//! - Carries resolved names on every identifier, along with whether it was imported
+//! - Parses PHPDoc into structured annotations (`@param`, `@return`, `@template`, ...)
+//! - Includes inferred assertions for simple boolean-returning functions
+//!
+//! # Output Formats
+//!
+//! - **Tree Format** (default): Human-readable tree structure with colors
+//! - **JSON Format** (`--json`): Machine-parseable structured output
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::ColorChoice;
+use clap::Parser;
+use colored::Colorize;
+use serde_json::json;
+use termtree::Tree;
+
+use mago_allocator::LocalArena;
+use mago_database::Database;
+use mago_database::DatabaseConfiguration;
+use mago_database::file::File;
+use mago_database::file::FileType;
+use mago_hir::ir::IR;
+use mago_hir::lower::LowerSettings;
+use mago_hir::lower::Lowering;
+use mago_hir::node::Node;
+use mago_reporting::Issue;
+use mago_reporting::IssueCollection;
+use mago_reporting::Level;
+use mago_syntax::parser::parse_file_with_settings;
+
+use crate::commands::args::reporting::ReportingArgs;
+use crate::config::Configuration;
+use crate::error::Error;
+use crate::utils::create_orchestrator;
+
+/// Command for inspecting the lowered HIR of a PHP file.
+///
+/// This command parses a file, lowers it into the high-level intermediate
+/// representation used by Mago's semantic tooling, and displays the result.
+/// Parse errors are reported before the (partial) HIR is printed.
+#[derive(Parser, Debug)]
+#[command(
+    name = "hir",
+    about = "Inspect the high-level intermediate representation (HIR) of a PHP file.",
+    long_about = "Lower a PHP file into Mago's high-level intermediate representation and display it.\n\n\
+                  Unlike the CST, the HIR carries resolved names and parsed PHPDoc annotations,\n\
+                  which makes it the representation to look at when debugging how Mago\n\
+                  understands a declaration rather than how it parses it."
+)]
+pub struct HirCommand {
+    /// The PHP file to lower and display.
+    #[arg(required = true)]
+    pub file: PathBuf,
+
+    /// Output in machine-readable JSON format.
+    ///
+    /// Instead of the human-readable tree format, output the lowered IR
+    /// as structured JSON that can be processed by other tools or scripts.
+    #[arg(long)]
+    pub json: bool,
+
+    /// Skip PHPDoc and attribute annotations when lowering.
+    ///
+    /// By default, docblock tags and attributes are interpreted into structured
+    /// annotations. This flag lowers the code alone, which keeps the output short
+    /// when only the executable structure is of interest.
+    #[arg(long)]
+    pub no_annotations: bool,
+
+    #[clap(flatten)]
+    pub reporting: ReportingArgs,
+}
+
+impl HirCommand {
+    /// Executes the HIR inspection command.
+    pub fn execute(self, mut configuration: Configuration, color_choice: ColorChoice) -> Result<ExitCode, Error> {
+        let arena = LocalArena::new();
+        let scratch = LocalArena::new();
+        let file = File::read(&configuration.source.workspace, &self.file, FileType::Host)?;
+
+        let program = parse_file_with_settings(&scratch, &file, configuration.parser.to_settings());
+        let settings = LowerSettings { process_annotations: !self.no_annotations, ..LowerSettings::default() };
+        let ir: IR<'_, (), (), ()> = Lowering::new(&arena, &scratch, &file, program, settings).lower();
+
+        if program.has_errors() {
+            let issues = IssueCollection::from(program.errors.iter().map(Issue::from).collect::<Vec<_>>());
+            let config = DatabaseConfiguration::new(Path::new("/"), vec![], vec![], vec![], vec![]).into_static();
+            let mut database = Database::single(file, config);
+            let editor_url = configuration.editor_url.take();
+            let orchestrator = create_orchestrator(&configuration, color_choice, false, true, false);
+
+            self.reporting.get_processor(color_choice, editor_url, Level::Error, false).process_issues(
+                &orchestrator,
+                &mut database,
+                issues,
+                None,
+                false,
+            )?;
+        }
+
+        if self.json {
+            print_hir_json(&ir)?;
+        } else {
+            print_hir_tree(&ir);
+        }
+
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+/// Prints the HIR as a rich, human-readable tree.
+fn print_hir_tree(ir: &IR<'_, (), (), ()>) {
+    let tree = node_to_tree(Node::Ir(ir));
+    println!();
+    println!("{tree}");
+    println!();
+}
+
+/// Prints the HIR in a machine-readable, pretty-printed JSON format.
+fn print_hir_json(ir: &IR<'_, (), (), ()>) -> Result<(), Error> {
+    let result = json!({
+        "ir": ir,
+    });
+
+    println!("{}", serde_json::to_string_pretty(&result)?);
+
+    Ok(())
+}
+
+/// Recursively converts an HIR `Node` into a rich `termtree::Tree`.
+fn node_to_tree(node: Node<'_, '_, (), (), ()>) -> Tree<String> {
+    let label = match node {
+        // Structural nodes
+        Node::Ir(_) => "IR".bold().underline().to_string(),
+        Node::Statement(_) | Node::Expression(_) | Node::ItemStatement(_) | Node::MemberItem(_) => {
+            format!("{:?}", node.kind()).bold().underline().to_string()
+        }
+        // Literals
+        Node::LiteralString(s) => {
+            let value = s.value.map(|v| String::from_utf8_lossy(v).into_owned()).unwrap_or_default();
+            format!("{} {}", "LiteralString".green(), format!("{value:?}").yellow())
+        }
+        Node::LiteralInteger(i) => {
+            format!("{} {}", "LiteralInteger".green(), String::from_utf8_lossy(i.raw).yellow())
+        }
+        Node::LiteralFloat(f) => format!("{} {}", "LiteralFloat".green(), String::from_utf8_lossy(f.raw).yellow()),
+        // Names
+        Node::Identifier(id) => {
+            let imported = if id.imported { " (imported)".dimmed().to_string() } else { String::new() };
+
+            format!(
+                "{} {}{}",
+                format!("Identifier<{:?}>", id.kind).cyan(),
+                String::from_utf8_lossy(id.value).bright_black(),
+                imported
+            )
+        }
+        Node::Name(name) => format!("{} {}", "Name".cyan(), String::from_utf8_lossy(name.value).bright_black()),
+        Node::DirectVariable(var) => {
+            format!("{} {}", "DirectVariable".cyan(), String::from_utf8_lossy(var.name).yellow())
+        }
+        // Operators
+        Node::BinaryOperator(op) => format!("{} {}", "BinaryOperator".magenta(), format!("{:?}", op.kind).bold()),
+        Node::UnaryPrefixOperator(op) => {
+            format!("{} {}", "UnaryPrefixOperator".magenta(), format!("{:?}", op.kind).bold())
+        }
+        Node::UnaryPostfixOperator(op) => {
+            format!("{} {}", "UnaryPostfixOperator".magenta(), format!("{:?}", op.kind).bold())
+        }
+        Node::AssignmentOperator(op) => {
+            format!("{} {}", "AssignmentOperator".magenta(), format!("{:?}", op.kind).bold())
+        }
+        // Errors
+        Node::Error(_) | Node::AnnotationError(_) => {
+            format!("<{:?}>", node.kind()).bold().underline().red().to_string()
+        }
+        // Everything else -> Dimmed
+        _ => format!("{:?}", node.kind()).dimmed().to_string(),
+    };
+
+    let mut tree = Tree::new(label);
+    node.visit_children(|child| {
+        tree.push(node_to_tree(child));
+    });
+
+    tree
+}
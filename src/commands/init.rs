@@ -69,6 +69,8 @@ enum AnalyzerPlugin {
     FlowPhp,
     /// Type providers for psr/container package
     PsrContainer,
+    /// Type providers and mapping checks for doctrine/orm package
+    Doctrine,
 }
 
 impl std::fmt::Display for AnalyzerPlugin {
@@ -77,6 +79,7 @@ impl std::fmt::Display for AnalyzerPlugin {
             Self::Psl => write!(f, "psl"),
             Self::FlowPhp => write!(f, "flow-php"),
             Self::PsrContainer => write!(f, "psr-container"),
+            Self::Doctrine => write!(f, "doctrine"),
         }
     }
 }
@@ -88,6 +91,7 @@ impl AnalyzerPlugin {
             Self::Psl => "PSL - Type providers for php-standard-library/php-standard-library package",
             Self::FlowPhp => "Flow-PHP - Type providers for flow-php/etl package",
             Self::PsrContainer => "PSR-11 Container - Type providers for psr/container package",
+            Self::Doctrine => "Doctrine ORM - Type providers and mapping checks for doctrine/orm package",
         }
     }
 }
@@ -846,6 +850,10 @@ fn detect_analyzer_plugins_from_composer(composer: &ComposerPackage) -> Vec<Anal
         plugins.push(AnalyzerPlugin::FlowPhp);
     }
 
+    if has_package(composer, "doctrine/orm") {
+        plugins.push(AnalyzerPlugin::Doctrine);
+    }
+
     plugins
 }
 
@@ -931,7 +939,7 @@ fn prompt_for_integrations(theme: &ColorfulTheme) -> Result<Vec<Integration>, Er
 }
 
 fn prompt_for_analyzer_plugins(theme: &ColorfulTheme) -> Result<Vec<AnalyzerPlugin>, Error> {
-    let items = &[AnalyzerPlugin::Psl, AnalyzerPlugin::FlowPhp, AnalyzerPlugin::PsrContainer];
+    let items = &[AnalyzerPlugin::Psl, AnalyzerPlugin::FlowPhp, AnalyzerPlugin::PsrContainer, AnalyzerPlugin::Doctrine];
 
     let descriptions: Vec<&str> = items.iter().map(|p| p.description()).collect();
 
@@ -1066,7 +1074,12 @@ mod tests {
     #[test]
     fn test_generated_config_parses_with_all_options() {
         let settings = InitializationAnalyzerSettings {
-            plugins: vec![AnalyzerPlugin::Psl, AnalyzerPlugin::FlowPhp, AnalyzerPlugin::PsrContainer],
+            plugins: vec![
+                AnalyzerPlugin::Psl,
+                AnalyzerPlugin::FlowPhp,
+                AnalyzerPlugin::PsrContainer,
+                AnalyzerPlugin::Doctrine,
+            ],
             find_unused_definitions: true,
             find_unused_expressions: true,
             analyze_dead_code: true,
@@ -1178,6 +1191,7 @@ mod tests {
         assert_eq!(AnalyzerPlugin::Psl.to_string(), "psl");
         assert_eq!(AnalyzerPlugin::FlowPhp.to_string(), "flow-php");
         assert_eq!(AnalyzerPlugin::PsrContainer.to_string(), "psr-container");
+        assert_eq!(AnalyzerPlugin::Doctrine.to_string(), "doctrine");
     }
 
     #[test]
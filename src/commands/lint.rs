@@ -27,6 +27,7 @@
 //! by ignoring pre-existing issues while catching new ones. See [`BaselineReportingArgs`]
 //! for baseline options.
 
+use std::path::Path;
 use std::path::PathBuf;
 use std::process::ExitCode;
 use std::sync::Arc;
@@ -36,12 +37,21 @@ use clap::ColorChoice;
 use clap::Parser;
 use colored::Colorize;
 
+use mago_allocator::LocalArena;
+use mago_database::Database;
 use mago_database::DatabaseReader;
+use mago_database::file::File;
+use mago_database::file::FileId;
 use mago_linter::registry::RuleRegistry;
 use mago_linter::rule::AnyRule;
+use mago_linter::rule::LintRule;
+use mago_linter::rule::PsrAutoloadFix;
+use mago_linter::rule::PsrAutoloadRule;
 use mago_linter::rule_meta::RuleEntry;
 use mago_orchestrator::service::lint::LintMode;
+use mago_reporting::IssueCollection;
 use mago_reporting::Level;
+use mago_syntax::parser::parse_file_with_settings;
 
 use crate::commands::args::baseline_reporting::BaselineReportingArgs;
 use crate::commands::args::substitution::SubstitutionArgs;
@@ -236,6 +246,9 @@ impl LintCommand {
         let mut orchestrator = create_orchestrator(&configuration, color_choice, self.pedantic, true, false);
         orchestrator.add_exclude_patterns(configuration.linter.excludes.iter());
         orchestrator.add_exclude_patterns(substitution_excludes.iter());
+        if configuration.twig.enabled {
+            orchestrator.include_twig_templates();
+        }
         for substitution in &substitutions {
             orchestrator.config.paths.push(substitution.temporary.to_string_lossy().into_owned());
         }
@@ -341,11 +354,37 @@ impl LintCommand {
             self.staged || !self.path.is_empty() || self.stdin_input,
         );
 
+        let reporting = &self.baseline_reporting.reporting;
+        let mut misplaced_files = if reporting.fix
+            && !reporting.dry_run
+            && configuration.linter.rules.psr_autoload.config.fix == PsrAutoloadFix::Move
+        {
+            find_misplaced_files(&configuration, &database, &issues)
+        } else {
+            vec![]
+        };
+
+        // Moving a file is at least as risky as rewriting its namespace, which is potentially unsafe.
+        if !misplaced_files.is_empty() && !reporting.r#unsafe && !reporting.potentially_unsafe {
+            tracing::warn!(
+                "Skipped moving {} misplaced files. Use `--potentially-unsafe` or `--unsafe` to move them.",
+                misplaced_files.len()
+            );
+
+            misplaced_files.clear();
+        }
+
         let (exit_code, changed_file_ids) = processor.process_issues(&orchestrator, &mut database, issues)?;
         let report_duration = report_start.map(|s| s.elapsed());
 
-        if self.staged && !changed_file_ids.is_empty() {
-            git::stage_files(&configuration.source.workspace, &database, changed_file_ids)?;
+        let moved_paths = move_misplaced_files(&configuration.source.workspace, &mut database, &misplaced_files);
+
+        if self.staged {
+            if !changed_file_ids.is_empty() {
+                git::stage_files(&configuration.source.workspace, &database, changed_file_ids)?;
+            }
+
+            git::stage_paths(&configuration.source.workspace, &moved_paths)?;
         }
 
         let drop_database_start = trace_enabled.then(Instant::now);
@@ -370,6 +409,94 @@ impl LintCommand {
     }
 }
 
+/// Finds the files reported by `psr-autoload`, and where the autoloader expects them.
+fn find_misplaced_files(
+    configuration: &Configuration,
+    database: &Database<'_>,
+    issues: &IssueCollection,
+) -> Vec<(FileId, String)> {
+    let config = &configuration.linter.rules.psr_autoload.config;
+    let arena = LocalArena::new();
+
+    let mut misplaced_files = vec![];
+    for issue in issues.iter() {
+        if issue.code.as_deref() != Some(PsrAutoloadRule::meta().code) {
+            continue;
+        }
+
+        let Some(span) = issue.primary_span() else {
+            continue;
+        };
+
+        let Ok(file) = database.get_ref(&span.file_id) else {
+            continue;
+        };
+
+        let program = parse_file_with_settings(&arena, file, configuration.parser.to_settings());
+        if let Some(destination) = config.destination(&String::from_utf8_lossy(&file.name), program) {
+            misplaced_files.push((span.file_id, destination));
+        }
+    }
+
+    misplaced_files
+}
+
+/// Moves the files reported by `psr-autoload` to where the autoloader expects them.
+///
+/// Files are never moved over an existing file. Moved files are renamed in the database as well,
+/// and the paths they were moved from and to are returned, relative to the workspace.
+fn move_misplaced_files(
+    workspace: &Path,
+    database: &mut Database<'_>,
+    misplaced_files: &[(FileId, String)],
+) -> Vec<PathBuf> {
+    let mut moved_paths = vec![];
+    for (file_id, destination) in misplaced_files {
+        let Ok(file) = database.get_ref(file_id) else {
+            continue;
+        };
+
+        let Some(path) = file.path.clone() else {
+            continue;
+        };
+
+        let file_name = PathBuf::from(String::from_utf8_lossy(&file.name).as_ref());
+        let file_type = file.file_type;
+        let destination_path = workspace.join(destination);
+        if destination_path.exists() {
+            tracing::warn!("Not moving `{}`, `{}` already exists.", path.display(), destination_path.display());
+
+            continue;
+        }
+
+        let result = match destination_path.parent() {
+            Some(parent) => std::fs::create_dir_all(parent).and_then(|()| std::fs::rename(&path, &destination_path)),
+            None => std::fs::rename(&path, &destination_path),
+        };
+
+        if let Err(error) = result {
+            tracing::warn!("Failed to move `{}`: {error}", path.display());
+
+            continue;
+        }
+
+        tracing::info!("Moved `{}` to `{}`.", path.display(), destination_path.display());
+
+        database.delete(*file_id);
+        match File::read(workspace, &destination_path, file_type) {
+            Ok(moved_file) => {
+                database.add(moved_file);
+            }
+            Err(error) => tracing::warn!("Failed to read `{}`: {error}", destination_path.display()),
+        }
+
+        moved_paths.push(file_name);
+        moved_paths.push(PathBuf::from(destination));
+    }
+
+    moved_paths
+}
+
 /// Displays detailed documentation for a specific linting rule.
 ///
 /// This function shows comprehensive information about a rule including its
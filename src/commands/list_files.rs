@@ -113,8 +113,18 @@ impl ListFilesCommand {
         let mut orchestrator = create_orchestrator(&configuration, color_choice, false, true, false);
         if let Some(command) = self.command {
             match command {
-                Command::Linter => orchestrator.add_exclude_patterns(configuration.linter.excludes.iter()),
-                Command::Formatter => orchestrator.add_exclude_patterns(configuration.formatter.excludes.iter()),
+                Command::Linter => {
+                    orchestrator.add_exclude_patterns(configuration.linter.excludes.iter());
+                    if configuration.twig.enabled {
+                        orchestrator.include_twig_templates();
+                    }
+                }
+                Command::Formatter => {
+                    orchestrator.add_exclude_patterns(configuration.formatter.excludes.iter());
+                    if configuration.twig.enabled {
+                        orchestrator.include_twig_templates();
+                    }
+                }
                 Command::Analyzer => orchestrator.add_exclude_patterns(configuration.analyzer.excludes.iter()),
                 Command::Guard => orchestrator.add_exclude_patterns(configuration.guard.excludes.iter()),
             }
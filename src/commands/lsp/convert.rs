@@ -0,0 +1,270 @@
+//! Conversions between Mago's data model and Language Server Protocol structures.
+//!
+//! The protocol addresses documents by `file://` URI and positions by zero-based
+//! line and UTF-16 code unit, while Mago works with logical file names and byte
+//! offsets. The helpers in this module translate between the two.
+
+use std::fmt::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use serde_json::Value;
+use serde_json::json;
+
+use mago_database::file::File;
+use mago_reporting::Annotation;
+use mago_reporting::Issue;
+use mago_reporting::Level;
+use mago_text_edit::Safety;
+use mago_text_edit::TextEdit;
+
+/// Characters that are left untouched when percent-encoding a path into a URI.
+const URI_SAFE_CHARACTERS: &[u8] = b"-._~/:";
+
+/// Converts a `file://` URI into a filesystem path.
+///
+/// Returns `None` for URIs using any other scheme.
+pub fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    let rest = uri.strip_prefix("file://")?;
+    // Skip the (usually empty) authority component.
+    let path = &rest[rest.find('/')?..];
+    let decoded = percent_decode(path);
+
+    #[cfg(windows)]
+    let decoded = match decoded.as_bytes() {
+        [b'/', drive, b':', ..] if drive.is_ascii_alphabetic() => decoded[1..].replace('/', "\\"),
+        _ => decoded,
+    };
+
+    Some(PathBuf::from(decoded))
+}
+
+/// Converts a filesystem path into a `file://` URI.
+pub fn path_to_uri(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let mut uri = String::with_capacity(path.len() + 8);
+
+    uri.push_str("file://");
+    if !path.starts_with('/') {
+        uri.push('/');
+    }
+
+    for byte in path.bytes() {
+        if byte.is_ascii_alphanumeric() || URI_SAFE_CHARACTERS.contains(&byte) {
+            uri.push(byte as char);
+        } else {
+            let _ = write!(uri, "%{byte:02X}");
+        }
+    }
+
+    uri
+}
+
+/// Converts a byte offset within `file` into an LSP position.
+pub fn offset_to_position(file: &File, offset: u32) -> Value {
+    let offset = offset.min(file.size);
+    let line = file.line_number(offset);
+    let line_start = file.get_line_start_offset(line).unwrap_or(0);
+    let prefix = &file.contents[line_start as usize..offset as usize];
+    let character: usize = String::from_utf8_lossy(prefix).chars().map(char::len_utf16).sum();
+
+    json!({ "line": line, "character": character })
+}
+
+/// Converts an LSP position into a byte offset within `file`.
+///
+/// Positions past the end of a line are clamped to the end of that line, and
+/// positions past the end of the file are clamped to the end of the file.
+pub fn position_to_offset(file: &File, position: &Value) -> u32 {
+    let line = position["line"].as_u64().unwrap_or(0) as u32;
+    let character = position["character"].as_u64().unwrap_or(0) as usize;
+
+    let Some(line_start) = file.get_line_start_offset(line) else {
+        return file.size;
+    };
+
+    let line_end = file.get_line_end_offset(line).unwrap_or(file.size);
+    let line_text = &file.contents[line_start as usize..line_end as usize];
+
+    let mut units = 0;
+    let mut offset = line_start;
+    for character_value in String::from_utf8_lossy(line_text).chars() {
+        if units >= character || character_value == '\n' || character_value == '\r' {
+            break;
+        }
+
+        units += character_value.len_utf16();
+        offset += character_value.len_utf8() as u32;
+    }
+
+    offset.min(line_end)
+}
+
+/// Converts a byte range within `file` into an LSP range.
+pub fn range(file: &File, start: u32, end: u32) -> Value {
+    json!({ "start": offset_to_position(file, start), "end": offset_to_position(file, end) })
+}
+
+/// Returns the range covering the whole contents of `file`.
+pub fn full_range(file: &File) -> Value {
+    range(file, 0, file.size)
+}
+
+/// Maps an issue level to an LSP diagnostic severity.
+pub const fn severity(level: Level) -> u8 {
+    match level {
+        Level::Error => 1,
+        Level::Warning => 2,
+        Level::Note => 3,
+        Level::Help => 4,
+    }
+}
+
+/// Converts an issue into an LSP diagnostic anchored in `file`.
+///
+/// Secondary annotations are exposed as related information; `locate` is used to
+/// resolve the file an annotation points into, since it may differ from `file`.
+pub fn issue_to_diagnostic<'a>(
+    issue: &Issue,
+    file: &File,
+    primary: &Annotation,
+    locate: impl Fn(&Annotation) -> Option<(String, &'a File)>,
+) -> Value {
+    let mut message = issue.message.clone();
+    if let Some(primary_message) = &primary.message
+        && !primary_message.is_empty()
+    {
+        message.push_str("\n\n");
+        message.push_str(primary_message);
+    }
+
+    for note in &issue.notes {
+        message.push_str("\n\nNote: ");
+        message.push_str(note);
+    }
+
+    if let Some(help) = &issue.help {
+        message.push_str("\n\nHelp: ");
+        message.push_str(help);
+    }
+
+    let related_information: Vec<Value> = issue
+        .annotations
+        .iter()
+        .filter(|annotation| annotation.kind.is_secondary())
+        .filter_map(|annotation| {
+            let (uri, annotation_file) = locate(annotation)?;
+
+            Some(json!({
+                "location": {
+                    "uri": uri,
+                    "range": range(annotation_file, annotation.span.start.offset, annotation.span.end.offset),
+                },
+                "message": annotation.message.clone().unwrap_or_default(),
+            }))
+        })
+        .collect();
+
+    let mut diagnostic = json!({
+        "range": range(file, primary.span.start.offset, primary.span.end.offset),
+        "severity": severity(issue.level),
+        "source": "mago",
+        "message": message,
+    });
+
+    if let Some(code) = &issue.code {
+        diagnostic["code"] = json!(code);
+        if let Some(link) = &issue.link {
+            diagnostic["codeDescription"] = json!({ "href": link });
+        }
+    }
+
+    if !related_information.is_empty() {
+        diagnostic["relatedInformation"] = Value::Array(related_information);
+    }
+
+    diagnostic
+}
+
+/// Converts a text edit into an LSP text edit against `file`.
+pub fn text_edit(file: &File, edit: &TextEdit) -> Value {
+    json!({
+        "range": range(file, edit.range.start, edit.range.end),
+        "newText": String::from_utf8_lossy(&edit.new_text),
+    })
+}
+
+/// Returns a human readable label for the safety of a fix.
+pub const fn safety_label(safety: Safety) -> &'static str {
+    match safety {
+        Safety::Safe => "safe",
+        Safety::PotentiallyUnsafe => "potentially unsafe",
+        _ => "unsafe",
+    }
+}
+
+/// Decodes `%XX` escape sequences in a URI component.
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%'
+            && let Some(hex) = input.get(index + 1..index + 3)
+            && let Ok(byte) = u8::from_str_radix(hex, 16)
+        {
+            decoded.push(byte);
+            index += 3;
+            continue;
+        }
+
+        decoded.push(bytes[index]);
+        index += 1;
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use mago_database::file::FileType;
+
+    use super::*;
+
+    fn file(contents: &'static str) -> File {
+        File::new(Cow::Borrowed(b"test.php"), FileType::Host, None, Cow::Borrowed(contents.as_bytes()))
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn converts_between_uris_and_paths() {
+        let path = Path::new("/home/user/my project/src/Foo.php");
+        let uri = path_to_uri(path);
+
+        assert_eq!(uri, "file:///home/user/my%20project/src/Foo.php");
+        assert_eq!(uri_to_path(&uri).as_deref(), Some(path));
+        assert_eq!(uri_to_path("untitled:Untitled-1"), None);
+    }
+
+    #[test]
+    fn converts_positions_using_utf16_columns() {
+        let file = file("<?php\n$a = 'é😀';\n");
+
+        let offset = "<?php\n$a = 'é😀".len() as u32;
+        let position = offset_to_position(&file, offset);
+
+        assert_eq!(position, json!({ "line": 1, "character": 9 }));
+        assert_eq!(position_to_offset(&file, &position), offset);
+    }
+
+    #[test]
+    fn clamps_positions_past_the_end_of_a_line() {
+        let file = file("<?php\necho 1;\n");
+
+        assert_eq!(position_to_offset(&file, &json!({ "line": 0, "character": 99 })), 5);
+        assert_eq!(position_to_offset(&file, &json!({ "line": 9, "character": 0 })), file.size);
+    }
+}
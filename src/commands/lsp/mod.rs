@@ -0,0 +1,144 @@
+//! Language server command implementation.
+//!
+//! This module implements the `mago lsp` command, which runs Mago as a Language
+//! Server Protocol server over standard input and output. Editors launch the
+//! command and receive diagnostics, formatting, and quick fixes as the user types.
+//!
+//! # Features
+//!
+//! - **Diagnostics**: Analyzer and linter issues for every open document, refreshed
+//!   incrementally on each change
+//! - **Formatting**: `textDocument/formatting` and `textDocument/rangeFormatting` using the
+//!   configured formatter settings
+//! - **Code Actions**: Quick fixes built from the edits attached to reported issues
+//! - **Hover**: The inferred type of the expression under the cursor
+//!
+//! # Unsaved Buffers
+//!
+//! Open documents are overlaid on the project database, so the analyzer sees the
+//! contents of the editor rather than the file on disk. Closing a document restores
+//! the on-disk contents. Changes made outside the editor are picked up through
+//! `workspace/didChangeWatchedFiles` when the client supports it.
+//!
+//! # Output
+//!
+//! Standard output is reserved for protocol messages; logs are written to standard
+//! error, where editors usually surface them in an output panel.
+
+use std::process::ExitCode;
+
+use clap::ColorChoice;
+use clap::Parser;
+
+use mago_prelude::Prelude;
+use mago_reporting::CompiledIgnoreSet;
+
+use crate::commands::lsp::server::Flow;
+use crate::commands::lsp::server::Server;
+use crate::commands::lsp::transport::read_message;
+use crate::config::Configuration;
+use crate::consts::PRELUDE_BYTES;
+use crate::error::Error;
+use crate::extensions::initialize_external_analyzer;
+use crate::utils::create_orchestrator;
+
+mod convert;
+mod server;
+mod transport;
+
+/// Command for running Mago as a language server.
+///
+/// The server communicates over standard input and output using the Language
+/// Server Protocol. It loads and analyzes the workspace once at startup, then
+/// keeps the analysis up to date incrementally as documents change.
+#[derive(Parser, Debug)]
+#[command(
+    name = "lsp",
+    about = "Run Mago as a language server over standard input and output.",
+    long_about = r#"
+Run Mago as a Language Server Protocol server.
+
+The server speaks LSP over standard input and output and provides analyzer and
+linter diagnostics, document formatting, quick fixes, and type information on
+hover. Unsaved editor buffers are analyzed as-is.
+
+Configure your editor to launch `mago lsp` in the project root.
+"#
+)]
+pub struct LspCommand {
+    /// Communicate over standard input and output.
+    ///
+    /// This is the only supported transport and is always used; the flag is
+    /// accepted for compatibility with clients that pass it unconditionally.
+    #[arg(long, hide = true)]
+    pub stdio: bool,
+
+    /// Disable the use of stubs for built-in PHP symbols and popular libraries.
+    #[arg(long, default_value_t = false)]
+    pub no_stubs: bool,
+
+    /// Skip the `ignore` entries from the analyzer configuration.
+    #[arg(long, default_value_t = false)]
+    pub skip_ignores: bool,
+}
+
+impl LspCommand {
+    /// Executes the language server until the client sends `exit`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the workspace cannot be loaded or analyzed, or if
+    /// communication with the client fails.
+    pub fn execute(self, configuration: Configuration, color_choice: ColorChoice) -> Result<ExitCode, Error> {
+        let Prelude { database: prelude_database, metadata, symbol_references } = if self.no_stubs {
+            Prelude::default()
+        } else {
+            Prelude::decode(PRELUDE_BYTES).expect("Failed to decode embedded prelude")
+        };
+
+        let mut orchestrator = create_orchestrator(&configuration, color_choice, false, false, false);
+        orchestrator.add_exclude_patterns(configuration.analyzer.excludes.iter());
+
+        if let Some(external_analyzer) = initialize_external_analyzer(
+            &configuration.extension_hosts,
+            configuration.php_version,
+            configuration.threads,
+            &configuration.analyzer.plugins,
+            configuration.analyzer.disable_default_plugins,
+        )
+        .map_err(|error| mago_orchestrator::OrchestratorError::General(error.to_string()))?
+        {
+            orchestrator.set_external_analyzer(external_analyzer);
+        }
+
+        tracing::info!("Loading workspace {}...", configuration.source.workspace.display());
+
+        let database =
+            orchestrator.load_database(&configuration.source.workspace, true, Some(prelude_database), None)?;
+
+        let mut service =
+            orchestrator.get_incremental_analysis_service(database.read_only(), metadata, symbol_references);
+        let analysis_result = service.analyze()?;
+
+        let ignore_set = if self.skip_ignores {
+            CompiledIgnoreSet::default()
+        } else {
+            CompiledIgnoreSet::compile(&configuration.analyzer.ignore, configuration.source.glob.to_database_settings())
+        };
+
+        tracing::info!("Analyzed {} files, waiting for client messages.", service.tracked_file_count());
+
+        let mut server =
+            Server::new(&configuration, &orchestrator, database, service, analysis_result.issues, ignore_set)?;
+
+        let mut reader = std::io::stdin().lock();
+        while let Some(message) = read_message(&mut reader).map_err(Error::LanguageServer)? {
+            if let Flow::Exit { graceful } = server.handle(message)? {
+                return Ok(if graceful { ExitCode::SUCCESS } else { ExitCode::FAILURE });
+            }
+        }
+
+        // The client went away without the `shutdown`/`exit` handshake.
+        Ok(ExitCode::FAILURE)
+    }
+}
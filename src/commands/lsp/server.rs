@@ -0,0 +1,678 @@
+//! Language server state and request dispatch.
+//!
+//! The [`Server`] owns the project [`Database`] and an [`IncrementalAnalysisService`]
+//! built on top of it. Open documents are overlaid on the database, so unsaved
+//! buffers are what the analyzer, linter, and formatter see; closing a document
+//! restores the on-disk contents.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::io::StdoutLock;
+use std::path::Path;
+use std::path::PathBuf;
+
+use serde_json::Value;
+use serde_json::json;
+
+use mago_codex::ttype::TType;
+use mago_database::Database;
+use mago_database::DatabaseReader;
+use mago_database::file::File;
+use mago_database::file::FileId;
+use mago_database::file::FileType;
+use mago_database::matcher::ExclusionMatcher;
+use mago_orchestrator::Orchestrator;
+use mago_orchestrator::service::format::FileFormatStatus;
+use mago_orchestrator::service::incremental_analysis::IncrementalAnalysisService;
+use mago_orchestrator::service::lint::LintMode;
+use mago_orchestrator::service::lint::LintService;
+use mago_reporting::CompiledIgnoreSet;
+use mago_reporting::Issue;
+use mago_reporting::IssueCollection;
+use mago_text_edit::Safety;
+use mago_text_edit::TextRange;
+
+use crate::commands::lsp::convert;
+use crate::commands::lsp::transport::write_message;
+use crate::config::Configuration;
+use crate::error::Error;
+
+/// JSON-RPC error code for requests the server does not implement.
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// JSON-RPC error code for requests sent before the `initialize` handshake.
+const SERVER_NOT_INITIALIZED: i64 = -32002;
+
+/// Text document sync kind: documents are synced by always sending the full content.
+const TEXT_DOCUMENT_SYNC_FULL: u8 = 1;
+
+/// File change type sent by the client when a watched file is deleted.
+const FILE_CHANGE_DELETED: u64 = 3;
+
+/// What the main loop should do after a message has been handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flow {
+    /// Keep reading messages.
+    Continue,
+    /// The client sent `exit`; stop with the given success state.
+    Exit { graceful: bool },
+}
+
+/// A document the client currently has open.
+#[derive(Debug)]
+struct Document {
+    /// The identifier of the document in the database.
+    id: FileId,
+    /// The logical name of the document, relative to the workspace.
+    name: Vec<u8>,
+    /// The canonical path of the document on disk.
+    path: PathBuf,
+    /// The unsaved contents of the document as last sent by the client.
+    contents: Vec<u8>,
+    /// Whether the document is part of the analyzed source set.
+    tracked: bool,
+}
+
+impl Document {
+    fn to_file(&self) -> File {
+        File::new(
+            Cow::Owned(self.name.clone()),
+            FileType::Host,
+            Some(self.path.clone()),
+            Cow::Owned(self.contents.clone()),
+        )
+    }
+}
+
+/// The language server state.
+pub struct Server<'cfg> {
+    configuration: &'cfg Configuration,
+    orchestrator: &'cfg Orchestrator<'cfg>,
+    database: Database<'cfg>,
+    analysis: IncrementalAnalysisService,
+    linter: LintService,
+    ignore_set: CompiledIgnoreSet,
+    source_excludes: ExclusionMatcher<&'cfg str>,
+    linter_excludes: ExclusionMatcher<&'cfg str>,
+    formatter_excludes: ExclusionMatcher<&'cfg str>,
+    writer: StdoutLock<'static>,
+    documents: HashMap<String, Document>,
+    analysis_issues: IssueCollection,
+    lint_issues: HashMap<String, IssueCollection>,
+    published: HashSet<String>,
+    initialized: bool,
+    shutdown_requested: bool,
+    supports_dynamic_watchers: bool,
+    next_request_id: u64,
+}
+
+impl<'cfg> Server<'cfg> {
+    /// Creates a new server over an already analyzed database.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if one of the configured exclusion patterns fails to compile.
+    pub fn new(
+        configuration: &'cfg Configuration,
+        orchestrator: &'cfg Orchestrator<'cfg>,
+        database: Database<'cfg>,
+        analysis: IncrementalAnalysisService,
+        analysis_issues: IssueCollection,
+        ignore_set: CompiledIgnoreSet,
+    ) -> Result<Self, Error> {
+        let glob_settings = configuration.source.glob.to_database_settings();
+        let linter = orchestrator.get_lint_service(database.read_only());
+
+        Ok(Self {
+            configuration,
+            orchestrator,
+            database,
+            analysis,
+            linter,
+            ignore_set,
+            source_excludes: ExclusionMatcher::compile(
+                configuration.source.excludes.iter().chain(configuration.analyzer.excludes.iter()).map(String::as_str),
+                glob_settings,
+            )?,
+            linter_excludes: ExclusionMatcher::compile(
+                configuration.linter.excludes.iter().map(String::as_str),
+                glob_settings,
+            )?,
+            formatter_excludes: ExclusionMatcher::compile(
+                configuration.formatter.excludes.iter().map(String::as_str),
+                glob_settings,
+            )?,
+            writer: std::io::stdout().lock(),
+            documents: HashMap::new(),
+            analysis_issues,
+            lint_issues: HashMap::new(),
+            published: HashSet::new(),
+            initialized: false,
+            shutdown_requested: false,
+            supports_dynamic_watchers: false,
+            next_request_id: 0,
+        })
+    }
+
+    /// Handles a single message received from the client.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a response or notification cannot be written.
+    pub fn handle(&mut self, message: Value) -> Result<Flow, Error> {
+        let Some(method) = message["method"].as_str().map(str::to_owned) else {
+            // A response to one of our own requests; nothing to do.
+            return Ok(Flow::Continue);
+        };
+
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+        let Some(id) = message.get("id").cloned() else {
+            return self.handle_notification(&method, &params);
+        };
+
+        let result = match method.as_str() {
+            "initialize" => Ok(self.initialize(&params)),
+            _ if !self.initialized => Err((SERVER_NOT_INITIALIZED, "The server has not been initialized.".to_owned())),
+            "shutdown" => {
+                self.shutdown_requested = true;
+
+                Ok(Value::Null)
+            }
+            "textDocument/formatting" | "textDocument/rangeFormatting" => Ok(self.formatting(&params)),
+            "textDocument/codeAction" => Ok(self.code_actions(&params)),
+            "textDocument/hover" => Ok(self.hover(&params)),
+            _ => Err((METHOD_NOT_FOUND, format!("Method `{method}` is not supported."))),
+        };
+
+        let response = match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err((code, message)) => {
+                json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+            }
+        };
+
+        self.send(&response)?;
+
+        Ok(Flow::Continue)
+    }
+
+    fn handle_notification(&mut self, method: &str, params: &Value) -> Result<Flow, Error> {
+        if method == "exit" {
+            return Ok(Flow::Exit { graceful: self.shutdown_requested });
+        }
+
+        if !self.initialized {
+            return Ok(Flow::Continue);
+        }
+
+        match method {
+            "initialized" => self.register_file_watchers()?,
+            "textDocument/didOpen" => {
+                let document = &params["textDocument"];
+                if let (Some(uri), Some(text)) = (document["uri"].as_str(), document["text"].as_str()) {
+                    self.open_document(uri, text.as_bytes().to_vec())?;
+                }
+            }
+            "textDocument/didChange" => {
+                let uri = params["textDocument"]["uri"].as_str();
+                let text = params["contentChanges"].as_array().and_then(|changes| changes.last());
+                if let (Some(uri), Some(text)) = (uri, text.and_then(|change| change["text"].as_str())) {
+                    self.change_document(uri, text.as_bytes().to_vec())?;
+                }
+            }
+            "textDocument/didClose" => {
+                if let Some(uri) = params["textDocument"]["uri"].as_str() {
+                    self.close_document(uri)?;
+                }
+            }
+            "workspace/didChangeWatchedFiles" => {
+                if let Some(changes) = params["changes"].as_array() {
+                    self.watched_files_changed(changes)?;
+                }
+            }
+            _ => {}
+        }
+
+        Ok(Flow::Continue)
+    }
+
+    fn initialize(&mut self, params: &Value) -> Value {
+        self.initialized = true;
+        self.supports_dynamic_watchers =
+            params["capabilities"]["workspace"]["didChangeWatchedFiles"]["dynamicRegistration"].as_bool() == Some(true);
+
+        json!({
+            "capabilities": {
+                "textDocumentSync": {
+                    "openClose": true,
+                    "change": TEXT_DOCUMENT_SYNC_FULL,
+                    "save": false,
+                },
+                "documentFormattingProvider": true,
+                "documentRangeFormattingProvider": true,
+                "codeActionProvider": { "codeActionKinds": ["quickfix"] },
+                "hoverProvider": true,
+            },
+            "serverInfo": {
+                "name": "mago",
+                "version": env!("CARGO_PKG_VERSION"),
+            },
+        })
+    }
+
+    /// Asks the client to notify us about changes to source files made outside the editor.
+    fn register_file_watchers(&mut self) -> Result<(), Error> {
+        if !self.supports_dynamic_watchers {
+            return Ok(());
+        }
+
+        let watchers: Vec<Value> = self
+            .configuration
+            .source
+            .extensions
+            .iter()
+            .map(|extension| json!({ "globPattern": format!("**/*.{extension}") }))
+            .collect();
+
+        self.next_request_id += 1;
+        self.send(&json!({
+            "jsonrpc": "2.0",
+            "id": format!("mago-{}", self.next_request_id),
+            "method": "client/registerCapability",
+            "params": {
+                "registrations": [{
+                    "id": "mago-watched-files",
+                    "method": "workspace/didChangeWatchedFiles",
+                    "registerOptions": { "watchers": watchers },
+                }],
+            },
+        }))
+    }
+
+    fn open_document(&mut self, uri: &str, contents: Vec<u8>) -> Result<(), Error> {
+        let Some(path) = convert::uri_to_path(uri) else {
+            return Ok(());
+        };
+
+        let path = path.canonicalize().unwrap_or(path);
+        let name = self.logical_name(&path);
+        let id = FileId::new(&name);
+        let tracked = self.database.get_ref(&id).is_ok_and(|file| file.file_type == FileType::Host)
+            || self.is_source_file(&path, &name);
+
+        self.documents.insert(uri.to_owned(), Document { id, name, path, contents, tracked });
+        self.sync_document(uri)
+    }
+
+    fn change_document(&mut self, uri: &str, contents: Vec<u8>) -> Result<(), Error> {
+        let Some(document) = self.documents.get_mut(uri) else {
+            return Ok(());
+        };
+
+        document.contents = contents;
+        self.sync_document(uri)
+    }
+
+    fn close_document(&mut self, uri: &str) -> Result<(), Error> {
+        let Some(document) = self.documents.remove(uri) else {
+            return Ok(());
+        };
+
+        self.lint_issues.remove(uri);
+        if document.tracked {
+            match std::fs::read(&document.path) {
+                Ok(contents) => {
+                    self.database.update(document.id, Cow::Owned(contents));
+                }
+                Err(_) => {
+                    self.database.delete(document.id);
+                }
+            }
+
+            self.reanalyze(&[document.id]);
+        }
+
+        self.publish_diagnostics()
+    }
+
+    fn watched_files_changed(&mut self, changes: &[Value]) -> Result<(), Error> {
+        let mut changed = Vec::new();
+
+        for change in changes {
+            let Some(uri) = change["uri"].as_str() else {
+                continue;
+            };
+
+            // Open documents are owned by the client; their buffer wins over the disk.
+            if self.documents.contains_key(uri) {
+                continue;
+            }
+
+            let Some(path) = convert::uri_to_path(uri) else {
+                continue;
+            };
+
+            let name = self.logical_name(&path);
+            let id = FileId::new(&name);
+
+            if change["type"].as_u64() == Some(FILE_CHANGE_DELETED) {
+                if self.database.delete(id) {
+                    changed.push(id);
+                }
+
+                continue;
+            }
+
+            let path = path.canonicalize().unwrap_or(path);
+            let Ok(contents) = std::fs::read(&path) else {
+                continue;
+            };
+
+            if self.database.get_ref(&id).is_ok() {
+                self.database.update(id, Cow::Owned(contents));
+            } else if self.is_source_file(&path, &name) {
+                self.database.add(File::new(Cow::Owned(name), FileType::Host, Some(path), Cow::Owned(contents)));
+            } else {
+                continue;
+            }
+
+            changed.push(id);
+        }
+
+        if changed.is_empty() {
+            return Ok(());
+        }
+
+        self.reanalyze(&changed);
+        self.publish_diagnostics()
+    }
+
+    /// Pushes the buffer of an open document into the database, then re-analyzes,
+    /// re-lints, and republishes diagnostics.
+    fn sync_document(&mut self, uri: &str) -> Result<(), Error> {
+        let Some(document) = self.documents.get(uri) else {
+            return Ok(());
+        };
+
+        let file = document.to_file();
+        if !self.linter_excludes.is_match(&String::from_utf8_lossy(&document.name)) {
+            let issues = self.linter.lint_file(&file, LintMode::Full, None, false);
+            self.lint_issues.insert(uri.to_owned(), issues);
+        }
+
+        if document.tracked {
+            let id = document.id;
+            if self.database.get_ref(&id).is_ok() {
+                self.database.update(id, file.contents);
+            } else {
+                self.database.add(file);
+            }
+
+            self.reanalyze(&[id]);
+        }
+
+        self.publish_diagnostics()
+    }
+
+    fn reanalyze(&mut self, changed: &[FileId]) {
+        self.analysis.update_database(self.database.read_only());
+
+        match self.analysis.analyze_incremental(Some(changed)) {
+            Ok(result) => self.analysis_issues = result.issues,
+            Err(error) => tracing::error!("Failed to re-analyze the workspace: {error}"),
+        }
+    }
+
+    /// Collects the diagnostics of every open document, after applying the configured ignore rules.
+    fn document_issues(&self, uri: &str, document: &Document) -> IssueCollection {
+        let mut issues = IssueCollection::new();
+        if document.tracked {
+            issues.extend(
+                self.analysis_issues.iter().filter(|issue| primary_file_id(issue) == Some(document.id)).cloned(),
+            );
+        }
+
+        if let Some(lint_issues) = self.lint_issues.get(uri) {
+            issues.extend(lint_issues.iter().cloned());
+        }
+
+        issues.filter_out_ignored(&self.ignore_set, |file_id| {
+            self.database.get_ref(&file_id).ok().map(|file| String::from_utf8_lossy(&file.name).into_owned())
+        });
+
+        issues
+    }
+
+    fn publish_diagnostics(&mut self) -> Result<(), Error> {
+        let mut notifications = Vec::with_capacity(self.documents.len());
+        for (uri, document) in &self.documents {
+            let file = document.to_file();
+            let diagnostics: Vec<Value> = self
+                .document_issues(uri, document)
+                .iter()
+                .filter_map(|issue| {
+                    let primary = issue.annotations.iter().find(|annotation| annotation.kind.is_primary())?;
+
+                    Some(convert::issue_to_diagnostic(issue, &file, primary, |annotation| {
+                        self.locate(annotation.span.file_id, document, &file)
+                    }))
+                })
+                .collect();
+
+            notifications.push((uri.clone(), diagnostics));
+        }
+
+        let stale: Vec<String> =
+            self.published.iter().filter(|uri| !self.documents.contains_key(*uri)).cloned().collect();
+        for uri in stale {
+            notifications.push((uri, Vec::new()));
+        }
+
+        self.published.clear();
+        for (uri, diagnostics) in notifications {
+            if !diagnostics.is_empty() {
+                self.published.insert(uri.clone());
+            }
+
+            self.send(&json!({
+                "jsonrpc": "2.0",
+                "method": "textDocument/publishDiagnostics",
+                "params": { "uri": uri, "diagnostics": diagnostics },
+            }))?;
+        }
+
+        Ok(())
+    }
+
+    /// Formats a document, or only the statements enclosing the requested range.
+    fn formatting(&self, params: &Value) -> Value {
+        let Some(document) = params["textDocument"]["uri"].as_str().and_then(|uri| self.documents.get(uri)) else {
+            return Value::Null;
+        };
+
+        if self.formatter_excludes.is_match(&String::from_utf8_lossy(&document.name)) {
+            return json!([]);
+        }
+
+        let file = document.to_file();
+        let status = match params.get("range") {
+            Some(range) => {
+                let start = convert::position_to_offset(&file, &range["start"]);
+                let end = convert::position_to_offset(&file, &range["end"]);
+
+                self.orchestrator.format_file_ranges(&file, &[TextRange::new(start, end)])
+            }
+            None => self.orchestrator.format_file(&file),
+        };
+
+        match status {
+            Ok(FileFormatStatus::Unchanged) => json!([]),
+            Ok(FileFormatStatus::Changed(formatted)) => {
+                json!([{ "range": convert::full_range(&file), "newText": String::from_utf8_lossy(&formatted) }])
+            }
+            Ok(FileFormatStatus::FailedToParse(error)) => {
+                tracing::warn!("Skipping formatting of `{}`: {error}", document.path.display());
+
+                Value::Null
+            }
+            Ok(FileFormatStatus::FailedToParseTemplate(error)) => {
+                tracing::warn!("Skipping formatting of `{}`: {error}", document.path.display());
+
+                Value::Null
+            }
+            Err(error) => {
+                tracing::error!("Failed to format `{}`: {error}", document.path.display());
+
+                Value::Null
+            }
+        }
+    }
+
+    fn code_actions(&self, params: &Value) -> Value {
+        let Some(uri) = params["textDocument"]["uri"].as_str() else {
+            return Value::Null;
+        };
+
+        let Some(document) = self.documents.get(uri) else {
+            return Value::Null;
+        };
+
+        let file = document.to_file();
+        let start = convert::position_to_offset(&file, &params["range"]["start"]);
+        let end = convert::position_to_offset(&file, &params["range"]["end"]);
+
+        let mut actions = Vec::new();
+        for issue in self.document_issues(uri, document).iter() {
+            if issue.edits.is_empty() {
+                continue;
+            }
+
+            let Some(primary) = issue.annotations.iter().find(|annotation| annotation.kind.is_primary()) else {
+                continue;
+            };
+
+            if primary.span.end.offset < start || primary.span.start.offset > end {
+                continue;
+            }
+
+            let mut changes = serde_json::Map::new();
+            let mut safety = Safety::Safe;
+            for (file_id, edits) in &issue.edits {
+                let Some((edit_uri, edit_file)) = self.locate(*file_id, document, &file) else {
+                    continue;
+                };
+
+                safety = edits.iter().map(|edit| edit.safety).fold(safety, Ord::max);
+                changes.insert(
+                    edit_uri,
+                    Value::Array(edits.iter().map(|edit| convert::text_edit(edit_file, edit)).collect()),
+                );
+            }
+
+            if changes.is_empty() {
+                continue;
+            }
+
+            let mut title = match &issue.code {
+                Some(code) => format!("Fix `{code}`: {}", issue.message),
+                None => format!("Fix: {}", issue.message),
+            };
+
+            if safety != Safety::Safe {
+                title = format!("{title} ({})", convert::safety_label(safety));
+            }
+
+            actions.push(json!({
+                "title": title,
+                "kind": "quickfix",
+                "diagnostics": [convert::issue_to_diagnostic(issue, &file, primary, |annotation| {
+                    self.locate(annotation.span.file_id, document, &file)
+                })],
+                "isPreferred": safety == Safety::Safe,
+                "edit": { "changes": changes },
+            }));
+        }
+
+        Value::Array(actions)
+    }
+
+    fn hover(&self, params: &Value) -> Value {
+        let Some(document) = params["textDocument"]["uri"].as_str().and_then(|uri| self.documents.get(uri)) else {
+            return Value::Null;
+        };
+
+        if !document.tracked {
+            return Value::Null;
+        }
+
+        let Ok(file) = self.database.get_ref(&document.id) else {
+            return Value::Null;
+        };
+
+        let offset = convert::position_to_offset(file, &params["position"]);
+        let Some((_, artifacts)) = self.analysis.analyze_file_with_artifacts(document.id) else {
+            return Value::Null;
+        };
+
+        let Some((&(start, end), expression_type)) = artifacts
+            .expression_types
+            .iter()
+            .filter(|((start, end), _)| *start <= offset && offset < *end)
+            .min_by_key(|((start, end), _)| end - start)
+        else {
+            return Value::Null;
+        };
+
+        json!({
+            "contents": {
+                "kind": "markdown",
+                "value": format!("```php\n{}\n```", expression_type.get_id()),
+            },
+            "range": convert::range(file, start, end),
+        })
+    }
+
+    /// Resolves a file referenced by an issue into its URI and contents.
+    ///
+    /// References to the document itself resolve to the open buffer, so ranges line
+    /// up with what the client is displaying.
+    fn locate<'a>(&'a self, file_id: FileId, document: &Document, file: &'a File) -> Option<(String, &'a File)> {
+        if file_id == document.id {
+            return Some((convert::path_to_uri(&document.path), file));
+        }
+
+        let referenced = self.database.get_ref(&file_id).ok()?;
+        let path = referenced.path.as_deref()?;
+
+        Some((convert::path_to_uri(path), referenced))
+    }
+
+    /// Returns the logical name of `path`, relative to the workspace.
+    fn logical_name(&self, path: &Path) -> Vec<u8> {
+        let relative = path.strip_prefix(&self.configuration.source.workspace).unwrap_or(path);
+
+        relative.to_string_lossy().replace('\\', "/").into_bytes()
+    }
+
+    /// Returns `true` if a file at `path` belongs to the analyzed source set.
+    fn is_source_file(&self, path: &Path, name: &[u8]) -> bool {
+        if !path.starts_with(&self.configuration.source.workspace) {
+            return false;
+        }
+
+        let has_source_extension = path.extension().and_then(|extension| extension.to_str()).is_some_and(|extension| {
+            self.configuration.source.extensions.iter().any(|candidate| candidate.eq_ignore_ascii_case(extension))
+        });
+
+        has_source_extension && !self.source_excludes.is_match(&String::from_utf8_lossy(name))
+    }
+
+    fn send(&mut self, message: &Value) -> Result<(), Error> {
+        write_message(&mut self.writer, message).map_err(Error::LanguageServer)
+    }
+}
+
+fn primary_file_id(issue: &Issue) -> Option<FileId> {
+    issue.annotations.iter().find(|annotation| annotation.kind.is_primary()).map(|annotation| annotation.span.file_id)
+}
@@ -0,0 +1,100 @@
+//! Base protocol framing for the language server.
+//!
+//! Messages are exchanged as JSON-RPC payloads preceded by a `Content-Length`
+//! header, as described by the Language Server Protocol base protocol.
+
+use std::io::BufRead;
+use std::io::Error as IoError;
+use std::io::ErrorKind;
+use std::io::Write;
+
+use serde_json::Value;
+
+/// Reads the next message from `reader`.
+///
+/// Returns `Ok(None)` when the input stream has been closed.
+///
+/// # Errors
+///
+/// Returns an I/O error if the stream cannot be read, the headers are malformed,
+/// or the payload is not valid JSON.
+pub fn read_message(reader: &mut impl BufRead) -> Result<Option<Value>, IoError> {
+    let mut content_length: Option<usize> = None;
+    let mut line = String::new();
+
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+
+        let header = line.trim_end_matches(['\r', '\n']);
+        if header.is_empty() {
+            if content_length.is_some() {
+                break;
+            }
+
+            continue;
+        }
+
+        let Some((name, value)) = header.split_once(':') else {
+            return Err(IoError::new(ErrorKind::InvalidData, format!("malformed header `{header}`")));
+        };
+
+        if name.trim().eq_ignore_ascii_case("content-length") {
+            let length = value
+                .trim()
+                .parse()
+                .map_err(|_| IoError::new(ErrorKind::InvalidData, format!("invalid content length `{value}`")))?;
+
+            content_length = Some(length);
+        }
+    }
+
+    let mut payload = vec![0; content_length.unwrap_or_default()];
+    reader.read_exact(&mut payload)?;
+
+    serde_json::from_slice(&payload).map(Some).map_err(IoError::from)
+}
+
+/// Writes `message` to `writer` using the base protocol framing.
+///
+/// # Errors
+///
+/// Returns an I/O error if the message cannot be written or flushed.
+pub fn write_message(writer: &mut impl Write, message: &Value) -> Result<(), IoError> {
+    let payload = serde_json::to_vec(message)?;
+
+    write!(writer, "Content-Length: {}\r\n\r\n", payload.len())?;
+    writer.write_all(&payload)?;
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn round_trips_messages() {
+        let message = json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {} });
+
+        let mut buffer = Vec::new();
+        write_message(&mut buffer, &message).unwrap();
+
+        let mut reader = Cursor::new(buffer);
+        assert_eq!(read_message(&mut reader).unwrap(), Some(message));
+        assert_eq!(read_message(&mut reader).unwrap(), None);
+    }
+
+    #[test]
+    fn ignores_unknown_headers() {
+        let input = "Content-Type: application/vscode-jsonrpc; charset=utf-8\r\nContent-Length: 2\r\n\r\n{}";
+
+        let mut reader = Cursor::new(input.as_bytes());
+        assert_eq!(read_message(&mut reader).unwrap(), Some(json!({})));
+    }
+}
@@ -29,10 +29,14 @@
 //! - **`list-files`** ([`ListFilesCommand`]): List files that will be processed
 //! - **`lint`** ([`LintCommand`]): Run linting rules on PHP code
 //! - **`analyze`** ([`AnalyzeCommand`]): Perform static analysis
+//! - **`fix`** ([`FixCommand`]): Apply linter and analyzer fixes
+//! - **`refactor`** ([`RefactorCommand`]): Rename or move symbols across the project
 //! - **`format`** ([`FormatCommand`]): Format PHP code
 //! - **`guard`** ([`GuardCommand`]): Enforce architectural rules
 //! - **`inspect-baseline`** ([`InspectBaselineCommand`]): Visualize a baseline file
 //! - **`cst`** ([`CstCommand`]): Display the abstract syntax tree
+//! - **`hir`** ([`HirCommand`]): Display the lowered high-level intermediate representation
+//! - **`lsp`** ([`LspCommand`]): Run Mago as a language server
 //! - **`self-update`** ([`SelfUpdateCommand`]): Update Mago to the latest version
 //! - **`generate-completions`** ([`GenerateCompletionsCommand`]): Generate shell completions
 //!
@@ -61,13 +65,17 @@ use crate::commands::analyze::AnalyzeCommand;
 use crate::commands::config::ConfigCommand;
 use crate::commands::cst::CstCommand;
 use crate::commands::extension::ExtensionCommand;
+use crate::commands::fix::FixCommand;
 use crate::commands::format::FormatCommand;
 use crate::commands::generate_completions::GenerateCompletionsCommand;
 use crate::commands::guard::GuardCommand;
+use crate::commands::hir::HirCommand;
 use crate::commands::init::InitCommand;
 use crate::commands::inspect_baseline::InspectBaselineCommand;
 use crate::commands::lint::LintCommand;
 use crate::commands::list_files::ListFilesCommand;
+use crate::commands::lsp::LspCommand;
+use crate::commands::refactor::RefactorCommand;
 use crate::commands::self_update::SelfUpdateCommand;
 use crate::error::Error;
 
@@ -77,13 +85,17 @@ pub mod analyze;
 pub mod config;
 pub mod cst;
 pub mod extension;
+pub mod fix;
 pub mod format;
 pub mod generate_completions;
 pub mod guard;
+pub mod hir;
 pub mod init;
 pub mod inspect_baseline;
 pub mod lint;
 pub mod list_files;
+pub mod lsp;
+pub mod refactor;
 pub mod self_update;
 pub mod stdin_input;
 
@@ -182,6 +194,16 @@ pub enum MagoCommand {
     #[command(name = "cst")]
     Cst(CstCommand),
 
+    /// Display the high-level intermediate representation (HIR) of PHP code.
+    ///
+    /// Lowers PHP code into the HIR, with resolved names and parsed PHPDoc
+    /// annotations, and displays it. Useful for debugging how Mago understands
+    /// declarations and docblocks.
+    ///
+    /// **Usage**: `mago hir <FILE>`
+    #[command(name = "hir")]
+    Hir(HirCommand),
+
     /// Run linting rules on PHP code.
     ///
     /// Checks PHP code against configured linting rules to identify style violations,
@@ -202,6 +224,26 @@ pub enum MagoCommand {
     #[command(name = "analyze")]
     Analyze(AnalyzeCommand),
 
+    /// Apply linter and analyzer fixes in one pass.
+    ///
+    /// Lints and analyzes the code, then applies the fixes attached to the issues
+    /// of both tools, subject to the selected safety level (`--potentially-unsafe`,
+    /// `--unsafe`).
+    ///
+    /// **Usage**: `mago fix [OPTIONS]`
+    #[command(name = "fix")]
+    Fix(FixCommand),
+
+    /// Rename or move symbols across the whole project.
+    ///
+    /// Renames class-likes and their members, or moves class-likes to another
+    /// namespace, updating every reference including imports and docblocks. The
+    /// changes are previewed as a diff unless `--apply` is given.
+    ///
+    /// **Usage**: `mago refactor rename <SYMBOL> <NEW_NAME>` or `mago refactor move <CLASS> <NAMESPACE>`
+    #[command(name = "refactor")]
+    Refactor(RefactorCommand),
+
     /// Enforce architectural rules and layer dependencies.
     ///
     /// Checks that code follows defined architectural constraints, such as ensuring
@@ -221,6 +263,16 @@ pub enum MagoCommand {
     #[command(name = "inspect-baseline")]
     InspectBaseline(InspectBaselineCommand),
 
+    /// Run Mago as a language server.
+    ///
+    /// Speaks the Language Server Protocol over standard input and output, providing
+    /// analyzer and linter diagnostics, formatting, quick fixes, and hover types to
+    /// editors. Unsaved buffers are analyzed incrementally as they change.
+    ///
+    /// **Usage**: `mago lsp`
+    #[command(name = "lsp")]
+    Lsp(LspCommand),
+
     /// Format PHP code according to style settings.
     ///
     /// Automatically formats PHP code to match the configured style preferences.
@@ -0,0 +1,179 @@
+//! Refactor command implementation.
+//!
+//! This module implements the `mago refactor` command, which renames or moves symbols
+//! across the whole project. The refactoring is planned by the orchestrator's
+//! [`RefactorService`](mago_orchestrator::service::refactor::RefactorService) and goes
+//! through the same pipeline as `mago fix`, so it is previewed as a diff by default and
+//! only written to disk with `--apply`.
+//!
+//! # Subcommands
+//!
+//! - **rename**: renames a class-like, or a method, property, or constant of one
+//! - **move**: moves a class-like to another namespace
+//!
+//! Occurrences that cannot be rewritten automatically are reported as warnings and
+//! must be updated by hand.
+
+use std::process::ExitCode;
+
+use clap::ColorChoice;
+use clap::Parser;
+use clap::Subcommand;
+
+use mago_database::DatabaseReader;
+use mago_database::file::FileType;
+use mago_orchestrator::service::refactor::Refactoring;
+use mago_prelude::Prelude;
+use mago_reporting::Level;
+use mago_reporting::ReportingFormat;
+use mago_reporting::ReportingTarget;
+
+use crate::config::Configuration;
+use crate::consts::PRELUDE_BYTES;
+use crate::error::Error;
+use crate::service::IssueProcessor;
+use crate::utils::create_orchestrator;
+
+/// Command for renaming and moving symbols across the project.
+///
+/// References are found through name resolution and the analyzer's inferred types, and
+/// include `use` imports, docblocks, `::class` lookups, and class names passed as strings
+/// to attributes. The result is shown as a diff unless `--apply` is given.
+#[derive(Parser, Debug)]
+#[command(
+    name = "refactor",
+    about = "Rename or move symbols across the whole project.",
+    long_about = "Rename or move symbols across the whole project.\n\n\
+                  Class-likes can be renamed or moved to another namespace, and methods, properties, and \
+                  constants can be renamed across the class hierarchy that declares them. The changes are \
+                  shown as a diff; pass `--apply` to write them."
+)]
+pub struct RefactorCommand {
+    #[command(subcommand)]
+    command: RefactorSubcommand,
+
+    /// Write the changes to disk instead of previewing them as a diff.
+    #[arg(long, global = true)]
+    pub apply: bool,
+
+    /// Disable built-in PHP and library stubs for analysis.
+    #[arg(long, global = true, default_value_t = false)]
+    pub no_stubs: bool,
+
+    /// Format the changed files after applying the refactoring.
+    #[arg(long, global = true, alias = "fmt")]
+    pub format_after_fix: bool,
+}
+
+#[derive(Subcommand, Debug)]
+enum RefactorSubcommand {
+    /// Rename a class-like or one of its members.
+    ///
+    /// Members are written as `App\Foo::method`, `App\Foo::CONSTANT`, or `App\Foo::$property`.
+    Rename {
+        /// The fully qualified symbol to rename.
+        symbol: String,
+
+        /// The new short name of the symbol.
+        new_name: String,
+    },
+    /// Move a class-like to another namespace, keeping its short name.
+    Move {
+        /// The fully qualified name of the class-like to move.
+        class_like: String,
+
+        /// The namespace to move the class-like to.
+        namespace: String,
+    },
+}
+
+impl RefactorCommand {
+    /// Executes the refactoring.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database cannot be loaded, if the refactoring cannot be
+    /// planned, or if the changes cannot be written back.
+    pub fn execute(self, configuration: Configuration, color_choice: ColorChoice) -> Result<ExitCode, Error> {
+        let refactoring = match self.command {
+            RefactorSubcommand::Rename { symbol, new_name } => Refactoring::Rename { symbol, new_name },
+            RefactorSubcommand::Move { class_like, namespace } => Refactoring::Move { class_like, namespace },
+        };
+
+        let mut orchestrator = create_orchestrator(&configuration, color_choice, false, true, false);
+        orchestrator.add_exclude_patterns(configuration.analyzer.excludes.iter());
+
+        let Prelude { database: prelude_database, metadata, symbol_references } = if self.no_stubs {
+            Prelude::default()
+        } else {
+            Prelude::decode(PRELUDE_BYTES).expect("Failed to decode embedded prelude")
+        };
+
+        let mut database =
+            orchestrator.load_database(&configuration.source.workspace, true, Some(prelude_database), None)?;
+
+        if !database.files().any(|f| f.file_type == FileType::Host) {
+            tracing::warn!("No files found to refactor.");
+
+            return Ok(ExitCode::SUCCESS);
+        }
+
+        let mut service = orchestrator.get_refactor_service(database.read_only(), metadata, symbol_references);
+        let mut plan = service.plan(&refactoring)?;
+
+        let skipped = std::mem::take(&mut plan.skipped);
+        if !skipped.is_empty() {
+            let reporter = IssueProcessor {
+                fixable_only: false,
+                sort: true,
+                fix: false,
+                r#unsafe: false,
+                potentially_unsafe: false,
+                format_after_fix: false,
+                dry_run: false,
+                fail_on_remaining: false,
+                reporting_target: ReportingTarget::default(),
+                reporting_format: ReportingFormat::default(),
+                minimum_fail_level: Level::Error,
+                minimum_report_level: None,
+                retain_code: Vec::new(),
+                color_choice,
+                editor_url: None,
+                scoped_analysis: false,
+            };
+
+            reporter.process_issues(&orchestrator, &mut database, skipped, None, false)?;
+        }
+
+        if plan.is_empty() {
+            tracing::info!("{} Nothing to change.", plan.description);
+
+            return Ok(ExitCode::SUCCESS);
+        }
+
+        tracing::info!("{} {} edit(s) across {} file(s).", plan.description, plan.edit_count(), plan.edits.len());
+
+        let processor = IssueProcessor {
+            fixable_only: false,
+            sort: false,
+            fix: true,
+            r#unsafe: true,
+            potentially_unsafe: true,
+            format_after_fix: self.format_after_fix,
+            dry_run: !self.apply,
+            fail_on_remaining: false,
+            reporting_target: ReportingTarget::default(),
+            reporting_format: ReportingFormat::default(),
+            minimum_fail_level: Level::Error,
+            minimum_report_level: None,
+            retain_code: Vec::new(),
+            color_choice,
+            editor_url: None,
+            scoped_analysis: false,
+        };
+
+        let (exit_code, _) = processor.process_issues(&orchestrator, &mut database, plan.into_issues(), None, false)?;
+
+        Ok(if self.apply { exit_code } else { ExitCode::SUCCESS })
+    }
+}
@@ -8,7 +8,15 @@ use mago_algebra::DEFAULT_SATURATION_COMPLEXITY;
 use mago_analyzer::settings::ClassInitializer;
 use mago_analyzer::settings::DEFAULT_FORMULA_SIZE_THRESHOLD;
 use mago_analyzer::settings::DEFAULT_LOOP_ASSIGNMENT_DEPTH_THRESHOLD;
+use mago_analyzer::settings::EntryPointSettings;
+use mago_analyzer::settings::EntryPointSymbol;
 use mago_analyzer::settings::Settings;
+use mago_analyzer::settings::TaintSanitizer;
+use mago_analyzer::settings::TaintSettings;
+use mago_analyzer::settings::TaintSink;
+use mago_analyzer::settings::TaintSource;
+use mago_analyzer::settings::TaintSymbol;
+use mago_codex::metadata::taint::TaintKinds;
 use mago_codex::ttype::combiner::DEFAULT_ARRAY_COMBINATION_THRESHOLD;
 use mago_codex::ttype::combiner::DEFAULT_INTEGER_COMBINATION_THRESHOLD;
 use mago_codex::ttype::combiner::DEFAULT_STRING_COMBINATION_THRESHOLD;
@@ -16,6 +24,7 @@ use mago_php_version::PHPVersion;
 use mago_reporting::IgnoreEntry;
 use mago_reporting::Level;
 use mago_reporting::baseline::BaselineVariant;
+use mago_word::ascii_lowercase_word;
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
@@ -74,6 +83,7 @@ pub struct AnalyzerConfiguration {
     /// - `psl` (aliases: `php-standard-library`, `azjezz-psl`)
     /// - `flow-php` (aliases: `flow`, `flow-etl`)
     /// - `psr-container` (aliases: `psr-11`)
+    /// - `doctrine` (aliases: `doctrine-orm`)
     ///
     /// Example: `plugins = ["stdlib", "psl"]`
     pub plugins: Vec<String>,
@@ -84,6 +94,12 @@ pub struct AnalyzerConfiguration {
     /// Whether to find unused definitions.
     pub find_unused_definitions: bool,
 
+    /// Whether to find class-likes, functions, constants, class constants and enum cases that
+    /// nothing in the project uses.
+    ///
+    /// Declarations matched by `[analyzer.entry-points]` are never reported.
+    pub find_unused_symbols: bool,
+
     /// Whether to warn when a function's declared return type contains a branch the body never
     /// actually returns (e.g. `: string|false` on a function that always returns a string).
     pub find_overly_wide_return_types: bool,
@@ -318,6 +334,21 @@ pub struct AnalyzerConfiguration {
     /// Lower values improve speed but may reduce precision on complex conditional code.
     #[serde(default)]
     pub performance: PerformanceConfiguration,
+
+    /// Taint analysis settings.
+    ///
+    /// When enabled, user-controlled data is followed from its sources through the variables of
+    /// each body, and through the returns and properties summarized for user-defined code, and
+    /// reported when it reaches a sink unsanitized.
+    #[serde(default)]
+    pub taint: TaintConfiguration,
+
+    /// Declarations that `--remove-dead-code` keeps even when nothing references them.
+    ///
+    /// Use this for code invoked by a framework through reflection, such as controllers,
+    /// event listeners, or console commands.
+    #[serde(default)]
+    pub entry_points: EntryPointsConfiguration,
 }
 
 /// Performance tuning settings for the analyzer.
@@ -434,6 +465,195 @@ impl Default for PerformanceConfiguration {
     }
 }
 
+/// Settings for the taint analysis.
+///
+/// Built-in sources, sinks and sanitizers are always active once enabled; the entries
+/// listed here extend them with project-specific functions and methods.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct TaintConfiguration {
+    /// Enable taint analysis.
+    ///
+    /// Defaults to `false`.
+    pub enabled: bool,
+
+    /// Functions or methods whose return value is user-controlled.
+    pub sources: Vec<TaintSourceConfiguration>,
+
+    /// Functions or methods whose arguments must not receive user-controlled data.
+    pub sinks: Vec<TaintSinkConfiguration>,
+
+    /// Functions or methods whose return value is safe for the given kinds of sinks.
+    pub sanitizers: Vec<TaintSanitizerConfiguration>,
+}
+
+/// A taint source entry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct TaintSourceConfiguration {
+    /// The function (`"name"`) or method (`"Class::method"`) returning tainted data.
+    pub symbol: String,
+
+    /// The kinds of taint introduced, e.g. `["html", "sql"]`.
+    ///
+    /// Defaults to `["input"]`, which covers every kind user input can carry.
+    #[serde(default = "default_taint_source_kinds")]
+    pub kinds: Vec<String>,
+}
+
+/// A taint sink entry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct TaintSinkConfiguration {
+    /// The function (`"name"`) or method (`"Class::method"`) receiving the data.
+    pub symbol: String,
+
+    /// The kinds of taint the sink is sensitive to, e.g. `["sql"]`.
+    pub kinds: Vec<String>,
+
+    /// Zero-based indexes of the sensitive arguments.
+    ///
+    /// Defaults to all arguments.
+    #[serde(default)]
+    pub arguments: Vec<usize>,
+}
+
+/// A taint sanitizer entry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct TaintSanitizerConfiguration {
+    /// The function (`"name"`) or method (`"Class::method"`) sanitizing its input.
+    pub symbol: String,
+
+    /// The kinds of taint removed from the return value.
+    ///
+    /// Defaults to `["all"]`.
+    #[serde(default = "default_taint_sanitizer_kinds")]
+    pub kinds: Vec<String>,
+}
+
+/// Entry points kept by dead-code removal and never reported as unused symbols.
+///
+/// Attributes commonly used by PHPUnit and Symfony (`#[Test]`, `#[Route]`, `#[AsCommand]`, ...)
+/// and PHP's own `#[Attribute]` are always treated as entry points; the entries listed here
+/// extend them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct EntryPointsConfiguration {
+    /// Fully qualified names of attributes marking a class, function, method or property
+    /// as an entry point, e.g. `["App\\Attribute\\Job"]`.
+    pub attributes: Vec<String>,
+
+    /// Namespaces whose declarations are always kept, including those of nested namespaces,
+    /// e.g. `["App\\Controller"]`.
+    pub namespaces: Vec<String>,
+
+    /// Symbols that are always kept: class-likes, functions and constants (`"name"`) or members
+    /// (`"Class::method"`, `"Class::$property"`, `"Class::CONSTANT"`).
+    ///
+    /// A trailing `*` matches every name with the preceding prefix, e.g. `"App\\Tests\\*::test*"`.
+    /// Keeping a class-like also keeps every class-like extending or implementing it.
+    pub symbols: Vec<String>,
+}
+
+impl EntryPointsConfiguration {
+    fn to_settings(&self) -> EntryPointSettings {
+        EntryPointSettings {
+            attributes: self
+                .attributes
+                .iter()
+                .map(|attribute| ascii_lowercase_word(attribute.trim_start_matches('\\').as_bytes()))
+                .collect(),
+            namespaces: self
+                .namespaces
+                .iter()
+                .map(|namespace| ascii_lowercase_word(namespace.trim_matches('\\').as_bytes()))
+                .filter(|namespace| !namespace.is_empty())
+                .collect(),
+            symbols: self
+                .symbols
+                .iter()
+                .filter_map(|symbol| {
+                    let parsed = EntryPointSymbol::parse(symbol);
+                    if parsed.is_none() {
+                        tracing::warn!("Ignoring invalid symbol `{symbol}` in `[analyzer.entry-points]`.");
+                    }
+
+                    parsed
+                })
+                .collect(),
+        }
+    }
+}
+
+fn default_taint_source_kinds() -> Vec<String> {
+    vec!["input".to_string()]
+}
+
+fn default_taint_sanitizer_kinds() -> Vec<String> {
+    vec!["all".to_string()]
+}
+
+impl TaintConfiguration {
+    fn to_settings(&self) -> TaintSettings {
+        TaintSettings {
+            enabled: self.enabled,
+            sources: self
+                .sources
+                .iter()
+                .filter_map(|source| {
+                    Some(TaintSource {
+                        symbol: parse_taint_symbol(&source.symbol)?,
+                        kinds: parse_taint_kinds(&source.kinds),
+                    })
+                })
+                .collect(),
+            sinks: self
+                .sinks
+                .iter()
+                .filter_map(|sink| {
+                    Some(TaintSink {
+                        symbol: parse_taint_symbol(&sink.symbol)?,
+                        kinds: parse_taint_kinds(&sink.kinds),
+                        arguments: sink.arguments.clone(),
+                    })
+                })
+                .collect(),
+            sanitizers: self
+                .sanitizers
+                .iter()
+                .filter_map(|sanitizer| {
+                    Some(TaintSanitizer {
+                        symbol: parse_taint_symbol(&sanitizer.symbol)?,
+                        kinds: parse_taint_kinds(&sanitizer.kinds),
+                    })
+                })
+                .collect(),
+        }
+    }
+}
+
+fn parse_taint_symbol(symbol: &str) -> Option<TaintSymbol> {
+    let parsed = TaintSymbol::parse(symbol);
+    if parsed.is_none() {
+        tracing::warn!("Ignoring invalid taint symbol `{symbol}` in `[analyzer.taint]`.");
+    }
+
+    parsed
+}
+
+fn parse_taint_kinds(kinds: &[String]) -> TaintKinds {
+    let mut result = TaintKinds::empty();
+    for kind in kinds {
+        match TaintKinds::from_name(kind) {
+            Some(parsed) => result |= parsed,
+            None => tracing::warn!("Ignoring unknown taint kind `{kind}` in `[analyzer.taint]`."),
+        }
+    }
+
+    result
+}
+
 impl AnalyzerConfiguration {
     pub fn to_settings(&self, php_version: PHPVersion, color_choice: ColorChoice, enable_diff: bool) -> Settings {
         // Backwards compatibility: if perform_heuristic_checks is set, use it for both options
@@ -453,6 +673,7 @@ impl AnalyzerConfiguration {
             version: php_version,
             analyze_dead_code: self.analyze_dead_code,
             find_unused_definitions: self.find_unused_definitions,
+            find_unused_symbols: self.find_unused_symbols,
             find_overly_wide_return_types: self.find_overly_wide_return_types,
             find_unused_expressions: self.find_unused_expressions,
             memoize_properties: self.memoize_properties,
@@ -476,6 +697,7 @@ impl AnalyzerConfiguration {
             check_closure_missing_type_hints: self.check_closure_missing_type_hints,
             check_arrow_function_missing_type_hints: self.check_arrow_function_missing_type_hints,
             allow_implicit_pipe_callable_types: self.allow_implicit_pipe_callable_types,
+            infer_types: false,
             register_super_globals: self.register_super_globals,
             use_colors: should_use_colors(color_choice),
             diff: enable_diff,
@@ -486,6 +708,8 @@ impl AnalyzerConfiguration {
             check_experimental: self.check_experimental,
             check_name_casing: self.check_name_casing,
             allow_side_effects_in_conditions: self.allow_side_effects_in_conditions,
+            taint: self.taint.to_settings(),
+            entry_points: self.entry_points.to_settings(),
             saturation_complexity_threshold: self.performance.saturation_complexity_threshold,
             disjunction_complexity_threshold: self.performance.disjunction_complexity_threshold,
             negation_complexity_threshold: self.performance.negation_complexity_threshold,
@@ -513,6 +737,7 @@ impl Default for AnalyzerConfiguration {
             minimum_fail_level: Level::Error,
             find_unused_expressions: defaults.find_unused_expressions,
             find_unused_definitions: defaults.find_unused_definitions,
+            find_unused_symbols: defaults.find_unused_symbols,
             find_overly_wide_return_types: defaults.find_overly_wide_return_types,
             analyze_dead_code: defaults.analyze_dead_code,
             memoize_properties: defaults.memoize_properties,
@@ -542,6 +767,8 @@ impl Default for AnalyzerConfiguration {
             allow_side_effects_in_conditions: defaults.allow_side_effects_in_conditions,
             perform_heuristic_checks: None,
             performance: PerformanceConfiguration::default(),
+            taint: TaintConfiguration::default(),
+            entry_points: EntryPointsConfiguration::default(),
         }
     }
 }
@@ -0,0 +1,164 @@
+//! Configuration defaults derived from the workspace `composer.json`.
+//!
+//! When not configured explicitly:
+//!
+//! - `php-version` is the minimum version allowed by `require.php`.
+//! - `source.paths` are the `autoload` and `autoload-dev` directories and files.
+//! - `source.includes` is the Composer vendor directory.
+//!
+//! A `php-version` that is configured explicitly is checked against `require.php` instead,
+//! and a warning is logged when it falls outside of the constraint.
+
+use std::path::Path;
+use std::str::FromStr;
+
+use mago_composer::AutoloadPsr0value;
+use mago_composer::AutoloadPsr4value;
+use mago_composer::ComposerPackage;
+use mago_composer::ComposerPackageAutoloadDevPsr0value;
+use mago_composer::ComposerPackageAutoloadDevPsr4value;
+
+use crate::consts::COMPOSER_JSON_FILE;
+
+/// The vendor directory Composer uses when `config.vendor-dir` is not set.
+const DEFAULT_VENDOR_DIRECTORY: &str = "vendor";
+
+/// The autoloading standard of an autoload mapping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AutoloadStandard {
+    Psr4,
+    Psr0,
+}
+
+/// Reads and parses the `composer.json` file in `workspace`, if there is one.
+///
+/// Read and parse failures are logged, and treated as if the file does not exist.
+pub(crate) fn read_composer_package(workspace: &Path) -> Option<ComposerPackage> {
+    let composer_file = workspace.join(COMPOSER_JSON_FILE);
+    if !composer_file.is_file() {
+        return None;
+    }
+
+    let contents = match std::fs::read_to_string(&composer_file) {
+        Ok(contents) => contents,
+        Err(error) => {
+            tracing::warn!("Failed to read `{}`: {error}", composer_file.display());
+
+            return None;
+        }
+    };
+
+    match ComposerPackage::from_str(&contents) {
+        Ok(composer) => Some(composer),
+        Err(error) => {
+            tracing::warn!("Failed to parse `{}`: {error}", composer_file.display());
+
+            None
+        }
+    }
+}
+
+/// Returns the `require.php` constraint of the package, if any.
+pub(crate) fn php_constraint(composer: &ComposerPackage) -> Option<&str> {
+    composer.require.get("php").map(String::as_str)
+}
+
+/// Returns the PSR-4 and PSR-0 mappings of `autoload` and `autoload-dev`, in that order.
+pub(crate) fn autoload_mappings(composer: &ComposerPackage) -> Vec<(AutoloadStandard, String, Vec<String>)> {
+    let mut mappings = vec![];
+
+    if let Some(autoload) = &composer.autoload {
+        for (prefix, value) in &autoload.psr_4 {
+            let directories = match value {
+                AutoloadPsr4value::Array(directories) => directories.clone(),
+                AutoloadPsr4value::String(directory) => vec![directory.clone()],
+            };
+
+            mappings.push((AutoloadStandard::Psr4, prefix.clone(), directories));
+        }
+
+        for (prefix, value) in &autoload.psr_0 {
+            let directories = match value {
+                AutoloadPsr0value::Array(directories) => directories.clone(),
+                AutoloadPsr0value::String(directory) => vec![directory.clone()],
+            };
+
+            mappings.push((AutoloadStandard::Psr0, prefix.clone(), directories));
+        }
+    }
+
+    if let Some(autoload_dev) = &composer.autoload_dev {
+        for (prefix, value) in &autoload_dev.psr_4 {
+            let directories = match value {
+                ComposerPackageAutoloadDevPsr4value::Array(directories) => directories.clone(),
+                ComposerPackageAutoloadDevPsr4value::String(directory) => vec![directory.clone()],
+            };
+
+            mappings.push((AutoloadStandard::Psr4, prefix.clone(), directories));
+        }
+
+        for (prefix, value) in &autoload_dev.psr_0 {
+            let directories = match value {
+                ComposerPackageAutoloadDevPsr0value::Array(directories) => directories.clone(),
+                ComposerPackageAutoloadDevPsr0value::String(directory) => vec![directory.clone()],
+            };
+
+            mappings.push((AutoloadStandard::Psr0, prefix.clone(), directories));
+        }
+    }
+
+    mappings
+}
+
+/// Returns the autoloaded paths that exist in `workspace`, without paths nested in other ones.
+///
+/// Includes the PSR-4, PSR-0, `classmap`, and `files` entries of `autoload` and `autoload-dev`.
+/// Returns no paths when the workspace root itself is autoloaded.
+pub(crate) fn autoload_paths(composer: &ComposerPackage, workspace: &Path) -> Vec<String> {
+    let mut paths: Vec<String> =
+        autoload_mappings(composer).into_iter().flat_map(|(_, _, directories)| directories).collect();
+
+    if let Some(autoload) = &composer.autoload {
+        paths.extend(autoload.classmap.iter().cloned());
+        paths.extend(autoload.files.iter().cloned());
+    }
+
+    if let Some(autoload_dev) = &composer.autoload_dev {
+        paths.extend(autoload_dev.classmap.iter().cloned());
+        paths.extend(autoload_dev.files.iter().cloned());
+    }
+
+    let paths: Vec<String> = paths
+        .iter()
+        .map(|path| path.replace('\\', "/").trim_start_matches("./").trim_end_matches('/').to_string())
+        .collect();
+
+    // A mapping to the workspace root autoloads everything.
+    if paths.iter().any(String::is_empty) {
+        return vec![];
+    }
+
+    let mut paths: Vec<String> = paths.into_iter().filter(|path| workspace.join(path).exists()).collect();
+
+    paths.sort();
+    paths.dedup();
+
+    let mut roots: Vec<String> = vec![];
+    for path in paths {
+        if !roots.iter().any(|root| path.starts_with(&format!("{root}/"))) {
+            roots.push(path);
+        }
+    }
+
+    roots
+}
+
+/// Returns the vendor directory of the package, relative to the workspace.
+pub(crate) fn vendor_directory(composer: &ComposerPackage) -> String {
+    composer
+        .config
+        .as_ref()
+        .and_then(|config| config.vendor_dir.as_deref())
+        .map_or(DEFAULT_VENDOR_DIRECTORY, |directory| directory.trim_start_matches("./").trim_end_matches('/'))
+        .to_string()
+}
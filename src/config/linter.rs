@@ -6,12 +6,16 @@ use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
 
+use mago_composer::ComposerPackage;
 use mago_linter::integration::Integration;
 use mago_linter::integration::IntegrationSet;
 use mago_linter::rule::filter_rules_settings;
 use mago_linter::settings::RulesSettings;
 use mago_php_version::PHPVersion;
 
+use crate::config::composer::AutoloadStandard;
+use crate::config::composer::autoload_mappings;
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
 pub struct LinterConfiguration {
@@ -79,4 +83,21 @@ impl LinterConfiguration {
             "baseline-variant": self.baseline_variant,
         })
     }
+
+    /// Fills the `psr-autoload` rule mappings from `composer.json`, unless they are configured.
+    pub(crate) fn load_autoload_mappings(&mut self, composer: &ComposerPackage) {
+        let settings = &mut self.rules.psr_autoload;
+        if !settings.enabled || !settings.config.is_empty() {
+            return;
+        }
+
+        for (standard, prefix, directories) in autoload_mappings(composer) {
+            let mappings = match standard {
+                AutoloadStandard::Psr4 => &mut settings.config.psr_4,
+                AutoloadStandard::Psr0 => &mut settings.config.psr_0,
+            };
+
+            mappings.entry(prefix).or_default().extend(directories);
+        }
+    }
 }
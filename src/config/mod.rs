@@ -80,6 +80,8 @@
 //! - Stack size is clamped between minimum and maximum bounds.
 //! - PHP version compatibility is validated against the supported range.
 //! - Source paths are resolved and validated.
+//! - `php-version`, `source.paths`, `source.includes`, and the linter `psr-autoload` mappings
+//!   are derived from `composer.json` when not configured (see [`composer`]).
 
 use std::collections::BTreeMap;
 use std::collections::HashSet;
@@ -94,6 +96,7 @@ use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
 
+use mago_composer::ComposerPackage;
 use mago_php_version::PHPVersion;
 use serde::de::IgnoredAny;
 use serde_json::Value;
@@ -105,17 +108,22 @@ use crate::config::guard::GuardConfiguration;
 use crate::config::linter::LinterConfiguration;
 use crate::config::parser::ParserConfiguration;
 use crate::config::source::SourceConfiguration;
+use crate::config::twig::TwigConfiguration;
 use crate::consts::*;
 use crate::error::Error;
+use crate::utils::version::minimum_php_version;
+use crate::utils::version::php_version_satisfies;
 use crate::version_check::VersionDriftFailLevel;
 
 pub mod analyzer;
+pub mod composer;
 pub mod extension;
 pub mod formatter;
 pub mod guard;
 pub mod linter;
 pub mod parser;
 pub mod source;
+pub mod twig;
 
 /// Default value for threads configuration field.
 fn default_threads() -> usize {
@@ -316,6 +324,13 @@ pub struct Configuration {
     #[serde(default)]
     pub guard: GuardConfiguration,
 
+    /// Twig template configuration.
+    ///
+    /// Enables formatting and linting of `.twig` templates alongside PHP sources, and holds
+    /// the template formatter and lint rule settings. Disabled by default.
+    #[serde(default)]
+    pub twig: TwigConfiguration,
+
     /// Editor URL template for OSC 8 terminal hyperlinks on file paths in diagnostics.
     ///
     /// When set, file paths in diagnostic output become clickable links in terminals
@@ -345,6 +360,20 @@ pub struct Configuration {
     #[serde(default, skip_serializing)]
     #[schemars(skip)]
     pub config_file_is_explicit: bool,
+
+    /// Whether `php-version` was set by a configuration file, environment variable, or CLI flag.
+    ///
+    /// When `false`, the PHP version is derived from `composer.json` during normalization.
+    #[serde(default, skip_serializing)]
+    #[schemars(skip)]
+    pub php_version_is_explicit: bool,
+
+    /// Whether `source.paths` or `source.includes` was set by a configuration file.
+    ///
+    /// When `false`, both are derived from `composer.json` during normalization.
+    #[serde(default, skip_serializing)]
+    #[schemars(skip)]
+    pub source_paths_are_explicit: bool,
 }
 
 impl Configuration {
@@ -421,8 +450,16 @@ impl Configuration {
         let mut configuration: Configuration = if let Some((path, format)) = &resolved_config_file {
             let mut visited: HashSet<PathBuf> = HashSet::new();
             let merged = load_layer(path, *format, &mut visited)?;
-            serde_json::from_value::<Configuration>(merged)
-                .map_err(|e| Error::ParseConfigFile { path: path.clone(), source: Box::new(e) })?
+            let php_version_is_explicit = merged.get("php-version").is_some();
+            let source_paths_are_explicit = merged
+                .get("source")
+                .is_some_and(|source| source.get("paths").is_some() || source.get("includes").is_some());
+
+            let mut configuration = serde_json::from_value::<Configuration>(merged)
+                .map_err(|e| Error::ParseConfigFile { path: path.clone(), source: Box::new(e) })?;
+            configuration.php_version_is_explicit = php_version_is_explicit;
+            configuration.source_paths_are_explicit = source_paths_are_explicit;
+            configuration
         } else {
             Configuration::from_workspace(workspace_dir.clone())
         };
@@ -448,6 +485,7 @@ impl Configuration {
             tracing::info!("Overriding PHP version with {}.", php_version);
 
             configuration.php_version = php_version;
+            configuration.php_version_is_explicit = true;
         }
 
         if let Some(threads) = threads {
@@ -542,6 +580,7 @@ impl Configuration {
         if let Ok(v) = std::env::var(ENV_PHP_VERSION) {
             self.php_version =
                 v.parse().map_err(|e| Error::EnvVarParse { name: ENV_PHP_VERSION, source: Box::new(e) })?;
+            self.php_version_is_explicit = true;
         }
         if let Ok(v) = std::env::var(ENV_THREADS) {
             self.threads = v.parse().map_err(|e| Error::EnvVarParse { name: ENV_THREADS, source: Box::new(e) })?;
@@ -611,14 +650,84 @@ impl Configuration {
             formatter: FormatterConfiguration::default(),
             analyzer: AnalyzerConfiguration::default(),
             guard: GuardConfiguration::default(),
+            twig: TwigConfiguration::default(),
             editor_url: None,
             config_file: None,
             config_file_is_explicit: false,
+            php_version_is_explicit: false,
+            source_paths_are_explicit: false,
         }
     }
 }
 
 impl Configuration {
+    /// Derives the settings that are not configured explicitly from `composer.json`, and
+    /// reports a configured PHP version outside of its `require.php` constraint.
+    ///
+    /// See the [`composer`] module for the derived settings.
+    fn apply_composer_defaults(&mut self, composer: &ComposerPackage) {
+        if let Some(constraint) = composer::php_constraint(composer) {
+            if self.php_version_is_explicit {
+                if php_version_satisfies(self.php_version, constraint) == Some(false) {
+                    tracing::warn!(
+                        "PHP version {} is outside of the `{constraint}` constraint required by `composer.json`.",
+                        self.php_version
+                    );
+                }
+            } else {
+                let php_version = match minimum_php_version(constraint) {
+                    Some(php_version)
+                        if php_version < MINIMUM_PHP_VERSION
+                            && php_version_satisfies(MINIMUM_PHP_VERSION, constraint) == Some(true) =>
+                    {
+                        Some(MINIMUM_PHP_VERSION)
+                    }
+                    php_version => php_version,
+                };
+
+                match php_version {
+                    Some(php_version)
+                        if self.allow_unsupported_php_version
+                            || (MINIMUM_PHP_VERSION..=MAXIMUM_PHP_VERSION).contains(&php_version) =>
+                    {
+                        tracing::debug!(
+                            "Using PHP version {php_version} from the `{constraint}` constraint in `composer.json`."
+                        );
+
+                        self.php_version = php_version;
+                    }
+                    Some(php_version) => {
+                        tracing::warn!(
+                            "PHP version {php_version} required by `composer.json` is not supported, using {}.",
+                            self.php_version
+                        );
+                    }
+                    None => {
+                        tracing::debug!(
+                            "Could not derive a PHP version from the `{constraint}` constraint in `composer.json`."
+                        );
+                    }
+                }
+            }
+        }
+
+        if !self.source_paths_are_explicit {
+            let paths = composer::autoload_paths(composer, &self.source.workspace);
+            if !paths.is_empty() {
+                tracing::debug!("Using source paths {paths:?} from `composer.json`.");
+
+                self.source.paths = paths;
+            }
+
+            let vendor_directory = composer::vendor_directory(composer);
+            if self.source.workspace.join(&vendor_directory).is_dir() {
+                tracing::debug!("Including the `{vendor_directory}` vendor directory from `composer.json`.");
+
+                self.source.includes = vec![vendor_directory];
+            }
+        }
+    }
+
     /// Returns a filtered version of the configuration suitable for display.
     ///
     /// This method excludes linter rules that don't match the configured integrations,
@@ -640,6 +749,7 @@ impl Configuration {
             "formatter": self.formatter.to_value(),
             "analyzer": self.analyzer,
             "guard": self.guard,
+            "twig": self.twig,
         })
     }
 
@@ -749,6 +859,11 @@ impl Configuration {
             self.guard.baseline = Some(resolved);
         }
 
+        if let Some(composer) = composer::read_composer_package(&self.source.workspace) {
+            self.apply_composer_defaults(&composer);
+            self.linter.load_autoload_mappings(&composer);
+        }
+
         Ok(())
     }
 }
@@ -1229,6 +1344,49 @@ environment = { APP_ENV = "test" }
         // that `load_isolated` doesn't panic.
         assert!(config._schema.is_some());
     }
+
+    #[test]
+    fn test_composer_json_provides_unconfigured_defaults() {
+        let workspace_path = temp_dir().join("workspace-with-composer-json");
+        let _ = fs::remove_dir_all(&workspace_path);
+        fs::create_dir_all(workspace_path.join("src")).unwrap();
+        fs::create_dir_all(workspace_path.join("tests")).unwrap();
+        fs::create_dir_all(workspace_path.join("vendor")).unwrap();
+
+        write_file(
+            &workspace_path.join("composer.json"),
+            r#"{
+                "require": { "php": "^8.2 || ^8.3" },
+                "autoload": { "psr-4": { "App\\": "src/" } },
+                "autoload-dev": { "psr-4": { "App\\Tests\\": "tests/", "App\\Missing\\": "missing/" } }
+            }"#,
+        );
+
+        let config = load_config(Some(workspace_path));
+
+        assert_eq!(config.php_version.to_string(), "8.2.0");
+        assert_eq!(config.source.paths, vec!["src".to_string(), "tests".to_string()]);
+        assert_eq!(config.source.includes, vec!["vendor".to_string()]);
+    }
+
+    #[test]
+    fn test_configured_values_take_precedence_over_composer_json() {
+        let workspace_path = temp_dir().join("workspace-with-composer-json-and-config");
+        let _ = fs::remove_dir_all(&workspace_path);
+        fs::create_dir_all(workspace_path.join("src")).unwrap();
+
+        write_file(
+            &workspace_path.join("composer.json"),
+            r#"{ "require": { "php": "^8.2" }, "autoload": { "psr-4": { "App\\": "src/" } } }"#,
+        );
+        create_tmp_file("php-version = \"8.1.0\"\n[source]\npaths = [\"lib\"]\n", &workspace_path, "toml");
+
+        let config = load_config(Some(workspace_path));
+
+        assert_eq!(config.php_version.to_string(), "8.1.0");
+        assert_eq!(config.source.paths, vec!["lib".to_string()]);
+        assert!(config.source.includes.is_empty());
+    }
 }
 
 /// Auto-detect the editor URL template from environment hints.
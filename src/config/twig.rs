@@ -0,0 +1,56 @@
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+
+use mago_twig_formatter::settings::TwigFormatSettings;
+use mago_twig_linter::settings::TwigLinterSettings;
+
+/// Configuration for Twig template support.
+///
+/// When enabled, `mago fmt` and `mago lint` also pick up `.twig` files from the
+/// configured source paths, formatting and linting them with the settings below.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct TwigConfiguration {
+    /// Whether to format and lint Twig templates.
+    ///
+    /// Defaults to `false`.
+    pub enabled: bool,
+
+    /// Formatting settings for Twig templates.
+    pub formatter: TwigFormatSettings,
+
+    /// Lint rule settings for Twig templates.
+    pub linter: TwigLinterSettings,
+}
+
+#[cfg(test)]
+mod tests {
+    use mago_reporting::Level;
+
+    use super::*;
+
+    #[test]
+    fn test_deserialize_twig_configuration() {
+        let toml = r#"
+            enabled = true
+
+            [formatter]
+            single-quote = false
+
+            [linter.deprecated-filter]
+            filters = ["spaceless", "filter"]
+
+            [linter.raw-output]
+            level = "error"
+        "#;
+
+        let config: TwigConfiguration = toml::from_str(toml).unwrap();
+        assert!(config.enabled);
+        assert!(!config.formatter.single_quote);
+        assert!(config.formatter.space_inside_delimiters);
+        assert_eq!(config.linter.deprecated_filter.filters, vec!["spaceless", "filter"]);
+        assert_eq!(config.linter.raw_output.level, Level::Error);
+        assert!(config.linter.undefined_block_reference.enabled);
+    }
+}
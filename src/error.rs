@@ -290,6 +290,12 @@ pub enum Error {
     /// such as `--substitute ORIG=TEMP`. Semantic validation that depends on filesystem state
     /// or orchestrator context surfaces through [`Orchestrator`](Self::Orchestrator) instead.
     InvalidArgument(String),
+
+    /// The language server failed to communicate with its client.
+    ///
+    /// This error occurs when reading a message from standard input or writing a
+    /// message to standard output fails, or when the client sends a malformed message.
+    LanguageServer(std::io::Error),
 }
 
 /// Formats the error for user-friendly display.
@@ -362,6 +368,7 @@ impl std::fmt::Display for Error {
                 write!(f, "Unknown formatter preset: `{preset}`. Available presets are: laravel, psr12, default")
             }
             Self::InvalidArgument(message) => write!(f, "{message}"),
+            Self::LanguageServer(error) => write!(f, "Language server communication failed: {error}"),
             Self::InvalidProjectVersionPin(error) => write!(f, "{error}"),
             Self::ProjectMajorVersionMismatch(pinned, installed) => {
                 write!(
@@ -427,6 +434,7 @@ impl std::error::Error for Error {
             Self::ThreadPoolBuildError(error) => Some(error),
             Self::Orchestrator(error) => Some(error),
             Self::InvalidProjectVersionPin(error) => Some(error),
+            Self::LanguageServer(error) => Some(error),
             _ => None,
         }
     }
@@ -23,6 +23,7 @@
 //! - `mago guard`: Enforce architectural rules
 //! - `mago ast`: Display the abstract syntax tree
 //! - `mago list-files`: List all files that would be processed
+//! - `mago lsp`: Run Mago as a language server
 //! - `mago self-update`: Update Mago to the latest version
 //! - `mago generate-completions`: Generate shell completion scripts
 //!
@@ -282,9 +283,13 @@ pub fn run(main_start: Instant) -> Result<ExitCode, Error> {
         MagoCommand::Lint(cmd) => cmd.execute(configuration, arguments.colors),
         MagoCommand::Format(cmd) => cmd.execute(configuration, arguments.colors),
         MagoCommand::Cst(cmd) => cmd.execute(configuration, arguments.colors),
+        MagoCommand::Hir(cmd) => cmd.execute(configuration, arguments.colors),
         MagoCommand::Analyze(cmd) => cmd.execute(configuration, arguments.colors),
+        MagoCommand::Fix(cmd) => cmd.execute(configuration, arguments.colors),
+        MagoCommand::Refactor(cmd) => cmd.execute(configuration, arguments.colors),
         MagoCommand::Guard(cmd) => cmd.execute(configuration, arguments.colors),
         MagoCommand::InspectBaseline(cmd) => cmd.execute(configuration, arguments.colors),
+        MagoCommand::Lsp(cmd) => cmd.execute(configuration, arguments.colors),
         MagoCommand::GenerateCompletions(cmd) => cmd.execute(),
         MagoCommand::SelfUpdate(_) => {
             unreachable!("The self-update command should have been handled before this point.")
@@ -474,6 +474,11 @@ impl IssueProcessor {
             sort: self.sort,
             minimum_report_level: self.minimum_report_level,
             editor_url: self.editor_url.clone(),
+            rules: if self.reporting_format == ReportingFormat::Sarif {
+                utils::linter_rule_descriptors()
+            } else {
+                Vec::new()
+            },
         };
 
         let reporter = Reporter::new(read_database, reporter_configuration);
@@ -632,6 +637,15 @@ impl IssueProcessor {
                                 parse_error
                             );
 
+                            ephemeral_file.contents.into_owned()
+                        }
+                        FileFormatStatus::FailedToParseTemplate(parse_error) => {
+                            tracing::warn!(
+                                "Failed to format template `{}` after applying fixes: {}",
+                                mago_bytes::BytesDisplay(&ephemeral_file.name),
+                                parse_error
+                            );
+
                             ephemeral_file.contents.into_owned()
                         }
                     }
@@ -2,12 +2,14 @@
 //!
 //! This module provides helper functions for interacting with git repositories,
 //! specifically for the `--staged` formatting feature that allows formatting
-//! staged files in pre-commit hooks.
+//! staged files in pre-commit hooks, and the `--changed-lines` feature that formats
+//! only the lines touched since the last commit.
 
 use std::borrow::Cow;
 use std::collections::HashSet;
 use std::ffi::OsString;
 use std::io::Write as _;
+use std::ops::RangeInclusive;
 use std::path::Path;
 use std::path::PathBuf;
 use std::process::Command;
@@ -21,6 +23,15 @@ use mago_database::file::FileId;
 
 use crate::error::Error;
 
+/// The lines of a file that changed, relative to the workspace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangedLines {
+    /// The path of the file, relative to the workspace.
+    pub path: PathBuf,
+    /// The changed lines, as 1-based inclusive ranges.
+    pub lines: Vec<RangeInclusive<u32>>,
+}
+
 /// Get staged file paths relative to the workspace.
 ///
 /// This function is used by `--staged` flags in lint and analyze commands
@@ -42,6 +53,60 @@ pub fn get_staged_file_paths(workspace: &Path) -> Result<Vec<PathBuf>, Error> {
     get_staged_files(workspace)
 }
 
+/// Get the lines changed in the working tree since `HEAD`.
+///
+/// This function is used by the `--changed-lines` flag of the format command. Files
+/// that are not tracked yet are reported as changed as a whole.
+///
+/// # Arguments
+///
+/// * `workspace` - The git repository root directory
+///
+/// # Returns
+///
+/// The changed lines of each file, or an error if not in a git repository.
+pub fn get_changed_lines(workspace: &Path) -> Result<Vec<ChangedLines>, Error> {
+    if !is_git_repository(workspace) {
+        return Err(Error::NotAGitRepository);
+    }
+
+    let mut changed_lines = get_diff_lines(workspace, "HEAD")?;
+
+    let output = Command::new("git")
+        .args(["ls-files", "--others", "--exclude-standard"])
+        .current_dir(workspace)
+        .output()
+        .map_err(|e| Error::Database(DatabaseError::IOError(e)))?;
+
+    changed_lines.extend(
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter(|l| !l.is_empty())
+            .map(|l| ChangedLines { path: PathBuf::from(l), lines: vec![1..=u32::MAX] }),
+    );
+
+    Ok(changed_lines)
+}
+
+/// Get the lines changed in the index since `HEAD`.
+///
+/// This function is used when `--changed-lines` is combined with `--staged`.
+///
+/// # Arguments
+///
+/// * `workspace` - The git repository root directory
+///
+/// # Returns
+///
+/// The staged lines of each file, or an error if not in a git repository.
+pub fn get_staged_changed_lines(workspace: &Path) -> Result<Vec<ChangedLines>, Error> {
+    if !is_git_repository(workspace) {
+        return Err(Error::NotAGitRepository);
+    }
+
+    get_diff_lines(workspace, "--cached")
+}
+
 /// Creates an ephemeral file with the contents of a staged file
 ///
 /// # Arguments
@@ -124,7 +189,7 @@ pub fn ensure_staged_files_are_clean(workspace: &Path, staged_files: &[PathBuf])
 /// Stage multiple files at once by their file IDs.
 ///
 /// This function looks up file paths from the database and runs
-/// `git add -- <files...>` to stage all specified files in a single git invocation.
+/// `git add --all -- <files...>` to stage all specified files in a single git invocation.
 ///
 /// # Arguments
 ///
@@ -145,13 +210,22 @@ where
         .map(|file| PathBuf::from(std::str::from_utf8(&file.name).unwrap_or("")))
         .collect();
 
+    stage_paths(workspace, &paths)
+}
+
+/// Stage the given paths, relative to the workspace, including paths that no longer exist.
+///
+/// # Errors
+///
+/// Returns an error if the `git add` command fails.
+pub fn stage_paths(workspace: &Path, paths: &[PathBuf]) -> Result<(), Error> {
     if paths.is_empty() {
         return Ok(());
     }
 
     let mut cmd = Command::new("git");
-    cmd.args(["add", "--"]);
-    for path in &paths {
+    cmd.args(["add", "--all", "--"]);
+    for path in paths {
         cmd.arg(path);
     }
 
@@ -211,6 +285,71 @@ fn get_staged_files(workspace: &Path) -> Result<Vec<PathBuf>, Error> {
     Ok(String::from_utf8_lossy(&output.stdout).lines().filter(|l| !l.is_empty()).map(PathBuf::from).collect())
 }
 
+/// Get the lines added or modified by a diff, per file.
+///
+/// This function runs `git diff --unified=0` with the given target (`HEAD` for the
+/// working tree, `--cached` for the index) and reads the line ranges from the hunk headers.
+///
+/// # Arguments
+///
+/// * `workspace` - The git repository root directory
+/// * `target` - What to diff against
+///
+/// # Returns
+///
+/// The changed lines of each file, or an error if the git command fails.
+fn get_diff_lines(workspace: &Path, target: &str) -> Result<Vec<ChangedLines>, Error> {
+    let output = Command::new("git")
+        .args(["-c", "core.quotePath=off", "diff", "--unified=0", "--no-color", "--no-ext-diff", "--no-prefix"])
+        .args(["--diff-filter=ACMR", target])
+        .current_dir(workspace)
+        .output()
+        .map_err(|e| Error::Database(DatabaseError::IOError(e)))?;
+
+    if !output.status.success() {
+        return Err(Error::NotAGitRepository);
+    }
+
+    Ok(parse_diff_lines(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Reads the changed lines of each file from a diff produced with `--unified=0 --no-prefix`.
+///
+/// A hunk that only removes lines marks the line before the removal as changed, so that the
+/// code around it is formatted too.
+fn parse_diff_lines(diff: &str) -> Vec<ChangedLines> {
+    let mut changed_lines: Vec<ChangedLines> = Vec::new();
+    let mut in_header = false;
+
+    for line in diff.lines() {
+        if line.starts_with("diff --git ") {
+            in_header = true;
+        } else if in_header && let Some(path) = line.strip_prefix("+++ ") {
+            changed_lines.push(ChangedLines { path: PathBuf::from(path), lines: Vec::new() });
+        } else if let Some(header) = line.strip_prefix("@@ ") {
+            in_header = false;
+
+            // `@@ -start[,count] +start[,count] @@`
+            let Some(new_range) = header.split(' ').find_map(|part| part.strip_prefix('+')) else {
+                continue;
+            };
+
+            let (start, count) = match new_range.split_once(',') {
+                Some((start, count)) => (start.parse::<u32>().unwrap_or(0), count.parse::<u32>().unwrap_or(1)),
+                None => (new_range.parse::<u32>().unwrap_or(0), 1),
+            };
+
+            if let Some(file) = changed_lines.last_mut() {
+                let start = start.max(1);
+
+                file.lines.push(start..=start + count.saturating_sub(1));
+            }
+        }
+    }
+
+    changed_lines
+}
+
 /// Get set of all files with unstaged changes.
 ///
 /// This function runs `git diff --name-only` once to get all files with unstaged
@@ -284,3 +423,45 @@ fn get_mode(workspace: &Path, path: &Path) -> Result<String, Error> {
 
     Ok(String::from_utf8_lossy(&output.stdout).trim().to_owned())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_changed_lines_from_hunk_headers() {
+        let diff = "\
+diff --git src/Foo.php src/Foo.php
+index 1111111..2222222 100644
+--- src/Foo.php
++++ src/Foo.php
+@@ -3 +3 @@ final class Foo
+-    public $a;
++    public $b;
+@@ -10,2 +10,4 @@ final class Foo
++++ this added line is not a file header
++
++
++
+@@ -20,3 +21,0 @@ final class Foo
+-
+-
+-
+diff --git src/Bar.php src/Bar.php
+new file mode 100644
+--- /dev/null
++++ src/Bar.php
+@@ -0,0 +1,2 @@
++<?php
++
+";
+
+        assert_eq!(
+            parse_diff_lines(diff),
+            vec![
+                ChangedLines { path: PathBuf::from("src/Foo.php"), lines: vec![3..=3, 10..=13, 21..=21] },
+                ChangedLines { path: PathBuf::from("src/Bar.php"), lines: vec![1..=2] },
+            ]
+        );
+    }
+}
@@ -7,12 +7,16 @@ use diffy::PatchFormatter;
 use mago_database::change::ChangeLog;
 use mago_database::file::File;
 use mago_linter::integration::IntegrationSet;
+use mago_linter::registry::RuleRegistry;
 use mago_linter::settings::RulesSettings;
 use mago_linter::settings::Settings;
 use mago_orchestrator::Orchestrator;
 use mago_orchestrator::OrchestratorConfiguration;
+use mago_reporting::RuleDescriptor;
 
 use crate::config::Configuration;
+use crate::consts::MAXIMUM_PHP_VERSION;
+use crate::consts::VERSION;
 use crate::error::Error;
 
 pub mod git;
@@ -97,6 +101,8 @@ pub(crate) fn create_orchestrator<'a>(
         linter_settings,
         guard_settings: configuration.guard.settings.clone(),
         formatter_settings: configuration.formatter.settings,
+        twig_formatter_settings: configuration.twig.formatter,
+        twig_linter_settings: configuration.twig.linter.clone(),
         disable_default_analyzer_plugins: configuration.analyzer.disable_default_plugins,
         analyzer_plugins: configuration.analyzer.plugins.clone(),
         use_progress_bars,
@@ -112,6 +118,27 @@ pub(crate) fn create_orchestrator<'a>(
     Orchestrator::new(orchestrator_config)
 }
 
+/// Returns the metadata of every built-in linter rule.
+///
+/// Reporting formats that describe their rules, such as SARIF, embed this metadata
+/// alongside the issues. Each rule links to its entry in the documentation for the
+/// running version of Mago.
+pub fn linter_rule_descriptors() -> Vec<RuleDescriptor> {
+    let settings =
+        Settings { php_version: MAXIMUM_PHP_VERSION, integrations: IntegrationSet::all(), ..Settings::default() };
+
+    RuleRegistry::build(&settings, None, true)
+        .rules()
+        .iter()
+        .map(|rule| {
+            let meta = rule.meta();
+
+            RuleDescriptor::new(meta.code, meta.name, meta.description)
+                .with_help_uri(format!("https://mago.carthage.software/{VERSION}/en/tools/linter/rules/#{}", meta.code))
+        })
+        .collect()
+}
+
 /// Processes the result of a modifying a single file.
 ///
 /// This function compares the original file content with the newly modified content.
@@ -1,73 +1,271 @@
-use std::str::FromStr;
+//! Composer version constraints for the `php` platform requirement.
+//!
+//! Supports the constraint syntax commonly found in `require.php`: comparison operators
+//! (`>=`, `>`, `<=`, `<`, `=`, `!=`), the tilde (`~`) and caret (`^`) operators, wildcards
+//! (`8.*`), hyphen ranges (`8.1 - 8.3`), and `,`/space separated conjunctions combined with
+//! `||` disjunctions. Stability flags (`@dev`) and pre-release suffixes are ignored.
 
 use mago_php_version::PHPVersion;
 
+/// A version as `(major, minor, patch)`.
+type Version = (u32, u32, u32);
+
+/// A contiguous range of versions, with a set of excluded versions.
+#[derive(Debug, Default)]
+struct Range {
+    lower: Option<(Version, bool)>,
+    upper: Option<(Version, bool)>,
+    excluded: Vec<Version>,
+}
+
+/// Returns the lowest PHP version allowed by the given Composer constraint.
+///
+/// Returns `None` if the constraint cannot be parsed, or allows no version.
+pub fn minimum_php_version(constraint: &str) -> Option<PHPVersion> {
+    parse_constraint(constraint)?
+        .iter()
+        .filter_map(Range::minimum)
+        .min()
+        .map(|(major, minor, patch)| PHPVersion::new(major, minor, patch))
+}
+
+/// Returns the lowest PHP version allowed by the given Composer constraint, as a string.
 pub fn extract_minimum_php_version(version_constraint: &str) -> Option<String> {
-    // Remove any whitespace and split by logical OR operator
-    let trimmed_constraint = version_constraint.replace(" ", "");
-    let constraints = trimmed_constraint.split("||").collect::<Vec<_>>();
-
-    // For each constraint, try to extract the minimum version
-    let mut min_versions = Vec::new();
-    for constraint in constraints {
-        if let Some(version) = extract_version_from_constraint(constraint) {
-            min_versions.push(version);
+    minimum_php_version(version_constraint).map(|version| version.to_string())
+}
+
+/// Checks whether `version` satisfies the given Composer constraint.
+///
+/// Returns `None` if the constraint cannot be parsed.
+pub fn php_version_satisfies(version: PHPVersion, constraint: &str) -> Option<bool> {
+    let version = (version.major(), version.minor(), version.patch());
+
+    Some(parse_constraint(constraint)?.iter().any(|range| range.contains(version)))
+}
+
+impl Range {
+    fn contains(&self, version: Version) -> bool {
+        let above_lower = match self.lower {
+            Some((lower, true)) => version >= lower,
+            Some((lower, false)) => version > lower,
+            None => true,
+        };
+
+        let below_upper = match self.upper {
+            Some((upper, true)) => version <= upper,
+            Some((upper, false)) => version < upper,
+            None => true,
+        };
+
+        above_lower && below_upper && !self.excluded.contains(&version)
+    }
+
+    fn minimum(&self) -> Option<Version> {
+        let mut candidate = match self.lower {
+            Some((lower, true)) => lower,
+            Some(((major, minor, patch), false)) => (major, minor, patch.checked_add(1)?),
+            None => (0, 0, 0),
+        };
+
+        while self.excluded.contains(&candidate) {
+            candidate.2 = candidate.2.checked_add(1)?;
         }
+
+        self.contains(candidate).then_some(candidate)
     }
 
-    // If we found any valid versions, return the minimum
-    if !min_versions.is_empty() {
-        min_versions.sort();
+    fn with_lower(mut self, version: Version, inclusive: bool) -> Self {
+        let replace = match self.lower {
+            Some((current, current_inclusive)) => version > current || (version == current && current_inclusive),
+            None => true,
+        };
+
+        if replace {
+            self.lower = Some((version, inclusive));
+        }
 
-        return Some(min_versions[0].to_string());
+        self
     }
 
-    None
+    fn with_upper(mut self, version: Version, inclusive: bool) -> Self {
+        let replace = match self.upper {
+            Some((current, current_inclusive)) => version < current || (version == current && current_inclusive),
+            None => true,
+        };
+
+        if replace {
+            self.upper = Some((version, inclusive));
+        }
+
+        self
+    }
+}
+
+/// Parses a constraint into the ranges of its `||` alternatives.
+fn parse_constraint(constraint: &str) -> Option<Vec<Range>> {
+    constraint.split('|').map(str::trim).filter(|alternative| !alternative.is_empty()).map(parse_conjunction).collect()
 }
 
-fn extract_version_from_constraint(constraint: &str) -> Option<PHPVersion> {
-    // Handle common version constraint patterns
+fn parse_conjunction(conjunction: &str) -> Option<Range> {
+    if let Some((from, to)) = conjunction.split_once(" - ") {
+        let (from, _) = parse_version(from.trim())?;
+        let (to, to_components) = parse_version(to.trim())?;
 
-    // Case: >=8.0
-    if constraint.starts_with(">=") {
-        let version_str = constraint.trim_start_matches(">=");
+        let range = Range::default().with_lower(pad(&from), true);
 
-        return PHPVersion::from_str(version_str).ok();
+        return Some(if to_components < 3 {
+            range.with_upper(next(&to, to_components)?, false)
+        } else {
+            range.with_upper(pad(&to), true)
+        });
     }
 
-    // Case: >8.0 (we add 0.0.1 to get the minimum allowed version)
-    if constraint.starts_with(">") {
-        let version_str = constraint.trim_start_matches(">");
-        if let Ok(version) = PHPVersion::from_str(version_str) {
-            // Increment the patch version to get the minimum allowed
-            return Some(PHPVersion::new(
-                version.major(),
-                version.minor(),
-                version.patch().checked_add(1).unwrap_or(0),
-            ));
+    let mut tokens = vec![];
+    let mut pending_operator: Option<&str> = None;
+    for token in conjunction.split([' ', ',']).filter(|token| !token.is_empty()) {
+        if token.chars().all(|character| matches!(character, '<' | '>' | '=' | '!' | '~' | '^')) {
+            pending_operator = Some(token);
+
+            continue;
+        }
+
+        match pending_operator.take() {
+            Some(operator) => tokens.push(format!("{operator}{token}")),
+            None => tokens.push(token.to_string()),
         }
     }
 
-    // Case: ~8.1 (compatible with 8.1.x)
-    if constraint.starts_with("~") {
-        let version_str = constraint.trim_start_matches("~");
+    if tokens.is_empty() {
+        return None;
+    }
+
+    tokens.iter().try_fold(Range::default(), |range, token| apply_token(range, token))
+}
+
+fn apply_token(range: Range, token: &str) -> Option<Range> {
+    let operators = [">=", "<=", "!=", "==", "<>", ">", "<", "=", "~", "^"];
+    let (operator, version) = operators
+        .iter()
+        .find_map(|operator| token.strip_prefix(operator).map(|version| (*operator, version)))
+        .unwrap_or(("", token));
+
+    let (components, count) = parse_version(version)?;
+    let padded = pad(&components);
+
+    Some(match operator {
+        ">=" => range.with_lower(padded, true),
+        ">" => range.with_lower(padded, false),
+        "<=" => range.with_upper(padded, true),
+        "<" => range.with_upper(padded, false),
+        "!=" | "<>" => {
+            let mut range = range;
+            range.excluded.push(padded);
+            range
+        }
+        "~" => {
+            let upper = if count == 3 { next(&components, 2)? } else { next(&components, 1)? };
 
-        return PHPVersion::from_str(version_str).ok();
+            range.with_lower(padded, true).with_upper(upper, false)
+        }
+        "^" => {
+            let significant = match components.iter().position(|component| *component != 0) {
+                Some(position) if position < count => position + 1,
+                _ => count,
+            };
+
+            range.with_lower(padded, true).with_upper(next(&components, significant.max(1))?, false)
+        }
+        _ if count == 0 => range,
+        _ if count < 3 => range.with_lower(padded, true).with_upper(next(&components, count)?, false),
+        _ => range.with_lower(padded, true).with_upper(padded, true),
+    })
+}
+
+/// Parses a version into its leading numeric components, and how many of them were given.
+///
+/// Wildcard components (`*`, `x`) end the version, so `8.1.*` has two components.
+fn parse_version(version: &str) -> Option<([u32; 3], usize)> {
+    let version = version.split('@').next().unwrap_or(version);
+    let version = version.split('-').next().unwrap_or(version);
+    let version = version.trim_start_matches(['v', 'V']);
+    if version.is_empty() {
+        return None;
     }
 
-    // Case: ^8.1 (compatible with 8.x.y where x >= 1)
-    if constraint.starts_with("^") {
-        let version_str = constraint.trim_start_matches("^");
+    let mut components = [0; 3];
+    let mut count = 0;
+    for part in version.split('.') {
+        if matches!(part, "*" | "x" | "X") {
+            break;
+        }
+
+        if count == 3 {
+            continue;
+        }
 
-        return PHPVersion::from_str(version_str).ok();
+        components[count] = part.parse().ok()?;
+        count += 1;
     }
 
-    // Case: 8.1.* or 8.1
-    if !constraint.starts_with("<") && !constraint.contains(">") {
-        let version_str = constraint.replace(".*", "").replace("*", "");
+    Some((components, count))
+}
+
+fn pad(components: &[u32; 3]) -> Version {
+    (components[0], components[1], components[2])
+}
+
+/// Returns the first version after all versions starting with the first `count` components.
+fn next(components: &[u32; 3], count: usize) -> Option<Version> {
+    Some(match count {
+        0 => return None,
+        1 => (components[0].checked_add(1)?, 0, 0),
+        2 => (components[0], components[1].checked_add(1)?, 0),
+        _ => (components[0], components[1], components[2].checked_add(1)?),
+    })
+}
 
-        return PHPVersion::from_str(&version_str).ok();
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_minimum_php_version() {
+        let cases = [
+            ("^8.1", Some("8.1.0")),
+            ("~8.1 || ~8.2", Some("8.1.0")),
+            (">=8.2", Some("8.2.0")),
+            (">8.2", Some("8.2.1")),
+            ("8.3.*", Some("8.3.0")),
+            (">= 8.1, <8.4", Some("8.1.0")),
+            ("8.2 - 8.4", Some("8.2.0")),
+            ("^8.4 | ^8.2", Some("8.2.0")),
+            ("<8.0 >8.1", None),
+            ("invalid", None),
+        ];
+
+        for (constraint, expected) in cases {
+            assert_eq!(extract_minimum_php_version(constraint).as_deref(), expected, "{constraint}");
+        }
     }
 
-    None
+    #[test]
+    fn test_php_version_satisfies() {
+        let cases = [
+            ("^8.1", PHPVersion::PHP84, Some(true)),
+            ("^8.1", PHPVersion::PHP80, Some(false)),
+            ("~8.1.0", PHPVersion::PHP82, Some(false)),
+            ("~8.1", PHPVersion::PHP84, Some(true)),
+            (">=8.1 <8.3", PHPVersion::PHP83, Some(false)),
+            ("8.2.*", PHPVersion::PHP82, Some(true)),
+            ("8.0 - 8.2", PHPVersion::PHP82, Some(true)),
+            ("~8.1.0 || ~8.3.0", PHPVersion::PHP82, Some(false)),
+            ("~8.1 || ~8.3", PHPVersion::PHP82, Some(true)),
+            ("*", PHPVersion::PHP80, Some(true)),
+            ("foo", PHPVersion::PHP80, None),
+        ];
+
+        for (constraint, version, expected) in cases {
+            assert_eq!(php_version_satisfies(version, constraint), expected, "{constraint} with {version}");
+        }
+    }
 }
@@ -32,6 +32,8 @@ use mago_php_version::PHPVersion;
 use mago_reporting::IssueCollection;
 use mago_syntax::settings::ParserSettings;
 use mago_text_edit::Safety;
+use mago_twig_formatter::settings::TwigFormatSettings;
+use mago_twig_linter::settings::TwigLinterSettings;
 use serde::Deserialize;
 
 mod common;
@@ -396,6 +398,8 @@ fn orchestrator_loads_initialization_stubs_into_the_source_database() {
         linter_settings: LinterSettings::default(),
         guard_settings: GuardSettings::default(),
         formatter_settings: FormatSettings::default(),
+        twig_formatter_settings: TwigFormatSettings::default(),
+        twig_linter_settings: TwigLinterSettings::default(),
         disable_default_analyzer_plugins: false,
         analyzer_plugins: vec![],
         use_progress_bars: false,